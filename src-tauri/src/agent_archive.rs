@@ -0,0 +1,291 @@
+//! Export an agent directory to a portable .tar.gz and import one under a new name, for sharing
+//! setups between machines. Archives hold a manifest (openclaw-agent.json) plus the agent files
+//! under files/. Uses the system `tar` (bsdtar on macOS/Windows 10+, GNU tar on Linux). Archives
+//! are untrusted: an import lists the archive first and refuses links, special files, absolute
+//! paths and `..` entries, then extracts into its own staging directory and checks the result
+//! again before anything is moved into ~/.openclaw. Listing checks are pure for unit tests.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+
+use crate::agent_lint::{self, AgentIssue};
+use crate::agents::{self, AgentModelsView, CloneAgentOptions};
+use crate::{openclaw_config, operations, policy};
+
+const MANIFEST: &str = "openclaw-agent.json";
+const FILES_DIR: &str = "files";
+const FORMAT: &str = "openclaw-agent";
+const FORMAT_VERSION: u64 = 1;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AgentArchiveManifest {
+    pub format: String,
+    pub version: u64,
+    /// Name of the exported agent; the default name on import.
+    pub name: String,
+    /// Unix seconds.
+    pub exported_at: u64,
+    pub api_keys_stripped: bool,
+    pub state_stripped: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ImportedAgent {
+    pub agent: AgentModelsView,
+    /// Lint results for the imported models.json (e.g. providers missing from this machine's openclaw.json).
+    pub issues: Vec<AgentIssue>,
+}
+
+/// A new scratch directory under ~/.openclaw/host-config, never shared with another export or
+/// import running at the same time.
+fn staging_dir(kind: &str, name: &str) -> Result<PathBuf, String> {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+    let parent = openclaw_config::host_config_dir().join(kind);
+    fs::create_dir_all(&parent).map_err(|e| e.to_string())?;
+    loop {
+        let dir = parent.join(format!(
+            "{}-{}-{}-{}",
+            name,
+            operations::now_secs(),
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        match fs::create_dir(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+}
+
+/// GNU tar reads `host:path` (and so `C:\...`) archive names as remote unless told otherwise;
+/// bsdtar has no such option and doesn't need it.
+fn is_gnu_tar() -> bool {
+    static GNU: OnceLock<bool> = OnceLock::new();
+    *GNU.get_or_init(|| {
+        Command::new("tar")
+            .arg("--version")
+            .output()
+            .is_ok_and(|o| String::from_utf8_lossy(&o.stdout).contains("GNU tar"))
+    })
+}
+
+fn run_tar(args: &[&str]) -> Result<String, String> {
+    let mut cmd = Command::new("tar");
+    if is_gnu_tar() {
+        cmd.arg("--force-local");
+    }
+    let out = cmd
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run tar: {}", e))?;
+    if out.status.success() {
+        Ok(String::from_utf8_lossy(&out.stdout).to_string())
+    } else {
+        Err(String::from_utf8_lossy(&out.stderr).trim().to_string())
+    }
+}
+
+/// Checks an archive's listing: `names` from `tar -tzf`, `verbose` from `tar -tvzf` (same
+/// order). Only regular files and directories with relative paths inside the archive pass.
+pub fn check_archive_listing(names: &str, verbose: &str) -> Result<(), String> {
+    let names: Vec<&str> = names.lines().collect();
+    let verbose: Vec<&str> = verbose.lines().collect();
+    if names.len() != verbose.len() {
+        return Err("Archive listing is inconsistent; refusing to import it".to_string());
+    }
+    for (name, line) in names.iter().zip(&verbose) {
+        let unsafe_path = name.starts_with('/')
+            || name.starts_with('\\')
+            || name.get(1..2) == Some(":")
+            || name.split(['/', '\\']).any(|part| part == "..");
+        if unsafe_path {
+            return Err(format!(
+                "Archive entry escapes the agent directory: {}",
+                name
+            ));
+        }
+        // Links show as 'l'/'h' (GNU) or with "link to"/"->" after the name (bsdtar).
+        let regular = matches!(line.chars().next(), Some('-' | 'd'));
+        if !regular || line.contains(" link to ") || line.contains(" -> ") {
+            return Err(format!(
+                "Archive entry is not a regular file or directory: {}",
+                name
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Fails if anything under `dir` is a symlink or other non-regular file.
+fn check_extracted(dir: &Path) -> Result<(), String> {
+    for entry in fs::read_dir(dir).map_err(|e| e.to_string())?.flatten() {
+        let path = entry.path();
+        let meta = fs::symlink_metadata(&path).map_err(|e| e.to_string())?;
+        if meta.is_dir() {
+            check_extracted(&path)?;
+        } else if !meta.is_file() {
+            return Err(format!(
+                "Archive entry is not a regular file or directory: {}",
+                path.display()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Checks a manifest read from an archive.
+pub fn parse_manifest(content: &str) -> Result<AgentArchiveManifest, String> {
+    let manifest: AgentArchiveManifest =
+        serde_json::from_str(content).map_err(|e| format!("Invalid {}: {}", MANIFEST, e))?;
+    if manifest.format != FORMAT {
+        return Err(format!(
+            "Not an openclaw agent archive ({})",
+            manifest.format
+        ));
+    }
+    if manifest.version > FORMAT_VERSION {
+        return Err(format!(
+            "Archive version {} is newer than supported ({})",
+            manifest.version, FORMAT_VERSION
+        ));
+    }
+    Ok(manifest)
+}
+
+/// Writes ~/.openclaw/agents/<name> to a .tar.gz at `path`. `options` are the clone options:
+/// strip API keys and/or session and memory state.
+pub fn export_agent(name: &str, path: &str, options: &CloneAgentOptions) -> Result<String, String> {
+    agents::validate_agent_name(name)?;
+    let src = agents::agents_dir().join(name);
+    if !src.is_dir() {
+        return Err(format!("Agent not found: {}", name));
+    }
+    let stage = staging_dir("exports", name)?;
+    let result = (|| {
+        agents::copy_agent_dir(&src, &stage.join(FILES_DIR), options)?;
+        let manifest = AgentArchiveManifest {
+            format: FORMAT.to_string(),
+            version: FORMAT_VERSION,
+            name: name.to_string(),
+            exported_at: operations::now_secs(),
+            api_keys_stripped: options.strip_api_keys,
+            state_stripped: options.strip_state,
+        };
+        fs::write(
+            stage.join(MANIFEST),
+            serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?,
+        )
+        .map_err(|e| e.to_string())?;
+        let stage_str = stage.to_string_lossy().to_string();
+        run_tar(&["-czf", path, "-C", &stage_str, MANIFEST, FILES_DIR]).map(|_| ())
+    })();
+    let _ = fs::remove_dir_all(&stage);
+    result.map(|()| path.to_string())
+}
+
+fn move_dir(from: &Path, to: &Path) -> Result<(), String> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    // Staging may sit on another filesystem than ~/.openclaw/agents; fall back to copying.
+    agents::copy_agent_dir(from, to, &CloneAgentOptions::default())
+}
+
+/// Extracts an archive made by `export_agent` as `new_name` (or the exported name). Fails if the
+/// manifest is invalid, models.json is missing or unreadable, or an agent with that name exists.
+pub fn import_agent(path: &str, new_name: Option<&str>) -> Result<ImportedAgent, String> {
+    if !Path::new(path).is_file() {
+        return Err(format!("Archive not found: {}", path));
+    }
+    let stage = staging_dir("imports", "agent")?;
+    let result = (|| {
+        let stage_str = stage.to_string_lossy().to_string();
+        check_archive_listing(&run_tar(&["-tzf", path])?, &run_tar(&["-tvzf", path])?)?;
+        run_tar(&["-xzf", path, "-C", &stage_str])?;
+        check_extracted(&stage)?;
+        let manifest = fs::read_to_string(stage.join(MANIFEST))
+            .map_err(|_| format!("Archive has no {}", MANIFEST))
+            .and_then(|c| parse_manifest(&c))?;
+        let name = new_name.unwrap_or(&manifest.name).to_string();
+        agents::validate_agent_name(&name)?;
+        let dst = agents::agents_dir().join(&name);
+        if dst.exists() {
+            return Err(format!(
+                "Agent already exists: {}. Import it under another name.",
+                name
+            ));
+        }
+        let files = stage.join(FILES_DIR);
+        let models = files.join("agent").join("models.json");
+        let content = fs::read_to_string(&models)
+            .map_err(|_| "Archive has no agent/models.json".to_string())?;
+        let root = serde_json::from_str::<serde_json::Value>(&content)
+            .map_err(|e| format!("Archived models.json is invalid: {}", e))?;
+        policy::enforce_models_json(&name, &root)?;
+        fs::create_dir_all(agents::agents_dir()).map_err(|e| e.to_string())?;
+        move_dir(&files, &dst)?;
+        Ok(name)
+    })();
+    let _ = fs::remove_dir_all(&stage);
+    let name = result?;
+    let agent = agents::get_agent_models(&name)
+        .ok_or_else(|| format!("Failed to read imported agent {}", name))?;
+    Ok(ImportedAgent {
+        agent,
+        issues: agent_lint::validate_agent(&name).unwrap_or_default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let ok = r#"{"format":"openclaw-agent","version":1,"name":"dev","exported_at":0,
+                     "api_keys_stripped":true,"state_stripped":false}"#;
+        assert_eq!(parse_manifest(ok).unwrap().name, "dev");
+        assert!(parse_manifest(&ok.replace("openclaw-agent", "other")).is_err());
+        assert!(parse_manifest(&ok.replace("\"version\":1", "\"version\":9")).is_err());
+        assert!(parse_manifest("{}").is_err());
+    }
+
+    #[test]
+    fn test_export_agent_rejects_paths() {
+        let options = CloneAgentOptions::default();
+        let err = export_agent("../../.ssh", "/tmp/x.tar.gz", &options).unwrap_err();
+        assert!(err.starts_with("Agent name"));
+    }
+
+    #[test]
+    fn test_check_archive_listing() {
+        let names = "openclaw-agent.json\nfiles/\nfiles/agent/models.json\n";
+        let verbose = concat!(
+            "-rw-r--r-- u/u 120 2026-03-01 12:00 openclaw-agent.json\n",
+            "drwxr-xr-x u/u   0 2026-03-01 12:00 files/\n",
+            "-rw-r--r-- u/u 900 2026-03-01 12:00 files/agent/models.json\n",
+        );
+        assert!(check_archive_listing(names, verbose).is_ok());
+
+        let link = verbose.replace(
+            "-rw-r--r-- u/u 900 2026-03-01 12:00 files/agent/models.json",
+            "lrwxrwxrwx u/u 0 2026-03-01 12:00 files/agent/models.json -> /etc/passwd",
+        );
+        assert!(check_archive_listing(names, &link).is_err());
+        let hardlink = verbose.replace(
+            "files/agent/models.json\n",
+            "files/agent/models.json link to openclaw-agent.json\n",
+        );
+        assert!(check_archive_listing(names, &hardlink).is_err());
+        for bad in ["../x", "files/../../x", "/etc/passwd", "C:/x"] {
+            let names = names.replace("files/agent/models.json", bad);
+            assert!(check_archive_listing(&names, verbose).is_err(), "{}", bad);
+        }
+        assert!(check_archive_listing("a\nb\n", "-rw-r--r-- a\n").is_err());
+    }
+}
@@ -0,0 +1,197 @@
+//! Read-only view of an agent beyond its model config: workspace docs, session and memory store
+//! sizes, and when it was last active. Workspace resolution is a pure function for unit tests.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::agents;
+use crate::disk_usage;
+use crate::openclaw_config;
+use crate::system;
+
+/// A file in the agent's workspace (AGENTS.md, SOUL.md, ...).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AgentFileInfo {
+    pub name: String,
+    pub size_bytes: u64,
+    /// Unix seconds.
+    pub modified: Option<u64>,
+}
+
+/// Combined size of one or more files/directories.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct StoreUsage {
+    /// Locations that exist.
+    pub paths: Vec<String>,
+    pub files: u64,
+    pub bytes: u64,
+    pub bytes_human: String,
+    /// Newest file modification, Unix seconds.
+    pub last_modified: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AgentDetails {
+    pub name: String,
+    pub path: String,
+    pub total_bytes: u64,
+    pub total_human: String,
+    pub workspace_path: String,
+    pub workspace_exists: bool,
+    /// Top-level files in the workspace, sorted by name.
+    pub workspace_files: Vec<AgentFileInfo>,
+    pub sessions: StoreUsage,
+    pub memory: StoreUsage,
+    /// Newest session write, Unix seconds.
+    pub last_active: Option<u64>,
+}
+
+/// Workspace directory: the agents.list entry's `workspace`, agents.defaults.workspace for the
+/// default agent, else ~/.openclaw/workspace (default agent) or ~/.openclaw/workspace-<name>.
+#[must_use]
+pub fn workspace_path(root: &serde_json::Value, agent_name: &str, openclaw_root: &Path) -> PathBuf {
+    let agents_cfg = root.get("agents");
+    let entry_ws = agents_cfg
+        .and_then(|a| a.get("list"))
+        .and_then(|l| l.as_array())
+        .and_then(|l| {
+            l.iter()
+                .find(|e| e.get("id").and_then(|v| v.as_str()) == Some(agent_name))
+        })
+        .and_then(|e| e.get("workspace"))
+        .and_then(|v| v.as_str());
+    let is_default = agents::default_agent_name(root) == agent_name;
+    let defaults_ws = agents_cfg
+        .and_then(|a| a.get("defaults"))
+        .and_then(|d| d.get("workspace"))
+        .and_then(|v| v.as_str())
+        .filter(|_| is_default);
+    match entry_ws.or(defaults_ws) {
+        Some(ws) => disk_usage::expand_home(ws),
+        None if is_default => openclaw_root.join("workspace"),
+        None => openclaw_root.join(format!("workspace-{}", agent_name)),
+    }
+}
+
+fn modified_secs(meta: &fs::Metadata) -> Option<u64> {
+    meta.modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+}
+
+/// Adds a file or directory tree (symlinks not followed) to `usage`.
+fn add_usage(path: &Path, usage: &mut StoreUsage) {
+    let meta = match fs::symlink_metadata(path) {
+        Ok(m) => m,
+        Err(_) => return,
+    };
+    if meta.is_file() {
+        usage.files += 1;
+        usage.bytes += meta.len();
+        usage.last_modified = usage.last_modified.max(modified_secs(&meta));
+    } else if meta.is_dir() {
+        for entry in fs::read_dir(path).into_iter().flatten().flatten() {
+            add_usage(&entry.path(), usage);
+        }
+    }
+}
+
+fn store_usage(paths: &[PathBuf]) -> StoreUsage {
+    let mut usage = StoreUsage::default();
+    for path in paths.iter().filter(|p| p.exists()) {
+        usage.paths.push(path.to_string_lossy().to_string());
+        add_usage(path, &mut usage);
+    }
+    usage.bytes_human = system::bytes_to_human(usage.bytes);
+    usage
+}
+
+fn workspace_files(dir: &Path) -> Vec<AgentFileInfo> {
+    let mut files: Vec<AgentFileInfo> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|e| {
+            let meta = e.metadata().ok().filter(|m| m.is_file())?;
+            Some(AgentFileInfo {
+                name: e.file_name().to_string_lossy().to_string(),
+                size_bytes: meta.len(),
+                modified: modified_secs(&meta),
+            })
+        })
+        .collect();
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+    files
+}
+
+/// Sizes and activity of ~/.openclaw/agents/<name>, its sessions, memory and workspace.
+pub fn get_agent_details(name: &str) -> Result<AgentDetails, String> {
+    let dir = agents::agents_dir().join(name);
+    if !dir.is_dir() {
+        return Err(format!("Agent not found: {}", name));
+    }
+    let root = openclaw_config::read_config_root().unwrap_or(serde_json::json!({}));
+    let openclaw_root = agents::openclaw_root();
+    let workspace = workspace_path(&root, name, &openclaw_root);
+    let sessions = store_usage(&[dir.join("sessions")]);
+    let memory = store_usage(&[
+        dir.join("memory"),
+        workspace.join("memory"),
+        workspace.join("MEMORY.md"),
+        openclaw_root
+            .join("memory")
+            .join(format!("{}.sqlite", name)),
+    ]);
+    let total_bytes = disk_usage::path_size(&dir);
+    Ok(AgentDetails {
+        name: name.to_string(),
+        path: dir.to_string_lossy().to_string(),
+        total_bytes,
+        total_human: system::bytes_to_human(total_bytes),
+        workspace_path: workspace.to_string_lossy().to_string(),
+        workspace_exists: workspace.is_dir(),
+        workspace_files: workspace_files(&workspace),
+        last_active: sessions.last_modified,
+        sessions,
+        memory,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workspace_path() {
+        let base = Path::new("/home/u/.openclaw");
+        let empty = serde_json::json!({});
+        assert_eq!(workspace_path(&empty, "main", base), base.join("workspace"));
+        assert_eq!(
+            workspace_path(&empty, "dev", base),
+            base.join("workspace-dev")
+        );
+        let root = serde_json::json!({ "agents": {
+            "defaults": { "workspace": "/srv/main-ws" },
+            "list": [{ "id": "main" }, { "id": "dev", "workspace": "/srv/dev-ws" }]
+        } });
+        assert_eq!(
+            workspace_path(&root, "main", base),
+            PathBuf::from("/srv/main-ws")
+        );
+        assert_eq!(
+            workspace_path(&root, "dev", base),
+            PathBuf::from("/srv/dev-ws")
+        );
+    }
+
+    #[test]
+    fn test_store_usage_missing_paths() {
+        let usage = store_usage(&[PathBuf::from("/nonexistent/agent/sessions")]);
+        assert!(usage.paths.is_empty());
+        assert_eq!(usage.bytes, 0);
+        assert!(usage.last_modified.is_none());
+    }
+}
@@ -0,0 +1,250 @@
+//! Side-by-side comparison of two agents: providers and models from their models.json, and
+//! settings from openclaw.json (model selection, subagent limits, workspace, other agents.list
+//! keys). API keys are compared as set/unset only. The comparison is a pure function for unit tests.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use crate::agent_details;
+use crate::agents;
+use crate::openclaw_config;
+
+/// Provider fields compared besides the model list.
+const PROVIDER_FIELDS: &[&str] = &["baseUrl", "api"];
+/// agents.list keys covered by the dedicated settings comparisons.
+const COMPARED_ENTRY_KEYS: &[&str] = &["id", "default", "model", "subagents", "workspace"];
+
+/// A value that differs between the agents, e.g. path "ollama.baseUrl" or "model.primary".
+/// Null when unset on that side.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ValueDiff {
+    pub path: String,
+    pub a: Value,
+    pub b: Value,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct AgentDiff {
+    pub agent_a: String,
+    pub agent_b: String,
+    pub providers_only_in_a: Vec<String>,
+    pub providers_only_in_b: Vec<String>,
+    /// Field differences for providers both agents have.
+    pub provider_differences: Vec<ValueDiff>,
+    /// "provider/model" ids, for providers both agents have.
+    pub models_only_in_a: Vec<String>,
+    pub models_only_in_b: Vec<String>,
+    pub setting_differences: Vec<ValueDiff>,
+    pub identical: bool,
+}
+
+fn is_identical(diff: &AgentDiff) -> bool {
+    diff.providers_only_in_a.is_empty()
+        && diff.providers_only_in_b.is_empty()
+        && diff.provider_differences.is_empty()
+        && diff.models_only_in_a.is_empty()
+        && diff.models_only_in_b.is_empty()
+        && diff.setting_differences.is_empty()
+}
+
+fn push_diff(out: &mut Vec<ValueDiff>, path: String, a: Value, b: Value) {
+    if a != b {
+        out.push(ValueDiff { path, a, b });
+    }
+}
+
+fn model_ids(provider: &Value) -> BTreeSet<String> {
+    provider
+        .get("models")
+        .and_then(|m| m.as_array())
+        .map(|a| {
+            a.iter()
+                .filter_map(|m| m.get("id").and_then(|v| v.as_str()).map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Compares two models.json roots and their settings in the openclaw.json `config` root.
+#[must_use]
+pub fn compare_agents(
+    (name_a, models_a): (&str, &Value),
+    (name_b, models_b): (&str, &Value),
+    config: &Value,
+    openclaw_root: &Path,
+) -> AgentDiff {
+    let empty = serde_json::Map::new();
+    let providers = |root: &Value| root.get("providers").and_then(|p| p.as_object()).cloned();
+    let (pa, pb) = (
+        providers(models_a).unwrap_or_else(|| empty.clone()),
+        providers(models_b).unwrap_or_else(|| empty.clone()),
+    );
+    let mut diff = AgentDiff {
+        agent_a: name_a.to_string(),
+        agent_b: name_b.to_string(),
+        providers_only_in_a: pa
+            .keys()
+            .filter(|k| !pb.contains_key(*k))
+            .cloned()
+            .collect(),
+        providers_only_in_b: pb
+            .keys()
+            .filter(|k| !pa.contains_key(*k))
+            .cloned()
+            .collect(),
+        ..Default::default()
+    };
+    for (name, a) in &pa {
+        let Some(b) = pb.get(name) else {
+            continue;
+        };
+        for field in PROVIDER_FIELDS {
+            push_diff(
+                &mut diff.provider_differences,
+                format!("{}.{}", name, field),
+                a.get(*field).cloned().unwrap_or(Value::Null),
+                b.get(*field).cloned().unwrap_or(Value::Null),
+            );
+        }
+        let key_set = |p: &Value| p.get("apiKey").is_some_and(|k| k.as_str() != Some(""));
+        push_diff(
+            &mut diff.provider_differences,
+            format!("{}.apiKey set", name),
+            Value::Bool(key_set(a)),
+            Value::Bool(key_set(b)),
+        );
+        let (ma, mb) = (model_ids(a), model_ids(b));
+        diff.models_only_in_a
+            .extend(ma.difference(&mb).map(|id| format!("{}/{}", name, id)));
+        diff.models_only_in_b
+            .extend(mb.difference(&ma).map(|id| format!("{}/{}", name, id)));
+    }
+
+    let settings = &mut diff.setting_differences;
+    let (ca, cb) = (
+        agents::agent_model_config(config, name_a),
+        agents::agent_model_config(config, name_b),
+    );
+    push_diff(
+        settings,
+        "model.primary".to_string(),
+        serde_json::json!(ca.effective_primary),
+        serde_json::json!(cb.effective_primary),
+    );
+    push_diff(
+        settings,
+        "model.fallbacks".to_string(),
+        serde_json::json!(ca.effective_fallbacks),
+        serde_json::json!(cb.effective_fallbacks),
+    );
+    let (sa, sb) = (
+        agents::agent_subagent_limits(config, name_a).effective,
+        agents::agent_subagent_limits(config, name_b).effective,
+    );
+    for (field, a, b) in [
+        ("maxConcurrent", sa.max_concurrent, sb.max_concurrent),
+        ("maxSpawnDepth", sa.max_spawn_depth, sb.max_spawn_depth),
+        (
+            "maxChildrenPerAgent",
+            sa.max_children_per_agent,
+            sb.max_children_per_agent,
+        ),
+    ] {
+        push_diff(
+            settings,
+            format!("subagents.{}", field),
+            serde_json::json!(a),
+            serde_json::json!(b),
+        );
+    }
+    push_diff(
+        settings,
+        "workspace".to_string(),
+        serde_json::json!(agent_details::workspace_path(config, name_a, openclaw_root)),
+        serde_json::json!(agent_details::workspace_path(config, name_b, openclaw_root)),
+    );
+    let entry = |name: &str| {
+        agents::agent_list_entry(config, name)
+            .and_then(|e| e.as_object())
+            .cloned()
+            .unwrap_or_default()
+    };
+    let (ea, eb) = (entry(name_a), entry(name_b));
+    let keys: BTreeSet<&String> = ea
+        .keys()
+        .chain(eb.keys())
+        .filter(|k| !COMPARED_ENTRY_KEYS.contains(&k.as_str()))
+        .collect();
+    for key in keys {
+        push_diff(
+            settings,
+            key.clone(),
+            ea.get(key).cloned().unwrap_or(Value::Null),
+            eb.get(key).cloned().unwrap_or(Value::Null),
+        );
+    }
+    diff.identical = is_identical(&diff);
+    diff
+}
+
+/// Compares two agents on disk.
+pub fn diff_agents(agent_a: &str, agent_b: &str) -> Result<AgentDiff, String> {
+    let models_a = agents::read_models_json(agent_a)?;
+    let models_b = agents::read_models_json(agent_b)?;
+    let config = openclaw_config::read_config_root()?;
+    Ok(compare_agents(
+        (agent_a, &models_a),
+        (agent_b, &models_b),
+        &config,
+        &agents::openclaw_root(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_agents() {
+        let main = serde_json::json!({ "providers": {
+            "ollama": { "baseUrl": "http://127.0.0.1:11434/v1",
+                        "models": [{ "id": "qwen3:8b" }, { "id": "llama3.2:3b" }] },
+            "anthropic": { "apiKey": "sk-main" }
+        } });
+        let dev = serde_json::json!({ "providers": {
+            "ollama": { "baseUrl": "http://10.0.0.5:11434/v1", "models": [{ "id": "qwen3:8b" }] },
+            "vllm": {}
+        } });
+        let config = serde_json::json!({ "agents": {
+            "defaults": { "model": { "primary": "ollama/qwen3:8b" } },
+            "list": [
+                { "id": "main" },
+                { "id": "dev", "model": "anthropic/claude-sonnet-4-5", "tools": { "profile": "coding" } }
+            ]
+        } });
+        let diff = compare_agents(
+            ("main", &main),
+            ("dev", &dev),
+            &config,
+            Path::new("/h/.openclaw"),
+        );
+        assert_eq!(diff.providers_only_in_a, vec!["anthropic"]);
+        assert_eq!(diff.providers_only_in_b, vec!["vllm"]);
+        assert_eq!(diff.provider_differences.len(), 1);
+        assert_eq!(diff.provider_differences[0].path, "ollama.baseUrl");
+        assert_eq!(diff.models_only_in_a, vec!["ollama/llama3.2:3b"]);
+        assert!(diff.models_only_in_b.is_empty());
+        let paths: Vec<&str> = diff
+            .setting_differences
+            .iter()
+            .map(|d| d.path.as_str())
+            .collect();
+        assert_eq!(paths, vec!["model.primary", "workspace", "tools"]);
+        assert!(!diff.identical);
+
+        let same = compare_agents(("main", &main), ("main", &main), &config, Path::new("/h"));
+        assert!(same.identical);
+    }
+}
@@ -0,0 +1,266 @@
+//! An agent's environment file (~/.openclaw/agents/<name>/.env), for per-agent tool credentials.
+//! Values of secret-looking keys are masked when read. Edits keep comments, order and unrelated
+//! lines; the file is written owner-only on Unix. Parsing and editing are pure functions for unit
+//! tests.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::agents;
+use crate::diagnostics::is_secret_key;
+
+const ENV_FILE: &str = ".env";
+/// Secrets at least this long show their last 4 characters.
+const MIN_PREVIEW_LEN: usize = 12;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AgentEnvVar {
+    pub key: String,
+    /// The value, or a masked preview like "****wxyz" for secrets.
+    pub value: String,
+    pub secret: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AgentEnv {
+    pub agent_name: String,
+    pub path: String,
+    pub exists: bool,
+    /// In file order.
+    pub vars: Vec<AgentEnvVar>,
+}
+
+#[must_use]
+pub fn mask_value(value: &str) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    if chars.len() < MIN_PREVIEW_LEN {
+        return "****".to_string();
+    }
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("****{}", tail)
+}
+
+/// Rejects keys a shell couldn't export: letters, digits and `_`, not starting with a digit.
+pub fn validate_env_key(key: &str) -> Result<(), String> {
+    let valid = key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid environment variable name: {}", key))
+    }
+}
+
+/// The key of an assignment line (`KEY=value` or `export KEY=value`); None for comments, blank
+/// lines and anything else.
+fn line_key(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    if line.starts_with('#') {
+        return None;
+    }
+    let line = line.strip_prefix("export ").unwrap_or(line);
+    let (key, _) = line.split_once('=')?;
+    let key = key.trim();
+    validate_env_key(key).ok().map(|()| key)
+}
+
+fn unquote(value: &str) -> String {
+    let value = value.trim();
+    if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        let mut out = String::new();
+        let mut chars = value[1..value.len() - 1].chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                out.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => out.push('\n'),
+                Some(escaped) => out.push(escaped),
+                None => out.push('\\'),
+            }
+        }
+        return out;
+    }
+    if value.len() >= 2 && value.starts_with('\'') && value.ends_with('\'') {
+        return value[1..value.len() - 1].to_string();
+    }
+    // Unquoted values end at an inline comment.
+    value
+        .split_once(" #")
+        .map_or(value, |(v, _)| v)
+        .trim_end()
+        .to_string()
+}
+
+/// Variables in `.env` content, in order; a repeated key keeps its last value, as shells do.
+#[must_use]
+pub fn parse_env(content: &str) -> Vec<(String, String)> {
+    let mut vars: Vec<(String, String)> = Vec::new();
+    for line in content.lines() {
+        let Some(key) = line_key(line) else {
+            continue;
+        };
+        let value = unquote(line.split_once('=').map_or("", |(_, v)| v));
+        match vars.iter_mut().find(|(k, _)| k == key) {
+            Some(existing) => existing.1 = value,
+            None => vars.push((key.to_string(), value)),
+        }
+    }
+    vars
+}
+
+fn quote(value: &str) -> String {
+    if !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:@+,".contains(c))
+    {
+        return value.to_string();
+    }
+    format!(
+        "\"{}\"",
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    )
+}
+
+/// `content` with `key` set to `value` in place of its first assignment (appended when missing)
+/// and other assignments of it dropped; `None` removes the key. Other lines are kept as is.
+#[must_use]
+pub fn set_env_var(content: &str, key: &str, value: Option<&str>) -> String {
+    let assignment = value.map(|v| format!("{}={}", key, quote(v)));
+    let mut lines: Vec<String> = Vec::new();
+    let mut replaced = false;
+    for line in content.lines() {
+        if line_key(line) != Some(key) {
+            lines.push(line.to_string());
+        } else if !replaced {
+            replaced = true;
+            lines.extend(assignment.clone());
+        }
+    }
+    if !replaced {
+        lines.extend(assignment);
+    }
+    let mut out = lines.join("\n");
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out
+}
+
+fn env_path(agent_name: &str) -> PathBuf {
+    agents::agents_dir().join(agent_name).join(ENV_FILE)
+}
+
+fn env_view(agent_name: &str, path: &Path, content: Option<&str>) -> AgentEnv {
+    let vars = parse_env(content.unwrap_or_default())
+        .into_iter()
+        .map(|(key, value)| {
+            let secret = is_secret_key(&key);
+            AgentEnvVar {
+                value: if secret { mask_value(&value) } else { value },
+                key,
+                secret,
+            }
+        })
+        .collect();
+    AgentEnv {
+        agent_name: agent_name.to_string(),
+        path: path.display().to_string(),
+        exists: content.is_some(),
+        vars,
+    }
+}
+
+/// The agent's `.env` variables with secrets masked.
+pub fn get_agent_env(agent_name: &str) -> Result<AgentEnv, String> {
+    agents::validate_agent_name(agent_name)?;
+    if !agents::agents_dir().join(agent_name).is_dir() {
+        return Err(format!("Agent not found: {}", agent_name));
+    }
+    let path = env_path(agent_name);
+    let content = fs::read_to_string(&path).ok();
+    Ok(env_view(agent_name, &path, content.as_deref()))
+}
+
+/// Writes `content` to a file only the owner can read. A new file is created 0600; an existing
+/// one is emptied and narrowed to 0600 before the new content goes in.
+fn write_private(path: &Path, content: &str) -> Result<(), String> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path).map_err(|e| e.to_string())?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))
+            .map_err(|e| e.to_string())?;
+    }
+    file.write_all(content.as_bytes())
+        .map_err(|e| e.to_string())
+}
+
+/// Sets (`Some`) or removes (`None`) one variable in the agent's `.env`, creating the file.
+pub fn set_agent_env(agent_name: &str, key: &str, value: Option<&str>) -> Result<AgentEnv, String> {
+    let key = key.trim();
+    validate_env_key(key)?;
+    let current = get_agent_env(agent_name)?;
+    let path = env_path(agent_name);
+    let content = if current.exists {
+        fs::read_to_string(&path).map_err(|e| e.to_string())?
+    } else {
+        String::new()
+    };
+    let updated = set_env_var(&content, key, value);
+    write_private(&path, &updated)?;
+    Ok(env_view(agent_name, &path, Some(&updated)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_env() {
+        let content = "# tools\nexport GITHUB_TOKEN=ghp_1234567890abcd\nNAME=\"my agent\"\n\
+                       BAD KEY=1\nURL=http://x # comment\nQUOTED='a#b'\nNAME=again\n";
+        assert_eq!(
+            parse_env(content),
+            vec![
+                ("GITHUB_TOKEN".to_string(), "ghp_1234567890abcd".to_string()),
+                ("NAME".to_string(), "again".to_string()),
+                ("URL".to_string(), "http://x".to_string()),
+                ("QUOTED".to_string(), "a#b".to_string()),
+            ]
+        );
+        assert!(is_secret_key("github_token"));
+        assert!(!is_secret_key("NAME"));
+        assert_eq!(mask_value("ghp_1234567890abcd"), "****abcd");
+        assert_eq!(mask_value("short"), "****");
+    }
+
+    #[test]
+    fn test_set_env_var() {
+        let content = "# tools\nA=1\nexport B=2\nA=3\n";
+        assert_eq!(
+            set_env_var(content, "A", Some("x y")),
+            "# tools\nA=\"x y\"\nexport B=2\n"
+        );
+        assert_eq!(set_env_var(content, "B", None), "# tools\nA=1\nA=3\n");
+        assert_eq!(set_env_var("", "C", Some("v")), "C=v\n");
+        let value = "say \"hi\"\\";
+        let written = set_env_var("", "Q", Some(value));
+        assert_eq!(parse_env(&written)[0].1, value);
+        assert!(validate_env_key("9X").is_err());
+    }
+}
@@ -0,0 +1,121 @@
+//! Read and write an agent's instruction files (AGENTS.md, SOUL.md, ...) in its workspace.
+//! Every write first copies the previous version to
+//! ~/.openclaw/backups/instructions/<agent>/<file>-<unix secs>.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::agent_details;
+use crate::agents;
+use crate::openclaw_config;
+use crate::operations;
+
+/// Workspace files the gateway injects into the agent's system prompt.
+pub const INSTRUCTION_FILES: &[&str] =
+    &["AGENTS.md", "SOUL.md", "TOOLS.md", "IDENTITY.md", "USER.md"];
+const DEFAULT_FILE: &str = "AGENTS.md";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AgentInstructions {
+    pub agent_name: String,
+    pub file: String,
+    pub path: String,
+    pub exists: bool,
+    /// Empty when the file does not exist.
+    pub text: String,
+    /// Where the previous version was copied by the last write, if there was one.
+    pub backup: Option<String>,
+}
+
+/// Picks the instruction file, defaulting to AGENTS.md. Rejects anything else so a caller
+/// can't write outside the workspace.
+fn instruction_file(file: Option<&str>) -> Result<&'static str, String> {
+    match file {
+        None => Ok(DEFAULT_FILE),
+        Some(f) => INSTRUCTION_FILES
+            .iter()
+            .find(|known| **known == f)
+            .copied()
+            .ok_or_else(|| format!("Not an instruction file: {}", f)),
+    }
+}
+
+fn instructions_path(agent_name: &str, file: &str) -> Result<PathBuf, String> {
+    if !agents::agents_dir().join(agent_name).is_dir() {
+        return Err(format!("Agent not found: {}", agent_name));
+    }
+    let root = openclaw_config::read_config_root().unwrap_or(serde_json::json!({}));
+    let workspace = agent_details::workspace_path(&root, agent_name, &agents::openclaw_root());
+    Ok(workspace.join(file))
+}
+
+/// Reads an instruction file (AGENTS.md by default) from the agent's workspace.
+pub fn get_agent_instructions(
+    agent_name: &str,
+    file: Option<&str>,
+) -> Result<AgentInstructions, String> {
+    let file = instruction_file(file)?;
+    let path = instructions_path(agent_name, file)?;
+    let (exists, text) = match fs::read_to_string(&path) {
+        Ok(text) => (true, text),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => (false, String::new()),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    Ok(AgentInstructions {
+        agent_name: agent_name.to_string(),
+        file: file.to_string(),
+        path: path.to_string_lossy().to_string(),
+        exists,
+        text,
+        backup: None,
+    })
+}
+
+/// Writes an instruction file, backing up the previous version first. Creates the workspace
+/// if needed.
+pub fn set_agent_instructions(
+    agent_name: &str,
+    file: Option<&str>,
+    text: &str,
+) -> Result<AgentInstructions, String> {
+    let file = instruction_file(file)?;
+    let path = instructions_path(agent_name, file)?;
+    let backup = if path.is_file() {
+        let secs = operations::now_secs();
+        let dir = agents::openclaw_root()
+            .join("backups")
+            .join("instructions")
+            .join(agent_name);
+        fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        let dest = dir.join(format!("{}-{}", file, secs));
+        fs::copy(&path, &dest).map_err(|e| format!("Failed to back up {}: {}", file, e))?;
+        Some(dest.to_string_lossy().to_string())
+    } else {
+        None
+    };
+    let dir = path.parent().ok_or("invalid path")?;
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    fs::write(&path, text).map_err(|e| e.to_string())?;
+    Ok(AgentInstructions {
+        agent_name: agent_name.to_string(),
+        file: file.to_string(),
+        path: path.to_string_lossy().to_string(),
+        exists: true,
+        text: text.to_string(),
+        backup,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instruction_file() {
+        assert_eq!(instruction_file(None).unwrap(), "AGENTS.md");
+        assert_eq!(instruction_file(Some("SOUL.md")).unwrap(), "SOUL.md");
+        assert!(instruction_file(Some("../openclaw.json")).is_err());
+        assert!(instruction_file(Some("MEMORY.md")).is_err());
+    }
+}
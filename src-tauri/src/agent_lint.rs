@@ -0,0 +1,210 @@
+//! Linter for an agent's models.json: providers the gateway doesn't know, local providers without
+//! a baseUrl, cloud providers without an apiKey, and malformed or duplicate model entries.
+//! Checks are pure functions over the parsed JSON for unit tests.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+
+use crate::agents;
+use crate::openclaw_config;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// The gateway will fail to use this provider or model.
+    Error,
+    /// Likely a mistake, but may work (e.g. the key comes from an auth profile).
+    Warning,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AgentIssue {
+    pub severity: Severity,
+    /// Stable identifier for the UI, e.g. "unknown_provider" or "duplicate_model".
+    pub code: String,
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub message: String,
+}
+
+fn issue(
+    severity: Severity,
+    code: &str,
+    provider: &str,
+    model: Option<&str>,
+    message: String,
+) -> AgentIssue {
+    AgentIssue {
+        severity,
+        code: code.to_string(),
+        provider: Some(provider.to_string()),
+        model: model.map(String::from),
+        message,
+    }
+}
+
+/// Checks a models.json root against openclaw.json's models.providers. Errors sort first.
+#[must_use]
+pub fn lint_models_json(
+    root: &serde_json::Value,
+    openclaw_providers: &serde_json::Value,
+) -> Vec<AgentIssue> {
+    let providers = match root.get("providers").and_then(|p| p.as_object()) {
+        Some(p) => p,
+        None => {
+            return vec![AgentIssue {
+                severity: Severity::Error,
+                code: "no_providers".to_string(),
+                provider: None,
+                model: None,
+                message: "models.json has no \"providers\" object".to_string(),
+            }]
+        }
+    };
+    let mut issues = Vec::new();
+    for (name, entry) in providers {
+        if openclaw_providers.get(name).is_none() {
+            issues.push(issue(
+                Severity::Warning,
+                "unknown_provider",
+                name,
+                None,
+                format!("{} is not in openclaw.json models.providers", name),
+            ));
+        }
+        let base_url = entry.get("baseUrl").and_then(|v| v.as_str());
+        if openclaw_config::is_local_provider(name, base_url) {
+            if base_url.is_none_or(str::is_empty) {
+                issues.push(issue(
+                    Severity::Error,
+                    "missing_base_url",
+                    name,
+                    None,
+                    format!("Local provider {} has no baseUrl", name),
+                ));
+            }
+        } else {
+            match entry.get("apiKey").and_then(|v| v.as_str()) {
+                Some(key) if key.trim().is_empty() => issues.push(issue(
+                    Severity::Error,
+                    "empty_api_key",
+                    name,
+                    None,
+                    format!("{} has an empty apiKey", name),
+                )),
+                None if entry.get("apiKey").is_none() => issues.push(issue(
+                    Severity::Warning,
+                    "missing_api_key",
+                    name,
+                    None,
+                    format!(
+                        "{} has no apiKey (fine if an auth profile provides it)",
+                        name
+                    ),
+                )),
+                _ => {}
+            }
+        }
+        let models = entry
+            .get("models")
+            .and_then(|m| m.as_array())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let mut seen = HashSet::new();
+        for (i, model) in models.iter().enumerate() {
+            match model
+                .get("id")
+                .and_then(|v| v.as_str())
+                .filter(|id| !id.is_empty())
+            {
+                None => issues.push(issue(
+                    Severity::Error,
+                    "missing_model_id",
+                    name,
+                    None,
+                    format!("{} models[{}] has no id", name, i),
+                )),
+                Some(id) if !seen.insert(id) => issues.push(issue(
+                    Severity::Error,
+                    "duplicate_model",
+                    name,
+                    Some(id),
+                    format!("{} lists {} more than once", name, id),
+                )),
+                Some(_) => {}
+            }
+        }
+    }
+    issues.sort_by_key(|i| i.severity);
+    issues
+}
+
+/// Lints ~/.openclaw/agents/<name>/agent/models.json.
+pub fn validate_agent(agent_name: &str) -> Result<Vec<AgentIssue>, String> {
+    let path = agents::agent_models_path(agent_name);
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read models.json for {}: {}", agent_name, e))?;
+    let root: serde_json::Value = match serde_json::from_str(&content) {
+        Ok(v) => v,
+        Err(e) => {
+            return Ok(vec![AgentIssue {
+                severity: Severity::Error,
+                code: "invalid_json".to_string(),
+                provider: None,
+                model: None,
+                message: format!("models.json is not valid JSON: {}", e),
+            }])
+        }
+    };
+    let providers = openclaw_config::get_openclaw_providers_raw().unwrap_or(serde_json::json!({}));
+    Ok(lint_models_json(&root, &providers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lint_models_json() {
+        let openclaw = serde_json::json!({ "ollama": {}, "anthropic": {} });
+        let root = serde_json::json!({ "providers": {
+            "ollama": { "models": [{ "id": "qwen3:8b" }, { "id": "qwen3:8b" }] },
+            "anthropic": { "apiKey": "", "models": [{ "name": "no id" }] },
+            "openrouter": { "baseUrl": "https://openrouter.ai/api/v1", "models": [] },
+            "box": { "baseUrl": "http://localhost:8080/v1", "models": [] }
+        } });
+        let issues = lint_models_json(&root, &openclaw);
+        let codes: Vec<&str> = issues.iter().map(|i| i.code.as_str()).collect();
+        assert!(codes.contains(&"missing_base_url"));
+        assert!(codes.contains(&"duplicate_model"));
+        assert!(codes.contains(&"empty_api_key"));
+        assert!(codes.contains(&"missing_model_id"));
+        assert!(codes.contains(&"missing_api_key"));
+        assert_eq!(
+            issues
+                .iter()
+                .filter(|i| i.code == "unknown_provider")
+                .count(),
+            2
+        );
+        assert!(!issues
+            .iter()
+            .any(|i| i.provider.as_deref() == Some("box") && i.code == "missing_api_key"));
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert_eq!(issues.last().unwrap().severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_lint_clean_and_missing_providers() {
+        let openclaw = serde_json::json!({ "ollama": {} });
+        let root = serde_json::json!({ "providers": {
+            "ollama": { "baseUrl": "http://127.0.0.1:11434/v1", "models": [{ "id": "llama3.2:3b" }] }
+        } });
+        assert!(lint_models_json(&root, &openclaw).is_empty());
+        assert_eq!(
+            lint_models_json(&serde_json::json!({}), &openclaw)[0].code,
+            "no_providers"
+        );
+    }
+}
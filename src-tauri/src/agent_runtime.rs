@@ -0,0 +1,142 @@
+//! Live agent state from the running gateway (`openclaw gateway call sessions.list`): active
+//! sessions, the model the newest session is using, and recent token use. Session keys are
+//! "agent:<id>:<rest>". Parsing is a pure function over the JSON for unit tests.
+
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+
+use crate::operations;
+
+/// A session updated within this window counts as active.
+const ACTIVE_WINDOW_MS: u64 = 5 * 60 * 1000;
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct AgentRuntimeStatus {
+    pub agent_name: String,
+    /// False when the gateway could not be reached; the other fields are then empty.
+    pub gateway_running: bool,
+    pub active_sessions: u32,
+    /// Sessions updated in the last 24 hours.
+    pub sessions_24h: u32,
+    /// "provider/model" of the most recently updated session.
+    pub current_model: Option<String>,
+    /// Total tokens of sessions updated in the last 24 hours (whole-session totals).
+    pub tokens_24h: u64,
+    /// Newest session update, Unix ms.
+    pub last_active_ms: Option<u64>,
+}
+
+fn session_agent(key: &str) -> Option<&str> {
+    let mut parts = key.splitn(3, ':');
+    match (parts.next(), parts.next()) {
+        (Some("agent"), Some(id)) => Some(id),
+        _ => None,
+    }
+}
+
+fn session_tokens(s: &serde_json::Value) -> u64 {
+    let field = |k: &str| s.get(k).and_then(|v| v.as_u64());
+    field("totalTokens")
+        .unwrap_or_else(|| field("inputTokens").unwrap_or(0) + field("outputTokens").unwrap_or(0))
+}
+
+fn session_model(s: &serde_json::Value) -> Option<String> {
+    let model = s.get("model").and_then(|v| v.as_str())?;
+    match s.get("modelProvider").and_then(|v| v.as_str()) {
+        Some(provider) if !model.contains('/') => Some(format!("{}/{}", provider, model)),
+        _ => Some(model.to_string()),
+    }
+}
+
+/// Summarizes one agent's sessions from a sessions.list response (`{ sessions: [...] }` or a bare
+/// array) as of `now_ms`.
+#[must_use]
+pub fn runtime_status_from_sessions(
+    body: &serde_json::Value,
+    agent_name: &str,
+    now_ms: u64,
+) -> AgentRuntimeStatus {
+    let sessions = body
+        .get("sessions")
+        .unwrap_or(body)
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let mut status = AgentRuntimeStatus {
+        agent_name: agent_name.to_string(),
+        gateway_running: true,
+        ..Default::default()
+    };
+    let mut newest: Option<&serde_json::Value> = None;
+    for s in sessions {
+        let key = s.get("key").and_then(|v| v.as_str()).unwrap_or_default();
+        if session_agent(key) != Some(agent_name) {
+            continue;
+        }
+        let updated = s.get("updatedAt").and_then(|v| v.as_u64()).unwrap_or(0);
+        let age = now_ms.saturating_sub(updated);
+        if age <= ACTIVE_WINDOW_MS {
+            status.active_sessions += 1;
+        }
+        if age <= DAY_MS {
+            status.sessions_24h += 1;
+            status.tokens_24h += session_tokens(s);
+        }
+        if status.last_active_ms.is_none_or(|t| updated > t) {
+            status.last_active_ms = Some(updated);
+            newest = Some(s);
+        }
+    }
+    status.current_model = newest.and_then(session_model);
+    status
+}
+
+/// Asks the running gateway for its sessions and summarizes `agent_name`'s. An unreachable
+/// gateway is reported as `gateway_running: false`, not an error.
+pub fn get_agent_runtime_status(agent_name: &str) -> Result<AgentRuntimeStatus, String> {
+    let not_running = AgentRuntimeStatus {
+        agent_name: agent_name.to_string(),
+        ..Default::default()
+    };
+    let out = match Command::new("openclaw")
+        .args(["gateway", "call", "sessions.list", "--json"])
+        .output()
+    {
+        Ok(out) if out.status.success() => out,
+        _ => return Ok(not_running),
+    };
+    let body: serde_json::Value = serde_json::from_slice(&out.stdout)
+        .map_err(|e| format!("Unexpected sessions.list output: {}", e))?;
+    let now_ms = operations::now_ms();
+    Ok(runtime_status_from_sessions(&body, agent_name, now_ms))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runtime_status_from_sessions() {
+        let now = 10 * DAY_MS;
+        let body = serde_json::json!({ "sessions": [
+            { "key": "agent:main:main", "updatedAt": now - 1000, "model": "qwen3:8b",
+              "modelProvider": "ollama", "totalTokens": 1200 },
+            { "key": "agent:main:subagent:abc", "updatedAt": now - 2 * 60 * 60 * 1000,
+              "model": "anthropic/claude-sonnet-4-5", "inputTokens": 300, "outputTokens": 50 },
+            { "key": "agent:main:old", "updatedAt": now - 3 * DAY_MS, "totalTokens": 99999 },
+            { "key": "agent:dev:main", "updatedAt": now, "totalTokens": 5 }
+        ] });
+        let main = runtime_status_from_sessions(&body, "main", now);
+        assert!(main.gateway_running);
+        assert_eq!(main.active_sessions, 1);
+        assert_eq!(main.sessions_24h, 2);
+        assert_eq!(main.tokens_24h, 1550);
+        assert_eq!(main.current_model.as_deref(), Some("ollama/qwen3:8b"));
+        assert_eq!(main.last_active_ms, Some(now - 1000));
+
+        let idle = runtime_status_from_sessions(&body, "research", now);
+        assert_eq!(idle.sessions_24h, 0);
+        assert!(idle.current_model.is_none());
+    }
+}
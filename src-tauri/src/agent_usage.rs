@@ -0,0 +1,271 @@
+//! Token and cost totals for an agent from its session transcripts
+//! (~/.openclaw/agents/<name>/sessions/*.jsonl): every assistant message carries the provider,
+//! model and a `usage` block. Line parsing is a pure function for unit tests.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::time::UNIX_EPOCH;
+
+use crate::agents;
+use crate::operations;
+
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UsagePeriod {
+    /// Last 24 hours.
+    Day,
+    /// Last 7 days.
+    Week,
+    /// Last 30 days.
+    Month,
+    All,
+}
+
+impl UsagePeriod {
+    pub(crate) fn window_ms(self) -> Option<u64> {
+        match self {
+            Self::Day => Some(DAY_MS),
+            Self::Week => Some(7 * DAY_MS),
+            Self::Month => Some(30 * DAY_MS),
+            Self::All => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ModelUsage {
+    /// "provider/model".
+    pub model: String,
+    pub requests: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_write_tokens: u64,
+    pub total_tokens: u64,
+    /// As reported by the provider integration; 0 for local models.
+    pub cost_usd: f64,
+}
+
+impl ModelUsage {
+    fn add(&mut self, other: &ModelUsage) {
+        self.requests += other.requests;
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cache_read_tokens += other.cache_read_tokens;
+        self.cache_write_tokens += other.cache_write_tokens;
+        self.total_tokens += other.total_tokens;
+        self.cost_usd += other.cost_usd;
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AgentUsage {
+    pub agent_name: String,
+    pub period: UsagePeriod,
+    /// Totals over all models; `model` is empty.
+    pub totals: ModelUsage,
+    /// Heaviest first by total tokens.
+    pub by_model: Vec<ModelUsage>,
+    pub sessions: u32,
+    /// Unix ms of the first and last counted request.
+    pub first_ms: Option<u64>,
+    pub last_ms: Option<u64>,
+}
+
+/// Unix ms from a UTC timestamp like "2026-03-01T12:34:56.789Z" (the transcript format).
+pub(crate) fn iso_to_unix_ms(s: &str) -> Option<u64> {
+    let s = s.strip_suffix('Z')?;
+    let (date, time) = s.split_once('T')?;
+    let mut d = date.splitn(3, '-').map(|p| p.parse::<i64>().ok());
+    let (y, m, day) = (d.next()??, d.next()??, d.next()??);
+    let (hms, frac) = time.split_once('.').unwrap_or((time, "0"));
+    let mut t = hms.splitn(3, ':').map(|p| p.parse::<u64>().ok());
+    let (h, min, sec) = (t.next()??, t.next()??, t.next()??);
+    let ms: u64 = format!("{:0<3}", frac).get(..3)?.parse().ok()?;
+    // Days since 1970-01-01 (proleptic Gregorian).
+    let (y, m) = if m <= 2 { (y - 1, m + 9) } else { (y, m - 3) };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * m + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = u64::try_from(era * 146_097 + doe - 719_468).ok()?;
+    Some(((days * 24 + h) * 60 + min) * 60 * 1000 + sec * 1000 + ms)
+}
+
+/// One assistant message's usage and timestamp, or None for any other line.
+fn parse_usage_line(line: &str) -> Option<(u64, ModelUsage)> {
+    let entry: serde_json::Value = serde_json::from_str(line).ok()?;
+    let message = entry.get("message")?;
+    if message.get("role").and_then(|v| v.as_str()) != Some("assistant") {
+        return None;
+    }
+    let usage = message.get("usage")?;
+    let ts = message
+        .get("timestamp")
+        .and_then(|v| v.as_u64())
+        .or_else(|| {
+            entry
+                .get("timestamp")
+                .and_then(|v| v.as_str())
+                .and_then(iso_to_unix_ms)
+        })?;
+    let count = |k: &str| usage.get(k).and_then(|v| v.as_u64()).unwrap_or(0);
+    let model = message
+        .get("model")
+        .and_then(|v| v.as_str())
+        .unwrap_or("unknown");
+    let model = match message.get("provider").and_then(|v| v.as_str()) {
+        Some(p) if !model.contains('/') => format!("{}/{}", p, model),
+        _ => model.to_string(),
+    };
+    let (input, output) = (count("input"), count("output"));
+    let (cache_read, cache_write) = (count("cacheRead"), count("cacheWrite"));
+    Some((
+        ts,
+        ModelUsage {
+            model,
+            requests: 1,
+            input_tokens: input,
+            output_tokens: output,
+            cache_read_tokens: cache_read,
+            cache_write_tokens: cache_write,
+            total_tokens: usage
+                .get("totalTokens")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(input + output + cache_read + cache_write),
+            cost_usd: usage
+                .pointer("/cost/total")
+                .and_then(|v| v.as_f64())
+                .unwrap_or(0.0),
+        },
+    ))
+}
+
+/// Sums the usage in transcripts (one string per session file) at or after `since_ms`.
+#[must_use]
+pub fn summarize_transcripts(
+    agent_name: &str,
+    period: UsagePeriod,
+    transcripts: &[String],
+    since_ms: Option<u64>,
+) -> AgentUsage {
+    let mut per_model: HashMap<String, ModelUsage> = HashMap::new();
+    let mut totals = ModelUsage::default();
+    let (mut sessions, mut first_ms, mut last_ms) = (0, None::<u64>, None::<u64>);
+    for transcript in transcripts {
+        let mut counted = false;
+        for (ts, usage) in transcript.lines().filter_map(parse_usage_line) {
+            if since_ms.is_some_and(|since| ts < since) {
+                continue;
+            }
+            counted = true;
+            first_ms = Some(first_ms.map_or(ts, |f| f.min(ts)));
+            last_ms = Some(last_ms.map_or(ts, |l| l.max(ts)));
+            totals.add(&usage);
+            per_model
+                .entry(usage.model.clone())
+                .or_insert_with(|| ModelUsage {
+                    model: usage.model.clone(),
+                    ..Default::default()
+                })
+                .add(&usage);
+        }
+        if counted {
+            sessions += 1;
+        }
+    }
+    let mut by_model: Vec<ModelUsage> = per_model.into_values().collect();
+    by_model.sort_by(|a, b| {
+        b.total_tokens
+            .cmp(&a.total_tokens)
+            .then_with(|| a.model.cmp(&b.model))
+    });
+    AgentUsage {
+        agent_name: agent_name.to_string(),
+        period,
+        totals,
+        by_model,
+        sessions,
+        first_ms,
+        last_ms,
+    }
+}
+
+/// Reads the agent's session transcripts and sums usage over `period`. Files last written before
+/// the period starts are skipped without reading.
+pub fn get_agent_usage(agent_name: &str, period: UsagePeriod) -> Result<AgentUsage, String> {
+    let dir = agents::agents_dir().join(agent_name);
+    if !dir.is_dir() {
+        return Err(format!("Agent not found: {}", agent_name));
+    }
+    let now_ms = operations::now_ms();
+    let since_ms = period.window_ms().map(|w| now_ms.saturating_sub(w));
+    let transcripts: Vec<String> = fs::read_dir(dir.join("sessions"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "jsonl"))
+        .filter(|p| {
+            let modified_ms = fs::metadata(p)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_millis() as u64);
+            match (since_ms, modified_ms) {
+                (Some(since), Some(modified)) => modified >= since,
+                _ => true,
+            }
+        })
+        .filter_map(|p| fs::read_to_string(p).ok())
+        .collect();
+    Ok(summarize_transcripts(
+        agent_name,
+        period,
+        &transcripts,
+        since_ms,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iso_to_unix_ms() {
+        assert_eq!(iso_to_unix_ms("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(
+            iso_to_unix_ms("2024-02-29T12:00:00.5Z"),
+            Some(1_709_208_000_500)
+        );
+        assert!(iso_to_unix_ms("2024-02-29 12:00:00").is_none());
+    }
+
+    #[test]
+    fn test_summarize_transcripts() {
+        let session = [
+            r#"{"type":"session","id":"abc"}"#,
+            r#"{"type":"message","message":{"role":"user","content":"hi"}}"#,
+            r#"{"type":"message","message":{"role":"assistant","provider":"anthropic","model":"claude-sonnet-4-5","timestamp":2000,"usage":{"input":100,"output":20,"cacheRead":50,"cost":{"total":0.01}}}}"#,
+            r#"{"type":"message","timestamp":"1970-01-01T00:00:03Z","message":{"role":"assistant","provider":"ollama","model":"qwen3:8b","usage":{"input":10,"output":5,"totalTokens":15}}}"#,
+            r#"{"type":"message","message":{"role":"assistant","provider":"ollama","model":"qwen3:8b","timestamp":500,"usage":{"input":1000,"output":1000}}}"#,
+        ]
+        .join("\n");
+        let usage = summarize_transcripts(
+            "main",
+            UsagePeriod::Day,
+            &[session, String::new()],
+            Some(1000),
+        );
+        assert_eq!(usage.sessions, 1);
+        assert_eq!(usage.totals.requests, 2);
+        assert_eq!(usage.totals.total_tokens, 185);
+        assert_eq!(usage.by_model[0].model, "anthropic/claude-sonnet-4-5");
+        assert_eq!(usage.by_model[0].cache_read_tokens, 50);
+        assert_eq!(usage.by_model[1].total_tokens, 15);
+        assert_eq!((usage.first_ms, usage.last_ms), (Some(2000), Some(3000)));
+    }
+}
@@ -1,17 +1,30 @@
-//! List ~/.openclaw/agents (main, dev, ...), read agent/agent/models.json, sync with openclaw.json providers.
+//! List ~/.openclaw/agents (main, dev, ...), read agent/agent/models.json, sync with openclaw.json providers,
+//! edit provider entries and per-agent models, and create, clone or delete agents. Deleted agents are moved to ~/.openclaw/backups/agents, not removed.
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::openclaw_config;
+use crate::config_git;
+use crate::openclaw_config::{self, SubagentsView};
+use crate::operations::{self, CancelToken};
+use crate::{policy, provider_merge};
 
 const AGENTS_DIR_NAME: &str = "agents";
 const AGENT_SUBDIR: &str = "agent";
 const MODELS_JSON: &str = "models.json";
+/// Agent used when openclaw.json doesn't mark one as default.
+const DEFAULT_AGENT: &str = "main";
+/// Prefix of the error returned when a destructive operation targets the default agent without
+/// `force`, so the UI can ask for confirmation and retry.
+pub const PROTECTED_AGENT_ERROR: &str = "protected_agent";
+/// Per-agent credential stores written by openclaw next to models.json.
+const AUTH_FILES: &[&str] = &["auth-profiles.json", "auth.json"];
+/// Conversation and memory state inside an agent directory.
+const STATE_DIRS: &[&str] = &["sessions", "memory"];
 
-fn openclaw_root() -> PathBuf {
+pub(crate) fn openclaw_root() -> PathBuf {
     dirs::home_dir()
         .unwrap_or_else(|| PathBuf::from("."))
         .join(".openclaw")
@@ -26,7 +39,13 @@ pub fn agents_dir() -> PathBuf {
 /// Path to an agent's models.json: ~/.openclaw/agents/<name>/agent/models.json.
 #[must_use]
 pub fn agent_models_path(agent_name: &str) -> PathBuf {
-    agents_dir().join(agent_name).join(AGENT_SUBDIR).join(MODELS_JSON)
+    models_path_in(&agents_dir(), agent_name)
+}
+
+/// Path to an agent's models.json under an arbitrary agents directory.
+#[must_use]
+pub fn models_path_in(agents_dir: &Path, agent_name: &str) -> PathBuf {
+    agents_dir.join(agent_name).join(AGENT_SUBDIR).join(MODELS_JSON)
 }
 
 /// List agent names (subdirs of ~/.openclaw/agents that contain agent/models.json).
@@ -106,6 +125,29 @@ pub fn get_agent_models(agent_name: &str) -> Option<AgentModelsView> {
     })
 }
 
+/// Which way a provider sync copies.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncDirection {
+    /// openclaw.json models.providers -> agent models.json (`update_agent_providers_from_openclaw`).
+    OpenclawToAgent,
+    /// Agent models.json -> openclaw.json (`update_openclaw_providers_from_agent`).
+    AgentToOpenclaw,
+}
+
+/// Provider fields compared by the sync status. apiKey is left out: it is kept per side on sync.
+const SYNCED_FIELDS: &[&str] = &["baseUrl", "api", "models"];
+
+/// One field that differs for a provider present on both sides. Values are null when unset;
+/// `models` is compared and reported as sorted model ids.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ProviderFieldDiff {
+    pub provider: String,
+    pub field: String,
+    pub openclaw: serde_json::Value,
+    pub agent: serde_json::Value,
+}
+
 /// Sync status: agent's models.json providers vs openclaw.json models.providers.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ProviderSyncStatus {
@@ -114,36 +156,140 @@ pub struct ProviderSyncStatus {
     pub agent_provider_names: Vec<String>,
     pub missing_in_agent: Vec<String>,
     pub extra_in_agent: Vec<String>,
+    /// Differences in providers both sides have.
+    pub field_differences: Vec<ProviderFieldDiff>,
+    /// Agent -> openclaw.json when the agent only adds providers (e.g. set up by the CLI),
+    /// otherwise openclaw.json -> agent. None when in sync.
+    pub suggested_direction: Option<SyncDirection>,
 }
 
-/// Compare openclaw.json models.providers with an agent's models.json providers.
+fn comparable_field(provider: &serde_json::Value, field: &str) -> serde_json::Value {
+    let value = provider.get(field).cloned().unwrap_or(serde_json::Value::Null);
+    if field != "models" {
+        return value;
+    }
+    let mut ids: Vec<String> = value
+        .as_array()
+        .map(|a| {
+            a.iter()
+                .filter_map(|m| m.get("id").and_then(|v| v.as_str()).map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+    ids.sort();
+    serde_json::json!(ids)
+}
+
+/// Per-field differences for providers present in both maps.
 #[must_use]
-pub fn get_provider_sync_status(agent_name: &str) -> ProviderSyncStatus {
-    let openclaw_names: Vec<String> = openclaw_config::get_openclaw_config()
-        .provider_names
-        .into_iter()
+pub fn provider_field_diffs(
+    openclaw: &serde_json::Map<String, serde_json::Value>,
+    agent: &serde_json::Map<String, serde_json::Value>,
+) -> Vec<ProviderFieldDiff> {
+    let mut diffs = Vec::new();
+    for (name, oc) in openclaw {
+        let Some(ag) = agent.get(name) else {
+            continue;
+        };
+        for field in SYNCED_FIELDS {
+            let (a, b) = (comparable_field(oc, field), comparable_field(ag, field));
+            if a != b {
+                diffs.push(ProviderFieldDiff {
+                    provider: name.clone(),
+                    field: field.to_string(),
+                    openclaw: a,
+                    agent: b,
+                });
+            }
+        }
+    }
+    diffs
+}
+
+/// Compares openclaw.json models.providers with an agent's models.json root (None when missing).
+#[must_use]
+pub fn sync_status(
+    openclaw_providers: &serde_json::Value,
+    agent_root: Option<&serde_json::Value>,
+) -> ProviderSyncStatus {
+    let empty = serde_json::Map::new();
+    let openclaw = openclaw_providers.as_object().unwrap_or(&empty);
+    let agent = agent_root
+        .and_then(|r| r.get("providers"))
+        .and_then(|p| p.as_object())
+        .unwrap_or(&empty);
+    let mut openclaw_names: Vec<String> = openclaw.keys().cloned().collect();
+    let mut agent_names: Vec<String> = agent.keys().cloned().collect();
+    openclaw_names.sort();
+    agent_names.sort();
+    let missing_in_agent: Vec<String> = openclaw_names
+        .iter()
+        .filter(|n| !agent.contains_key(*n))
+        .cloned()
         .collect();
-    let agent = get_agent_models(agent_name);
-    let agent_names = agent
-        .map(|a| a.provider_names)
-        .unwrap_or_default();
-    let openclaw_set: std::collections::HashSet<_> = openclaw_names.iter().cloned().collect();
-    let agent_set: std::collections::HashSet<_> = agent_names.iter().cloned().collect();
-    let missing_in_agent: Vec<String> = openclaw_set.difference(&agent_set).cloned().collect();
-    let extra_in_agent: Vec<String> = agent_set.difference(&openclaw_set).cloned().collect();
-    let in_sync = missing_in_agent.is_empty() && extra_in_agent.is_empty();
+    let extra_in_agent: Vec<String> = agent_names
+        .iter()
+        .filter(|n| !openclaw.contains_key(*n))
+        .cloned()
+        .collect();
+    let field_differences = provider_field_diffs(openclaw, agent);
+    let in_sync =
+        missing_in_agent.is_empty() && extra_in_agent.is_empty() && field_differences.is_empty();
+    let suggested_direction = if in_sync {
+        None
+    } else if missing_in_agent.is_empty() && field_differences.is_empty() {
+        Some(SyncDirection::AgentToOpenclaw)
+    } else {
+        Some(SyncDirection::OpenclawToAgent)
+    };
     ProviderSyncStatus {
         in_sync,
         openclaw_provider_names: openclaw_names,
         agent_provider_names: agent_names,
         missing_in_agent,
         extra_in_agent,
+        field_differences,
+        suggested_direction,
+    }
+}
+
+/// Compare openclaw.json models.providers with an agent's models.json providers.
+#[must_use]
+pub fn get_provider_sync_status(agent_name: &str) -> ProviderSyncStatus {
+    let openclaw = openclaw_config::get_openclaw_providers_raw().unwrap_or(serde_json::json!({}));
+    sync_status(&openclaw, read_models_json(agent_name).ok().as_ref())
+}
+
+/// Copies `src` providers into `dest`, keeping `dest`'s apiKey where a provider exists in both.
+/// With `remove_extra`, providers only in `dest` are dropped.
+pub fn merge_providers(
+    dest: &mut serde_json::Map<String, serde_json::Value>,
+    src: &serde_json::Map<String, serde_json::Value>,
+    remove_extra: bool,
+) {
+    for (name, src_val) in src {
+        let mut merged = src_val.clone();
+        if let (Some(merged_obj), Some(existing)) = (
+            merged.as_object_mut(),
+            dest.get(name).and_then(|v| v.as_object()),
+        ) {
+            if let Some(api_key) = existing.get("apiKey") {
+                merged_obj.insert("apiKey".to_string(), api_key.clone());
+            }
+        }
+        dest.insert(name.clone(), merged);
+    }
+    if remove_extra {
+        dest.retain(|k, _| src.contains_key(k));
     }
 }
 
 /// Overwrite an agent's models.json providers with openclaw.json's models.providers.
 /// Preserves existing provider keys (e.g. apiKey) when the provider exists in both; otherwise uses openclaw's value.
-pub fn update_agent_providers_from_openclaw(agent_name: &str) -> Result<(), String> {
+/// The default agent needs `force`.
+pub fn update_agent_providers_from_openclaw(agent_name: &str, force: bool) -> Result<(), String> {
+    let config = openclaw_config::read_config_root()?;
+    guard_default_agent(&config, agent_name, "overwrite its providers", force)?;
     let openclaw_providers = openclaw_config::get_openclaw_providers_raw()?;
     let openclaw_obj = openclaw_providers.as_object().ok_or("openclaw providers not an object")?;
 
@@ -162,31 +308,1019 @@ pub fn update_agent_providers_from_openclaw(agent_name: &str) -> Result<(), Stri
         .as_object_mut()
         .ok_or("models.json providers not an object")?;
 
-    for (name, openclaw_val) in openclaw_obj {
-        let mut merged = openclaw_val.clone();
-        if let (Some(merged_obj), Some(existing)) = (
-            merged.as_object_mut(),
-            agents_providers.get(name).and_then(|v| v.as_object()),
-        ) {
-            if let Some(api_key) = existing.get("apiKey") {
-                merged_obj.insert("apiKey".to_string(), api_key.clone());
-            }
-        }
-        agents_providers.insert(name.clone(), merged);
-    }
-
     // Remove providers that exist in the agent but not in openclaw.json so sync status becomes in_sync.
-    agents_providers.retain(|k, _| openclaw_obj.contains_key(k));
+    merge_providers(agents_providers, openclaw_obj, true);
 
     let parent = path.parent().ok_or("invalid path")?;
     if !parent.exists() {
         fs::create_dir_all(parent).map_err(|e| e.to_string())?;
     }
+    write_models_json(agent_name, &root)?;
+    provider_merge::record_sync(agent_name, openclaw_obj)
+}
+
+/// Outcome of `sync_all_agents`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BulkSyncResult {
+    pub synced: Vec<String>,
+    /// (agent, error) for agents that couldn't be synced, e.g. the default agent without force.
+    pub failed: Vec<(String, String)>,
+}
+
+/// Syncs every agent whose providers differ from openclaw.json, reporting (done, total, agent)
+/// before each. Stops between agents when cancelled.
+pub fn sync_all_agents(
+    force: bool,
+    cancel: &CancelToken,
+    mut on_progress: impl FnMut(usize, usize, &str),
+) -> Result<BulkSyncResult, String> {
+    let drifted: Vec<String> = list_agent_names()
+        .into_iter()
+        .filter(|name| !get_provider_sync_status(name).in_sync)
+        .collect();
+    let mut result = BulkSyncResult::default();
+    for (i, name) in drifted.iter().enumerate() {
+        cancel.check()?;
+        on_progress(i, drifted.len(), name);
+        match update_agent_providers_from_openclaw(name, force) {
+            Ok(()) => result.synced.push(name.clone()),
+            Err(e) => result.failed.push((name.clone(), e)),
+        }
+    }
+    Ok(result)
+}
+
+/// Copies an agent's models.json providers into openclaw.json models.providers, keeping
+/// openclaw.json's apiKey where a provider exists in both. Providers only in openclaw.json stay,
+/// since other agents may use them.
+pub fn update_openclaw_providers_from_agent(agent_name: &str) -> Result<(), String> {
+    let agent_root = read_models_json(agent_name)?;
+    let agent_obj = agent_root
+        .get("providers")
+        .and_then(|p| p.as_object())
+        .ok_or("models.json providers not an object")?;
+
+    let mut root = openclaw_config::read_config_root()?;
+    let openclaw_providers = openclaw_config::providers_mut(&mut root)?;
+    merge_providers(openclaw_providers, agent_obj, false);
+    openclaw_config::write_config_root(&root)?;
+    provider_merge::record_sync(agent_name, agent_obj)
+}
+
+pub(crate) fn read_models_json(agent_name: &str) -> Result<serde_json::Value, String> {
+    let content = fs::read_to_string(agent_models_path(agent_name))
+        .map_err(|e| format!("Failed to read models.json for {}: {}", agent_name, e))?;
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+/// Writes an agent's models.json if the team policy allows it; with config versioning on, the
+/// change is committed.
+pub(crate) fn write_models_json(agent_name: &str, root: &serde_json::Value) -> Result<(), String> {
+    policy::enforce_models_json(agent_name, root)?;
+    let before = config_git::is_enabled().then(|| read_models_json(agent_name).unwrap_or_default());
     fs::write(
-        &path,
-        serde_json::to_string_pretty(&root).map_err(|e| e.to_string())?,
+        agent_models_path(agent_name),
+        serde_json::to_string_pretty(root).map_err(|e| e.to_string())?,
     )
-    .map_err(|e| e.to_string())
+    .map_err(|e| e.to_string())?;
+    if let Some(before) = before {
+        let file = format!("agents/{}/{}/{}", agent_name, AGENT_SUBDIR, MODELS_JSON);
+        config_git::record_json_change(&file, &before, root);
+    }
+    Ok(())
+}
+
+/// Changes to one provider in an agent's models.json. Unset fields are left alone;
+/// an empty `api_key` removes the key.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AgentProviderChanges {
+    pub base_url: Option<String>,
+    pub api: Option<String>,
+    pub api_key: Option<String>,
+    /// Replaces the models array; each entry needs a non-empty "id".
+    pub models: Option<Vec<serde_json::Value>>,
+}
+
+/// Applies `changes` to a provider entry, keeping fields the UI doesn't know about.
+pub fn apply_provider_changes(
+    entry: &mut serde_json::Map<String, serde_json::Value>,
+    changes: &AgentProviderChanges,
+) -> Result<(), String> {
+    if let Some(base_url) = &changes.base_url {
+        if !(base_url.starts_with("http://") || base_url.starts_with("https://")) {
+            return Err(format!("baseUrl must be an http(s) URL: {}", base_url));
+        }
+        entry.insert("baseUrl".to_string(), serde_json::json!(base_url));
+    }
+    if let Some(api) = &changes.api {
+        entry.insert("api".to_string(), serde_json::json!(api));
+    }
+    match changes.api_key.as_deref() {
+        Some("") => {
+            entry.remove("apiKey");
+        }
+        Some(key) => {
+            entry.insert("apiKey".to_string(), serde_json::json!(key));
+        }
+        None => {}
+    }
+    if let Some(models) = &changes.models {
+        let has_id = |m: &serde_json::Value| {
+            m.get("id")
+                .and_then(|v| v.as_str())
+                .is_some_and(|id| !id.is_empty())
+        };
+        if let Some(bad) = models.iter().position(|m| !has_id(m)) {
+            return Err(format!("models[{}] needs a non-empty \"id\"", bad));
+        }
+        entry.insert("models".to_string(), serde_json::json!(models));
+    }
+    Ok(())
+}
+
+/// Updates (or adds) a provider in an agent's models.json and returns the new view.
+pub fn update_agent_provider(
+    agent_name: &str,
+    provider: &str,
+    changes: &AgentProviderChanges,
+) -> Result<AgentModelsView, String> {
+    if provider.trim().is_empty() {
+        return Err("Provider name is empty".to_string());
+    }
+    let mut root = read_models_json(agent_name)?;
+    let providers = root
+        .as_object_mut()
+        .ok_or("models.json root not an object")?
+        .entry("providers")
+        .or_insert_with(|| serde_json::json!({}))
+        .as_object_mut()
+        .ok_or("models.json providers not an object")?;
+    let entry = providers
+        .entry(provider)
+        .or_insert_with(|| serde_json::json!({ "models": [] }))
+        .as_object_mut()
+        .ok_or_else(|| format!("Provider {} is not an object", provider))?;
+    apply_provider_changes(entry, changes)?;
+    write_models_json(agent_name, &root)?;
+    get_agent_models(agent_name).ok_or_else(|| format!("Failed to read agent {}", agent_name))
+}
+
+/// Removes a provider from an agent's models.json and returns the new view.
+pub fn remove_agent_provider(agent_name: &str, provider: &str) -> Result<AgentModelsView, String> {
+    let mut root = read_models_json(agent_name)?;
+    let removed = root
+        .get_mut("providers")
+        .and_then(|p| p.as_object_mut())
+        .and_then(|p| p.remove(provider));
+    if removed.is_none() {
+        return Err(format!(
+            "Provider not found in {}: {}",
+            agent_name, provider
+        ));
+    }
+    write_models_json(agent_name, &root)?;
+    get_agent_models(agent_name).ok_or_else(|| format!("Failed to read agent {}", agent_name))
+}
+
+/// Where an agent provider's apiKey comes from relative to openclaw.json.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeySource {
+    /// Same key as openclaw.json models.providers (copied by sync).
+    Inherited,
+    /// The agent has its own key (different from openclaw.json's, or openclaw.json has none).
+    Overridden,
+    /// The agent has no key.
+    Unset,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ProviderApiKeyStatus {
+    pub provider: String,
+    pub source: ApiKeySource,
+    /// openclaw.json has a key for this provider.
+    pub global_key_set: bool,
+}
+
+fn non_empty_key(provider: Option<&serde_json::Value>) -> Option<&serde_json::Value> {
+    provider
+        .and_then(|p| p.get("apiKey"))
+        .filter(|k| !k.is_null() && k.as_str() != Some(""))
+}
+
+/// Classifies each agent provider's apiKey against openclaw.json models.providers.
+#[must_use]
+pub fn api_key_statuses(
+    agent_providers: &serde_json::Map<String, serde_json::Value>,
+    openclaw_providers: &serde_json::Value,
+) -> Vec<ProviderApiKeyStatus> {
+    let mut statuses: Vec<ProviderApiKeyStatus> = agent_providers
+        .iter()
+        .map(|(name, entry)| {
+            let global = non_empty_key(openclaw_providers.get(name));
+            let source = match non_empty_key(Some(entry)) {
+                None => ApiKeySource::Unset,
+                Some(key) if Some(key) == global => ApiKeySource::Inherited,
+                Some(_) => ApiKeySource::Overridden,
+            };
+            ProviderApiKeyStatus {
+                provider: name.clone(),
+                source,
+                global_key_set: global.is_some(),
+            }
+        })
+        .collect();
+    statuses.sort_by(|a, b| a.provider.cmp(&b.provider));
+    statuses
+}
+
+/// Per-provider apiKey source for an agent.
+pub fn get_agent_api_key_statuses(agent_name: &str) -> Result<Vec<ProviderApiKeyStatus>, String> {
+    let root = read_models_json(agent_name)?;
+    let providers = root
+        .get("providers")
+        .and_then(|p| p.as_object())
+        .ok_or("models.json providers not an object")?;
+    let openclaw = openclaw_config::get_openclaw_providers_raw().unwrap_or(serde_json::json!({}));
+    Ok(api_key_statuses(providers, &openclaw))
+}
+
+/// Sets the agent's own apiKey for a provider (`Some`), or clears the override (`None`) so the
+/// provider goes back to openclaw.json's key, or no key if openclaw.json has none.
+pub fn set_agent_api_key_override(
+    agent_name: &str,
+    provider: &str,
+    api_key: Option<&str>,
+) -> Result<Vec<ProviderApiKeyStatus>, String> {
+    if api_key.is_some_and(|k| k.trim().is_empty()) {
+        return Err("API key must not be empty; clear the override instead".to_string());
+    }
+    let openclaw = openclaw_config::get_openclaw_providers_raw().unwrap_or(serde_json::json!({}));
+    let mut root = read_models_json(agent_name)?;
+    let entry = root
+        .get_mut("providers")
+        .and_then(|p| p.get_mut(provider))
+        .and_then(|p| p.as_object_mut())
+        .ok_or_else(|| format!("Provider not found in {}: {}", agent_name, provider))?;
+    match api_key
+        .map(|k| serde_json::json!(k))
+        .or_else(|| non_empty_key(openclaw.get(provider)).cloned())
+    {
+        Some(key) => {
+            entry.insert("apiKey".to_string(), key);
+        }
+        None => {
+            entry.remove("apiKey");
+        }
+    }
+    write_models_json(agent_name, &root)?;
+    let providers = root
+        .get("providers")
+        .and_then(|p| p.as_object())
+        .ok_or("models.json providers not an object")?;
+    Ok(api_key_statuses(providers, &openclaw))
+}
+
+/// An agent's model selection: its override in openclaw.json agents.list and what it resolves to.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AgentModelConfig {
+    pub agent_name: String,
+    /// The agent's own primary model; None inherits agents.defaults.model.primary.
+    pub primary: Option<String>,
+    /// The agent's own fallbacks; None inherits agents.defaults.model.fallbacks.
+    pub fallbacks: Option<Vec<String>>,
+    pub effective_primary: Option<String>,
+    pub effective_fallbacks: Vec<String>,
+}
+
+/// Changes to an agent's model override. An empty `primary` removes the primary override;
+/// `reset` removes the whole override so the agent follows agents.defaults again.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AgentModelConfigUpdates {
+    pub primary: Option<String>,
+    pub fallbacks: Option<Vec<String>>,
+    pub reset: bool,
+}
+
+fn string_list(v: Option<&serde_json::Value>) -> Option<Vec<String>> {
+    v.and_then(|a| a.as_array()).map(|a| {
+        a.iter()
+            .filter_map(|v| v.as_str().map(String::from))
+            .collect()
+    })
+}
+
+/// The agent's entry in openclaw.json agents.list, if any.
+pub(crate) fn agent_list_entry<'a>(
+    root: &'a serde_json::Value,
+    agent_name: &str,
+) -> Option<&'a serde_json::Value> {
+    root.get("agents")
+        .and_then(|a| a.get("list"))
+        .and_then(|l| l.as_array())
+        .and_then(|l| {
+            l.iter()
+                .find(|e| e.get("id").and_then(|v| v.as_str()) == Some(agent_name))
+        })
+}
+
+/// Reads an agent's model override from the openclaw.json root. `model` may be a bare
+/// "provider/model" string or `{ primary, fallbacks }`.
+#[must_use]
+pub fn agent_model_config(root: &serde_json::Value, agent_name: &str) -> AgentModelConfig {
+    let model = agent_list_entry(root, agent_name).and_then(|e| e.get("model"));
+    let (primary, fallbacks) = match model {
+        Some(serde_json::Value::String(s)) => (Some(s.clone()), None),
+        Some(m) => (
+            m.get("primary").and_then(|v| v.as_str()).map(String::from),
+            string_list(m.get("fallbacks")),
+        ),
+        None => (None, None),
+    };
+    let defaults = root
+        .get("agents")
+        .and_then(|a| a.get("defaults"))
+        .and_then(|d| d.get("model"));
+    AgentModelConfig {
+        agent_name: agent_name.to_string(),
+        effective_primary: primary.clone().or_else(|| {
+            defaults
+                .and_then(|m| m.get("primary"))
+                .and_then(|v| v.as_str())
+                .map(String::from)
+        }),
+        effective_fallbacks: fallbacks
+            .clone()
+            .or_else(|| string_list(defaults.and_then(|m| m.get("fallbacks"))))
+            .unwrap_or_default(),
+        primary,
+        fallbacks,
+    }
+}
+
+/// The agent's entry in openclaw.json agents.list, adding it (and the list) if needed.
+fn agent_list_entry_mut<'a>(
+    root: &'a mut serde_json::Value,
+    agent_name: &str,
+) -> Result<&'a mut serde_json::Map<String, serde_json::Value>, String> {
+    let agents = root
+        .as_object_mut()
+        .ok_or("openclaw.json root not an object")?
+        .entry("agents")
+        .or_insert_with(|| serde_json::json!({}))
+        .as_object_mut()
+        .ok_or("agents not an object")?;
+    let list = agents
+        .entry("list")
+        .or_insert_with(|| serde_json::json!([]))
+        .as_array_mut()
+        .ok_or("agents.list not an array")?;
+    // The first entry becomes the default agent, so keep "main" first when starting the list.
+    if list.is_empty() && agent_name != DEFAULT_AGENT {
+        list.push(serde_json::json!({ "id": DEFAULT_AGENT }));
+    }
+    let pos = match list
+        .iter()
+        .position(|e| e.get("id").and_then(|v| v.as_str()) == Some(agent_name))
+    {
+        Some(pos) => pos,
+        None => {
+            list.push(serde_json::json!({ "id": agent_name }));
+            list.len() - 1
+        }
+    };
+    list[pos]
+        .as_object_mut()
+        .ok_or_else(|| "agents.list entry not an object".to_string())
+}
+
+/// Applies `updates` to the agent's agents.list entry, adding the entry if needed.
+pub fn apply_agent_model_updates(
+    root: &mut serde_json::Value,
+    agent_name: &str,
+    updates: &AgentModelConfigUpdates,
+) -> Result<(), String> {
+    let ids = updates
+        .primary
+        .iter()
+        .filter(|p| !p.is_empty())
+        .chain(updates.fallbacks.iter().flatten());
+    if let Some(bad) = ids.into_iter().find(|id| !id.contains('/')) {
+        return Err(format!("Model id must be \"provider/model\": {}", bad));
+    }
+    let entry = agent_list_entry_mut(root, agent_name)?;
+    if updates.reset {
+        entry.remove("model");
+        return Ok(());
+    }
+    let mut model = match entry.remove("model") {
+        Some(serde_json::Value::String(s)) => serde_json::json!({ "primary": s }),
+        Some(m @ serde_json::Value::Object(_)) => m,
+        _ => serde_json::json!({}),
+    };
+    let model_obj = model.as_object_mut().ok_or("model not an object")?;
+    match updates.primary.as_deref() {
+        Some("") => {
+            model_obj.remove("primary");
+        }
+        Some(p) => {
+            model_obj.insert("primary".to_string(), serde_json::json!(p));
+        }
+        None => {}
+    }
+    if let Some(f) = &updates.fallbacks {
+        model_obj.insert("fallbacks".to_string(), serde_json::json!(f));
+    }
+    if !model_obj.is_empty() {
+        entry.insert("model".to_string(), model);
+    }
+    Ok(())
+}
+
+/// Makes `id` the agent's own primary ("primary") or adds it to its fallbacks ("fallback"),
+/// starting from the fallbacks it currently inherits. A model is never both primary and fallback.
+pub fn set_agent_model_role(
+    root: &mut serde_json::Value,
+    agent_name: &str,
+    id: &str,
+    role: &str,
+) -> Result<(), String> {
+    let current = agent_model_config(root, agent_name);
+    let mut fallbacks = current.effective_fallbacks;
+    let updates = match role {
+        "primary" => {
+            let listed = fallbacks.iter().any(|f| f == id);
+            fallbacks.retain(|f| f != id);
+            AgentModelConfigUpdates {
+                primary: Some(id.to_string()),
+                fallbacks: (listed || current.fallbacks.is_some()).then_some(fallbacks),
+                reset: false,
+            }
+        }
+        "fallback" => {
+            if current.effective_primary.as_deref() == Some(id) || fallbacks.iter().any(|f| f == id)
+            {
+                return Ok(());
+            }
+            fallbacks.push(id.to_string());
+            AgentModelConfigUpdates {
+                fallbacks: Some(fallbacks),
+                ..Default::default()
+            }
+        }
+        other => return Err(format!("Unknown model role: {}", other)),
+    };
+    apply_agent_model_updates(root, agent_name, &updates)
+}
+
+/// Reads an agent's model override and effective models from openclaw.json.
+pub fn get_agent_model_config(agent_name: &str) -> Result<AgentModelConfig, String> {
+    let root = openclaw_config::read_config_root()?;
+    Ok(agent_model_config(&root, agent_name))
+}
+
+/// Updates an agent's primary/fallback override in openclaw.json and returns the result.
+pub fn update_agent_model_config(
+    agent_name: &str,
+    updates: &AgentModelConfigUpdates,
+) -> Result<AgentModelConfig, String> {
+    validate_agent_name(agent_name)?;
+    let mut root = openclaw_config::read_config_root()?;
+    apply_agent_model_updates(&mut root, agent_name, updates)?;
+    openclaw_config::write_config_root(&root)?;
+    Ok(agent_model_config(&root, agent_name))
+}
+
+/// An agent's subagent limits: its override in agents.list and what applies after falling back to
+/// agents.defaults.subagents and the gateway defaults.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AgentSubagentLimits {
+    pub agent_name: String,
+    pub overrides: SubagentsView,
+    pub effective: SubagentsView,
+}
+
+/// Changes to an agent's subagent overrides. Unset fields are left alone; 0 removes that
+/// override; `reset` removes all of them.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AgentSubagentLimitUpdates {
+    pub max_concurrent: Option<u32>,
+    pub max_spawn_depth: Option<u32>,
+    pub max_children_per_agent: Option<u32>,
+    pub reset: bool,
+}
+
+/// Reads an agent's subagent overrides and effective limits from the openclaw.json root.
+#[must_use]
+pub fn agent_subagent_limits(root: &serde_json::Value, agent_name: &str) -> AgentSubagentLimits {
+    let none = serde_json::json!({});
+    let overrides = openclaw_config::parse_subagents_view(
+        agent_list_entry(root, agent_name)
+            .and_then(|e| e.get("subagents"))
+            .unwrap_or(&none),
+    );
+    let defaults = openclaw_config::parse_subagents_view(
+        root.get("agents")
+            .and_then(|a| a.get("defaults"))
+            .and_then(|d| d.get("subagents"))
+            .unwrap_or(&none),
+    );
+    let builtin = SubagentsView::default();
+    AgentSubagentLimits {
+        agent_name: agent_name.to_string(),
+        effective: SubagentsView {
+            max_concurrent: overrides
+                .max_concurrent
+                .or(defaults.max_concurrent)
+                .or(builtin.max_concurrent),
+            max_spawn_depth: overrides
+                .max_spawn_depth
+                .or(defaults.max_spawn_depth)
+                .or(builtin.max_spawn_depth),
+            max_children_per_agent: overrides
+                .max_children_per_agent
+                .or(defaults.max_children_per_agent)
+                .or(builtin.max_children_per_agent),
+        },
+        overrides,
+    }
+}
+
+/// Applies `updates` to the agent's agents.list `subagents`, adding the entry if needed.
+pub fn apply_subagent_limit_updates(
+    root: &mut serde_json::Value,
+    agent_name: &str,
+    updates: &AgentSubagentLimitUpdates,
+) -> Result<(), String> {
+    let entry = agent_list_entry_mut(root, agent_name)?;
+    if updates.reset {
+        entry.remove("subagents");
+        return Ok(());
+    }
+    let mut subagents = match entry.remove("subagents") {
+        Some(s @ serde_json::Value::Object(_)) => s,
+        _ => serde_json::json!({}),
+    };
+    let obj = subagents.as_object_mut().ok_or("subagents not an object")?;
+    for (key, value) in [
+        ("maxConcurrent", updates.max_concurrent),
+        ("maxSpawnDepth", updates.max_spawn_depth),
+        ("maxChildrenPerAgent", updates.max_children_per_agent),
+    ] {
+        match value {
+            Some(0) => {
+                obj.remove(key);
+            }
+            Some(n) => {
+                obj.insert(key.to_string(), serde_json::json!(n));
+            }
+            None => {}
+        }
+    }
+    if !obj.is_empty() {
+        entry.insert("subagents".to_string(), subagents);
+    }
+    Ok(())
+}
+
+/// Reads an agent's subagent overrides and effective limits from openclaw.json.
+pub fn get_agent_subagent_limits(agent_name: &str) -> Result<AgentSubagentLimits, String> {
+    let root = openclaw_config::read_config_root()?;
+    Ok(agent_subagent_limits(&root, agent_name))
+}
+
+/// Updates an agent's subagent overrides in openclaw.json and returns the result.
+pub fn update_agent_subagent_limits(
+    agent_name: &str,
+    updates: &AgentSubagentLimitUpdates,
+) -> Result<AgentSubagentLimits, String> {
+    validate_agent_name(agent_name)?;
+    let mut root = openclaw_config::read_config_root()?;
+    apply_subagent_limit_updates(&mut root, agent_name, updates)?;
+    openclaw_config::write_config_root(&root)?;
+    Ok(agent_subagent_limits(&root, agent_name))
+}
+
+/// Options for `create_agent`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CreateAgentOptions {
+    /// openclaw.json providers to seed models.json with; all of them when None.
+    pub providers: Option<Vec<String>>,
+}
+
+/// Agent names become directory names: 1-64 ASCII letters, digits, '-' or '_', starting with a letter or digit.
+pub fn validate_agent_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.len() > 64 {
+        return Err("Agent name must be 1-64 characters".to_string());
+    }
+    if !name.starts_with(|c: char| c.is_ascii_alphanumeric()) {
+        return Err("Agent name must start with a letter or digit".to_string());
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || *c == '-' || *c == '_'))
+    {
+        return Err(format!("Agent name contains invalid character '{}'", c));
+    }
+    Ok(())
+}
+
+/// Builds a new agent's models.json from openclaw.json's models.providers, optionally limited to `only`.
+pub fn seed_models_json(
+    openclaw_providers: &serde_json::Value,
+    only: Option<&[String]>,
+) -> Result<serde_json::Value, String> {
+    let all = openclaw_providers
+        .as_object()
+        .ok_or("openclaw providers not an object")?;
+    if let Some(missing) = only.and_then(|names| names.iter().find(|n| !all.contains_key(*n))) {
+        return Err(format!("Provider not in openclaw.json: {}", missing));
+    }
+    let providers: serde_json::Map<String, serde_json::Value> = all
+        .iter()
+        .filter(|(name, _)| only.is_none_or(|names| names.contains(name)))
+        .map(|(name, val)| (name.clone(), val.clone()))
+        .collect();
+    Ok(serde_json::json!({ "providers": providers }))
+}
+
+/// Scaffolds ~/.openclaw/agents/<name>/agent/ with a models.json seeded from openclaw.json providers.
+/// Fails if the name is invalid or the agent directory already exists.
+pub fn create_agent(name: &str, options: &CreateAgentOptions) -> Result<AgentModelsView, String> {
+    validate_agent_name(name)?;
+    let agent_dir = agents_dir().join(name);
+    if agent_dir.exists() {
+        return Err(format!("Agent already exists: {}", name));
+    }
+    let providers = openclaw_config::get_openclaw_providers_raw()?;
+    let root = seed_models_json(&providers, options.providers.as_deref())?;
+    let path = agent_models_path(name);
+    let parent = path.parent().ok_or("invalid path")?;
+    fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    write_models_json(name, &root)?;
+    if let Some(seeded) = root.get("providers").and_then(|p| p.as_object()) {
+        provider_merge::record_sync(name, seeded)?;
+    }
+    get_agent_models(name).ok_or_else(|| format!("Failed to read new agent {}", name))
+}
+
+/// What's wrong with an agent directory, if anything.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentHealth {
+    Ok,
+    MissingModelsJson,
+    InvalidJson,
+    /// Valid JSON, but not `{ "providers": { "<name>": { ... } } }`.
+    WrongStructure,
+}
+
+/// An agent directory, including ones `list_agent_names` hides because models.json is unusable.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AgentListEntry {
+    pub name: String,
+    pub health: AgentHealth,
+    pub problem: Option<String>,
+}
+
+/// Checks models.json content (None when the file is missing).
+#[must_use]
+pub fn check_models_json(content: Option<&str>) -> (AgentHealth, Option<String>) {
+    let content = match content {
+        Some(c) => c,
+        None => {
+            return (
+                AgentHealth::MissingModelsJson,
+                Some("agent/models.json is missing".to_string()),
+            )
+        }
+    };
+    let root: serde_json::Value = match serde_json::from_str(content) {
+        Ok(v) => v,
+        Err(e) => {
+            return (
+                AgentHealth::InvalidJson,
+                Some(format!("models.json is not valid JSON: {}", e)),
+            )
+        }
+    };
+    let problem = match root.get("providers") {
+        _ if !root.is_object() => Some("models.json root is not an object".to_string()),
+        None => Some("models.json has no \"providers\"".to_string()),
+        Some(p) => match p.as_object() {
+            None => Some("\"providers\" is not an object".to_string()),
+            Some(providers) => providers
+                .iter()
+                .find(|(_, v)| !v.is_object())
+                .map(|(name, _)| format!("provider \"{}\" is not an object", name)),
+        },
+    };
+    match problem {
+        Some(p) => (AgentHealth::WrongStructure, Some(p)),
+        None => (AgentHealth::Ok, None),
+    }
+}
+
+/// Every directory under ~/.openclaw/agents with its health, sorted by name.
+#[must_use]
+pub fn list_agents_detailed() -> Vec<AgentListEntry> {
+    let dir = match fs::read_dir(agents_dir()) {
+        Ok(d) => d,
+        Err(_) => return vec![],
+    };
+    let mut entries: Vec<AgentListEntry> = dir
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|name| !name.starts_with('.'))
+        .map(|name| {
+            let content = fs::read_to_string(agent_models_path(&name)).ok();
+            let (health, problem) = check_models_json(content.as_deref());
+            AgentListEntry {
+                name,
+                health,
+                problem,
+            }
+        })
+        .collect();
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    entries
+}
+
+/// Rebuilds a broken models.json: keeps valid provider entries and other top-level keys, and
+/// fills in the rest from openclaw.json. The old file is kept as models.json.broken-<unix secs>.
+pub fn repair_agent(name: &str) -> Result<AgentModelsView, String> {
+    validate_agent_name(name)?;
+    if !agents_dir().join(name).is_dir() {
+        return Err(format!("Agent not found: {}", name));
+    }
+    let path = agent_models_path(name);
+    let content = fs::read_to_string(&path).ok();
+    let (health, _) = check_models_json(content.as_deref());
+    if health == AgentHealth::Ok {
+        return get_agent_models(name).ok_or_else(|| format!("Failed to read agent {}", name));
+    }
+    let seed = seed_models_json(&openclaw_config::get_openclaw_providers_raw()?, None)?;
+    let mut root = content
+        .as_deref()
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(c).ok())
+        .filter(|v| v.is_object())
+        .unwrap_or_else(|| serde_json::json!({}));
+    if health != AgentHealth::MissingModelsJson {
+        let secs = operations::now_secs();
+        fs::copy(
+            &path,
+            path.with_file_name(format!("{}.broken-{}", MODELS_JSON, secs)),
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    let mut providers: serde_json::Map<String, serde_json::Value> = root
+        .get("providers")
+        .and_then(|p| p.as_object())
+        .map(|p| {
+            p.iter()
+                .filter(|(_, v)| v.is_object())
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect()
+        })
+        .unwrap_or_default();
+    let reseeded = providers.is_empty();
+    if let Some(seeded) = seed["providers"].as_object() {
+        for (k, v) in seeded {
+            providers.entry(k.clone()).or_insert_with(|| v.clone());
+        }
+    }
+    if let Some(obj) = root.as_object_mut() {
+        obj.insert(
+            "providers".to_string(),
+            serde_json::Value::Object(providers),
+        );
+    }
+    let parent = path.parent().ok_or("invalid path")?;
+    fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    write_models_json(name, &root)?;
+    if reseeded {
+        if let Some(seeded) = seed["providers"].as_object() {
+            provider_merge::record_sync(name, seeded)?;
+        }
+    }
+    get_agent_models(name).ok_or_else(|| format!("Failed to read agent {}", name))
+}
+
+/// Options for `clone_agent`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CloneAgentOptions {
+    /// Drop apiKey from models.json providers and skip credential files.
+    pub strip_api_keys: bool,
+    /// Skip session history and memory.
+    pub strip_state: bool,
+}
+
+/// Removes apiKey from every provider in a models.json root.
+pub fn strip_provider_api_keys(root: &mut serde_json::Value) {
+    if let Some(providers) = root.get_mut("providers").and_then(|p| p.as_object_mut()) {
+        for provider in providers.values_mut().filter_map(|p| p.as_object_mut()) {
+            provider.remove("apiKey");
+        }
+    }
+}
+
+/// Whether a path relative to the agent directory is left out of a clone.
+#[must_use]
+pub fn skip_in_clone(rel: &Path, options: &CloneAgentOptions) -> bool {
+    let file_name = rel.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    let top = rel
+        .components()
+        .next()
+        .and_then(|c| c.as_os_str().to_str())
+        .unwrap_or("");
+    (options.strip_api_keys && AUTH_FILES.contains(&file_name))
+        || (options.strip_state && STATE_DIRS.contains(&top))
+}
+
+/// Recursive copy that skips symlinks rather than following them, so a link loop or a link out
+/// of the agent directory isn't copied.
+fn copy_dir(
+    src: &Path,
+    dst: &Path,
+    base: &Path,
+    options: &CloneAgentOptions,
+) -> Result<(), String> {
+    fs::create_dir_all(dst).map_err(|e| e.to_string())?;
+    for entry in fs::read_dir(src).map_err(|e| e.to_string())?.flatten() {
+        let path = entry.path();
+        let rel = path.strip_prefix(base).map_err(|e| e.to_string())?;
+        if skip_in_clone(rel, options) {
+            continue;
+        }
+        let file_type = entry.file_type().map_err(|e| e.to_string())?;
+        let target = dst.join(entry.file_name());
+        if file_type.is_symlink() {
+            continue;
+        } else if file_type.is_dir() {
+            copy_dir(&path, &target, base, options)?;
+        } else {
+            fs::copy(&path, &target).map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+/// Copies an agent directory to `dst`, skipping what `options` excludes and removing apiKey
+/// from the copied models.json when asked. Shared by cloning and export.
+pub(crate) fn copy_agent_dir(
+    src: &Path,
+    dst: &Path,
+    options: &CloneAgentOptions,
+) -> Result<(), String> {
+    copy_dir(src, dst, src, options)?;
+    let models = dst.join(AGENT_SUBDIR).join(MODELS_JSON);
+    if options.strip_api_keys && models.exists() {
+        let content = fs::read_to_string(&models).map_err(|e| e.to_string())?;
+        let mut root: serde_json::Value =
+            serde_json::from_str(&content).map_err(|e| e.to_string())?;
+        strip_provider_api_keys(&mut root);
+        fs::write(
+            &models,
+            serde_json::to_string_pretty(&root).map_err(|e| e.to_string())?,
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Copies ~/.openclaw/agents/<source> (models.json, workspace files) to a new agent, optionally
+/// without API keys and session/memory state.
+pub fn clone_agent(
+    source: &str,
+    new_name: &str,
+    options: &CloneAgentOptions,
+) -> Result<AgentModelsView, String> {
+    validate_agent_name(source)?;
+    validate_agent_name(new_name)?;
+    let src = agents_dir().join(source);
+    if !src.is_dir() {
+        return Err(format!("Agent not found: {}", source));
+    }
+    let dst = agents_dir().join(new_name);
+    if dst.exists() {
+        return Err(format!("Agent already exists: {}", new_name));
+    }
+    let copied = copy_agent_dir(&src, &dst, options).and_then(|()| {
+        read_models_json(new_name)
+            .map_or(Ok(()), |root| policy::enforce_models_json(new_name, &root))
+    });
+    if let Err(e) = copied {
+        let _ = fs::remove_dir_all(&dst);
+        return Err(e);
+    }
+    config_git::record_change(&format!("Clone agent {} as {}", source, new_name));
+    get_agent_models(new_name)
+        .ok_or_else(|| format!("Cloned agent {} has no models.json", new_name))
+}
+
+/// The default agent: the agents.list entry marked `"default": true`, else the first entry, else "main".
+#[must_use]
+pub fn default_agent_name(root: &serde_json::Value) -> String {
+    let list = root
+        .get("agents")
+        .and_then(|a| a.get("list"))
+        .and_then(|l| l.as_array());
+    let id = |e: &serde_json::Value| e.get("id").and_then(|v| v.as_str()).map(String::from);
+    list.and_then(|l| {
+        l.iter()
+            .find(|e| e.get("default").and_then(|v| v.as_bool()) == Some(true))
+            .or_else(|| l.first())
+            .and_then(id)
+    })
+    .unwrap_or_else(|| DEFAULT_AGENT.to_string())
+}
+
+/// Reads the default agent from openclaw.json (see `default_agent_name`).
+pub fn get_default_agent() -> Result<String, String> {
+    Ok(default_agent_name(&openclaw_config::read_config_root()?))
+}
+
+/// Fails with a `PROTECTED_AGENT_ERROR`-prefixed message when `name` is the default agent and
+/// `force` is false.
+pub fn guard_default_agent(
+    root: &serde_json::Value,
+    name: &str,
+    action: &str,
+    force: bool,
+) -> Result<(), String> {
+    if !force && default_agent_name(root) == name {
+        return Err(format!(
+            "{}: {} is the default agent; confirm to {}",
+            PROTECTED_AGENT_ERROR, name, action
+        ));
+    }
+    Ok(())
+}
+
+/// Moves ~/.openclaw/agents/<name> to ~/.openclaw/backups/agents/<name>-<unix secs> and returns
+/// the backup path. The default agent needs `force`.
+pub fn delete_agent(name: &str, force: bool) -> Result<String, String> {
+    validate_agent_name(name)?;
+    let root = openclaw_config::read_config_root()?;
+    guard_default_agent(&root, name, "delete it", force)?;
+    let agent_dir = agents_dir().join(name);
+    if !agent_dir.is_dir() {
+        return Err(format!("Agent not found: {}", name));
+    }
+    let backups = openclaw_root().join("backups").join(AGENTS_DIR_NAME);
+    fs::create_dir_all(&backups).map_err(|e| e.to_string())?;
+    let dest = backups.join(format!("{}-{}", name, operations::now_secs()));
+    fs::rename(&agent_dir, &dest).map_err(|e| format!("Failed to move agent to backups: {}", e))?;
+    config_git::record_change(&format!("Delete agent {}", name));
+    Ok(dest.to_string_lossy().to_string())
+}
+
+/// Points the agents.list entry for `old` at `new`. No-op when the agent has no entry.
+pub fn rename_list_entry(root: &mut serde_json::Value, old: &str, new: &str) {
+    let entry = root
+        .pointer_mut("/agents/list")
+        .and_then(|l| l.as_array_mut())
+        .and_then(|l| {
+            l.iter_mut()
+                .find(|e| e.get("id").and_then(|v| v.as_str()) == Some(old))
+        });
+    if let Some(obj) = entry.and_then(|e| e.as_object_mut()) {
+        obj.insert("id".to_string(), serde_json::json!(new));
+    }
+}
+
+/// Renames ~/.openclaw/agents/<old> to <new> and updates its agents.list entry. The default
+/// agent needs `force`.
+pub fn rename_agent(old: &str, new: &str, force: bool) -> Result<AgentModelsView, String> {
+    validate_agent_name(old)?;
+    validate_agent_name(new)?;
+    let mut root = openclaw_config::read_config_root()?;
+    guard_default_agent(&root, old, "rename it", force)?;
+    let (old_dir, new_dir) = (agents_dir().join(old), agents_dir().join(new));
+    if !old_dir.is_dir() {
+        return Err(format!("Agent not found: {}", old));
+    }
+    if new_dir.exists() {
+        return Err(format!("Agent already exists: {}", new));
+    }
+    fs::rename(&old_dir, &new_dir).map_err(|e| format!("Failed to rename agent: {}", e))?;
+    if agent_list_entry(&root, old).is_some() {
+        rename_list_entry(&mut root, old, new);
+        // agents.list must keep pointing at an existing directory.
+        if let Err(e) = openclaw_config::write_config_root(&root) {
+            let _ = fs::rename(&new_dir, &old_dir);
+            return Err(e);
+        }
+    }
+    config_git::record_change(&format!("Rename agent {} to {}", old, new));
+    provider_merge::rename_snapshot(old, new);
+    get_agent_models(new).ok_or_else(|| format!("Failed to read agent {}", new))
 }
 
 #[cfg(test)]
@@ -211,4 +1345,341 @@ mod tests {
     fn test_list_agent_names_no_panic() {
         let _ = list_agent_names();
     }
+
+    #[test]
+    fn test_validate_agent_name() {
+        assert!(validate_agent_name("research-2").is_ok());
+        assert!(validate_agent_name("dev_agent").is_ok());
+        assert!(validate_agent_name("").is_err());
+        assert!(validate_agent_name("-x").is_err());
+        assert!(validate_agent_name("../main").is_err());
+        assert!(validate_agent_name("my agent").is_err());
+    }
+
+    #[test]
+    fn test_default_agent_name() {
+        assert_eq!(default_agent_name(&serde_json::json!({})), "main");
+        let root = serde_json::json!({ "agents": { "list": [
+            { "id": "work" }, { "id": "home", "default": true }
+        ] } });
+        assert_eq!(default_agent_name(&root), "home");
+        let first = serde_json::json!({ "agents": { "list": [{ "id": "work" }] } });
+        assert_eq!(default_agent_name(&first), "work");
+    }
+
+    #[test]
+    fn test_apply_provider_changes() {
+        let mut entry = serde_json::json!({
+            "baseUrl": "http://127.0.0.1:11434/v1",
+            "apiKey": "ollama",
+            "headers": { "X-Custom": "1" }
+        })
+        .as_object()
+        .cloned()
+        .unwrap();
+        let changes = AgentProviderChanges {
+            base_url: Some("http://10.0.0.2:11434/v1".to_string()),
+            api_key: Some(String::new()),
+            models: Some(vec![serde_json::json!({ "id": "qwen3:8b" })]),
+            ..Default::default()
+        };
+        apply_provider_changes(&mut entry, &changes).unwrap();
+        assert_eq!(entry["baseUrl"], "http://10.0.0.2:11434/v1");
+        assert!(entry.get("apiKey").is_none());
+        assert_eq!(entry["headers"]["X-Custom"], "1");
+        assert_eq!(entry["models"][0]["id"], "qwen3:8b");
+
+        let bad_url = AgentProviderChanges {
+            base_url: Some("localhost:1234".to_string()),
+            ..Default::default()
+        };
+        assert!(apply_provider_changes(&mut entry, &bad_url).is_err());
+        let bad_models = AgentProviderChanges {
+            models: Some(vec![serde_json::json!({ "name": "x" })]),
+            ..Default::default()
+        };
+        assert!(apply_provider_changes(&mut entry, &bad_models).is_err());
+    }
+
+    #[test]
+    fn test_api_key_statuses() {
+        let openclaw = serde_json::json!({
+            "anthropic": { "apiKey": "sk-global" },
+            "openrouter": { "apiKey": "" }
+        });
+        let agent = serde_json::json!({
+            "anthropic": { "apiKey": "sk-global" },
+            "openrouter": { "apiKey": "sk-agent" },
+            "ollama": {}
+        })
+        .as_object()
+        .cloned()
+        .unwrap();
+        let statuses = api_key_statuses(&agent, &openclaw);
+        let sources: Vec<(&str, ApiKeySource, bool)> = statuses
+            .iter()
+            .map(|s| (s.provider.as_str(), s.source, s.global_key_set))
+            .collect();
+        assert_eq!(
+            sources,
+            vec![
+                ("anthropic", ApiKeySource::Inherited, true),
+                ("ollama", ApiKeySource::Unset, false),
+                ("openrouter", ApiKeySource::Overridden, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_guard_default_agent() {
+        let mut root =
+            serde_json::json!({ "agents": { "list": [{ "id": "main" }, { "id": "dev" }] } });
+        let err = guard_default_agent(&root, "main", "delete it", false).unwrap_err();
+        assert!(err.starts_with(PROTECTED_AGENT_ERROR));
+        assert!(guard_default_agent(&root, "main", "delete it", true).is_ok());
+        assert!(guard_default_agent(&root, "dev", "delete it", false).is_ok());
+
+        rename_list_entry(&mut root, "dev", "research");
+        assert_eq!(root["agents"]["list"][1]["id"], "research");
+    }
+
+    #[test]
+    fn test_rename_agent() {
+        let mut root =
+            serde_json::json!({ "agents": { "list": [{ "id": "dev", "model": "a/b" }] } });
+        rename_list_entry(&mut root, "dev", "research");
+        assert_eq!(
+            root["agents"]["list"][0],
+            serde_json::json!({ "id": "research", "model": "a/b" })
+        );
+        rename_list_entry(&mut root, "missing", "other");
+        assert_eq!(root["agents"]["list"].as_array().unwrap().len(), 1);
+
+        for (old, new) in [("../../.ssh", "x"), ("../backups/x", "x"), ("dev", "../x")] {
+            let err = rename_agent(old, new, true).unwrap_err();
+            assert!(err.starts_with("Agent name"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_agent_model_config() {
+        let mut root = serde_json::json!({ "agents": {
+            "defaults": { "model": { "primary": "anthropic/claude-sonnet-4-5", "fallbacks": ["ollama/qwen3:8b"] } },
+            "list": [{ "id": "dev", "model": "ollama/qwen2.5-coder:7b" }]
+        } });
+        let dev = agent_model_config(&root, "dev");
+        assert_eq!(dev.primary.as_deref(), Some("ollama/qwen2.5-coder:7b"));
+        assert!(dev.fallbacks.is_none());
+        assert_eq!(dev.effective_fallbacks, vec!["ollama/qwen3:8b"]);
+        let main = agent_model_config(&root, "main");
+        assert_eq!(
+            main.effective_primary.as_deref(),
+            Some("anthropic/claude-sonnet-4-5")
+        );
+
+        let updates = AgentModelConfigUpdates {
+            fallbacks: Some(vec![]),
+            ..Default::default()
+        };
+        apply_agent_model_updates(&mut root, "dev", &updates).unwrap();
+        let dev = agent_model_config(&root, "dev");
+        assert_eq!(dev.primary.as_deref(), Some("ollama/qwen2.5-coder:7b"));
+        assert!(dev.effective_fallbacks.is_empty());
+
+        let add = AgentModelConfigUpdates {
+            primary: Some("ollama/llama3.2:3b".to_string()),
+            ..Default::default()
+        };
+        apply_agent_model_updates(&mut root, "research", &add).unwrap();
+        assert_eq!(root["agents"]["list"][1]["id"], "research");
+        let mut fresh = serde_json::json!({});
+        apply_agent_model_updates(&mut fresh, "research", &add).unwrap();
+        assert_eq!(default_agent_name(&fresh), "main");
+        let reset = AgentModelConfigUpdates {
+            reset: true,
+            ..Default::default()
+        };
+        apply_agent_model_updates(&mut root, "dev", &reset).unwrap();
+        assert!(agent_model_config(&root, "dev").primary.is_none());
+        let bad = AgentModelConfigUpdates {
+            primary: Some("qwen3".to_string()),
+            ..Default::default()
+        };
+        assert!(apply_agent_model_updates(&mut root, "dev", &bad).is_err());
+    }
+
+    #[test]
+    fn test_set_agent_model_role() {
+        let mut root = serde_json::json!({ "agents": {
+            "defaults": { "model": { "primary": "a/x", "fallbacks": ["ollama/qwen3:8b"] } },
+            "list": [{ "id": "main" }, { "id": "dev" }]
+        } });
+        set_agent_model_role(&mut root, "dev", "ollama/qwen3:8b", "primary").unwrap();
+        let dev = agent_model_config(&root, "dev");
+        assert_eq!(dev.primary.as_deref(), Some("ollama/qwen3:8b"));
+        assert_eq!(dev.fallbacks, Some(vec![]));
+        set_agent_model_role(&mut root, "dev", "ollama/qwen3:8b", "fallback").unwrap();
+        set_agent_model_role(&mut root, "dev", "ollama/llama3.2:3b", "fallback").unwrap();
+        let dev = agent_model_config(&root, "dev");
+        assert_eq!(dev.effective_fallbacks, vec!["ollama/llama3.2:3b"]);
+        assert_eq!(root["agents"]["defaults"]["model"]["primary"], "a/x");
+        assert!(set_agent_model_role(&mut root, "dev", "a/x", "backup").is_err());
+    }
+
+    #[test]
+    fn test_agent_subagent_limits() {
+        let mut root = serde_json::json!({ "agents": {
+            "defaults": { "subagents": { "maxConcurrent": 4 } },
+            "list": [{ "id": "main" }]
+        } });
+        let main = agent_subagent_limits(&root, "main");
+        assert!(main.overrides.max_concurrent.is_none());
+        assert_eq!(main.effective.max_concurrent, Some(4));
+        assert_eq!(main.effective.max_children_per_agent, Some(5));
+
+        let updates = AgentSubagentLimitUpdates {
+            max_concurrent: Some(16),
+            max_spawn_depth: Some(3),
+            ..Default::default()
+        };
+        apply_subagent_limit_updates(&mut root, "research", &updates).unwrap();
+        let research = agent_subagent_limits(&root, "research");
+        assert_eq!(research.overrides.max_spawn_depth, Some(3));
+        assert_eq!(research.effective.max_concurrent, Some(16));
+        let main = agent_subagent_limits(&root, "main");
+        assert_eq!(main.effective.max_concurrent, Some(4));
+
+        let clear = AgentSubagentLimitUpdates {
+            max_concurrent: Some(0),
+            ..Default::default()
+        };
+        apply_subagent_limit_updates(&mut root, "research", &clear).unwrap();
+        let research = agent_subagent_limits(&root, "research");
+        assert_eq!(research.effective.max_concurrent, Some(4));
+        let reset = AgentSubagentLimitUpdates {
+            reset: true,
+            ..Default::default()
+        };
+        apply_subagent_limit_updates(&mut root, "research", &reset).unwrap();
+        assert!(root["agents"]["list"][1].get("subagents").is_none());
+    }
+
+    #[test]
+    fn test_merge_providers_keeps_dest_api_key() {
+        let mut dest = serde_json::json!({
+            "anthropic": { "apiKey": "dest-key", "api": "old" },
+            "local": { "baseUrl": "http://127.0.0.1:8080/v1" }
+        })
+        .as_object()
+        .cloned()
+        .unwrap();
+        let src = serde_json::json!({
+            "anthropic": { "apiKey": "src-key", "api": "anthropic-messages" },
+            "openrouter": { "apiKey": "or-key" }
+        })
+        .as_object()
+        .cloned()
+        .unwrap();
+        merge_providers(&mut dest, &src, false);
+        assert_eq!(dest["anthropic"]["apiKey"], "dest-key");
+        assert_eq!(dest["anthropic"]["api"], "anthropic-messages");
+        assert_eq!(dest["openrouter"]["apiKey"], "or-key");
+        assert!(dest.contains_key("local"));
+        merge_providers(&mut dest, &src, true);
+        assert!(!dest.contains_key("local"));
+    }
+
+    #[test]
+    fn test_sync_status_field_diffs() {
+        let openclaw = serde_json::json!({
+            "ollama": { "baseUrl": "http://127.0.0.1:11434/v1", "api": "openai-completions",
+                        "models": [{ "id": "a" }, { "id": "b" }] },
+            "anthropic": { "apiKey": "k1" }
+        });
+        let agent = serde_json::json!({ "providers": {
+            "ollama": { "baseUrl": "http://10.0.0.5:11434/v1", "api": "openai-completions",
+                        "models": [{ "id": "b", "name": "B" }, { "id": "a" }] },
+            "anthropic": { "apiKey": "k2" }
+        } });
+        let status = sync_status(&openclaw, Some(&agent));
+        assert!(!status.in_sync);
+        assert_eq!(status.field_differences.len(), 1);
+        assert_eq!(status.field_differences[0].field, "baseUrl");
+        assert_eq!(
+            status.suggested_direction,
+            Some(SyncDirection::OpenclawToAgent)
+        );
+
+        let same = serde_json::json!({ "providers": openclaw.clone() });
+        assert!(sync_status(&openclaw, Some(&same)).in_sync);
+        let missing = sync_status(&openclaw, None);
+        assert_eq!(missing.missing_in_agent, vec!["anthropic", "ollama"]);
+    }
+
+    #[test]
+    fn test_check_models_json() {
+        assert_eq!(check_models_json(None).0, AgentHealth::MissingModelsJson);
+        assert_eq!(check_models_json(Some("{oops")).0, AgentHealth::InvalidJson);
+        assert_eq!(check_models_json(Some("[]")).0, AgentHealth::WrongStructure);
+        assert_eq!(
+            check_models_json(Some(r#"{"providers":[]}"#)).0,
+            AgentHealth::WrongStructure
+        );
+        let (health, problem) = check_models_json(Some(r#"{"providers":{"x":1}}"#));
+        assert_eq!(health, AgentHealth::WrongStructure);
+        assert!(problem.unwrap().contains("\"x\""));
+        assert_eq!(
+            check_models_json(Some(r#"{"providers":{"x":{}}}"#)),
+            (AgentHealth::Ok, None)
+        );
+    }
+
+    #[test]
+    fn test_clone_filters() {
+        let mut root = serde_json::json!({ "providers": {
+            "anthropic": { "apiKey": "sk-x", "api": "anthropic-messages" }
+        } });
+        strip_provider_api_keys(&mut root);
+        assert!(root["providers"]["anthropic"].get("apiKey").is_none());
+        assert_eq!(root["providers"]["anthropic"]["api"], "anthropic-messages");
+
+        let all = CloneAgentOptions {
+            strip_api_keys: true,
+            strip_state: true,
+        };
+        assert!(skip_in_clone(Path::new("agent/auth-profiles.json"), &all));
+        assert!(skip_in_clone(Path::new("sessions/abc.jsonl"), &all));
+        assert!(!skip_in_clone(Path::new("agent/models.json"), &all));
+        assert!(!skip_in_clone(
+            Path::new("sessions"),
+            &CloneAgentOptions::default()
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_skips_symlinks() {
+        let base = std::env::temp_dir().join(format!("openclaw-clone-{}", std::process::id()));
+        let (src, dst) = (base.join("src"), base.join("dst"));
+        fs::create_dir_all(src.join("workspace")).unwrap();
+        fs::write(src.join("workspace/notes.md"), "hi").unwrap();
+        std::os::unix::fs::symlink(&src, src.join("workspace/loop")).unwrap();
+        copy_agent_dir(&src, &dst, &CloneAgentOptions::default()).unwrap();
+        assert!(dst.join("workspace/notes.md").is_file());
+        assert!(fs::symlink_metadata(dst.join("workspace/loop")).is_err());
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn test_seed_models_json() {
+        let providers = serde_json::json!({
+            "ollama": { "baseUrl": "http://127.0.0.1:11434/v1", "models": [] },
+            "anthropic": { "apiKey": "sk-x", "models": [] }
+        });
+        let all = seed_models_json(&providers, None).unwrap();
+        assert_eq!(all["providers"].as_object().unwrap().len(), 2);
+        let only = seed_models_json(&providers, Some(&["ollama".to_string()])).unwrap();
+        assert!(only["providers"].get("anthropic").is_none());
+        assert!(seed_models_json(&providers, Some(&["vllm".to_string()])).is_err());
+    }
 }
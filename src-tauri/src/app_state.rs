@@ -0,0 +1,174 @@
+//! Managed cache shared by commands so repeated calls don't re-read and re-parse the same files:
+//! openclaw.json keyed by its modification time and size, plus local runtime detection and the
+//! model catalog, which expire after a short TTL (runtimes start and stop without touching any
+//! file) and are dropped by the model-directory watcher or a config change.
+
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::catalog::{self, CatalogModel, CatalogQuery};
+use crate::detection::{self, LocalLLMDetection};
+use crate::openclaw_config::{self, OpenClawConfigView};
+use crate::pagination::{self, Page};
+use crate::settings;
+
+/// How long detection and catalog results are reused.
+pub const CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Identifies one version of a file; None fields when the file is missing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct FileStamp {
+    modified: Option<SystemTime>,
+    len: Option<u64>,
+}
+
+fn file_stamp(path: &Path) -> FileStamp {
+    fs::metadata(path)
+        .map(|m| FileStamp {
+            modified: m.modified().ok(),
+            len: Some(m.len()),
+        })
+        .unwrap_or_default()
+}
+
+/// True while a value fetched at `fetched` is younger than `ttl` at `now`.
+fn is_fresh(fetched: Instant, now: Instant, ttl: Duration) -> bool {
+    now.saturating_duration_since(fetched) < ttl
+}
+
+struct CachedConfig {
+    stamp: FileStamp,
+    root: Result<Value, String>,
+}
+
+struct Timed<T> {
+    value: T,
+    fetched: Instant,
+}
+
+fn cached<T: Clone>(slot: &Mutex<Option<Timed<T>>>, fetch: impl FnOnce() -> T) -> T {
+    let mut guard = slot.lock().unwrap_or_else(|e| e.into_inner());
+    match guard.as_ref() {
+        Some(t) if is_fresh(t.fetched, Instant::now(), CACHE_TTL) => t.value.clone(),
+        _ => {
+            let value = fetch();
+            *guard = Some(Timed {
+                value: value.clone(),
+                fetched: Instant::now(),
+            });
+            value
+        }
+    }
+}
+
+fn clear<T>(slot: &Mutex<Option<T>>) {
+    *slot.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+#[derive(Default)]
+pub struct AppState {
+    config: Mutex<Option<CachedConfig>>,
+    detection: Mutex<Option<Timed<LocalLLMDetection>>>,
+    /// The whole catalog; queries are applied on top.
+    catalog: Mutex<Option<Timed<Vec<CatalogModel>>>>,
+}
+
+impl AppState {
+    /// openclaw.json as a Value, re-read only when the file changed. A change also drops the
+    /// catalog, which depends on the configured providers.
+    pub fn config_root(&self) -> Result<Value, String> {
+        let stamp = file_stamp(&openclaw_config::openclaw_config_path());
+        let mut guard = self.config.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(c) = guard.as_ref().filter(|c| c.stamp == stamp) {
+            return c.root.clone();
+        }
+        if guard.is_some() {
+            clear(&self.catalog);
+        }
+        let root = openclaw_config::read_config_root();
+        *guard = Some(CachedConfig {
+            stamp,
+            root: root.clone(),
+        });
+        root
+    }
+
+    /// The config panel's view of openclaw.json; defaults when the file is missing or invalid.
+    #[must_use]
+    pub fn config_view(&self) -> OpenClawConfigView {
+        self.config_root()
+            .ok()
+            .and_then(|root| openclaw_config::config_view_from_root(&root).ok())
+            .unwrap_or_else(openclaw_config::default_view)
+    }
+
+    #[must_use]
+    pub fn detection(&self) -> LocalLLMDetection {
+        cached(&self.detection, detection::detect_local_llms)
+    }
+
+    #[must_use]
+    pub fn catalog(&self, query: &CatalogQuery) -> Vec<CatalogModel> {
+        // Keep the config stamp current so an edited openclaw.json drops the catalog first.
+        let _ = self.config_root();
+        let mut all = cached(&self.catalog, || {
+            catalog::get_model_catalog(&CatalogQuery::default())
+        });
+        // Pins change without invalidating the cache.
+        catalog::mark_pinned(&mut all, &settings::get_app_settings().pinned_models);
+        catalog::apply_query(all, query)
+    }
+
+    /// One page of `catalog(query)` by `query.offset` and `query.limit`.
+    #[must_use]
+    pub fn catalog_page(&self, query: &CatalogQuery) -> Page<CatalogModel> {
+        pagination::paginate(self.catalog(query), query.offset, query.limit)
+    }
+
+    /// Drops detection and catalog results; for the model-directory watcher and after pulls or
+    /// deletes.
+    pub fn invalidate_models(&self) {
+        clear(&self.detection);
+        clear(&self.catalog);
+    }
+
+    /// Drops everything, e.g. for a manual refresh.
+    pub fn invalidate_all(&self) {
+        clear(&self.config);
+        self.invalidate_models();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_fresh() {
+        let fetched = Instant::now();
+        let ttl = Duration::from_secs(30);
+        assert!(is_fresh(fetched, fetched + Duration::from_secs(29), ttl));
+        assert!(!is_fresh(fetched, fetched + ttl, ttl));
+        assert!(is_fresh(fetched + ttl, fetched, ttl));
+    }
+
+    #[test]
+    fn test_config_view_no_panic() {
+        let view = AppState::default().config_view();
+        assert!(view.provider_names.len() <= 100);
+        assert!(view.models.len() <= 500);
+        assert!(view
+            .subagents
+            .max_spawn_depth
+            .map(|d| (1..=5).contains(&d))
+            .unwrap_or(true));
+        assert!(view
+            .subagents
+            .max_children_per_agent
+            .map(|c| (1..=20).contains(&c))
+            .unwrap_or(true));
+    }
+}
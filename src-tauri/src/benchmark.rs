@@ -0,0 +1,304 @@
+//! Model benchmarking against local runtimes (Ollama native API, OpenAI-compatible LM Studio / vLLM).
+//! Results are appended to ~/.openclaw/host-config/benchmarks.json so models can be compared over time.
+//! Response parsing and rate math are separated for unit tests.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::catalog::{RUNTIME_LM_STUDIO, RUNTIME_OLLAMA, RUNTIME_VLLM};
+use crate::ollama;
+use crate::openclaw_config;
+use crate::operations::{self, CancelToken};
+
+/// Standardized prompt so results are comparable between models and machines.
+pub const BENCHMARK_PROMPT: &str =
+    "Explain in one paragraph what a large language model is and how it generates text.";
+const BENCHMARK_MAX_TOKENS: u32 = 128;
+const HISTORY_LIMIT: usize = 200;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct BenchmarkResult {
+    pub runtime: String,
+    pub model: String,
+    /// Unix seconds.
+    pub timestamp: u64,
+    /// Model load time reported by the runtime (Ollama only).
+    pub load_ms: Option<f64>,
+    pub prompt_tokens: Option<u64>,
+    /// Prompt processing rate (tokens/sec).
+    pub prompt_tps: Option<f64>,
+    pub generated_tokens: Option<u64>,
+    /// Generation rate (tokens/sec).
+    pub generation_tps: Option<f64>,
+    /// Wall-clock time from request to last token.
+    pub total_ms: f64,
+    /// Time to first token (OpenAI-compatible runtimes).
+    pub time_to_first_token_ms: Option<f64>,
+}
+
+fn rate(tokens: Option<u64>, ms: Option<f64>) -> Option<f64> {
+    match (tokens, ms) {
+        (Some(t), Some(ms)) if ms > 0.0 && t > 0 => Some(t as f64 / (ms / 1000.0)),
+        _ => None,
+    }
+}
+
+/// Builds a result from Ollama's /api/generate response (or the final `done` line when streamed),
+/// whose durations are nanoseconds.
+#[must_use]
+pub fn parse_ollama_generate_stats(
+    body: &str,
+    model: &str,
+    total_ms: f64,
+) -> Option<BenchmarkResult> {
+    let v: serde_json::Value = serde_json::from_str(body).ok()?;
+    let ns_to_ms = |key: &str| v.get(key).and_then(|d| d.as_u64()).map(|n| n as f64 / 1e6);
+    let prompt_tokens = v.get("prompt_eval_count").and_then(|n| n.as_u64());
+    let generated_tokens = v.get("eval_count").and_then(|n| n.as_u64());
+    Some(BenchmarkResult {
+        runtime: RUNTIME_OLLAMA.to_string(),
+        model: model.to_string(),
+        timestamp: operations::now_secs(),
+        load_ms: ns_to_ms("load_duration"),
+        prompt_tokens,
+        prompt_tps: rate(prompt_tokens, ns_to_ms("prompt_eval_duration")),
+        generated_tokens,
+        generation_tps: rate(generated_tokens, ns_to_ms("eval_duration")),
+        total_ms,
+        time_to_first_token_ms: None,
+    })
+}
+
+/// What one SSE `data:` line of a streamed chat completion contributes.
+#[derive(Debug, Default, PartialEq)]
+pub struct SseChunk {
+    pub has_content: bool,
+    pub usage: Option<(u64, u64)>,
+    pub done: bool,
+}
+
+/// Parses one line of an OpenAI-style SSE stream.
+#[must_use]
+pub fn parse_sse_line(line: &str) -> Option<SseChunk> {
+    let data = line.trim().strip_prefix("data:")?.trim();
+    if data == "[DONE]" {
+        return Some(SseChunk {
+            done: true,
+            ..Default::default()
+        });
+    }
+    let v: serde_json::Value = serde_json::from_str(data).ok()?;
+    let has_content = v
+        .get("choices")
+        .and_then(|c| c.as_array())
+        .and_then(|c| c.first())
+        .and_then(|c| c.get("delta"))
+        .and_then(|d| d.get("content"))
+        .and_then(|c| c.as_str())
+        .is_some_and(|s| !s.is_empty());
+    let usage = v.get("usage").and_then(|u| {
+        Some((
+            u.get("prompt_tokens")?.as_u64()?,
+            u.get("completion_tokens")?.as_u64()?,
+        ))
+    });
+    Some(SseChunk {
+        has_content,
+        usage,
+        done: false,
+    })
+}
+
+fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .timeout_connect(Duration::from_secs(5))
+        .timeout_read(Duration::from_secs(600))
+        .build()
+}
+
+/// Streams so a cancel takes effect between tokens; the stats arrive on the final line.
+fn benchmark_ollama(model: &str, cancel: &CancelToken) -> Result<BenchmarkResult, String> {
+    let started = Instant::now();
+    let resp = agent()
+        .post(&format!("{}/api/generate", ollama::ollama_base_url()))
+        .send_json(serde_json::json!({
+            "model": model,
+            "prompt": BENCHMARK_PROMPT,
+            "stream": true,
+            "options": { "temperature": 0, "num_predict": BENCHMARK_MAX_TOKENS },
+        }))
+        .map_err(|e| format!("Ollama request failed: {}", e))?;
+    let mut last = String::new();
+    for line in BufReader::new(resp.into_reader()).lines() {
+        cancel.check()?;
+        let line = line.map_err(|e| e.to_string())?;
+        if !line.trim().is_empty() {
+            last = line;
+        }
+    }
+    let total_ms = started.elapsed().as_secs_f64() * 1000.0;
+    parse_ollama_generate_stats(&last, model, total_ms)
+        .ok_or_else(|| "Unexpected response from Ollama".to_string())
+}
+
+/// Streams a chat completion and derives rates from time-to-first-token and usage.
+/// Falls back to counting content chunks when the server doesn't report usage.
+fn benchmark_openai_compatible(
+    runtime: &str,
+    model: &str,
+    cancel: &CancelToken,
+) -> Result<BenchmarkResult, String> {
+    let base = openclaw_config::default_local_base_url(runtime)
+        .ok_or_else(|| format!("Unknown runtime: {}", runtime))?;
+    let started = Instant::now();
+    let resp = agent()
+        .post(&format!("{}/chat/completions", base))
+        .send_json(serde_json::json!({
+            "model": model,
+            "messages": [{ "role": "user", "content": BENCHMARK_PROMPT }],
+            "max_tokens": BENCHMARK_MAX_TOKENS,
+            "temperature": 0,
+            "stream": true,
+            "stream_options": { "include_usage": true },
+        }))
+        .map_err(|e| format!("{} request failed: {}", runtime, e))?;
+
+    let mut first_token: Option<Duration> = None;
+    let mut chunks: u64 = 0;
+    let mut usage = None;
+    for line in BufReader::new(resp.into_reader()).lines() {
+        cancel.check()?;
+        let line = line.map_err(|e| e.to_string())?;
+        let chunk = match parse_sse_line(&line) {
+            Some(c) => c,
+            None => continue,
+        };
+        if chunk.done {
+            break;
+        }
+        if chunk.has_content {
+            chunks += 1;
+            first_token.get_or_insert_with(|| started.elapsed());
+        }
+        usage = chunk.usage.or(usage);
+    }
+    let total_ms = started.elapsed().as_secs_f64() * 1000.0;
+    let ttft_ms = first_token.map(|d| d.as_secs_f64() * 1000.0);
+    let (prompt_tokens, generated_tokens) = match usage {
+        Some((p, c)) => (Some(p), Some(c)),
+        None => (None, Some(chunks).filter(|c| *c > 0)),
+    };
+    Ok(BenchmarkResult {
+        runtime: runtime.to_string(),
+        model: model.to_string(),
+        timestamp: operations::now_secs(),
+        load_ms: None,
+        prompt_tokens,
+        prompt_tps: rate(prompt_tokens, ttft_ms),
+        generated_tokens,
+        generation_tps: rate(generated_tokens, ttft_ms.map(|t| total_ms - t)),
+        total_ms,
+        time_to_first_token_ms: ttft_ms,
+    })
+}
+
+fn history_path() -> PathBuf {
+    openclaw_config::host_config_dir().join("benchmarks.json")
+}
+
+/// Reads stored benchmark results, newest last. Optionally filtered by runtime and/or model.
+#[must_use]
+pub fn get_benchmark_history(runtime: Option<&str>, model: Option<&str>) -> Vec<BenchmarkResult> {
+    let all: Vec<BenchmarkResult> = fs::read_to_string(history_path())
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default();
+    all.into_iter()
+        .filter(|r| runtime.is_none_or(|rt| r.runtime == rt))
+        .filter(|r| model.is_none_or(|m| r.model == m))
+        .collect()
+}
+
+fn append_history(result: &BenchmarkResult) -> Result<(), String> {
+    let mut all = get_benchmark_history(None, None);
+    all.push(result.clone());
+    if all.len() > HISTORY_LIMIT {
+        all.drain(..all.len() - HISTORY_LIMIT);
+    }
+    let path = history_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    fs::write(
+        &path,
+        serde_json::to_string_pretty(&all).map_err(|e| e.to_string())?,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Runs the standard prompt against `model` on `runtime` ("ollama", "lmstudio", "vllm") and stores the result.
+/// A cancelled run stores nothing.
+pub fn benchmark_model(
+    runtime: &str,
+    model: &str,
+    cancel: &CancelToken,
+) -> Result<BenchmarkResult, String> {
+    if model.trim().is_empty() {
+        return Err("Model is required".to_string());
+    }
+    let result = match runtime {
+        RUNTIME_OLLAMA => benchmark_ollama(model, cancel)?,
+        RUNTIME_LM_STUDIO | RUNTIME_VLLM => benchmark_openai_compatible(runtime, model, cancel)?,
+        other => return Err(format!("Unsupported runtime: {}", other)),
+    };
+    append_history(&result)?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ollama_generate_stats() {
+        let body = r#"{
+            "model":"llama3.2:3b","response":"...","done":true,
+            "load_duration":1500000000,
+            "prompt_eval_count":20,"prompt_eval_duration":100000000,
+            "eval_count":128,"eval_duration":2000000000
+        }"#;
+        let r = parse_ollama_generate_stats(body, "llama3.2:3b", 3700.0).unwrap();
+        assert_eq!(r.load_ms, Some(1500.0));
+        assert_eq!(r.prompt_tps, Some(200.0));
+        assert_eq!(r.generation_tps, Some(64.0));
+        assert_eq!(r.generated_tokens, Some(128));
+        assert!(parse_ollama_generate_stats("nope", "m", 1.0).is_none());
+    }
+
+    #[test]
+    fn test_parse_sse_line() {
+        let content = r#"data: {"choices":[{"delta":{"content":"Hi"}}]}"#;
+        assert!(parse_sse_line(content).unwrap().has_content);
+        let usage = r#"data: {"choices":[],"usage":{"prompt_tokens":21,"completion_tokens":128}}"#;
+        assert_eq!(parse_sse_line(usage).unwrap().usage, Some((21, 128)));
+        assert!(parse_sse_line("data: [DONE]").unwrap().done);
+        assert!(parse_sse_line(": keep-alive").is_none());
+    }
+
+    #[test]
+    fn test_rate() {
+        assert_eq!(rate(Some(100), Some(2000.0)), Some(50.0));
+        assert_eq!(rate(Some(0), Some(2000.0)), None);
+        assert_eq!(rate(Some(10), None), None);
+    }
+
+    #[test]
+    fn test_benchmark_model_validates_input() {
+        let cancel = CancelToken::default();
+        assert!(benchmark_model("ollama", " ", &cancel).is_err());
+        assert!(benchmark_model("unknown", "m", &cancel).is_err());
+    }
+}
@@ -0,0 +1,212 @@
+//! Headless companion to the desktop app, for scripts and SSH sessions where the GUI can't run.
+//! Runs the same status, sync and detection logic and prints text, or JSON with `--json`.
+
+use clap::{Parser, Subcommand};
+use openclaw_host_config::{agents, detection, doctor, gateway, openclaw_config};
+use serde::Serialize;
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(
+    name = "openclaw-host-config",
+    version,
+    about = "Inspect and sync an OpenClaw host"
+)]
+struct Cli {
+    /// Print JSON instead of text.
+    #[arg(long, global = true)]
+    json: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Gateway state and each agent's provider sync status.
+    Status,
+    /// Copy openclaw.json's providers into agents' models.json.
+    Sync {
+        /// Agent to sync; all agents when omitted.
+        agent: Option<String>,
+        /// Also overwrite the default agent.
+        #[arg(long)]
+        force: bool,
+    },
+    /// Installed and running local runtimes (Ollama, LM Studio, vLLM).
+    Detect,
+    /// End-to-end health check with suggested fixes.
+    Doctor,
+}
+
+#[derive(Serialize)]
+struct Status {
+    openclaw_config_path: String,
+    gateway_running: bool,
+    gateway_error: Option<String>,
+    agents: Vec<AgentSync>,
+}
+
+#[derive(Serialize)]
+struct AgentSync {
+    name: String,
+    #[serde(flatten)]
+    status: agents::ProviderSyncStatus,
+}
+
+#[derive(Serialize)]
+struct SyncOutcome {
+    agent: String,
+    synced: bool,
+    /// Set when the agent is the default one and `--force` wasn't given.
+    skipped: bool,
+    error: Option<String>,
+}
+
+fn status() -> Status {
+    let gateway = gateway::gateway_running();
+    Status {
+        openclaw_config_path: openclaw_config::openclaw_config_path()
+            .display()
+            .to_string(),
+        gateway_running: matches!(gateway, Ok(true)),
+        gateway_error: gateway.err(),
+        agents: agents::list_agent_names()
+            .into_iter()
+            .map(|name| AgentSync {
+                status: agents::get_provider_sync_status(&name),
+                name,
+            })
+            .collect(),
+    }
+}
+
+fn sync_agent(name: String, force: bool) -> SyncOutcome {
+    match agents::update_agent_providers_from_openclaw(&name, force) {
+        Ok(()) => SyncOutcome {
+            agent: name,
+            synced: true,
+            skipped: false,
+            error: None,
+        },
+        Err(e) => SyncOutcome {
+            agent: name,
+            synced: false,
+            skipped: e.starts_with(agents::PROTECTED_AGENT_ERROR),
+            error: Some(e),
+        },
+    }
+}
+
+fn sync(agent: Option<String>, force: bool) -> Vec<SyncOutcome> {
+    agent
+        .map_or_else(agents::list_agent_names, |a| vec![a])
+        .into_iter()
+        .map(|name| sync_agent(name, force))
+        .collect()
+}
+
+fn print_json(value: &impl Serialize) {
+    match serde_json::to_string_pretty(value) {
+        Ok(s) => println!("{}", s),
+        Err(e) => eprintln!("{}", e),
+    }
+}
+
+fn print_runtime(name: &str, status: &detection::LLMStatus) {
+    let state = match (status.installed, status.running) {
+        (_, true) => "running",
+        (true, false) => "installed",
+        (false, false) => "not found",
+    };
+    let version = status.version.as_deref().unwrap_or("");
+    println!("{:<10} {:<10} {}", name, state, version);
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Status => {
+            let status = status();
+            if cli.json {
+                print_json(&status);
+            } else {
+                println!("openclaw.json: {}", status.openclaw_config_path);
+                match &status.gateway_error {
+                    Some(e) => println!("gateway:       unknown ({})", e),
+                    None if status.gateway_running => println!("gateway:       running"),
+                    None => println!("gateway:       stopped"),
+                }
+                for agent in &status.agents {
+                    let s = &agent.status;
+                    if s.in_sync {
+                        println!("agent {}: in sync", agent.name);
+                    } else {
+                        println!(
+                            "agent {}: out of sync (missing: [{}], extra: [{}], changed: {})",
+                            agent.name,
+                            s.missing_in_agent.join(", "),
+                            s.extra_in_agent.join(", "),
+                            s.field_differences.len()
+                        );
+                    }
+                }
+            }
+            ExitCode::SUCCESS
+        }
+        Command::Sync { agent, force } => {
+            let outcomes = sync(agent, force);
+            if cli.json {
+                print_json(&outcomes);
+            } else {
+                for o in &outcomes {
+                    match &o.error {
+                        None => println!("{}: synced", o.agent),
+                        Some(_) if o.skipped => {
+                            println!("{}: skipped (default agent, use --force)", o.agent)
+                        }
+                        Some(e) => println!("{}: failed: {}", o.agent, e),
+                    }
+                }
+            }
+            if outcomes.iter().any(|o| o.error.is_some() && !o.skipped) {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+        Command::Detect => {
+            let detected = detection::detect_local_llms();
+            if cli.json {
+                print_json(&detected);
+            } else {
+                print_runtime("ollama", &detected.ollama);
+                print_runtime("lm-studio", &detected.lm_studio);
+                print_runtime("vllm", &detected.vllm);
+            }
+            ExitCode::SUCCESS
+        }
+        Command::Doctor => {
+            let report = doctor::run_doctor();
+            if cli.json {
+                print_json(&report);
+            } else {
+                for c in &report.checks {
+                    let status = match c.status {
+                        doctor::CheckStatus::Pass => "ok",
+                        doctor::CheckStatus::Warn => "warn",
+                        doctor::CheckStatus::Fail => "FAIL",
+                    };
+                    println!("[{:<4}] {}", status, c.message);
+                    if let Some(fix) = &c.fix {
+                        println!("       fix: {}", fix);
+                    }
+                }
+            }
+            if report.status == doctor::CheckStatus::Fail {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+    }
+}
@@ -0,0 +1,784 @@
+//! Unified model catalog: one list across Ollama, LM Studio, vLLM and the remote providers in openclaw.json.
+//! Each entry carries provenance (runtime, openclaw provider) so the UI doesn't stitch per-runtime lists.
+//! Parsing and filtering are pure functions for unit tests.
+
+use serde::{Deserialize, Serialize};
+
+use crate::model_limits::{self, ModelLimits};
+use crate::models_available;
+use crate::openclaw_config;
+use crate::settings;
+
+pub const RUNTIME_OLLAMA: &str = "ollama";
+pub const RUNTIME_LM_STUDIO: &str = "lmstudio";
+pub const RUNTIME_VLLM: &str = "vllm";
+pub const RUNTIME_REMOTE: &str = "remote";
+
+/// One model in the catalog.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CatalogModel {
+    /// openclaw-style id: "<provider>/<name>" (falls back to the runtime name when no provider maps).
+    pub id: String,
+    /// Model name as the runtime or provider knows it.
+    pub name: String,
+    /// "ollama", "lmstudio", "vllm" or "remote".
+    pub runtime: String,
+    /// openclaw.json models.providers key serving this model, if any.
+    pub provider: Option<String>,
+    pub size_bytes: Option<u64>,
+    pub context_length: Option<u64>,
+    pub parameter_size: Option<String>,
+    pub quantization: Option<String>,
+    /// Content digest (Ollama); tags copied from one another share it until one is re-pulled.
+    pub digest: Option<String>,
+    /// Whether the model is currently loaded in memory; None when the runtime can't tell.
+    pub loaded: Option<bool>,
+    pub kind: ModelKind,
+    /// Context window / max output tokens, from the runtime or config when reported, else the built-in registry.
+    pub limits: Option<ModelLimits>,
+    /// In the pinned models of the app settings; pinned models sort first.
+    pub pinned: bool,
+}
+
+/// What a model is for. openclaw's memory search needs an embedding model; agents need a chat model.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelKind {
+    #[default]
+    Chat,
+    Embedding,
+}
+
+/// Architectures/families that only produce embeddings (as reported in Ollama details or GGUF metadata).
+const EMBEDDING_FAMILIES: &[&str] = &["bert", "nomic-bert", "jina-bert-v2", "xlm-roberta", "gte"];
+/// Name fragments of common embedding models.
+const EMBEDDING_NAME_HINTS: &[&str] = &[
+    "embed",
+    "bge-",
+    "/bge",
+    "e5-",
+    "minilm",
+    "gte-",
+    "-gte",
+    "paraphrase-",
+    "sentence-t5",
+];
+
+/// Classifies a model from its name and, when known, its architecture family.
+#[must_use]
+pub fn classify_model_kind(name: &str, family: Option<&str>) -> ModelKind {
+    let name = name.to_lowercase();
+    let family_is_embedding =
+        family.is_some_and(|f| EMBEDDING_FAMILIES.contains(&f.to_lowercase().as_str()));
+    if family_is_embedding
+        || EMBEDDING_NAME_HINTS.iter().any(|h| name.contains(h))
+        || name.starts_with("bge")
+    {
+        ModelKind::Embedding
+    } else {
+        ModelKind::Chat
+    }
+}
+
+impl CatalogModel {
+    pub(crate) fn local(runtime: &str, name: &str) -> Self {
+        Self {
+            id: format!("{}/{}", runtime, name),
+            name: name.to_string(),
+            runtime: runtime.to_string(),
+            provider: None,
+            size_bytes: None,
+            context_length: None,
+            parameter_size: None,
+            quantization: None,
+            digest: None,
+            loaded: None,
+            kind: classify_model_kind(name, None),
+            limits: None,
+            pinned: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CatalogSort {
+    #[default]
+    Name,
+    Size,
+    Runtime,
+    ContextLength,
+}
+
+/// Filtering/sorting parameters for `get_model_catalog`. All fields optional.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CatalogQuery {
+    /// Only include this runtime ("ollama", "lmstudio", "vllm", "remote").
+    pub runtime: Option<String>,
+    /// Only include models served by this openclaw provider.
+    pub provider: Option<String>,
+    /// Case-insensitive substring match on id, name and provider.
+    pub search: Option<String>,
+    pub loaded_only: bool,
+    /// Only include chat or embedding models.
+    pub kind: Option<ModelKind>,
+    pub sort_by: CatalogSort,
+    pub descending: bool,
+    /// Paging of the catalog commands; `apply_query` itself returns every match.
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+/// Parses Ollama /api/tags into catalog entries (name, size, parameter size, quantization).
+#[must_use]
+pub fn parse_ollama_tags(body: &str) -> Vec<CatalogModel> {
+    models_available::parse_ollama_tags_models(body)
+        .into_iter()
+        .map(|m| {
+            let mut entry = CatalogModel::local(RUNTIME_OLLAMA, &m.name);
+            entry.size_bytes = m.size_bytes;
+            entry.digest = m.digest;
+            entry.parameter_size = m.parameter_size;
+            entry.quantization = m.quantization;
+            entry.kind = classify_model_kind(&m.name, m.family.as_deref());
+            entry.loaded = Some(false);
+            entry
+        })
+        .collect()
+}
+
+/// Parses Ollama /api/ps into (name, context_length) for loaded models.
+#[must_use]
+pub fn parse_ollama_ps(body: &str) -> Vec<(String, Option<u64>)> {
+    let root: serde_json::Value = match serde_json::from_str(body) {
+        Ok(v) => v,
+        Err(_) => return vec![],
+    };
+    root.get("models")
+        .and_then(|m| m.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|m| {
+                    let name = m.get("name").and_then(|v| v.as_str())?;
+                    let ctx = m.get("context_length").and_then(|v| v.as_u64());
+                    Some((name.to_string(), ctx))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parses LM Studio's REST /api/v0/models (id, state, quantization, max_context_length).
+#[must_use]
+pub fn parse_lm_studio_api_models(body: &str) -> Vec<CatalogModel> {
+    let root: serde_json::Value = match serde_json::from_str(body) {
+        Ok(v) => v,
+        Err(_) => return vec![],
+    };
+    root.get("data")
+        .and_then(|d| d.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|m| {
+                    let id = m
+                        .get("id")
+                        .and_then(|v| v.as_str())
+                        .filter(|s| !s.is_empty())?;
+                    let mut entry = CatalogModel::local(RUNTIME_LM_STUDIO, id);
+                    entry.context_length = m.get("max_context_length").and_then(|v| v.as_u64());
+                    entry.quantization = m
+                        .get("quantization")
+                        .and_then(|v| v.as_str())
+                        .map(String::from);
+                    entry.loaded = m
+                        .get("state")
+                        .and_then(|v| v.as_str())
+                        .map(|s| s == "loaded");
+                    if m.get("type").and_then(|v| v.as_str()) == Some("embeddings") {
+                        entry.kind = ModelKind::Embedding;
+                    }
+                    Some(entry)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parses an OpenAI-style /v1/models response (as served by vLLM); vLLM models are always loaded.
+#[must_use]
+pub fn parse_openai_models(body: &str, runtime: &str) -> Vec<CatalogModel> {
+    let root: serde_json::Value = match serde_json::from_str(body) {
+        Ok(v) => v,
+        Err(_) => return vec![],
+    };
+    root.get("data")
+        .and_then(|d| d.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|m| {
+                    let id = m
+                        .get("id")
+                        .and_then(|v| v.as_str())
+                        .filter(|s| !s.is_empty())?;
+                    let mut entry = CatalogModel::local(runtime, id);
+                    entry.context_length = m.get("max_model_len").and_then(|v| v.as_u64());
+                    entry.loaded = Some(true);
+                    Some(entry)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Finds the openclaw provider that points at a local runtime, by name or by default port in baseUrl.
+#[must_use]
+pub fn provider_for_runtime(runtime: &str, providers: &serde_json::Value) -> Option<String> {
+    let obj = providers.as_object()?;
+    let (names, port): (&[&str], &str) = match runtime {
+        RUNTIME_OLLAMA => (&["ollama"], ":11434"),
+        RUNTIME_LM_STUDIO => (&["lmstudio", "lm-studio", "lm_studio"], ":1234"),
+        RUNTIME_VLLM => (&["vllm"], ":8000"),
+        _ => return None,
+    };
+    if let Some(name) = obj.keys().find(|k| names.contains(&k.as_str())) {
+        return Some(name.clone());
+    }
+    obj.iter()
+        .find(|(_, v)| {
+            v.get("baseUrl")
+                .and_then(|u| u.as_str())
+                .map(|u| u.contains(port))
+                .unwrap_or(false)
+        })
+        .map(|(k, _)| k.clone())
+}
+
+/// Catalog entries for models configured under non-local providers in openclaw.json.
+#[must_use]
+pub fn remote_models_from_providers(
+    providers: &serde_json::Value,
+    local_providers: &[String],
+) -> Vec<CatalogModel> {
+    let obj = match providers.as_object() {
+        Some(o) => o,
+        None => return vec![],
+    };
+    let mut out = Vec::new();
+    for (provider, val) in obj {
+        if local_providers.contains(provider) {
+            continue;
+        }
+        let models = match val.get("models").and_then(|m| m.as_array()) {
+            Some(m) => m,
+            None => continue,
+        };
+        for m in models {
+            let name = match m.get("id").and_then(|v| v.as_str()).or_else(|| m.as_str()) {
+                Some(n) if !n.is_empty() => n,
+                _ => continue,
+            };
+            out.push(CatalogModel {
+                id: format!("{}/{}", provider, name),
+                name: name.to_string(),
+                runtime: RUNTIME_REMOTE.to_string(),
+                provider: Some(provider.clone()),
+                size_bytes: None,
+                context_length: m.get("contextWindow").and_then(|v| v.as_u64()),
+                parameter_size: None,
+                quantization: None,
+                digest: None,
+                loaded: None,
+                kind: classify_model_kind(name, None),
+                limits: None,
+                pinned: false,
+            });
+        }
+    }
+    out
+}
+
+/// Rewrites ids of local entries to use the openclaw provider name when one maps to the runtime.
+fn assign_provider(models: &mut [CatalogModel], provider: Option<&String>) {
+    if let Some(p) = provider {
+        for m in models.iter_mut() {
+            m.provider = Some(p.clone());
+            m.id = format!("{}/{}", p, m.name);
+        }
+    }
+}
+
+/// Marks the models whose id is in `pinned`.
+pub fn mark_pinned(models: &mut [CatalogModel], pinned: &[String]) {
+    for m in models.iter_mut() {
+        m.pinned = pinned.contains(&m.id);
+    }
+}
+
+/// A model name for a picker, with its pin state.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ModelChoice {
+    pub name: String,
+    pub pinned: bool,
+}
+
+/// Marks the pinned names and moves them to the front, otherwise keeping the order. Names of a
+/// local `runtime` are matched as "<provider>/<name>", with the openclaw provider that points at
+/// the runtime (the runtime name when none does); cloud names are already provider/model ids.
+#[must_use]
+pub fn pinned_first(
+    names: Vec<String>,
+    runtime: Option<&str>,
+    providers: &serde_json::Value,
+    pinned: &[String],
+) -> Vec<ModelChoice> {
+    let prefix =
+        runtime.map(|r| provider_for_runtime(r, providers).unwrap_or_else(|| r.to_string()));
+    let mut choices: Vec<ModelChoice> = names
+        .into_iter()
+        .map(|name| {
+            let id = prefix
+                .as_ref()
+                .map_or_else(|| name.clone(), |p| format!("{}/{}", p, name));
+            ModelChoice {
+                pinned: pinned.contains(&id),
+                name,
+            }
+        })
+        .collect();
+    choices.sort_by_key(|c| !c.pinned);
+    choices
+}
+
+/// `pinned_first` with the providers in openclaw.json and the pins in the app settings.
+#[must_use]
+pub fn model_choices(names: Vec<String>, runtime: Option<&str>) -> Vec<ModelChoice> {
+    let providers = openclaw_config::get_openclaw_providers_raw().unwrap_or(serde_json::json!({}));
+    let pinned = settings::get_app_settings().pinned_models;
+    pinned_first(names, runtime, &providers, &pinned)
+}
+
+/// Applies runtime/provider/search/loaded filters and sorting, pinned models first.
+#[must_use]
+pub fn apply_query(mut models: Vec<CatalogModel>, query: &CatalogQuery) -> Vec<CatalogModel> {
+    let search = query
+        .search
+        .as_ref()
+        .map(|s| s.trim().to_lowercase())
+        .filter(|s| !s.is_empty());
+    models.retain(|m| {
+        query.runtime.as_ref().is_none_or(|r| &m.runtime == r)
+            && query
+                .provider
+                .as_ref()
+                .is_none_or(|p| m.provider.as_ref() == Some(p))
+            && (!query.loaded_only || m.loaded == Some(true))
+            && query.kind.is_none_or(|k| m.kind == k)
+            && search.as_ref().is_none_or(|s| {
+                m.id.to_lowercase().contains(s)
+                    || m.name.to_lowercase().contains(s)
+                    || m.provider
+                        .as_ref()
+                        .is_some_and(|p| p.to_lowercase().contains(s))
+            })
+    });
+    models.sort_by(|a, b| {
+        let ord = match query.sort_by {
+            CatalogSort::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            CatalogSort::Runtime => a.runtime.cmp(&b.runtime).then_with(|| a.name.cmp(&b.name)),
+            CatalogSort::Size => a.size_bytes.cmp(&b.size_bytes),
+            CatalogSort::ContextLength => a.context_length.cmp(&b.context_length),
+        };
+        let ord = if query.descending { ord.reverse() } else { ord };
+        b.pinned.cmp(&a.pinned).then(ord)
+    });
+    models
+}
+
+fn ollama_catalog() -> Vec<CatalogModel> {
+    let mut models = match models_available::http_get_body(&models_available::ollama_tags_url()) {
+        Some(body) => parse_ollama_tags(&body),
+        None => return vec![],
+    };
+    if let Some(body) = models_available::http_get_body(&models_available::ollama_ps_url()) {
+        for (name, ctx) in parse_ollama_ps(&body) {
+            if let Some(m) = models.iter_mut().find(|m| m.name == name) {
+                m.loaded = Some(true);
+                m.context_length = ctx.or(m.context_length);
+            }
+        }
+    }
+    models
+}
+
+fn lm_studio_catalog() -> Vec<CatalogModel> {
+    // The REST API reports load state and context; fall back to `lms ls` when the server is off.
+    if let Some(body) =
+        models_available::http_get_body(&models_available::lm_studio_api_models_url())
+    {
+        let models = parse_lm_studio_api_models(&body);
+        if !models.is_empty() {
+            return models;
+        }
+    }
+    models_available::get_lm_studio_ls_models()
+        .into_iter()
+        .map(|m| {
+            let mut entry = CatalogModel::local(RUNTIME_LM_STUDIO, &m.name);
+            entry.size_bytes = m.size_bytes;
+            entry.parameter_size = m.params;
+            entry.loaded = Some(m.loaded);
+            if m.embedding {
+                entry.kind = ModelKind::Embedding;
+            }
+            entry
+        })
+        .collect()
+}
+
+fn vllm_catalog() -> Vec<CatalogModel> {
+    let url = models_available::vllm_models_url(&models_available::configured_vllm_base_url());
+    models_available::http_get_body(&url)
+        .map(|body| parse_openai_models(&body, RUNTIME_VLLM))
+        .unwrap_or_default()
+}
+
+/// Builds the merged catalog from every runtime plus openclaw.json remote providers, then applies the query.
+#[must_use]
+pub fn get_model_catalog(query: &CatalogQuery) -> Vec<CatalogModel> {
+    let providers = openclaw_config::get_openclaw_providers_raw().unwrap_or(serde_json::json!({}));
+
+    let mut all = Vec::new();
+    let mut local_providers = Vec::new();
+    for (runtime, mut models) in [
+        (RUNTIME_OLLAMA, ollama_catalog()),
+        (RUNTIME_LM_STUDIO, lm_studio_catalog()),
+        (RUNTIME_VLLM, vllm_catalog()),
+    ] {
+        let provider = provider_for_runtime(runtime, &providers);
+        assign_provider(&mut models, provider.as_ref());
+        local_providers.extend(provider);
+        all.extend(models);
+    }
+    all.extend(remote_models_from_providers(&providers, &local_providers));
+    for m in all.iter_mut() {
+        m.limits = if m.runtime == RUNTIME_REMOTE {
+            model_limits::limits_for_model_id(&m.id, &providers)
+        } else {
+            model_limits::limits_for_catalog_model(m)
+        };
+    }
+    mark_pinned(&mut all, &settings::get_app_settings().pinned_models);
+    apply_query(all, query)
+}
+
+/// Lists catalog models of one kind (e.g. embedding models for openclaw's memory search).
+#[must_use]
+pub fn list_models_by_kind(kind: ModelKind) -> Vec<CatalogModel> {
+    get_model_catalog(&CatalogQuery {
+        kind: Some(kind),
+        ..Default::default()
+    })
+}
+
+/// An installed local model expressed as an openclaw provider/model id.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SuggestedModelId {
+    /// "<provider>/<model>", e.g. "ollama/llama3.2:3b".
+    pub id: String,
+    pub provider: String,
+    pub model: String,
+    pub runtime: String,
+    /// Already listed under the provider and in agents.defaults.models.
+    pub configured: bool,
+    pub pinned: bool,
+}
+
+/// Converts local catalog entries into openclaw ids, marking those already present in openclaw.json.
+#[must_use]
+pub fn suggestions_from_catalog(
+    models: &[CatalogModel],
+    root: &serde_json::Value,
+) -> Vec<SuggestedModelId> {
+    let allowlist = root
+        .get("agents")
+        .and_then(|a| a.get("defaults"))
+        .and_then(|d| d.get("models"))
+        .and_then(|m| m.as_object());
+    models
+        .iter()
+        .filter(|m| m.runtime != RUNTIME_REMOTE)
+        .map(|m| {
+            let provider = m.provider.clone().unwrap_or_else(|| m.runtime.clone());
+            let id = format!("{}/{}", provider, m.name);
+            let listed_under_provider = root
+                .get("models")
+                .and_then(|ms| ms.get("providers"))
+                .and_then(|p| p.get(&provider))
+                .and_then(|p| p.get("models"))
+                .and_then(|a| a.as_array())
+                .is_some_and(|a| {
+                    a.iter().any(|e| {
+                        e.get("id").and_then(|v| v.as_str()) == Some(m.name.as_str())
+                            || e.as_str() == Some(m.name.as_str())
+                    })
+                });
+            let configured =
+                listed_under_provider && allowlist.is_some_and(|a| a.contains_key(&id));
+            SuggestedModelId {
+                id,
+                provider,
+                model: m.name.clone(),
+                runtime: m.runtime.clone(),
+                configured,
+                pinned: m.pinned,
+            }
+        })
+        .collect()
+}
+
+/// Lists installed runtime models as openclaw provider/model ids.
+#[must_use]
+pub fn suggest_provider_model_ids() -> Vec<SuggestedModelId> {
+    let root = openclaw_config::read_config_root().unwrap_or(serde_json::json!({}));
+    suggestions_from_catalog(&get_model_catalog(&CatalogQuery::default()), &root)
+}
+
+/// Inserts the given "<provider>/<model>" ids into models.providers and agents.defaults.models
+/// so they become selectable as primary/fallback. Returns the ids that were newly added.
+pub fn add_provider_model_ids(ids: &[String]) -> Result<Vec<String>, String> {
+    let mut root = openclaw_config::read_config_root()?;
+    let mut added = Vec::new();
+    for id in ids {
+        let (provider, model) = id
+            .split_once('/')
+            .filter(|(p, m)| !p.is_empty() && !m.is_empty())
+            .ok_or_else(|| format!("Invalid model id (expected provider/model): {}", id))?;
+        if openclaw_config::register_provider_model(&mut root, provider, model)? {
+            added.push(id.clone());
+        }
+    }
+    if !added.is_empty() {
+        openclaw_config::write_config_root(&root)?;
+    }
+    Ok(added)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ollama_tags_and_ps() {
+        let tags = r#"{"models":[
+            {"name":"llama3.2:3b","size":2019393189,"digest":"a80c4f17acd5","details":{"parameter_size":"3.2B","quantization_level":"Q4_K_M"}},
+            {"name":"nomic-embed-text:latest","size":274302450}
+        ]}"#;
+        let models = parse_ollama_tags(tags);
+        assert_eq!(models.len(), 2);
+        assert_eq!(models[0].id, "ollama/llama3.2:3b");
+        assert_eq!(models[0].size_bytes, Some(2019393189));
+        assert_eq!(models[0].parameter_size.as_deref(), Some("3.2B"));
+        assert_eq!(models[0].quantization.as_deref(), Some("Q4_K_M"));
+        assert_eq!(models[0].loaded, Some(false));
+        assert_eq!(models[0].digest.as_deref(), Some("a80c4f17acd5"));
+        assert_eq!(models[0].kind, ModelKind::Chat);
+        assert_eq!(models[1].kind, ModelKind::Embedding);
+        assert!(parse_ollama_tags("not json").is_empty());
+
+        let ps = r#"{"models":[{"name":"llama3.2:3b","context_length":8192}]}"#;
+        assert_eq!(
+            parse_ollama_ps(ps),
+            vec![("llama3.2:3b".to_string(), Some(8192))]
+        );
+    }
+
+    #[test]
+    fn test_parse_lm_studio_api_models() {
+        let body = r#"{"data":[
+            {"id":"qwen2.5-7b-instruct","state":"loaded","quantization":"Q4_K_M","max_context_length":32768},
+            {"id":"phi-3","state":"not-loaded"},
+            {"id":"text-embedding-granite","type":"embeddings","state":"not-loaded"}
+        ]}"#;
+        let models = parse_lm_studio_api_models(body);
+        assert_eq!(models.len(), 3);
+        assert_eq!(models[2].kind, ModelKind::Embedding);
+        assert_eq!(models[0].loaded, Some(true));
+        assert_eq!(models[0].context_length, Some(32768));
+        assert_eq!(models[1].loaded, Some(false));
+    }
+
+    #[test]
+    fn test_provider_mapping_and_remote_models() {
+        let providers = serde_json::json!({
+            "local-llama": { "baseUrl": "http://127.0.0.1:11434/v1", "models": [{ "id": "llama3.2" }] },
+            "lmstudio": { "baseUrl": "http://localhost:1234/v1" },
+            "anthropic": { "models": [{ "id": "claude-sonnet-4-5", "contextWindow": 200000 }] }
+        });
+        assert_eq!(
+            provider_for_runtime(RUNTIME_OLLAMA, &providers).as_deref(),
+            Some("local-llama")
+        );
+        assert_eq!(
+            provider_for_runtime(RUNTIME_LM_STUDIO, &providers).as_deref(),
+            Some("lmstudio")
+        );
+        assert_eq!(provider_for_runtime(RUNTIME_VLLM, &providers), None);
+
+        let local = vec!["local-llama".to_string(), "lmstudio".to_string()];
+        let remote = remote_models_from_providers(&providers, &local);
+        assert_eq!(remote.len(), 1);
+        assert_eq!(remote[0].id, "anthropic/claude-sonnet-4-5");
+        assert_eq!(remote[0].context_length, Some(200000));
+    }
+
+    #[test]
+    fn test_apply_query_filters_and_sorts() {
+        let mut a = CatalogModel::local(RUNTIME_OLLAMA, "b-model");
+        a.size_bytes = Some(10);
+        a.loaded = Some(true);
+        let mut b = CatalogModel::local(RUNTIME_LM_STUDIO, "a-model");
+        b.size_bytes = Some(20);
+        let models = vec![a, b];
+
+        let by_name = apply_query(models.clone(), &CatalogQuery::default());
+        assert_eq!(by_name[0].name, "a-model");
+
+        let by_size_desc = apply_query(
+            models.clone(),
+            &CatalogQuery {
+                sort_by: CatalogSort::Size,
+                descending: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(by_size_desc[0].name, "a-model");
+
+        let loaded = apply_query(
+            models.clone(),
+            &CatalogQuery {
+                loaded_only: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(loaded.len(), 1);
+
+        let ollama = apply_query(
+            models.clone(),
+            &CatalogQuery {
+                runtime: Some(RUNTIME_OLLAMA.into()),
+                ..Default::default()
+            },
+        );
+        assert_eq!(ollama.len(), 1);
+
+        let search = apply_query(
+            models.clone(),
+            &CatalogQuery {
+                search: Some("A-MOD".into()),
+                ..Default::default()
+            },
+        );
+        assert_eq!(search.len(), 1);
+        assert_eq!(search[0].runtime, RUNTIME_LM_STUDIO);
+
+        let mut pinned = models;
+        mark_pinned(&mut pinned, &["ollama/b-model".to_string()]);
+        let by_name = apply_query(pinned.clone(), &CatalogQuery::default());
+        assert_eq!(by_name[0].name, "b-model");
+        assert!(by_name[0].pinned && !by_name[1].pinned);
+        mark_pinned(&mut pinned, &["lmstudio/a-model".to_string()]);
+        let desc = apply_query(
+            pinned,
+            &CatalogQuery {
+                descending: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(desc[0].name, "a-model");
+    }
+
+    #[test]
+    fn test_pinned_first() {
+        let names = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let pins = vec!["ollama/c".to_string(), "groq/b".to_string()];
+        let none = serde_json::json!({});
+        let local = pinned_first(names.clone(), Some(RUNTIME_OLLAMA), &none, &pins);
+        let order: Vec<&str> = local.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(order, vec!["c", "a", "b"]);
+        assert!(local[0].pinned && !local[2].pinned);
+        let renamed =
+            serde_json::json!({ "local-llama": { "baseUrl": "http://127.0.0.1:11434/v1" } });
+        let pins = vec!["local-llama/b".to_string()];
+        let local = pinned_first(names, Some(RUNTIME_OLLAMA), &renamed, &pins);
+        assert!(local[0].pinned && local[0].name == "b");
+        let cloud = pinned_first(
+            vec!["groq/a".into(), "local-llama/b".into()],
+            None,
+            &none,
+            &pins,
+        );
+        assert_eq!(cloud[0].name, "local-llama/b");
+        assert!(cloud[0].pinned && !cloud[1].pinned);
+    }
+
+    #[test]
+    fn test_classify_model_kind() {
+        assert_eq!(
+            classify_model_kind("nomic-embed-text:latest", None),
+            ModelKind::Embedding
+        );
+        assert_eq!(classify_model_kind("bge-m3", None), ModelKind::Embedding);
+        assert_eq!(
+            classify_model_kind("all-minilm:l6-v2", None),
+            ModelKind::Embedding
+        );
+        assert_eq!(
+            classify_model_kind("custom", Some("nomic-bert")),
+            ModelKind::Embedding
+        );
+        assert_eq!(
+            classify_model_kind("llama3.2:3b", Some("llama")),
+            ModelKind::Chat
+        );
+        assert_eq!(
+            classify_model_kind("qwen2.5-7b-instruct", None),
+            ModelKind::Chat
+        );
+
+        let models = vec![
+            CatalogModel::local(RUNTIME_OLLAMA, "llama3.2:3b"),
+            CatalogModel::local(RUNTIME_OLLAMA, "mxbai-embed-large"),
+        ];
+        let embeddings = apply_query(
+            models,
+            &CatalogQuery {
+                kind: Some(ModelKind::Embedding),
+                ..Default::default()
+            },
+        );
+        assert_eq!(embeddings.len(), 1);
+        assert_eq!(embeddings[0].name, "mxbai-embed-large");
+    }
+
+    #[test]
+    fn test_suggestions_from_catalog() {
+        let mut lm = CatalogModel::local(RUNTIME_LM_STUDIO, "qwen2.5-7b-instruct");
+        lm.provider = Some("lmstudio".into());
+        let models = vec![
+            CatalogModel::local(RUNTIME_OLLAMA, "llama3.2:3b"),
+            lm,
+            CatalogModel::local(RUNTIME_REMOTE, "claude"),
+        ];
+        let root = serde_json::json!({
+            "models": { "providers": { "ollama": { "models": [{ "id": "llama3.2:3b" }] } } },
+            "agents": { "defaults": { "models": { "ollama/llama3.2:3b": {} } } }
+        });
+        let s = suggestions_from_catalog(&models, &root);
+        assert_eq!(s.len(), 2);
+        assert_eq!(s[0].id, "ollama/llama3.2:3b");
+        assert!(s[0].configured);
+        assert_eq!(s[1].id, "lmstudio/qwen2.5-7b-instruct");
+        assert!(!s[1].configured);
+    }
+}
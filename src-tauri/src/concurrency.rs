@@ -0,0 +1,263 @@
+//! Checks agents.defaults.maxConcurrent and subagents.maxConcurrent against this machine. Every
+//! concurrent run on a local model holds its own context (KV cache) next to one copy of the
+//! weights, so `maxConcurrent × subagents.maxConcurrent` contexts of each local primary or
+//! fallback model must fit in VRAM and RAM. Suggests the largest limits that do.
+//! The check itself is a pure function for unit tests.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::catalog::{self, CatalogQuery, RUNTIME_REMOTE};
+use crate::hardware::{self, FitLevel, HardwareProfile};
+use crate::llmfit::{self, LlmfitCache};
+use crate::openclaw_config::{self, SubagentsView};
+use crate::{llmfit_schema, model_limits};
+
+const GB: f64 = 1024.0 * 1024.0 * 1024.0;
+/// openclaw's agents.defaults.maxConcurrent when unset.
+const DEFAULT_MAX_CONCURRENT: u32 = 4;
+
+/// A local model sized for the check.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct LocalModelSize {
+    pub model: String,
+    pub params_b: f64,
+    pub quantization: String,
+    /// Tokens each context holds.
+    pub context_len: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ModelConcurrency {
+    pub model: String,
+    pub weights_bytes: u64,
+    pub per_context_bytes: u64,
+    /// Weights plus every context plus runtime overhead.
+    pub required_bytes: u64,
+    pub fit: FitLevel,
+    /// Most contexts that still fit; 0 when not even one does.
+    pub max_contexts: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ConcurrencyCheck {
+    pub max_concurrent: u32,
+    pub subagent_max_concurrent: u32,
+    /// maxConcurrent × subagents.maxConcurrent.
+    pub contexts: u32,
+    pub models: Vec<ModelConcurrency>,
+    /// Local models that couldn't be sized and were left out.
+    pub unsized_models: Vec<String>,
+    pub ok: bool,
+    pub warnings: Vec<String>,
+    /// Set when the limits are too high.
+    pub suggested_max_concurrent: Option<u32>,
+    pub suggested_subagent_max_concurrent: Option<u32>,
+}
+
+fn need_gb(breakdown: &llmfit::MemoryBreakdown, contexts: u32) -> f64 {
+    breakdown.weights_gb + breakdown.kv_cache_gb * f64::from(contexts) + breakdown.overhead_gb
+}
+
+/// Largest limits whose product is at most `max_contexts`: subagents first, keeping
+/// maxConcurrent, then maxConcurrent with one subagent each.
+fn suggest_limits(max_concurrent: u32, max_contexts: u32) -> (u32, u32) {
+    let max_contexts = max_contexts.max(1);
+    if max_concurrent <= max_contexts {
+        (max_concurrent, (max_contexts / max_concurrent).max(1))
+    } else {
+        (max_contexts, 1)
+    }
+}
+
+/// Checks `models` at the configured limits on `profile`. A model that only fits by spilling
+/// into system RAM is a warning; one that doesn't fit at all fails the check.
+#[must_use]
+pub fn check_concurrency(
+    max_concurrent: u32,
+    subagent_max_concurrent: u32,
+    models: &[LocalModelSize],
+    profile: &HardwareProfile,
+) -> ConcurrencyCheck {
+    let max_concurrent = max_concurrent.max(1);
+    let subagent_max_concurrent = subagent_max_concurrent.max(1);
+    let contexts = max_concurrent.saturating_mul(subagent_max_concurrent);
+    let mut warnings = Vec::new();
+    let mut ok = true;
+    let mut safe_contexts = contexts;
+    let checked = models
+        .iter()
+        .map(|m| {
+            let breakdown = llmfit::memory_breakdown(m.params_b, &m.quantization, m.context_len);
+            let required = need_gb(&breakdown, contexts);
+            let (fit, _) = hardware::assess_fit(required, profile);
+            let max_contexts = (1..=contexts)
+                .take_while(|&n| {
+                    hardware::assess_fit(need_gb(&breakdown, n), profile).0 != FitLevel::None
+                })
+                .last()
+                .unwrap_or(0);
+            match fit {
+                FitLevel::None => {
+                    ok = false;
+                    safe_contexts = safe_contexts.min(max_contexts);
+                    warnings.push(format!(
+                        "{} needs ~{:.1} GB for {} contexts of {} tokens; only {} fit",
+                        m.model, required, contexts, m.context_len, max_contexts
+                    ));
+                }
+                FitLevel::Ram => warnings.push(format!(
+                    "{} needs ~{:.1} GB for {} contexts and spills into system RAM; expect slow \
+                     responses under load",
+                    m.model, required, contexts
+                )),
+                FitLevel::Vram => {}
+            }
+            ModelConcurrency {
+                model: m.model.clone(),
+                weights_bytes: (breakdown.weights_gb * GB) as u64,
+                per_context_bytes: (breakdown.kv_cache_gb * GB) as u64,
+                required_bytes: (required * GB) as u64,
+                fit,
+                max_contexts,
+            }
+        })
+        .collect();
+    let suggested = (!ok).then(|| suggest_limits(max_concurrent, safe_contexts));
+    ConcurrencyCheck {
+        max_concurrent,
+        subagent_max_concurrent,
+        contexts,
+        models: checked,
+        unsized_models: vec![],
+        ok,
+        warnings,
+        suggested_max_concurrent: suggested.map(|s| s.0),
+        suggested_subagent_max_concurrent: suggested.map(|s| s.1),
+    }
+}
+
+/// Checks the default primary and fallbacks that run locally against the detected hardware.
+/// Installed models are sized from the catalog, others from their name; contexts hold
+/// agents.defaults.contextTokens, else the model's configured contextWindow, else the runtime
+/// default.
+pub fn validate_concurrency_settings(cache: &LlmfitCache) -> Result<ConcurrencyCheck, String> {
+    let root = openclaw_config::read_config_root()?;
+    let view = openclaw_config::config_view_from_root(&root)
+        .map_err(|()| "openclaw.json root is not an object".to_string())?;
+    let providers = root
+        .pointer("/models/providers")
+        .cloned()
+        .unwrap_or(serde_json::json!({}));
+    let context_tokens = root
+        .pointer("/agents/defaults/contextTokens")
+        .and_then(|v| v.as_u64());
+    let catalog = catalog::get_model_catalog(&CatalogQuery::default());
+    let mut sized = Vec::new();
+    let mut unsized_models = Vec::new();
+    let mut ids: Vec<&String> = Vec::new();
+    for id in view.primary_model.iter().chain(&view.fallbacks) {
+        if !ids.contains(&id) && openclaw_config::is_local_model(id, &providers) {
+            ids.push(id);
+        }
+    }
+    for id in ids {
+        let name = id.split_once('/').map_or(id.as_str(), |(_, n)| n);
+        let entry = catalog
+            .iter()
+            .find(|m| m.runtime != RUNTIME_REMOTE && (&m.id == id || m.name == name));
+        let params_b = entry
+            .and_then(|m| m.parameter_size.as_deref())
+            .and_then(|p| llmfit_schema::params_billions(Some(&Value::from(p))))
+            .or_else(|| llmfit::table_params_b(name))
+            .or_else(|| hardware::params_from_name(name));
+        let Some(params_b) = params_b else {
+            unsized_models.push(id.clone());
+            continue;
+        };
+        let quantization = entry
+            .and_then(|m| m.quantization.clone())
+            .filter(|q| llmfit::gb_per_billion_params(q).is_some())
+            .unwrap_or_else(|| "Q4_K_M".to_string());
+        let context_len = context_tokens
+            .or_else(|| {
+                model_limits::limits_for_model_id(id, &providers)
+                    .filter(|l| l.source == "config")
+                    .map(|l| l.context_window)
+            })
+            .unwrap_or(llmfit::DEFAULT_CONTEXT_LEN);
+        sized.push(LocalModelSize {
+            model: id.clone(),
+            params_b,
+            quantization,
+            context_len,
+        });
+    }
+    let subagents = view
+        .subagents
+        .max_concurrent
+        .or(SubagentsView::default().max_concurrent);
+    let mut check = check_concurrency(
+        view.max_concurrent.unwrap_or(DEFAULT_MAX_CONCURRENT),
+        subagents.unwrap_or(1),
+        &sized,
+        &hardware::get_hardware_profile(cache),
+    );
+    check.unsized_models = unsized_models;
+    Ok(check)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::{MemoryPressure, SystemInfo};
+
+    fn profile(ram_gb: u64) -> HardwareProfile {
+        let bytes = ram_gb * GB as u64;
+        let info = SystemInfo {
+            total_memory_bytes: bytes,
+            available_memory_bytes: bytes,
+            total_memory_human: String::new(),
+            available_memory_human: String::new(),
+            swap_total_bytes: 0,
+            swap_used_bytes: 0,
+            memory_pressure: MemoryPressure::Green,
+            cpu: Default::default(),
+            os: Default::default(),
+            gpu: None,
+        };
+        hardware::merge_profile(&info, Some(8), None, None)
+    }
+
+    #[test]
+    fn test_check_concurrency() {
+        let model = LocalModelSize {
+            model: "ollama/qwen3:8b".to_string(),
+            params_b: 8.0,
+            quantization: "Q4_K_M".to_string(),
+            context_len: 32_768,
+        };
+        // 4 GB context each at 32k tokens: 4 × 8 contexts can't fit in 16 GB.
+        let tight = check_concurrency(4, 8, std::slice::from_ref(&model), &profile(16));
+        assert_eq!(tight.contexts, 32);
+        assert!(!tight.ok);
+        let max = tight.models[0].max_contexts;
+        assert!((1..32).contains(&max));
+        let (a, b) = (
+            tight.suggested_max_concurrent.unwrap(),
+            tight.suggested_subagent_max_concurrent.unwrap(),
+        );
+        assert!(a * b <= max.max(1));
+
+        let roomy = check_concurrency(1, 1, &[model], &profile(64));
+        assert!(roomy.ok);
+        assert!(roomy.suggested_max_concurrent.is_none());
+    }
+
+    #[test]
+    fn test_suggest_limits() {
+        assert_eq!(suggest_limits(4, 10), (4, 2));
+        assert_eq!(suggest_limits(4, 3), (3, 1));
+        assert_eq!(suggest_limits(2, 0), (1, 1));
+    }
+}
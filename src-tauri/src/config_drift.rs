@@ -0,0 +1,328 @@
+//! Whether the running gateway still uses what openclaw.json says. The gateway's effective config
+//! comes from `openclaw gateway status --json`, or `openclaw config show --json` for gateways that
+//! don't report it, and is compared path by path with the file. Secrets are masked in the result.
+//! `reload_gateway_config` asks a running gateway to re-read the file (`openclaw gateway reload`)
+//! and waits until the config in its own status report matches. There is no signal fallback: a
+//! Node gateway without a SIGHUP handler exits on it. The comparison is a pure function for unit
+//! tests.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::diagnostics::is_secret_key;
+use crate::operations::{self, LIST_TIMEOUT};
+use crate::{gateway, openclaw_config};
+
+/// Top-level keys openclaw rewrites on its own (timestamps, wizard state).
+const IGNORED_KEYS: [&str; 2] = ["meta", "wizard"];
+const RESTART_HINT: &str = "Restart the gateway to apply openclaw.json";
+/// How long a reload may take to show up in the gateway's reported config.
+const RELOAD_CONFIRM_TIMEOUT: Duration = Duration::from_secs(10);
+const RELOAD_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub const RELOAD_CLI: &str = "openclaw gateway reload";
+
+/// A path whose value differs, e.g. "models.providers.ollama.baseUrl". Null when unset on that
+/// side.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ConfigDriftPath {
+    pub path: String,
+    pub running: Value,
+    pub on_disk: Value,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ConfigReload {
+    pub gateway_running: bool,
+    /// How the gateway was asked to reload (`RELOAD_CLI`); None when it wasn't running.
+    pub method: Option<String>,
+    /// The gateway's own status reported openclaw.json's values afterwards.
+    pub confirmed: bool,
+    /// Paths that still differed when confirmation gave up.
+    pub differences: Vec<ConfigDriftPath>,
+    pub message: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ConfigDrift {
+    pub gateway_running: bool,
+    /// The command the effective config came from.
+    pub source: Option<String>,
+    pub in_sync: bool,
+    pub differences: Vec<ConfigDriftPath>,
+    /// Set when there are differences.
+    pub hint: Option<String>,
+}
+
+/// Shows whether a secret is set, not its value. Numbers and booleans under secret-looking keys
+/// (maxTokens) are kept, as in the diagnostics bundle.
+fn mask(value: &Value) -> Value {
+    match value {
+        Value::Null | Value::Number(_) | Value::Bool(_) => value.clone(),
+        _ => Value::String("***".to_string()),
+    }
+}
+
+/// A copy of `value` with secrets inside it masked.
+fn redact(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| {
+                    let v = if is_secret_key(k) { mask(v) } else { redact(v) };
+                    (k.clone(), v)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(redact).collect()),
+        _ => value.clone(),
+    }
+}
+
+fn collect_diffs(
+    path: &str,
+    secret: bool,
+    running: &Value,
+    on_disk: &Value,
+    out: &mut Vec<ConfigDriftPath>,
+) {
+    if running == on_disk {
+        return;
+    }
+    if let (Value::Object(a), Value::Object(b)) = (running, on_disk) {
+        let keys: BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+        for key in keys {
+            if path.is_empty() && IGNORED_KEYS.contains(&key.as_str()) {
+                continue;
+            }
+            let child = if path.is_empty() {
+                key.clone()
+            } else {
+                format!("{}.{}", path, key)
+            };
+            collect_diffs(
+                &child,
+                secret || is_secret_key(key) || (path.is_empty() && key == "env"),
+                a.get(key).unwrap_or(&Value::Null),
+                b.get(key).unwrap_or(&Value::Null),
+                out,
+            );
+        }
+        return;
+    }
+    // The gateway may report `${VAR}` references resolved; their values can't be compared.
+    if secret
+        && on_disk
+            .as_str()
+            .is_some_and(|v| v.trim_start().starts_with("${"))
+    {
+        return;
+    }
+    let (running, on_disk) = if secret {
+        (mask(running), mask(on_disk))
+    } else {
+        (redact(running), redact(on_disk))
+    };
+    out.push(ConfigDriftPath {
+        path: path.to_string(),
+        running,
+        on_disk,
+    });
+}
+
+/// Paths where the gateway's config differs from openclaw.json, sorted. Objects are compared key
+/// by key, anything else (arrays included) as a whole; `env` and key/token fields are masked.
+#[must_use]
+pub fn diff_configs(running: &Value, on_disk: &Value) -> Vec<ConfigDriftPath> {
+    let mut out = Vec::new();
+    collect_diffs("", false, running, on_disk, &mut out);
+    out
+}
+
+/// The config object in a status report: `config` or `effectiveConfig`, else the report itself
+/// when it looks like an openclaw.json root.
+#[must_use]
+pub fn effective_config_from_status(status: &Value) -> Option<Value> {
+    status
+        .get("config")
+        .or_else(|| status.get("effectiveConfig"))
+        .filter(|c| c.is_object())
+        .or_else(|| {
+            ["models", "agents", "gateway"]
+                .iter()
+                .any(|k| status.get(k).is_some())
+                .then_some(status)
+        })
+        .cloned()
+}
+
+fn run_json(args: &[&str]) -> Option<Value> {
+    let output = Command::new(gateway::openclaw_bin())
+        .args(args)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    serde_json::from_slice(&output.stdout).ok()
+}
+
+/// The running gateway's config and the command it came from.
+fn running_config() -> Result<(Value, String), String> {
+    for args in [
+        ["gateway", "status", "--json"],
+        ["config", "show", "--json"],
+    ] {
+        if let Some(config) = run_json(&args).and_then(|v| effective_config_from_status(&v)) {
+            return Ok((config, format!("openclaw {}", args.join(" "))));
+        }
+    }
+    Err("The gateway did not report its config".to_string())
+}
+
+/// Compares the running gateway's config with openclaw.json. Nothing is compared while the
+/// gateway is stopped, since it reads the file on start.
+pub fn get_config_drift() -> Result<ConfigDrift, String> {
+    if !gateway::gateway_running()? {
+        return Ok(ConfigDrift {
+            gateway_running: false,
+            in_sync: true,
+            ..Default::default()
+        });
+    }
+    let on_disk = openclaw_config::read_config_root()?;
+    let (running, source) = running_config()?;
+    let differences = diff_configs(&running, &on_disk);
+    Ok(ConfigDrift {
+        gateway_running: true,
+        source: Some(source),
+        in_sync: differences.is_empty(),
+        hint: (!differences.is_empty()).then(|| RESTART_HINT.to_string()),
+        differences,
+    })
+}
+
+/// The config the gateway itself reports. Unlike `running_config` there is no `config show`
+/// fallback, which only re-reads the file and so can't confirm anything.
+fn gateway_reported_config() -> Option<Value> {
+    run_json(&["gateway", "status", "--json"]).and_then(|v| effective_config_from_status(&v))
+}
+
+/// Asks the gateway to re-read openclaw.json with its reload command.
+fn request_reload() -> Result<&'static str, String> {
+    let output = operations::run_command(
+        Command::new(gateway::openclaw_bin()).args(["gateway", "reload"]),
+        LIST_TIMEOUT,
+        None,
+    )?;
+    if output.status.success() {
+        Ok(RELOAD_CLI)
+    } else {
+        Err("The gateway can't reload its config; restart it instead".to_string())
+    }
+}
+
+/// Makes the running gateway pick up openclaw.json without a restart and waits until its
+/// reported config matches the file. Unconfirmed when the gateway doesn't report its config or
+/// still differs after 10 seconds; `differences` then lists what didn't apply.
+pub fn reload_gateway_config() -> Result<ConfigReload, String> {
+    if !gateway::gateway_running()? {
+        return Ok(ConfigReload {
+            message: Some("The gateway is stopped; it reads openclaw.json when it starts".into()),
+            ..Default::default()
+        });
+    }
+    let method = request_reload()?;
+    let on_disk = openclaw_config::read_config_root()?;
+    let deadline = Instant::now() + RELOAD_CONFIRM_TIMEOUT;
+    let (differences, message) = loop {
+        thread::sleep(RELOAD_POLL_INTERVAL);
+        match gateway_reported_config() {
+            Some(running) => {
+                let differences = diff_configs(&running, &on_disk);
+                if differences.is_empty() {
+                    break (differences, None);
+                }
+                if Instant::now() >= deadline {
+                    break (differences, Some(RESTART_HINT.to_string()));
+                }
+            }
+            None if Instant::now() >= deadline => {
+                let message = "The gateway doesn't report its config, so the reload is unconfirmed";
+                break (vec![], Some(message.to_string()));
+            }
+            None => {}
+        }
+    };
+    Ok(ConfigReload {
+        gateway_running: true,
+        method: Some(method.to_string()),
+        confirmed: message.is_none(),
+        differences,
+        message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_configs() {
+        let running = serde_json::json!({
+            "meta": { "lastTouchedAt": "yesterday" },
+            "env": { "GROQ_API_KEY": "old" },
+            "models": { "providers": {
+                "ollama": { "baseUrl": "http://127.0.0.1:11434/v1", "apiKey": "a" },
+                "groq": { "baseUrl": "https://api.groq.com/openai/v1", "apiKey": "gsk-1" },
+                "openai": { "apiKey": "sk-resolved" }
+            } },
+            "agents": { "defaults": { "model": { "fallbacks": ["ollama/qwen3:8b"] } } }
+        });
+        let on_disk = serde_json::json!({
+            "meta": { "lastTouchedAt": "today" },
+            "env": { "GROQ_API_KEY": "new" },
+            "models": { "providers": {
+                "ollama": { "baseUrl": "http://10.0.0.5:11434/v1", "apiKey": "b" },
+                "openai": { "apiKey": "${OPENAI_API_KEY}" }
+            } },
+            "agents": { "defaults": { "model": { "fallbacks": [] } } }
+        });
+        let diffs = diff_configs(&running, &on_disk);
+        let paths: Vec<&str> = diffs.iter().map(|d| d.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "agents.defaults.model.fallbacks",
+                "env.GROQ_API_KEY",
+                "models.providers.groq",
+                "models.providers.ollama.apiKey",
+                "models.providers.ollama.baseUrl",
+            ]
+        );
+        assert_eq!(diffs[1].running, "***");
+        assert_eq!(diffs[2].running["apiKey"], "***");
+        assert_eq!(diffs[2].on_disk, Value::Null);
+        assert_eq!(diffs[4].on_disk, "http://10.0.0.5:11434/v1");
+        assert!(diff_configs(&on_disk, &on_disk).is_empty());
+        let tokens = diff_configs(
+            &serde_json::json!({ "maxTokens": 4096 }),
+            &serde_json::json!({ "maxTokens": 8192 }),
+        );
+        assert_eq!(tokens[0].on_disk, 8192);
+    }
+
+    #[test]
+    fn test_effective_config_from_status() {
+        let wrapped = serde_json::json!({ "running": true, "config": { "models": {} } });
+        assert_eq!(
+            effective_config_from_status(&wrapped),
+            Some(serde_json::json!({ "models": {} }))
+        );
+        let bare = serde_json::json!({ "agents": {} });
+        assert_eq!(effective_config_from_status(&bare), Some(bare.clone()));
+        assert!(effective_config_from_status(&serde_json::json!({ "running": true })).is_none());
+    }
+}
@@ -0,0 +1,442 @@
+//! Optional git history of ~/.openclaw's configuration. `init_config_repo` turns ~/.openclaw into
+//! a local git repository whose .gitignore admits only openclaw.json and the agents' models.json
+//! (never sessions, logs or .env files); from then on every save made through this app is
+//! committed with a message naming what changed, and any commit can be restored as a new commit
+//! on top, so a revert can itself be undone. Nothing is pushed anywhere. Uses the `git` CLI.
+//! Secrets are kept out of the history: API keys, tokens and the `env` block are committed as
+//! "[redacted]" (the working files keep them), and a revert takes them from the current files,
+//! dropping any that are gone there. openclaw.json is restored through the normal save path.
+//! Commits from older versions of this app may still hold plaintext keys.
+//! Change descriptions and log parsing are pure functions for unit tests.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+use std::time::Duration;
+
+use crate::diagnostics::{self, REDACTED};
+use crate::{agents, config_drift, openclaw_config, operations, policy};
+
+const GIT_TIMEOUT: Duration = Duration::from_secs(20);
+const DEFAULT_HISTORY: usize = 50;
+/// Changed paths named in a commit message before the rest are counted.
+const MAX_DESCRIBED: usize = 3;
+const FALLBACK_NAME: &str = "OpenClaw Host Config";
+const FALLBACK_EMAIL: &str = "host-config@localhost";
+const GITIGNORE: &str = "# Written by openclaw-host-config: only configuration is versioned.
+/*
+!/.gitignore
+!/openclaw.json
+!/agents/
+/agents/*/*
+!/agents/*/agent/
+/agents/*/agent/*
+!/agents/*/agent/models.json
+";
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ConfigCommit {
+    pub sha: String,
+    /// Unix seconds.
+    pub timestamp: u64,
+    pub message: String,
+    /// Files changed, relative to ~/.openclaw.
+    pub files: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ConfigHistory {
+    /// ~/.openclaw is a git repository.
+    pub enabled: bool,
+    /// Newest first.
+    pub commits: Vec<ConfigCommit>,
+}
+
+fn repo_dir() -> PathBuf {
+    agents::openclaw_root()
+}
+
+#[must_use]
+pub fn is_enabled() -> bool {
+    repo_dir().join(".git").is_dir()
+}
+
+/// A commit message for a JSON file going from `before` to `after`, e.g.
+/// "openclaw.json: set agents.defaults.model.primary, add models.providers.lmstudio".
+#[must_use]
+pub fn describe_json_change(file: &str, before: &Value, after: &Value) -> String {
+    if !before.is_object() {
+        return format!("Create {}", file);
+    }
+    let diffs = config_drift::diff_configs(before, after);
+    if diffs.is_empty() {
+        return format!("Save {}", file);
+    }
+    let changes: Vec<String> = diffs
+        .iter()
+        .take(MAX_DESCRIBED)
+        .map(|d| {
+            let verb = if d.running.is_null() {
+                "add"
+            } else if d.on_disk.is_null() {
+                "remove"
+            } else {
+                "set"
+            };
+            format!("{} {}", verb, d.path)
+        })
+        .collect();
+    let more = diffs.len().saturating_sub(MAX_DESCRIBED);
+    let suffix = if more > 0 {
+        format!(" (+{} more)", more)
+    } else {
+        String::new()
+    };
+    format!("{}: {}{}", file, changes.join(", "), suffix)
+}
+
+/// Commits from `git log --format=%x1e%H%x1f%ct%x1f%s --name-only` output.
+#[must_use]
+pub fn parse_log(stdout: &str) -> Vec<ConfigCommit> {
+    stdout
+        .split('\x1e')
+        .filter_map(|record| {
+            let mut lines = record.lines();
+            let mut header = lines.next()?.split('\x1f');
+            let sha = header.next()?.trim().to_string();
+            let timestamp = header.next()?.trim().parse().ok()?;
+            let message = header.next().unwrap_or_default().to_string();
+            let files = lines
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .map(String::from)
+                .collect();
+            Some(ConfigCommit {
+                sha,
+                timestamp,
+                message,
+                files,
+            })
+        })
+        .collect()
+}
+
+fn git(args: &[&str]) -> Result<Output, String> {
+    operations::run_command(
+        Command::new("git").arg("-C").arg(repo_dir()).args(args),
+        GIT_TIMEOUT,
+        None,
+    )
+}
+
+/// stdout of a git command that has to succeed.
+fn git_ok(args: &[&str]) -> Result<String, String> {
+    let out = git(args)?;
+    if !out.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&out.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&out.stdout).to_string())
+}
+
+/// openclaw.json or an agent's models.json, relative to ~/.openclaw.
+fn is_config_file(file: &str) -> bool {
+    file == "openclaw.json"
+        || file
+            .strip_prefix("agents/")
+            .is_some_and(|f| f.ends_with("/agent/models.json"))
+}
+
+/// `content` with its secrets replaced by "[redacted]"; None when there are none. Files that
+/// don't parse are redacted line by line.
+fn redacted_content(content: &str) -> Option<String> {
+    let redacted = match serde_json::from_str::<Value>(content) {
+        Ok(root) => {
+            let mut redacted = root.clone();
+            diagnostics::redact_secrets(&mut redacted);
+            if redacted == root {
+                return None;
+            }
+            serde_json::to_string_pretty(&redacted).ok()? + "\n"
+        }
+        Err(_) => diagnostics::redact_log_text(content),
+    };
+    (redacted != content).then_some(redacted)
+}
+
+/// `old` with each "[redacted]" replaced by the string at the same path in `current`, so restoring
+/// an old config keeps today's keys. A secret `current` no longer has is dropped rather than
+/// written as the placeholder; None when `old` itself is such a placeholder.
+fn restore_secrets(old: &Value, current: &Value) -> Option<Value> {
+    match old {
+        Value::String(s) if s == REDACTED => current.as_str().map(Value::from),
+        Value::Object(obj) => Some(Value::Object(
+            obj.iter()
+                .filter_map(|(k, v)| {
+                    let v = restore_secrets(v, current.get(k).unwrap_or(&Value::Null))?;
+                    Some((k.clone(), v))
+                })
+                .collect(),
+        )),
+        Value::Array(items) => Some(Value::Array(
+            items
+                .iter()
+                .enumerate()
+                .filter_map(|(i, v)| restore_secrets(v, current.get(i).unwrap_or(&Value::Null)))
+                .collect(),
+        )),
+        _ => Some(old.clone()),
+    }
+}
+
+/// Replaces the staged config files with redacted copies; the working files are left alone.
+fn stage_redacted() -> Result<(), String> {
+    let tmp = std::env::temp_dir().join(format!("openclaw-config-git-{}", std::process::id()));
+    for file in git_ok(&["ls-files"])?.lines().filter(|f| is_config_file(f)) {
+        let Ok(content) = fs::read_to_string(repo_dir().join(file)) else {
+            continue;
+        };
+        let Some(redacted) = redacted_content(&content) else {
+            continue;
+        };
+        fs::write(&tmp, redacted).map_err(|e| e.to_string())?;
+        let blob = git_ok(&["hash-object", "-w", "--no-filters", &tmp.to_string_lossy()]);
+        let _ = fs::remove_file(&tmp);
+        let info = format!("100644,{},{}", blob?.trim(), file);
+        git_ok(&["update-index", "--cacheinfo", &info])?;
+    }
+    Ok(())
+}
+
+/// Stages everything the .gitignore admits, secrets redacted, and commits it; None when nothing
+/// changed.
+fn commit_all(message: &str) -> Result<Option<String>, String> {
+    git_ok(&["add", "-A"])?;
+    stage_redacted()?;
+    if git(&["diff", "--cached", "--quiet"])?.status.success() {
+        return Ok(None);
+    }
+    git_ok(&["commit", "-q", "-m", message])?;
+    Ok(Some(git_ok(&["rev-parse", "HEAD"])?.trim().to_string()))
+}
+
+/// Commits the current config with `message` when versioning is on. Failures are dropped so a
+/// save never fails because of git.
+pub fn record_change(message: &str) {
+    if is_enabled() {
+        let _ = commit_all(message);
+    }
+}
+
+/// `record_change` with a message describing the difference between two versions of `file`.
+pub fn record_json_change(file: &str, before: &Value, after: &Value) {
+    if is_enabled() {
+        let _ = commit_all(&describe_json_change(file, before, after));
+    }
+}
+
+/// Makes ~/.openclaw a git repository (keeping an existing one and its .gitignore) and commits
+/// the current config.
+pub fn init_config_repo() -> Result<ConfigHistory, String> {
+    let dir = repo_dir();
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    if !is_enabled() {
+        git_ok(&["init", "-q"])?;
+    }
+    let ignore = dir.join(".gitignore");
+    if !ignore.exists() {
+        fs::write(&ignore, GITIGNORE).map_err(|e| e.to_string())?;
+    }
+    // Commits need an identity; use the user's when git has one.
+    if git_ok(&["config", "user.email"])
+        .unwrap_or_default()
+        .trim()
+        .is_empty()
+    {
+        git_ok(&["config", "user.name", FALLBACK_NAME])?;
+        git_ok(&["config", "user.email", FALLBACK_EMAIL])?;
+    }
+    commit_all("Start versioning openclaw config")?;
+    get_config_history(None)
+}
+
+/// The newest `limit` (default 50) config commits; empty when versioning is off.
+pub fn get_config_history(limit: Option<usize>) -> Result<ConfigHistory, String> {
+    if !is_enabled() {
+        return Ok(ConfigHistory::default());
+    }
+    // An empty repository has no HEAD to log.
+    if !git(&["rev-parse", "--verify", "-q", "HEAD"])?
+        .status
+        .success()
+    {
+        return Ok(ConfigHistory {
+            enabled: true,
+            commits: vec![],
+        });
+    }
+    let count = format!("-n{}", limit.unwrap_or(DEFAULT_HISTORY));
+    let log = git_ok(&["log", &count, "--format=%x1e%H%x1f%ct%x1f%s", "--name-only"])?;
+    Ok(ConfigHistory {
+        enabled: true,
+        commits: parse_log(&log),
+    })
+}
+
+/// Fails when a config file at `commit` breaks the team policy, so a revert can't bring back
+/// what the policy forbids.
+fn enforce_policy_at(commit: &str) -> Result<(), String> {
+    for file in git_ok(&["ls-tree", "-r", "--name-only", commit])?.lines() {
+        let agent = file
+            .strip_prefix("agents/")
+            .and_then(|f| f.strip_suffix("/agent/models.json"));
+        if file != "openclaw.json" && agent.is_none() {
+            continue;
+        }
+        let content = git_ok(&["show", &format!("{}:{}", commit, file)])?;
+        let Ok(root) = serde_json::from_str::<Value>(&content) else {
+            continue;
+        };
+        match agent {
+            Some(name) => policy::enforce_models_json(name, &root)?,
+            None => policy::enforce_config(&root)?,
+        }
+    }
+    Ok(())
+}
+
+/// Restores the config files to their state at `sha` and commits that as a new commit. Changes
+/// made outside the app are committed first so they stay in the history.
+pub fn revert_to_commit(sha: &str) -> Result<ConfigHistory, String> {
+    let sha = sha.trim();
+    if !(4..=40).contains(&sha.len()) || !sha.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("Invalid commit: {}", sha));
+    }
+    if !is_enabled() {
+        return Err("Config versioning is off; call init_config_repo first".to_string());
+    }
+    let commit = git_ok(&[
+        "rev-parse",
+        "--verify",
+        "-q",
+        &format!("{}^{{commit}}", sha),
+    ])
+    .map_err(|_| format!("Unknown commit: {}", sha))?;
+    let commit = commit.trim();
+    enforce_policy_at(commit)?;
+    commit_all("Record config changes made outside the app")?;
+    let subject = git_ok(&["log", "-1", "--format=%s", commit])?;
+    let files = git_ok(&["ls-tree", "-r", "--name-only", commit])?;
+    let files: Vec<&str> = files.lines().collect();
+    // Read everything first so a file that doesn't parse stops the revert before any write.
+    let mut restored = Vec::new();
+    for file in &files {
+        let content = git_ok(&["show", &format!("{}:{}", commit, file)])?;
+        let root = if is_config_file(file) {
+            let old: Value = serde_json::from_str(&content)
+                .map_err(|e| format!("{} at {} doesn't parse: {}", file, sha, e))?;
+            let current = fs::read_to_string(repo_dir().join(file))
+                .ok()
+                .and_then(|c| serde_json::from_str(&c).ok())
+                .unwrap_or_default();
+            Some(restore_secrets(&old, &current).unwrap_or_default())
+        } else {
+            None
+        };
+        restored.push((*file, content, root));
+    }
+    for file in git_ok(&["ls-files"])?.lines() {
+        if !files.contains(&file) {
+            let _ = fs::remove_file(repo_dir().join(file));
+        }
+    }
+    for (file, content, root) in restored {
+        let path = repo_dir().join(file);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        match root {
+            Some(root) if file == "openclaw.json" => openclaw_config::write_config_file(&root)?,
+            Some(root) => {
+                let json = serde_json::to_string_pretty(&root).map_err(|e| e.to_string())?;
+                fs::write(&path, json).map_err(|e| e.to_string())?;
+            }
+            None => fs::write(&path, content).map_err(|e| e.to_string())?,
+        }
+    }
+    commit_all(&format!(
+        "Revert config to {}: {}",
+        &commit[..7.min(commit.len())],
+        subject.trim()
+    ))?;
+    get_config_history(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_json_change() {
+        let before = serde_json::json!({
+            "agents": { "defaults": { "model": { "primary": "ollama/qwen3:8b" } } },
+            "models": { "providers": { "ollama": {}, "vllm": {} } }
+        });
+        let mut after = before.clone();
+        after["agents"]["defaults"]["model"]["primary"] = "anthropic/claude-sonnet-4-5".into();
+        after["models"]["providers"]["lmstudio"] = serde_json::json!({});
+        after["models"]["providers"]
+            .as_object_mut()
+            .unwrap()
+            .remove("vllm");
+        assert_eq!(
+            describe_json_change("openclaw.json", &before, &after),
+            "openclaw.json: set agents.defaults.model.primary, add models.providers.lmstudio, \
+             remove models.providers.vllm"
+        );
+        after["gateway"] = serde_json::json!({ "port": 1 });
+        assert!(describe_json_change("openclaw.json", &before, &after).ends_with("(+1 more)"));
+        assert_eq!(
+            describe_json_change("openclaw.json", &Value::Null, &after),
+            "Create openclaw.json"
+        );
+    }
+
+    #[test]
+    fn test_redacted_content() {
+        let content = r#"{ "env": { "GROQ_API_KEY": "gsk-1" }, "gateway": { "port": 18789 } }"#;
+        let redacted = redacted_content(content).unwrap();
+        assert!(!redacted.contains("gsk-1") && redacted.contains("18789"));
+        assert!(redacted_content(r#"{ "gateway": { "port": 18789 } }"#).is_none());
+        assert!(!redacted_content("{ \"apiKey\": \"sk-1\",")
+            .unwrap()
+            .contains("sk-1"));
+
+        let mut old: Value = serde_json::from_str(&redacted).unwrap();
+        old["gateway"]["port"] = 1.into();
+        let current = serde_json::json!({ "env": { "GROQ_API_KEY": "gsk-2" } });
+        let restored = restore_secrets(&old, &current).unwrap();
+        assert_eq!(restored["env"]["GROQ_API_KEY"], "gsk-2");
+        assert_eq!(restored["gateway"]["port"], 1);
+        let restored = restore_secrets(&old, &serde_json::json!({})).unwrap();
+        assert_eq!(restored["env"], serde_json::json!({}));
+    }
+
+    #[test]
+    fn test_parse_log() {
+        let log = "\x1eabc123\x1f1700000000\x1fopenclaw.json: set gateway.port\n\nopenclaw.json\n\
+                   \x1edef456\x1f1690000000\x1fStart versioning openclaw config\n\n.gitignore\n\
+                   agents/main/agent/models.json\n";
+        let commits = parse_log(log);
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].sha, "abc123");
+        assert_eq!(commits[0].files, vec!["openclaw.json"]);
+        assert_eq!(commits[1].timestamp, 1_690_000_000);
+        assert_eq!(commits[1].files.len(), 2);
+    }
+}
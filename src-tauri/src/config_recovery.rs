@@ -0,0 +1,169 @@
+//! Safe mode for an openclaw.json that doesn't parse. The broken file is copied to
+//! `openclaw.json.broken-<unix secs>` before anything replaces it, the parse error's line and
+//! column are reported so the UI can point at it, and the file can be restored from the scheduled
+//! backups (~/.openclaw/backups/config/<unix secs>) or replaced with an empty config.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{openclaw_config, operations, scheduler};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ConfigBackup {
+    /// The backup directory name (unix secs).
+    pub id: String,
+    pub created_at: u64,
+    pub path: String,
+}
+
+/// Payload of the `config-corrupt` event.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ConfigCorruption {
+    pub path: String,
+    pub error: String,
+    /// 1-based position of the parse error; 0 when the JSON parses but isn't an object.
+    pub line: usize,
+    pub column: usize,
+    /// Where the broken file was copied.
+    pub preserved_as: Option<String>,
+    /// Backups with an openclaw.json that parses, newest first.
+    pub backups: Vec<ConfigBackup>,
+}
+
+/// Why `content` isn't a usable openclaw.json: the message, line and column. None when it is.
+#[must_use]
+pub fn parse_problem(content: &str) -> Option<(String, usize, usize)> {
+    match serde_json::from_str::<serde_json::Value>(content) {
+        Ok(root) if root.is_object() => None,
+        Ok(_) => Some(("openclaw.json is not a JSON object".to_string(), 0, 0)),
+        Err(e) => Some((e.to_string(), e.line(), e.column())),
+    }
+}
+
+fn broken_copy_path(path: &Path, secs: u64) -> PathBuf {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!("{}.broken-{}", name, secs))
+}
+
+/// An existing `<name>.broken-*` copy with the same content as `path`.
+fn existing_copy(path: &Path, content: &[u8]) -> Option<PathBuf> {
+    let prefix = format!("{}.broken-", path.file_name()?.to_string_lossy());
+    fs::read_dir(path.parent()?)
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .is_some_and(|n| n.to_string_lossy().starts_with(&prefix))
+        })
+        .find(|p| fs::read(p).is_ok_and(|c| c == content))
+}
+
+/// Copies openclaw.json next to itself as `.broken-<ts>` when it doesn't parse, unless an
+/// identical copy is already there. Returns the copy; None when the file is fine or missing.
+pub fn preserve_broken_config() -> Result<Option<PathBuf>, String> {
+    let path = openclaw_config::openclaw_config_path();
+    let Ok(content) = fs::read(&path) else {
+        return Ok(None);
+    };
+    if parse_problem(&String::from_utf8_lossy(&content)).is_none() {
+        return Ok(None);
+    }
+    if let Some(copy) = existing_copy(&path, &content) {
+        return Ok(Some(copy));
+    }
+    let copy = broken_copy_path(&path, operations::now_secs());
+    fs::write(&copy, &content).map_err(|e| format!("{}: {}", copy.display(), e))?;
+    Ok(Some(copy))
+}
+
+/// Backups whose openclaw.json parses, newest first.
+#[must_use]
+pub fn list_config_backups() -> Vec<ConfigBackup> {
+    let mut backups: Vec<ConfigBackup> = fs::read_dir(scheduler::config_backups_dir())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|e| {
+            let id = e.file_name().to_string_lossy().to_string();
+            let created_at = id.parse().ok()?;
+            let path = e.path().join("openclaw.json");
+            let content = fs::read_to_string(&path).ok()?;
+            parse_problem(&content).is_none().then(|| ConfigBackup {
+                id,
+                created_at,
+                path: path.display().to_string(),
+            })
+        })
+        .collect();
+    backups.sort_by_key(|b| std::cmp::Reverse(b.created_at));
+    backups
+}
+
+/// Checks openclaw.json; when it doesn't parse, preserves a copy and describes the problem.
+pub fn check_config_integrity() -> Result<Option<ConfigCorruption>, String> {
+    let path = openclaw_config::openclaw_config_path();
+    let Ok(content) = fs::read(&path) else {
+        return Ok(None);
+    };
+    let Some((error, line, column)) = parse_problem(&String::from_utf8_lossy(&content)) else {
+        return Ok(None);
+    };
+    let preserved_as = preserve_broken_config()?;
+    Ok(Some(ConfigCorruption {
+        path: path.display().to_string(),
+        error,
+        line,
+        column,
+        preserved_as: preserved_as.map(|p| p.display().to_string()),
+        backups: list_config_backups(),
+    }))
+}
+
+/// Replaces openclaw.json with a backup: `backup_id`, else the newest that parses. A broken
+/// current file is preserved first. Returns the backup used.
+pub fn restore_from_backup(backup_id: Option<&str>) -> Result<ConfigBackup, String> {
+    let backups = list_config_backups();
+    let backup = match backup_id {
+        Some(id) => backups
+            .into_iter()
+            .find(|b| b.id == id)
+            .ok_or_else(|| format!("No usable backup {}", id))?,
+        None => backups
+            .into_iter()
+            .next()
+            .ok_or("No backup of openclaw.json to restore")?,
+    };
+    preserve_broken_config()?;
+    let content = fs::read_to_string(&backup.path).map_err(|e| e.to_string())?;
+    let root: serde_json::Value = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    openclaw_config::write_config_root(&root)?;
+    Ok(backup)
+}
+
+/// Replaces a broken openclaw.json with an empty config, after preserving it. Refuses when the
+/// file parses, so a working config can't be wiped by accident.
+pub fn start_fresh() -> Result<PathBuf, String> {
+    let preserved =
+        preserve_broken_config()?.ok_or("openclaw.json parses; there is nothing to replace")?;
+    openclaw_config::write_config_root(&openclaw_config::empty_config_root())?;
+    Ok(preserved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_problem() {
+        assert!(parse_problem(r#"{ "models": {} }"#).is_none());
+        let (_, line, column) = parse_problem("{\n  \"models\": {,\n}").unwrap();
+        assert_eq!((line, column), (2, 14));
+        assert_eq!(parse_problem("[]").map(|p| p.1), Some(0));
+        assert_eq!(
+            broken_copy_path(Path::new("/home/u/.openclaw/openclaw.json"), 42),
+            PathBuf::from("/home/u/.openclaw/openclaw.json.broken-42")
+        );
+    }
+}
@@ -0,0 +1,360 @@
+//! Curated openclaw.json starting points ("Ollama-only coder", "Anthropic + local fallback",
+//! "multi-agent research") with a few parameters each, so a new user applies a known-good setup
+//! instead of assembling one field by field. Applying merges the template's models, providers and
+//! limits into the current file after a backup. Rendering is a pure function for unit tests.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::openclaw_config::{self, set_nested};
+use crate::scheduler;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ParamKind {
+    /// A TCP port, 1-65535.
+    Port,
+    /// A model name as its provider knows it, e.g. "qwen3:8b".
+    Model,
+    /// A limit from 1 to 64.
+    Count,
+    /// An API key; empty leaves the provider's environment variable in charge.
+    Secret,
+}
+
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct TemplateParam {
+    pub name: &'static str,
+    pub label: &'static str,
+    pub kind: ParamKind,
+    pub default: &'static str,
+}
+
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct ConfigTemplate {
+    pub id: &'static str,
+    pub label: &'static str,
+    pub description: &'static str,
+    pub params: &'static [TemplateParam],
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AppliedTemplate {
+    pub template: String,
+    pub primary_model: String,
+    pub fallbacks: Vec<String>,
+    /// The backup taken of the previous config.
+    pub backup: Option<String>,
+}
+
+const OLLAMA_PORT: TemplateParam = TemplateParam {
+    name: "ollama_port",
+    label: "Ollama port",
+    kind: ParamKind::Port,
+    default: "11434",
+};
+const ANTHROPIC_KEY: TemplateParam = TemplateParam {
+    name: "anthropic_api_key",
+    label: "Anthropic API key",
+    kind: ParamKind::Secret,
+    default: "",
+};
+
+pub const CONFIG_TEMPLATES: [ConfigTemplate; 3] = [
+    ConfigTemplate {
+        id: "ollama-coder",
+        label: "Ollama-only coder",
+        description: "A local coding model on Ollama with a general local fallback; nothing \
+                      leaves the machine.",
+        params: &[
+            OLLAMA_PORT,
+            TemplateParam {
+                name: "model",
+                label: "Coding model",
+                kind: ParamKind::Model,
+                default: "qwen2.5-coder:7b",
+            },
+            TemplateParam {
+                name: "fallback_model",
+                label: "Fallback model",
+                kind: ParamKind::Model,
+                default: "qwen3:8b",
+            },
+            TemplateParam {
+                name: "max_concurrent",
+                label: "Concurrent runs",
+                kind: ParamKind::Count,
+                default: "2",
+            },
+        ],
+    },
+    ConfigTemplate {
+        id: "anthropic-local-fallback",
+        label: "Anthropic + local fallback",
+        description: "Claude as the primary model, falling back to a local Ollama model when the \
+                      API is unreachable or rate limited.",
+        params: &[
+            TemplateParam {
+                name: "primary_model",
+                label: "Claude model",
+                kind: ParamKind::Model,
+                default: "claude-sonnet-4-5",
+            },
+            ANTHROPIC_KEY,
+            OLLAMA_PORT,
+            TemplateParam {
+                name: "fallback_model",
+                label: "Local fallback model",
+                kind: ParamKind::Model,
+                default: "qwen3:8b",
+            },
+        ],
+    },
+    ConfigTemplate {
+        id: "multi-agent-research",
+        label: "Multi-agent research",
+        description: "Claude orchestrating parallel subagents two levels deep, with a larger \
+                      local model as fallback.",
+        params: &[
+            TemplateParam {
+                name: "primary_model",
+                label: "Claude model",
+                kind: ParamKind::Model,
+                default: "claude-sonnet-4-5",
+            },
+            ANTHROPIC_KEY,
+            OLLAMA_PORT,
+            TemplateParam {
+                name: "local_model",
+                label: "Local fallback model",
+                kind: ParamKind::Model,
+                default: "qwen3:14b",
+            },
+            TemplateParam {
+                name: "subagents",
+                label: "Parallel subagents",
+                kind: ParamKind::Count,
+                default: "4",
+            },
+        ],
+    },
+];
+
+#[must_use]
+pub fn find_template(id: &str) -> Option<&'static ConfigTemplate> {
+    CONFIG_TEMPLATES
+        .iter()
+        .find(|t| t.id.eq_ignore_ascii_case(id))
+}
+
+/// Every parameter of `template`: the given value (trimmed) or the default, validated.
+fn resolve_params(
+    template: &ConfigTemplate,
+    params: &HashMap<String, String>,
+) -> Result<HashMap<&'static str, String>, String> {
+    if let Some(unknown) = params
+        .keys()
+        .find(|k| !template.params.iter().any(|p| p.name == k.as_str()))
+    {
+        return Err(format!(
+            "Unknown parameter for {}: {}",
+            template.id, unknown
+        ));
+    }
+    let mut values = HashMap::new();
+    for param in template.params {
+        let value = params
+            .get(param.name)
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+            .unwrap_or(param.default)
+            .to_string();
+        let valid = match param.kind {
+            ParamKind::Port => value.parse::<u16>().is_ok_and(|p| p > 0),
+            ParamKind::Model => !value.is_empty() && !value.contains(char::is_whitespace),
+            ParamKind::Count => value.parse::<u32>().is_ok_and(|n| (1..=64).contains(&n)),
+            ParamKind::Secret => true,
+        };
+        if !valid {
+            return Err(format!("Invalid {}: {}", param.label.to_lowercase(), value));
+        }
+        values.insert(param.name, value);
+    }
+    Ok(values)
+}
+
+/// "<provider>/<model>" for a model parameter, accepting values that already carry the prefix.
+fn model_id(provider: &str, model: &str) -> String {
+    let model = model
+        .strip_prefix(provider)
+        .and_then(|m| m.strip_prefix('/'))
+        .unwrap_or(model);
+    format!("{}/{}", provider, model)
+}
+
+/// Registers local models under the ollama provider pointed at `port`.
+fn add_ollama_models(root: &mut Value, port: &str, models: &[&str]) -> Result<Vec<String>, String> {
+    let mut ids = Vec::new();
+    for model in models {
+        let id = model_id("ollama", model);
+        let name = &id["ollama/".len()..];
+        openclaw_config::register_provider_model(root, "ollama", name)?;
+        ids.push(id);
+    }
+    set_nested(
+        root,
+        &["models", "providers", "ollama", "baseUrl"],
+        Value::from(format!("http://127.0.0.1:{}/v1", port)),
+    );
+    Ok(ids)
+}
+
+/// Allows a built-in Anthropic model; a key, when given, goes into openclaw.json's env.
+fn add_anthropic_model(root: &mut Value, model: &str, key: &str) -> String {
+    let id = model_id("anthropic", model);
+    let listed = root
+        .pointer("/agents/defaults/models")
+        .is_some_and(|m| m.get(&id).is_some());
+    if !listed {
+        set_nested(
+            root,
+            &["agents", "defaults", "models", &id],
+            serde_json::json!({}),
+        );
+    }
+    if !key.is_empty() {
+        set_nested(root, &["env", "ANTHROPIC_API_KEY"], Value::from(key));
+    }
+    id
+}
+
+/// Merges `template` with `params` into `root`: its models and providers, primary and fallbacks,
+/// and concurrency limits. Everything else in `root` is kept.
+pub fn render_template(
+    root: &mut Value,
+    template: &ConfigTemplate,
+    params: &HashMap<String, String>,
+) -> Result<AppliedTemplate, String> {
+    if !root.is_object() {
+        return Err("openclaw.json root not an object".to_string());
+    }
+    let values = resolve_params(template, params)?;
+    let value = |name: &str| values.get(name).map_or("", String::as_str);
+    let port = value("ollama_port");
+    let (primary, fallbacks, max_concurrent, subagents) = match template.id {
+        "ollama-coder" => {
+            let mut ids =
+                add_ollama_models(root, port, &[value("model"), value("fallback_model")])?;
+            ids.dedup();
+            let primary = ids.remove(0);
+            let max_concurrent: u32 = value("max_concurrent").parse().unwrap_or(1);
+            (primary, ids, max_concurrent, (1, 1, 2))
+        }
+        "anthropic-local-fallback" => {
+            let primary =
+                add_anthropic_model(root, value("primary_model"), value("anthropic_api_key"));
+            let fallbacks = add_ollama_models(root, port, &[value("fallback_model")])?;
+            (primary, fallbacks, 4, (4, 1, 5))
+        }
+        "multi-agent-research" => {
+            let primary =
+                add_anthropic_model(root, value("primary_model"), value("anthropic_api_key"));
+            let fallbacks = add_ollama_models(root, port, &[value("local_model")])?;
+            let n: u32 = value("subagents").parse().unwrap_or(4);
+            (primary, fallbacks, 2, (n, 2, n))
+        }
+        other => return Err(format!("Template {} has no renderer", other)),
+    };
+    set_nested(
+        root,
+        &["agents", "defaults", "model"],
+        serde_json::json!({ "primary": primary, "fallbacks": fallbacks }),
+    );
+    set_nested(
+        root,
+        &["agents", "defaults", "maxConcurrent"],
+        Value::from(max_concurrent),
+    );
+    let (sub_concurrent, spawn_depth, children) = subagents;
+    set_nested(
+        root,
+        &["agents", "defaults", "subagents"],
+        serde_json::json!({
+            "maxConcurrent": sub_concurrent,
+            "maxSpawnDepth": spawn_depth,
+            "maxChildrenPerAgent": children
+        }),
+    );
+    Ok(AppliedTemplate {
+        template: template.id.to_string(),
+        primary_model: primary,
+        fallbacks,
+        backup: None,
+    })
+}
+
+/// Backs up the current configs, then applies the template to openclaw.json.
+pub fn apply_config_template(
+    id: &str,
+    params: &HashMap<String, String>,
+) -> Result<AppliedTemplate, String> {
+    let template = find_template(id).ok_or_else(|| {
+        let ids: Vec<&str> = CONFIG_TEMPLATES.iter().map(|t| t.id).collect();
+        format!("Unknown config template {}; known: {}", id, ids.join(", "))
+    })?;
+    let mut root = openclaw_config::read_config_root()?;
+    let mut applied = render_template(&mut root, template, params)?;
+    if openclaw_config::openclaw_config_path().exists() {
+        let dir = scheduler::backup_configs()?;
+        applied.backup = Some(dir.display().to_string());
+    }
+    openclaw_config::write_config_root(&root)?;
+    Ok(applied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        let mut root = serde_json::json!({ "gateway": { "port": 18789 } });
+        let params = HashMap::from([
+            ("ollama_port".to_string(), "11500".to_string()),
+            (
+                "fallback_model".to_string(),
+                "ollama/llama3.2:3b".to_string(),
+            ),
+        ]);
+        let template = find_template("anthropic-local-fallback").unwrap();
+        let applied = render_template(&mut root, template, &params).unwrap();
+        assert_eq!(applied.primary_model, "anthropic/claude-sonnet-4-5");
+        assert_eq!(applied.fallbacks, vec!["ollama/llama3.2:3b"]);
+        assert_eq!(root["gateway"]["port"], 18789);
+        assert_eq!(
+            root["models"]["providers"]["ollama"]["baseUrl"],
+            "http://127.0.0.1:11500/v1"
+        );
+        assert_eq!(
+            root["agents"]["defaults"]["model"]["fallbacks"][0],
+            "ollama/llama3.2:3b"
+        );
+        assert!(root["agents"]["defaults"]["models"]
+            .get("anthropic/claude-sonnet-4-5")
+            .is_some());
+        assert!(root.get("env").is_none());
+
+        let coder = find_template("ollama-coder").unwrap();
+        let mut fresh = openclaw_config::empty_config_root();
+        let applied = render_template(&mut fresh, coder, &HashMap::new()).unwrap();
+        assert_eq!(applied.primary_model, "ollama/qwen2.5-coder:7b");
+        assert_eq!(applied.fallbacks, vec!["ollama/qwen3:8b"]);
+        assert_eq!(fresh["agents"]["defaults"]["maxConcurrent"], 2);
+
+        let bad_port = HashMap::from([("ollama_port".to_string(), "99999".to_string())]);
+        assert!(render_template(&mut fresh, coder, &bad_port).is_err());
+        let unknown = HashMap::from([("port".to_string(), "1".to_string())]);
+        assert!(render_template(&mut fresh, coder, &unknown).is_err());
+    }
+}
@@ -0,0 +1,416 @@
+//! Spend estimates from agent session usage: per provider, model and agent over a period, plus
+//! what a month of the same traffic would cost on the configured primary and fallbacks.
+//! Prices come from a model's `cost` in openclaw.json, OpenRouter's catalog for openrouter
+//! models, or a built-in table of list prices; local runtimes are free. Pricing and aggregation
+//! are pure functions for unit tests.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::agent_usage::{self, AgentUsage, ModelUsage, UsagePeriod};
+use crate::openrouter::{self, OpenRouterModel, OPENROUTER_PROVIDER};
+use crate::{agents, openclaw_config};
+
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+/// Cached input is billed as a fraction of the input price (Anthropic and OpenAI both discount
+/// reads to about a tenth; Anthropic charges a premium for writes).
+const CACHE_READ_FACTOR: f64 = 0.1;
+const CACHE_WRITE_FACTOR: f64 = 1.25;
+
+/// List prices in USD per million tokens (input, output), matched by longest model-id prefix.
+const BUILTIN_PRICES: [(&str, f64, f64); 22] = [
+    ("claude-opus-4-5", 5.0, 25.0),
+    ("claude-opus-4", 15.0, 75.0),
+    ("claude-sonnet-4", 3.0, 15.0),
+    ("claude-3-7-sonnet", 3.0, 15.0),
+    ("claude-haiku-4", 1.0, 5.0),
+    ("claude-3-5-haiku", 0.8, 4.0),
+    ("gpt-5-nano", 0.05, 0.4),
+    ("gpt-5-mini", 0.25, 2.0),
+    ("gpt-5", 1.25, 10.0),
+    ("gpt-4.1-nano", 0.1, 0.4),
+    ("gpt-4.1-mini", 0.4, 1.6),
+    ("gpt-4.1", 2.0, 8.0),
+    ("gpt-4o-mini", 0.15, 0.6),
+    ("gpt-4o", 2.5, 10.0),
+    ("o4-mini", 1.1, 4.4),
+    ("o3", 2.0, 8.0),
+    ("deepseek-chat", 0.28, 0.42),
+    ("deepseek-reasoner", 0.28, 0.42),
+    ("mistral-large", 2.0, 6.0),
+    ("mistral-small", 0.1, 0.3),
+    ("llama-3.3-70b-versatile", 0.59, 0.79),
+    ("llama-3.1-8b-instant", 0.05, 0.08),
+];
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ModelPrice {
+    pub input_per_mtok: f64,
+    pub output_per_mtok: f64,
+}
+
+impl ModelPrice {
+    const FREE: ModelPrice = ModelPrice {
+        input_per_mtok: 0.0,
+        output_per_mtok: 0.0,
+    };
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct CostLine {
+    /// Provider name, "provider/model" or agent name.
+    pub key: String,
+    pub requests: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ModelProjection {
+    pub model: String,
+    /// None when the model has no known price.
+    pub usd_per_month: Option<f64>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ConfigProjection {
+    /// Period traffic scaled to 30 days.
+    pub monthly_input_tokens: u64,
+    pub monthly_output_tokens: u64,
+    /// All of that traffic on the primary.
+    pub primary: Option<ModelProjection>,
+    /// All of that traffic on each fallback, for comparison.
+    pub fallbacks: Vec<ModelProjection>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CostEstimate {
+    pub period: UsagePeriod,
+    pub total_usd: f64,
+    /// Most expensive first.
+    pub by_provider: Vec<CostLine>,
+    pub by_model: Vec<CostLine>,
+    pub by_agent: Vec<CostLine>,
+    /// Models with usage but no price; their cost counts as 0.
+    pub unpriced_models: Vec<String>,
+    pub projection: ConfigProjection,
+}
+
+/// List price from the built-in table, for a model id with or without provider prefixes.
+#[must_use]
+pub fn builtin_price(model: &str) -> Option<ModelPrice> {
+    let name = model.rsplit('/').next().unwrap_or(model).to_lowercase();
+    BUILTIN_PRICES
+        .iter()
+        .filter(|(prefix, ..)| name.starts_with(prefix))
+        .max_by_key(|(prefix, ..)| prefix.len())
+        .map(|(_, input, output)| ModelPrice {
+            input_per_mtok: *input,
+            output_per_mtok: *output,
+        })
+}
+
+/// Price for "provider/model": free on local runtimes, else the model's `cost` in openclaw.json,
+/// then the OpenRouter catalog, then the built-in table.
+#[must_use]
+pub fn price_for(
+    model_id: &str,
+    providers: &Value,
+    openrouter_models: &[OpenRouterModel],
+) -> Option<ModelPrice> {
+    let (provider, model) = model_id.split_once('/')?;
+    let entry = providers.get(provider);
+    let base_url = entry
+        .and_then(|e| e.get("baseUrl"))
+        .and_then(|v| v.as_str());
+    if openclaw_config::is_local_provider(provider, base_url) {
+        return Some(ModelPrice::FREE);
+    }
+    let configured = entry
+        .and_then(|e| e.get("models"))
+        .and_then(|m| m.as_array())
+        .and_then(|models| {
+            models
+                .iter()
+                .find(|m| m.get("id").and_then(|v| v.as_str()) == Some(model))
+        })
+        .and_then(|m| m.get("cost"))
+        .and_then(|cost| {
+            Some(ModelPrice {
+                input_per_mtok: cost.get("input")?.as_f64()?,
+                output_per_mtok: cost.get("output")?.as_f64()?,
+            })
+        });
+    configured
+        .or_else(|| {
+            (provider == OPENROUTER_PROVIDER)
+                .then(|| openrouter_models.iter().find(|m| m.id == model))
+                .flatten()
+                .and_then(|m| {
+                    Some(ModelPrice {
+                        input_per_mtok: m.input_price_per_mtok?,
+                        output_per_mtok: m.output_price_per_mtok?,
+                    })
+                })
+        })
+        .or_else(|| builtin_price(model))
+}
+
+/// Cost of `usage` at `price`, cache tokens included.
+#[must_use]
+pub fn usage_cost(usage: &ModelUsage, price: &ModelPrice) -> f64 {
+    let input = usage.input_tokens as f64
+        + usage.cache_read_tokens as f64 * CACHE_READ_FACTOR
+        + usage.cache_write_tokens as f64 * CACHE_WRITE_FACTOR;
+    (input * price.input_per_mtok + usage.output_tokens as f64 * price.output_per_mtok) / 1e6
+}
+
+fn add_line(lines: &mut HashMap<String, CostLine>, key: &str, usage: &ModelUsage, cost: f64) {
+    let line = lines.entry(key.to_string()).or_insert_with(|| CostLine {
+        key: key.to_string(),
+        ..Default::default()
+    });
+    line.requests += usage.requests;
+    line.input_tokens += usage.input_tokens + usage.cache_read_tokens + usage.cache_write_tokens;
+    line.output_tokens += usage.output_tokens;
+    line.cost_usd += cost;
+}
+
+fn sorted_lines(lines: HashMap<String, CostLine>) -> Vec<CostLine> {
+    let mut lines: Vec<CostLine> = lines.into_values().collect();
+    lines.sort_by(|a, b| {
+        b.cost_usd
+            .total_cmp(&a.cost_usd)
+            .then_with(|| a.key.cmp(&b.key))
+    });
+    lines
+}
+
+/// Days the usage covers: the period's window, or the span of the data for `All`.
+fn covered_days(period: UsagePeriod, usages: &[AgentUsage]) -> f64 {
+    match period.window_ms() {
+        Some(ms) => (ms / DAY_MS) as f64,
+        None => {
+            let first = usages.iter().filter_map(|u| u.first_ms).min();
+            let last = usages.iter().filter_map(|u| u.last_ms).max();
+            match (first, last) {
+                (Some(f), Some(l)) => ((l - f) as f64 / DAY_MS as f64).max(1.0),
+                _ => 30.0,
+            }
+        }
+    }
+}
+
+/// Aggregates agent usage into costs. A cost reported in the transcript wins over the price
+/// lookup. `primary` and `fallbacks` are the configured model ids.
+#[must_use]
+pub fn estimate_from_usage(
+    period: UsagePeriod,
+    usages: &[AgentUsage],
+    price_of: impl Fn(&str) -> Option<ModelPrice>,
+    primary: Option<&str>,
+    fallbacks: &[String],
+) -> CostEstimate {
+    let (mut by_provider, mut by_model, mut by_agent) =
+        (HashMap::new(), HashMap::new(), HashMap::new());
+    let mut unpriced: Vec<String> = Vec::new();
+    let mut totals = ModelUsage::default();
+    let mut total_usd = 0.0;
+    for agent in usages {
+        for usage in &agent.by_model {
+            let cost = if usage.cost_usd > 0.0 {
+                usage.cost_usd
+            } else if let Some(price) = price_of(&usage.model) {
+                usage_cost(usage, &price)
+            } else {
+                if !unpriced.contains(&usage.model) {
+                    unpriced.push(usage.model.clone());
+                }
+                0.0
+            };
+            let provider = usage.model.split_once('/').map_or("unknown", |(p, _)| p);
+            add_line(&mut by_provider, provider, usage, cost);
+            add_line(&mut by_model, &usage.model, usage, cost);
+            add_line(&mut by_agent, &agent.agent_name, usage, cost);
+            totals.input_tokens += usage.input_tokens;
+            totals.output_tokens += usage.output_tokens;
+            totals.cache_read_tokens += usage.cache_read_tokens;
+            totals.cache_write_tokens += usage.cache_write_tokens;
+            total_usd += cost;
+        }
+    }
+    unpriced.sort();
+
+    let scale = 30.0 / covered_days(period, usages);
+    let monthly = ModelUsage {
+        input_tokens: (totals.input_tokens as f64 * scale) as u64,
+        output_tokens: (totals.output_tokens as f64 * scale) as u64,
+        cache_read_tokens: (totals.cache_read_tokens as f64 * scale) as u64,
+        cache_write_tokens: (totals.cache_write_tokens as f64 * scale) as u64,
+        ..Default::default()
+    };
+    let project = |model: &str| ModelProjection {
+        model: model.to_string(),
+        usd_per_month: price_of(model).map(|p| usage_cost(&monthly, &p)),
+    };
+    CostEstimate {
+        period,
+        total_usd,
+        by_provider: sorted_lines(by_provider),
+        by_model: sorted_lines(by_model),
+        by_agent: sorted_lines(by_agent),
+        unpriced_models: unpriced,
+        projection: ConfigProjection {
+            monthly_input_tokens: monthly.input_tokens
+                + monthly.cache_read_tokens
+                + monthly.cache_write_tokens,
+            monthly_output_tokens: monthly.output_tokens,
+            primary: primary.map(project),
+            fallbacks: fallbacks.iter().map(|m| project(m)).collect(),
+        },
+    }
+}
+
+/// Estimates spend over `period` for all agents. The OpenRouter catalog is only fetched when an
+/// openrouter model needs a price.
+pub fn estimate_costs(period: UsagePeriod) -> Result<CostEstimate, String> {
+    let usages: Vec<AgentUsage> = agents::list_agent_names()
+        .iter()
+        .filter_map(|name| agent_usage::get_agent_usage(name, period).ok())
+        .collect();
+    let root = openclaw_config::read_config_root()?;
+    let providers = root
+        .pointer("/models/providers")
+        .cloned()
+        .unwrap_or(serde_json::json!({}));
+    let primary = root
+        .pointer("/agents/defaults/model/primary")
+        .and_then(|v| v.as_str());
+    let fallbacks: Vec<String> = root
+        .pointer("/agents/defaults/model/fallbacks")
+        .and_then(|v| v.as_array())
+        .map(|a| {
+            a.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let prefix = format!("{}/", OPENROUTER_PROVIDER);
+    let needs_openrouter = usages
+        .iter()
+        .flat_map(|u| &u.by_model)
+        .map(|m| m.model.as_str())
+        .chain(primary)
+        .chain(fallbacks.iter().map(String::as_str))
+        .any(|m| m.starts_with(&prefix) && price_for(m, &providers, &[]).is_none());
+    let openrouter_models = if needs_openrouter {
+        openrouter::get_openrouter_models().unwrap_or_default()
+    } else {
+        vec![]
+    };
+
+    Ok(estimate_from_usage(
+        period,
+        &usages,
+        |model| price_for(model, &providers, &openrouter_models),
+        primary,
+        &fallbacks,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price_for() {
+        let providers = serde_json::json!({
+            "lan": { "baseUrl": "http://localhost:9000/v1" },
+            "openrouter": { "models": [{ "id": "x/priced", "cost": { "input": 1.0, "output": 2.0 } }] }
+        });
+        assert_eq!(
+            price_for("ollama/qwen3:8b", &providers, &[]),
+            Some(ModelPrice::FREE)
+        );
+        assert_eq!(
+            price_for("lan/anything", &providers, &[]),
+            Some(ModelPrice::FREE)
+        );
+        assert_eq!(
+            price_for("openrouter/x/priced", &providers, &[])
+                .unwrap()
+                .output_per_mtok,
+            2.0
+        );
+        assert_eq!(
+            price_for("anthropic/claude-opus-4-5-20251101", &providers, &[])
+                .unwrap()
+                .input_per_mtok,
+            5.0
+        );
+        assert_eq!(
+            builtin_price("openrouter/openai/gpt-5-mini")
+                .unwrap()
+                .output_per_mtok,
+            2.0
+        );
+        assert!(price_for("acme/unknown", &providers, &[]).is_none());
+    }
+
+    #[test]
+    fn test_estimate_from_usage() {
+        let usage = |model: &str, input, output, cost_usd| ModelUsage {
+            model: model.to_string(),
+            requests: 1,
+            input_tokens: input,
+            output_tokens: output,
+            cost_usd,
+            ..Default::default()
+        };
+        let agent = |name: &str, by_model| AgentUsage {
+            agent_name: name.to_string(),
+            period: UsagePeriod::Week,
+            totals: ModelUsage::default(),
+            by_model,
+            sessions: 1,
+            first_ms: None,
+            last_ms: None,
+        };
+        let usages = vec![
+            agent(
+                "main",
+                vec![
+                    usage("openai/gpt-5", 1_000_000, 100_000, 0.0),
+                    usage("ollama/qwen3:8b", 5_000_000, 1_000_000, 0.0),
+                ],
+            ),
+            agent(
+                "coder",
+                vec![
+                    usage("anthropic/claude-sonnet-4-5", 0, 0, 0.5),
+                    usage("acme/mystery", 10, 10, 0.0),
+                ],
+            ),
+        ];
+        let price = |m: &str| price_for(m, &serde_json::json!({}), &[]);
+        let est = estimate_from_usage(
+            UsagePeriod::Week,
+            &usages,
+            price,
+            Some("openai/gpt-5"),
+            &["ollama/qwen3:8b".to_string()],
+        );
+        assert!((est.total_usd - 2.75).abs() < 1e-9);
+        assert_eq!(est.by_agent[0].key, "main");
+        assert!((est.by_agent[0].cost_usd - 2.25).abs() < 1e-9);
+        assert_eq!(est.by_provider[0].key, "openai");
+        assert_eq!(est.unpriced_models, vec!["acme/mystery"]);
+        let primary = est.projection.primary.unwrap();
+        assert_eq!(est.projection.monthly_input_tokens, 25_714_328);
+        assert!(primary.usd_per_month.unwrap() > 40.0);
+        assert_eq!(est.projection.fallbacks[0].usd_per_month, Some(0.0));
+    }
+}
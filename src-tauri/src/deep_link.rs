@@ -0,0 +1,362 @@
+//! `openclaw-config://` deep links that open the app on a panel, e.g.
+//! `openclaw-config://agent/main/sync` or `openclaw-config://gateway/logs?window=new`. A link only
+//! navigates: its action is offered to the user in that panel, never run by the backend, since any
+//! web page can open one. Links that arrive before the frontend listens (the one that launched the
+//! app) are queued until it takes them on load; later ones are emitted as `navigate` events.
+//! `window=new` opens the panel in a window of its own. Parsing and routes are pure functions for
+//! unit tests.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard};
+
+use crate::agents;
+
+pub const SCHEME: &str = "openclaw-config";
+/// Event the frontend listens to for links arriving while it runs.
+pub const NAVIGATE_EVENT: &str = "navigate";
+const MAX_URL_LEN: usize = 2048;
+
+/// A panel a link may open: its name, whether a target (agent, model, host) follows, and the
+/// actions it accepts after that.
+struct Panel {
+    name: &'static str,
+    target: Option<&'static str>,
+    actions: &'static [&'static str],
+}
+
+const PANELS: &[Panel] = &[
+    Panel {
+        name: "dashboard",
+        target: None,
+        actions: &[],
+    },
+    Panel {
+        name: "agents",
+        target: None,
+        actions: &["new", "import"],
+    },
+    Panel {
+        name: "agent",
+        target: Some("an agent name"),
+        actions: &["sync", "validate", "env", "instructions", "export"],
+    },
+    Panel {
+        name: "models",
+        target: None,
+        actions: &["catalog", "huggingface"],
+    },
+    Panel {
+        name: "model",
+        target: Some("a model id"),
+        actions: &["pin", "benchmark"],
+    },
+    Panel {
+        name: "gateway",
+        target: None,
+        actions: &["start", "stop", "restart", "logs"],
+    },
+    Panel {
+        name: "remote",
+        target: Some("a remote host name"),
+        actions: &["detect"],
+    },
+    Panel {
+        name: "history",
+        target: None,
+        actions: &[],
+    },
+    Panel {
+        name: "policy",
+        target: None,
+        actions: &[],
+    },
+    Panel {
+        name: "diagnostics",
+        target: None,
+        actions: &[],
+    },
+    Panel {
+        name: "settings",
+        target: None,
+        actions: &[],
+    },
+    Panel {
+        name: "setup",
+        target: None,
+        actions: &[],
+    },
+];
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct Navigation {
+    /// "agent", "gateway", ...
+    pub panel: String,
+    /// Agent name, model id or remote host name, for panels that take one.
+    pub target: Option<String>,
+    /// What the panel should offer to do, e.g. "sync".
+    pub action: Option<String>,
+    /// Other query parameters, passed through to the panel.
+    pub params: BTreeMap<String, String>,
+    /// Open in a window of its own (`?window=new`).
+    pub new_window: bool,
+}
+
+impl Navigation {
+    /// The frontend route, e.g. "/agent/main/sync"; segments are percent-encoded.
+    #[must_use]
+    pub fn route(&self) -> String {
+        let segments = [
+            Some(&self.panel),
+            self.target.as_ref(),
+            self.action.as_ref(),
+        ];
+        let path: Vec<String> = segments
+            .into_iter()
+            .flatten()
+            .map(|s| encode_segment(s))
+            .collect();
+        let query: Vec<String> = self
+            .params
+            .iter()
+            .map(|(k, v)| format!("{}={}", encode_segment(k), encode_segment(v)))
+            .collect();
+        if query.is_empty() {
+            format!("/{}", path.join("/"))
+        } else {
+            format!("/{}?{}", path.join("/"), query.join("&"))
+        }
+    }
+
+    /// Label of the window this navigation opens with `window=new`, one per panel and target so
+    /// a second link to the same place focuses it instead of stacking windows.
+    #[must_use]
+    pub fn window_label(&self) -> String {
+        let mut label = format!("panel-{}", self.panel);
+        if let Some(target) = &self.target {
+            label.push('-');
+            label.push_str(target);
+        }
+        label
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    }
+
+    /// Title for a panel window, e.g. "OpenClaw Config: agent main".
+    #[must_use]
+    pub fn window_title(&self) -> String {
+        match &self.target {
+            Some(target) => format!("OpenClaw Config: {} {}", self.panel, target),
+            None => format!("OpenClaw Config: {}", self.panel),
+        }
+    }
+}
+
+fn encode_segment(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for b in s.bytes() {
+        if b.is_ascii_alphanumeric() || b"-._~:@".contains(&b) {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{:02X}", b));
+        }
+    }
+    out
+}
+
+fn decode_component(s: &str, plus_is_space: bool) -> Result<String, String> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let hex = s
+                    .get(i + 1..i + 3)
+                    .and_then(|h| u8::from_str_radix(h, 16).ok())
+                    .ok_or_else(|| format!("Bad escape in deep link: {}", s))?;
+                out.push(hex);
+                i += 3;
+            }
+            b'+' if plus_is_space => {
+                out.push(b' ');
+                i += 1;
+            }
+            b => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+    let decoded = String::from_utf8(out).map_err(|_| format!("Deep link is not UTF-8: {}", s))?;
+    if decoded.chars().any(char::is_control) {
+        return Err(format!("Deep link has control characters: {}", s));
+    }
+    Ok(decoded)
+}
+
+/// Parses an `openclaw-config://panel[/target][/action][?key=value...]` link.
+pub fn parse_deep_link(url: &str) -> Result<Navigation, String> {
+    let url = url.trim();
+    if url.len() > MAX_URL_LEN {
+        return Err("Deep link is too long".to_string());
+    }
+    let rest = url
+        .split_once(':')
+        .filter(|(scheme, _)| scheme.eq_ignore_ascii_case(SCHEME))
+        .map(|(_, rest)| rest.trim_start_matches('/'))
+        .ok_or_else(|| format!("Not an {}:// link: {}", SCHEME, url))?;
+    let rest = rest.split('#').next().unwrap_or_default();
+    let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+
+    let segments = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| decode_component(s, false))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut segments = segments.into_iter();
+    let name = segments
+        .next()
+        .map(|s| s.to_ascii_lowercase())
+        .unwrap_or_else(|| "dashboard".to_string());
+    let panel = PANELS
+        .iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| format!("Unknown panel in deep link: {}", name))?;
+
+    let target = match panel.target {
+        Some(what) => {
+            let target = segments
+                .next()
+                .ok_or_else(|| format!("{} link needs {}", panel.name, what))?;
+            if panel.name == "agent" {
+                agents::validate_agent_name(&target)?;
+            }
+            Some(target)
+        }
+        None => None,
+    };
+    let action = segments.next().map(|a| a.to_ascii_lowercase());
+    if let Some(action) = &action {
+        if !panel.actions.contains(&action.as_str()) {
+            return Err(format!(
+                "Unknown {} action in deep link: {}",
+                panel.name, action
+            ));
+        }
+    }
+    if let Some(extra) = segments.next() {
+        return Err(format!("Unexpected segment in deep link: {}", extra));
+    }
+
+    let mut nav = Navigation {
+        panel: panel.name.to_string(),
+        target,
+        action,
+        ..Navigation::default()
+    };
+    for pair in query.split('&').filter(|p| !p.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let key = decode_component(key, true)?;
+        let value = decode_component(value, true)?;
+        if key == "window" {
+            nav.new_window = value == "new";
+        } else {
+            nav.params.insert(key, value);
+        }
+    }
+    Ok(nav)
+}
+
+#[derive(Default)]
+struct Queue {
+    listening: bool,
+    pending: Option<Navigation>,
+}
+
+/// Holds a link until the main window's frontend is listening for `navigate` events.
+#[derive(Default)]
+pub struct NavigationState {
+    queue: Mutex<Queue>,
+}
+
+impl NavigationState {
+    fn lock(&self) -> MutexGuard<'_, Queue> {
+        self.queue.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Queues `nav` while the frontend isn't listening yet (the newest link wins). Returns true
+    /// when it should be emitted right away instead.
+    pub fn push(&self, nav: Navigation) -> bool {
+        let mut queue = self.lock();
+        if queue.listening {
+            return true;
+        }
+        queue.pending = Some(nav);
+        false
+    }
+
+    /// Called by the frontend once its `navigate` listener is attached: the queued link, if any.
+    /// Later links are emitted instead of queued.
+    pub fn take_pending(&self) -> Option<Navigation> {
+        let mut queue = self.lock();
+        queue.listening = true;
+        queue.pending.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_deep_link() {
+        let nav = parse_deep_link("openclaw-config://agent/main/sync").unwrap();
+        assert_eq!(nav.panel, "agent");
+        assert_eq!(nav.target.as_deref(), Some("main"));
+        assert_eq!(nav.action.as_deref(), Some("sync"));
+        assert_eq!(nav.route(), "/agent/main/sync");
+
+        let nav = parse_deep_link(
+            "OpenClaw-Config://model/ollama%2Fqwen3:8b/benchmark?runs=3&window=new",
+        )
+        .unwrap();
+        assert_eq!(nav.target.as_deref(), Some("ollama/qwen3:8b"));
+        assert_eq!(nav.params["runs"], "3");
+        assert!(nav.new_window);
+        assert_eq!(nav.route(), "/model/ollama%2Fqwen3:8b/benchmark?runs=3");
+        assert_eq!(nav.window_label(), "panel-model-ollama_qwen3_8b");
+
+        assert_eq!(
+            parse_deep_link("openclaw-config://").unwrap().panel,
+            "dashboard"
+        );
+        assert!(parse_deep_link("https://agent/main").is_err());
+        assert!(parse_deep_link("openclaw-config://agent").is_err());
+        assert!(parse_deep_link("openclaw-config://agent/..%2Fetc").is_err());
+        assert!(parse_deep_link("openclaw-config://agent/main/delete").is_err());
+        assert!(parse_deep_link("openclaw-config://gateway/start/now").is_err());
+        assert!(parse_deep_link("openclaw-config://settings?x=%0A").is_err());
+    }
+
+    #[test]
+    fn test_navigation_state_queues_until_listening() {
+        let state = NavigationState::default();
+        let nav = |panel: &str| Navigation {
+            panel: panel.to_string(),
+            ..Navigation::default()
+        };
+        assert!(!state.push(nav("gateway")));
+        assert!(!state.push(nav("settings")));
+        assert_eq!(state.take_pending().unwrap().panel, "settings");
+        assert!(state.push(nav("history")));
+        assert_eq!(state.take_pending(), None);
+    }
+}
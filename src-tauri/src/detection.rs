@@ -1,12 +1,16 @@
-//! Local LLM runtime detection (Ollama, LM Studio, vLLM).
+//! Local LLM runtime detection (Ollama, LM Studio, vLLM), honouring the endpoint, binary path
+//! and disabled-runtime overrides in settings.
 //! Parsing and pure logic are separated for unit testing.
 
 use serde::{Deserialize, Serialize};
-use std::net::{SocketAddr, TcpStream};
+use std::net::{TcpStream, ToSocketAddrs};
 use std::process::Command;
 use std::time::Duration;
 
-#[derive(Clone, Serialize, Deserialize)]
+use crate::operations::{self, VERSION_TIMEOUT};
+use crate::{lm_studio, models_available, ollama, settings};
+
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub struct LLMStatus {
     pub installed: bool,
     pub running: bool,
@@ -16,7 +20,7 @@ pub struct LLMStatus {
     pub path: Option<String>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub struct LocalLLMDetection {
     pub ollama: LLMStatus,
     pub lm_studio: LLMStatus,
@@ -36,13 +40,39 @@ pub fn parse_version_line(stdout: &str) -> Option<String> {
 
 /// Returns true if something is listening on host:port (TCP).
 pub fn port_open(host: &str, port: u16) -> bool {
-    let addr = format!("{}:{}", host, port);
-    addr.parse::<SocketAddr>()
+    (host, port)
+        .to_socket_addrs()
         .ok()
+        .and_then(|mut addrs| addrs.next())
         .and_then(|a| TcpStream::connect_timeout(&a, Duration::from_millis(500)).ok())
         .is_some()
 }
 
+/// Host and port of an http(s) URL; the port defaults from the scheme.
+#[must_use]
+pub fn url_host_port(url: &str) -> Option<(String, u16)> {
+    let (scheme, rest) = url.split_once("://")?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let authority = authority.rsplit('@').next()?;
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) if !port.contains(']') => (host, Some(port.parse().ok()?)),
+        _ => (authority, None),
+    };
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let port = match (port, scheme) {
+        (Some(port), _) => port,
+        (None, "https") => 443,
+        (None, "http") => 80,
+        _ => return None,
+    };
+    (!host.is_empty()).then(|| (host.to_string(), port))
+}
+
+/// Whether the server at a runtime's base URL accepts connections.
+fn endpoint_open(url: &str) -> bool {
+    url_host_port(url).is_some_and(|(host, port)| port_open(&host, port))
+}
+
 /// Runs `command -v CMD` (Unix) or `where CMD` (Windows) and returns the first path line.
 pub fn command_exists(cmd: &str) -> Option<String> {
     let out = if cfg!(target_os = "windows") {
@@ -83,13 +113,12 @@ pub fn lms_path() -> Option<String> {
 }
 
 pub fn detect_ollama() -> LLMStatus {
-    let path = command_exists("ollama");
+    let path = settings::binary_path("ollama").or_else(|| command_exists("ollama"));
     let installed = path.is_some();
-    let running = port_open("127.0.0.1", 11434);
+    let running = endpoint_open(&ollama::ollama_base_url());
     let version = if installed {
-        Command::new("ollama")
-            .args(["--version"])
-            .output()
+        let cmd = path.as_deref().unwrap_or("ollama");
+        operations::run_command(Command::new(cmd).args(["--version"]), VERSION_TIMEOUT, None)
             .ok()
             .filter(|o| o.status.success())
             .and_then(|o| parse_version_line(&String::from_utf8_lossy(&o.stdout)))
@@ -105,14 +134,14 @@ pub fn detect_ollama() -> LLMStatus {
 }
 
 pub fn detect_lm_studio() -> LLMStatus {
-    let path = lms_path().or_else(|| command_exists("lms"));
+    let path = settings::binary_path("lms")
+        .or_else(lms_path)
+        .or_else(|| command_exists("lms"));
     let installed = path.is_some();
-    let running = port_open("127.0.0.1", 1234);
+    let running = endpoint_open(&lm_studio::lm_studio_base_url());
     let version = if installed {
         let cmd = path.as_ref().map(String::as_str).unwrap_or("lms");
-        Command::new(cmd)
-            .args(["--version"])
-            .output()
+        operations::run_command(Command::new(cmd).args(["--version"]), VERSION_TIMEOUT, None)
             .ok()
             .filter(|o| o.status.success())
             .and_then(|o| parse_version_line(&String::from_utf8_lossy(&o.stdout)))
@@ -128,15 +157,18 @@ pub fn detect_lm_studio() -> LLMStatus {
 }
 
 pub fn detect_vllm() -> LLMStatus {
-    let out = Command::new("python3")
-        .args(["-c", "import vllm; print(getattr(vllm, '__version__', 'unknown'))"])
-        .output()
-        .or_else(|_| {
-            Command::new("python")
-                .args(["-c", "import vllm; print(getattr(vllm, '__version__', 'unknown'))"])
-                .output()
-        })
-        .ok();
+    // Importing vllm pulls in torch, which can take a while on a cold start.
+    let probe = |python: &str| {
+        operations::run_command(
+            Command::new(python).args([
+                "-c",
+                "import vllm; print(getattr(vllm, '__version__', 'unknown'))",
+            ]),
+            operations::LIST_TIMEOUT,
+            None,
+        )
+    };
+    let out = probe("python3").or_else(|_| probe("python")).ok();
     let (installed, version) = match out {
         Some(o) if o.status.success() => {
             let v = String::from_utf8_lossy(&o.stdout).trim().to_string();
@@ -144,7 +176,7 @@ pub fn detect_vllm() -> LLMStatus {
         }
         _ => (false, None),
     };
-    let running = port_open("127.0.0.1", 8000);
+    let running = endpoint_open(&models_available::configured_vllm_base_url());
     LLMStatus {
         installed,
         running,
@@ -153,11 +185,19 @@ pub fn detect_vllm() -> LLMStatus {
     }
 }
 
+/// Detects each runtime not turned off in settings; a disabled one reports as not installed.
 pub fn detect_local_llms() -> LocalLLMDetection {
+    let detect = |runtime: &str, detect: fn() -> LLMStatus| {
+        if settings::runtime_disabled(runtime) {
+            LLMStatus::default()
+        } else {
+            detect()
+        }
+    };
     LocalLLMDetection {
-        ollama: detect_ollama(),
-        lm_studio: detect_lm_studio(),
-        vllm: detect_vllm(),
+        ollama: detect("ollama", detect_ollama),
+        lm_studio: detect("lmstudio", detect_lm_studio),
+        vllm: detect("vllm", detect_vllm),
     }
 }
 
@@ -173,6 +213,23 @@ mod tests {
         assert_eq!(parse_version_line("\n\n"), None);
     }
 
+    #[test]
+    fn test_url_host_port() {
+        assert_eq!(
+            url_host_port("http://127.0.0.1:11434"),
+            Some(("127.0.0.1".to_string(), 11434))
+        );
+        assert_eq!(
+            url_host_port("https://user:pw@gpu-box/v1"),
+            Some(("gpu-box".to_string(), 443))
+        );
+        assert_eq!(
+            url_host_port("http://[::1]:8000/v1"),
+            Some(("::1".to_string(), 8000))
+        );
+        assert_eq!(url_host_port("gpu-box:8000"), None);
+    }
+
     #[test]
     fn test_port_open_closed() {
         // Port 0 is invalid for connect; use a high port that's very unlikely to be in use.
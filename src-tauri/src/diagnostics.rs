@@ -0,0 +1,377 @@
+//! Diagnostics bundle for bug reports: versions, runtime detection, system info, config files
+//! with secrets redacted, and the tail of recent log files (redacted the same way line by line),
+//! zipped into one file. Zipping uses
+//! the system `zip`, or bsdtar where `zip` is missing (macOS and Windows 10+ ship it as `tar`).
+
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+
+use crate::detection::{self, LocalLLMDetection};
+use crate::{agents, gateway, openclaw_config, operations, system};
+
+pub(crate) const REDACTED: &str = "[redacted]";
+/// Object keys whose string values (and everything below them) are treated as secrets.
+const SECRET_KEY_PARTS: [&str; 6] = [
+    "key",
+    "token",
+    "secret",
+    "password",
+    "authorization",
+    "cookie",
+];
+/// Only the end of each log file is included.
+const LOG_TAIL_BYTES: u64 = 512 * 1024;
+const MAX_LOG_FILES: usize = 10;
+
+#[derive(Serialize)]
+struct Versions {
+    app: &'static str,
+    os: String,
+    openclaw_cli: Option<String>,
+    ollama: Option<String>,
+    lm_studio: Option<String>,
+    vllm: Option<String>,
+    created_at: u64,
+}
+
+/// Whether a key (config field, env var, header) names a secret; shared by every place that
+/// masks or redacts values.
+#[must_use]
+pub(crate) fn is_secret_key(key: &str) -> bool {
+    let key = key.to_lowercase();
+    SECRET_KEY_PARTS.iter().any(|part| key.contains(part))
+}
+
+/// Replaces non-empty strings stored under secret-looking keys (apiKey, token, env vars like
+/// OPENAI_API_KEY, ...) with a placeholder, at any depth. Numbers and booleans are kept, so
+/// e.g. maxTokens survives.
+pub fn redact_secrets(value: &mut Value) {
+    redact(value, false);
+}
+
+fn redact(value: &mut Value, secret: bool) {
+    match value {
+        Value::String(s) if secret && !s.is_empty() => *s = REDACTED.to_string(),
+        Value::Object(obj) => {
+            for (k, v) in obj.iter_mut() {
+                redact(v, secret || is_secret_key(k));
+            }
+        }
+        Value::Array(items) => {
+            for v in items {
+                redact(v, secret);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Redacts secrets in log text. JSON lines go through `redact_secrets`; in other lines the value
+/// after a secret-looking `key=` or `key:` is replaced (numbers and booleans are kept), as is the
+/// token after `Bearer`.
+#[must_use]
+pub fn redact_log_text(text: &str) -> String {
+    text.split_inclusive('\n')
+        .map(|line| {
+            let (body, newline) = match line.strip_suffix('\n') {
+                Some(body) => (body, "\n"),
+                None => (line, ""),
+            };
+            redact_log_line(body) + newline
+        })
+        .collect()
+}
+
+fn redact_log_line(line: &str) -> String {
+    if let Ok(mut value) = serde_json::from_str::<Value>(line.trim()) {
+        if value.is_object() {
+            redact_secrets(&mut value);
+            return value.to_string();
+        }
+    }
+    let line = redact_bearer(line);
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line.as_str();
+    while let Some(i) = rest.find([':', '=']) {
+        let (before, after) = rest.split_at(i + 1);
+        out.push_str(before);
+        rest = after;
+        let key = before[..i]
+            .trim_end()
+            .trim_end_matches('"')
+            .rsplit(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.')))
+            .next()
+            .unwrap_or("");
+        if key.is_empty() || !is_secret_key(key) {
+            continue;
+        }
+        let value = after.trim_start();
+        out.push_str(&after[..after.len() - value.len()]);
+        let (quote, body) = match value.strip_prefix('"') {
+            Some(body) => ("\"", body),
+            None => ("", value),
+        };
+        let end = if quote.is_empty() {
+            body.find(|c: char| c.is_whitespace() || matches!(c, ',' | ';' | '}' | '&'))
+                .unwrap_or(body.len())
+        } else {
+            body.find('"').unwrap_or(body.len())
+        };
+        let token = &body[..end];
+        if token.is_empty() || token.parse::<f64>().is_ok() || token.parse::<bool>().is_ok() {
+            rest = value;
+            continue;
+        }
+        out.push_str(quote);
+        out.push_str(REDACTED);
+        rest = &body[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn redact_bearer(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(i) = rest.find("Bearer ") {
+        let (before, after) = rest.split_at(i + "Bearer ".len());
+        out.push_str(before);
+        let end = after
+            .find(|c: char| c.is_whitespace() || matches!(c, '"' | ',' | '\''))
+            .unwrap_or(after.len());
+        if end > 0 {
+            out.push_str(REDACTED);
+        }
+        rest = &after[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn write_json(path: &Path, value: &impl Serialize) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    fs::write(
+        path,
+        serde_json::to_string_pretty(value).map_err(|e| e.to_string())?,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Copies a JSON file with secrets redacted; unparseable files are noted instead of copied.
+fn copy_sanitized(src: &Path, dest: &Path) -> Result<(), String> {
+    let Ok(content) = fs::read_to_string(src) else {
+        return Ok(());
+    };
+    let value = match serde_json::from_str::<Value>(&content) {
+        Ok(mut v) => {
+            redact_secrets(&mut v);
+            v
+        }
+        Err(e) => serde_json::json!({ "unparseable": e.to_string() }),
+    };
+    write_json(dest, &value)
+}
+
+fn log_dirs() -> Vec<PathBuf> {
+    vec![
+        agents::openclaw_root().join("logs"),
+        openclaw_config::host_config_dir().join("logs"),
+        std::env::temp_dir().join("openclaw"),
+    ]
+}
+
+/// The most recently modified *.log files in the known log directories.
+pub(crate) fn recent_logs() -> Vec<PathBuf> {
+    let mut logs: Vec<(SystemTime, PathBuf)> = log_dirs()
+        .iter()
+        .filter_map(|d| fs::read_dir(d).ok())
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "log"))
+        .filter_map(|p| Some((fs::metadata(&p).ok()?.modified().ok()?, p)))
+        .collect();
+    logs.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+    logs.into_iter()
+        .take(MAX_LOG_FILES)
+        .map(|(_, p)| p)
+        .collect()
+}
+
+/// The last `LOG_TAIL_BYTES` of a log file.
+pub(crate) fn read_log_tail(path: &Path) -> Result<Vec<u8>, String> {
+    let mut file = fs::File::open(path).map_err(|e| e.to_string())?;
+    let len = file.metadata().map_err(|e| e.to_string())?.len();
+    file.seek(SeekFrom::Start(len.saturating_sub(LOG_TAIL_BYTES)))
+        .map_err(|e| e.to_string())?;
+    let mut tail = Vec::new();
+    file.read_to_end(&mut tail).map_err(|e| e.to_string())?;
+    Ok(tail)
+}
+
+fn copy_log_tail(src: &Path, dest: &Path) -> Result<(), String> {
+    let tail = read_log_tail(src)?;
+    fs::write(dest, redact_log_text(&String::from_utf8_lossy(&tail))).map_err(|e| e.to_string())
+}
+
+fn versions(detected: &LocalLLMDetection) -> Versions {
+    Versions {
+        app: env!("CARGO_PKG_VERSION"),
+        os: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+        openclaw_cli: system::command_stdout(&gateway::openclaw_bin(), &["--version"])
+            .and_then(|out| detection::parse_version_line(&out)),
+        ollama: detected.ollama.version.clone(),
+        lm_studio: detected.lm_studio.version.clone(),
+        vllm: detected.vllm.version.clone(),
+        created_at: operations::now_secs(),
+    }
+}
+
+/// Fills `stage` with the bundle contents.
+fn collect(stage: &Path) -> Result<(), String> {
+    let detected = detection::detect_local_llms();
+    write_json(&stage.join("versions.json"), &versions(&detected))?;
+    write_json(&stage.join("detection.json"), &detected)?;
+    write_json(
+        &stage.join("system.json"),
+        &system::get_system_info_with_gpu(),
+    )?;
+    write_json(&stage.join("disks.json"), &system::get_disk_space())?;
+
+    let config = stage.join("config");
+    let root = agents::openclaw_root();
+    copy_sanitized(
+        &openclaw_config::openclaw_config_path(),
+        &config.join("openclaw.json"),
+    )?;
+    copy_sanitized(&root.join("config.json"), &config.join("config.json"))?;
+    for name in ["settings.json", "notifications.json", "setup.json"] {
+        copy_sanitized(
+            &openclaw_config::host_config_dir().join(name),
+            &config.join("host-config").join(name),
+        )?;
+    }
+    for agent in agents::list_agent_names() {
+        copy_sanitized(
+            &agents::agent_models_path(&agent),
+            &config.join("agents").join(&agent).join("models.json"),
+        )?;
+    }
+
+    let logs = stage.join("logs");
+    fs::create_dir_all(&logs).map_err(|e| e.to_string())?;
+    for log in recent_logs() {
+        if let Some(name) = log.file_name() {
+            // A log that can't be read is left out rather than failing the whole bundle.
+            let _ = copy_log_tail(&log, &logs.join(name));
+        }
+    }
+    Ok(())
+}
+
+fn run_zip(stage: &Path, path: &Path) -> Result<(), String> {
+    let zip = Command::new("zip")
+        .arg("-qr")
+        .arg(path)
+        .arg(".")
+        .current_dir(stage)
+        .output();
+    if matches!(&zip, Ok(out) if out.status.success()) {
+        return Ok(());
+    }
+    let stage_str = stage.to_string_lossy().to_string();
+    // GNU tar (Linux's `tar`) would write a plain tar under the .zip name.
+    let tars: &[&str] = if cfg!(target_os = "linux") {
+        &["bsdtar"]
+    } else {
+        &["bsdtar", "tar"]
+    };
+    for tar in tars {
+        if let Ok(out) = Command::new(tar)
+            .args(["-a", "-cf"])
+            .arg(path)
+            .args(["-C", &stage_str, "."])
+            .output()
+        {
+            if out.status.success() {
+                return Ok(());
+            }
+        }
+    }
+    Err("Creating the zip needs `zip` or bsdtar".to_string())
+}
+
+/// Writes the diagnostics bundle to `path` (a .zip) and returns its absolute path. A relative
+/// `path` is taken from the current directory; zipping runs inside the stage directory, which is
+/// removed afterwards.
+pub fn export_diagnostics(path: &str) -> Result<String, String> {
+    let path = std::path::absolute(path).map_err(|e| e.to_string())?;
+    let stage = openclaw_config::host_config_dir()
+        .join("diagnostics")
+        .join(operations::now_secs().to_string());
+    let _ = fs::remove_dir_all(&stage);
+    fs::create_dir_all(&stage).map_err(|e| e.to_string())?;
+    let _ = fs::remove_file(&path);
+    let result = collect(&stage).and_then(|()| run_zip(&stage, &path));
+    let _ = fs::remove_dir_all(&stage);
+    result.map(|()| path.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_secrets() {
+        let mut root = serde_json::json!({
+            "env": { "OPENAI_API_KEY": "sk-123" },
+            "models": { "providers": { "anthropic": {
+                "baseUrl": "https://api.anthropic.com",
+                "apiKey": "sk-ant-456",
+                "models": [{ "id": "claude", "maxTokens": 8192 }]
+            }, "ollama": { "apiKey": "" } } },
+            "api_keys": { "helius": "h-789", "jupiter": null },
+            "gateway": { "auth": { "token": "t" }, "port": 8080 }
+        });
+        redact_secrets(&mut root);
+        assert_eq!(root["env"]["OPENAI_API_KEY"], REDACTED);
+        let anthropic = &root["models"]["providers"]["anthropic"];
+        assert_eq!(anthropic["apiKey"], REDACTED);
+        assert_eq!(anthropic["baseUrl"], "https://api.anthropic.com");
+        assert_eq!(anthropic["models"][0]["maxTokens"], 8192);
+        assert_eq!(root["models"]["providers"]["ollama"]["apiKey"], "");
+        assert_eq!(root["api_keys"]["helius"], REDACTED);
+        assert!(root["api_keys"]["jupiter"].is_null());
+        assert_eq!(root["gateway"]["auth"]["token"], REDACTED);
+        assert_eq!(root["gateway"]["port"], 8080);
+    }
+
+    #[test]
+    fn test_redact_log_text() {
+        let log = concat!(
+            "INFO start apiKey=sk-123 maxTokens=8192 port: 8080\n",
+            "DEBUG header Authorization: Bearer abc.def\n",
+            "{\"level\":\"info\",\"token\":\"t-1\",\"msg\":\"ok\"}\n",
+            "WARN \"password\": \"hunter2\", user=bob"
+        );
+        let redacted = redact_log_text(log);
+        let lines: Vec<&str> = redacted.lines().collect();
+        assert_eq!(
+            lines[0],
+            "INFO start apiKey=[redacted] maxTokens=8192 port: 8080"
+        );
+        assert!(!lines[1].contains("abc.def"));
+        let json: Value = serde_json::from_str(lines[2]).unwrap();
+        assert_eq!(json["token"], REDACTED);
+        assert_eq!(json["msg"], "ok");
+        assert_eq!(lines[3], "WARN \"password\": \"[redacted]\", user=bob");
+        assert!(redacted.ends_with("user=bob"));
+    }
+}
@@ -0,0 +1,340 @@
+//! Disk usage of local models: Ollama models dir, LM Studio models folder, GGUF files referenced in openclaw.json.
+//! Manifest parsing and naming are separated for unit tests.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::openclaw_config;
+use crate::system;
+
+/// Disk usage of a single model.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ModelDiskEntry {
+    /// "ollama", "lmstudio" or "gguf".
+    pub runtime: String,
+    pub name: String,
+    pub path: String,
+    pub size_bytes: u64,
+    pub size_human: String,
+}
+
+/// A scanned models location and the free space on its volume.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DiskUsageLocation {
+    pub runtime: String,
+    pub path: String,
+    pub total_bytes: u64,
+    pub total_human: String,
+    pub free_bytes: Option<u64>,
+    pub free_human: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ModelDiskUsage {
+    /// Per-model sizes, largest first.
+    pub models: Vec<ModelDiskEntry>,
+    pub locations: Vec<DiskUsageLocation>,
+    pub total_bytes: u64,
+    pub total_human: String,
+}
+
+/// Ollama models directory: $OLLAMA_MODELS, ~/.ollama/models, or the Linux service location.
+#[must_use]
+pub fn ollama_models_dir() -> Option<PathBuf> {
+    if let Ok(p) = std::env::var("OLLAMA_MODELS") {
+        let p = PathBuf::from(p);
+        if p.is_dir() {
+            return Some(p);
+        }
+    }
+    let candidates = [
+        dirs::home_dir().map(|h| h.join(".ollama").join("models")),
+        Some(PathBuf::from("/usr/share/ollama/.ollama/models")),
+    ];
+    candidates.into_iter().flatten().find(|p| p.is_dir())
+}
+
+/// LM Studio models folder: ~/.lmstudio/models or the legacy ~/.cache/lm-studio/models.
+#[must_use]
+pub fn lm_studio_models_dir() -> Option<PathBuf> {
+    let home = dirs::home_dir()?;
+    [
+        home.join(".lmstudio").join("models"),
+        home.join(".cache").join("lm-studio").join("models"),
+    ]
+    .into_iter()
+    .find(|p| p.is_dir())
+}
+
+/// Recursive size of a file or directory in bytes. Symlinks are not followed.
+#[must_use]
+pub fn path_size(path: &Path) -> u64 {
+    let meta = match fs::symlink_metadata(path) {
+        Ok(m) => m,
+        Err(_) => return 0,
+    };
+    if meta.is_file() {
+        return meta.len();
+    }
+    if !meta.is_dir() {
+        return 0;
+    }
+    fs::read_dir(path)
+        .map(|rd| {
+            rd.filter_map(|e| e.ok())
+                .map(|e| path_size(&e.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// Sums layer and config sizes from an Ollama manifest JSON.
+#[must_use]
+pub fn parse_ollama_manifest_size(body: &str) -> u64 {
+    let root: serde_json::Value = match serde_json::from_str(body) {
+        Ok(v) => v,
+        Err(_) => return 0,
+    };
+    let layers: u64 = root
+        .get("layers")
+        .and_then(|l| l.as_array())
+        .map(|a| {
+            a.iter()
+                .filter_map(|l| l.get("size").and_then(|s| s.as_u64()))
+                .sum()
+        })
+        .unwrap_or(0);
+    let config = root
+        .get("config")
+        .and_then(|c| c.get("size"))
+        .and_then(|s| s.as_u64())
+        .unwrap_or(0);
+    layers + config
+}
+
+/// Model name from a manifest path relative to manifests/ (host/namespace/model/tag),
+/// matching how `ollama list` displays it.
+#[must_use]
+pub fn ollama_manifest_name(components: &[String]) -> Option<String> {
+    match components {
+        [host, namespace, model, tag] => Some(if host == "registry.ollama.ai" {
+            if namespace == "library" {
+                format!("{}:{}", model, tag)
+            } else {
+                format!("{}/{}:{}", namespace, model, tag)
+            }
+        } else {
+            format!("{}/{}/{}:{}", host, namespace, model, tag)
+        }),
+        _ => None,
+    }
+}
+
+fn collect_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    if let Ok(rd) = fs::read_dir(dir) {
+        for e in rd.filter_map(|e| e.ok()) {
+            let p = e.path();
+            if p.is_dir() {
+                collect_files(&p, out);
+            } else {
+                out.push(p);
+            }
+        }
+    }
+}
+
+fn ollama_entries(models_dir: &Path) -> Vec<ModelDiskEntry> {
+    let manifests = models_dir.join("manifests");
+    let mut files = Vec::new();
+    collect_files(&manifests, &mut files);
+    files
+        .into_iter()
+        .filter_map(|f| {
+            let rel: Vec<String> = f
+                .strip_prefix(&manifests)
+                .ok()?
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect();
+            let name = ollama_manifest_name(&rel)?;
+            let size = parse_ollama_manifest_size(&fs::read_to_string(&f).ok()?);
+            Some(entry("ollama", name, &f, size))
+        })
+        .collect()
+}
+
+/// LM Studio stores models as <publisher>/<model>/<files>; each model directory is one entry.
+fn lm_studio_entries(models_dir: &Path) -> Vec<ModelDiskEntry> {
+    let mut out = Vec::new();
+    let publishers = match fs::read_dir(models_dir) {
+        Ok(rd) => rd,
+        Err(_) => return out,
+    };
+    for publisher in publishers
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+    {
+        if let Ok(models) = fs::read_dir(publisher.path()) {
+            for model in models.filter_map(|e| e.ok()) {
+                let name = format!(
+                    "{}/{}",
+                    publisher.file_name().to_string_lossy(),
+                    model.file_name().to_string_lossy()
+                );
+                let path = model.path();
+                out.push(entry("lmstudio", name, &path, path_size(&path)));
+            }
+        }
+    }
+    out
+}
+
+/// Collects every string value in the config that looks like a .gguf path.
+#[must_use]
+pub fn gguf_paths_in_config(root: &serde_json::Value) -> Vec<String> {
+    let mut out = Vec::new();
+    collect_gguf_strings(root, &mut out);
+    out.sort();
+    out.dedup();
+    out
+}
+
+fn collect_gguf_strings(v: &serde_json::Value, out: &mut Vec<String>) {
+    match v {
+        serde_json::Value::String(s) if s.to_lowercase().ends_with(".gguf") => out.push(s.clone()),
+        serde_json::Value::Array(a) => a.iter().for_each(|x| collect_gguf_strings(x, out)),
+        serde_json::Value::Object(o) => o.values().for_each(|x| collect_gguf_strings(x, out)),
+        _ => {}
+    }
+}
+
+pub(crate) fn expand_home(p: &str) -> PathBuf {
+    match (p.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(p),
+    }
+}
+
+fn entry(runtime: &str, name: String, path: &Path, size: u64) -> ModelDiskEntry {
+    ModelDiskEntry {
+        runtime: runtime.to_string(),
+        name,
+        path: path.to_string_lossy().to_string(),
+        size_bytes: size,
+        size_human: system::bytes_to_human(size),
+    }
+}
+
+fn location(runtime: &str, path: &Path, total: u64) -> DiskUsageLocation {
+    let free = system::volume_space_for_path(path).map(|(free, _)| free);
+    DiskUsageLocation {
+        runtime: runtime.to_string(),
+        path: path.to_string_lossy().to_string(),
+        total_bytes: total,
+        total_human: system::bytes_to_human(total),
+        free_bytes: free,
+        free_human: free.map(system::bytes_to_human),
+    }
+}
+
+/// Scans all model locations and returns per-model sizes, per-location totals and free space.
+#[must_use]
+pub fn get_model_disk_usage() -> ModelDiskUsage {
+    let mut models = Vec::new();
+    let mut locations = Vec::new();
+
+    if let Some(dir) = ollama_models_dir() {
+        models.extend(ollama_entries(&dir));
+        // Blobs are shared between tags, so the location total is the real on-disk size.
+        locations.push(location("ollama", &dir, path_size(&dir)));
+    }
+    if let Some(dir) = lm_studio_models_dir() {
+        let entries = lm_studio_entries(&dir);
+        let total = entries.iter().map(|e| e.size_bytes).sum();
+        models.extend(entries);
+        locations.push(location("lmstudio", &dir, total));
+    }
+    let root = openclaw_config::read_config_root().unwrap_or(serde_json::json!({}));
+    for p in gguf_paths_in_config(&root) {
+        let path = expand_home(&p);
+        if path.is_file() {
+            let size = path_size(&path);
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or(p);
+            models.push(entry("gguf", name, &path, size));
+            locations.push(location("gguf", &path, size));
+        }
+    }
+
+    models.sort_by_key(|m| std::cmp::Reverse(m.size_bytes));
+    let total: u64 = locations.iter().map(|l| l.total_bytes).sum();
+    ModelDiskUsage {
+        models,
+        locations,
+        total_bytes: total,
+        total_human: system::bytes_to_human(total),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ollama_manifest_size() {
+        let manifest = r#"{
+            "config": { "digest": "sha256:a", "size": 485 },
+            "layers": [
+                { "digest": "sha256:b", "size": 2019377376 },
+                { "digest": "sha256:c", "size": 1429 }
+            ]
+        }"#;
+        assert_eq!(
+            parse_ollama_manifest_size(manifest),
+            2019377376 + 1429 + 485
+        );
+        assert_eq!(parse_ollama_manifest_size("not json"), 0);
+    }
+
+    #[test]
+    fn test_ollama_manifest_name() {
+        let c = |s: &[&str]| s.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            ollama_manifest_name(&c(&["registry.ollama.ai", "library", "llama3.2", "3b"]))
+                .as_deref(),
+            Some("llama3.2:3b")
+        );
+        assert_eq!(
+            ollama_manifest_name(&c(&["registry.ollama.ai", "bob", "coder", "latest"])).as_deref(),
+            Some("bob/coder:latest")
+        );
+        assert_eq!(
+            ollama_manifest_name(&c(&["hf.co", "org", "repo", "Q4_K_M"])).as_deref(),
+            Some("hf.co/org/repo:Q4_K_M")
+        );
+        assert_eq!(ollama_manifest_name(&c(&["too", "short"])), None);
+    }
+
+    #[test]
+    fn test_gguf_paths_in_config() {
+        let root = serde_json::json!({
+            "models": { "providers": { "llamacpp": { "modelPath": "~/models/qwen.Q4_K_M.GGUF" } } },
+            "other": ["/opt/a.gguf", "not-a-model.txt", "/opt/a.gguf"]
+        });
+        assert_eq!(
+            gguf_paths_in_config(&root),
+            vec![
+                "/opt/a.gguf".to_string(),
+                "~/models/qwen.Q4_K_M.GGUF".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_path_size_missing_is_zero() {
+        assert_eq!(path_size(Path::new("/definitely/not/here")), 0);
+    }
+}
@@ -0,0 +1,444 @@
+//! End-to-end health check: config files parse, the openclaw CLI and gateway are there, every
+//! provider's baseUrl answers, agents are in sync with openclaw.json, and the primary model is
+//! installed locally or has a key. Each check comes back pass/warn/fail with a suggested fix.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::time::Duration;
+
+use crate::agents::{self, AgentHealth};
+use crate::catalog::{self, CatalogQuery, RUNTIME_OLLAMA, RUNTIME_REMOTE};
+use crate::{detection, gateway, openclaw_config, system};
+
+/// Ordered from best to worst, so the worst of a list is its max.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct DoctorCheck {
+    /// Stable identifier, e.g. "openclaw_cli" or "provider:ollama".
+    pub id: String,
+    pub status: CheckStatus,
+    pub message: String,
+    pub fix: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DoctorReport {
+    /// The worst status of all checks.
+    pub status: CheckStatus,
+    pub checks: Vec<DoctorCheck>,
+}
+
+fn check(id: &str, status: CheckStatus, message: String, fix: Option<&str>) -> DoctorCheck {
+    DoctorCheck {
+        id: id.to_string(),
+        status,
+        message,
+        fix: fix.map(String::from),
+    }
+}
+
+fn check_openclaw_json() -> DoctorCheck {
+    let id = "config:openclaw.json";
+    if !openclaw_config::openclaw_config_path().exists() {
+        return check(
+            id,
+            CheckStatus::Warn,
+            "openclaw.json does not exist yet".to_string(),
+            Some("Run setup or save settings in the OpenClaw tab to create it"),
+        );
+    }
+    match openclaw_config::read_config_root() {
+        Ok(root) if root.is_object() => check(
+            id,
+            CheckStatus::Pass,
+            "openclaw.json parses".to_string(),
+            None,
+        ),
+        Ok(_) => check(
+            id,
+            CheckStatus::Fail,
+            "openclaw.json is not a JSON object".to_string(),
+            Some("Restore openclaw.json from a backup"),
+        ),
+        Err(e) => check(
+            id,
+            CheckStatus::Fail,
+            format!("openclaw.json does not parse: {}", e),
+            Some("Fix the JSON syntax or restore openclaw.json from a backup"),
+        ),
+    }
+}
+
+fn check_gateway_config() -> DoctorCheck {
+    let id = "config:config.json";
+    match fs::read_to_string(agents::openclaw_root().join("config.json")) {
+        Err(_) => check(
+            id,
+            CheckStatus::Pass,
+            "config.json not present; defaults apply".to_string(),
+            None,
+        ),
+        Ok(content) => match serde_json::from_str::<Value>(&content) {
+            Ok(_) => check(
+                id,
+                CheckStatus::Pass,
+                "config.json parses".to_string(),
+                None,
+            ),
+            Err(e) => check(
+                id,
+                CheckStatus::Fail,
+                format!("config.json does not parse: {}", e),
+                Some("Fix the JSON syntax or delete config.json to use defaults"),
+            ),
+        },
+    }
+}
+
+fn check_agent_files() -> Vec<DoctorCheck> {
+    agents::list_agents_detailed()
+        .into_iter()
+        .map(|a| {
+            let id = format!("config:agents/{}", a.name);
+            match a.health {
+                AgentHealth::Ok => check(
+                    &id,
+                    CheckStatus::Pass,
+                    format!("{}'s models.json parses", a.name),
+                    None,
+                ),
+                _ => check(
+                    &id,
+                    CheckStatus::Fail,
+                    format!(
+                        "{}: {}",
+                        a.name,
+                        a.problem
+                            .unwrap_or_else(|| "models.json is unusable".to_string())
+                    ),
+                    Some("Use Repair in the Agents tab"),
+                ),
+            }
+        })
+        .collect()
+}
+
+fn check_openclaw_cli() -> DoctorCheck {
+    match system::command_stdout(&gateway::openclaw_bin(), &["--version"])
+        .and_then(|out| detection::parse_version_line(&out))
+    {
+        Some(version) => check(
+            "openclaw_cli",
+            CheckStatus::Pass,
+            format!("openclaw CLI found ({})", version),
+            None,
+        ),
+        None => check(
+            "openclaw_cli",
+            CheckStatus::Fail,
+            "openclaw CLI not found".to_string(),
+            Some("Install it with `npm install -g openclaw` or set its path in settings"),
+        ),
+    }
+}
+
+fn check_gateway() -> DoctorCheck {
+    match gateway::gateway_running() {
+        Ok(true) => check(
+            "gateway",
+            CheckStatus::Pass,
+            "Gateway is running".to_string(),
+            None,
+        ),
+        Ok(false) => check(
+            "gateway",
+            CheckStatus::Fail,
+            "Gateway is not running".to_string(),
+            Some("Start it from the Gateway tab or the tray menu"),
+        ),
+        Err(e) => check(
+            "gateway",
+            CheckStatus::Fail,
+            e,
+            Some("Install the openclaw CLI"),
+        ),
+    }
+}
+
+/// Result of probing a provider's baseUrl: an HTTP status (any status means it answered) or a
+/// transport error.
+#[must_use]
+pub fn provider_check(
+    name: &str,
+    base_url: Option<&str>,
+    probe: Result<u16, String>,
+) -> DoctorCheck {
+    let id = format!("provider:{}", name);
+    let local = openclaw_config::is_local_provider(name, base_url);
+    let Some(url) = base_url.filter(|u| !u.trim().is_empty()) else {
+        return check(
+            &id,
+            if local {
+                CheckStatus::Fail
+            } else {
+                CheckStatus::Warn
+            },
+            format!("{} has no baseUrl", name),
+            Some("Set the provider's baseUrl in openclaw.json"),
+        );
+    };
+    match probe {
+        Ok(status) => check(
+            &id,
+            CheckStatus::Pass,
+            format!("{} answered at {} (HTTP {})", name, url, status),
+            None,
+        ),
+        Err(e) if local => check(
+            &id,
+            CheckStatus::Fail,
+            format!("{} is not reachable at {}: {}", name, url, e),
+            Some("Start the runtime, or fix the port in baseUrl"),
+        ),
+        Err(e) => check(
+            &id,
+            CheckStatus::Fail,
+            format!("{} is not reachable at {}: {}", name, url, e),
+            Some("Check the baseUrl and your network connection"),
+        ),
+    }
+}
+
+fn probe_url(url: &str) -> Result<u16, String> {
+    let agent = ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(3))
+        .build();
+    let url = format!("{}/models", url.trim_end_matches('/'));
+    match agent.get(&url).call() {
+        Ok(resp) => Ok(resp.status()),
+        Err(ureq::Error::Status(status, _)) => Ok(status),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn check_providers(providers: &Value) -> Vec<DoctorCheck> {
+    let Some(obj) = providers.as_object() else {
+        return vec![];
+    };
+    std::thread::scope(|s| {
+        let handles: Vec<_> = obj
+            .iter()
+            .map(|(name, p)| {
+                let base_url = p.get("baseUrl").and_then(|v| v.as_str());
+                s.spawn(move || {
+                    let probe = match base_url {
+                        Some(url) if !url.trim().is_empty() => probe_url(url),
+                        _ => Err("no baseUrl".to_string()),
+                    };
+                    provider_check(name, base_url, probe)
+                })
+            })
+            .collect();
+        handles.into_iter().filter_map(|h| h.join().ok()).collect()
+    })
+}
+
+fn check_agent_sync() -> Vec<DoctorCheck> {
+    agents::list_agent_names()
+        .into_iter()
+        .map(|name| {
+            let id = format!("sync:{}", name);
+            let status = agents::get_provider_sync_status(&name);
+            if status.in_sync {
+                check(
+                    &id,
+                    CheckStatus::Pass,
+                    format!("{} is in sync with openclaw.json", name),
+                    None,
+                )
+            } else {
+                check(
+                    &id,
+                    CheckStatus::Warn,
+                    format!(
+                        "{} differs from openclaw.json (missing: [{}], extra: [{}])",
+                        name,
+                        status.missing_in_agent.join(", "),
+                        status.extra_in_agent.join(", ")
+                    ),
+                    Some("Update the agent's providers in the Agents tab"),
+                )
+            }
+        })
+        .collect()
+}
+
+/// Whether the primary model can be used: installed in a local runtime (`local_ids` are catalog
+/// ids of installed models), or served by a remote provider with an apiKey.
+#[must_use]
+pub fn primary_model_check(
+    primary: Option<&str>,
+    providers: &Value,
+    local_ids: &[String],
+) -> DoctorCheck {
+    let id = "primary_model";
+    let Some(primary) = primary.filter(|p| !p.is_empty()) else {
+        return check(
+            id,
+            CheckStatus::Warn,
+            "No primary model is set".to_string(),
+            Some("Pick a primary model in the OpenClaw tab"),
+        );
+    };
+    let Some((provider, model)) = primary.split_once('/') else {
+        return check(
+            id,
+            CheckStatus::Fail,
+            format!("Primary model {} is not a provider/model id", primary),
+            Some("Pick a primary model in the OpenClaw tab"),
+        );
+    };
+    let Some(entry) = providers.get(provider) else {
+        return check(
+            id,
+            CheckStatus::Fail,
+            format!("Provider {} of {} is not configured", provider, primary),
+            Some("Add the provider to openclaw.json or pick another primary model"),
+        );
+    };
+    let base_url = entry.get("baseUrl").and_then(|v| v.as_str());
+    if openclaw_config::is_local_provider(provider, base_url) {
+        if local_ids.iter().any(|i| i == primary) {
+            return check(
+                id,
+                CheckStatus::Pass,
+                format!("{} is installed", primary),
+                None,
+            );
+        }
+        let is_ollama =
+            catalog::provider_for_runtime(RUNTIME_OLLAMA, providers).as_deref() == Some(provider);
+        let fix = if is_ollama {
+            format!("Run `ollama pull {}`", model)
+        } else {
+            format!("Download or load {} in its runtime", model)
+        };
+        return check(
+            id,
+            CheckStatus::Fail,
+            format!(
+                "{} is not installed (or its runtime is not running)",
+                primary
+            ),
+            Some(&fix),
+        );
+    }
+    match entry.get("apiKey").and_then(|v| v.as_str()) {
+        Some(key) if !key.trim().is_empty() => check(
+            id,
+            CheckStatus::Pass,
+            format!("{} has an API key", provider),
+            None,
+        ),
+        _ => check(
+            id,
+            CheckStatus::Warn,
+            format!(
+                "{} has no apiKey in openclaw.json (fine if an auth profile provides it)",
+                provider
+            ),
+            Some("Set the provider's API key"),
+        ),
+    }
+}
+
+/// Runs every check.
+#[must_use]
+pub fn run_doctor() -> DoctorReport {
+    let root = openclaw_config::read_config_root().unwrap_or(serde_json::json!({}));
+    let providers = root
+        .pointer("/models/providers")
+        .cloned()
+        .unwrap_or(serde_json::json!({}));
+    let primary = root
+        .pointer("/agents/defaults/model/primary")
+        .and_then(|v| v.as_str());
+
+    let mut checks = vec![check_openclaw_json(), check_gateway_config()];
+    checks.extend(check_agent_files());
+    checks.push(check_openclaw_cli());
+    checks.push(check_gateway());
+    checks.extend(check_providers(&providers));
+    checks.extend(check_agent_sync());
+    let local_ids: Vec<String> = catalog::get_model_catalog(&CatalogQuery::default())
+        .into_iter()
+        .filter(|m| m.runtime != RUNTIME_REMOTE)
+        .map(|m| m.id)
+        .collect();
+    checks.push(primary_model_check(primary, &providers, &local_ids));
+
+    DoctorReport {
+        status: checks
+            .iter()
+            .map(|c| c.status)
+            .max()
+            .unwrap_or(CheckStatus::Pass),
+        checks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_primary_model_check() {
+        let providers = serde_json::json!({
+            "ollama": { "baseUrl": "http://127.0.0.1:11434/v1" },
+            "anthropic": { "baseUrl": "https://api.anthropic.com", "apiKey": "sk-ant" },
+            "openai": { "baseUrl": "https://api.openai.com/v1" }
+        });
+        let local = vec!["ollama/qwen2.5:7b".to_string()];
+        let status = |p: Option<&str>| primary_model_check(p, &providers, &local).status;
+        assert_eq!(status(Some("ollama/qwen2.5:7b")), CheckStatus::Pass);
+        assert_eq!(
+            status(Some("anthropic/claude-sonnet-4-5")),
+            CheckStatus::Pass
+        );
+        assert_eq!(status(Some("openai/gpt-5-mini")), CheckStatus::Warn);
+        assert_eq!(status(Some("mistral/large")), CheckStatus::Fail);
+        assert_eq!(status(None), CheckStatus::Warn);
+        let missing = primary_model_check(Some("ollama/llama3.2"), &providers, &local);
+        assert_eq!(missing.status, CheckStatus::Fail);
+        assert_eq!(missing.fix.as_deref(), Some("Run `ollama pull llama3.2`"));
+    }
+
+    #[test]
+    fn test_provider_check() {
+        let local = Some("http://127.0.0.1:11434/v1");
+        assert_eq!(
+            provider_check("ollama", local, Ok(200)).status,
+            CheckStatus::Pass
+        );
+        let remote = Some("https://api.openai.com/v1");
+        assert_eq!(
+            provider_check("openai", remote, Ok(401)).status,
+            CheckStatus::Pass
+        );
+        let down = provider_check("ollama", local, Err("connection refused".to_string()));
+        assert_eq!(down.status, CheckStatus::Fail);
+        assert!(down.fix.unwrap().contains("Start the runtime"));
+        assert_eq!(
+            provider_check("openai", None, Err("no baseUrl".to_string())).status,
+            CheckStatus::Warn
+        );
+    }
+}
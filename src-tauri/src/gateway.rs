@@ -0,0 +1,60 @@
+//! OpenClaw gateway state and control, through the `openclaw` CLI (or the path set in settings).
+
+use std::process::Command;
+
+use crate::settings;
+
+/// The `openclaw` executable: the path set in settings, else `openclaw` on PATH.
+pub fn openclaw_bin() -> String {
+    settings::get_app_settings()
+        .binary_paths
+        .openclaw
+        .unwrap_or_else(|| "openclaw".to_string())
+}
+
+/// Spawns `openclaw gateway <action>` without waiting for it.
+fn spawn_gateway(action: &str) -> Result<(), String> {
+    Command::new(openclaw_bin())
+        .arg("gateway")
+        .arg(action)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to {} gateway: {}", action, e))
+}
+
+pub fn start_gateway() -> Result<String, String> {
+    spawn_gateway("start").map(|()| "Gateway start initiated".to_string())
+}
+
+pub fn stop_gateway() -> Result<String, String> {
+    spawn_gateway("stop").map(|()| "Gateway stop initiated".to_string())
+}
+
+pub fn restart_gateway() -> Result<String, String> {
+    spawn_gateway("restart").map(|()| "Gateway restart initiated".to_string())
+}
+
+/// The gateway count in `openclaw gateway discover --json` output.
+#[must_use]
+pub fn discovered_count(stdout: &str) -> Option<u64> {
+    serde_json::from_str::<serde_json::Value>(stdout)
+        .ok()?
+        .get("count")?
+        .as_u64()
+}
+
+/// Whether `openclaw gateway discover` finds a running gateway.
+pub fn gateway_running() -> Result<bool, String> {
+    match Command::new(openclaw_bin())
+        .arg("gateway")
+        .arg("discover")
+        .arg("--json")
+        .arg("--timeout")
+        .arg("500")
+        .output()
+    {
+        Ok(output) => Ok(output.status.success()
+            && discovered_count(&String::from_utf8_lossy(&output.stdout)).is_some_and(|n| n > 0)),
+        Err(e) => Err(format!("Failed to check gateway status: {}", e)),
+    }
+}
@@ -0,0 +1,161 @@
+//! Background gateway health check on the interval from settings. Every result feeds crash
+//! notifications; a change is pushed to the tray and emitted to the window as "gateway-status",
+//! and a change after the first check also goes to webhooks. The gateway's TLS certificate is
+//! checked every few hours; one close to expiry is emitted as "gateway-cert-expiring" and notified.
+//! Spend limits are enforced every few minutes; a report that hits a limit or changes the model
+//! selection is emitted as "spend-limits" and notified.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager};
+
+use crate::notifications::{NotificationKind, Notifier};
+use crate::webhooks::{self, WebhookEvent};
+use crate::{gateway, gateway_tls, settings, spend_limits, tray};
+
+const CERT_CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+const SPEND_CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Managed state: the last gateway status seen.
+#[derive(Default)]
+pub struct GatewayMonitor {
+    running: Mutex<Option<bool>>,
+    cert_checked_at: Mutex<Option<Instant>>,
+    spend_checked_at: Mutex<Option<Instant>>,
+}
+
+/// Whether `interval` has passed since `checked`; marks it checked when it has.
+fn due(checked: &Mutex<Option<Instant>>, interval: Duration) -> bool {
+    let mut checked = checked.lock().unwrap_or_else(|e| e.into_inner());
+    if checked.is_some_and(|t| t.elapsed() < interval) {
+        return false;
+    }
+    *checked = Some(Instant::now());
+    true
+}
+
+impl GatewayMonitor {
+    #[must_use]
+    pub fn last_status(&self) -> Option<bool> {
+        *self.running.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Stores `running`; returns the previous status.
+    fn record(&self, running: bool) -> Option<bool> {
+        self.running
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .replace(running)
+    }
+
+    fn cert_check_due(&self) -> bool {
+        due(&self.cert_checked_at, CERT_CHECK_INTERVAL)
+    }
+
+    fn spend_check_due(&self) -> bool {
+        due(&self.spend_checked_at, SPEND_CHECK_INTERVAL)
+    }
+}
+
+/// Checks the gateway once and propagates the result.
+pub fn check_now(app: &tauri::AppHandle) -> Result<bool, String> {
+    let running = gateway::gateway_running()?;
+    app.state::<Notifier>().record_gateway_status(app, running);
+    let previous = app.state::<GatewayMonitor>().record(running);
+    if previous != Some(running) {
+        let _ = app.emit("gateway-status", running);
+        tray::refresh(app);
+    }
+    if previous == Some(!running) {
+        let event = if running {
+            WebhookEvent::GatewayUp
+        } else {
+            WebhookEvent::GatewayDown
+        };
+        webhooks::emit(event, serde_json::json!({ "running": running }));
+    }
+    Ok(running)
+}
+
+/// Reports a gateway TLS certificate that expires within `EXPIRY_WARNING_DAYS` or has expired.
+fn check_cert_expiry(app: &tauri::AppHandle) {
+    if !app.state::<GatewayMonitor>().cert_check_due() {
+        return;
+    }
+    let Ok(status) = gateway_tls::get_gateway_tls_status() else {
+        return;
+    };
+    let Some(days) = status.days_left.filter(|_| status.enabled) else {
+        return;
+    };
+    if days > gateway_tls::EXPIRY_WARNING_DAYS {
+        return;
+    }
+    let body = if days < 0 {
+        "The gateway's TLS certificate has expired. Generate a new one in OpenClaw Config."
+            .to_string()
+    } else {
+        format!("The gateway's TLS certificate expires in {} days.", days)
+    };
+    app.state::<Notifier>().notify(
+        app,
+        NotificationKind::CertExpiring,
+        "Gateway certificate",
+        &body,
+    );
+    let _ = app.emit("gateway-cert-expiring", status);
+}
+
+/// Enforces spend limits and reports hits and model selection changes.
+fn check_spend_limits(app: &tauri::AppHandle) {
+    if !app.state::<GatewayMonitor>().spend_check_due() {
+        return;
+    }
+    let Ok(report) = spend_limits::enforce_spend_limits() else {
+        return;
+    };
+    let rate_hit = report
+        .breaches
+        .iter()
+        .any(|b| b.kind != spend_limits::LimitKind::Budget);
+    let body = if !report.suspended.is_empty() {
+        format!(
+            "Budget used up; switched away from {}.",
+            report.suspended.join(", ")
+        )
+    } else if !report.restored.is_empty() {
+        format!(
+            "Spend is back under budget; restored {}.",
+            report.restored.join(", ")
+        )
+    } else if let Some(warning) = report.warnings.first() {
+        warning.clone()
+    } else if rate_hit {
+        "Gateway traffic is over a rate limit set in OpenClaw Config.".to_string()
+    } else {
+        return;
+    };
+    app.state::<Notifier>()
+        .notify(app, NotificationKind::SpendLimit, "Spend limits", &body);
+    let _ = app.emit("spend-limits", report);
+}
+
+/// Checks again shortly after a start/stop/restart so the tray doesn't wait a full interval.
+pub fn check_soon(app: &tauri::AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_secs(1));
+        let _ = check_now(&app);
+    });
+}
+
+/// Polls for the app's lifetime; the interval is re-read from settings after each check.
+pub fn spawn(app: tauri::AppHandle) {
+    std::thread::spawn(move || loop {
+        let _ = check_now(&app);
+        check_cert_expiry(&app);
+        check_spend_limits(&app);
+        let secs = settings::get_app_settings().gateway_poll_secs;
+        std::thread::sleep(Duration::from_secs(u64::from(secs)));
+    });
+}
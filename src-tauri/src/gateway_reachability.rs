@@ -0,0 +1,411 @@
+//! Whether phones and other machines on the LAN can reach the gateway: finds this machine's LAN
+//! address, reads which addresses the gateway port is listening on (`ss`, `lsof` or `netstat`),
+//! connects to it over loopback and over the LAN address, and turns what it finds into problems
+//! with fix hints (loopback-only binding, a restart pending after changing `gateway.bind`, a
+//! host firewall, no auth token). A connection from this machine to its own LAN address doesn't
+//! pass its firewall, so the firewall is checked separately (ufw, Windows Defender Firewall, the
+//! macOS application firewall). Output parsing and the diagnosis are pure functions for unit
+//! tests.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::net::UdpSocket;
+use std::process::Command;
+use std::time::Duration;
+
+use crate::{detection, openclaw_config, operations};
+
+pub const DEFAULT_GATEWAY_PORT: u16 = 18789;
+const TOOL_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GatewayBinding {
+    /// Only 127.0.0.1 / ::1: other devices can't connect.
+    Loopback,
+    /// 0.0.0.0 / :: (every interface).
+    AllInterfaces,
+    /// Specific non-loopback addresses.
+    Specific,
+    NotListening,
+    /// No tool to list listening sockets.
+    Unknown,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FirewallStatus {
+    /// Active without a rule allowing the port (ufw).
+    Blocks,
+    /// On, with per-app rules this can't see (Windows, macOS).
+    MayBlock,
+    /// Off, or allows the port.
+    Open,
+    /// Couldn't be checked (e.g. `ufw status` needs root).
+    Unknown,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ReachabilityProblem {
+    pub message: String,
+    pub fix: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ExternalReachability {
+    /// This machine's address on the LAN, if it has one.
+    pub lan_ip: Option<String>,
+    pub port: u16,
+    /// openclaw.json gateway.bind, if set.
+    pub configured_bind: Option<String>,
+    /// Addresses the port is listening on.
+    pub listen_addresses: Vec<String>,
+    pub binding: GatewayBinding,
+    pub loopback_reachable: bool,
+    pub lan_reachable: bool,
+    pub firewall: FirewallStatus,
+    /// What other devices should connect to when reachable, e.g. "ws://192.168.1.20:18789".
+    pub url: Option<String>,
+    /// Empty when other devices should be able to connect.
+    pub problems: Vec<ReachabilityProblem>,
+}
+
+/// The gateway port from openclaw.json, or the default.
+#[must_use]
+pub fn gateway_port(root: &Value) -> u16 {
+    root.pointer("/gateway/port")
+        .and_then(|p| p.as_u64())
+        .and_then(|p| u16::try_from(p).ok())
+        .unwrap_or(DEFAULT_GATEWAY_PORT)
+}
+
+/// Local addresses listening on `port` in `ss -ltn`, `lsof -iTCP -sTCP:LISTEN` or `netstat -an`
+/// output: the first `host:port` token of each LISTEN line, brackets removed and `*` as
+/// 0.0.0.0.
+#[must_use]
+pub fn parse_listen_addresses(stdout: &str, port: u16) -> Vec<String> {
+    let suffix = format!(":{}", port);
+    let mut out: Vec<String> = Vec::new();
+    for line in stdout.lines().filter(|l| l.contains("LISTEN")) {
+        let Some(host) = line
+            .split_whitespace()
+            .find_map(|token| token.strip_suffix(suffix.as_str()))
+        else {
+            continue;
+        };
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        let host = if host == "*" { "0.0.0.0" } else { host };
+        // ss shows a zone-scoped address as "127.0.0.1%lo".
+        let host = host.split('%').next().unwrap_or(host).to_string();
+        if !out.contains(&host) {
+            out.push(host);
+        }
+    }
+    out
+}
+
+/// How the gateway is bound, from the addresses its port listens on.
+#[must_use]
+pub fn classify_binding(addresses: &[String]) -> GatewayBinding {
+    let is_loopback = |a: &str| a.starts_with("127.") || a == "::1" || a == "localhost";
+    if addresses.is_empty() {
+        GatewayBinding::NotListening
+    } else if addresses.iter().any(|a| a == "0.0.0.0" || a == "::") {
+        GatewayBinding::AllInterfaces
+    } else if addresses.iter().all(|a| is_loopback(a)) {
+        GatewayBinding::Loopback
+    } else {
+        GatewayBinding::Specific
+    }
+}
+
+/// The firewall status for `port` from `ufw status` output.
+#[must_use]
+pub fn parse_ufw_status(stdout: &str, port: u16) -> FirewallStatus {
+    if !stdout.lines().any(|l| l.trim() == "Status: active") {
+        return FirewallStatus::Open;
+    }
+    let allowed = stdout.lines().any(|l| {
+        let mut fields = l.split_whitespace();
+        let target = fields.next().unwrap_or_default();
+        let port_matches = target == port.to_string()
+            || target
+                .strip_suffix("/tcp")
+                .is_some_and(|p| p == port.to_string());
+        port_matches && l.contains("ALLOW")
+    });
+    if allowed {
+        FirewallStatus::Open
+    } else {
+        FirewallStatus::Blocks
+    }
+}
+
+/// The command that allows `port` through the firewall on this OS.
+fn firewall_fix(port: u16) -> String {
+    if cfg!(target_os = "windows") {
+        format!(
+            "Allow it in Windows Defender Firewall, e.g. from an admin prompt: netsh advfirewall \
+             firewall add rule name=\"OpenClaw gateway\" dir=in action=allow protocol=TCP \
+             localport={}",
+            port
+        )
+    } else if cfg!(target_os = "macos") {
+        "Allow incoming connections for openclaw (node) in System Settings > Network > Firewall \
+         > Options"
+            .to_string()
+    } else {
+        format!("Allow the port, e.g. sudo ufw allow {}/tcp", port)
+    }
+}
+
+/// Problems keeping other devices from the gateway, with fixes.
+#[must_use]
+pub fn diagnose(report: &ExternalReachability, has_auth: bool) -> Vec<ReachabilityProblem> {
+    let problem = |message: String, fix: String| ReachabilityProblem { message, fix };
+    let mut out = Vec::new();
+    if report.lan_ip.is_none() {
+        out.push(problem(
+            "This machine has no LAN address".to_string(),
+            "Connect it to the same Wi-Fi or Ethernet network as the other devices".to_string(),
+        ));
+    }
+    let bind = report.configured_bind.as_deref().unwrap_or("loopback");
+    match report.binding {
+        GatewayBinding::NotListening if !report.loopback_reachable => out.push(problem(
+            format!("Nothing is listening on port {}", report.port),
+            "Start the gateway".to_string(),
+        )),
+        GatewayBinding::Loopback if bind == "loopback" => out.push(problem(
+            format!(
+                "The gateway only listens on 127.0.0.1:{}, so only this machine can connect",
+                report.port
+            ),
+            "Set gateway.bind to \"lan\" in openclaw.json and restart the gateway".to_string(),
+        )),
+        GatewayBinding::Loopback => out.push(problem(
+            format!(
+                "gateway.bind is \"{}\" but the gateway still listens on 127.0.0.1 only",
+                bind
+            ),
+            "Restart the gateway to apply gateway.bind".to_string(),
+        )),
+        GatewayBinding::Specific if report.lan_ip.is_some() && !report.lan_reachable => {
+            out.push(problem(
+                format!(
+                    "The gateway listens on {} but not on the LAN address",
+                    report.listen_addresses.join(", ")
+                ),
+                "Set gateway.bind to \"lan\" in openclaw.json and restart the gateway".to_string(),
+            ))
+        }
+        GatewayBinding::Unknown if report.lan_ip.is_some() && !report.lan_reachable => {
+            out.push(problem(
+                format!(
+                    "The gateway isn't reachable on the LAN address, port {}",
+                    report.port
+                ),
+                "Make sure it is running with gateway.bind set to \"lan\", then restart it"
+                    .to_string(),
+            ))
+        }
+        _ => {}
+    }
+    let listening_outside = matches!(
+        report.binding,
+        GatewayBinding::AllInterfaces | GatewayBinding::Specific
+    );
+    let all_interfaces = report.binding == GatewayBinding::AllInterfaces;
+    if all_interfaces && report.lan_ip.is_some() && !report.lan_reachable {
+        out.push(problem(
+            "The gateway can't be reached on the LAN address even from this machine".to_string(),
+            "Check for a VPN or network filter on this machine".to_string(),
+        ));
+    }
+    match report.firewall {
+        FirewallStatus::Blocks => out.push(problem(
+            format!(
+                "The firewall blocks incoming connections to port {}",
+                report.port
+            ),
+            firewall_fix(report.port),
+        )),
+        FirewallStatus::MayBlock if listening_outside => out.push(problem(
+            format!(
+                "The firewall is on and may block incoming connections to port {}",
+                report.port
+            ),
+            firewall_fix(report.port),
+        )),
+        _ => {}
+    }
+    if (listening_outside || bind != "loopback") && !has_auth {
+        out.push(problem(
+            "The gateway is open to the network without an auth token".to_string(),
+            "Set gateway.auth.token in openclaw.json and use it on the other devices".to_string(),
+        ));
+    }
+    out
+}
+
+/// This machine's address on the default route. No packet is sent: connecting a UDP socket only
+/// picks the interface.
+pub(crate) fn lan_ip() -> Option<String> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("8.8.8.8:80").ok()?;
+    let ip = socket.local_addr().ok()?.ip();
+    (!ip.is_loopback() && !ip.is_unspecified()).then(|| ip.to_string())
+}
+
+fn tool_stdout(program: &str, args: &[&str]) -> Option<String> {
+    let out = operations::run_command(Command::new(program).args(args), TOOL_TIMEOUT, None).ok()?;
+    out.status
+        .success()
+        .then(|| String::from_utf8_lossy(&out.stdout).to_string())
+}
+
+/// Addresses listening on `port`; None when no tool could list them.
+fn listen_addresses(port: u16) -> Option<Vec<String>> {
+    let stdout = if cfg!(target_os = "windows") {
+        tool_stdout("netstat", &["-an", "-p", "tcp"])
+    } else if cfg!(target_os = "macos") {
+        // lsof exits 1 when nothing matches.
+        tool_stdout("lsof", &["-nP", "-iTCP", "-sTCP:LISTEN"]).or(Some(String::new()))
+    } else {
+        tool_stdout("ss", &["-ltn"])
+    }?;
+    Some(parse_listen_addresses(&stdout, port))
+}
+
+fn firewall_status(port: u16) -> FirewallStatus {
+    let on_or_open = |on: bool| {
+        if on {
+            FirewallStatus::MayBlock
+        } else {
+            FirewallStatus::Open
+        }
+    };
+    let status = if cfg!(target_os = "windows") {
+        tool_stdout("netsh", &["advfirewall", "show", "currentprofile", "state"])
+            .map(|s| on_or_open(s.contains("ON")))
+    } else if cfg!(target_os = "macos") {
+        tool_stdout(
+            "/usr/libexec/ApplicationFirewall/socketfilterfw",
+            &["--getglobalstate"],
+        )
+        .map(|s| on_or_open(s.contains("enabled")))
+    } else {
+        // `ufw status` needs root; without it the firewall isn't checked.
+        tool_stdout("ufw", &["status"]).map(|s| parse_ufw_status(&s, port))
+    };
+    status.unwrap_or(FirewallStatus::Unknown)
+}
+
+/// Checks whether other devices on the LAN can reach the gateway and what to fix if not.
+pub fn test_external_reachability() -> Result<ExternalReachability, String> {
+    let root = openclaw_config::read_config_root().unwrap_or_default();
+    let port = gateway_port(&root);
+    let lan_ip = lan_ip();
+    let addresses = listen_addresses(port);
+    let lan_reachable = lan_ip
+        .as_deref()
+        .is_some_and(|ip| detection::port_open(ip, port));
+    let mut report = ExternalReachability {
+        url: lan_ip
+            .as_ref()
+            .filter(|_| lan_reachable)
+            .map(|ip| format!("ws://{}:{}", ip, port)),
+        lan_ip,
+        port,
+        configured_bind: root
+            .pointer("/gateway/bind")
+            .and_then(|b| b.as_str())
+            .map(String::from),
+        binding: addresses
+            .as_deref()
+            .map_or(GatewayBinding::Unknown, classify_binding),
+        listen_addresses: addresses.unwrap_or_default(),
+        loopback_reachable: detection::port_open("127.0.0.1", port),
+        lan_reachable,
+        firewall: firewall_status(port),
+        problems: vec![],
+    };
+    let has_auth = root
+        .pointer("/gateway/auth/token")
+        .and_then(|t| t.as_str())
+        .is_some_and(|t| !t.is_empty())
+        || root
+            .pointer("/gateway/auth/password")
+            .is_some_and(|p| !p.is_null());
+    report.problems = diagnose(&report, has_auth);
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_listen_addresses() {
+        let ss = "State  Recv-Q Send-Q Local Address:Port Peer Address:Port\n\
+                  LISTEN 0      511        127.0.0.1:18789      0.0.0.0:*\n\
+                  LISTEN 0      511            [::1]:18789         [::]:*\n\
+                  LISTEN 0      4096         0.0.0.0:11434      0.0.0.0:*\n";
+        let addresses = parse_listen_addresses(ss, 18789);
+        assert_eq!(addresses, vec!["127.0.0.1", "::1"]);
+        assert_eq!(classify_binding(&addresses), GatewayBinding::Loopback);
+
+        let lsof = "node 4242 sam 20u IPv4 0xabc 0t0 TCP *:18789 (LISTEN)\n";
+        let netstat = "  TCP    0.0.0.0:18789          0.0.0.0:0              LISTENING\n";
+        for out in [lsof, netstat] {
+            let addresses = parse_listen_addresses(out, 18789);
+            assert_eq!(classify_binding(&addresses), GatewayBinding::AllInterfaces);
+        }
+        assert_eq!(
+            classify_binding(&parse_listen_addresses(ss, 9999)),
+            GatewayBinding::NotListening
+        );
+    }
+
+    #[test]
+    fn test_diagnose() {
+        let mut report = ExternalReachability {
+            lan_ip: Some("192.168.1.20".to_string()),
+            port: 18789,
+            configured_bind: None,
+            listen_addresses: vec!["127.0.0.1".to_string()],
+            binding: GatewayBinding::Loopback,
+            loopback_reachable: true,
+            lan_reachable: false,
+            firewall: FirewallStatus::Unknown,
+            url: None,
+            problems: vec![],
+        };
+        let problems = diagnose(&report, false);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].fix.contains("gateway.bind to \"lan\""));
+
+        report.configured_bind = Some("lan".to_string());
+        assert!(diagnose(&report, true)[0]
+            .fix
+            .starts_with("Restart the gateway"));
+
+        report.binding = GatewayBinding::AllInterfaces;
+        report.listen_addresses = vec!["0.0.0.0".to_string()];
+        report.lan_reachable = true;
+        report.firewall = FirewallStatus::Blocks;
+        let problems = diagnose(&report, true);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].message.contains("firewall"));
+
+        let ufw = "Status: active\n\nTo                         Action      From\n\
+                   22/tcp                     ALLOW       Anywhere\n";
+        assert_eq!(parse_ufw_status(ufw, 18789), FirewallStatus::Blocks);
+        let allowed = format!("{}18789/tcp ALLOW Anywhere\n", ufw);
+        assert_eq!(parse_ufw_status(&allowed, 18789), FirewallStatus::Open);
+        assert_eq!(
+            parse_ufw_status("Status: inactive\n", 18789),
+            FirewallStatus::Open
+        );
+    }
+}
@@ -0,0 +1,316 @@
+//! Recent model requests handled by the gateway, read from the end of its log files (the same
+//! files the diagnostics bundle collects). The gateway writes JSON lines or `key=value` text
+//! depending on its version; a line counts as a request when it names a model together with a
+//! latency or token counts. Line parsing and filtering are pure functions for unit tests.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{agent_usage, diagnostics};
+
+const DEFAULT_LIMIT: usize = 200;
+const MAX_LIMIT: usize = 2000;
+
+const AGENT_KEYS: [&str; 4] = ["agent", "agentId", "agentName", "agent_id"];
+const MODEL_KEYS: [&str; 3] = ["model", "modelId", "model_id"];
+const LATENCY_KEYS: [&str; 6] = [
+    "durationMs",
+    "latencyMs",
+    "elapsedMs",
+    "duration_ms",
+    "latency_ms",
+    "elapsed_ms",
+];
+const INPUT_KEYS: [&str; 5] = [
+    "inputTokens",
+    "promptTokens",
+    "input_tokens",
+    "prompt_tokens",
+    "input",
+];
+const OUTPUT_KEYS: [&str; 5] = [
+    "outputTokens",
+    "completionTokens",
+    "output_tokens",
+    "completion_tokens",
+    "output",
+];
+const TIME_KEYS: [&str; 4] = ["time", "timestamp", "ts", "date"];
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct GatewayRequest {
+    /// Unix ms.
+    pub timestamp_ms: u64,
+    pub agent: Option<String>,
+    /// "provider/model" when the log names the provider.
+    pub model: String,
+    pub latency_ms: Option<u64>,
+    pub input_tokens: Option<u64>,
+    pub output_tokens: Option<u64>,
+    /// "ok", "error", or what the log reports (e.g. an HTTP status).
+    pub status: String,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GatewayRequestFilter {
+    /// Exact agent id, case-insensitive.
+    pub agent: Option<String>,
+    /// Part of the model id, case-insensitive, e.g. "qwen3" or "ollama/".
+    pub model: Option<String>,
+    /// Only requests after this Unix ms; pass the newest timestamp seen to poll for new ones.
+    pub since_ms: Option<u64>,
+    /// Defaults to 200.
+    pub limit: Option<usize>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GatewayRequestTail {
+    /// Newest first.
+    pub requests: Vec<GatewayRequest>,
+    /// The log files that were read.
+    pub log_files: Vec<String>,
+}
+
+/// Unix ms from a number (seconds or ms) or an ISO timestamp in UTC or with an offset.
+fn timestamp_ms(value: &Value) -> Option<u64> {
+    if let Some(n) = value.as_u64() {
+        return Some(if n < 10_000_000_000 { n * 1000 } else { n });
+    }
+    let s = value.as_str()?.trim();
+    if let Some(ms) = agent_usage::iso_to_unix_ms(s) {
+        return Some(ms);
+    }
+    // "+01:00" / "-05:00" suffix.
+    let split = s.len().checked_sub(6)?;
+    let (local, offset) = (s.get(..split)?, s.get(split..)?);
+    let sign: i64 = match offset.as_bytes()[0] {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let (h, m) = offset[1..].split_once(':')?;
+    let offset_ms = sign * (h.parse::<i64>().ok()? * 60 + m.parse::<i64>().ok()?) * 60_000;
+    let utc = agent_usage::iso_to_unix_ms(&format!("{}Z", local))? as i64 - offset_ms;
+    u64::try_from(utc).ok()
+}
+
+/// `key=value` pairs of a text log line as a JSON object; a leading timestamp becomes `time`.
+fn parse_logfmt(line: &str) -> Map<String, Value> {
+    let mut fields = Map::new();
+    let mut rest = line.trim();
+    if let Some((first, tail)) = rest.split_once(' ') {
+        let first = first.trim_matches(|c| c == '[' || c == ']');
+        if timestamp_ms(&Value::from(first)).is_some() {
+            fields.insert("time".to_string(), Value::from(first));
+            rest = tail;
+        }
+    }
+    while let Some(eq) = rest.find('=') {
+        let key = rest[..eq].rsplit(' ').next().unwrap_or_default();
+        let after = &rest[eq + 1..];
+        let (value, tail) = match after.strip_prefix('"') {
+            Some(quoted) => match quoted.find('"') {
+                Some(end) => (&quoted[..end], &quoted[end + 1..]),
+                None => (quoted, ""),
+            },
+            None => after.split_once(' ').unwrap_or((after, "")),
+        };
+        if !key.is_empty() {
+            let value = value
+                .parse::<u64>()
+                .map(Value::from)
+                .unwrap_or_else(|_| Value::from(value));
+            fields.insert(key.to_string(), value);
+        }
+        rest = tail;
+    }
+    fields
+}
+
+/// The first of `keys` in `fields` or in an object nested one level below it (`usage`, `meta`,
+/// a logger's argument slots).
+fn lookup<'a>(fields: &'a Map<String, Value>, keys: &[&str]) -> Option<&'a Value> {
+    let find =
+        |m: &'a Map<String, Value>| keys.iter().find_map(|k| m.get(*k).filter(|v| !v.is_null()));
+    find(fields).or_else(|| fields.values().filter_map(|v| v.as_object()).find_map(find))
+}
+
+fn lookup_u64(fields: &Map<String, Value>, keys: &[&str]) -> Option<u64> {
+    lookup(fields, keys).and_then(|v| {
+        v.as_u64()
+            .or_else(|| v.as_f64().filter(|f| *f >= 0.0).map(|f| f.round() as u64))
+            .or_else(|| v.as_str().and_then(|s| s.parse().ok()))
+    })
+}
+
+fn lookup_str(fields: &Map<String, Value>, keys: &[&str]) -> Option<String> {
+    lookup(fields, keys)
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+}
+
+/// The request a log line records, or None for any other line.
+#[must_use]
+pub fn parse_request_line(line: &str) -> Option<GatewayRequest> {
+    let line = line.trim();
+    let fields = match serde_json::from_str::<Value>(line) {
+        Ok(Value::Object(map)) => map,
+        Ok(_) => return None,
+        Err(_) => parse_logfmt(line),
+    };
+    let model = lookup_str(&fields, &MODEL_KEYS)?;
+    let model = match lookup_str(&fields, &["provider"]) {
+        Some(provider) if !model.contains('/') => format!("{}/{}", provider, model),
+        _ => model,
+    };
+    let latency_ms = lookup_u64(&fields, &LATENCY_KEYS);
+    let input_tokens = lookup_u64(&fields, &INPUT_KEYS);
+    let output_tokens = lookup_u64(&fields, &OUTPUT_KEYS);
+    if latency_ms.is_none() && input_tokens.is_none() && output_tokens.is_none() {
+        return None;
+    }
+    let timestamp_ms = lookup(&fields, &TIME_KEYS).and_then(timestamp_ms)?;
+    let error = lookup(&fields, &["error", "err"]).and_then(|e| match e {
+        Value::String(s) => Some(s.clone()),
+        Value::Object(o) => o.get("message").and_then(|m| m.as_str()).map(String::from),
+        _ => None,
+    });
+    let status = match (&error, lookup(&fields, &["status", "ok"])) {
+        (Some(_), _) | (None, Some(Value::Bool(false))) => "error".to_string(),
+        (None, Some(Value::String(s))) => s.clone(),
+        (None, Some(Value::Number(n))) => n.to_string(),
+        _ => "ok".to_string(),
+    };
+    Some(GatewayRequest {
+        timestamp_ms,
+        agent: lookup_str(&fields, &AGENT_KEYS),
+        model,
+        latency_ms,
+        input_tokens,
+        output_tokens,
+        status,
+        error,
+    })
+}
+
+/// The requests in `logs` that match `filter`, newest first and at most `filter.limit`.
+#[must_use]
+pub fn filter_requests(logs: &[String], filter: &GatewayRequestFilter) -> Vec<GatewayRequest> {
+    let agent = filter.agent.as_deref().map(str::to_lowercase);
+    let model = filter.model.as_deref().map(str::to_lowercase);
+    let mut requests: Vec<GatewayRequest> = logs
+        .iter()
+        .flat_map(|log| log.lines())
+        .filter_map(parse_request_line)
+        .filter(|r| filter.since_ms.is_none_or(|since| r.timestamp_ms > since))
+        .filter(|r| {
+            agent
+                .as_deref()
+                .is_none_or(|a| r.agent.as_deref().is_some_and(|ra| ra.to_lowercase() == a))
+        })
+        .filter(|r| {
+            model
+                .as_deref()
+                .is_none_or(|m| r.model.to_lowercase().contains(m))
+        })
+        .collect();
+    requests.sort_by_key(|r| std::cmp::Reverse(r.timestamp_ms));
+    requests.dedup();
+    requests.truncate(filter.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT));
+    requests
+}
+
+/// Reads the end of the recent gateway logs and returns the requests matching `filter`.
+pub fn tail_gateway_requests(filter: &GatewayRequestFilter) -> Result<GatewayRequestTail, String> {
+    let files = diagnostics::recent_logs();
+    let logs: Vec<String> = files
+        .iter()
+        .filter_map(|p| diagnostics::read_log_tail(p).ok())
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        .collect();
+    Ok(GatewayRequestTail {
+        requests: filter_requests(&logs, filter),
+        log_files: files.iter().map(|p| p.display().to_string()).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request_line() {
+        let json = r#"{"time":"2026-03-01T12:00:00.000Z","msg":"model request","agentId":"main",
+            "provider":"ollama","model":"qwen3:8b","durationMs":1840,
+            "usage":{"input":512,"output":64}}"#
+            .replace('\n', "");
+        let r = parse_request_line(&json).unwrap();
+        assert_eq!(r.agent.as_deref(), Some("main"));
+        assert_eq!(r.model, "ollama/qwen3:8b");
+        assert_eq!(r.latency_ms, Some(1840));
+        assert_eq!((r.input_tokens, r.output_tokens), (Some(512), Some(64)));
+        assert_eq!(r.status, "ok");
+
+        let text = concat!(
+            "2026-03-01T13:00:00.000+01:00 [gateway] request agent=coder ",
+            r#"model=groq/llama-3.3-70b latencyMs=420 status=429 error="rate limited""#
+        );
+        let r = parse_request_line(text).unwrap();
+        assert_eq!(r.timestamp_ms, 1_772_366_400_000);
+        assert_eq!(r.agent.as_deref(), Some("coder"));
+        assert_eq!(r.model, "groq/llama-3.3-70b");
+        assert_eq!(r.status, "error");
+        assert_eq!(r.error.as_deref(), Some("rate limited"));
+
+        assert!(parse_request_line(r#"{"time":"2026-03-01T12:00:00Z","msg":"started"}"#).is_none());
+        assert!(parse_request_line("2026-03-01T12:00:00Z model=ollama/qwen3:8b loaded").is_none());
+    }
+
+    #[test]
+    fn test_non_ascii_lines() {
+        assert_eq!(timestamp_ms(&Value::from("✓✓✓a")), None);
+        assert_eq!(
+            timestamp_ms(&Value::from("2026-03-01T13:00:00é+01:00")),
+            None
+        );
+        for line in [
+            "✓✓✓a model=x latencyMs=3",
+            "über=✓ model=ollama/qwen3:8b",
+            "日本語",
+        ] {
+            let _ = parse_request_line(line);
+        }
+    }
+
+    #[test]
+    fn test_filter_requests() {
+        let log = [
+            r#"{"ts":1772366400000,"agent":"main","model":"ollama/qwen3:8b","durationMs":10}"#,
+            r#"{"ts":1772366401000,"agent":"coder","model":"ollama/qwen3:8b","durationMs":20}"#,
+            r#"{"ts":1772366402000,"agent":"main","model":"openai/gpt-4o","durationMs":30}"#,
+        ]
+        .join("\n");
+        let logs = [log];
+        let all = filter_requests(&logs, &GatewayRequestFilter::default());
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].model, "openai/gpt-4o");
+
+        let main = GatewayRequestFilter {
+            agent: Some("MAIN".to_string()),
+            model: Some("qwen".to_string()),
+            ..Default::default()
+        };
+        let only = filter_requests(&logs, &main);
+        assert_eq!(only.len(), 1);
+        assert_eq!(only[0].latency_ms, Some(10));
+
+        let newer = GatewayRequestFilter {
+            since_ms: Some(1_772_366_401_000),
+            ..Default::default()
+        };
+        assert_eq!(filter_requests(&logs, &newer).len(), 1);
+    }
+}
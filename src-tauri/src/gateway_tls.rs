@@ -0,0 +1,325 @@
+//! TLS for the gateway: a self-signed certificate made with the `openssl` CLI and kept in
+//! ~/.openclaw/host-config/tls (key readable only by the user), its paths written to
+//! openclaw.json `gateway.tls`, and a status with the certificate's expiry and SHA-256
+//! fingerprint (for clients that pin a self-signed certificate). The certificate covers
+//! localhost, this machine's host name and its LAN address; regenerate it after the address
+//! changes. Parsing openssl output and editing the gateway section are pure functions for unit
+//! tests.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use crate::{gateway_reachability, openclaw_config, operations};
+
+const CERT_FILE: &str = "gateway-cert.pem";
+const KEY_FILE: &str = "gateway-key.pem";
+/// The longest validity Apple platforms accept for a TLS server certificate.
+const DEFAULT_VALID_DAYS: u32 = 825;
+/// A certificate expiring within this many days is reported.
+pub const EXPIRY_WARNING_DAYS: i64 = 30;
+const OPENSSL_TIMEOUT: Duration = Duration::from_secs(30);
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct GatewayTlsStatus {
+    /// gateway.tls.enabled.
+    pub enabled: bool,
+    pub cert_path: Option<String>,
+    pub key_path: Option<String>,
+    /// Unix seconds the certificate expires at.
+    pub not_after: Option<u64>,
+    /// Negative once expired.
+    pub days_left: Option<i64>,
+    /// "AB:CD:..." SHA-256 of the certificate.
+    pub fingerprint: Option<String>,
+    pub problems: Vec<String>,
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let month = i64::from(month);
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Unix seconds of an openssl date such as "Jan  1 00:00:00 2027 GMT".
+#[must_use]
+pub fn parse_openssl_date(date: &str) -> Option<u64> {
+    let mut parts = date.split_whitespace();
+    let month = parts.next()?;
+    let month = MONTHS.iter().position(|m| *m == month)? as u32 + 1;
+    let day: u32 = parts.next()?.parse().ok()?;
+    let mut time = parts.next()?.split(':').map(|p| p.parse::<i64>().ok());
+    let (h, m, s) = (time.next()??, time.next()??, time.next()??);
+    let year: i64 = parts.next()?.parse().ok()?;
+    let secs = days_from_civil(year, month, day) * 86_400 + h * 3_600 + m * 60 + s;
+    u64::try_from(secs).ok()
+}
+
+/// Expiry and fingerprint from `openssl x509 -noout -enddate -fingerprint -sha256` output.
+#[must_use]
+pub fn parse_x509_output(stdout: &str) -> (Option<u64>, Option<String>) {
+    let mut not_after = None;
+    let mut fingerprint = None;
+    for line in stdout.lines() {
+        if let Some(date) = line.trim().strip_prefix("notAfter=") {
+            not_after = parse_openssl_date(date);
+        } else if let Some((label, value)) = line.split_once("Fingerprint=") {
+            if label.trim().eq_ignore_ascii_case("sha256") {
+                fingerprint = Some(value.trim().to_string());
+            }
+        }
+    }
+    (not_after, fingerprint)
+}
+
+/// Sets gateway.tls in `root`, keeping its other keys; paths are only replaced when given.
+pub fn set_tls_section(root: &mut Value, enabled: bool, paths: Option<(&str, &str)>) {
+    if !root.get("gateway").is_some_and(|g| g.is_object()) {
+        root["gateway"] = serde_json::json!({});
+    }
+    if !root["gateway"].get("tls").is_some_and(|t| t.is_object()) {
+        root["gateway"]["tls"] = serde_json::json!({});
+    }
+    let tls = &mut root["gateway"]["tls"];
+    tls["enabled"] = Value::Bool(enabled);
+    if let Some((cert, key)) = paths {
+        tls["certPath"] = Value::from(cert);
+        tls["keyPath"] = Value::from(key);
+    }
+}
+
+fn tls_dir() -> PathBuf {
+    openclaw_config::host_config_dir().join("tls")
+}
+
+fn openssl(args: &[&str]) -> Result<String, String> {
+    let out = operations::run_command(Command::new("openssl").args(args), OPENSSL_TIMEOUT, None)
+        .map_err(|e| format!("openssl is needed for gateway TLS: {}", e))?;
+    if !out.status.success() {
+        return Err(format!(
+            "openssl {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&out.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&out.stdout).to_string())
+}
+
+/// Names and addresses the certificate is valid for, as a subjectAltName value.
+fn subject_alt_names(host_name: Option<&str>, lan_ip: Option<&str>) -> String {
+    let mut names = vec!["DNS:localhost".to_string(), "IP:127.0.0.1".to_string()];
+    if let Some(host) = host_name.filter(|h| !h.is_empty()) {
+        names.push(format!("DNS:{}", host));
+        if !host.contains('.') {
+            names.push(format!("DNS:{}.local", host));
+        }
+    }
+    if let Some(ip) = lan_ip {
+        names.push(format!("IP:{}", ip));
+    }
+    names.join(",")
+}
+
+fn path_str(root: &Value, key: &str) -> Option<String> {
+    root.pointer(&format!("/gateway/tls/{}", key))
+        .and_then(|p| p.as_str())
+        .filter(|p| !p.is_empty())
+        .map(String::from)
+}
+
+/// gateway.tls from openclaw.json and what the certificate says.
+pub fn get_gateway_tls_status() -> Result<GatewayTlsStatus, String> {
+    let root = openclaw_config::read_config_root()?;
+    let mut status = GatewayTlsStatus {
+        enabled: root
+            .pointer("/gateway/tls/enabled")
+            .and_then(|e| e.as_bool())
+            .unwrap_or(false),
+        cert_path: path_str(&root, "certPath"),
+        key_path: path_str(&root, "keyPath"),
+        ..GatewayTlsStatus::default()
+    };
+    let bind = root
+        .pointer("/gateway/bind")
+        .and_then(|b| b.as_str())
+        .unwrap_or("loopback");
+    if !status.enabled && bind != "loopback" {
+        status.problems.push(format!(
+            "The gateway is on the network (gateway.bind is \"{}\") without TLS",
+            bind
+        ));
+    }
+    if let Some(key) = status
+        .key_path
+        .as_deref()
+        .filter(|k| !Path::new(k).exists())
+    {
+        status.problems.push(format!("Key file not found: {}", key));
+    }
+    let Some(cert) = status.cert_path.clone() else {
+        if status.enabled {
+            status
+                .problems
+                .push("TLS is on but gateway.tls.certPath is not set".to_string());
+        }
+        return Ok(status);
+    };
+    if !Path::new(&cert).exists() {
+        status
+            .problems
+            .push(format!("Certificate file not found: {}", cert));
+        return Ok(status);
+    }
+    match openssl(&[
+        "x509",
+        "-in",
+        &cert,
+        "-noout",
+        "-enddate",
+        "-fingerprint",
+        "-sha256",
+    ]) {
+        Ok(out) => {
+            let (not_after, fingerprint) = parse_x509_output(&out);
+            status.not_after = not_after;
+            status.fingerprint = fingerprint;
+            status.days_left =
+                not_after.map(|t| (t as i64 - operations::now_secs() as i64).div_euclid(86_400));
+        }
+        Err(e) => status.problems.push(e),
+    }
+    match status.days_left {
+        Some(days) if days < 0 => status
+            .problems
+            .push("The certificate has expired; generate a new one".to_string()),
+        Some(days) if days <= EXPIRY_WARNING_DAYS => status
+            .problems
+            .push(format!("The certificate expires in {} days", days)),
+        _ => {}
+    }
+    Ok(status)
+}
+
+/// Creates a new self-signed certificate and key valid for `days` (default 825), replacing any
+/// made before, and turns TLS on with them in openclaw.json.
+pub fn generate_self_signed_cert(days: Option<u32>) -> Result<GatewayTlsStatus, String> {
+    let dir = tls_dir();
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let (cert, key) = (dir.join(CERT_FILE), dir.join(KEY_FILE));
+    let (cert_tmp, key_tmp) = (dir.join("cert.pem.tmp"), dir.join("key.pem.tmp"));
+    let host_name = sysinfo::System::host_name();
+    let lan_ip = gateway_reachability::lan_ip();
+    let subject = format!("/CN={}", host_name.as_deref().unwrap_or("openclaw-gateway"));
+    let san = format!(
+        "subjectAltName={}",
+        subject_alt_names(host_name.as_deref(), lan_ip.as_deref())
+    );
+    let days = days
+        .unwrap_or(DEFAULT_VALID_DAYS)
+        .clamp(1, 3650)
+        .to_string();
+    let result = openssl(&[
+        "req",
+        "-x509",
+        "-newkey",
+        "ec",
+        "-pkeyopt",
+        "ec_paramgen_curve:prime256v1",
+        "-nodes",
+        "-keyout",
+        &key_tmp.to_string_lossy(),
+        "-out",
+        &cert_tmp.to_string_lossy(),
+        "-days",
+        &days,
+        "-subj",
+        &subject,
+        "-addext",
+        &san,
+    ]);
+    if let Err(e) = result {
+        let _ = fs::remove_file(&cert_tmp);
+        let _ = fs::remove_file(&key_tmp);
+        return Err(e);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&key_tmp, fs::Permissions::from_mode(0o600))
+            .map_err(|e| e.to_string())?;
+    }
+    fs::rename(&key_tmp, &key).map_err(|e| e.to_string())?;
+    fs::rename(&cert_tmp, &cert).map_err(|e| e.to_string())?;
+
+    let mut root = openclaw_config::read_config_root()?;
+    let (cert, key) = (cert.display().to_string(), key.display().to_string());
+    set_tls_section(&mut root, true, Some((&cert, &key)));
+    openclaw_config::write_config_root(&root)?;
+    get_gateway_tls_status()
+}
+
+/// Turns gateway TLS on or off, keeping the certificate paths. Turning it on needs both files.
+pub fn set_gateway_tls(enabled: bool) -> Result<GatewayTlsStatus, String> {
+    let mut root = openclaw_config::read_config_root()?;
+    if enabled {
+        for key in ["certPath", "keyPath"] {
+            let path = path_str(&root, key).ok_or_else(|| {
+                format!(
+                    "gateway.tls.{} is not set; generate a certificate first",
+                    key
+                )
+            })?;
+            if !Path::new(&path).exists() {
+                return Err(format!("{} not found: {}", key, path));
+            }
+        }
+    }
+    set_tls_section(&mut root, enabled, None);
+    openclaw_config::write_config_root(&root)?;
+    get_gateway_tls_status()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_x509_output() {
+        assert_eq!(parse_openssl_date("Jan  1 00:00:00 1970 GMT"), Some(0));
+        assert_eq!(
+            parse_openssl_date("Mar  1 12:30:05 2024 GMT"),
+            Some(1_709_296_205)
+        );
+        let out = "notAfter=Feb 29 00:00:00 2028 GMT\nsha256 Fingerprint=AB:CD:EF\n";
+        let (not_after, fingerprint) = parse_x509_output(out);
+        assert_eq!(not_after, Some(1_835_395_200));
+        assert_eq!(fingerprint.as_deref(), Some("AB:CD:EF"));
+        assert_eq!(parse_x509_output("garbage"), (None, None));
+    }
+
+    #[test]
+    fn test_set_tls_section() {
+        let mut root = serde_json::json!({ "gateway": { "port": 18789 } });
+        set_tls_section(&mut root, true, Some(("/c.pem", "/k.pem")));
+        assert_eq!(root["gateway"]["tls"]["certPath"], "/c.pem");
+        assert_eq!(root["gateway"]["port"], 18789);
+        set_tls_section(&mut root, false, None);
+        assert_eq!(root["gateway"]["tls"]["enabled"], false);
+        assert_eq!(root["gateway"]["tls"]["keyPath"], "/k.pem");
+        assert_eq!(
+            subject_alt_names(Some("studio"), Some("192.168.1.20")),
+            "DNS:localhost,IP:127.0.0.1,DNS:studio,DNS:studio.local,IP:192.168.1.20"
+        );
+    }
+}
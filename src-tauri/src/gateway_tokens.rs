@@ -0,0 +1,248 @@
+//! Gateway auth tokens. The gateway accepts one shared token, `gateway.auth.token` in
+//! openclaw.json, so creating a token here rotates it: the new one is written there and the
+//! previous one stops working once the gateway reloads its config. Labels and creation times are
+//! kept in ~/.openclaw/host-config/gateway-tokens.json under a hash of each token, never the token
+//! itself; views only ever show a masked preview, and a new token's secret is returned once, by
+//! `create_gateway_token`. Listing and labelling are pure functions for unit tests.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::{agent_env, openclaw_config, operations};
+
+const TOKEN_BYTES: usize = 32;
+/// Older labels kept for the history in the list.
+const MAX_RECORDS: usize = 20;
+const MAX_LABEL_LEN: usize = 64;
+const UNLABELED: &str = "Set outside the app";
+
+/// What gateway-tokens.json keeps per token.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TokenRecord {
+    pub id: String,
+    pub label: String,
+    pub masked: String,
+    pub created_at: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct GatewayToken {
+    /// The first 12 hex digits of the token's SHA-256: stable and safe to show.
+    pub id: String,
+    pub label: String,
+    /// e.g. "****a1b2", or the `${VAR}` reference the token is read from.
+    pub masked: String,
+    /// Unix seconds; 0 when it was set outside the app.
+    pub created_at: u64,
+    /// It is gateway.auth.token, the one the gateway accepts.
+    pub active: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CreatedGatewayToken {
+    pub token: GatewayToken,
+    /// The token itself, shown to the user this once.
+    pub secret: String,
+}
+
+#[must_use]
+pub fn token_id(token: &str) -> String {
+    let digest = Sha256::digest(token.as_bytes());
+    digest
+        .iter()
+        .take(6)
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn masked(token: &str) -> String {
+    if token.starts_with("${") && token.ends_with('}') {
+        token.to_string()
+    } else {
+        agent_env::mask_value(token)
+    }
+}
+
+fn active_token(root: &Value) -> Option<&str> {
+    root.pointer("/gateway/auth/token")
+        .and_then(|t| t.as_str())
+        .filter(|t| !t.trim().is_empty())
+}
+
+/// The active token first, then older ones newest first.
+#[must_use]
+pub fn token_views(records: &[TokenRecord], active: Option<&str>) -> Vec<GatewayToken> {
+    let active_id = active.map(token_id);
+    let mut out: Vec<GatewayToken> = records
+        .iter()
+        .map(|r| GatewayToken {
+            id: r.id.clone(),
+            label: r.label.clone(),
+            masked: r.masked.clone(),
+            created_at: r.created_at,
+            active: active_id.as_deref() == Some(r.id.as_str()),
+        })
+        .collect();
+    if let (Some(token), Some(id)) = (active, &active_id) {
+        if !out.iter().any(|t| t.active) {
+            out.push(GatewayToken {
+                id: id.clone(),
+                label: UNLABELED.to_string(),
+                masked: masked(token),
+                created_at: 0,
+                active: true,
+            });
+        }
+    }
+    out.sort_by_key(|t| (!t.active, std::cmp::Reverse(t.created_at)));
+    out
+}
+
+fn records_path() -> PathBuf {
+    openclaw_config::host_config_dir().join("gateway-tokens.json")
+}
+
+fn read_records() -> Vec<TokenRecord> {
+    fs::read_to_string(records_path())
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn write_records(records: &[TokenRecord]) -> Result<(), String> {
+    let path = records_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    fs::write(
+        &path,
+        serde_json::to_string_pretty(records).map_err(|e| e.to_string())?,
+    )
+    .map_err(|e| e.to_string())
+}
+
+fn new_secret() -> Result<String, String> {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    getrandom::fill(&mut bytes).map_err(|e| format!("No randomness for a token: {}", e))?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// The gateway tokens, secrets masked.
+pub fn list_gateway_tokens() -> Result<Vec<GatewayToken>, String> {
+    let root = openclaw_config::read_config_root()?;
+    Ok(token_views(&read_records(), active_token(&root)))
+}
+
+/// Makes a new random token gateway.auth.token, replacing the current one, and returns it.
+pub fn create_gateway_token(label: &str) -> Result<CreatedGatewayToken, String> {
+    let label = label.trim();
+    if label.is_empty() || label.chars().count() > MAX_LABEL_LEN {
+        return Err(format!(
+            "Token label must be 1-{} characters",
+            MAX_LABEL_LEN
+        ));
+    }
+    let secret = new_secret()?;
+    let mut root = openclaw_config::read_config_root()?;
+    if !root.get("gateway").is_some_and(|g| g.is_object()) {
+        root["gateway"] = serde_json::json!({});
+    }
+    if !root["gateway"].get("auth").is_some_and(|a| a.is_object()) {
+        root["gateway"]["auth"] = serde_json::json!({});
+    }
+    let auth = &mut root["gateway"]["auth"];
+    auth["token"] = Value::from(secret.as_str());
+    // A token does nothing while the gateway expects a password.
+    if auth.get("mode").is_some() {
+        auth["mode"] = Value::from("token");
+    }
+    openclaw_config::write_config_root(&root)?;
+
+    let record = TokenRecord {
+        id: token_id(&secret),
+        label: label.to_string(),
+        masked: masked(&secret),
+        created_at: operations::now_secs(),
+    };
+    let mut records = read_records();
+    records.push(record.clone());
+    records.sort_by_key(|r| std::cmp::Reverse(r.created_at));
+    records.truncate(MAX_RECORDS);
+    write_records(&records)?;
+    Ok(CreatedGatewayToken {
+        token: token_views(&[record], Some(&secret)).remove(0),
+        secret,
+    })
+}
+
+/// Forgets token `id`. Revoking the active token removes gateway.auth.token, which is refused
+/// unless the gateway only listens on loopback: create a new token to rotate it instead.
+pub fn revoke_gateway_token(id: &str) -> Result<Vec<GatewayToken>, String> {
+    let mut records = read_records();
+    let known = records.len();
+    records.retain(|r| r.id != id);
+    let mut root = openclaw_config::read_config_root()?;
+    let is_active = active_token(&root).is_some_and(|t| token_id(t) == id);
+    if !is_active && records.len() == known {
+        return Err(format!("Unknown gateway token: {}", id));
+    }
+    if is_active {
+        let bind = root
+            .pointer("/gateway/bind")
+            .and_then(|b| b.as_str())
+            .unwrap_or("loopback");
+        if bind != "loopback" {
+            return Err(format!(
+                "The gateway listens beyond this machine (gateway.bind is \"{}\"); create a new \
+                 token to replace this one instead of leaving it without auth",
+                bind
+            ));
+        }
+        if let Some(auth) = root
+            .pointer_mut("/gateway/auth")
+            .and_then(|a| a.as_object_mut())
+        {
+            auth.remove("token");
+            if auth.get("mode").and_then(|m| m.as_str()) == Some("token") {
+                auth.remove("mode");
+            }
+        }
+        openclaw_config::write_config_root(&root)?;
+    }
+    write_records(&records)?;
+    Ok(token_views(&records, active_token(&root)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_views() {
+        let record = |token: &str, label: &str, created_at| TokenRecord {
+            id: token_id(token),
+            label: label.to_string(),
+            masked: masked(token),
+            created_at,
+        };
+        let records = vec![
+            record("aaaa1111bbbb2222", "laptop", 100),
+            record("cccc3333dddd4444", "phone", 200),
+        ];
+        let views = token_views(&records, Some("aaaa1111bbbb2222"));
+        assert_eq!(views[0].label, "laptop");
+        assert!(views[0].active);
+        assert_eq!(views[0].masked, "****2222");
+        assert!(!views[1].active);
+
+        let views = token_views(&records, Some("${OPENCLAW_GATEWAY_TOKEN}"));
+        assert_eq!(views.len(), 3);
+        assert_eq!(views[0].label, UNLABELED);
+        assert_eq!(views[0].masked, "${OPENCLAW_GATEWAY_TOKEN}");
+        assert_eq!(views[1].label, "phone");
+        assert_eq!(token_id("x").len(), 12);
+    }
+}
@@ -0,0 +1,402 @@
+//! GGUF header reader for local model files (architecture, parameters, quantization, context length).
+//! Only the header, metadata and tensor infos are read; tensor data is never loaded.
+//! See https://github.com/ggml-org/ggml/blob/master/docs/gguf.md
+
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use crate::system;
+
+const GGUF_MAGIC: &[u8; 4] = b"GGUF";
+/// Upper bound for a single metadata string; real files stay far below this.
+const MAX_STRING_LEN: u64 = 16 * 1024 * 1024;
+
+/// Metadata extracted from a .gguf file.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct GgufMetadata {
+    pub path: String,
+    pub file_size_bytes: u64,
+    pub file_size_human: String,
+    pub version: u32,
+    /// general.architecture (e.g. "llama", "qwen2").
+    pub architecture: Option<String>,
+    /// general.name
+    pub name: Option<String>,
+    /// Sum of all tensor element counts.
+    pub parameter_count: u64,
+    /// Human form of parameter_count (e.g. "7.6B").
+    pub parameter_count_human: String,
+    /// From general.file_type, or guessed from the file name.
+    pub quantization: Option<String>,
+    /// <arch>.context_length
+    pub context_length: Option<u64>,
+    /// <arch>.block_count
+    pub block_count: Option<u64>,
+    /// <arch>.embedding_length
+    pub embedding_length: Option<u64>,
+    pub tensor_count: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum GgufValue {
+    Uint(u64),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Str(String),
+    /// Arrays are skipped; only their length is kept.
+    Array(u64),
+}
+
+impl GgufValue {
+    fn as_u64(&self) -> Option<u64> {
+        match self {
+            GgufValue::Uint(n) => Some(*n),
+            GgufValue::Int(n) if *n >= 0 => Some(*n as u64),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            GgufValue::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+fn read_bytes<const N: usize>(r: &mut impl Read) -> Result<[u8; N], String> {
+    let mut buf = [0u8; N];
+    r.read_exact(&mut buf)
+        .map_err(|e| format!("Truncated GGUF header: {}", e))?;
+    Ok(buf)
+}
+
+fn read_u32(r: &mut impl Read) -> Result<u32, String> {
+    read_bytes::<4>(r).map(u32::from_le_bytes)
+}
+
+fn read_u64(r: &mut impl Read) -> Result<u64, String> {
+    read_bytes::<8>(r).map(u64::from_le_bytes)
+}
+
+/// Version 1 used 32-bit counts and lengths; later versions use 64-bit.
+fn read_len(r: &mut impl Read, version: u32) -> Result<u64, String> {
+    if version == 1 {
+        read_u32(r).map(u64::from)
+    } else {
+        read_u64(r)
+    }
+}
+
+fn read_string(r: &mut impl Read, version: u32) -> Result<String, String> {
+    let len = read_len(r, version)?;
+    if len > MAX_STRING_LEN {
+        return Err(format!("GGUF string too long ({} bytes)", len));
+    }
+    let mut buf = vec![0u8; len as usize];
+    r.read_exact(&mut buf)
+        .map_err(|e| format!("Truncated GGUF string: {}", e))?;
+    Ok(String::from_utf8_lossy(&buf).to_string())
+}
+
+fn read_value(r: &mut impl Read, value_type: u32, version: u32) -> Result<GgufValue, String> {
+    Ok(match value_type {
+        0 => GgufValue::Uint(u64::from(read_bytes::<1>(r)?[0])),
+        1 => GgufValue::Int(i64::from(i8::from_le_bytes(read_bytes::<1>(r)?))),
+        2 => GgufValue::Uint(u64::from(u16::from_le_bytes(read_bytes::<2>(r)?))),
+        3 => GgufValue::Int(i64::from(i16::from_le_bytes(read_bytes::<2>(r)?))),
+        4 => GgufValue::Uint(u64::from(read_u32(r)?)),
+        5 => GgufValue::Int(i64::from(i32::from_le_bytes(read_bytes::<4>(r)?))),
+        6 => GgufValue::Float(f64::from(f32::from_le_bytes(read_bytes::<4>(r)?))),
+        7 => GgufValue::Bool(read_bytes::<1>(r)?[0] != 0),
+        8 => GgufValue::Str(read_string(r, version)?),
+        9 => {
+            let elem_type = read_u32(r)?;
+            let count = read_len(r, version)?;
+            for _ in 0..count {
+                read_value(r, elem_type, version)?;
+            }
+            GgufValue::Array(count)
+        }
+        10 => GgufValue::Uint(read_u64(r)?),
+        11 => GgufValue::Int(i64::from_le_bytes(read_bytes::<8>(r)?)),
+        12 => GgufValue::Float(f64::from_le_bytes(read_bytes::<8>(r)?)),
+        other => return Err(format!("Unknown GGUF value type {}", other)),
+    })
+}
+
+/// Maps general.file_type (llama_ftype) to its quantization name.
+#[must_use]
+pub fn file_type_name(file_type: u64) -> Option<&'static str> {
+    Some(match file_type {
+        0 => "F32",
+        1 => "F16",
+        2 => "Q4_0",
+        3 => "Q4_1",
+        7 => "Q8_0",
+        8 => "Q5_0",
+        9 => "Q5_1",
+        10 => "Q2_K",
+        11 => "Q3_K_S",
+        12 => "Q3_K_M",
+        13 => "Q3_K_L",
+        14 => "Q4_K_S",
+        15 => "Q4_K_M",
+        16 => "Q5_K_S",
+        17 => "Q5_K_M",
+        18 => "Q6_K",
+        19 => "IQ2_XXS",
+        20 => "IQ2_XS",
+        21 => "Q2_K_S",
+        22 => "IQ3_XS",
+        23 => "IQ3_XXS",
+        24 => "IQ1_S",
+        25 => "IQ4_NL",
+        26 => "IQ3_S",
+        27 => "IQ3_M",
+        28 => "IQ2_S",
+        29 => "IQ2_M",
+        30 => "IQ4_XS",
+        31 => "IQ1_M",
+        32 => "BF16",
+        _ => return None,
+    })
+}
+
+/// Guesses the quantization from a file name like "qwen2.5-7b-instruct-q4_k_m.gguf".
+#[must_use]
+pub fn quantization_from_filename(name: &str) -> Option<String> {
+    let upper = name.to_uppercase();
+    let stem = upper.strip_suffix(".GGUF").unwrap_or(&upper);
+    stem.rsplit(['-', '.', '_'])
+        .scan(String::new(), |acc, part| {
+            // Rejoin trailing pieces so "Q4_K_M" survives splitting on '_'.
+            *acc = if acc.is_empty() {
+                part.to_string()
+            } else {
+                format!("{}_{}", part, acc)
+            };
+            Some(acc.clone())
+        })
+        .take(3)
+        .filter(|cand| (0..=32).filter_map(file_type_name).any(|n| n == cand))
+        .last()
+}
+
+/// Formats a parameter count as e.g. "7.6B" or "135M".
+#[must_use]
+pub fn params_to_human(n: u64) -> String {
+    if n >= 1_000_000_000 {
+        format!("{:.1}B", n as f64 / 1e9)
+    } else if n >= 1_000_000 {
+        format!("{:.0}M", n as f64 / 1e6)
+    } else {
+        n.to_string()
+    }
+}
+
+/// Parses a GGUF stream up to (and including) the tensor infos.
+pub fn parse_gguf(r: &mut impl Read) -> Result<GgufMetadata, String> {
+    if &read_bytes::<4>(r)? != GGUF_MAGIC {
+        return Err("Not a GGUF file (bad magic)".to_string());
+    }
+    let version = read_u32(r)?;
+    if !(1..=3).contains(&version) {
+        return Err(format!("Unsupported GGUF version {}", version));
+    }
+    let tensor_count = read_len(r, version)?;
+    let kv_count = read_len(r, version)?;
+
+    let mut kv = std::collections::HashMap::new();
+    for _ in 0..kv_count {
+        let key = read_string(r, version)?;
+        let value_type = read_u32(r)?;
+        let value = read_value(r, value_type, version)?;
+        kv.insert(key, value);
+    }
+
+    let mut parameter_count: u64 = 0;
+    for _ in 0..tensor_count {
+        read_string(r, version)?;
+        let n_dims = read_u32(r)?;
+        let mut elements: u64 = 1;
+        for _ in 0..n_dims {
+            elements = elements.saturating_mul(read_len(r, version)?);
+        }
+        read_u32(r)?; // ggml type
+        read_u64(r)?; // data offset
+        parameter_count = parameter_count.saturating_add(elements);
+    }
+
+    let architecture = kv
+        .get("general.architecture")
+        .and_then(|v| v.as_str())
+        .map(String::from);
+    let arch_u64 = |suffix: &str| {
+        architecture
+            .as_ref()
+            .and_then(|a| kv.get(&format!("{}.{}", a, suffix)))
+            .and_then(|v| v.as_u64())
+    };
+    Ok(GgufMetadata {
+        version,
+        name: kv
+            .get("general.name")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        parameter_count,
+        parameter_count_human: params_to_human(parameter_count),
+        quantization: kv
+            .get("general.file_type")
+            .and_then(|v| v.as_u64())
+            .and_then(file_type_name)
+            .map(String::from),
+        context_length: arch_u64("context_length"),
+        block_count: arch_u64("block_count"),
+        embedding_length: arch_u64("embedding_length"),
+        architecture,
+        tensor_count,
+        ..Default::default()
+    })
+}
+
+/// Reads metadata from a single .gguf file.
+pub fn read_gguf_file(path: &Path) -> Result<GgufMetadata, String> {
+    let file = File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let mut meta =
+        parse_gguf(&mut BufReader::new(file)).map_err(|e| format!("{}: {}", path.display(), e))?;
+    if meta.quantization.is_none() {
+        meta.quantization = path
+            .file_name()
+            .and_then(|n| quantization_from_filename(&n.to_string_lossy()));
+    }
+    meta.path = path.to_string_lossy().to_string();
+    meta.file_size_bytes = size;
+    meta.file_size_human = system::bytes_to_human(size);
+    Ok(meta)
+}
+
+fn find_gguf_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    if let Ok(rd) = fs::read_dir(dir) {
+        for e in rd.filter_map(|e| e.ok()) {
+            let p = e.path();
+            if p.is_dir() {
+                find_gguf_files(&p, out);
+            } else if p
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("gguf"))
+            {
+                out.push(p);
+            }
+        }
+    }
+}
+
+/// Reads a .gguf file, or every .gguf file under a directory (unreadable files are skipped).
+pub fn read_gguf_metadata(path: &str) -> Result<Vec<GgufMetadata>, String> {
+    let p = Path::new(path);
+    if p.is_file() {
+        return read_gguf_file(p).map(|m| vec![m]);
+    }
+    if !p.is_dir() {
+        return Err(format!("Path not found: {}", path));
+    }
+    let mut files = Vec::new();
+    find_gguf_files(p, &mut files);
+    files.sort();
+    Ok(files
+        .iter()
+        .filter_map(|f| read_gguf_file(f).ok())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn put_str(buf: &mut Vec<u8>, s: &str) {
+        buf.extend((s.len() as u64).to_le_bytes());
+        buf.extend(s.as_bytes());
+    }
+
+    /// Minimal v3 file: a few metadata keys, a string array, and two tensors.
+    fn sample_gguf() -> Vec<u8> {
+        let mut b = Vec::new();
+        b.extend(GGUF_MAGIC);
+        b.extend(3u32.to_le_bytes());
+        b.extend(2u64.to_le_bytes()); // tensors
+        b.extend(5u64.to_le_bytes()); // kv pairs
+        put_str(&mut b, "general.architecture");
+        b.extend(8u32.to_le_bytes());
+        put_str(&mut b, "llama");
+        put_str(&mut b, "general.name");
+        b.extend(8u32.to_le_bytes());
+        put_str(&mut b, "Tiny Llama");
+        put_str(&mut b, "general.file_type");
+        b.extend(4u32.to_le_bytes());
+        b.extend(15u32.to_le_bytes());
+        put_str(&mut b, "tokenizer.ggml.tokens");
+        b.extend(9u32.to_le_bytes());
+        b.extend(8u32.to_le_bytes());
+        b.extend(2u64.to_le_bytes());
+        put_str(&mut b, "<s>");
+        put_str(&mut b, "</s>");
+        put_str(&mut b, "llama.context_length");
+        b.extend(4u32.to_le_bytes());
+        b.extend(4096u32.to_le_bytes());
+        for (name, dims) in [("tok_embd", vec![32u64, 1000]), ("output_norm", vec![32])] {
+            put_str(&mut b, name);
+            b.extend((dims.len() as u32).to_le_bytes());
+            for d in dims {
+                b.extend(d.to_le_bytes());
+            }
+            b.extend(0u32.to_le_bytes());
+            b.extend(0u64.to_le_bytes());
+        }
+        b
+    }
+
+    #[test]
+    fn test_parse_gguf() {
+        let meta = parse_gguf(&mut sample_gguf().as_slice()).unwrap();
+        assert_eq!(meta.version, 3);
+        assert_eq!(meta.architecture.as_deref(), Some("llama"));
+        assert_eq!(meta.name.as_deref(), Some("Tiny Llama"));
+        assert_eq!(meta.quantization.as_deref(), Some("Q4_K_M"));
+        assert_eq!(meta.context_length, Some(4096));
+        assert_eq!(meta.tensor_count, 2);
+        assert_eq!(meta.parameter_count, 32 * 1000 + 32);
+    }
+
+    #[test]
+    fn test_parse_gguf_rejects_bad_input() {
+        assert!(parse_gguf(&mut &b"GGML\x03\x00\x00\x00"[..]).is_err());
+        let mut truncated = sample_gguf();
+        truncated.truncate(40);
+        assert!(parse_gguf(&mut truncated.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_quantization_from_filename() {
+        assert_eq!(
+            quantization_from_filename("qwen2.5-7b-instruct-q4_k_m.gguf").as_deref(),
+            Some("Q4_K_M")
+        );
+        assert_eq!(
+            quantization_from_filename("Llama-3.2-3B.Q8_0.gguf").as_deref(),
+            Some("Q8_0")
+        );
+        assert_eq!(quantization_from_filename("model.gguf"), None);
+    }
+
+    #[test]
+    fn test_params_to_human() {
+        assert_eq!(params_to_human(7_615_616_512), "7.6B");
+        assert_eq!(params_to_human(135_000_000), "135M");
+        assert_eq!(params_to_human(42), "42");
+    }
+}
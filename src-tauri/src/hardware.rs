@@ -0,0 +1,611 @@
+//! One hardware profile merged from sysinfo, vendor GPU tools and llmfit, so the UI doesn't have to
+//! reconcile `get_system_info`, `get_llmfit_system` and GPU detection itself. Every field records
+//! where its value came from and how far to trust it. Also checks whether a given model fits and
+//! estimates memory from parameters, quantization and context length.
+//! Merging and fit assessment are pure functions for unit tests.
+
+use serde::{Deserialize, Serialize};
+
+use crate::catalog::{self, CatalogModel, CatalogQuery, RUNTIME_REMOTE};
+use crate::llmfit::{self, LlmfitCache, LlmfitSystemJson, MemoryBreakdown};
+use crate::llmfit_schema;
+use crate::system::{self, GpuInfo, MemoryPressure, SystemInfo};
+
+const GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Confidence {
+    /// Measured directly (OS APIs, nvidia-smi, rocm-smi).
+    High,
+    /// Reported by llmfit or derived from a rule of thumb (Apple unified memory share).
+    Medium,
+    /// Nothing detected; a default was assumed.
+    Low,
+}
+
+/// A value with its origin: "sysinfo", "os", "nvidia-smi", "rocm-smi", "apple-silicon", "llmfit" or "default".
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Sourced<T> {
+    pub value: T,
+    pub source: String,
+    pub confidence: Confidence,
+}
+
+impl<T> Sourced<T> {
+    fn new(value: T, source: &str, confidence: Confidence) -> Self {
+        Self {
+            value,
+            source: source.to_string(),
+            confidence,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct HardwareProfile {
+    pub total_memory_bytes: Sourced<u64>,
+    pub available_memory_bytes: Sourced<u64>,
+    pub swap_total_bytes: u64,
+    pub swap_used_bytes: u64,
+    pub memory_pressure: MemoryPressure,
+    pub cpu_cores: Option<Sourced<u32>>,
+    pub gpu_name: Option<Sourced<String>>,
+    /// Dedicated VRAM, or the GPU-usable share of unified memory.
+    pub vram_bytes: Option<Sourced<u64>>,
+    /// "cuda", "rocm", "metal" or "cpu".
+    pub backend: Sourced<String>,
+    /// True on Apple Silicon, where VRAM and RAM are the same pool.
+    pub unified_memory: bool,
+    /// SIMD extensions from `system::CpuInfo::features`; empty when unknown.
+    pub cpu_features: Vec<String>,
+}
+
+fn gpu_source(gpu: &GpuInfo) -> (&'static str, Confidence) {
+    match gpu.backend.as_str() {
+        "cuda" => ("nvidia-smi", Confidence::High),
+        "rocm" => ("rocm-smi", Confidence::High),
+        _ => ("apple-silicon", Confidence::Medium),
+    }
+}
+
+fn gb_to_bytes(gb: f64) -> u64 {
+    (gb * GB) as u64
+}
+
+/// Merges the sources, preferring direct measurement: sysinfo for RAM, the OS for cores,
+/// vendor tools for the GPU. llmfit fills gaps and beats the Apple Silicon VRAM estimate.
+#[must_use]
+pub fn merge_profile(
+    info: &SystemInfo,
+    cpu_cores: Option<u32>,
+    gpu: Option<&GpuInfo>,
+    llmfit: Option<&LlmfitSystemJson>,
+) -> HardwareProfile {
+    let memory = |measured: u64, reported: Option<f64>| match reported {
+        Some(gb) if measured == 0 => Sourced::new(gb_to_bytes(gb), "llmfit", Confidence::Medium),
+        _ => Sourced::new(measured, "sysinfo", Confidence::High),
+    };
+    let total_memory_bytes = memory(info.total_memory_bytes, llmfit.and_then(|l| l.total_ram_gb));
+    let available_memory_bytes = memory(
+        info.available_memory_bytes,
+        llmfit.and_then(|l| l.available_ram_gb),
+    );
+    let cpu_cores = cpu_cores
+        .map(|n| Sourced::new(n, "os", Confidence::High))
+        .or_else(|| {
+            llmfit
+                .and_then(|l| l.cpu_cores)
+                .map(|n| Sourced::new(n, "llmfit", Confidence::Medium))
+        });
+
+    let llmfit_gpu = llmfit.filter(|l| l.gpu_name.is_some() || l.vram_gb.is_some());
+    let measured_gpu = gpu.filter(|g| gpu_source(g).1 == Confidence::High);
+    let (gpu_name, vram_bytes, backend) = if let Some(g) = measured_gpu {
+        let (source, confidence) = gpu_source(g);
+        (
+            Some(Sourced::new(g.name.clone(), source, confidence)),
+            Some(Sourced::new(g.vram_bytes, source, confidence)),
+            Sourced::new(g.backend.clone(), source, confidence),
+        )
+    } else if let Some(l) = llmfit_gpu {
+        let backend = l
+            .backend
+            .clone()
+            .or_else(|| gpu.map(|g| g.backend.clone()))
+            .unwrap_or_else(|| "cpu".to_string());
+        (
+            l.gpu_name
+                .clone()
+                .or_else(|| gpu.map(|g| g.name.clone()))
+                .map(|n| Sourced::new(n, "llmfit", Confidence::Medium)),
+            l.vram_gb
+                .map(|gb| Sourced::new(gb_to_bytes(gb), "llmfit", Confidence::Medium)),
+            Sourced::new(backend, "llmfit", Confidence::Medium),
+        )
+    } else if let Some(g) = gpu {
+        let (source, confidence) = gpu_source(g);
+        (
+            Some(Sourced::new(g.name.clone(), source, confidence)),
+            Some(Sourced::new(g.vram_bytes, source, confidence)),
+            Sourced::new(g.backend.clone(), source, confidence),
+        )
+    } else {
+        let backend = match llmfit.and_then(|l| l.backend.clone()) {
+            Some(b) => Sourced::new(b, "llmfit", Confidence::Medium),
+            None => Sourced::new("cpu".to_string(), "default", Confidence::Low),
+        };
+        (None, None, backend)
+    };
+
+    HardwareProfile {
+        total_memory_bytes,
+        available_memory_bytes,
+        swap_total_bytes: info.swap_total_bytes,
+        swap_used_bytes: info.swap_used_bytes,
+        memory_pressure: info.memory_pressure,
+        cpu_cores,
+        gpu_name,
+        vram_bytes,
+        unified_memory: backend.value == "metal",
+        backend,
+        cpu_features: info.cpu.features.clone(),
+    }
+}
+
+/// Detects the hardware now, using cached llmfit output when llmfit is installed.
+#[must_use]
+pub fn get_hardware_profile(cache: &LlmfitCache) -> HardwareProfile {
+    let cores = std::thread::available_parallelism()
+        .ok()
+        .map(|n| n.get() as u32);
+    merge_profile(
+        &system::get_system_info(),
+        cores,
+        system::detect_gpu().as_ref(),
+        cache.llmfit_system().as_ref(),
+    )
+}
+
+/// Where a model can run on this machine.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FitLevel {
+    /// Entirely in VRAM (or the GPU share of unified memory).
+    Vram,
+    /// Needs system RAM: partial GPU offload or CPU-only inference.
+    Ram,
+    /// Doesn't fit.
+    None,
+}
+
+/// Rough generation speed: "fast" (30+ tokens/s), "moderate" (10-30) or "slow" (under 10).
+/// Memory bandwidth dominates, so this follows where the weights live and how large they are.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SpeedTier {
+    Fast,
+    Moderate,
+    Slow,
+}
+
+/// Where a model's weights and KV cache would be held.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryDevice {
+    /// Dedicated VRAM.
+    Gpu,
+    /// Apple Silicon's shared pool.
+    UnifiedMemory,
+    /// Split between VRAM and system RAM (partial offload).
+    GpuAndCpuRam,
+    /// System RAM only (CPU inference).
+    CpuRam,
+}
+
+/// Maps a fit to the device it lands on; None when the model doesn't fit.
+fn memory_device(fit: FitLevel, profile: &HardwareProfile) -> Option<MemoryDevice> {
+    let has_gpu = profile.vram_bytes.as_ref().is_some_and(|v| v.value > 0);
+    match fit {
+        FitLevel::None => None,
+        _ if profile.unified_memory => Some(MemoryDevice::UnifiedMemory),
+        FitLevel::Vram => Some(MemoryDevice::Gpu),
+        FitLevel::Ram if has_gpu => Some(MemoryDevice::GpuAndCpuRam),
+        FitLevel::Ram => Some(MemoryDevice::CpuRam),
+    }
+}
+
+/// Answer to "can I run this?": the estimate, its parts and where it lands.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct MemoryEstimate {
+    pub params_b: f64,
+    pub quantization: String,
+    pub context_len: u64,
+    pub breakdown: MemoryBreakdown,
+    pub estimated_memory_bytes: u64,
+    pub fit: FitLevel,
+    /// None when the model doesn't fit.
+    pub device: Option<MemoryDevice>,
+    pub speed_tier: Option<SpeedTier>,
+    /// The formula with this estimate's numbers, for display.
+    pub formula: String,
+}
+
+/// Estimates memory for `params_b` billion parameters at `quantization` (Q4_K_M when None) and
+/// `context_len` tokens (4096 when None) and places it on the profile's hardware.
+pub fn memory_estimate(
+    params_b: f64,
+    quantization: Option<&str>,
+    context_len: Option<u64>,
+    profile: &HardwareProfile,
+) -> Result<MemoryEstimate, String> {
+    if params_b.is_nan() || params_b <= 0.0 {
+        return Err("Parameter count must be positive".to_string());
+    }
+    let quantization = quantization.unwrap_or("Q4_K_M").to_uppercase();
+    let per_b = llmfit::gb_per_billion_params(&quantization)
+        .ok_or_else(|| format!("Unknown quantization: {}", quantization))?;
+    let context_len = context_len.unwrap_or(llmfit::DEFAULT_CONTEXT_LEN);
+    let breakdown = llmfit::memory_breakdown(params_b, &quantization, context_len);
+    let mem_gb = breakdown.total_gb();
+    let (fit, speed_tier) = assess_fit(mem_gb, profile);
+    let formula = format!(
+        "{} B x {} GB/B ({}) = {:.1} GB weights + {:.1} GB KV cache ({} tokens) + {:.1} GB runtime = {:.1} GB",
+        params_b,
+        per_b,
+        quantization,
+        breakdown.weights_gb,
+        breakdown.kv_cache_gb,
+        context_len,
+        breakdown.overhead_gb,
+        mem_gb
+    );
+    Ok(MemoryEstimate {
+        params_b,
+        quantization,
+        context_len,
+        breakdown,
+        estimated_memory_bytes: gb_to_bytes(mem_gb),
+        fit,
+        device: memory_device(fit, profile),
+        speed_tier,
+        formula,
+    })
+}
+
+/// `memory_estimate` against the hardware detected now.
+pub fn estimate_model_memory(
+    params_b: f64,
+    quantization: Option<&str>,
+    context_len: Option<u64>,
+    cache: &LlmfitCache,
+) -> Result<MemoryEstimate, String> {
+    memory_estimate(
+        params_b,
+        quantization,
+        context_len,
+        &get_hardware_profile(cache),
+    )
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ModelFit {
+    pub model: String,
+    pub params_b: Option<f64>,
+    pub quantization: String,
+    /// True when the runtime didn't report a quantization and Q4_K_M was assumed.
+    pub quantization_assumed: bool,
+    pub estimated_memory_bytes: u64,
+    pub fit: FitLevel,
+    /// None when the model doesn't fit.
+    pub device: Option<MemoryDevice>,
+    /// None when the model doesn't fit.
+    pub speed_tier: Option<SpeedTier>,
+    pub message: String,
+    /// Set when loading the model would push memory pressure to red.
+    pub swap_warning: Option<String>,
+}
+
+/// True when the CPU is known to lack the vector units llama.cpp's CPU kernels rely on
+/// (AVX2 on x86, NEON on ARM).
+fn lacks_fast_simd(profile: &HardwareProfile) -> bool {
+    !profile.cpu_features.is_empty()
+        && !profile
+            .cpu_features
+            .iter()
+            .any(|f| f == "avx2" || f == "neon")
+}
+
+/// Classifies a memory need against the profile. RAM offload leaves 30% of available RAM for
+/// the OS and apps on GPU machines and 20% on CPU-only ones, matching llmfit's fit labels.
+/// CPU-only inference without AVX2/NEON is always slow.
+#[must_use]
+pub fn assess_fit(mem_gb: f64, profile: &HardwareProfile) -> (FitLevel, Option<SpeedTier>) {
+    let ram = profile.available_memory_bytes.value as f64 / GB;
+    let vram = profile
+        .vram_bytes
+        .as_ref()
+        .map(|v| v.value as f64 / GB)
+        .filter(|v| *v > 0.0);
+    match vram {
+        Some(vram) if mem_gb <= vram => {
+            let tier = if mem_gb <= 12.0 || (!profile.unified_memory && mem_gb <= 24.0) {
+                SpeedTier::Fast
+            } else {
+                SpeedTier::Moderate
+            };
+            (FitLevel::Vram, Some(tier))
+        }
+        Some(vram) if mem_gb <= vram + ram * 0.7 => (FitLevel::Ram, Some(SpeedTier::Slow)),
+        None if mem_gb <= ram * 0.8 => {
+            let tier = if mem_gb <= 4.0 && !lacks_fast_simd(profile) {
+                SpeedTier::Moderate
+            } else {
+                SpeedTier::Slow
+            };
+            (FitLevel::Ram, Some(tier))
+        }
+        _ => (FitLevel::None, None),
+    }
+}
+
+/// Warns when the part of the model held in system RAM (all of it on unified memory) would leave
+/// the machine under red memory pressure, i.e. swapping heavily.
+fn swap_warning(
+    name: &str,
+    mem_gb: f64,
+    fit: FitLevel,
+    profile: &HardwareProfile,
+) -> Option<String> {
+    let vram = profile
+        .vram_bytes
+        .as_ref()
+        .map_or(0.0, |v| v.value as f64 / GB);
+    let ram_gb = match fit {
+        FitLevel::Vram | FitLevel::Ram if profile.unified_memory => mem_gb,
+        FitLevel::Ram => (mem_gb - vram).max(0.0),
+        _ => return None,
+    };
+    let after = profile
+        .available_memory_bytes
+        .value
+        .saturating_sub(gb_to_bytes(ram_gb));
+    let pressure = system::classify_memory_pressure(
+        profile.total_memory_bytes.value,
+        after,
+        profile.swap_total_bytes,
+        profile.swap_used_bytes,
+    );
+    (pressure == MemoryPressure::Red).then(|| {
+        format!(
+            "Loading {} takes ~{:.1} GB of RAM and leaves ~{:.1} GB free; expect heavy swapping",
+            name,
+            ram_gb,
+            after as f64 / GB
+        )
+    })
+}
+
+/// Parameter count from a name tag such as "qwen2.5-coder:7b" or "Llama-3.1-8B-Instruct".
+#[must_use]
+pub fn params_from_name(name: &str) -> Option<f64> {
+    name.rsplit('/')
+        .next()
+        .unwrap_or(name)
+        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '.'))
+        .filter(|t| t.len() > 1 && t.to_lowercase().ends_with('b'))
+        .find_map(|t| {
+            llmfit_schema::params_billions(Some(&serde_json::Value::from(t))).filter(|n| *n > 0.0)
+        })
+}
+
+/// Estimates memory for a model and checks it against the profile. On-disk size is the best
+/// estimate of weight memory when known; otherwise parameters times bytes per weight.
+pub fn model_fit(
+    query: &str,
+    model: Option<&CatalogModel>,
+    profile: &HardwareProfile,
+) -> Result<ModelFit, String> {
+    if model.is_some_and(|m| m.runtime == RUNTIME_REMOTE) {
+        return Err(format!("{} runs remotely and needs no local memory", query));
+    }
+    let name = model.map_or(query, |m| m.name.as_str());
+    let params_b = model
+        .and_then(|m| m.parameter_size.as_deref())
+        .and_then(|p| llmfit_schema::params_billions(Some(&serde_json::Value::from(p))))
+        .or_else(|| llmfit::table_params_b(name))
+        .or_else(|| params_from_name(name));
+    let reported = model
+        .and_then(|m| m.quantization.clone())
+        .filter(|q| llmfit::gb_per_billion_params(q).is_some());
+    let quantization_assumed = reported.is_none();
+    let quantization = reported.unwrap_or_else(|| "Q4_K_M".to_string());
+    let mem_gb = match (model.and_then(|m| m.size_bytes), params_b) {
+        (Some(size), _) => size as f64 / GB + 1.0,
+        (None, Some(p)) => llmfit::estimate_mem_gb(p, &quantization),
+        (None, None) => return Err(format!("Can't estimate the size of {}", query)),
+    };
+    let (fit, speed_tier) = assess_fit(mem_gb, profile);
+    let message = match fit {
+        FitLevel::Vram => format!("{} fits in GPU memory (~{:.1} GB)", name, mem_gb),
+        FitLevel::Ram => format!(
+            "{} needs ~{:.1} GB and will run partly or fully on the CPU",
+            name, mem_gb
+        ),
+        FitLevel::None => format!(
+            "{} needs ~{:.1} GB, more than this machine can provide",
+            name, mem_gb
+        ),
+    };
+    Ok(ModelFit {
+        model: query.to_string(),
+        params_b,
+        quantization,
+        quantization_assumed,
+        estimated_memory_bytes: (mem_gb * GB) as u64,
+        fit,
+        device: memory_device(fit, profile),
+        speed_tier,
+        message,
+        swap_warning: swap_warning(name, mem_gb, fit, profile),
+    })
+}
+
+/// Checks an openclaw id ("ollama/qwen3:8b") or runtime name against the current hardware.
+/// Installed models are sized from the catalog; others from the name or the built-in table.
+pub fn check_model_fit(model: &str, cache: &LlmfitCache) -> Result<ModelFit, String> {
+    let catalog = catalog::get_model_catalog(&CatalogQuery::default());
+    let entry = catalog
+        .iter()
+        .find(|m| m.id == model || m.name == model)
+        .or_else(|| {
+            let (_, name) = model.split_once('/')?;
+            catalog.iter().find(|m| m.name == name)
+        });
+    let name = match entry {
+        Some(_) => model,
+        None => model.split_once('/').map_or(model, |(_, n)| n),
+    };
+    model_fit(name, entry, &get_hardware_profile(cache)).map(|mut f| {
+        f.model = model.to_string();
+        f
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(total_gb: u64) -> SystemInfo {
+        SystemInfo {
+            total_memory_bytes: total_gb * GB as u64,
+            available_memory_bytes: total_gb * GB as u64 / 2,
+            total_memory_human: String::new(),
+            available_memory_human: String::new(),
+            swap_total_bytes: 0,
+            swap_used_bytes: 0,
+            memory_pressure: MemoryPressure::Green,
+            cpu: Default::default(),
+            os: Default::default(),
+            gpu: None,
+        }
+    }
+
+    fn gpu(backend: &str, vram_gb: u64) -> GpuInfo {
+        GpuInfo {
+            name: "GPU".to_string(),
+            vram_bytes: vram_gb * GB as u64,
+            backend: backend.to_string(),
+            vram_used_bytes: None,
+            driver_version: None,
+        }
+    }
+
+    #[test]
+    fn test_merge_prefers_measured_values() {
+        let llmfit = LlmfitSystemJson {
+            total_ram_gb: Some(31.0),
+            cpu_cores: Some(8),
+            gpu_name: Some("NVIDIA RTX 4090".to_string()),
+            vram_gb: Some(23.0),
+            backend: Some("cuda".to_string()),
+            ..Default::default()
+        };
+        let p = merge_profile(&info(32), Some(16), Some(&gpu("cuda", 24)), Some(&llmfit));
+        assert_eq!(p.total_memory_bytes.source, "sysinfo");
+        assert_eq!(p.cpu_cores.unwrap().value, 16);
+        let vram = p.vram_bytes.unwrap();
+        assert_eq!(vram.source, "nvidia-smi");
+        assert_eq!(vram.confidence, Confidence::High);
+        assert_eq!(vram.value, 24 * GB as u64);
+        assert!(!p.unified_memory);
+    }
+
+    #[test]
+    fn test_merge_llmfit_beats_apple_estimate() {
+        let llmfit = LlmfitSystemJson {
+            gpu_name: Some("Apple M3 Max".to_string()),
+            vram_gb: Some(48.0),
+            backend: Some("metal".to_string()),
+            ..Default::default()
+        };
+        let p = merge_profile(&info(64), None, Some(&gpu("metal", 48)), Some(&llmfit));
+        assert_eq!(p.gpu_name.unwrap().value, "Apple M3 Max");
+        assert_eq!(p.backend.source, "llmfit");
+        assert!(p.unified_memory);
+        assert!(p.cpu_cores.is_none());
+
+        let native = merge_profile(&info(64), None, Some(&gpu("metal", 48)), None);
+        assert_eq!(native.vram_bytes.unwrap().confidence, Confidence::Medium);
+    }
+
+    #[test]
+    fn test_merge_cpu_only() {
+        let p = merge_profile(&info(16), Some(4), None, None);
+        assert!(p.gpu_name.is_none());
+        assert_eq!(p.backend.value, "cpu");
+        assert_eq!(p.backend.confidence, Confidence::Low);
+    }
+
+    #[test]
+    fn test_memory_estimate() {
+        let gpu_box = merge_profile(&info(32), Some(16), Some(&gpu("cuda", 12)), None);
+        let e = memory_estimate(8.0, None, Some(8192), &gpu_box).unwrap();
+        assert_eq!(e.quantization, "Q4_K_M");
+        assert!((e.breakdown.total_gb() - 6.3).abs() < 1e-9);
+        assert_eq!(e.device, Some(MemoryDevice::Gpu));
+        assert!(e.formula.ends_with("= 6.3 GB"));
+        let e = memory_estimate(32.0, Some("q4_k_m"), None, &gpu_box).unwrap();
+        assert_eq!(e.device, Some(MemoryDevice::GpuAndCpuRam));
+        let mac = merge_profile(&info(64), None, Some(&gpu("metal", 48)), None);
+        let e = memory_estimate(32.0, None, None, &mac).unwrap();
+        assert_eq!(e.device, Some(MemoryDevice::UnifiedMemory));
+        let laptop = merge_profile(&info(16), Some(8), None, None);
+        let e = memory_estimate(3.0, None, None, &laptop).unwrap();
+        assert_eq!(e.device, Some(MemoryDevice::CpuRam));
+        let e = memory_estimate(70.0, None, None, &laptop).unwrap();
+        assert_eq!((e.fit, e.device), (FitLevel::None, None));
+        assert!(memory_estimate(8.0, Some("Q3_XYZ"), None, &laptop).is_err());
+    }
+
+    #[test]
+    fn test_model_fit() {
+        let gpu_box = merge_profile(&info(32), Some(16), Some(&gpu("cuda", 12)), None);
+        let fit = model_fit("qwen2.5:7b", None, &gpu_box).unwrap();
+        assert_eq!(fit.fit, FitLevel::Vram);
+        assert_eq!(fit.speed_tier, Some(SpeedTier::Fast));
+        assert!(fit.quantization_assumed);
+        let big = model_fit("llama3.3:70b", None, &gpu_box).unwrap();
+        assert_eq!(big.fit, FitLevel::None);
+
+        let mut m = CatalogModel::local("ollama", "custom:latest");
+        m.size_bytes = Some(15 * GB as u64);
+        assert_eq!(
+            model_fit("custom:latest", Some(&m), &gpu_box).unwrap().fit,
+            FitLevel::Ram
+        );
+        assert!(model_fit("my-model", None, &gpu_box).is_err());
+
+        let laptop = merge_profile(&info(16), Some(8), None, None);
+        let small = model_fit("Llama-3.2-1B-Instruct", None, &laptop).unwrap();
+        assert_eq!(small.params_b, Some(1.0));
+        assert_eq!(small.fit, FitLevel::Ram);
+        assert_eq!(small.speed_tier, Some(SpeedTier::Moderate));
+        assert!(small.swap_warning.is_none());
+        let swapping = HardwareProfile {
+            swap_total_bytes: 4 * GB as u64,
+            swap_used_bytes: 3 * GB as u64,
+            ..laptop.clone()
+        };
+        m.size_bytes = Some(5 * GB as u64);
+        let fit = model_fit("custom:latest", Some(&m), &swapping).unwrap();
+        assert_eq!(fit.fit, FitLevel::Ram);
+        assert!(fit.swap_warning.is_some());
+        let old_cpu = HardwareProfile {
+            cpu_features: vec!["avx".to_string()],
+            ..laptop
+        };
+        let small = model_fit("Llama-3.2-1B-Instruct", None, &old_cpu).unwrap();
+        assert_eq!(small.speed_tier, Some(SpeedTier::Slow));
+    }
+}
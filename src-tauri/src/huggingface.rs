@@ -0,0 +1,391 @@
+//! Hugging Face model search and GGUF download into Ollama.
+//! Response parsing and name derivation are separated for unit tests.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::catalog::{self, ModelKind};
+use crate::gguf;
+use crate::ollama::{self, ModelTransferProgress};
+use crate::openclaw_config;
+use crate::operations::CancelToken;
+
+const HF_API_URL: &str = "https://huggingface.co/api";
+const HF_BASE_URL: &str = "https://huggingface.co";
+
+/// Search filters; all optional.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HfSearchFilters {
+    /// Only repos tagged "gguf" (default true).
+    pub gguf_only: bool,
+    /// Restrict to an author/organization (e.g. "bartowski").
+    pub author: Option<String>,
+    pub min_downloads: Option<u64>,
+    /// Keep only repos with at least one GGUF file at or below this size; also lists those files.
+    pub max_file_size_bytes: Option<u64>,
+    /// Max results (1-50, default 20).
+    pub limit: Option<u32>,
+}
+
+impl Default for HfSearchFilters {
+    fn default() -> Self {
+        Self {
+            gguf_only: true,
+            author: None,
+            min_downloads: None,
+            max_file_size_bytes: None,
+            limit: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct HfModel {
+    /// Repo id, e.g. "bartowski/Qwen2.5-7B-Instruct-GGUF".
+    pub id: String,
+    pub downloads: u64,
+    pub likes: u64,
+    pub tags: Vec<String>,
+    /// Hugging Face task, e.g. "text-generation" or "feature-extraction".
+    pub pipeline_tag: Option<String>,
+    pub kind: ModelKind,
+    /// GGUF files matching the size filter (only populated when max_file_size_bytes is set).
+    pub gguf_files: Vec<HfGgufFile>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct HfGgufFile {
+    pub path: String,
+    pub size_bytes: u64,
+    /// sha256 of the file from LFS metadata, when provided.
+    pub sha256: Option<String>,
+    pub quantization: Option<String>,
+}
+
+fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .timeout_connect(Duration::from_secs(10))
+        .timeout_read(Duration::from_secs(60))
+        .build()
+}
+
+/// Classifies a repo as chat or embedding from its pipeline tag and tags, falling back to the name.
+#[must_use]
+pub fn hf_model_kind(id: &str, pipeline_tag: Option<&str>, tags: &[String]) -> ModelKind {
+    let embedding_task = matches!(
+        pipeline_tag,
+        Some("feature-extraction" | "sentence-similarity")
+    );
+    if embedding_task || tags.iter().any(|t| t == "sentence-transformers") {
+        ModelKind::Embedding
+    } else {
+        catalog::classify_model_kind(id, None)
+    }
+}
+
+/// Parses the /api/models search response.
+#[must_use]
+pub fn parse_search_response(body: &str) -> Vec<HfModel> {
+    let arr: Vec<serde_json::Value> = serde_json::from_str(body).unwrap_or_default();
+    arr.iter()
+        .filter_map(|m| {
+            let id = m
+                .get("id")
+                .or_else(|| m.get("modelId"))
+                .and_then(|v| v.as_str())?;
+            let tags: Vec<String> = m
+                .get("tags")
+                .and_then(|t| t.as_array())
+                .map(|a| {
+                    a.iter()
+                        .filter_map(|v| v.as_str().map(String::from))
+                        .collect()
+                })
+                .unwrap_or_default();
+            let pipeline_tag = m.get("pipeline_tag").and_then(|v| v.as_str());
+            Some(HfModel {
+                id: id.to_string(),
+                downloads: m.get("downloads").and_then(|v| v.as_u64()).unwrap_or(0),
+                likes: m.get("likes").and_then(|v| v.as_u64()).unwrap_or(0),
+                kind: hf_model_kind(id, pipeline_tag, &tags),
+                pipeline_tag: pipeline_tag.map(String::from),
+                tags,
+                gguf_files: vec![],
+            })
+        })
+        .collect()
+}
+
+/// Parses /api/models/<repo>/tree/main and returns the .gguf files.
+#[must_use]
+pub fn parse_tree_gguf_files(body: &str) -> Vec<HfGgufFile> {
+    let arr: Vec<serde_json::Value> = serde_json::from_str(body).unwrap_or_default();
+    arr.iter()
+        .filter(|e| e.get("type").and_then(|v| v.as_str()) == Some("file"))
+        .filter_map(|e| {
+            let path = e.get("path").and_then(|v| v.as_str())?;
+            if !path.to_lowercase().ends_with(".gguf") {
+                return None;
+            }
+            let lfs = e.get("lfs");
+            Some(HfGgufFile {
+                path: path.to_string(),
+                size_bytes: lfs
+                    .and_then(|l| l.get("size"))
+                    .or_else(|| e.get("size"))
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(0),
+                sha256: lfs
+                    .and_then(|l| l.get("oid"))
+                    .and_then(|v| v.as_str())
+                    .map(String::from),
+                quantization: gguf::quantization_from_filename(path),
+            })
+        })
+        .collect()
+}
+
+/// Lists the GGUF files in a repo (main branch).
+pub fn list_huggingface_gguf_files(repo: &str) -> Result<Vec<HfGgufFile>, String> {
+    let body = agent()
+        .get(&format!("{}/models/{}/tree/main", HF_API_URL, repo))
+        .call()
+        .map_err(|e| format!("Hugging Face request failed: {}", e))?
+        .into_string()
+        .map_err(|e| e.to_string())?;
+    Ok(parse_tree_gguf_files(&body))
+}
+
+/// Size of one GGUF file in a repo, for the disk space check before downloading it.
+#[must_use]
+pub fn gguf_file_size(repo: &str, file: &str) -> Option<u64> {
+    list_huggingface_gguf_files(repo)
+        .ok()?
+        .into_iter()
+        .find(|f| f.path == file)
+        .map(|f| f.size_bytes)
+        .filter(|&size| size > 0)
+}
+
+/// Searches Hugging Face models, sorted by downloads.
+pub fn search_huggingface_models(
+    query: &str,
+    filters: &HfSearchFilters,
+) -> Result<Vec<HfModel>, String> {
+    let limit = filters.limit.unwrap_or(20).clamp(1, 50).to_string();
+    let mut req = agent()
+        .get(&format!("{}/models", HF_API_URL))
+        .query("search", query)
+        .query("sort", "downloads")
+        .query("direction", "-1")
+        .query("limit", &limit);
+    if filters.gguf_only {
+        req = req.query("filter", "gguf");
+    }
+    if let Some(author) = &filters.author {
+        req = req.query("author", author);
+    }
+    let body = req
+        .call()
+        .map_err(|e| format!("Hugging Face request failed: {}", e))?
+        .into_string()
+        .map_err(|e| e.to_string())?;
+
+    let mut models = parse_search_response(&body);
+    if let Some(min) = filters.min_downloads {
+        models.retain(|m| m.downloads >= min);
+    }
+    if let Some(max) = filters.max_file_size_bytes {
+        for m in models.iter_mut() {
+            m.gguf_files = list_huggingface_gguf_files(&m.id)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|f| f.size_bytes <= max)
+                .collect();
+        }
+        models.retain(|m| !m.gguf_files.is_empty());
+    }
+    Ok(models)
+}
+
+/// Derives an Ollama model name from a repo and file, e.g.
+/// ("bartowski/Qwen2.5-7B-Instruct-GGUF", "Qwen2.5-7B-Instruct-Q4_K_M.gguf") -> "qwen2.5-7b-instruct:q4_k_m".
+#[must_use]
+pub fn default_ollama_name(repo: &str, file: &str) -> String {
+    let base = repo.rsplit('/').next().unwrap_or(repo).to_lowercase();
+    let base = base
+        .strip_suffix("-gguf")
+        .or_else(|| base.strip_suffix("_gguf"))
+        .unwrap_or(&base);
+    let base: String = base
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let tag = gguf::quantization_from_filename(file)
+        .map(|q| q.to_lowercase())
+        .unwrap_or_else(|| "latest".to_string());
+    format!("{}:{}", base, tag)
+}
+
+fn downloads_dir() -> PathBuf {
+    openclaw_config::host_config_dir().join("downloads")
+}
+
+fn download_file(
+    url: &str,
+    dest: &Path,
+    model: &str,
+    cancel: &CancelToken,
+    on_progress: &mut impl FnMut(ModelTransferProgress),
+) -> Result<String, String> {
+    let resp = agent()
+        .get(url)
+        .call()
+        .map_err(|e| format!("Download failed: {}", e))?;
+    let total = resp
+        .header("Content-Length")
+        .and_then(|v| v.parse::<u64>().ok());
+    let mut reader = resp.into_reader();
+    let mut out = File::create(dest).map_err(|e| e.to_string())?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 256 * 1024];
+    let mut completed: u64 = 0;
+    let mut last_reported: u64 = 0;
+    loop {
+        cancel.check()?;
+        let n = reader.read(&mut buf).map_err(|e| e.to_string())?;
+        if n == 0 {
+            break;
+        }
+        out.write_all(&buf[..n]).map_err(|e| e.to_string())?;
+        hasher.update(&buf[..n]);
+        completed += n as u64;
+        // Report roughly every 8 MB to keep event traffic low.
+        if completed - last_reported >= 8 * 1024 * 1024 {
+            last_reported = completed;
+            on_progress(
+                ModelTransferProgress::new(model, "download", "downloading")
+                    .with_bytes(completed, total),
+            );
+        }
+    }
+    on_progress(
+        ModelTransferProgress::new(model, "download", "downloaded").with_bytes(completed, total),
+    );
+    let hex: String = hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    Ok(format!("sha256:{}", hex))
+}
+
+/// Downloads a GGUF file from a Hugging Face repo and creates an Ollama model from it.
+/// Returns the Ollama model name. The downloaded file is removed once Ollama has the blob.
+pub fn download_to_ollama(
+    repo: &str,
+    file: &str,
+    model_name: Option<&str>,
+    cancel: &CancelToken,
+    mut on_progress: impl FnMut(ModelTransferProgress),
+) -> Result<String, String> {
+    if !file.to_lowercase().ends_with(".gguf") {
+        return Err("Only .gguf files can be imported into Ollama".to_string());
+    }
+    let model = model_name
+        .map(String::from)
+        .filter(|n| !n.trim().is_empty())
+        .unwrap_or_else(|| default_ollama_name(repo, file));
+    let file_name = Path::new(file)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or("Invalid file name")?;
+
+    let dir = downloads_dir();
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let dest = dir.join(&file_name);
+    let url = format!("{}/{}/resolve/main/{}", HF_BASE_URL, repo, file);
+
+    let result = (|| {
+        let digest = download_file(&url, &dest, &model, cancel, &mut on_progress)?;
+        on_progress(ModelTransferProgress::new(
+            &model,
+            "upload",
+            "uploading to Ollama",
+        ));
+        cancel.check()?;
+        ollama::push_blob(&dest, &digest)?;
+        ollama::create_model_from_blob(&model, &file_name, &digest, cancel, &mut on_progress)
+    })();
+    let _ = fs::remove_file(&dest);
+    result.map(|_| model)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_search_response() {
+        let body = r#"[
+            {"id":"bartowski/Qwen2.5-7B-Instruct-GGUF","downloads":120000,"likes":80,"tags":["gguf","text-generation"]},
+            {"modelId":"org/other","downloads":5},
+            {"id":"nomic-ai/nomic-embed-text-v1.5-GGUF","pipeline_tag":"sentence-similarity"}
+        ]"#;
+        let models = parse_search_response(body);
+        assert_eq!(models.len(), 3);
+        assert_eq!(models[0].downloads, 120000);
+        assert_eq!(models[0].kind, ModelKind::Chat);
+        assert_eq!(models[2].kind, ModelKind::Embedding);
+        assert!(models[0].tags.contains(&"gguf".to_string()));
+        assert_eq!(models[1].id, "org/other");
+        assert!(parse_search_response("not json").is_empty());
+    }
+
+    #[test]
+    fn test_parse_tree_gguf_files() {
+        let body = r#"[
+            {"type":"file","path":"README.md","size":100},
+            {"type":"file","path":"Qwen2.5-7B-Instruct-Q4_K_M.gguf","size":135,
+             "lfs":{"oid":"abc123","size":4683074240}},
+            {"type":"directory","path":"sub.gguf"}
+        ]"#;
+        let files = parse_tree_gguf_files(body);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].size_bytes, 4683074240);
+        assert_eq!(files[0].sha256.as_deref(), Some("abc123"));
+        assert_eq!(files[0].quantization.as_deref(), Some("Q4_K_M"));
+    }
+
+    #[test]
+    fn test_default_ollama_name() {
+        assert_eq!(
+            default_ollama_name(
+                "bartowski/Qwen2.5-7B-Instruct-GGUF",
+                "Qwen2.5-7B-Instruct-Q4_K_M.gguf"
+            ),
+            "qwen2.5-7b-instruct:q4_k_m"
+        );
+        assert_eq!(
+            default_ollama_name("me/My Model", "model.gguf"),
+            "my-model:latest"
+        );
+    }
+
+    #[test]
+    fn test_download_to_ollama_rejects_non_gguf() {
+        let cancel = CancelToken::default();
+        assert!(download_to_ollama("a/b", "model.safetensors", None, &cancel, |_| {}).is_err());
+    }
+}
@@ -0,0 +1,74 @@
+//! Host configuration logic shared by the desktop app and the `openclaw-host-config` CLI:
+//! openclaw.json and agents, runtime detection, the model catalog, hardware and llmfit. Nothing
+//! here depends on Tauri.
+
+pub mod agent_archive;
+pub mod agent_details;
+pub mod agent_diff;
+pub mod agent_env;
+pub mod agent_instructions;
+pub mod agent_lint;
+pub mod agent_runtime;
+pub mod agent_usage;
+pub mod agents;
+pub mod app_state;
+pub mod benchmark;
+pub mod catalog;
+pub mod concurrency;
+pub mod config_drift;
+pub mod config_git;
+pub mod config_recovery;
+pub mod config_snippet;
+pub mod config_templates;
+pub mod costs;
+pub mod deep_link;
+pub mod detection;
+pub mod diagnostics;
+pub mod disk_usage;
+pub mod doctor;
+pub mod gateway;
+pub mod gateway_reachability;
+pub mod gateway_tls;
+pub mod gateway_tokens;
+pub mod gateway_requests;
+pub mod gguf;
+pub mod hardware;
+pub mod huggingface;
+pub mod live_stats;
+pub mod llmfit;
+pub mod llmfit_install;
+pub mod llmfit_schema;
+pub mod mcp;
+pub mod mcp_import;
+pub mod metrics;
+pub mod lm_studio;
+pub mod local_mode;
+pub mod model_deprecations;
+pub mod model_limits;
+pub mod model_search;
+pub mod modelfile;
+pub mod models_available;
+pub mod ollama;
+pub mod ollama_update;
+pub mod openclaw_config;
+pub mod operations;
+pub mod openrouter;
+pub mod pagination;
+pub mod pairing;
+pub mod policy;
+pub mod provider_api;
+pub mod provider_export;
+pub mod provider_merge;
+pub mod provider_presets;
+pub mod remote_hosts;
+pub mod routing;
+pub mod runtime_usage;
+pub mod scheduler;
+pub mod settings;
+pub mod spend_limits;
+pub mod setup;
+pub mod system;
+pub mod thermal;
+pub mod usage_stats;
+pub mod watcher;
+pub mod webhooks;
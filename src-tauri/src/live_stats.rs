@@ -0,0 +1,96 @@
+//! Periodic resource samples (CPU load, memory, swap, temperatures and throttling) pushed to the
+//! UI while a dashboard is open, so slow generation can be matched against what the machine was
+//! doing at the time. The stream runs on its own thread until stopped.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use sysinfo::System;
+
+use crate::operations;
+use crate::system::{self, MemoryPressure};
+use crate::thermal::{self, ThermalState};
+
+const DEFAULT_INTERVAL_MS: u64 = 2000;
+/// Thermal readings shell out to vendor tools; sampling faster than this isn't useful.
+const MIN_INTERVAL_MS: u64 = 500;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LiveStats {
+    pub timestamp_ms: u64,
+    /// Across all cores, 0-100.
+    pub cpu_usage_percent: f32,
+    pub available_memory_bytes: u64,
+    pub swap_used_bytes: u64,
+    pub memory_pressure: MemoryPressure,
+    pub thermal: ThermalState,
+}
+
+fn sample(sys: &mut System) -> LiveStats {
+    sys.refresh_cpu_usage();
+    sys.refresh_memory();
+    let (total, available) = (sys.total_memory(), sys.available_memory());
+    let (swap_total, swap_used) = (sys.total_swap(), sys.used_swap());
+    LiveStats {
+        timestamp_ms: operations::now_ms(),
+        cpu_usage_percent: sys.global_cpu_usage(),
+        available_memory_bytes: available,
+        swap_used_bytes: swap_used,
+        memory_pressure: system::classify_memory_pressure(total, available, swap_total, swap_used),
+        thermal: thermal::get_thermal_state(),
+    }
+}
+
+/// Managed state for the stream; at most one runs at a time. Each run has its own flag, so a
+/// thread from a stopped run can't be revived by the next `start`.
+#[derive(Default)]
+pub struct LiveStatsStream {
+    running: Mutex<Option<Arc<AtomicBool>>>,
+}
+
+impl LiveStatsStream {
+    /// Starts sampling every `interval_ms` (default 2 s, at least 0.5 s). Does nothing if the
+    /// stream is already running.
+    pub fn start<F>(&self, interval_ms: Option<u64>, on_sample: F)
+    where
+        F: Fn(LiveStats) + Send + 'static,
+    {
+        let mut current = self.running.lock().unwrap_or_else(|e| e.into_inner());
+        if current.is_some() {
+            return;
+        }
+        let running = Arc::new(AtomicBool::new(true));
+        *current = Some(Arc::clone(&running));
+        drop(current);
+        let interval = Duration::from_millis(
+            interval_ms
+                .unwrap_or(DEFAULT_INTERVAL_MS)
+                .max(MIN_INTERVAL_MS),
+        );
+        thread::spawn(move || {
+            let mut sys = System::new();
+            // CPU usage is a delta between refreshes; prime the first one.
+            sys.refresh_cpu_usage();
+            loop {
+                thread::sleep(interval);
+                if !running.load(Ordering::SeqCst) {
+                    break;
+                }
+                on_sample(sample(&mut sys));
+            }
+        });
+    }
+
+    pub fn stop(&self) {
+        let current = self
+            .running
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+        if let Some(running) = current {
+            running.store(false, Ordering::SeqCst);
+        }
+    }
+}
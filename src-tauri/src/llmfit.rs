@@ -1,45 +1,561 @@
 //! llmfit integration: system specs and model recommendations.
 //! See https://github.com/AlexsJones/llmfit
+//! When llmfit isn't installed, a native fallback (sysinfo + GPU detection + an embedded
+//! requirements table) produces the same shapes.
 
 use serde::{Deserialize, Serialize};
 use std::process::Command;
+use std::sync::Mutex;
+use std::time::Duration;
 
-/// Runs `llmfit --json system` and parses JSON. Returns None if llmfit not installed or fails.
-#[must_use]
-pub fn get_llmfit_system() -> Option<LlmfitSystemJson> {
-    let out = Command::new("llmfit")
-        .args(["--json", "system"])
-        .output()
-        .ok()?;
+use crate::agents;
+use crate::catalog::{self, RUNTIME_LM_STUDIO, RUNTIME_OLLAMA};
+use crate::llmfit_install;
+use crate::llmfit_schema;
+use crate::models_available;
+use crate::ollama::{self, ModelTransferProgress};
+use crate::openclaw_config;
+use crate::operations::{self, CancelToken};
+use crate::system;
+
+const GB: f64 = 1024.0 * 1024.0 * 1024.0;
+/// llmfit scans the hardware and scores its whole model table, which can take a while.
+const LLMFIT_TIMEOUT: Duration = Duration::from_secs(120);
+/// How many recommendations to ask llmfit for: more than its model table holds, so the filters see
+/// every model before the result is cut to the requested limit.
+const LLMFIT_FETCH_LIMIT: u16 = 1000;
+
+/// Models the native fallback can recommend: (Ollama name, billions of parameters, use case, native context).
+const MODEL_TABLE: &[(&str, f64, &str, u64)] = &[
+    ("qwen2.5:0.5b", 0.5, "general", 32_768),
+    ("llama3.2:1b", 1.2, "general", 131_072),
+    ("qwen2.5:1.5b", 1.5, "general", 32_768),
+    ("llama3.2:3b", 3.2, "general", 131_072),
+    ("gemma3:4b", 4.3, "general", 131_072),
+    ("qwen2.5:7b", 7.6, "general", 32_768),
+    ("qwen2.5-coder:7b", 7.6, "coding", 32_768),
+    ("deepseek-r1:7b", 7.6, "reasoning", 131_072),
+    ("llama3.1:8b", 8.0, "general", 131_072),
+    ("qwen3:8b", 8.2, "general", 40_960),
+    ("gemma3:12b", 12.2, "general", 131_072),
+    ("mistral-nemo:12b", 12.2, "general", 131_072),
+    ("phi4:14b", 14.7, "reasoning", 16_384),
+    ("qwen2.5:14b", 14.8, "general", 32_768),
+    ("qwen2.5-coder:14b", 14.8, "coding", 32_768),
+    ("deepseek-r1:14b", 14.8, "reasoning", 131_072),
+    ("gpt-oss:20b", 20.9, "reasoning", 131_072),
+    ("mistral-small:24b", 23.6, "general", 32_768),
+    ("gemma3:27b", 27.4, "general", 131_072),
+    ("qwen3:30b", 30.5, "general", 40_960),
+    ("qwen2.5-coder:32b", 32.8, "coding", 32_768),
+    ("deepseek-r1:32b", 32.8, "reasoning", 131_072),
+    ("llama3.3:70b", 70.6, "general", 131_072),
+    ("qwen2.5:72b", 72.7, "general", 32_768),
+    ("nomic-embed-text", 0.137, "embedding", 8_192),
+    ("mxbai-embed-large", 0.335, "embedding", 512),
+    ("bge-m3", 0.568, "embedding", 8_192),
+];
+
+/// One round of shelled-out llmfit results (or native specs when llmfit is missing).
+#[derive(Clone, Debug)]
+struct LlmfitSnapshot {
+    system: LlmfitSystemJson,
+    /// What llmfit itself reported; None when `system` is the native fallback.
+    llmfit_system: Option<LlmfitSystemJson>,
+    /// llmfit's unfiltered list; None when llmfit is unavailable and recommendations are native.
+    llmfit_recommendations: Option<Vec<LlmfitRecommendation>>,
+    /// Unix seconds.
+    fetched_at: u64,
+}
+
+/// Age of the cached results, for the UI's "updated N minutes ago".
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LlmfitCacheInfo {
+    /// Unix seconds.
+    pub fetched_at: u64,
+    pub age_secs: u64,
+    /// "llmfit" or "native".
+    pub source: String,
+}
+
+/// Managed state caching llmfit output between panel loads; filled on first use and by `refresh`.
+#[derive(Default)]
+pub struct LlmfitCache(Mutex<Option<LlmfitSnapshot>>);
+
+fn fetch_snapshot() -> LlmfitSnapshot {
+    let llmfit_system = run_llmfit_system();
+    LlmfitSnapshot {
+        system: llmfit_system.clone().unwrap_or_else(native_system),
+        llmfit_system,
+        llmfit_recommendations: get_llmfit_recommendations_inner(LLMFIT_FETCH_LIMIT),
+        fetched_at: operations::now_secs(),
+    }
+}
+
+impl LlmfitCache {
+    fn snapshot(&self) -> LlmfitSnapshot {
+        let mut guard = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        guard.get_or_insert_with(fetch_snapshot).clone()
+    }
+
+    /// Re-runs llmfit (or native detection) and replaces the cached results.
+    pub fn refresh(&self) -> LlmfitCacheInfo {
+        let snapshot = fetch_snapshot();
+        let info = cache_info(&snapshot);
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = Some(snapshot);
+        info
+    }
+
+    /// Drops cached results so the next read fetches again (e.g. after installing llmfit).
+    pub fn invalidate(&self) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    #[must_use]
+    pub fn info(&self) -> LlmfitCacheInfo {
+        cache_info(&self.snapshot())
+    }
+
+    /// llmfit system specs, or the native fallback when llmfit isn't installed.
+    #[must_use]
+    pub fn system(&self) -> LlmfitSystemJson {
+        self.snapshot().system
+    }
+
+    /// llmfit system specs only when llmfit produced them (no native fallback).
+    #[must_use]
+    pub fn llmfit_system(&self) -> Option<LlmfitSystemJson> {
+        self.snapshot().llmfit_system
+    }
+
+    /// Filtered recommendations from cached llmfit output, or native ones computed from cached specs.
+    /// Installed state is checked live since models come and go.
+    #[must_use]
+    pub fn recommendations(
+        &self,
+        limit: u8,
+        filters: &RecommendationFilters,
+    ) -> Vec<LlmfitRecommendation> {
+        let snapshot = self.snapshot();
+        let mut recs = match snapshot.llmfit_recommendations {
+            Some(recs) => apply_filters(recs, filters),
+            None => native_recommendations(&snapshot.system, filters),
+        };
+        recs.truncate(limit.clamp(1, 20) as usize);
+        let providers =
+            openclaw_config::get_openclaw_providers_raw().unwrap_or(serde_json::json!({}));
+        enrich_recommendations(
+            &mut recs,
+            &models_available::get_ollama_models(),
+            &models_available::get_lm_studio_models(),
+            &providers,
+        );
+        recs
+    }
+}
+
+fn cache_info(snapshot: &LlmfitSnapshot) -> LlmfitCacheInfo {
+    LlmfitCacheInfo {
+        fetched_at: snapshot.fetched_at,
+        age_secs: operations::now_secs().saturating_sub(snapshot.fetched_at),
+        source: if snapshot.llmfit_recommendations.is_some() {
+            "llmfit".to_string()
+        } else {
+            "native".to_string()
+        },
+    }
+}
+
+/// Runs `llmfit --json system` and parses it for the installed llmfit version.
+/// Returns None if llmfit not installed or fails.
+fn run_llmfit_system() -> Option<LlmfitSystemJson> {
+    let cmd = llmfit_install::llmfit_command()?;
+    let out = operations::run_command(
+        Command::new(&cmd).args(["--json", "system"]),
+        LLMFIT_TIMEOUT,
+        None,
+    )
+    .ok()?;
     if !out.status.success() {
         return None;
     }
     let body = String::from_utf8_lossy(&out.stdout);
-    serde_json::from_str(&body).ok()
+    let version = llmfit_install::llmfit_version(&cmd);
+    llmfit_schema::parse_llmfit_system(&body, version.as_deref())
+}
+
+/// Narrows recommendations to what the agent will do. All fields optional.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecommendationFilters {
+    /// "chat" (general-purpose), "coding", "reasoning" or "embedding". When unset, embedding models are left out.
+    pub use_case: Option<String>,
+    /// Upper bound on estimated memory use.
+    pub max_memory_gb: Option<f64>,
+    pub min_context_length: Option<u64>,
+    /// Preferred quantization ("Q4_K_M", "Q5_K_M", "Q6_K", "Q8_0", "F16"); default Q4_K_M.
+    pub quantization: Option<String>,
 }
 
-/// Runs `llmfit recommend --json --limit N` and parses JSON. Returns empty vec if llmfit fails.
+/// Approximate bytes per weight for common GGUF quantizations.
+pub(crate) fn gb_per_billion_params(quantization: &str) -> Option<f64> {
+    match quantization.to_uppercase().as_str() {
+        "Q4_0" | "Q4_K_S" => Some(0.57),
+        "Q4_K_M" => Some(0.6),
+        "Q5_K_M" | "Q5_K_S" => Some(0.71),
+        "Q6_K" => Some(0.82),
+        "Q8_0" => Some(1.06),
+        "F16" | "BF16" => Some(2.0),
+        _ => None,
+    }
+}
+
+fn use_case_matches(filter: Option<&str>, use_case: Option<&str>) -> bool {
+    let use_case = use_case.unwrap_or("general").to_lowercase();
+    match filter.map(str::to_lowercase).as_deref() {
+        None => use_case != "embedding",
+        Some("chat") | Some("general") => use_case == "general" || use_case == "chat",
+        Some(f) => use_case.contains(f),
+    }
+}
+
+/// Applies filters to recommendations (llmfit output or native). Unknown values pass the context
+/// and quantization filters, since llmfit doesn't always report them.
 #[must_use]
-pub fn get_llmfit_recommendations(limit: u8) -> Vec<LlmfitRecommendation> {
-    get_llmfit_recommendations_inner(limit).unwrap_or_default()
+pub fn apply_filters(
+    recs: Vec<LlmfitRecommendation>,
+    filters: &RecommendationFilters,
+) -> Vec<LlmfitRecommendation> {
+    recs.into_iter()
+        .filter(|r| use_case_matches(filters.use_case.as_deref(), r.use_case.as_deref()))
+        .filter(|r| {
+            filters
+                .max_memory_gb
+                .is_none_or(|max| r.mem_gb.is_none_or(|m| m <= max))
+        })
+        .filter(|r| {
+            filters
+                .min_context_length
+                .is_none_or(|min| r.context_length.is_none_or(|c| c >= min))
+        })
+        .filter(|r| {
+            filters.quantization.as_ref().is_none_or(|q| {
+                r.quantization
+                    .as_ref()
+                    .is_none_or(|rq| rq.eq_ignore_ascii_case(q))
+            })
+        })
+        .collect()
 }
 
-fn get_llmfit_recommendations_inner(limit: u8) -> Option<Vec<LlmfitRecommendation>> {
-    let limit = limit.min(20).max(1);
-    let out = Command::new("llmfit")
-        .args(["recommend", "--json", "--limit", &limit.to_string()])
-        .output()
-        .ok()?;
+/// Comparison key for a model name and tag: lowercase, org prefix, ".gguf", a quantization suffix
+/// ("-q4_k_m", "@q4_k_m") and "-instruct"/"-gguf" dropped, ':' as '-', "latest" tag ignored.
+fn match_key(name: &str) -> String {
+    let base = name.rsplit('/').next().unwrap_or(name).to_lowercase();
+    let base = base.strip_suffix(".gguf").unwrap_or(&base);
+    let base = base.split('@').next().unwrap_or(base);
+    let base = base.strip_suffix(":latest").unwrap_or(base);
+    let base = match base.rsplit_once('-') {
+        Some((rest, quant)) if gb_per_billion_params(quant).is_some() => rest,
+        _ => base,
+    };
+    base.replace(':', "-")
+        .replace("-instruct", "")
+        .replace("-gguf", "")
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '.' || *c == '-')
+        .collect()
+}
+
+/// Finds an installed model whose normalized name and tag equal the recommendation's:
+/// (runtime, installed name).
+#[must_use]
+pub fn find_installed(
+    name: &str,
+    ollama: &[String],
+    lm_studio: &[String],
+) -> Option<(&'static str, String)> {
+    let key = match_key(name);
+    let find = |models: &[String]| models.iter().find(|m| match_key(m) == key).cloned();
+    find(ollama)
+        .map(|m| (RUNTIME_OLLAMA, m))
+        .or_else(|| find(lm_studio).map(|m| (RUNTIME_LM_STUDIO, m)))
+}
+
+/// Marks each recommendation installed/not and attaches the action the UI offers: the openclaw
+/// provider/model id to use, or an `ollama pull` command to install.
+pub fn enrich_recommendations(
+    recs: &mut [LlmfitRecommendation],
+    ollama: &[String],
+    lm_studio: &[String],
+    providers: &serde_json::Value,
+) {
+    for r in recs.iter_mut() {
+        let name = match &r.name {
+            Some(n) => n.clone(),
+            None => continue,
+        };
+        match find_installed(&name, ollama, lm_studio) {
+            Some((runtime, installed_name)) => {
+                let provider = catalog::provider_for_runtime(runtime, providers)
+                    .unwrap_or_else(|| runtime.to_string());
+                r.installed = true;
+                r.runtime = Some(runtime.to_string());
+                r.provider_model_id = Some(format!("{}/{}", provider, installed_name));
+                r.pull_command = None;
+            }
+            None => {
+                r.installed = false;
+                r.runtime = None;
+                // Ollama library names have no org prefix; HF-style repo ids can't be pulled by name.
+                let provider = catalog::provider_for_runtime(RUNTIME_OLLAMA, providers)
+                    .unwrap_or_else(|| RUNTIME_OLLAMA.to_string());
+                let pullable = !name.contains('/');
+                r.pull_command = pullable.then(|| format!("ollama pull {}", name));
+                r.provider_model_id = pullable.then(|| format!("{}/{}", provider, name));
+            }
+        }
+    }
+}
+
+/// Outcome of `apply_recommendation`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AppliedRecommendation {
+    /// openclaw "provider/model" id now configured.
+    pub model_id: String,
+    /// Whether the model had to be pulled.
+    pub pulled: bool,
+    /// "primary" or "fallback" when the model was assigned a role.
+    pub role: Option<String>,
+}
+
+/// Pulls a recommended model into Ollama (unless already installed in a runtime), registers it under
+/// its provider and agents.defaults.models, optionally makes it primary/fallback, and syncs the
+/// providers into `agent`'s models.json when given. The role goes on `agent`'s own model override
+/// when given, else on agents.defaults. Syncing the default agent needs `force`; without it that
+/// fails before anything is pulled.
+pub fn apply_recommendation(
+    recommendation: &LlmfitRecommendation,
+    agent: Option<&str>,
+    force: bool,
+    role: Option<&str>,
+    cancel: &CancelToken,
+    on_progress: impl FnMut(ModelTransferProgress),
+) -> Result<AppliedRecommendation, String> {
+    let name = recommendation
+        .name
+        .as_deref()
+        .filter(|n| !n.trim().is_empty())
+        .ok_or("Recommendation has no model name")?;
+    if let Some(r) = role {
+        if r != "primary" && r != "fallback" {
+            return Err(format!("Unknown model role: {}", r));
+        }
+    }
+    if let Some(agent) = agent {
+        let root = openclaw_config::read_config_root()?;
+        agents::guard_default_agent(&root, agent, "overwrite its providers", force)?;
+    }
+
+    let installed = find_installed(
+        name,
+        &models_available::get_ollama_models(),
+        &models_available::get_lm_studio_models(),
+    );
+    let (runtime, model, pulled) = match installed {
+        Some((runtime, installed_name)) => (runtime, installed_name, false),
+        None => {
+            if name.contains('/') {
+                return Err(format!("{} is not in the Ollama library", name));
+            }
+            ollama::pull_model(name, cancel, on_progress)?;
+            (RUNTIME_OLLAMA, name.to_string(), true)
+        }
+    };
+
+    let mut root = openclaw_config::read_config_root()?;
+    let providers = root
+        .pointer("/models/providers")
+        .cloned()
+        .unwrap_or(serde_json::json!({}));
+    let provider =
+        catalog::provider_for_runtime(runtime, &providers).unwrap_or_else(|| runtime.to_string());
+    openclaw_config::register_provider_model(&mut root, &provider, &model)?;
+    let model_id = format!("{}/{}", provider, model);
+    match (role, agent) {
+        (Some(r), Some(agent)) => agents::set_agent_model_role(&mut root, agent, &model_id, r)?,
+        (Some(r), None) => openclaw_config::set_model_role(&mut root, &model_id, r)?,
+        (None, _) => {}
+    }
+    openclaw_config::write_config_root(&root)?;
+
+    if let Some(agent) = agent {
+        agents::update_agent_providers_from_openclaw(agent, force)?;
+    }
+    Ok(AppliedRecommendation {
+        model_id,
+        pulled,
+        role: role.map(String::from),
+    })
+}
+
+fn get_llmfit_recommendations_inner(limit: u16) -> Option<Vec<LlmfitRecommendation>> {
+    let out = operations::run_command(
+        Command::new(llmfit_install::llmfit_command()?).args([
+            "recommend",
+            "--json",
+            "--limit",
+            &limit.to_string(),
+        ]),
+        LLMFIT_TIMEOUT,
+        None,
+    )
+    .ok()?;
     if !out.status.success() {
         return None;
     }
     let body = String::from_utf8_lossy(&out.stdout);
-    serde_json::from_str(&body).ok()
+    llmfit_schema::parse_llmfit_recommendations(&body)
+}
+
+/// System specs from sysinfo and GPU vendor tools, in llmfit's shape.
+#[must_use]
+pub fn native_system() -> LlmfitSystemJson {
+    let info = system::get_system_info();
+    let gpu = system::detect_gpu();
+    LlmfitSystemJson {
+        total_ram_gb: Some(info.total_memory_bytes as f64 / GB),
+        available_ram_gb: Some(info.available_memory_bytes as f64 / GB),
+        cpu_cores: std::thread::available_parallelism()
+            .ok()
+            .map(|n| n.get() as u32),
+        gpu_name: gpu.as_ref().map(|g| g.name.clone()),
+        vram_gb: gpu.as_ref().map(|g| g.vram_bytes as f64 / GB),
+        backend: Some(gpu.map_or_else(|| "cpu".to_string(), |g| g.backend)),
+    }
+}
+
+/// Parameter count (billions) of a model in the embedded table, matched by Ollama name.
+#[must_use]
+pub fn table_params_b(name: &str) -> Option<f64> {
+    let name = name.to_lowercase();
+    MODEL_TABLE
+        .iter()
+        .find(|(n, ..)| *n == name || name.strip_suffix(":latest") == Some(*n))
+        .map(|(_, params_b, ..)| *params_b)
+}
+
+/// Context length runtimes allocate when none is configured (Ollama's default).
+pub const DEFAULT_CONTEXT_LEN: u64 = 4096;
+/// f16 KV cache per token for an 8B model with grouped-query attention
+/// (32 layers x 8 KV heads x 128 dims x K and V x 2 bytes).
+const KV_BYTES_PER_TOKEN_8B: f64 = 128.0 * 1024.0;
+/// Compute buffers and runtime state besides weights and KV cache.
+const RUNTIME_OVERHEAD_GB: f64 = 0.5;
+
+/// The parts of a model's memory need, in GB.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct MemoryBreakdown {
+    pub weights_gb: f64,
+    pub kv_cache_gb: f64,
+    pub overhead_gb: f64,
+}
+
+impl MemoryBreakdown {
+    #[must_use]
+    pub fn total_gb(&self) -> f64 {
+        self.weights_gb + self.kv_cache_gb + self.overhead_gb
+    }
+}
+
+/// Memory for a model: weights = params x GB per billion for the quantization (Q4_K_M, 0.6,
+/// when unknown); KV cache = context x 128 KiB per token scaled by sqrt(params / 8), since layer
+/// count grows roughly with the square root of size; plus a fixed 0.5 GB runtime overhead.
+#[must_use]
+pub fn memory_breakdown(params_b: f64, quantization: &str, context_len: u64) -> MemoryBreakdown {
+    let kv_per_token = KV_BYTES_PER_TOKEN_8B * (params_b.max(0.0) / 8.0).sqrt();
+    MemoryBreakdown {
+        weights_gb: params_b * gb_per_billion_params(quantization).unwrap_or(0.6),
+        kv_cache_gb: kv_per_token * context_len as f64 / GB,
+        overhead_gb: RUNTIME_OVERHEAD_GB,
+    }
+}
+
+/// Approximate memory for a quantization (default Q4_K_M, ~4.7 bits/weight) at the default
+/// context length; see `memory_breakdown`.
+#[must_use]
+pub fn estimate_mem_gb(params_b: f64, quantization: &str) -> f64 {
+    memory_breakdown(params_b, quantization, DEFAULT_CONTEXT_LEN).total_gb()
+}
+
+/// Fit label for a model needing `mem_gb`: "Perfect" fits VRAM with headroom, "Good" fits VRAM
+/// (or RAM comfortably on CPU), "Marginal" needs GPU/CPU offload or most of RAM. None = too tight.
+#[must_use]
+pub fn fit_for(mem_gb: f64, system: &LlmfitSystemJson) -> Option<&'static str> {
+    let ram = system
+        .available_ram_gb
+        .or(system.total_ram_gb)
+        .unwrap_or(0.0);
+    match system.vram_gb.filter(|v| *v > 0.0) {
+        Some(vram) if mem_gb <= vram * 0.85 => Some("Perfect"),
+        Some(vram) if mem_gb <= vram => Some("Good"),
+        Some(vram) if mem_gb <= vram + ram * 0.7 => Some("Marginal"),
+        Some(_) => None,
+        None if mem_gb <= ram * 0.5 => Some("Good"),
+        None if mem_gb <= ram * 0.8 => Some("Marginal"),
+        None => None,
+    }
+}
+
+/// Recommends models from the embedded table that fit `system` and pass `filters`, best first:
+/// larger models score higher, scaled down for weaker fits.
+#[must_use]
+pub fn native_recommendations(
+    system: &LlmfitSystemJson,
+    filters: &RecommendationFilters,
+) -> Vec<LlmfitRecommendation> {
+    let quantization = filters
+        .quantization
+        .as_deref()
+        .filter(|q| gb_per_billion_params(q).is_some())
+        .unwrap_or("Q4_K_M")
+        .to_uppercase();
+    let recs: Vec<LlmfitRecommendation> = MODEL_TABLE
+        .iter()
+        .filter_map(|(name, params_b, use_case, context)| {
+            let mem_gb = estimate_mem_gb(*params_b, &quantization);
+            let fit = fit_for(mem_gb, system)?;
+            let fit_weight = match fit {
+                "Perfect" => 1.0,
+                "Good" => 0.8,
+                _ => 0.4,
+            };
+            let quality = (params_b.ln_1p() / 72.7_f64.ln_1p()).min(1.0);
+            Some(LlmfitRecommendation {
+                name: Some(name.to_string()),
+                params_b: Some(*params_b),
+                fit: Some(fit.to_string()),
+                score: Some((fit_weight * quality * 100.0).round()),
+                use_case: Some(use_case.to_string()),
+                mem_gb: Some((mem_gb * 10.0).round() / 10.0),
+                context_length: Some(*context),
+                quantization: Some(quantization.clone()),
+                ..Default::default()
+            })
+        })
+        .collect();
+    let mut recs = apply_filters(recs, filters);
+    recs.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    recs
 }
 
 // --- JSON shapes (subset of llmfit output; we only need a few fields) ---
 
-/// llmfit system JSON; field names may vary by llmfit version.
+/// llmfit system JSON; field names vary by llmfit version (see `llmfit_schema`).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct LlmfitSystemJson {
@@ -76,6 +592,18 @@ pub struct LlmfitRecommendation {
     pub score: Option<f64>,
     pub use_case: Option<String>,
     pub mem_gb: Option<f64>,
+    #[serde(alias = "context", alias = "max_context")]
+    pub context_length: Option<u64>,
+    #[serde(alias = "quant", alias = "best_quant")]
+    pub quantization: Option<String>,
+    /// Present in a local runtime (see `runtime`).
+    pub installed: bool,
+    /// "ollama" or "lmstudio" when installed.
+    pub runtime: Option<String>,
+    /// Shell command that installs the model, when not installed and pullable from the Ollama library.
+    pub pull_command: Option<String>,
+    /// openclaw "provider/model" id to configure once installed.
+    pub provider_model_id: Option<String>,
 }
 
 impl Default for LlmfitRecommendation {
@@ -87,7 +615,150 @@ impl Default for LlmfitRecommendation {
             score: None,
             use_case: None,
             mem_gb: None,
+            context_length: None,
+            quantization: None,
+            installed: false,
+            runtime: None,
+            pull_command: None,
+            provider_model_id: None,
         }
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn system(ram: f64, vram: Option<f64>) -> LlmfitSystemJson {
+        LlmfitSystemJson {
+            total_ram_gb: Some(ram),
+            available_ram_gb: Some(ram),
+            vram_gb: vram,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_memory_breakdown() {
+        let m = memory_breakdown(8.0, "Q4_K_M", 8192);
+        assert!((m.weights_gb - 4.8).abs() < 1e-9);
+        assert!((m.kv_cache_gb - 1.0).abs() < 1e-9);
+        assert!((m.total_gb() - 6.3).abs() < 1e-9);
+        let f16 = memory_breakdown(8.0, "F16", 8192);
+        assert!((f16.weights_gb - 16.0).abs() < 1e-9);
+        assert!((estimate_mem_gb(8.0, "Q4_K_M") - 5.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fit_for() {
+        let gpu = system(32.0, Some(12.0));
+        assert_eq!(
+            fit_for(estimate_mem_gb(7.6, "Q4_K_M"), &gpu),
+            Some("Perfect")
+        );
+        assert_eq!(
+            fit_for(estimate_mem_gb(23.6, "Q4_K_M"), &gpu),
+            Some("Marginal")
+        );
+        assert_eq!(fit_for(estimate_mem_gb(72.7, "Q4_K_M"), &gpu), None);
+        let cpu = system(16.0, None);
+        assert_eq!(fit_for(estimate_mem_gb(3.2, "Q4_K_M"), &cpu), Some("Good"));
+        assert_eq!(fit_for(estimate_mem_gb(32.8, "Q4_K_M"), &cpu), None);
+    }
+
+    #[test]
+    fn test_native_recommendations() {
+        let none = RecommendationFilters::default();
+        let recs = native_recommendations(&system(64.0, Some(24.0)), &none);
+        assert_eq!(recs[0].fit.as_deref(), Some("Perfect"));
+        assert!(recs[0].params_b.unwrap() >= 20.0);
+        assert!(recs.windows(2).all(|w| w[0].score >= w[1].score));
+        assert!(recs
+            .iter()
+            .all(|r| r.use_case.as_deref() != Some("embedding")));
+        assert!(native_recommendations(&system(0.5, None), &none).is_empty());
+    }
+
+    #[test]
+    fn test_recommendation_filters() {
+        let sys = system(64.0, Some(24.0));
+        let coding = native_recommendations(
+            &sys,
+            &RecommendationFilters {
+                use_case: Some("coding".into()),
+                max_memory_gb: Some(10.0),
+                ..Default::default()
+            },
+        );
+        assert!(!coding.is_empty());
+        assert!(coding
+            .iter()
+            .all(|r| r.use_case.as_deref() == Some("coding") && r.mem_gb.unwrap() <= 10.0));
+
+        let long_ctx = native_recommendations(
+            &sys,
+            &RecommendationFilters {
+                min_context_length: Some(100_000),
+                quantization: Some("q8_0".into()),
+                ..Default::default()
+            },
+        );
+        assert!(long_ctx
+            .iter()
+            .all(|r| r.context_length.unwrap() >= 100_000
+                && r.quantization.as_deref() == Some("Q8_0")));
+
+        let embed = native_recommendations(
+            &sys,
+            &RecommendationFilters {
+                use_case: Some("embedding".into()),
+                ..Default::default()
+            },
+        );
+        assert_eq!(embed.len(), 3);
+        assert!(apply_filters(embed, &RecommendationFilters::default()).is_empty());
+    }
+
+    #[test]
+    fn test_enrich_recommendations() {
+        let rec = |name: &str| LlmfitRecommendation {
+            name: Some(name.to_string()),
+            ..Default::default()
+        };
+        let mut recs = vec![
+            rec("llama3.2:3b"),
+            rec("Qwen/Qwen2.5-7B-Instruct"),
+            rec("phi4:14b"),
+            rec("org/Unknown-Model"),
+        ];
+        let ollama = vec!["llama3.2:3b".to_string()];
+        let lms = vec!["qwen2.5-7b-instruct-q4_k_m".to_string()];
+        let providers =
+            serde_json::json!({ "lmstudio": { "baseUrl": "http://127.0.0.1:1234/v1" } });
+        enrich_recommendations(&mut recs, &ollama, &lms, &providers);
+
+        assert!(recs[0].installed);
+        assert_eq!(
+            recs[0].provider_model_id.as_deref(),
+            Some("ollama/llama3.2:3b")
+        );
+        assert_eq!(recs[1].runtime.as_deref(), Some(RUNTIME_LM_STUDIO));
+        assert_eq!(
+            recs[1].provider_model_id.as_deref(),
+            Some("lmstudio/qwen2.5-7b-instruct-q4_k_m")
+        );
+        assert!(!recs[2].installed);
+        assert_eq!(
+            recs[2].pull_command.as_deref(),
+            Some("ollama pull phi4:14b")
+        );
+        assert!(recs[3].pull_command.is_none());
+
+        let installed = vec!["llama3.1:70b".to_string(), "llama3:latest".to_string()];
+        assert_eq!(
+            find_installed("llama3", &installed, &[]),
+            Some((RUNTIME_OLLAMA, "llama3:latest".to_string()))
+        );
+        assert!(find_installed("llama3.1", &installed, &[]).is_none());
+    }
+}
@@ -0,0 +1,397 @@
+//! Installing llmfit from within the app: Homebrew, cargo, or a prebuilt GitHub release binary
+//! placed in ~/.openclaw/host-config/bin. A release archive is only extracted when it matches
+//! the SHA-256 published next to it. Asset selection and checksum parsing are separated for unit
+//! tests.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use crate::detection;
+use crate::lm_studio;
+use crate::openclaw_config;
+use crate::operations::{self, VERSION_TIMEOUT};
+use crate::settings;
+
+const RELEASES_API_URL: &str = "https://api.github.com/repos/AlexsJones/llmfit/releases/latest";
+
+pub const METHOD_BREW: &str = "brew";
+pub const METHOD_CARGO: &str = "cargo";
+pub const METHOD_BINARY: &str = "binary";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LlmfitStatus {
+    pub installed: bool,
+    pub path: Option<String>,
+    pub version: Option<String>,
+    /// Install methods usable on this machine, preferred first ("brew", "cargo", "binary").
+    pub installers: Vec<String>,
+}
+
+/// Progress update emitted while installing.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InstallProgress {
+    pub method: String,
+    /// Latest line of installer output or a step description.
+    pub message: String,
+    pub done: bool,
+}
+
+fn binary_name() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "llmfit.exe"
+    } else {
+        "llmfit"
+    }
+}
+
+/// Where the prebuilt binary is installed.
+fn app_bin_dir() -> PathBuf {
+    openclaw_config::host_config_dir().join("bin")
+}
+
+/// llmfit executable: the path set in settings, PATH, then ~/.cargo/bin and the app's bin dir
+/// (neither may be on the GUI's PATH).
+#[must_use]
+pub fn llmfit_command() -> Option<String> {
+    if let Some(p) = settings::binary_path("llmfit").or_else(|| detection::command_exists("llmfit"))
+    {
+        return Some(p);
+    }
+    let cargo_bin = dirs::home_dir().map(|h| h.join(".cargo").join("bin").join(binary_name()));
+    [cargo_bin, Some(app_bin_dir().join(binary_name()))]
+        .into_iter()
+        .flatten()
+        .find(|p| p.exists())
+        .and_then(|p| p.to_str().map(String::from))
+}
+
+/// First line of `<path> --version`, e.g. "llmfit 0.4.2".
+#[must_use]
+pub fn llmfit_version(path: &str) -> Option<String> {
+    operations::run_command(Command::new(path).arg("--version"), VERSION_TIMEOUT, None)
+        .ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| detection::parse_version_line(&String::from_utf8_lossy(&o.stdout)))
+}
+
+/// Installed state, version and available installers.
+#[must_use]
+pub fn get_llmfit_status() -> LlmfitStatus {
+    let path = llmfit_command();
+    let version = path.as_deref().and_then(llmfit_version);
+    LlmfitStatus {
+        installed: path.is_some(),
+        path,
+        version,
+        installers: available_installers(),
+    }
+}
+
+/// Install methods available here, preferred first. The binary download is always possible.
+#[must_use]
+pub fn available_installers() -> Vec<String> {
+    let mut out = Vec::new();
+    if detection::command_exists("brew").is_some() {
+        out.push(METHOD_BREW.to_string());
+    }
+    if detection::command_exists("cargo").is_some() {
+        out.push(METHOD_CARGO.to_string());
+    }
+    out.push(METHOD_BINARY.to_string());
+    out
+}
+
+/// Picks the release asset for an OS/arch (Rust `std::env::consts` names) from asset names.
+#[must_use]
+pub fn select_release_asset<'a>(names: &[&'a str], os: &str, arch: &str) -> Option<&'a str> {
+    let os_keys: &[&str] = match os {
+        "macos" => &["darwin", "macos", "apple"],
+        "windows" => &["windows", "win64", "msvc"],
+        _ => &["linux"],
+    };
+    let arch_keys: &[&str] = match arch {
+        "aarch64" => &["aarch64", "arm64"],
+        _ => &["x86_64", "amd64", "x64"],
+    };
+    names.iter().copied().find(|name| {
+        let n = name.to_lowercase();
+        (n.ends_with(".tar.gz") || n.ends_with(".tgz") || n.ends_with(".zip"))
+            && os_keys.iter().any(|k| n.contains(k))
+            && arch_keys.iter().any(|k| n.contains(k))
+    })
+}
+
+/// Runs an installer command, forwarding each output line (stdout and stderr) as progress.
+pub(crate) fn run_installer(
+    method: &str,
+    cmd: &str,
+    args: &[&str],
+    on_progress: &mut impl FnMut(InstallProgress),
+) -> Result<(), String> {
+    let mut child = Command::new(cmd)
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run {}: {}", cmd, e))?;
+    let (tx, rx) = mpsc::channel::<(bool, String)>();
+    let mut readers = Vec::new();
+    if let Some(stdout) = child.stdout.take() {
+        let tx = tx.clone();
+        readers.push(thread::spawn(move || {
+            lm_studio::forward_lines(stdout, false, &tx)
+        }));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        let tx = tx.clone();
+        readers.push(thread::spawn(move || {
+            lm_studio::forward_lines(stderr, true, &tx)
+        }));
+    }
+    drop(tx);
+    let mut last_err = String::new();
+    for (is_stderr, line) in rx {
+        if is_stderr {
+            last_err = line.clone();
+        }
+        on_progress(InstallProgress {
+            method: method.to_string(),
+            message: line,
+            done: false,
+        });
+    }
+    for r in readers {
+        let _ = r.join();
+    }
+    let status = child.wait().map_err(|e| e.to_string())?;
+    if status.success() {
+        Ok(())
+    } else if last_err.is_empty() {
+        Err(format!("{} exited with {}", cmd, status))
+    } else {
+        Err(last_err)
+    }
+}
+
+fn find_file(dir: &Path, name: &str) -> Option<PathBuf> {
+    for entry in fs::read_dir(dir).ok()?.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if let Some(found) = find_file(&path, name) {
+                return Some(found);
+            }
+        } else if path.file_name().is_some_and(|n| n == name) {
+            return Some(path);
+        }
+    }
+    None
+}
+
+/// The digest in a `.sha256` file: `<hex>` or `<hex>  <file name>`, lowercased.
+fn parse_sha256_file(text: &str) -> Option<String> {
+    let hex = text.split_whitespace().next()?;
+    (hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+        .then(|| hex.to_ascii_lowercase())
+}
+
+/// Downloads the latest release archive for this platform and extracts the binary into the app's bin dir.
+fn install_binary(on_progress: &mut impl FnMut(InstallProgress)) -> Result<(), String> {
+    let step = |message: &str| InstallProgress {
+        method: METHOD_BINARY.to_string(),
+        message: message.to_string(),
+        done: false,
+    };
+    let agent = ureq::AgentBuilder::new()
+        .timeout_connect(Duration::from_secs(10))
+        .timeout_read(Duration::from_secs(120))
+        .build();
+    on_progress(step("Finding latest release"));
+    let release: serde_json::Value = agent
+        .get(RELEASES_API_URL)
+        .set("User-Agent", "openclaw-host-config")
+        .call()
+        .map_err(|e| format!("GitHub request failed: {}", e))?
+        .into_json()
+        .map_err(|e| e.to_string())?;
+    let assets: Vec<(&str, &str)> = release
+        .get("assets")
+        .and_then(|a| a.as_array())
+        .map(|a| {
+            a.iter()
+                .filter_map(|asset| {
+                    Some((
+                        asset.get("name")?.as_str()?,
+                        asset.get("browser_download_url")?.as_str()?,
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let names: Vec<&str> = assets.iter().map(|(n, _)| *n).collect();
+    let name = select_release_asset(&names, std::env::consts::OS, std::env::consts::ARCH)
+        .ok_or("No prebuilt llmfit binary for this platform")?;
+    let url = assets
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, u)| *u)
+        .ok_or("Release asset has no download URL")?;
+    let checksum_name = format!("{}.sha256", name);
+    let checksum_url = assets
+        .iter()
+        .find(|(n, _)| *n == checksum_name)
+        .map(|(_, u)| *u)
+        .ok_or_else(|| {
+            format!(
+                "The release has no checksum for {}; not installing it",
+                name
+            )
+        })?;
+
+    let work = openclaw_config::host_config_dir()
+        .join("downloads")
+        .join("llmfit");
+    let _ = fs::remove_dir_all(&work);
+    fs::create_dir_all(&work).map_err(|e| e.to_string())?;
+    let archive = work.join(name);
+    on_progress(step(&format!("Downloading {}", name)));
+    let expected = agent
+        .get(checksum_url)
+        .call()
+        .map_err(|e| format!("Checksum download failed: {}", e))?
+        .into_string()
+        .map_err(|e| e.to_string())
+        .map(|text| parse_sha256_file(&text))?
+        .ok_or_else(|| format!("{} is not a SHA-256 checksum", checksum_name))?;
+    let mut reader = agent
+        .get(url)
+        .call()
+        .map_err(|e| format!("Download failed: {}", e))?
+        .into_reader();
+    let mut file = fs::File::create(&archive).map_err(|e| e.to_string())?;
+    std::io::copy(&mut reader, &mut file).map_err(|e| e.to_string())?;
+    drop(file);
+    let bytes = fs::read(&archive).map_err(|e| e.to_string())?;
+    let actual: String = Sha256::digest(&bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    if actual != expected {
+        let _ = fs::remove_dir_all(&work);
+        return Err(format!(
+            "{} does not match its published checksum (expected {}, got {}); not installing it",
+            name, expected, actual
+        ));
+    }
+
+    // bsdtar (macOS, Windows 10+) and GNU tar handle .tar.gz; bsdtar also handles .zip.
+    on_progress(step("Extracting"));
+    let archive_str = archive.to_string_lossy().to_string();
+    let work_str = work.to_string_lossy().to_string();
+    let tar_args: Vec<&str> = if name.ends_with(".zip") {
+        vec!["-xf", &archive_str, "-C", &work_str]
+    } else {
+        vec!["-xzf", &archive_str, "-C", &work_str]
+    };
+    let out = Command::new("tar")
+        .args(&tar_args)
+        .output()
+        .map_err(|e| format!("Failed to run tar: {}", e))?;
+    if !out.status.success() {
+        return Err(String::from_utf8_lossy(&out.stderr).trim().to_string());
+    }
+    let extracted = find_file(&work, binary_name()).ok_or("llmfit binary not found in archive")?;
+    let bin_dir = app_bin_dir();
+    fs::create_dir_all(&bin_dir).map_err(|e| e.to_string())?;
+    let dest = bin_dir.join(binary_name());
+    fs::copy(&extracted, &dest).map_err(|e| e.to_string())?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&dest, fs::Permissions::from_mode(0o755)).map_err(|e| e.to_string())?;
+    }
+    let _ = fs::remove_dir_all(&work);
+    Ok(())
+}
+
+/// Installs llmfit with `method` (or the first available installer), then verifies it runs.
+/// Returns the installed status.
+pub fn install_llmfit(
+    method: Option<&str>,
+    mut on_progress: impl FnMut(InstallProgress),
+) -> Result<LlmfitStatus, String> {
+    let installers = available_installers();
+    let method = match method {
+        Some(m) if installers.iter().any(|i| i == m) => m.to_string(),
+        Some(m) => return Err(format!("Installer not available: {}", m)),
+        None => installers[0].clone(),
+    };
+    match method.as_str() {
+        METHOD_BREW => run_installer(&method, "brew", &["install", "llmfit"], &mut on_progress)?,
+        METHOD_CARGO => run_installer(&method, "cargo", &["install", "llmfit"], &mut on_progress)?,
+        _ => install_binary(&mut on_progress)?,
+    }
+
+    let status = get_llmfit_status();
+    if status.version.is_none() {
+        return Err("llmfit was installed but could not be run".to_string());
+    }
+    on_progress(InstallProgress {
+        method,
+        message: format!(
+            "Installed llmfit {}",
+            status.version.as_deref().unwrap_or_default()
+        ),
+        done: true,
+    });
+    Ok(status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_release_asset() {
+        let names = [
+            "llmfit-v0.4.0-x86_64-unknown-linux-gnu.tar.gz",
+            "llmfit-v0.4.0-aarch64-apple-darwin.tar.gz",
+            "llmfit-v0.4.0-x86_64-pc-windows-msvc.zip",
+            "llmfit-v0.4.0-x86_64-unknown-linux-gnu.tar.gz.sha256",
+        ];
+        assert_eq!(
+            select_release_asset(&names, "linux", "x86_64"),
+            Some(names[0])
+        );
+        assert_eq!(
+            select_release_asset(&names, "macos", "aarch64"),
+            Some(names[1])
+        );
+        assert_eq!(
+            select_release_asset(&names, "windows", "x86_64"),
+            Some(names[2])
+        );
+        assert_eq!(select_release_asset(&names, "linux", "aarch64"), None);
+    }
+
+    #[test]
+    fn test_parse_sha256_file() {
+        let hex = "9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08";
+        assert_eq!(
+            parse_sha256_file(&format!("{}  llmfit.tar.gz\n", hex)),
+            Some(hex.to_ascii_lowercase())
+        );
+        assert_eq!(parse_sha256_file("not a digest"), None);
+        assert_eq!(parse_sha256_file(""), None);
+    }
+
+    #[test]
+    fn test_install_rejects_unknown_method() {
+        assert!(install_llmfit(Some("apt"), |_| {}).is_err());
+    }
+}
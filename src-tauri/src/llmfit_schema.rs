@@ -0,0 +1,308 @@
+//! Version-tolerant parsing of llmfit JSON output. llmfit has renamed and nested fields across
+//! releases, and a strict serde parse drops everything on the first mismatch. Output is first
+//! classified into a schema (from its shape, with the reported version as a tie-breaker), then
+//! read by the matching adapter.
+
+use serde_json::Value;
+
+use crate::llmfit::{LlmfitRecommendation, LlmfitSystemJson};
+
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// Version assumed to switch from flat to nested output. Only consulted when the shape is ambiguous.
+const NESTED_SINCE: (u32, u32, u32) = (0, 3, 0);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LlmfitSchema {
+    /// `{"total_ram_gb": .., "gpu_name": ..}` and a bare array of recommendations.
+    Flat,
+    /// `{"system": {"ram": {..}, "gpus": [..]}}` and `{"models": [..]}`.
+    Nested,
+}
+
+/// Extracts (major, minor, patch) from output like "llmfit 0.4.2" or "v0.4".
+#[must_use]
+pub fn parse_version(text: &str) -> Option<(u32, u32, u32)> {
+    let token = text.split_whitespace().find(|t| {
+        t.trim_start_matches('v')
+            .starts_with(|c: char| c.is_ascii_digit())
+    })?;
+    let mut parts = token
+        .trim_start_matches('v')
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|p| !p.is_empty())
+        .map(|p| p.parse::<u32>().ok());
+    let major = parts.next()??;
+    let minor = parts.next().flatten().unwrap_or(0);
+    let patch = parts.next().flatten().unwrap_or(0);
+    Some((major, minor, patch))
+}
+
+fn schema_from_version(version: Option<&str>) -> LlmfitSchema {
+    match version.and_then(parse_version) {
+        Some(v) if v < NESTED_SINCE => LlmfitSchema::Flat,
+        Some(_) => LlmfitSchema::Nested,
+        None => LlmfitSchema::Flat,
+    }
+}
+
+/// Classifies `llmfit system` output. The shape wins; the version only decides when the shape is ambiguous.
+#[must_use]
+pub fn detect_system_schema(root: &Value, version: Option<&str>) -> LlmfitSchema {
+    let nested_keys = ["system", "ram", "memory", "gpus", "gpu", "cpu"];
+    let flat_keys = [
+        "total_ram_gb",
+        "total_ram",
+        "available_ram_gb",
+        "gpu_name",
+        "vram_gb",
+    ];
+    let has = |keys: &[&str]| keys.iter().any(|k| root.get(k).is_some());
+    let nested = has(&nested_keys) && root.get("gpu").is_none_or(Value::is_object);
+    match (has(&flat_keys), nested) {
+        (true, false) => LlmfitSchema::Flat,
+        (false, true) => LlmfitSchema::Nested,
+        _ => schema_from_version(version),
+    }
+}
+
+fn num(v: Option<&Value>) -> Option<f64> {
+    let v = v?;
+    v.as_f64()
+        .or_else(|| v.as_str().and_then(|s| s.trim().parse().ok()))
+}
+
+/// First numeric value among `keys`; keys ending in "_bytes" are converted to GB, "_mb" to GB.
+fn gb_field(obj: &Value, keys: &[&str]) -> Option<f64> {
+    keys.iter().find_map(|k| {
+        let n = num(obj.get(k))?;
+        Some(if k.ends_with("_bytes") {
+            n / BYTES_PER_GB
+        } else if k.ends_with("_mb") {
+            n / 1024.0
+        } else {
+            n
+        })
+    })
+}
+
+fn str_field(obj: &Value, keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .find_map(|k| obj.get(k).and_then(|v| v.as_str()))
+        .map(String::from)
+}
+
+fn parse_system_flat(root: &Value) -> LlmfitSystemJson {
+    LlmfitSystemJson {
+        total_ram_gb: gb_field(root, &["total_ram_gb", "total_ram", "total_ram_bytes"]),
+        available_ram_gb: gb_field(
+            root,
+            &["available_ram_gb", "available_ram", "available_ram_bytes"],
+        ),
+        cpu_cores: num(root.get("cpu_cores").or_else(|| root.get("cores"))).map(|n| n as u32),
+        gpu_name: str_field(root, &["gpu_name", "gpu"]),
+        vram_gb: gb_field(root, &["vram_gb", "gpu_vram_gb", "vram_bytes", "vram_mb"]),
+        backend: str_field(root, &["backend"]),
+    }
+}
+
+fn parse_system_nested(root: &Value) -> LlmfitSystemJson {
+    let sys = root.get("system").unwrap_or(root);
+    let ram = sys.get("ram").or_else(|| sys.get("memory")).unwrap_or(sys);
+    let cpu = sys.get("cpu").unwrap_or(sys);
+    // Pick the GPU with the most VRAM when several are listed.
+    let gpu = sys
+        .get("gpus")
+        .and_then(|g| g.as_array())
+        .and_then(|gpus| {
+            gpus.iter().max_by(|a, b| {
+                let va = gb_field(a, &["vram_gb", "vram_bytes", "vram_mb"]).unwrap_or(0.0);
+                let vb = gb_field(b, &["vram_gb", "vram_bytes", "vram_mb"]).unwrap_or(0.0);
+                va.partial_cmp(&vb).unwrap_or(std::cmp::Ordering::Equal)
+            })
+        })
+        .or_else(|| sys.get("gpu").filter(|g| g.is_object()));
+    LlmfitSystemJson {
+        total_ram_gb: gb_field(ram, &["total_gb", "total_ram_gb", "total_bytes"]),
+        available_ram_gb: gb_field(
+            ram,
+            &["available_gb", "available_ram_gb", "available_bytes"],
+        ),
+        cpu_cores: num(cpu.get("cores").or_else(|| cpu.get("cpu_cores"))).map(|n| n as u32),
+        gpu_name: gpu.and_then(|g| str_field(g, &["name", "model"])),
+        vram_gb: gpu.and_then(|g| gb_field(g, &["vram_gb", "vram_bytes", "vram_mb"])),
+        backend: gpu
+            .and_then(|g| str_field(g, &["backend"]))
+            .or_else(|| str_field(sys, &["backend"])),
+    }
+}
+
+/// Parses `llmfit --json system` output from any known llmfit release.
+#[must_use]
+pub fn parse_llmfit_system(body: &str, version: Option<&str>) -> Option<LlmfitSystemJson> {
+    let root: Value = serde_json::from_str(body).ok()?;
+    if !root.is_object() {
+        return None;
+    }
+    Some(match detect_system_schema(&root, version) {
+        LlmfitSchema::Flat => parse_system_flat(&root),
+        LlmfitSchema::Nested => parse_system_nested(&root),
+    })
+}
+
+/// Parameter count in billions from a number or strings like "7.6B" / "500M".
+pub(crate) fn params_billions(v: Option<&Value>) -> Option<f64> {
+    let v = v?;
+    if let Some(n) = v.as_f64() {
+        // Raw counts are large; billions are small.
+        return Some(if n > 1e6 { n / 1e9 } else { n });
+    }
+    let s = v.as_str()?.trim().to_uppercase();
+    if let Some(b) = s.strip_suffix('B') {
+        b.trim().parse().ok()
+    } else if let Some(m) = s.strip_suffix('M') {
+        m.trim().parse::<f64>().ok().map(|m| m / 1000.0)
+    } else {
+        s.parse().ok()
+    }
+}
+
+fn parse_recommendation(item: &Value) -> Option<LlmfitRecommendation> {
+    let name = str_field(item, &["name", "model", "model_name", "id"])?;
+    let score = item.get("score").and_then(|s| {
+        s.as_f64()
+            .or_else(|| s.get("total").and_then(|t| t.as_f64()))
+            .or_else(|| s.get("overall").and_then(|t| t.as_f64()))
+    });
+    Some(LlmfitRecommendation {
+        name: Some(name),
+        params_b: params_billions(
+            item.get("params_b")
+                .or_else(|| item.get("params"))
+                .or_else(|| item.get("parameters"))
+                .or_else(|| item.get("parameter_count")),
+        ),
+        fit: str_field(item, &["fit", "fit_level", "fit_label"]),
+        score,
+        use_case: str_field(item, &["use_case", "category", "use"]),
+        mem_gb: gb_field(
+            item,
+            &[
+                "mem_gb",
+                "memory_gb",
+                "memory_required_gb",
+                "required_mem_gb",
+                "mem_bytes",
+            ],
+        ),
+        context_length: num(item
+            .get("context_length")
+            .or_else(|| item.get("context"))
+            .or_else(|| item.get("max_context")))
+        .map(|n| n as u64),
+        quantization: str_field(item, &["quantization", "quant", "best_quant"]),
+        ..Default::default()
+    })
+}
+
+/// Parses `llmfit recommend --json` output: a bare array (flat releases) or an object wrapping
+/// the list under "models"/"recommendations" (nested releases). Both share the item adapter, which
+/// accepts every known field spelling. Unnamed entries are skipped.
+#[must_use]
+pub fn parse_llmfit_recommendations(body: &str) -> Option<Vec<LlmfitRecommendation>> {
+    let root: Value = serde_json::from_str(body).ok()?;
+    let items = match &root {
+        Value::Array(a) => a,
+        Value::Object(_) => ["models", "recommendations", "results"]
+            .iter()
+            .find_map(|k| root.get(k).and_then(|v| v.as_array()))?,
+        _ => return None,
+    };
+    Some(items.iter().filter_map(parse_recommendation).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SYSTEM_FLAT: &str = r#"{
+        "total_ram_gb": 31.2, "available_ram_gb": 18.4, "cpu_cores": 16,
+        "gpu_name": "NVIDIA GeForce RTX 4070", "vram_gb": 12.0, "backend": "cuda"
+    }"#;
+    const SYSTEM_FLAT_BYTES: &str = r#"{
+        "total_ram_bytes": 34359738368, "available_ram": "12.5", "cores": 8
+    }"#;
+    const SYSTEM_NESTED: &str = r#"{
+        "system": {
+            "cpu": { "name": "AMD Ryzen 9 7950X", "cores": 16 },
+            "ram": { "total_gb": 64.0, "available_gb": 40.5 },
+            "gpus": [
+                { "name": "iGPU", "vram_mb": 512, "backend": "vulkan" },
+                { "name": "NVIDIA GeForce RTX 4090", "vram_gb": 24.0, "backend": "cuda" }
+            ]
+        }
+    }"#;
+    const RECS_FLAT: &str = r#"[
+        { "name": "qwen2.5:7b", "params_b": 7.6, "fit": "Perfect", "score": 92.5, "use_case": "general", "mem_gb": 5.6 },
+        { "params": 3.2 }
+    ]"#;
+    const RECS_NESTED: &str = r#"{
+        "models": [
+            { "model": "Qwen/Qwen2.5-Coder-14B-Instruct", "parameter_count": "14.8B",
+              "fit_level": "Good", "score": { "total": 81.0 }, "category": "coding",
+              "memory_required_gb": 10.1, "best_quant": "Q4_K_M", "context": 32768 }
+        ]
+    }"#;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("llmfit 0.4.2"), Some((0, 4, 2)));
+        assert_eq!(parse_version("v1.2"), Some((1, 2, 0)));
+        assert_eq!(parse_version("llmfit"), None);
+    }
+
+    #[test]
+    fn test_parse_llmfit_system_flat() {
+        let s = parse_llmfit_system(SYSTEM_FLAT, Some("llmfit 0.2.1")).unwrap();
+        assert_eq!(s.total_ram_gb, Some(31.2));
+        assert_eq!(s.vram_gb, Some(12.0));
+        assert_eq!(s.backend.as_deref(), Some("cuda"));
+
+        let b = parse_llmfit_system(SYSTEM_FLAT_BYTES, None).unwrap();
+        assert_eq!(b.total_ram_gb, Some(32.0));
+        assert_eq!(b.available_ram_gb, Some(12.5));
+        assert_eq!(b.cpu_cores, Some(8));
+    }
+
+    #[test]
+    fn test_parse_llmfit_system_nested() {
+        // Shape wins over a version that suggests the flat schema.
+        let s = parse_llmfit_system(SYSTEM_NESTED, Some("llmfit 0.1.0")).unwrap();
+        assert_eq!(s.total_ram_gb, Some(64.0));
+        assert_eq!(s.cpu_cores, Some(16));
+        assert_eq!(s.gpu_name.as_deref(), Some("NVIDIA GeForce RTX 4090"));
+        assert_eq!(s.vram_gb, Some(24.0));
+        assert!(parse_llmfit_system("not json", None).is_none());
+        assert!(parse_llmfit_system("[]", None).is_none());
+    }
+
+    #[test]
+    fn test_parse_llmfit_recommendations() {
+        let flat = parse_llmfit_recommendations(RECS_FLAT).unwrap();
+        assert_eq!(flat.len(), 1);
+        assert_eq!(flat[0].score, Some(92.5));
+
+        let nested = parse_llmfit_recommendations(RECS_NESTED).unwrap();
+        assert_eq!(
+            nested[0].name.as_deref(),
+            Some("Qwen/Qwen2.5-Coder-14B-Instruct")
+        );
+        assert_eq!(nested[0].params_b, Some(14.8));
+        assert_eq!(nested[0].fit.as_deref(), Some("Good"));
+        assert_eq!(nested[0].score, Some(81.0));
+        assert_eq!(nested[0].use_case.as_deref(), Some("coding"));
+        assert_eq!(nested[0].mem_gb, Some(10.1));
+        assert_eq!(nested[0].context_length, Some(32768));
+        assert!(parse_llmfit_recommendations("{}").is_none());
+    }
+}
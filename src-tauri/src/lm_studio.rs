@@ -0,0 +1,320 @@
+//! LM Studio model control via the `lms` CLI (load/unload) and bootstrapping that CLI.
+//! Progress parsing is separated for unit tests.
+
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+
+use crate::operations::{self, LIST_TIMEOUT};
+use crate::{detection, settings};
+
+pub const LM_STUDIO_BASE_URL: &str = "http://127.0.0.1:1234";
+
+/// The LM Studio server root: the `detection.lm_studio_url` setting, else `LM_STUDIO_BASE_URL`.
+#[must_use]
+pub fn lm_studio_base_url() -> String {
+    settings::runtime_url("lmstudio").unwrap_or_else(|| LM_STUDIO_BASE_URL.to_string())
+}
+
+/// Progress update emitted while `lms load` / `lms unload` runs.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ModelLoadProgress {
+    pub model_id: String,
+    /// "load" or "unload".
+    pub action: String,
+    /// Percentage (0-100) when the CLI reports one.
+    pub percent: Option<f32>,
+    /// Last line of CLI output, for display.
+    pub message: String,
+    pub done: bool,
+}
+
+/// Extracts the last percentage (e.g. "45.12%") from a line of `lms` output.
+#[must_use]
+pub fn parse_progress_percent(line: &str) -> Option<f32> {
+    let end = line.rfind('%')?;
+    let head = &line[..end];
+    let start = head
+        .rfind(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    head[start..]
+        .parse::<f32>()
+        .ok()
+        .map(|p| p.clamp(0.0, 100.0))
+}
+
+pub(crate) fn lms_command() -> String {
+    find_lms_cli().unwrap_or_else(|| "lms".to_string())
+}
+
+/// Loads a model into LM Studio (`lms load <id> --yes`), reporting progress as output arrives.
+pub fn load_lm_studio_model(
+    model_id: &str,
+    on_progress: impl FnMut(ModelLoadProgress),
+) -> Result<(), String> {
+    run_lms(&["load", model_id, "--yes"], model_id, "load", on_progress)
+}
+
+/// Unloads a model from LM Studio (`lms unload <id>`).
+pub fn unload_lm_studio_model(
+    model_id: &str,
+    on_progress: impl FnMut(ModelLoadProgress),
+) -> Result<(), String> {
+    run_lms(&["unload", model_id], model_id, "unload", on_progress)
+}
+
+fn run_lms(
+    args: &[&str],
+    model_id: &str,
+    action: &str,
+    mut on_progress: impl FnMut(ModelLoadProgress),
+) -> Result<(), String> {
+    if model_id.trim().is_empty() {
+        return Err("Model id is required".to_string());
+    }
+    let mut child = Command::new(lms_command())
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run lms: {}", e))?;
+
+    let progress = |percent: Option<f32>, message: String, done: bool| ModelLoadProgress {
+        model_id: model_id.to_string(),
+        action: action.to_string(),
+        percent,
+        message,
+        done,
+    };
+
+    // lms redraws its progress bar with '\r' and may write it to either stream,
+    // so both are read on helper threads and funneled through one channel.
+    let (tx, rx) = mpsc::channel::<(bool, String)>();
+    let mut readers = Vec::new();
+    if let Some(stdout) = child.stdout.take() {
+        let tx = tx.clone();
+        readers.push(thread::spawn(move || forward_lines(stdout, false, &tx)));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        let tx = tx.clone();
+        readers.push(thread::spawn(move || forward_lines(stderr, true, &tx)));
+    }
+    drop(tx);
+
+    let mut stderr_text = String::new();
+    for (is_stderr, line) in rx {
+        if is_stderr {
+            stderr_text.push_str(&line);
+            stderr_text.push('\n');
+        }
+        on_progress(progress(parse_progress_percent(&line), line, false));
+    }
+    for r in readers {
+        let _ = r.join();
+    }
+
+    let status = child.wait().map_err(|e| e.to_string())?;
+    if status.success() {
+        on_progress(progress(Some(100.0), format!("{} complete", action), true));
+        Ok(())
+    } else {
+        let msg = stderr_text.trim();
+        Err(if msg.is_empty() {
+            format!("lms {} failed for {}", action, model_id)
+        } else {
+            msg.to_string()
+        })
+    }
+}
+
+/// Reads a child stream in chunks and sends each non-empty line (split on '\r' or '\n').
+pub(crate) fn forward_lines(mut reader: impl Read, is_stderr: bool, tx: &mpsc::Sender<(bool, String)>) {
+    let mut buf = [0u8; 1024];
+    let mut pending = String::new();
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => n,
+        };
+        pending.push_str(&String::from_utf8_lossy(&buf[..n]));
+        while let Some(pos) = pending.find(['\r', '\n']) {
+            let line: String = pending.drain(..=pos).collect();
+            let line = line.trim();
+            if !line.is_empty() {
+                let _ = tx.send((is_stderr, line.to_string()));
+            }
+        }
+    }
+    let rest = pending.trim();
+    if !rest.is_empty() {
+        let _ = tx.send((is_stderr, rest.to_string()));
+    }
+}
+
+/// Whether the `lms` CLI can list models.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LmsCliState {
+    Ready,
+    /// The CLI exists but fails until `lms bootstrap` has run.
+    NotBootstrapped,
+    /// LM Studio is installed but hasn't unpacked its CLI; it does so on first launch.
+    MissingCli,
+    NotInstalled,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LmsCliStatus {
+    pub state: LmsCliState,
+    pub cli_path: Option<String>,
+    /// Output of the failed `lms ls` when not bootstrapped.
+    pub error: Option<String>,
+}
+
+/// Classifies the CLI from what was found: an LM Studio install, an `lms` binary, and whether
+/// `lms ls` succeeded.
+#[must_use]
+pub fn classify_lms_cli(app_installed: bool, cli_found: bool, ls_ok: bool) -> LmsCliState {
+    match (cli_found, ls_ok) {
+        (true, true) => LmsCliState::Ready,
+        (true, false) => LmsCliState::NotBootstrapped,
+        _ if app_installed => LmsCliState::MissingCli,
+        _ => LmsCliState::NotInstalled,
+    }
+}
+
+/// The `lms` binary: the path set in settings, ~/.lmstudio/bin, PATH, then the pre-0.3 location
+/// ~/.cache/lm-studio/bin.
+fn find_lms_cli() -> Option<String> {
+    settings::binary_path("lms")
+        .or_else(detection::lms_path)
+        .or_else(|| detection::command_exists("lms"))
+        .or_else(|| {
+            let name = if cfg!(target_os = "windows") {
+                "lms.exe"
+            } else {
+                "lms"
+            };
+            let p = dirs::home_dir()?
+                .join(".cache")
+                .join("lm-studio")
+                .join("bin")
+                .join(name);
+            p.exists().then(|| p.to_string_lossy().to_string())
+        })
+}
+
+/// Whether the LM Studio app is installed or has run on this machine.
+fn lm_studio_app_installed() -> bool {
+    let home = dirs::home_dir().unwrap_or_default();
+    let mut candidates = vec![
+        home.join(".lmstudio"),
+        home.join(".cache").join("lm-studio"),
+    ];
+    if cfg!(target_os = "macos") {
+        candidates.push("/Applications/LM Studio.app".into());
+        candidates.push(home.join("Applications").join("LM Studio.app"));
+    } else if cfg!(target_os = "windows") {
+        if let Some(local) = dirs::data_local_dir() {
+            candidates.push(local.join("Programs").join("LM Studio"));
+        }
+    }
+    candidates.iter().any(|p| p.exists())
+}
+
+/// Why LM Studio model listing may come back empty: runs `lms ls` and classifies the result.
+#[must_use]
+pub fn get_lms_cli_status() -> LmsCliStatus {
+    let cli_path = find_lms_cli();
+    let ls = cli_path.as_deref().and_then(|cmd| {
+        operations::run_command(Command::new(cmd).arg("ls"), LIST_TIMEOUT, None).ok()
+    });
+    let ls_ok = ls.as_ref().is_some_and(|o| o.status.success());
+    let error = ls.filter(|_| !ls_ok).map(|o| {
+        let err = String::from_utf8_lossy(&o.stderr).trim().to_string();
+        if err.is_empty() {
+            format!("lms ls exited with {}", o.status)
+        } else {
+            err
+        }
+    });
+    LmsCliStatus {
+        state: classify_lms_cli(lm_studio_app_installed(), cli_path.is_some(), ls_ok),
+        cli_path,
+        error,
+    }
+}
+
+/// Runs LM Studio's documented `lms bootstrap`, which puts the CLI in ~/.lmstudio/bin and on
+/// the shell PATH. Returns the status afterwards; model listing works once it is Ready.
+pub fn bootstrap_lms_cli() -> Result<LmsCliStatus, String> {
+    let status = get_lms_cli_status();
+    let cli = match status.state {
+        LmsCliState::Ready => return Ok(status),
+        LmsCliState::NotInstalled => return Err("LM Studio is not installed".to_string()),
+        LmsCliState::MissingCli => {
+            return Err(
+                "Open LM Studio once so it installs the lms CLI, then try again".to_string(),
+            )
+        }
+        LmsCliState::NotBootstrapped => status.cli_path.unwrap_or_default(),
+    };
+    let out = operations::run_command(Command::new(&cli).arg("bootstrap"), LIST_TIMEOUT, None)?;
+    if !out.status.success() {
+        let err = String::from_utf8_lossy(&out.stderr).trim().to_string();
+        return Err(if err.is_empty() {
+            format!("lms bootstrap exited with {}", out.status)
+        } else {
+            err
+        });
+    }
+    let status = get_lms_cli_status();
+    if status.state != LmsCliState::Ready {
+        return Err(status
+            .error
+            .unwrap_or_else(|| "lms bootstrap ran but lms still can't list models".to_string()));
+    }
+    Ok(status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_progress_percent() {
+        assert_eq!(
+            parse_progress_percent("Loading model ⠙ 45.12%"),
+            Some(45.12)
+        );
+        assert_eq!(parse_progress_percent("100%"), Some(100.0));
+        assert_eq!(parse_progress_percent("[=====>   ] 60% 1.2 GB"), Some(60.0));
+        assert_eq!(parse_progress_percent("Model loaded"), None);
+        assert_eq!(parse_progress_percent("%"), None);
+    }
+
+    #[test]
+    fn test_classify_lms_cli() {
+        assert_eq!(classify_lms_cli(true, true, true), LmsCliState::Ready);
+        assert_eq!(
+            classify_lms_cli(true, true, false),
+            LmsCliState::NotBootstrapped
+        );
+        assert_eq!(
+            classify_lms_cli(true, false, false),
+            LmsCliState::MissingCli
+        );
+        assert_eq!(
+            classify_lms_cli(false, false, false),
+            LmsCliState::NotInstalled
+        );
+    }
+
+    #[test]
+    fn test_load_requires_model_id() {
+        assert!(load_lm_studio_model("  ", |_| {}).is_err());
+    }
+}
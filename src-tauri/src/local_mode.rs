@@ -0,0 +1,359 @@
+//! Local-only mode, for travel or privacy-sensitive work: openclaw.json keeps only local
+//! providers (Ollama, LM Studio, vLLM or anything on localhost) and local models. Cloud provider
+//! entries, their keys in `env`, cloud model selections and per-agent overrides that use them are
+//! moved to ~/.openclaw/host-config/local-only-stash.json and put back when the mode is turned
+//! off. Agents' models.json files are left alone; they only serve the models selected here.
+//! Switching is a pure function for unit tests.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::openclaw_config;
+
+/// What local-only mode took out of openclaw.json.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct LocalOnlyStash {
+    pub providers: Map<String, Value>,
+    /// Key env vars of those providers.
+    pub env: Map<String, Value>,
+    /// agents.defaults.model as it was.
+    pub default_model: Option<Value>,
+    /// Cloud entries of agents.defaults.models.
+    pub models: Map<String, Value>,
+    /// Agent id to its agents.list `model` override.
+    pub agent_models: Map<String, Value>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct LocalOnlyStatus {
+    pub enabled: bool,
+    pub stashed_providers: Vec<String>,
+    /// Primary model while the mode is on.
+    pub primary: Option<String>,
+}
+
+fn stash_path() -> PathBuf {
+    openclaw_config::host_config_dir().join("local-only-stash.json")
+}
+
+fn read_stash() -> Option<LocalOnlyStash> {
+    fs::read_to_string(stash_path())
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+}
+
+/// Model ids a `model` value ("provider/model" or `{ primary, fallbacks }`) refers to.
+pub(crate) fn model_value_ids(model: &Value) -> Vec<&str> {
+    match model {
+        Value::String(s) => vec![s.as_str()],
+        _ => model
+            .get("primary")
+            .into_iter()
+            .chain(
+                model
+                    .get("fallbacks")
+                    .and_then(|f| f.as_array())
+                    .into_iter()
+                    .flatten(),
+            )
+            .filter_map(|v| v.as_str())
+            .collect(),
+    }
+}
+
+fn object_mut<'a>(
+    root: &'a mut Value,
+    path: &[&str],
+) -> Result<&'a mut Map<String, Value>, String> {
+    let mut node = root;
+    for key in path {
+        node = node
+            .as_object_mut()
+            .ok_or_else(|| format!("{} is not an object", key))?
+            .entry(key.to_string())
+            .or_insert_with(|| serde_json::json!({}));
+    }
+    node.as_object_mut()
+        .ok_or_else(|| format!("{} is not an object", path.join(".")))
+}
+
+/// The env vars holding a provider's key: a `${VAR}` apiKey and the conventional
+/// `<PROVIDER>_API_KEY`.
+fn key_vars(name: &str, entry: Option<&Value>) -> Vec<String> {
+    let referenced = entry
+        .and_then(|e| e.get("apiKey"))
+        .and_then(|v| v.as_str())
+        .and_then(|k| k.trim().strip_prefix("${")?.strip_suffix('}'))
+        .map(String::from);
+    let conventional = format!("{}_API_KEY", name.to_uppercase().replace('-', "_"));
+    referenced.into_iter().chain([conventional]).collect()
+}
+
+/// Removes everything cloud from `root` and returns it. The local primary is the first local
+/// model among the primary and fallbacks, else the first configured local model.
+pub fn enter_local_only(root: &mut Value) -> Result<LocalOnlyStash, String> {
+    let providers = root
+        .pointer("/models/providers")
+        .cloned()
+        .unwrap_or(serde_json::json!({}));
+    let default_model = root.pointer("/agents/defaults/model").cloned();
+    let chain: Vec<String> = default_model
+        .as_ref()
+        .map(|m| model_value_ids(m).into_iter().map(String::from).collect())
+        .unwrap_or_default();
+    let configured_local = providers
+        .as_object()
+        .into_iter()
+        .flatten()
+        .flat_map(|(name, p)| {
+            p.get("models")
+                .and_then(|m| m.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|m| m.get("id").and_then(|v| v.as_str()).or(m.as_str()))
+                .map(move |id| format!("{}/{}", name, id))
+        });
+    let mut local: Vec<String> = Vec::new();
+    for id in chain.iter().cloned().chain(configured_local) {
+        if openclaw_config::is_local_model(&id, &providers) && !local.contains(&id) {
+            local.push(id);
+        }
+    }
+    if local.is_empty() {
+        return Err(
+            "No local models configured; add an Ollama or LM Studio model first".to_string(),
+        );
+    }
+    let mut stash = LocalOnlyStash {
+        default_model,
+        ..Default::default()
+    };
+
+    let cloud_names: Vec<String> = providers
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(name, _)| name.clone())
+        .chain(
+            chain
+                .iter()
+                .filter_map(|id| Some(id.split_once('/')?.0.to_string())),
+        )
+        .filter(|name| {
+            let base_url = openclaw_config::provider_base_url(&providers, name);
+            !openclaw_config::is_local_provider(name, base_url)
+        })
+        .collect();
+    let provider_map = object_mut(root, &["models", "providers"])?;
+    for name in &cloud_names {
+        if let Some(entry) = provider_map.remove(name) {
+            stash.providers.insert(name.clone(), entry);
+        }
+    }
+    let env = object_mut(root, &["env"])?;
+    for name in &cloud_names {
+        for var in key_vars(name, stash.providers.get(name)) {
+            if let Some(value) = env.remove(&var) {
+                stash.env.insert(var, value);
+            }
+        }
+    }
+    if env.is_empty() {
+        root.as_object_mut().map(|r| r.remove("env"));
+    }
+
+    let defaults = object_mut(root, &["agents", "defaults"])?;
+    let primary = local[0].clone();
+    let fallbacks: Vec<&String> = local[1..].iter().filter(|id| chain.contains(id)).collect();
+    defaults.insert(
+        "model".to_string(),
+        serde_json::json!({ "primary": primary, "fallbacks": fallbacks }),
+    );
+    if let Some(models) = defaults.get_mut("models").and_then(|m| m.as_object_mut()) {
+        let cloud: Vec<String> = models
+            .keys()
+            .filter(|id| !openclaw_config::is_local_model(id, &providers))
+            .cloned()
+            .collect();
+        for id in cloud {
+            if let Some(v) = models.remove(&id) {
+                stash.models.insert(id, v);
+            }
+        }
+    }
+    let list = root
+        .pointer_mut("/agents/list")
+        .and_then(|l| l.as_array_mut());
+    for entry in list.into_iter().flatten() {
+        let Some(id) = entry.get("id").and_then(|v| v.as_str()).map(String::from) else {
+            continue;
+        };
+        let uses_cloud = entry.get("model").is_some_and(|m| {
+            model_value_ids(m)
+                .iter()
+                .any(|id| !openclaw_config::is_local_model(id, &providers))
+        });
+        if uses_cloud {
+            if let Some(model) = entry.as_object_mut().and_then(|e| e.remove("model")) {
+                stash.agent_models.insert(id, model);
+            }
+        }
+    }
+    Ok(stash)
+}
+
+/// Puts the stash back. Providers, env vars and models added meanwhile win over stashed ones;
+/// the model selection goes back to what it was.
+pub fn leave_local_only(root: &mut Value, stash: LocalOnlyStash) -> Result<(), String> {
+    let providers = object_mut(root, &["models", "providers"])?;
+    for (name, entry) in stash.providers {
+        providers.entry(name).or_insert(entry);
+    }
+    if !stash.env.is_empty() {
+        let env = object_mut(root, &["env"])?;
+        for (var, value) in stash.env {
+            env.entry(var).or_insert(value);
+        }
+    }
+    let defaults = object_mut(root, &["agents", "defaults"])?;
+    match stash.default_model {
+        Some(model) => defaults.insert("model".to_string(), model),
+        None => defaults.remove("model"),
+    };
+    if !stash.models.is_empty() {
+        let models = object_mut(root, &["agents", "defaults", "models"])?;
+        for (id, value) in stash.models {
+            models.entry(id).or_insert(value);
+        }
+    }
+    let list = root
+        .pointer_mut("/agents/list")
+        .and_then(|l| l.as_array_mut());
+    for entry in list.into_iter().flatten() {
+        let id = entry.get("id").and_then(|v| v.as_str()).unwrap_or("");
+        if let Some(model) = stash.agent_models.get(id).cloned() {
+            if let Some(obj) = entry.as_object_mut() {
+                obj.entry("model").or_insert(model);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether local-only mode is on, and what it stashed.
+#[must_use]
+pub fn get_local_only_mode() -> LocalOnlyStatus {
+    let primary = openclaw_config::read_config_root().ok().and_then(|r| {
+        r.pointer("/agents/defaults/model/primary")
+            .and_then(|v| v.as_str())
+            .map(String::from)
+    });
+    match read_stash() {
+        Some(stash) => LocalOnlyStatus {
+            enabled: true,
+            stashed_providers: stash.providers.keys().cloned().collect(),
+            primary,
+        },
+        None => LocalOnlyStatus::default(),
+    }
+}
+
+/// Turns local-only mode on or off; doing it twice is a no-op. The stash is written before
+/// openclaw.json and removed only after it, so a failure never loses the cloud settings.
+pub fn set_local_only_mode(enabled: bool) -> Result<LocalOnlyStatus, String> {
+    let path = stash_path();
+    let mut root = openclaw_config::read_config_root()?;
+    match (enabled, read_stash()) {
+        (true, None) => {
+            let stash = enter_local_only(&mut root)?;
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+            }
+            fs::write(
+                &path,
+                serde_json::to_string_pretty(&stash).map_err(|e| e.to_string())?,
+            )
+            .map_err(|e| e.to_string())?;
+            if let Err(e) = openclaw_config::write_config_root(&root) {
+                let _ = fs::remove_file(&path);
+                return Err(e);
+            }
+        }
+        (false, Some(stash)) => {
+            leave_local_only(&mut root, stash)?;
+            openclaw_config::write_config_root(&root)?;
+            fs::remove_file(&path).map_err(|e| e.to_string())?;
+        }
+        _ => {}
+    }
+    Ok(get_local_only_mode())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Value {
+        serde_json::json!({
+            "env": { "ANTHROPIC_API_KEY": "sk-ant-1", "GROQ_API_KEY": "gsk-1", "HTTP_PROXY": "x" },
+            "models": { "providers": {
+                "ollama": { "baseUrl": "http://127.0.0.1:11434/v1",
+                    "models": [{ "id": "qwen3:8b" }, { "id": "llama3.2" }] },
+                "groq": { "baseUrl": "https://api.groq.com/openai/v1", "apiKey": "${GROQ_API_KEY}",
+                    "models": [{ "id": "llama-3.3-70b" }] }
+            } },
+            "agents": {
+                "defaults": {
+                    "model": { "primary": "anthropic/claude-sonnet-4-5",
+                        "fallbacks": ["groq/llama-3.3-70b", "ollama/llama3.2"] },
+                    "models": { "anthropic/claude-sonnet-4-5": {}, "ollama/llama3.2": {} }
+                },
+                "list": [
+                    { "id": "main" },
+                    { "id": "coder", "model": "groq/llama-3.3-70b" },
+                    { "id": "local", "model": "ollama/qwen3:8b" }
+                ]
+            }
+        })
+    }
+
+    #[test]
+    fn test_enter_local_only() {
+        let mut root = config();
+        let stash = enter_local_only(&mut root).unwrap();
+        assert_eq!(root["models"]["providers"].as_object().unwrap().len(), 1);
+        assert_eq!(root["env"], serde_json::json!({ "HTTP_PROXY": "x" }));
+        assert_eq!(
+            root["agents"]["defaults"]["model"]["primary"],
+            "ollama/llama3.2"
+        );
+        assert_eq!(
+            root["agents"]["defaults"]["model"]["fallbacks"],
+            serde_json::json!([])
+        );
+        assert!(root["agents"]["defaults"]["models"]
+            .get("anthropic/claude-sonnet-4-5")
+            .is_none());
+        assert!(root["agents"]["list"][1].get("model").is_none());
+        assert_eq!(root["agents"]["list"][2]["model"], "ollama/qwen3:8b");
+        assert!(stash.providers.contains_key("groq"));
+        assert_eq!(stash.env.len(), 2);
+        assert_eq!(stash.agent_models["coder"], "groq/llama-3.3-70b");
+
+        let no_local = &mut serde_json::json!({ "models": { "providers": {
+            "groq": { "baseUrl": "https://api.groq.com/openai/v1" }
+        } } });
+        assert!(enter_local_only(no_local).is_err());
+    }
+
+    #[test]
+    fn test_leave_local_only_round_trip() {
+        let mut root = config();
+        let stash = enter_local_only(&mut root).unwrap();
+        leave_local_only(&mut root, stash).unwrap();
+        assert_eq!(root, config());
+    }
+}
@@ -1,15 +1,27 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows
 
-mod agents;
-mod detection;
-mod llmfit;
-mod models_available;
-mod openclaw_config;
-mod system;
+mod gateway_monitor;
+mod notifications;
+mod tray;
 
+use openclaw_host_config::{
+    agent_archive, agent_details, agent_diff, agent_env, agent_instructions, agent_lint,
+    agent_runtime, agent_usage, agents, app_state, benchmark, catalog, concurrency, config_drift,
+    config_git, config_recovery, config_snippet, config_templates, costs, deep_link, detection,
+    diagnostics, disk_usage, doctor, gateway, gateway_reachability, gateway_requests, gateway_tls,
+    gateway_tokens, gguf, hardware, huggingface, live_stats, llmfit, llmfit_install, lm_studio,
+    local_mode, mcp, mcp_import, metrics, model_deprecations, model_limits, model_search,
+    modelfile, models_available, ollama, ollama_update, openclaw_config, openrouter, operations,
+    pagination, pairing, policy, provider_api, provider_export, provider_merge, provider_presets,
+    remote_hosts, routing, runtime_usage, scheduler, settings, setup, spend_limits, system,
+    thermal, usage_stats, watcher, webhooks,
+};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
+use tauri::{Emitter, Manager};
+use tauri_plugin_deep_link::DeepLinkExt;
 
 #[derive(Serialize, Deserialize)]
 struct Config {
@@ -81,20 +93,19 @@ fn save_config(config: Config) -> Result<(), String> {
 
 #[tauri::command]
 fn start_gateway() -> Result<String, String> {
-    use std::process::Command;
-    match Command::new("openclaw").arg("gateway").arg("start").spawn() {
-        Ok(_) => Ok("Gateway start initiated".to_string()),
-        Err(e) => Err(format!("Failed to start gateway: {}", e)),
-    }
+    gateway::start_gateway()
 }
 
 #[tauri::command]
-fn stop_gateway() -> Result<String, String> {
-    use std::process::Command;
-    match Command::new("openclaw").arg("gateway").arg("stop").spawn() {
-        Ok(_) => Ok("Gateway stop initiated".to_string()),
-        Err(e) => Err(format!("Failed to stop gateway: {}", e)),
-    }
+fn stop_gateway(notifier: tauri::State<notifications::Notifier>) -> Result<String, String> {
+    notifier.expect_gateway_stop();
+    gateway::stop_gateway()
+}
+
+#[tauri::command]
+fn restart_gateway(notifier: tauri::State<notifications::Notifier>) -> Result<String, String> {
+    notifier.expect_gateway_stop();
+    gateway::restart_gateway()
 }
 
 #[tauri::command]
@@ -143,38 +154,841 @@ fn save_api_key(service: String, key: String) -> Result<(), String> {
 // --- Local LLM detection (delegate to detection module) ---
 
 #[tauri::command]
-fn detect_local_llms() -> detection::LocalLLMDetection {
-    detection::detect_local_llms()
+fn detect_local_llms(state: tauri::State<app_state::AppState>) -> detection::LocalLLMDetection {
+    state.detection()
 }
 
-#[tauri::command]
+#[tauri::command(async)]
 fn get_system_info() -> system::SystemInfo {
-    system::get_system_info()
+    system::get_system_info_with_gpu()
+}
+
+#[tauri::command]
+fn get_ollama_models() -> Vec<catalog::ModelChoice> {
+    catalog::model_choices(
+        models_available::get_ollama_models(),
+        Some(catalog::RUNTIME_OLLAMA),
+    )
+}
+
+#[tauri::command]
+fn get_ollama_model_details() -> Vec<models_available::OllamaModelInfo> {
+    models_available::get_ollama_model_details()
+}
+
+#[tauri::command]
+fn get_ollama_models_page(
+    query: Option<models_available::OllamaModelsQuery>,
+) -> pagination::Page<models_available::OllamaModelInfo> {
+    models_available::get_ollama_models_page(&query.unwrap_or_default())
+}
+
+#[tauri::command]
+fn get_lm_studio_models() -> Vec<catalog::ModelChoice> {
+    catalog::model_choices(
+        models_available::get_lm_studio_models(),
+        Some(catalog::RUNTIME_LM_STUDIO),
+    )
+}
+
+#[tauri::command]
+fn get_vllm_models(base_url: Option<String>) -> Vec<catalog::ModelChoice> {
+    catalog::model_choices(
+        models_available::get_vllm_models(base_url.as_deref()),
+        Some(catalog::RUNTIME_VLLM),
+    )
+}
+
+#[tauri::command(async)]
+fn get_model_catalog(
+    state: tauri::State<app_state::AppState>,
+    query: Option<catalog::CatalogQuery>,
+) -> Vec<catalog::CatalogModel> {
+    state.catalog_page(&query.unwrap_or_default()).items
+}
+
+#[tauri::command(async)]
+fn get_model_catalog_page(
+    state: tauri::State<app_state::AppState>,
+    query: Option<catalog::CatalogQuery>,
+) -> pagination::Page<catalog::CatalogModel> {
+    state.catalog_page(&query.unwrap_or_default())
+}
+
+#[tauri::command(async)]
+fn search_models(
+    state: tauri::State<app_state::AppState>,
+    query: String,
+    limit: Option<usize>,
+) -> Vec<model_search::ModelSearchResult> {
+    let models = state.catalog(&catalog::CatalogQuery::default());
+    let aliases = state
+        .config_root()
+        .map(|root| model_search::aliases_from_root(&root))
+        .unwrap_or_default();
+    model_search::search_models(&models, &aliases, &query, limit)
+}
+
+#[tauri::command(async)]
+fn list_chat_models(state: tauri::State<app_state::AppState>) -> Vec<catalog::CatalogModel> {
+    state.catalog(&catalog::CatalogQuery {
+        kind: Some(catalog::ModelKind::Chat),
+        ..Default::default()
+    })
+}
+
+#[tauri::command(async)]
+fn list_embedding_models(state: tauri::State<app_state::AppState>) -> Vec<catalog::CatalogModel> {
+    state.catalog(&catalog::CatalogQuery {
+        kind: Some(catalog::ModelKind::Embedding),
+        ..Default::default()
+    })
 }
 
 #[tauri::command]
-fn get_ollama_models() -> Vec<String> {
-    models_available::get_ollama_models()
+fn get_model_limits(model: String) -> Option<model_limits::ModelLimits> {
+    let providers = openclaw_config::get_openclaw_providers_raw().unwrap_or(serde_json::json!({}));
+    model_limits::limits_for_model_id(&model, &providers)
 }
 
 #[tauri::command]
-fn get_lm_studio_models() -> Vec<String> {
-    models_available::get_lm_studio_models()
+fn check_agent_defaults_limits() -> Vec<model_limits::LimitWarning> {
+    model_limits::check_agent_defaults_limits()
+}
+
+#[tauri::command(async)]
+fn suggest_provider_model_ids() -> Vec<catalog::SuggestedModelId> {
+    catalog::suggest_provider_model_ids()
 }
 
 #[tauri::command]
-fn get_llmfit_system() -> Option<llmfit::LlmfitSystemJson> {
-    llmfit::get_llmfit_system()
+fn add_provider_model_ids(ids: Vec<String>) -> Result<Vec<String>, String> {
+    catalog::add_provider_model_ids(&ids)
 }
 
 #[tauri::command]
-fn get_llmfit_recommendations(limit: u8) -> Vec<llmfit::LlmfitRecommendation> {
-    llmfit::get_llmfit_recommendations(limit)
+fn get_app_settings() -> settings::AppSettings {
+    settings::get_app_settings()
 }
 
 #[tauri::command]
-fn get_openclaw_config() -> openclaw_config::OpenClawConfigView {
-    openclaw_config::get_openclaw_config()
+fn pin_model(id: String) -> Result<Vec<String>, String> {
+    settings::pin_model(&id)
+}
+
+#[tauri::command]
+fn unpin_model(id: String) -> Result<Vec<String>, String> {
+    settings::unpin_model(&id)
+}
+
+#[tauri::command(async)]
+fn send_test_webhook(url: String) -> Result<u16, String> {
+    webhooks::send_test_webhook(&url)
+}
+
+/// Runs a scheduled job and reacts to it: agents that drifted since the last check are notified
+/// and sent to webhooks, refreshed models and finished runs are emitted to the window.
+fn run_scheduled_job(
+    app: &tauri::AppHandle,
+    kind: scheduler::JobKind,
+) -> Result<scheduler::JobRun, String> {
+    let previous = scheduler::last_run(kind).map(|r| r.drifted_agents);
+    let ops = app.state::<operations::OperationRegistry>();
+    // Backups show in the progress tray; the frequent checks would only clutter it.
+    let op = match kind {
+        scheduler::JobKind::ConfigBackup => {
+            Some(ops.begin(operations::OperationKind::Backup, kind.label(), None)?)
+        }
+        _ => None,
+    };
+    let run = scheduler::run_job(kind, &app.state::<app_state::AppState>());
+    if let Some(op) = op {
+        let outcome = match &run {
+            Ok(r) if !r.ok => Err(r.message.clone()),
+            other => other.as_ref().map(|_| ()).map_err(String::clone),
+        };
+        op.finish(&outcome);
+    }
+    let run = run?;
+    let new_drift: Vec<&String> = run
+        .drifted_agents
+        .iter()
+        .filter(|a| !previous.as_ref().is_some_and(|p| p.contains(a)))
+        .collect();
+    if !new_drift.is_empty() {
+        let names: Vec<&str> = new_drift.iter().map(|a| a.as_str()).collect();
+        app.state::<notifications::Notifier>().notify(
+            app,
+            notifications::NotificationKind::SyncDrift,
+            "Agents out of sync",
+            &format!(
+                "{} no longer match openclaw.json. Open OpenClaw Config to sync them.",
+                names.join(", ")
+            ),
+        );
+    }
+    if kind == scheduler::JobKind::SyncCheck && run.ok {
+        let tracker = app.state::<webhooks::SyncDriftTracker>();
+        webhooks::emit_sync_drift(&tracker.all_checked(&run.drifted_agents));
+    }
+    if kind == scheduler::JobKind::ModelRefresh {
+        let _ = app.emit("models-available-changed", ());
+    }
+    let _ = app.emit("job-finished", &run);
+    Ok(run)
+}
+
+#[tauri::command]
+fn list_jobs() -> Vec<scheduler::JobStatus> {
+    scheduler::list_jobs()
+}
+
+#[tauri::command(async)]
+fn run_job_now(
+    app: tauri::AppHandle,
+    kind: scheduler::JobKind,
+) -> Result<scheduler::JobRun, String> {
+    run_scheduled_job(&app, kind)
+}
+
+/// Starts, moves or stops the metrics listener; scrapes reuse the monitor's gateway status and
+/// the cached detection.
+fn apply_metrics_settings(
+    app: &tauri::AppHandle,
+    config: &metrics::MetricsSettings,
+) -> Result<(), String> {
+    let handle = app.clone();
+    let collect = move || {
+        let monitor = handle.state::<gateway_monitor::GatewayMonitor>();
+        let detection = handle.state::<app_state::AppState>().detection();
+        metrics::MetricsSnapshot::collect(monitor.last_status(), Some(detection))
+    };
+    app.state::<metrics::MetricsServer>().apply(config, collect)
+}
+
+#[tauri::command]
+fn update_app_settings(
+    app: tauri::AppHandle,
+    updates: settings::AppSettingsUpdates,
+) -> Result<settings::AppSettings, String> {
+    let saved = settings::update_app_settings(updates)?;
+    apply_metrics_settings(&app, &saved.metrics)?;
+    app.state::<usage_stats::UsageCounter>()
+        .set_enabled(saved.telemetry_opt_in);
+    Ok(saved)
+}
+
+#[tauri::command]
+fn get_usage_stats(
+    counter: tauri::State<usage_stats::UsageCounter>,
+) -> Result<usage_stats::UsageStats, String> {
+    usage_stats::get_usage_stats(&counter)
+}
+
+#[tauri::command]
+fn export_usage_stats(
+    counter: tauri::State<usage_stats::UsageCounter>,
+    path: Option<String>,
+) -> Result<String, String> {
+    usage_stats::export_usage_stats(&counter, path.as_deref())
+}
+
+#[tauri::command]
+fn reset_usage_stats(counter: tauri::State<usage_stats::UsageCounter>) -> Result<(), String> {
+    usage_stats::reset_usage_stats(&counter)
+}
+
+/// Counts each invoked command for the opt-in usage stats, then runs it.
+fn counting_handler<R: tauri::Runtime>(
+    handler: impl Fn(tauri::ipc::Invoke<R>) -> bool + Send + Sync + 'static,
+) -> impl Fn(tauri::ipc::Invoke<R>) -> bool + Send + Sync + 'static {
+    move |invoke| {
+        if let Some(counter) = invoke
+            .message
+            .webview_ref()
+            .try_state::<usage_stats::UsageCounter>()
+        {
+            counter.record(invoke.message.command());
+        }
+        handler(invoke)
+    }
+}
+
+#[tauri::command]
+fn get_setup_state() -> setup::SetupState {
+    setup::get_setup_state()
+}
+
+#[tauri::command(async)]
+fn run_setup_step(
+    cache: tauri::State<llmfit::LlmfitCache>,
+    step: setup::SetupStep,
+    input: Option<setup::SetupInput>,
+) -> Result<setup::SetupState, String> {
+    setup::run_setup_step(step, input.unwrap_or_default(), cache.inner())
+}
+
+#[tauri::command]
+fn reset_setup() -> Result<setup::SetupState, String> {
+    setup::reset_setup()
+}
+
+#[tauri::command]
+fn get_notification_rules() -> notifications::NotificationRules {
+    notifications::get_notification_rules()
+}
+
+#[tauri::command]
+fn set_notification_rules(rules: notifications::NotificationRules) -> Result<(), String> {
+    notifications::set_notification_rules(&rules)
+}
+
+#[tauri::command]
+fn send_notification(
+    app: tauri::AppHandle,
+    notifier: tauri::State<notifications::Notifier>,
+    kind: notifications::NotificationKind,
+    title: String,
+    body: String,
+) -> bool {
+    notifier.notify(&app, kind, &title, &body)
+}
+
+#[tauri::command(async)]
+fn run_doctor() -> doctor::DoctorReport {
+    doctor::run_doctor()
+}
+
+#[tauri::command(async)]
+fn test_provider(
+    app: tauri::AppHandle,
+    notifier: tauri::State<notifications::Notifier>,
+    provider_name: String,
+) -> Result<provider_api::ProviderTestResult, String> {
+    let result = provider_api::test_provider(&provider_name)?;
+    if result.key_rejected {
+        notifier.notify(
+            &app,
+            notifications::NotificationKind::ProviderKeyInvalid,
+            "Provider key rejected",
+            &format!("{} rejected its API key.", provider_name),
+        );
+    }
+    Ok(result)
+}
+
+#[tauri::command(async)]
+fn get_cloud_provider_models(provider: String) -> Result<Vec<catalog::ModelChoice>, String> {
+    let models = provider_api::get_cloud_provider_models(&provider)?;
+    Ok(catalog::model_choices(models, None))
+}
+
+#[tauri::command(async)]
+fn export_diagnostics(path: String) -> Result<String, String> {
+    diagnostics::export_diagnostics(&path)
+}
+
+#[tauri::command(async)]
+fn get_disk_space() -> Vec<system::DiskSpace> {
+    system::get_disk_space()
+}
+
+#[tauri::command(async)]
+fn get_model_disk_usage() -> disk_usage::ModelDiskUsage {
+    disk_usage::get_model_disk_usage()
+}
+
+#[tauri::command(async)]
+fn read_gguf_metadata(path: String) -> Result<Vec<gguf::GgufMetadata>, String> {
+    gguf::read_gguf_metadata(&path)
+}
+
+#[tauri::command(async)]
+fn search_huggingface_models(
+    query: String,
+    filters: Option<huggingface::HfSearchFilters>,
+) -> Result<Vec<huggingface::HfModel>, String> {
+    huggingface::search_huggingface_models(&query, &filters.unwrap_or_default())
+}
+
+#[tauri::command(async)]
+fn list_huggingface_gguf_files(repo: String) -> Result<Vec<huggingface::HfGgufFile>, String> {
+    huggingface::list_huggingface_gguf_files(&repo)
+}
+
+#[tauri::command(async)]
+fn get_openrouter_models() -> Result<Vec<openrouter::OpenRouterModel>, String> {
+    openrouter::get_openrouter_models()
+}
+
+#[tauri::command(async)]
+fn add_openrouter_models(ids: Vec<String>, api_key: Option<String>) -> Result<Vec<String>, String> {
+    openrouter::add_openrouter_models(&ids, api_key.as_deref())
+}
+
+#[tauri::command]
+fn list_config_templates() -> Vec<config_templates::ConfigTemplate> {
+    config_templates::CONFIG_TEMPLATES.to_vec()
+}
+
+#[tauri::command]
+fn apply_config_template(
+    state: tauri::State<app_state::AppState>,
+    id: String,
+    params: Option<HashMap<String, String>>,
+) -> Result<config_templates::AppliedTemplate, String> {
+    let applied = config_templates::apply_config_template(&id, &params.unwrap_or_default())?;
+    state.invalidate_all();
+    Ok(applied)
+}
+
+#[tauri::command]
+fn list_provider_presets() -> Vec<provider_presets::ProviderPreset> {
+    provider_presets::PROVIDER_PRESETS.to_vec()
+}
+
+#[tauri::command]
+fn add_provider_from_preset(preset: String, key: Option<String>) -> Result<Vec<String>, String> {
+    provider_presets::add_provider_from_preset(&preset, key.as_deref())
+}
+
+#[tauri::command]
+fn export_provider_config(
+    format: provider_export::ExportFormat,
+    path: Option<String>,
+) -> Result<String, String> {
+    provider_export::export_provider_config(format, path.as_deref())
+}
+
+#[tauri::command]
+fn export_snippet(section: config_snippet::SnippetSection) -> Result<String, String> {
+    config_snippet::export_snippet(&section)
+}
+
+#[tauri::command(async)]
+fn import_from_claude_desktop() -> Result<mcp_import::ImportReport, String> {
+    mcp_import::import_from_claude_desktop()
+}
+
+#[tauri::command]
+fn list_mcp_servers() -> Vec<mcp::McpServerEntry> {
+    mcp::list_mcp_servers()
+}
+
+#[tauri::command]
+fn add_mcp_server(
+    name: Option<String>,
+    command: String,
+    args: Option<Vec<String>>,
+    env: Option<std::collections::BTreeMap<String, String>>,
+) -> Result<mcp::McpServerEntry, String> {
+    mcp::add_mcp_server(
+        name.as_deref(),
+        &command,
+        args.unwrap_or_default(),
+        env.unwrap_or_default(),
+    )
+}
+
+#[tauri::command]
+fn remove_mcp_server(name: String) -> Result<(), String> {
+    mcp::remove_mcp_server(&name)
+}
+
+#[tauri::command(async)]
+fn test_mcp_server(name: String) -> Result<mcp::McpTestResult, String> {
+    mcp::test_mcp_server(&name)
+}
+
+fn warn_if_low_space(app: &tauri::AppHandle, needed_bytes: u64) {
+    if let Some(w) =
+        disk_usage::ollama_models_dir().and_then(|dir| system::check_low_space(&dir, needed_bytes))
+    {
+        app.state::<notifications::Notifier>().notify(
+            app,
+            notifications::NotificationKind::DiskLow,
+            "Low disk space",
+            &w.message,
+        );
+        let _ = app.emit("disk-space-low", w);
+    }
+}
+
+fn notify_pull_finished(app: &tauri::AppHandle, model: &str, result: &Result<impl Sized, String>) {
+    let body = match result {
+        Ok(_) => format!("{} is ready to use.", model),
+        Err(e) => format!("{} failed: {}", model, e),
+    };
+    app.state::<notifications::Notifier>().notify(
+        app,
+        notifications::NotificationKind::ModelPullFinished,
+        "Model download finished",
+        &body,
+    );
+    webhooks::emit(
+        webhooks::WebhookEvent::ModelPullFinished,
+        serde_json::json!({ "model": model, "ok": result.is_ok(), "error": result.as_ref().err() }),
+    );
+}
+
+#[tauri::command(async)]
+fn download_to_ollama(
+    app: tauri::AppHandle,
+    ops: tauri::State<operations::OperationRegistry>,
+    repo: String,
+    file: String,
+    model_name: Option<String>,
+    op_id: Option<String>,
+) -> Result<String, String> {
+    let model = model_name.as_deref();
+    let label = format!("Download {}", model.unwrap_or(&file));
+    let kind = operations::OperationKind::ModelPull;
+    let op = ops.begin(kind, &label, op_id.as_deref())?;
+    warn_if_low_space(&app, huggingface::gguf_file_size(&repo, &file).unwrap_or(0));
+    let result = huggingface::download_to_ollama(&repo, &file, model, op.token(), |p| {
+        op.progress(p.percent, &p.status);
+        let _ = app.emit("model-transfer-progress", p);
+    });
+    if !op.token().is_cancelled() {
+        notify_pull_finished(&app, model.unwrap_or(&file), &result);
+    }
+    op.finish(&result);
+    result
+}
+
+#[tauri::command]
+fn validate_modelfile(modelfile_text: String) -> Result<modelfile::Modelfile, String> {
+    modelfile::parse_modelfile(&modelfile_text)
+}
+
+#[tauri::command(async)]
+fn create_ollama_model(
+    app: tauri::AppHandle,
+    ops: tauri::State<operations::OperationRegistry>,
+    name: String,
+    modelfile_text: String,
+    op_id: Option<String>,
+) -> Result<(), String> {
+    let label = format!("Create {}", name);
+    let kind = operations::OperationKind::ModelCreate;
+    let op = ops.begin(kind, &label, op_id.as_deref())?;
+    let result = ollama::create_model_from_modelfile(&name, &modelfile_text, op.token(), |p| {
+        op.progress(p.percent, &p.status);
+        let _ = app.emit("model-transfer-progress", p);
+    });
+    op.finish(&result);
+    result
+}
+
+#[tauri::command(async)]
+fn copy_ollama_model(source: String, destination: String) -> Result<(), String> {
+    ollama::copy_model(&source, &destination)
+}
+
+#[tauri::command(async)]
+fn benchmark_model(
+    ops: tauri::State<operations::OperationRegistry>,
+    runtime: String,
+    model: String,
+    op_id: Option<String>,
+) -> Result<benchmark::BenchmarkResult, String> {
+    let label = format!("Benchmark {}", model);
+    let kind = operations::OperationKind::Benchmark;
+    let op = ops.begin(kind, &label, op_id.as_deref())?;
+    op.progress(None, "Running the benchmark prompt");
+    let result = benchmark::benchmark_model(&runtime, &model, op.token());
+    op.finish(&result);
+    result
+}
+
+#[tauri::command]
+fn cancel_operation(
+    ops: tauri::State<operations::OperationRegistry>,
+    op_id: String,
+) -> Result<(), String> {
+    if ops.cancel(&op_id) {
+        Ok(())
+    } else {
+        Err(format!("No running operation {}", op_id))
+    }
+}
+
+#[tauri::command]
+fn list_operations(
+    ops: tauri::State<operations::OperationRegistry>,
+) -> Vec<operations::OperationInfo> {
+    ops.list()
+}
+
+#[tauri::command]
+fn get_benchmark_history(
+    runtime: Option<String>,
+    model: Option<String>,
+) -> Vec<benchmark::BenchmarkResult> {
+    benchmark::get_benchmark_history(runtime.as_deref(), model.as_deref())
+}
+
+#[tauri::command(async)]
+fn load_lm_studio_model(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    lm_studio::load_lm_studio_model(&id, |p| {
+        let _ = app.emit("lm-studio-model-progress", p);
+    })
+}
+
+#[tauri::command(async)]
+fn unload_lm_studio_model(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    lm_studio::unload_lm_studio_model(&id, |p| {
+        let _ = app.emit("lm-studio-model-progress", p);
+    })
+}
+
+#[tauri::command(async)]
+fn get_lms_cli_status() -> lm_studio::LmsCliStatus {
+    lm_studio::get_lms_cli_status()
+}
+
+#[tauri::command(async)]
+fn bootstrap_lms_cli(
+    state: tauri::State<app_state::AppState>,
+) -> Result<lm_studio::LmsCliStatus, String> {
+    let status = lm_studio::bootstrap_lms_cli()?;
+    state.invalidate_models();
+    Ok(status)
+}
+
+#[tauri::command]
+fn start_live_stats(
+    app: tauri::AppHandle,
+    stream: tauri::State<live_stats::LiveStatsStream>,
+    interval_ms: Option<u64>,
+) {
+    let interval_ms = interval_ms.or(Some(settings::get_app_settings().live_stats_interval_ms));
+    stream.start(interval_ms, move |stats| {
+        let _ = app.emit("live-stats", stats);
+    });
+}
+
+#[tauri::command]
+fn stop_live_stats(stream: tauri::State<live_stats::LiveStatsStream>) {
+    stream.stop();
+}
+
+#[tauri::command(async)]
+fn get_runtime_resource_usage() -> Vec<runtime_usage::RuntimeResourceUsage> {
+    runtime_usage::get_runtime_resource_usage()
+}
+
+#[tauri::command(async)]
+fn get_thermal_state() -> thermal::ThermalState {
+    thermal::get_thermal_state()
+}
+
+#[tauri::command(async)]
+fn get_hardware_profile(cache: tauri::State<llmfit::LlmfitCache>) -> hardware::HardwareProfile {
+    hardware::get_hardware_profile(&cache)
+}
+
+#[tauri::command(async)]
+fn check_model_fit(
+    cache: tauri::State<llmfit::LlmfitCache>,
+    model: String,
+) -> Result<hardware::ModelFit, String> {
+    hardware::check_model_fit(&model, &cache)
+}
+
+#[tauri::command(async)]
+fn validate_concurrency_settings(
+    cache: tauri::State<llmfit::LlmfitCache>,
+) -> Result<concurrency::ConcurrencyCheck, String> {
+    concurrency::validate_concurrency_settings(&cache)
+}
+
+#[tauri::command(async)]
+fn estimate_model_memory(
+    cache: tauri::State<llmfit::LlmfitCache>,
+    params_b: f64,
+    quantization: Option<String>,
+    context_len: Option<u64>,
+) -> Result<hardware::MemoryEstimate, String> {
+    hardware::estimate_model_memory(params_b, quantization.as_deref(), context_len, &cache)
+}
+
+#[tauri::command(async)]
+fn get_llmfit_system(
+    cache: tauri::State<llmfit::LlmfitCache>,
+) -> Option<llmfit::LlmfitSystemJson> {
+    cache.llmfit_system()
+}
+
+#[tauri::command(async)]
+fn get_llmfit_recommendations(
+    cache: tauri::State<llmfit::LlmfitCache>,
+    limit: u8,
+    filters: Option<llmfit::RecommendationFilters>,
+) -> Vec<llmfit::LlmfitRecommendation> {
+    cache.recommendations(limit, &filters.unwrap_or_default())
+}
+
+#[tauri::command(async)]
+fn refresh_llmfit(cache: tauri::State<llmfit::LlmfitCache>) -> llmfit::LlmfitCacheInfo {
+    cache.refresh()
+}
+
+#[tauri::command(async)]
+fn get_llmfit_cache_info(cache: tauri::State<llmfit::LlmfitCache>) -> llmfit::LlmfitCacheInfo {
+    cache.info()
+}
+
+#[tauri::command(async)]
+fn apply_recommendation(
+    app: tauri::AppHandle,
+    ops: tauri::State<operations::OperationRegistry>,
+    recommendation: llmfit::LlmfitRecommendation,
+    agent: Option<String>,
+    force: Option<bool>,
+    role: Option<String>,
+    op_id: Option<String>,
+) -> Result<llmfit::AppliedRecommendation, String> {
+    let name = recommendation
+        .name
+        .as_deref()
+        .unwrap_or("The recommended model");
+    let label = format!("Add {}", name);
+    let kind = operations::OperationKind::ModelPull;
+    let op = ops.begin(kind, &label, op_id.as_deref())?;
+    if !recommendation.installed {
+        let needed = recommendation
+            .mem_gb
+            .map_or(0, |gb| (gb * 1024.0 * 1024.0 * 1024.0) as u64);
+        warn_if_low_space(&app, needed);
+    }
+    let (agent, role, force) = (agent.as_deref(), role.as_deref(), force.unwrap_or(false));
+    let result =
+        llmfit::apply_recommendation(&recommendation, agent, force, role, op.token(), |p| {
+            op.progress(p.percent, &p.status);
+            let _ = app.emit("model-transfer-progress", p);
+        });
+    if !recommendation.installed && !op.token().is_cancelled() {
+        notify_pull_finished(&app, name, &result);
+    }
+    op.finish(&result);
+    result
+}
+
+#[tauri::command(async)]
+fn get_llmfit_status() -> llmfit_install::LlmfitStatus {
+    llmfit_install::get_llmfit_status()
+}
+
+#[tauri::command(async)]
+fn install_llmfit(
+    app: tauri::AppHandle,
+    cache: tauri::State<llmfit::LlmfitCache>,
+    method: Option<String>,
+) -> Result<llmfit_install::LlmfitStatus, String> {
+    let status = llmfit_install::install_llmfit(method.as_deref(), |p| {
+        let _ = app.emit("llmfit-install-progress", p);
+    })?;
+    cache.invalidate();
+    Ok(status)
+}
+
+#[tauri::command(async)]
+fn check_ollama_update() -> ollama_update::OllamaUpdate {
+    ollama_update::check_ollama_update()
+}
+
+#[tauri::command(async)]
+fn upgrade_ollama(
+    app: tauri::AppHandle,
+    state: tauri::State<app_state::AppState>,
+) -> Result<ollama_update::OllamaUpdate, String> {
+    let update = ollama_update::upgrade_ollama(|p| {
+        let _ = app.emit("ollama-update-progress", p);
+    })?;
+    state.invalidate_models();
+    Ok(update)
+}
+
+/// Emits `config-corrupt` when openclaw.json doesn't parse, instead of silently showing defaults.
+fn emit_config_corrupt(app: &tauri::AppHandle) {
+    if let Ok(Some(corruption)) = config_recovery::check_config_integrity() {
+        let _ = app.emit("config-corrupt", corruption);
+    }
+}
+
+/// Emits `model-ids-deprecated` when openclaw.json selects retired, deprecated or renamed models,
+/// on load and then only when the warnings change.
+fn emit_model_id_warnings(app: &tauri::AppHandle, root: &serde_json::Value) {
+    let warnings = model_deprecations::check_config_model_ids(root);
+    let tracker = app.state::<model_deprecations::ModelIdWarningTracker>();
+    if tracker.changed(&warnings) && !warnings.is_empty() {
+        let _ = app.emit("model-ids-deprecated", warnings);
+    }
+}
+
+#[tauri::command]
+fn get_openclaw_config(
+    app: tauri::AppHandle,
+    state: tauri::State<app_state::AppState>,
+) -> openclaw_config::OpenClawConfigView {
+    match state.config_root() {
+        Ok(root) => emit_model_id_warnings(&app, &root),
+        Err(_) => emit_config_corrupt(&app),
+    }
+    state.config_view()
+}
+
+#[tauri::command]
+fn check_model_ids() -> Result<Vec<model_deprecations::ModelIdWarning>, String> {
+    model_deprecations::check_model_ids()
+}
+
+#[tauri::command]
+fn check_config_integrity() -> Result<Option<config_recovery::ConfigCorruption>, String> {
+    config_recovery::check_config_integrity()
+}
+
+#[tauri::command]
+fn restore_from_backup(
+    state: tauri::State<app_state::AppState>,
+    backup_id: Option<String>,
+) -> Result<config_recovery::ConfigBackup, String> {
+    let backup = config_recovery::restore_from_backup(backup_id.as_deref())?;
+    state.invalidate_all();
+    Ok(backup)
+}
+
+#[tauri::command]
+fn start_fresh(state: tauri::State<app_state::AppState>) -> Result<String, String> {
+    let preserved = config_recovery::start_fresh()?;
+    state.invalidate_all();
+    Ok(preserved.display().to_string())
+}
+
+#[tauri::command(async)]
+fn init_config_repo() -> Result<config_git::ConfigHistory, String> {
+    config_git::init_config_repo()
+}
+
+#[tauri::command(async)]
+fn get_config_history(limit: Option<usize>) -> Result<config_git::ConfigHistory, String> {
+    config_git::get_config_history(limit)
+}
+
+#[tauri::command(async)]
+fn revert_to_commit(
+    state: tauri::State<app_state::AppState>,
+    sha: String,
+) -> Result<config_git::ConfigHistory, String> {
+    let history = config_git::revert_to_commit(&sha)?;
+    state.invalidate_all();
+    Ok(history)
+}
+
+#[tauri::command]
+fn get_config_policy() -> Result<policy::PolicyStatus, String> {
+    policy::get_config_policy()
 }
 
 #[tauri::command]
@@ -182,6 +996,46 @@ fn update_openclaw_config(updates: openclaw_config::OpenClawConfigUpdates) -> Re
     openclaw_config::update_openclaw_config(updates)
 }
 
+#[tauri::command]
+fn list_remote_hosts() -> Vec<remote_hosts::RemoteHost> {
+    remote_hosts::list_remote_hosts()
+}
+
+#[tauri::command]
+fn add_remote_host(
+    host: remote_hosts::RemoteHost,
+) -> Result<Vec<remote_hosts::RemoteHost>, String> {
+    remote_hosts::add_remote_host(host)
+}
+
+#[tauri::command]
+fn remove_remote_host(name: String) -> Result<Vec<remote_hosts::RemoteHost>, String> {
+    remote_hosts::remove_remote_host(&name)
+}
+
+#[tauri::command(async)]
+fn detect_remote_llms(name: String) -> Result<detection::LocalLLMDetection, String> {
+    remote_hosts::detect_remote_llms(&name)
+}
+
+#[tauri::command(async)]
+fn remote_gateway_running(name: String) -> Result<bool, String> {
+    remote_hosts::remote_gateway_running(&name)
+}
+
+#[tauri::command(async)]
+fn get_remote_openclaw_config(name: String) -> Result<openclaw_config::OpenClawConfigView, String> {
+    remote_hosts::get_remote_openclaw_config(&name)
+}
+
+#[tauri::command(async)]
+fn update_remote_openclaw_config(
+    name: String,
+    updates: openclaw_config::OpenClawConfigUpdates,
+) -> Result<openclaw_config::OpenClawConfigView, String> {
+    remote_hosts::update_remote_openclaw_config(&name, updates)
+}
+
 #[tauri::command]
 fn list_agents() -> Vec<String> {
     agents::list_agent_names()
@@ -192,66 +1046,729 @@ fn get_agent_models(agent_name: String) -> Option<agents::AgentModelsView> {
     agents::get_agent_models(&agent_name)
 }
 
+#[tauri::command(async)]
+fn get_agent_details(name: String) -> Result<agent_details::AgentDetails, String> {
+    agent_details::get_agent_details(&name)
+}
+
 #[tauri::command]
 fn get_agent_provider_sync_status(agent_name: String) -> agents::ProviderSyncStatus {
     agents::get_provider_sync_status(&agent_name)
 }
 
 #[tauri::command]
-fn update_agent_providers_from_openclaw(agent_name: String) -> Result<(), String> {
-    agents::update_agent_providers_from_openclaw(&agent_name)
+fn update_agent_providers_from_openclaw(
+    agent_name: String,
+    force: Option<bool>,
+) -> Result<(), String> {
+    agents::update_agent_providers_from_openclaw(&agent_name, force.unwrap_or(false))
+}
+
+#[tauri::command(async)]
+fn sync_all_agents(
+    app: tauri::AppHandle,
+    ops: tauri::State<operations::OperationRegistry>,
+    force: Option<bool>,
+    op_id: Option<String>,
+) -> Result<agents::BulkSyncResult, String> {
+    let kind = operations::OperationKind::AgentSync;
+    let op = ops.begin(kind, "Sync agent providers", op_id.as_deref())?;
+    let force = force.unwrap_or(false);
+    let result = agents::sync_all_agents(force, op.token(), |done, total, agent| {
+        let percent = done as f32 * 100.0 / total as f32;
+        op.progress(Some(percent), &format!("Syncing {}", agent));
+    });
+    op.finish(&result);
+    let _ = app.emit("agents-changed", agents::list_agent_names());
+    result
+}
+
+#[tauri::command]
+fn merge_agent_providers(
+    agent_name: String,
+    resolutions: Option<Vec<provider_merge::ConflictResolution>>,
+) -> Result<provider_merge::ProviderMergeOutcome, String> {
+    provider_merge::merge_agent_providers(&agent_name, &resolutions.unwrap_or_default())
 }
 
 #[tauri::command]
-fn check_gateway_status() -> Result<bool, String> {
-    use std::process::Command;
-    match Command::new("openclaw")
-        .arg("gateway")
-        .arg("discover")
-        .arg("--json")
-        .arg("--timeout")
-        .arg("500")
-        .output()
-    {
-        Ok(output) => {
-            if output.status.success() {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                if let Ok(json) = serde_json::from_str::<serde_json::Value>(&stdout) {
-                    if let Some(count) = json.get("count").and_then(|c| c.as_u64()) {
-                        return Ok(count > 0);
-                    }
-                }
-            }
-            Ok(false)
+fn update_openclaw_providers_from_agent(agent_name: String) -> Result<(), String> {
+    agents::update_openclaw_providers_from_agent(&agent_name)
+}
+
+#[tauri::command]
+fn update_agent_provider(
+    agent_name: String,
+    provider: String,
+    changes: agents::AgentProviderChanges,
+) -> Result<agents::AgentModelsView, String> {
+    agents::update_agent_provider(&agent_name, &provider, &changes)
+}
+
+#[tauri::command]
+fn remove_agent_provider(
+    agent_name: String,
+    provider: String,
+) -> Result<agents::AgentModelsView, String> {
+    agents::remove_agent_provider(&agent_name, &provider)
+}
+
+#[tauri::command]
+fn get_agent_api_key_statuses(
+    agent_name: String,
+) -> Result<Vec<agents::ProviderApiKeyStatus>, String> {
+    agents::get_agent_api_key_statuses(&agent_name)
+}
+
+#[tauri::command]
+fn set_agent_api_key_override(
+    agent_name: String,
+    provider: String,
+    api_key: String,
+) -> Result<Vec<agents::ProviderApiKeyStatus>, String> {
+    agents::set_agent_api_key_override(&agent_name, &provider, Some(&api_key))
+}
+
+#[tauri::command]
+fn clear_agent_api_key_override(
+    agent_name: String,
+    provider: String,
+) -> Result<Vec<agents::ProviderApiKeyStatus>, String> {
+    agents::set_agent_api_key_override(&agent_name, &provider, None)
+}
+
+#[tauri::command]
+fn get_agent_env(name: String) -> Result<agent_env::AgentEnv, String> {
+    agent_env::get_agent_env(&name)
+}
+
+#[tauri::command]
+fn set_agent_env(
+    name: String,
+    key: String,
+    value: Option<String>,
+) -> Result<agent_env::AgentEnv, String> {
+    agent_env::set_agent_env(&name, &key, value.as_deref())
+}
+
+#[tauri::command]
+fn get_agent_model_config(agent_name: String) -> Result<agents::AgentModelConfig, String> {
+    agents::get_agent_model_config(&agent_name)
+}
+
+#[tauri::command(async)]
+fn simulate_routing(
+    model_request: routing::RoutingRequest,
+) -> Result<routing::RoutingSimulation, String> {
+    routing::simulate_routing(&model_request)
+}
+
+#[tauri::command]
+fn update_agent_model_config(
+    agent_name: String,
+    updates: agents::AgentModelConfigUpdates,
+) -> Result<agents::AgentModelConfig, String> {
+    agents::update_agent_model_config(&agent_name, &updates)
+}
+
+#[tauri::command]
+fn get_agent_subagent_limits(agent_name: String) -> Result<agents::AgentSubagentLimits, String> {
+    agents::get_agent_subagent_limits(&agent_name)
+}
+
+#[tauri::command]
+fn update_agent_subagent_limits(
+    agent_name: String,
+    updates: agents::AgentSubagentLimitUpdates,
+) -> Result<agents::AgentSubagentLimits, String> {
+    agents::update_agent_subagent_limits(&agent_name, &updates)
+}
+
+#[tauri::command]
+fn validate_agent(agent_name: String) -> Result<Vec<agent_lint::AgentIssue>, String> {
+    agent_lint::validate_agent(&agent_name)
+}
+
+#[tauri::command]
+fn create_agent(
+    app: tauri::AppHandle,
+    name: String,
+    options: Option<agents::CreateAgentOptions>,
+) -> Result<agents::AgentModelsView, String> {
+    let view = agents::create_agent(&name, &options.unwrap_or_default())?;
+    let _ = app.emit("agents-changed", agents::list_agent_names());
+    Ok(view)
+}
+
+#[tauri::command]
+fn clone_agent(
+    app: tauri::AppHandle,
+    source: String,
+    new_name: String,
+    options: Option<agents::CloneAgentOptions>,
+) -> Result<agents::AgentModelsView, String> {
+    let view = agents::clone_agent(&source, &new_name, &options.unwrap_or_default())?;
+    let _ = app.emit("agents-changed", agents::list_agent_names());
+    Ok(view)
+}
+
+#[tauri::command(async)]
+fn export_agent(
+    name: String,
+    path: String,
+    options: Option<agents::CloneAgentOptions>,
+) -> Result<String, String> {
+    agent_archive::export_agent(&name, &path, &options.unwrap_or_default())
+}
+
+#[tauri::command(async)]
+fn import_agent(
+    app: tauri::AppHandle,
+    path: String,
+    new_name: Option<String>,
+) -> Result<agent_archive::ImportedAgent, String> {
+    let imported = agent_archive::import_agent(&path, new_name.as_deref())?;
+    let _ = app.emit("agents-changed", agents::list_agent_names());
+    Ok(imported)
+}
+
+#[tauri::command]
+fn get_agent_instructions(
+    name: String,
+    file: Option<String>,
+) -> Result<agent_instructions::AgentInstructions, String> {
+    agent_instructions::get_agent_instructions(&name, file.as_deref())
+}
+
+#[tauri::command]
+fn set_agent_instructions(
+    name: String,
+    text: String,
+    file: Option<String>,
+) -> Result<agent_instructions::AgentInstructions, String> {
+    agent_instructions::set_agent_instructions(&name, file.as_deref(), &text)
+}
+
+#[tauri::command(async)]
+fn get_agent_runtime_status(name: String) -> Result<agent_runtime::AgentRuntimeStatus, String> {
+    agent_runtime::get_agent_runtime_status(&name)
+}
+
+#[tauri::command(async)]
+fn get_agent_usage(
+    name: String,
+    period: Option<agent_usage::UsagePeriod>,
+) -> Result<agent_usage::AgentUsage, String> {
+    agent_usage::get_agent_usage(&name, period.unwrap_or(agent_usage::UsagePeriod::Week))
+}
+
+#[tauri::command(async)]
+fn estimate_costs(period: Option<agent_usage::UsagePeriod>) -> Result<costs::CostEstimate, String> {
+    costs::estimate_costs(period.unwrap_or(agent_usage::UsagePeriod::Month))
+}
+
+#[tauri::command]
+fn diff_agents(agent_a: String, agent_b: String) -> Result<agent_diff::AgentDiff, String> {
+    agent_diff::diff_agents(&agent_a, &agent_b)
+}
+
+#[tauri::command]
+fn list_agents_detailed() -> Vec<agents::AgentListEntry> {
+    agents::list_agents_detailed()
+}
+
+#[tauri::command]
+fn repair_agent(app: tauri::AppHandle, name: String) -> Result<agents::AgentModelsView, String> {
+    let view = agents::repair_agent(&name)?;
+    let _ = app.emit("agents-changed", agents::list_agent_names());
+    Ok(view)
+}
+
+#[tauri::command]
+fn delete_agent(
+    app: tauri::AppHandle,
+    name: String,
+    force: Option<bool>,
+) -> Result<String, String> {
+    let backup = agents::delete_agent(&name, force.unwrap_or(false))?;
+    let _ = app.emit("agents-changed", agents::list_agent_names());
+    Ok(backup)
+}
+
+#[tauri::command]
+fn rename_agent(
+    app: tauri::AppHandle,
+    name: String,
+    new_name: String,
+    force: Option<bool>,
+) -> Result<agents::AgentModelsView, String> {
+    let view = agents::rename_agent(&name, &new_name, force.unwrap_or(false))?;
+    let _ = app.emit("agents-changed", agents::list_agent_names());
+    Ok(view)
+}
+
+#[tauri::command]
+fn get_default_agent() -> Result<String, String> {
+    agents::get_default_agent()
+}
+
+#[tauri::command]
+fn check_gateway_status(app: tauri::AppHandle) -> Result<bool, String> {
+    gateway_monitor::check_now(&app)
+}
+
+#[tauri::command]
+fn get_local_only_mode() -> local_mode::LocalOnlyStatus {
+    local_mode::get_local_only_mode()
+}
+
+#[tauri::command]
+fn set_local_only_mode(
+    app: tauri::AppHandle,
+    enabled: bool,
+) -> Result<local_mode::LocalOnlyStatus, String> {
+    let status = local_mode::set_local_only_mode(enabled)?;
+    app.state::<app_state::AppState>().invalidate_all();
+    Ok(status)
+}
+
+#[tauri::command(async)]
+fn test_external_reachability() -> Result<gateway_reachability::ExternalReachability, String> {
+    gateway_reachability::test_external_reachability()
+}
+
+#[tauri::command(async)]
+fn generate_pairing_info() -> Result<pairing::PairingInfo, String> {
+    pairing::generate_pairing_info()
+}
+
+#[tauri::command]
+fn list_gateway_tokens() -> Result<Vec<gateway_tokens::GatewayToken>, String> {
+    gateway_tokens::list_gateway_tokens()
+}
+
+#[tauri::command]
+fn create_gateway_token(label: String) -> Result<gateway_tokens::CreatedGatewayToken, String> {
+    gateway_tokens::create_gateway_token(&label)
+}
+
+#[tauri::command]
+fn revoke_gateway_token(id: String) -> Result<Vec<gateway_tokens::GatewayToken>, String> {
+    gateway_tokens::revoke_gateway_token(&id)
+}
+
+#[tauri::command(async)]
+fn get_gateway_tls_status() -> Result<gateway_tls::GatewayTlsStatus, String> {
+    gateway_tls::get_gateway_tls_status()
+}
+
+#[tauri::command(async)]
+fn generate_self_signed_cert(days: Option<u32>) -> Result<gateway_tls::GatewayTlsStatus, String> {
+    gateway_tls::generate_self_signed_cert(days)
+}
+
+#[tauri::command(async)]
+fn set_gateway_tls(enabled: bool) -> Result<gateway_tls::GatewayTlsStatus, String> {
+    gateway_tls::set_gateway_tls(enabled)
+}
+
+#[tauri::command]
+fn get_spend_limits() -> Result<spend_limits::SpendLimitsView, String> {
+    spend_limits::get_spend_limits()
+}
+
+#[tauri::command]
+fn update_spend_limits(
+    provider: Option<String>,
+    limits: spend_limits::SpendLimits,
+) -> Result<spend_limits::SpendLimitsView, String> {
+    spend_limits::update_spend_limits(provider.as_deref(), &limits)
+}
+
+#[tauri::command(async)]
+fn enforce_spend_limits() -> Result<spend_limits::SpendLimitReport, String> {
+    spend_limits::enforce_spend_limits()
+}
+
+#[tauri::command(async)]
+fn get_config_drift() -> Result<config_drift::ConfigDrift, String> {
+    config_drift::get_config_drift()
+}
+
+#[tauri::command(async)]
+fn reload_gateway_config() -> Result<config_drift::ConfigReload, String> {
+    config_drift::reload_gateway_config()
+}
+
+#[tauri::command(async)]
+fn tail_gateway_requests(
+    filter: Option<gateway_requests::GatewayRequestFilter>,
+) -> Result<gateway_requests::GatewayRequestTail, String> {
+    gateway_requests::tail_gateway_requests(&filter.unwrap_or_default())
+}
+
+#[derive(Serialize)]
+struct DashboardState {
+    status: Config,
+    gateway_running: bool,
+    detection: detection::LocalLLMDetection,
+    system_info: system::SystemInfo,
+    ollama_models: Vec<catalog::ModelChoice>,
+    lm_studio_models: Vec<catalog::ModelChoice>,
+    openclaw_config: openclaw_config::OpenClawConfigView,
+    agents: Vec<String>,
+    llmfit_system: llmfit::LlmfitSystemJson,
+    llmfit_recommendations: Vec<llmfit::LlmfitRecommendation>,
+}
+
+#[tauri::command(async)]
+fn get_dashboard_state(
+    state: tauri::State<app_state::AppState>,
+    cache: tauri::State<llmfit::LlmfitCache>,
+    recommendation_limit: Option<u8>,
+    refresh: Option<bool>,
+) -> DashboardState {
+    let (state, cache) = (state.inner(), cache.inner());
+    if refresh.unwrap_or(false) {
+        state.invalidate_all();
+    }
+    std::thread::scope(|s| {
+        let gateway_running = s.spawn(|| gateway::gateway_running().unwrap_or(false));
+        let detection = s.spawn(|| state.detection());
+        let system_info = s.spawn(system::get_system_info_with_gpu);
+        let ollama_models = s.spawn(get_ollama_models);
+        let lm_studio_models = s.spawn(get_lm_studio_models);
+        let llmfit_system = s.spawn(|| cache.system());
+        let llmfit_recommendations = s.spawn(|| {
+            cache.recommendations(recommendation_limit.unwrap_or(10), &Default::default())
+        });
+        DashboardState {
+            status: get_status(),
+            openclaw_config: state.config_view(),
+            agents: agents::list_agent_names(),
+            gateway_running: gateway_running.join().unwrap_or_default(),
+            detection: detection.join().unwrap_or_default(),
+            system_info: system_info.join().unwrap_or_default(),
+            ollama_models: ollama_models.join().unwrap_or_default(),
+            lm_studio_models: lm_studio_models.join().unwrap_or_default(),
+            llmfit_system: llmfit_system.join().unwrap_or_default(),
+            llmfit_recommendations: llmfit_recommendations.join().unwrap_or_default(),
+        }
+    })
+}
+
+/// Brings the main window to the front.
+fn show_main_window(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+/// Focuses the window for `nav`'s panel, pointing it at `nav`, or opens one on its route.
+fn open_panel_window(app: &tauri::AppHandle, nav: &deep_link::Navigation) -> Result<(), String> {
+    let label = nav.window_label();
+    if let Some(window) = app.get_webview_window(&label) {
+        let _ = app.emit_to(label.as_str(), deep_link::NAVIGATE_EVENT, nav);
+        let _ = window.unminimize();
+        return window.set_focus().map_err(|e| e.to_string());
+    }
+    let url = tauri::WebviewUrl::App(format!("index.html#{}", nav.route()).into());
+    tauri::WebviewWindowBuilder::new(app, label, url)
+        .title(nav.window_title())
+        .inner_size(800.0, 600.0)
+        .build()
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Opens the panel a deep link points at: in a window of its own with `window=new`, otherwise in
+/// the main window, queued until its frontend listens (see `take_pending_navigation`).
+fn open_deep_link(app: &tauri::AppHandle, url: &str) -> Result<deep_link::Navigation, String> {
+    let nav = deep_link::parse_deep_link(url)?;
+    if nav.new_window {
+        open_panel_window(app, &nav)?;
+        return Ok(nav);
+    }
+    show_main_window(app);
+    if app.state::<deep_link::NavigationState>().push(nav.clone()) {
+        let _ = app.emit_to("main", deep_link::NAVIGATE_EVENT, &nav);
+    }
+    Ok(nav)
+}
+
+/// Follows links from the OS; a bad one is reported as `deep-link-error` rather than dropped.
+fn open_deep_links<U: AsRef<str>>(app: &tauri::AppHandle, urls: &[U]) {
+    for url in urls {
+        if let Err(e) = open_deep_link(app, url.as_ref()) {
+            let _ = app.emit("deep-link-error", e);
         }
-        Err(e) => Err(format!("Failed to check gateway status: {}", e)),
     }
 }
 
+/// The link the app was opened with before the frontend listened; call once the `navigate`
+/// listener is attached.
+#[tauri::command]
+fn take_pending_navigation(
+    state: tauri::State<deep_link::NavigationState>,
+) -> Option<deep_link::Navigation> {
+    state.take_pending()
+}
+
+/// Follows an `openclaw-config://` link from inside the app the same way as one from the OS.
+#[tauri::command(async)]
+fn navigate(app: tauri::AppHandle, url: String) -> Result<deep_link::Navigation, String> {
+    open_deep_link(&app, &url)
+}
+
 fn main() {
     tauri::Builder::default()
+        // First, so a second launch (e.g. a clicked link) hands over to the running instance,
+        // whose deep-link handler then gets the URL.
+        .plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| {
+            show_main_window(app);
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_shell::init())
-        .invoke_handler(tauri::generate_handler![
+        .plugin(tauri_plugin_notification::init())
+        .manage(llmfit::LlmfitCache::default())
+        .manage(app_state::AppState::default())
+        .manage(notifications::Notifier::default())
+        .manage(webhooks::SyncDriftTracker::default())
+        .manage(model_deprecations::ModelIdWarningTracker::default())
+        .manage(live_stats::LiveStatsStream::default())
+        .manage(gateway_monitor::GatewayMonitor::default())
+        .manage(metrics::MetricsServer::default())
+        .manage(operations::OperationRegistry::default())
+        .manage(usage_stats::UsageCounter::new(
+            settings::get_app_settings().telemetry_opt_in,
+        ))
+        .manage(deep_link::NavigationState::default())
+        .setup(|app| {
+            // Not fatal: some Linux desktops have no tray; the window still works.
+            let _ = tray::create(app.handle());
+            gateway_monitor::spawn(app.handle().clone());
+            // Not fatal: a taken port only disables the metrics endpoint.
+            let _ = apply_metrics_settings(app.handle(), &settings::get_app_settings().metrics);
+            emit_config_corrupt(app.handle());
+            if let Ok(root) = app.state::<app_state::AppState>().config_root() {
+                emit_model_id_warnings(app.handle(), &root);
+            }
+            // Linux and Windows register the scheme on install; this covers dev builds and
+            // AppImages.
+            #[cfg(any(target_os = "linux", windows))]
+            let _ = app.deep_link().register_all();
+            let handle = app.handle().clone();
+            app.deep_link().on_open_url(move |event| {
+                open_deep_links(&handle, &event.urls());
+            });
+            if let Ok(Some(urls)) = app.deep_link().get_current() {
+                open_deep_links(app.handle(), &urls);
+            }
+            let handle = app.handle().clone();
+            let ops = app.state::<operations::OperationRegistry>();
+            ops.set_listener(move |info| {
+                let _ = handle.emit("operation-progress", info);
+            });
+            let handle = app.handle().clone();
+            scheduler::spawn(move |kind| {
+                let _ = run_scheduled_job(&handle, kind);
+            });
+            let handle = app.handle().clone();
+            // Not fatal: without model directories there is simply nothing to watch.
+            if let Ok(w) = watcher::watch_model_directories(move |ev| {
+                handle.state::<app_state::AppState>().invalidate_models();
+                let _ = handle.emit("models-available-changed", ev);
+            }) {
+                app.manage(w);
+            }
+            let handle = app.handle().clone();
+            if let Ok(w) = watcher::watch_agents(move |ev| {
+                for name in &ev.added {
+                    let _ = handle.emit("agent-added", name);
+                }
+                for name in &ev.removed {
+                    let _ = handle.emit("agent-removed", name);
+                }
+                for name in &ev.models_changed {
+                    let _ = handle.emit("agent-models-changed", name);
+                    let in_sync = agents::get_provider_sync_status(name).in_sync;
+                    let tracker = handle.state::<webhooks::SyncDriftTracker>();
+                    if tracker.agent_checked(name, in_sync) {
+                        webhooks::emit_sync_drift(std::slice::from_ref(name));
+                    }
+                }
+            }) {
+                app.manage(w);
+            }
+            Ok(())
+        })
+        .invoke_handler(counting_handler(tauri::generate_handler![
             get_status,
             save_config,
             start_gateway,
             stop_gateway,
+            restart_gateway,
             check_gateway_status,
+            test_external_reachability,
+            generate_pairing_info,
+            list_gateway_tokens,
+            create_gateway_token,
+            revoke_gateway_token,
+            get_gateway_tls_status,
+            generate_self_signed_cert,
+            set_gateway_tls,
+            get_spend_limits,
+            update_spend_limits,
+            enforce_spend_limits,
+            get_config_drift,
+            reload_gateway_config,
+            tail_gateway_requests,
+            get_local_only_mode,
+            set_local_only_mode,
+            get_dashboard_state,
+            take_pending_navigation,
+            navigate,
+            get_app_settings,
+            pin_model,
+            unpin_model,
+            update_app_settings,
+            get_usage_stats,
+            export_usage_stats,
+            reset_usage_stats,
+            send_test_webhook,
+            list_jobs,
+            run_job_now,
+            get_setup_state,
+            run_setup_step,
+            reset_setup,
+            get_notification_rules,
+            set_notification_rules,
+            send_notification,
             add_model,
             save_api_key,
             detect_local_llms,
             get_system_info,
             get_ollama_models,
+            get_ollama_model_details,
+            get_ollama_models_page,
             get_lm_studio_models,
+            get_vllm_models,
+            get_model_catalog,
+            get_model_catalog_page,
+            search_models,
+            list_chat_models,
+            list_embedding_models,
+            get_model_limits,
+            check_agent_defaults_limits,
+            suggest_provider_model_ids,
+            add_provider_model_ids,
+            get_model_disk_usage,
+            get_disk_space,
+            export_diagnostics,
+            run_doctor,
+            test_provider,
+            get_cloud_provider_models,
+            read_gguf_metadata,
+            search_huggingface_models,
+            list_huggingface_gguf_files,
+            get_openrouter_models,
+            add_openrouter_models,
+            list_config_templates,
+            apply_config_template,
+            list_provider_presets,
+            add_provider_from_preset,
+            export_provider_config,
+            export_snippet,
+            import_from_claude_desktop,
+            list_mcp_servers,
+            add_mcp_server,
+            remove_mcp_server,
+            test_mcp_server,
+            download_to_ollama,
+            validate_modelfile,
+            create_ollama_model,
+            copy_ollama_model,
+            benchmark_model,
+            cancel_operation,
+            list_operations,
+            get_benchmark_history,
+            load_lm_studio_model,
+            unload_lm_studio_model,
+            get_lms_cli_status,
+            bootstrap_lms_cli,
+            get_hardware_profile,
+            check_model_fit,
+            validate_concurrency_settings,
+            estimate_model_memory,
+            start_live_stats,
+            stop_live_stats,
+            get_thermal_state,
+            get_runtime_resource_usage,
             get_llmfit_system,
             get_llmfit_recommendations,
+            refresh_llmfit,
+            get_llmfit_cache_info,
+            apply_recommendation,
+            get_llmfit_status,
+            install_llmfit,
+            check_ollama_update,
+            upgrade_ollama,
             get_openclaw_config,
+            check_model_ids,
+            check_config_integrity,
+            restore_from_backup,
+            start_fresh,
+            init_config_repo,
+            get_config_history,
+            revert_to_commit,
+            get_config_policy,
             update_openclaw_config,
+            list_remote_hosts,
+            add_remote_host,
+            remove_remote_host,
+            detect_remote_llms,
+            remote_gateway_running,
+            get_remote_openclaw_config,
+            update_remote_openclaw_config,
             list_agents,
             get_agent_models,
+            get_agent_details,
             get_agent_provider_sync_status,
-            update_agent_providers_from_openclaw
-        ])
+            update_agent_providers_from_openclaw,
+            sync_all_agents,
+            update_openclaw_providers_from_agent,
+            merge_agent_providers,
+            update_agent_provider,
+            remove_agent_provider,
+            get_agent_api_key_statuses,
+            set_agent_api_key_override,
+            clear_agent_api_key_override,
+            get_agent_env,
+            set_agent_env,
+            get_agent_model_config,
+            simulate_routing,
+            update_agent_model_config,
+            get_agent_subagent_limits,
+            update_agent_subagent_limits,
+            validate_agent,
+            create_agent,
+            clone_agent,
+            export_agent,
+            import_agent,
+            delete_agent,
+            rename_agent,
+            get_default_agent,
+            list_agents_detailed,
+            repair_agent,
+            get_agent_instructions,
+            set_agent_instructions,
+            get_agent_runtime_status,
+            diff_agents,
+            get_agent_usage,
+            estimate_costs
+        ]))
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
\ No newline at end of file
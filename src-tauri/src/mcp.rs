@@ -0,0 +1,404 @@
+//! MCP servers in openclaw.json under `mcp.servers.<name>`, in the same shape MCP clients use
+//! (`command`, `args`, `env`): a typed list, add/remove, and a start-up test that spawns the
+//! server and sends it an MCP `initialize` request over stdio. The test server runs in its own
+//! process group, so whatever `npx`/`uvx` spawned is killed with it.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use crate::openclaw_config;
+
+/// How long a server gets to answer `initialize`; npx/uvx may download the package first.
+const TEST_TIMEOUT: Duration = Duration::from_secs(20);
+const MCP_PROTOCOL_VERSION: &str = "2024-11-05";
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct McpServer {
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+}
+
+/// Servers configured in `root`; entries that don't parse are left out.
+#[must_use]
+pub fn servers_from_root(root: &Value) -> BTreeMap<String, McpServer> {
+    root.pointer("/mcp/servers")
+        .and_then(|s| s.as_object())
+        .map(|servers| {
+            servers
+                .iter()
+                .filter_map(|(name, v)| {
+                    Some((name.clone(), serde_json::from_value(v.clone()).ok()?))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The `mcp.servers` object, created if missing.
+pub(crate) fn servers_mut(root: &mut Value) -> Result<&mut serde_json::Map<String, Value>, String> {
+    root.as_object_mut()
+        .ok_or("openclaw.json root not an object")?
+        .entry("mcp")
+        .or_insert_with(|| serde_json::json!({}))
+        .as_object_mut()
+        .ok_or("mcp not an object")?
+        .entry("servers")
+        .or_insert_with(|| serde_json::json!({}))
+        .as_object_mut()
+        .ok_or_else(|| "mcp.servers not an object".to_string())
+}
+
+/// Adds or replaces server `name`.
+pub fn set_server(root: &mut Value, name: &str, server: &McpServer) -> Result<(), String> {
+    let value = serde_json::to_value(server).map_err(|e| e.to_string())?;
+    servers_mut(root)?.insert(name.to_string(), value);
+    Ok(())
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct McpServerEntry {
+    pub name: String,
+    #[serde(flatten)]
+    pub server: McpServer,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct McpTestResult {
+    pub name: String,
+    /// The process spawned.
+    pub started: bool,
+    /// It answered `initialize` with a result.
+    pub ok: bool,
+    /// serverInfo name and version from the answer.
+    pub server_info: Option<String>,
+    /// Set when the process exited before answering.
+    pub exit_status: Option<String>,
+    pub error: Option<String>,
+    /// Last lines the server wrote to stderr.
+    pub stderr_tail: String,
+    pub elapsed_ms: u64,
+}
+
+/// Checks a server name: letters, digits, `-`, `_` and `.`.
+pub fn validate_server_name(name: &str) -> Result<(), String> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err(format!(
+            "Invalid MCP server name {:?}: use letters, digits, -, _ or .",
+            name
+        ));
+    }
+    Ok(())
+}
+
+/// A name for a server without one: the package after runners like npx/uvx, minus scope and
+/// common prefixes ("@modelcontextprotocol/server-filesystem" -> "filesystem").
+#[must_use]
+pub fn default_server_name(command: &str, args: &[String]) -> String {
+    let program = command.rsplit(['/', '\\']).next().unwrap_or(command);
+    let target = if matches!(program, "npx" | "uvx" | "bunx" | "pnpx") {
+        args.iter()
+            .find(|a| !a.starts_with('-'))
+            .map_or(program, String::as_str)
+    } else {
+        program
+    };
+    let base = target.rsplit('/').next().unwrap_or(target);
+    let base = base.split('@').next().unwrap_or(base);
+    let base = base.split(':').next().unwrap_or(base);
+    let name = ["mcp-server-", "server-", "mcp-"]
+        .iter()
+        .find_map(|p| base.strip_prefix(p))
+        .unwrap_or(base);
+    let name = name.strip_suffix("-mcp").unwrap_or(name);
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+#[must_use]
+pub fn list_mcp_servers() -> Vec<McpServerEntry> {
+    openclaw_config::read_config_root()
+        .map(|root| {
+            servers_from_root(&root)
+                .into_iter()
+                .map(|(name, server)| McpServerEntry { name, server })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Adds a server under `name` (derived from the command when None); fails if the name is taken.
+pub fn add_mcp_server(
+    name: Option<&str>,
+    command: &str,
+    args: Vec<String>,
+    env: BTreeMap<String, String>,
+) -> Result<McpServerEntry, String> {
+    let command = command.trim();
+    if command.is_empty() {
+        return Err("MCP server command is empty".to_string());
+    }
+    let name = match name.map(str::trim).filter(|n| !n.is_empty()) {
+        Some(n) => n.to_string(),
+        None => default_server_name(command, &args),
+    };
+    validate_server_name(&name)?;
+    let mut root = openclaw_config::read_config_root()?;
+    if servers_mut(&mut root)?.contains_key(&name) {
+        return Err(format!(
+            "MCP server {} already exists; remove it first",
+            name
+        ));
+    }
+    let server = McpServer {
+        command: command.to_string(),
+        args,
+        env,
+    };
+    set_server(&mut root, &name, &server)?;
+    openclaw_config::write_config_root(&root)?;
+    Ok(McpServerEntry { name, server })
+}
+
+pub fn remove_mcp_server(name: &str) -> Result<(), String> {
+    let mut root = openclaw_config::read_config_root()?;
+    if servers_mut(&mut root)?.remove(name).is_none() {
+        return Err(format!("MCP server {} not found", name));
+    }
+    openclaw_config::write_config_root(&root)
+}
+
+fn initialize_request() -> String {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": MCP_PROTOCOL_VERSION,
+            "capabilities": {},
+            "clientInfo": { "name": "openclaw-host-config", "version": env!("CARGO_PKG_VERSION") }
+        }
+    })
+    .to_string()
+}
+
+/// The answer to our `initialize` (id 1) in a stdout line: Ok(server info) or Err(error
+/// message). None for anything else (logs, notifications).
+#[must_use]
+pub fn parse_initialize_response(line: &str) -> Option<Result<Option<String>, String>> {
+    let msg: Value = serde_json::from_str(line.trim()).ok()?;
+    if msg.get("id").and_then(|v| v.as_u64()) != Some(1) {
+        return None;
+    }
+    if let Some(err) = msg.get("error") {
+        let message = err
+            .get("message")
+            .and_then(|m| m.as_str())
+            .unwrap_or("error");
+        return Some(Err(message.to_string()));
+    }
+    let info = msg.pointer("/result/serverInfo").map(|i| {
+        let name = i.get("name").and_then(|v| v.as_str()).unwrap_or("?");
+        match i.get("version").and_then(|v| v.as_str()) {
+            Some(v) => format!("{} {}", name, v),
+            None => name.to_string(),
+        }
+    });
+    Some(Ok(info))
+}
+
+fn tail_lines(text: &str, n: usize) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    lines[lines.len().saturating_sub(n)..].join("\n")
+}
+
+/// Kills `child` and everything it started: its process group on Unix (it was spawned as a group
+/// leader), its process tree on Windows.
+fn kill_tree(child: &mut Child) {
+    let pid = child.id().to_string();
+    #[cfg(unix)]
+    let _ = Command::new("kill")
+        .args(["-KILL", "--", &format!("-{}", pid)])
+        .stderr(Stdio::null())
+        .status();
+    #[cfg(windows)]
+    let _ = Command::new("taskkill")
+        .args(["/T", "/F", "/PID", &pid])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    let _ = child.kill();
+}
+
+/// Spawns the server, sends `initialize` and waits for the answer, then kills it.
+pub fn test_mcp_server(name: &str) -> Result<McpTestResult, String> {
+    let root = openclaw_config::read_config_root()?;
+    let server = servers_from_root(&root)
+        .remove(name)
+        .ok_or_else(|| format!("MCP server {} not found", name))?;
+    let mut result = McpTestResult {
+        name: name.to_string(),
+        started: false,
+        ok: false,
+        server_info: None,
+        exit_status: None,
+        error: None,
+        stderr_tail: String::new(),
+        elapsed_ms: 0,
+    };
+    let started = Instant::now();
+    let mut command = Command::new(&server.command);
+    command
+        .args(&server.args)
+        .envs(&server.env)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    let mut child = match command.spawn() {
+        Ok(c) => c,
+        Err(e) => {
+            result.error = Some(format!("Failed to start {}: {}", server.command, e));
+            return Ok(result);
+        }
+    };
+    result.started = true;
+
+    let (tx, rx) = mpsc::channel();
+    if let Some(stdout) = child.stdout.take() {
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    let stderr = child.stderr.take().map(|mut err| {
+        std::thread::spawn(move || {
+            let mut text = String::new();
+            let _ = err.read_to_string(&mut text);
+            text
+        })
+    });
+    // Kept open until the child is killed; closing stdin makes some servers exit.
+    let mut stdin = child.stdin.take();
+    if let Some(input) = stdin.as_mut() {
+        let _ = writeln!(input, "{}", initialize_request());
+    }
+
+    let deadline = started + TEST_TIMEOUT;
+    loop {
+        let now = Instant::now();
+        if now >= deadline {
+            result.error = Some(format!(
+                "No answer to initialize within {}s",
+                TEST_TIMEOUT.as_secs()
+            ));
+            break;
+        }
+        match rx.recv_timeout((deadline - now).min(Duration::from_millis(200))) {
+            Ok(line) => match parse_initialize_response(&line) {
+                Some(Ok(info)) => {
+                    result.ok = true;
+                    result.server_info = info;
+                    break;
+                }
+                Some(Err(e)) => {
+                    result.error = Some(format!("initialize failed: {}", e));
+                    break;
+                }
+                None => {}
+            },
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                // stdout closed; the process is exiting.
+                let status = child.wait().ok();
+                result.exit_status = status.map(|s| s.to_string());
+                result.error = Some("Exited before answering initialize".to_string());
+                break;
+            }
+        }
+        if let Ok(Some(status)) = child.try_wait() {
+            result.exit_status = Some(status.to_string());
+            result.error = Some("Exited before answering initialize".to_string());
+            break;
+        }
+    }
+    result.elapsed_ms = started.elapsed().as_millis() as u64;
+    kill_tree(&mut child);
+    let _ = child.wait();
+    drop(stdin);
+    if let Some(handle) = stderr {
+        result.stderr_tail = tail_lines(&handle.join().unwrap_or_default(), 20);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_server_name() {
+        let args = |a: &[&str]| a.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            default_server_name(
+                "npx",
+                &args(&["-y", "@modelcontextprotocol/server-filesystem", "/tmp"])
+            ),
+            "filesystem"
+        );
+        assert_eq!(
+            default_server_name("uvx", &args(&["mcp-server-git"])),
+            "git"
+        );
+        assert_eq!(
+            default_server_name("/usr/local/bin/github-mcp", &[]),
+            "github"
+        );
+        assert!(validate_server_name("fs_1.local").is_ok());
+        assert!(validate_server_name("has space").is_err());
+    }
+
+    #[test]
+    fn test_parse_initialize_response() {
+        let ok = r#"{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2024-11-05","serverInfo":{"name":"fs","version":"0.6.2"}}}"#;
+        assert_eq!(
+            parse_initialize_response(ok),
+            Some(Ok(Some("fs 0.6.2".to_string())))
+        );
+        let err = r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32602,"message":"bad version"}}"#;
+        assert_eq!(
+            parse_initialize_response(err),
+            Some(Err("bad version".to_string()))
+        );
+        assert_eq!(
+            parse_initialize_response(r#"{"jsonrpc":"2.0","method":"notifications/message"}"#),
+            None
+        );
+        assert_eq!(parse_initialize_response("Starting server..."), None);
+    }
+}
@@ -0,0 +1,317 @@
+//! Imports MCP servers (and provider API keys found in their env) from other MCP clients'
+//! configs: Claude Desktop, Cursor and Cline all keep servers under `mcpServers`. Servers land
+//! in openclaw.json `mcp.servers`, keys in its `env` block. Anything that can't be mapped as-is
+//! is reported for manual attention. Parsing and merging are pure functions for unit tests.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::mcp::{self, McpServer};
+use crate::{detection, openclaw_config, provider_presets};
+
+/// Key env vars worth copying into openclaw.json, besides the provider presets' ones.
+const PROVIDER_KEY_VARS: [&str; 3] = ["ANTHROPIC_API_KEY", "OPENAI_API_KEY", "OPENROUTER_API_KEY"];
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ImportSource {
+    pub client: String,
+    pub path: String,
+    pub found: bool,
+    /// Set when the file exists but couldn't be read or parsed.
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ImportIssue {
+    pub client: String,
+    /// Server name or env var.
+    pub item: String,
+    pub reason: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ImportReport {
+    pub sources: Vec<ImportSource>,
+    pub imported_servers: Vec<String>,
+    /// Env var names copied into openclaw.json `env`.
+    pub imported_keys: Vec<String>,
+    /// Servers already in openclaw.json with the same settings.
+    pub unchanged: Vec<String>,
+    pub needs_attention: Vec<ImportIssue>,
+}
+
+/// A server entry from a client config.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClientServer {
+    pub name: String,
+    /// None for remote (url) servers.
+    pub server: Option<McpServer>,
+    pub url: Option<String>,
+    pub disabled: bool,
+}
+
+/// Client name and config path, for every client this knows about.
+fn client_config_paths() -> Vec<(&'static str, PathBuf)> {
+    let mut paths = Vec::new();
+    if let Some(config) = dirs::config_dir() {
+        paths.push((
+            "Claude Desktop",
+            config.join("Claude").join("claude_desktop_config.json"),
+        ));
+        paths.push((
+            "Cline",
+            config
+                .join("Code")
+                .join("User")
+                .join("globalStorage")
+                .join("saoudrizwan.claude-dev")
+                .join("settings")
+                .join("cline_mcp_settings.json"),
+        ));
+    }
+    if let Some(home) = dirs::home_dir() {
+        paths.push(("Cursor", home.join(".cursor").join("mcp.json")));
+    }
+    paths
+}
+
+/// Servers from a client config's `mcpServers`.
+pub fn parse_client_config(content: &str) -> Result<Vec<ClientServer>, String> {
+    let root: Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    let Some(servers) = root.get("mcpServers").and_then(|s| s.as_object()) else {
+        return Ok(vec![]);
+    };
+    Ok(servers
+        .iter()
+        .map(|(name, v)| ClientServer {
+            name: name.clone(),
+            server: v
+                .get("command")
+                .and_then(|c| c.as_str())
+                .and_then(|_| serde_json::from_value(v.clone()).ok()),
+            url: v
+                .get("url")
+                .or_else(|| v.get("serverUrl"))
+                .and_then(|u| u.as_str())
+                .map(String::from),
+            disabled: v.get("disabled").and_then(|d| d.as_bool()).unwrap_or(false),
+        })
+        .collect())
+}
+
+fn is_placeholder(value: &str) -> bool {
+    let v = value.trim();
+    v.is_empty() || (v.starts_with('<') && v.ends_with('>')) || v.to_lowercase().contains("your")
+}
+
+fn is_provider_key_var(name: &str) -> bool {
+    PROVIDER_KEY_VARS.contains(&name)
+        || provider_presets::PROVIDER_PRESETS
+            .iter()
+            .any(|p| p.key_env == name)
+}
+
+/// Merges one client's servers into `root`, recording the outcome in `report`. Servers are
+/// never overwritten: a name already used by different settings is reported instead.
+pub fn merge_client_servers(
+    root: &mut Value,
+    client: &str,
+    servers: &[ClientServer],
+    command_exists: impl Fn(&str) -> bool,
+    report: &mut ImportReport,
+) -> Result<(), String> {
+    let existing = mcp::servers_from_root(root);
+    let mut issue = |item: &str, reason: String| {
+        report.needs_attention.push(ImportIssue {
+            client: client.to_string(),
+            item: item.to_string(),
+            reason,
+        })
+    };
+    for entry in servers {
+        let Some(server) = &entry.server else {
+            match &entry.url {
+                Some(url) => issue(
+                    &entry.name,
+                    format!("Remote server ({}); add it by hand", url),
+                ),
+                None => issue(&entry.name, "No command or url".to_string()),
+            }
+            continue;
+        };
+        if entry.disabled {
+            issue(
+                &entry.name,
+                "Disabled in this client; not imported".to_string(),
+            );
+            continue;
+        }
+        match existing.get(&entry.name) {
+            Some(current) if current == server => {
+                if !report.unchanged.contains(&entry.name) {
+                    report.unchanged.push(entry.name.clone());
+                }
+                continue;
+            }
+            Some(_) => {
+                issue(
+                    &entry.name,
+                    "A different server with this name is already configured".to_string(),
+                );
+                continue;
+            }
+            None => {}
+        }
+        if !command_exists(&server.command) {
+            issue(
+                &entry.name,
+                format!("Command {} not found on PATH", server.command),
+            );
+        }
+        for (var, value) in &server.env {
+            if is_placeholder(value) {
+                issue(
+                    &entry.name,
+                    format!("{} looks like a placeholder; set a real value", var),
+                );
+            }
+        }
+        mcp::set_server(root, &entry.name, server)?;
+        report.imported_servers.push(entry.name.clone());
+
+        for (var, value) in &server.env {
+            if !is_provider_key_var(var) || is_placeholder(value) {
+                continue;
+            }
+            let env = root
+                .as_object_mut()
+                .ok_or("openclaw.json root not an object")?
+                .entry("env")
+                .or_insert_with(|| serde_json::json!({}))
+                .as_object_mut()
+                .ok_or("env not an object")?;
+            match env.get(var).and_then(|v| v.as_str()) {
+                None => {
+                    env.insert(var.clone(), serde_json::json!(value));
+                    report.imported_keys.push(var.clone());
+                }
+                Some(current) if current != value => issue(
+                    var,
+                    "openclaw.json already sets a different value; kept it".to_string(),
+                ),
+                Some(_) => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reads every known client config, merges what it can into openclaw.json and reports the rest.
+pub fn import_from_claude_desktop() -> Result<ImportReport, String> {
+    let mut root = openclaw_config::read_config_root()?;
+    let mut report = ImportReport::default();
+    for (client, path) in client_config_paths() {
+        let mut source = ImportSource {
+            client: client.to_string(),
+            path: path.to_string_lossy().to_string(),
+            found: path.exists(),
+            error: None,
+        };
+        if source.found {
+            match fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|c| parse_client_config(&c))
+            {
+                Ok(servers) => merge_client_servers(
+                    &mut root,
+                    client,
+                    &servers,
+                    |cmd| detection::command_exists(cmd).is_some(),
+                    &mut report,
+                )?,
+                Err(e) => source.error = Some(e),
+            }
+        }
+        report.sources.push(source);
+    }
+    if !report.imported_servers.is_empty() || !report.imported_keys.is_empty() {
+        openclaw_config::write_config_root(&root)?;
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_client_config() {
+        let content = r#"{"mcpServers":{
+            "filesystem":{"command":"npx","args":["-y","@modelcontextprotocol/server-filesystem","/tmp"]},
+            "remote":{"url":"https://mcp.example.com/sse"},
+            "off":{"command":"uvx","args":["mcp-server-git"],"disabled":true}
+        }}"#;
+        let servers = parse_client_config(content).unwrap();
+        let find = |name: &str| servers.iter().find(|s| s.name == name).unwrap();
+        assert_eq!(servers.len(), 3);
+        assert_eq!(find("filesystem").server.as_ref().unwrap().args.len(), 3);
+        assert!(find("off").disabled);
+        let remote = find("remote");
+        assert!(remote.server.is_none());
+        assert_eq!(remote.url.as_deref(), Some("https://mcp.example.com/sse"));
+        assert!(parse_client_config("{}").unwrap().is_empty());
+        assert!(parse_client_config("nope").is_err());
+    }
+
+    #[test]
+    fn test_merge_client_servers() {
+        let mut root = serde_json::json!({ "mcp": { "servers": {
+            "git": { "command": "uvx", "args": ["mcp-server-git"] }
+        } } });
+        let server = |command: &str, env: &[(&str, &str)]| McpServer {
+            command: command.to_string(),
+            args: vec![],
+            env: env
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        };
+        let entry = |name: &str, server| ClientServer {
+            name: name.to_string(),
+            server: Some(server),
+            url: None,
+            disabled: false,
+        };
+        let servers = vec![
+            entry("git", server("git-mcp", &[])),
+            entry(
+                "search",
+                server(
+                    "search-mcp",
+                    &[("OPENAI_API_KEY", "sk-1"), ("BRAVE_KEY", "<your key>")],
+                ),
+            ),
+        ];
+        let mut report = ImportReport::default();
+        merge_client_servers(
+            &mut root,
+            "Cursor",
+            &servers,
+            |cmd| cmd != "search-mcp",
+            &mut report,
+        )
+        .unwrap();
+        assert_eq!(report.imported_servers, vec!["search"]);
+        assert_eq!(report.imported_keys, vec!["OPENAI_API_KEY"]);
+        assert_eq!(root["env"]["OPENAI_API_KEY"], "sk-1");
+        assert_eq!(root["mcp"]["servers"]["git"]["command"], "uvx");
+        let items: Vec<&str> = report
+            .needs_attention
+            .iter()
+            .map(|i| i.item.as_str())
+            .collect();
+        assert_eq!(items, vec!["git", "search", "search"]);
+    }
+}
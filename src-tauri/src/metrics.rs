@@ -0,0 +1,342 @@
+//! Opt-in Prometheus endpoint: a small HTTP listener (default 127.0.0.1:9464) serving gateway
+//! health, runtime detection, agent sync state and memory/CPU/GPU gauges in the text exposition
+//! format at `/metrics`. The caller supplies the snapshot, so cached app state can be reused
+//! between scrapes. Rendering is a pure function for unit tests.
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use sysinfo::System;
+
+use crate::detection::LocalLLMDetection;
+use crate::{agents, system};
+
+pub const DEFAULT_METRICS_PORT: u16 = 9464;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct MetricsSettings {
+    pub enabled: bool,
+    /// Address to listen on; 0.0.0.0 lets another host scrape.
+    pub bind: String,
+    pub port: u16,
+}
+
+impl Default for MetricsSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind: "127.0.0.1".to_string(),
+            port: DEFAULT_METRICS_PORT,
+        }
+    }
+}
+
+/// Values for one scrape; None leaves a metric out.
+#[derive(Clone, Default)]
+pub struct MetricsSnapshot {
+    pub gateway_up: Option<bool>,
+    pub detection: Option<LocalLLMDetection>,
+    /// Agent name and whether its providers match openclaw.json.
+    pub agents_in_sync: Vec<(String, bool)>,
+    pub total_memory_bytes: u64,
+    pub available_memory_bytes: u64,
+    pub swap_total_bytes: u64,
+    pub swap_used_bytes: u64,
+    pub cpu_usage_percent: Option<f32>,
+    pub gpu_vram_bytes: Option<u64>,
+    pub gpu_vram_used_bytes: Option<u64>,
+}
+
+impl MetricsSnapshot {
+    /// Resource gauges and agent sync state; the caller adds gateway and detection status.
+    #[must_use]
+    pub fn collect(gateway_up: Option<bool>, detection: Option<LocalLLMDetection>) -> Self {
+        let info = system::get_system_info_with_gpu();
+        let mut sys = System::new();
+        // CPU usage is a delta between two refreshes.
+        sys.refresh_cpu_usage();
+        thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+        sys.refresh_cpu_usage();
+        Self {
+            gateway_up,
+            detection,
+            agents_in_sync: agents::list_agent_names()
+                .into_iter()
+                .map(|name| {
+                    let in_sync = agents::get_provider_sync_status(&name).in_sync;
+                    (name, in_sync)
+                })
+                .collect(),
+            total_memory_bytes: info.total_memory_bytes,
+            available_memory_bytes: info.available_memory_bytes,
+            swap_total_bytes: info.swap_total_bytes,
+            swap_used_bytes: info.swap_used_bytes,
+            cpu_usage_percent: Some(sys.global_cpu_usage()),
+            gpu_vram_bytes: info.gpu.as_ref().map(|g| g.vram_bytes),
+            gpu_vram_used_bytes: info.gpu.as_ref().and_then(|g| g.vram_used_bytes),
+        }
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn header(out: &mut String, name: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}\n# TYPE {} gauge", name, help, name);
+}
+
+fn flag(b: bool) -> u8 {
+    u8::from(b)
+}
+
+/// The snapshot in Prometheus text exposition format.
+#[must_use]
+pub fn render_metrics(s: &MetricsSnapshot) -> String {
+    let mut out = String::new();
+    header(&mut out, "openclaw_host_config_info", "App version.");
+    let _ = writeln!(
+        out,
+        "openclaw_host_config_info{{version=\"{}\"}} 1",
+        env!("CARGO_PKG_VERSION")
+    );
+    if let Some(up) = s.gateway_up {
+        header(
+            &mut out,
+            "openclaw_gateway_up",
+            "Whether the OpenClaw gateway is running.",
+        );
+        let _ = writeln!(out, "openclaw_gateway_up {}", flag(up));
+    }
+    if let Some(d) = &s.detection {
+        let runtimes = [
+            ("ollama", &d.ollama),
+            ("lmstudio", &d.lm_studio),
+            ("vllm", &d.vllm),
+        ];
+        header(
+            &mut out,
+            "openclaw_runtime_installed",
+            "Whether a local LLM runtime is installed.",
+        );
+        for (name, status) in runtimes {
+            let _ = writeln!(
+                out,
+                "openclaw_runtime_installed{{runtime=\"{}\"}} {}",
+                name,
+                flag(status.installed)
+            );
+        }
+        header(
+            &mut out,
+            "openclaw_runtime_up",
+            "Whether a local LLM runtime is serving.",
+        );
+        for (name, status) in runtimes {
+            let _ = writeln!(
+                out,
+                "openclaw_runtime_up{{runtime=\"{}\"}} {}",
+                name,
+                flag(status.running)
+            );
+        }
+    }
+    if !s.agents_in_sync.is_empty() {
+        header(
+            &mut out,
+            "openclaw_agent_providers_in_sync",
+            "Whether an agent's models.json providers match openclaw.json.",
+        );
+        for (name, in_sync) in &s.agents_in_sync {
+            let _ = writeln!(
+                out,
+                "openclaw_agent_providers_in_sync{{agent=\"{}\"}} {}",
+                escape_label(name),
+                flag(*in_sync)
+            );
+        }
+    }
+    let gauges = [
+        (
+            "openclaw_host_memory_total_bytes",
+            "Physical memory.",
+            Some(s.total_memory_bytes),
+        ),
+        (
+            "openclaw_host_memory_available_bytes",
+            "Available physical memory.",
+            Some(s.available_memory_bytes),
+        ),
+        (
+            "openclaw_host_swap_total_bytes",
+            "Swap size.",
+            Some(s.swap_total_bytes),
+        ),
+        (
+            "openclaw_host_swap_used_bytes",
+            "Swap in use.",
+            Some(s.swap_used_bytes),
+        ),
+        (
+            "openclaw_host_gpu_vram_bytes",
+            "Primary GPU memory.",
+            s.gpu_vram_bytes,
+        ),
+        (
+            "openclaw_host_gpu_vram_used_bytes",
+            "Primary GPU memory in use.",
+            s.gpu_vram_used_bytes,
+        ),
+    ];
+    for (name, help, value) in gauges {
+        if let Some(v) = value {
+            header(&mut out, name, help);
+            let _ = writeln!(out, "{} {}", name, v);
+        }
+    }
+    if let Some(cpu) = s.cpu_usage_percent {
+        header(
+            &mut out,
+            "openclaw_host_cpu_usage_percent",
+            "CPU load across all cores, 0-100.",
+        );
+        let _ = writeln!(out, "openclaw_host_cpu_usage_percent {:.1}", cpu);
+    }
+    out
+}
+
+type Collector = Arc<dyn Fn() -> MetricsSnapshot + Send + Sync>;
+
+fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &str) {
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+}
+
+fn handle(mut stream: TcpStream, collect: &Collector) {
+    let _ = stream.set_nonblocking(false);
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(2)));
+    let mut request_line = String::new();
+    if BufReader::new(&stream)
+        .read_line(&mut request_line)
+        .is_err()
+    {
+        return;
+    }
+    let mut parts = request_line.split_whitespace();
+    match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => respond(
+            &mut stream,
+            "200 OK",
+            "text/plain; version=0.0.4",
+            &render_metrics(&collect()),
+        ),
+        (Some("GET"), Some("/")) => respond(
+            &mut stream,
+            "200 OK",
+            "text/plain",
+            "openclaw-host-config metrics: see /metrics\n",
+        ),
+        _ => respond(&mut stream, "404 Not Found", "text/plain", "not found\n"),
+    }
+}
+
+/// Managed state for the listener; at most one runs at a time.
+#[derive(Default)]
+pub struct MetricsServer {
+    /// Listen address and stop flag of the running listener.
+    current: Mutex<Option<(String, Arc<AtomicBool>)>>,
+}
+
+impl MetricsServer {
+    /// Starts, stops or moves the listener to match `settings`. Errors when the address can't
+    /// be bound.
+    pub fn apply<F>(&self, settings: &MetricsSettings, collect: F) -> Result<(), String>
+    where
+        F: Fn() -> MetricsSnapshot + Send + Sync + 'static,
+    {
+        let mut current = self.current.lock().unwrap_or_else(|e| e.into_inner());
+        let wanted = settings
+            .enabled
+            .then(|| format!("{}:{}", settings.bind, settings.port));
+        if current.as_ref().map(|(addr, _)| addr) == wanted.as_ref() {
+            return Ok(());
+        }
+        if let Some((_, running)) = current.take() {
+            running.store(false, Ordering::SeqCst);
+        }
+        let Some(addr) = wanted else {
+            return Ok(());
+        };
+        let listener =
+            TcpListener::bind(&addr).map_err(|e| format!("Metrics listener on {}: {}", addr, e))?;
+        listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+        let running = Arc::new(AtomicBool::new(true));
+        let flag = Arc::clone(&running);
+        let collect: Collector = Arc::new(collect);
+        thread::spawn(move || {
+            while flag.load(Ordering::SeqCst) {
+                match listener.accept() {
+                    Ok((stream, _)) => handle(stream, &collect),
+                    Err(_) => thread::sleep(Duration::from_millis(100)),
+                }
+            }
+        });
+        *current = Some((addr, running));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::LLMStatus;
+
+    #[test]
+    fn test_render_metrics() {
+        let status = |installed, running| LLMStatus {
+            installed,
+            running,
+            version: None,
+            path: None,
+        };
+        let snapshot = MetricsSnapshot {
+            gateway_up: Some(true),
+            detection: Some(LocalLLMDetection {
+                ollama: status(true, true),
+                lm_studio: status(true, false),
+                vllm: status(false, false),
+            }),
+            agents_in_sync: vec![("main".to_string(), false), ("we\"ird".to_string(), true)],
+            total_memory_bytes: 32,
+            gpu_vram_bytes: Some(24),
+            cpu_usage_percent: Some(12.34),
+            ..Default::default()
+        };
+        let text = render_metrics(&snapshot);
+        assert!(text.contains("# TYPE openclaw_gateway_up gauge\nopenclaw_gateway_up 1\n"));
+        assert!(text.contains("openclaw_runtime_up{runtime=\"ollama\"} 1\n"));
+        assert!(text.contains("openclaw_runtime_up{runtime=\"lmstudio\"} 0\n"));
+        assert!(text.contains("openclaw_runtime_installed{runtime=\"vllm\"} 0\n"));
+        assert!(text.contains("openclaw_agent_providers_in_sync{agent=\"main\"} 0\n"));
+        assert!(text.contains("{agent=\"we\\\"ird\"} 1\n"));
+        assert!(text.contains("openclaw_host_memory_total_bytes 32\n"));
+        assert!(text.contains("openclaw_host_gpu_vram_bytes 24\n"));
+        assert!(!text.contains("openclaw_host_gpu_vram_used_bytes"));
+        assert!(text.contains("openclaw_host_cpu_usage_percent 12.3\n"));
+    }
+}
@@ -0,0 +1,227 @@
+//! Deprecated and renamed model ids: a small built-in registry of hosted models that providers
+//! have retired, scheduled for retirement or renamed, and a scan of openclaw.json's model
+//! selections (defaults primary/fallbacks, the models allow-list, subagents and each agent in
+//! agents.list) that suggests a replacement for each hit. `ModelIdWarningTracker` keeps the UI from
+//! being sent the same warnings on every config read. Lookups and the scan are pure functions for
+//! unit tests.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Mutex;
+
+use crate::openclaw_config;
+use DeprecationKind::{Deprecated, Renamed, Retired};
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeprecationKind {
+    /// The provider no longer serves it; requests fail.
+    Retired,
+    /// Still served, but scheduled for retirement.
+    Deprecated,
+    /// An old name for the replacement.
+    Renamed,
+}
+
+/// Known ids as (model name prefix, kind, replacement). A prefix matches the model name (the part
+/// after the last '/') exactly or followed by '-', so dated snapshots and `-latest` aliases are
+/// covered; the longest match wins.
+const REGISTRY: &[(&str, DeprecationKind, &str)] = &[
+    // Anthropic
+    ("claude-instant-1", Retired, "claude-haiku-4-5"),
+    ("claude-2", Retired, "claude-sonnet-4-5"),
+    ("claude-2.0", Retired, "claude-sonnet-4-5"),
+    ("claude-2.1", Retired, "claude-sonnet-4-5"),
+    ("claude-3-sonnet", Retired, "claude-sonnet-4-5"),
+    ("claude-3-opus", Retired, "claude-opus-4-1"),
+    ("claude-3-5-sonnet", Retired, "claude-sonnet-4-5"),
+    ("claude-3-7-sonnet", Retired, "claude-sonnet-4-5"),
+    // OpenAI
+    ("text-davinci-003", Retired, "gpt-4.1-mini"),
+    ("gpt-4-32k", Retired, "gpt-4.1"),
+    ("gpt-4-vision-preview", Retired, "gpt-4.1"),
+    ("gpt-4-turbo-preview", Renamed, "gpt-4-turbo"),
+    ("gpt-4.5-preview", Retired, "gpt-4.1"),
+    ("o1-preview", Retired, "o3"),
+    ("o1-mini", Deprecated, "o4-mini"),
+    // Google
+    ("gemini-pro", Retired, "gemini-2.5-flash"),
+    ("gemini-1.0-pro", Retired, "gemini-2.5-flash"),
+    ("gemini-1.5-pro", Retired, "gemini-2.5-pro"),
+    ("gemini-1.5-flash", Retired, "gemini-2.5-flash"),
+];
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ModelIdWarning {
+    /// Where the id is set, e.g. "agents.defaults.model.fallbacks.0".
+    pub path: String,
+    pub model_id: String,
+    pub kind: DeprecationKind,
+    /// `model_id` with its model name replaced, provider prefix kept.
+    pub replacement: String,
+}
+
+/// The kind and suggested replacement for `model_id` ("provider/model" or a bare name), if the
+/// registry knows it.
+#[must_use]
+pub fn lookup_deprecation(model_id: &str) -> Option<(DeprecationKind, String)> {
+    let (prefix, name) = match model_id.rfind('/') {
+        Some(i) => model_id.split_at(i + 1),
+        None => ("", model_id),
+    };
+    let lower = name.to_ascii_lowercase();
+    REGISTRY
+        .iter()
+        .filter(|(known, _, _)| {
+            lower == *known
+                || lower
+                    .strip_prefix(known)
+                    .is_some_and(|rest| rest.starts_with('-'))
+        })
+        .max_by_key(|(known, _, _)| known.len())
+        .map(|(_, kind, replacement)| (*kind, format!("{}{}", prefix, replacement)))
+}
+
+/// Model ids in a model setting (a string or `{ primary, fallbacks }`) with their paths.
+fn model_refs<'a>(path: &str, model: &'a Value, out: &mut Vec<(String, &'a str)>) {
+    if let Some(id) = model.as_str() {
+        out.push((path.to_string(), id));
+        return;
+    }
+    if let Some(id) = model.get("primary").and_then(|p| p.as_str()) {
+        out.push((format!("{}.primary", path), id));
+    }
+    let fallbacks = model.get("fallbacks").and_then(|f| f.as_array());
+    for (i, id) in fallbacks.into_iter().flatten().enumerate() {
+        if let Some(id) = id.as_str() {
+            out.push((format!("{}.fallbacks.{}", path, i), id));
+        }
+    }
+}
+
+/// Deprecated or renamed ids selected in openclaw.json `root`.
+#[must_use]
+pub fn check_config_model_ids(root: &Value) -> Vec<ModelIdWarning> {
+    let mut refs = Vec::new();
+    if let Some(model) = root.pointer("/agents/defaults/model") {
+        model_refs("agents.defaults.model", model, &mut refs);
+    }
+    if let Some(models) = root
+        .pointer("/agents/defaults/models")
+        .and_then(|m| m.as_object())
+    {
+        for id in models.keys() {
+            refs.push((format!("agents.defaults.models.{}", id), id.as_str()));
+        }
+    }
+    if let Some(model) = root.pointer("/agents/defaults/subagents/model") {
+        model_refs("agents.defaults.subagents.model", model, &mut refs);
+    }
+    let list = root.pointer("/agents/list").and_then(|l| l.as_array());
+    for (i, agent) in list.into_iter().flatten().enumerate() {
+        if let Some(model) = agent.get("model") {
+            model_refs(&format!("agents.list.{}.model", i), model, &mut refs);
+        }
+        if let Some(model) = agent.pointer("/subagents/model") {
+            model_refs(
+                &format!("agents.list.{}.subagents.model", i),
+                model,
+                &mut refs,
+            );
+        }
+    }
+    refs.into_iter()
+        .filter_map(|(path, id)| {
+            lookup_deprecation(id).map(|(kind, replacement)| ModelIdWarning {
+                path,
+                model_id: id.to_string(),
+                kind,
+                replacement,
+            })
+        })
+        .collect()
+}
+
+/// The warnings last sent to the UI, so repeated config reads don't send the same ones again.
+#[derive(Default)]
+pub struct ModelIdWarningTracker {
+    last: Mutex<Vec<ModelIdWarning>>,
+}
+
+impl ModelIdWarningTracker {
+    /// Whether `warnings` differ from the last ones seen; remembers them either way.
+    pub fn changed(&self, warnings: &[ModelIdWarning]) -> bool {
+        let mut last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+        if last.as_slice() == warnings {
+            return false;
+        }
+        *last = warnings.to_vec();
+        true
+    }
+}
+
+/// Deprecated or renamed ids selected in the current openclaw.json.
+pub fn check_model_ids() -> Result<Vec<ModelIdWarning>, String> {
+    openclaw_config::read_config_root().map(|root| check_config_model_ids(&root))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_deprecation() {
+        assert_eq!(
+            lookup_deprecation("anthropic/claude-3-5-sonnet-20241022"),
+            Some((
+                DeprecationKind::Retired,
+                "anthropic/claude-sonnet-4-5".to_string()
+            ))
+        );
+        assert_eq!(
+            lookup_deprecation("openrouter/openai/gpt-4-turbo-preview").map(|d| d.1),
+            Some("openrouter/openai/gpt-4-turbo".to_string())
+        );
+        // Prefixes end at '-': "claude-2.1" has its own entry and "gpt-4o" is not "gpt-4-...".
+        assert_eq!(
+            lookup_deprecation("claude-2.1").unwrap().1,
+            "claude-sonnet-4-5"
+        );
+        assert_eq!(lookup_deprecation("openai/gpt-4o"), None);
+        assert_eq!(lookup_deprecation("anthropic/claude-sonnet-4-5"), None);
+    }
+
+    #[test]
+    fn test_check_config_model_ids() {
+        let root = serde_json::json!({
+            "agents": {
+                "defaults": {
+                    "model": {
+                        "primary": "ollama/qwen3:8b",
+                        "fallbacks": ["google/gemini-1.5-flash"]
+                    },
+                    "models": { "openai/o1-mini": {} }
+                },
+                "list": [{ "id": "dev", "model": "anthropic/claude-3-opus-20240229" }]
+            }
+        });
+        let warnings = check_config_model_ids(&root);
+        let paths: Vec<&str> = warnings.iter().map(|w| w.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "agents.defaults.model.fallbacks.0",
+                "agents.defaults.models.openai/o1-mini",
+                "agents.list.0.model"
+            ]
+        );
+        assert_eq!(warnings[1].kind, DeprecationKind::Deprecated);
+        assert_eq!(warnings[2].replacement, "anthropic/claude-opus-4-1");
+
+        let tracker = ModelIdWarningTracker::default();
+        assert!(tracker.changed(&warnings));
+        assert!(!tracker.changed(&warnings));
+        assert!(tracker.changed(&[]));
+        assert!(tracker.changed(&warnings));
+    }
+}
@@ -0,0 +1,251 @@
+//! Context-window registry: built-in limits for well-known models, overridden by values the
+//! runtime or openclaw.json reports. Used to flag agents.defaults settings a model can't honor.
+//! Matching and checks are pure functions for unit tests.
+
+use serde::{Deserialize, Serialize};
+
+use crate::catalog::CatalogModel;
+use crate::openclaw_config;
+
+/// Known limits as (name prefix, context window, max output tokens). Prefixes are compared against
+/// the normalized model name (see `normalize_model_name`); the longest match wins.
+const REGISTRY: &[(&str, u64, Option<u64>)] = &[
+    // Anthropic
+    ("claude-opus-4", 200_000, Some(32_000)),
+    ("claude-sonnet-4", 200_000, Some(64_000)),
+    ("claude-haiku-4", 200_000, Some(64_000)),
+    ("claude-3-7-sonnet", 200_000, Some(64_000)),
+    ("claude-3-5-sonnet", 200_000, Some(8_192)),
+    ("claude-3-5-haiku", 200_000, Some(8_192)),
+    // OpenAI
+    ("gpt-5", 400_000, Some(128_000)),
+    ("gpt-4.1", 1_047_576, Some(32_768)),
+    ("gpt-4o", 128_000, Some(16_384)),
+    ("o3", 200_000, Some(100_000)),
+    ("o4-mini", 200_000, Some(100_000)),
+    // Google
+    ("gemini-2.5-pro", 1_048_576, Some(65_536)),
+    ("gemini-2.5-flash", 1_048_576, Some(65_536)),
+    ("gemini-2.0-flash", 1_048_576, Some(8_192)),
+    // Open weights (native limits; local runtimes often load with less, see catalog context_length)
+    ("llama3", 8_192, None),
+    ("llama3.1", 131_072, None),
+    ("llama3.2", 131_072, None),
+    ("llama3.3", 131_072, None),
+    ("llama4", 1_048_576, None),
+    ("qwen2.5", 32_768, None),
+    ("qwen2.5-coder", 32_768, None),
+    ("qwen3", 40_960, None),
+    ("mistral", 32_768, None),
+    ("mistral-nemo", 131_072, None),
+    ("mixtral", 32_768, None),
+    ("gemma2", 8_192, None),
+    ("gemma3", 131_072, None),
+    ("phi4", 16_384, None),
+    ("deepseek-r1", 131_072, None),
+    ("deepseek-v3", 131_072, None),
+    ("gpt-oss", 131_072, None),
+    // Embedding
+    ("nomic-embed-text", 8_192, None),
+    ("mxbai-embed-large", 512, None),
+    ("bge-m3", 8_192, None),
+];
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ModelLimits {
+    pub context_window: u64,
+    pub max_output_tokens: Option<u64>,
+    /// "runtime" (reported by the runtime), "config" (openclaw.json) or "registry" (built-in).
+    pub source: String,
+}
+
+/// One agents.defaults setting that exceeds what a selected model supports.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct LimitWarning {
+    /// openclaw model id, e.g. "ollama/llama3.2:3b".
+    pub model: String,
+    /// Setting path, e.g. "agents.defaults.contextTokens".
+    pub setting: String,
+    pub configured: u64,
+    pub supported: u64,
+    pub message: String,
+}
+
+/// Lowercases and drops the provider/org prefix and the Ollama tag: "Qwen/Qwen2.5-7B" -> "qwen2.5-7b".
+#[must_use]
+pub fn normalize_model_name(name: &str) -> String {
+    let base = name.rsplit('/').next().unwrap_or(name);
+    let base = base.split(':').next().unwrap_or(base);
+    base.to_lowercase()
+}
+
+/// Built-in limits for a model name, if it matches a known family.
+#[must_use]
+pub fn registry_limits(name: &str) -> Option<ModelLimits> {
+    let normalized = normalize_model_name(name);
+    REGISTRY
+        .iter()
+        .filter(|(prefix, _, _)| normalized.starts_with(prefix))
+        .max_by_key(|(prefix, _, _)| prefix.len())
+        .map(|(_, ctx, out)| ModelLimits {
+            context_window: *ctx,
+            max_output_tokens: *out,
+            source: "registry".to_string(),
+        })
+}
+
+/// Limits for a catalog entry: the runtime-reported context length wins over the registry.
+#[must_use]
+pub fn limits_for_catalog_model(model: &CatalogModel) -> Option<ModelLimits> {
+    let registry = registry_limits(&model.name);
+    match model.context_length {
+        Some(ctx) => Some(ModelLimits {
+            context_window: ctx,
+            max_output_tokens: registry.and_then(|r| r.max_output_tokens),
+            source: "runtime".to_string(),
+        }),
+        None => registry,
+    }
+}
+
+/// Limits for an openclaw id ("provider/model") from the provider's contextWindow/maxTokens in
+/// models.providers, else the registry.
+#[must_use]
+pub fn limits_for_model_id(id: &str, providers: &serde_json::Value) -> Option<ModelLimits> {
+    let (provider, model) = id.split_once('/').unwrap_or(("", id));
+    let entry = providers
+        .get(provider)
+        .and_then(|p| p.get("models"))
+        .and_then(|a| a.as_array())
+        .and_then(|a| {
+            a.iter()
+                .find(|e| e.get("id").and_then(|v| v.as_str()) == Some(model))
+        });
+    let registry = registry_limits(model);
+    match entry
+        .and_then(|e| e.get("contextWindow"))
+        .and_then(|v| v.as_u64())
+    {
+        Some(ctx) => Some(ModelLimits {
+            context_window: ctx,
+            max_output_tokens: entry
+                .and_then(|e| e.get("maxTokens"))
+                .and_then(|v| v.as_u64())
+                .or(registry.and_then(|r| r.max_output_tokens)),
+            source: "config".to_string(),
+        }),
+        None => registry,
+    }
+}
+
+/// Compares agents.defaults.contextTokens with the primary and fallback models' context windows.
+#[must_use]
+pub fn agent_defaults_limit_warnings(root: &serde_json::Value) -> Vec<LimitWarning> {
+    let defaults = match root.get("agents").and_then(|a| a.get("defaults")) {
+        Some(d) => d,
+        None => return vec![],
+    };
+    let context_tokens = match defaults.get("contextTokens").and_then(|v| v.as_u64()) {
+        Some(n) => n,
+        None => return vec![],
+    };
+    let model = defaults.get("model");
+    let primary = model
+        .and_then(|m| m.get("primary"))
+        .and_then(|v| v.as_str());
+    let fallbacks = model
+        .and_then(|m| m.get("fallbacks"))
+        .and_then(|a| a.as_array())
+        .map(|a| a.iter().filter_map(|v| v.as_str()).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    let providers = root
+        .get("models")
+        .and_then(|m| m.get("providers"))
+        .cloned()
+        .unwrap_or(serde_json::json!({}));
+    primary
+        .into_iter()
+        .chain(fallbacks)
+        .filter_map(|id| {
+            let limits = limits_for_model_id(id, &providers)?;
+            (context_tokens > limits.context_window).then(|| LimitWarning {
+                model: id.to_string(),
+                setting: "agents.defaults.contextTokens".to_string(),
+                configured: context_tokens,
+                supported: limits.context_window,
+                message: format!(
+                    "contextTokens is {} but {} supports {} tokens",
+                    context_tokens, id, limits.context_window
+                ),
+            })
+        })
+        .collect()
+}
+
+/// Reads openclaw.json and reports agents.defaults settings the selected models can't honor.
+#[must_use]
+pub fn check_agent_defaults_limits() -> Vec<LimitWarning> {
+    openclaw_config::read_config_root()
+        .map(|root| agent_defaults_limit_warnings(&root))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_limits_longest_prefix() {
+        assert_eq!(
+            normalize_model_name("Qwen/Qwen2.5-7B-Instruct"),
+            "qwen2.5-7b-instruct"
+        );
+        assert_eq!(
+            registry_limits("llama3.2:3b").unwrap().context_window,
+            131_072
+        );
+        assert_eq!(registry_limits("llama3:8b").unwrap().context_window, 8_192);
+        assert_eq!(
+            registry_limits("qwen2.5-coder:7b").unwrap().context_window,
+            32_768
+        );
+        let claude = registry_limits("anthropic/claude-sonnet-4-5").unwrap();
+        assert_eq!(claude.max_output_tokens, Some(64_000));
+        assert!(registry_limits("my-custom-model").is_none());
+    }
+
+    #[test]
+    fn test_limits_prefer_reported_values() {
+        let providers = serde_json::json!({ "ollama": { "models": [
+            { "id": "llama3.2:3b", "contextWindow": 8192 }
+        ] } });
+        let l = limits_for_model_id("ollama/llama3.2:3b", &providers).unwrap();
+        assert_eq!(l.context_window, 8192);
+        assert_eq!(l.source, "config");
+        let r = limits_for_model_id("ollama/qwen3:8b", &providers).unwrap();
+        assert_eq!(r.source, "registry");
+
+        let mut m = CatalogModel::local("ollama", "qwen3:8b");
+        assert_eq!(limits_for_catalog_model(&m).unwrap().context_window, 40_960);
+        m.context_length = Some(4096);
+        let reported = limits_for_catalog_model(&m).unwrap();
+        assert_eq!(reported.context_window, 4096);
+        assert_eq!(reported.source, "runtime");
+    }
+
+    #[test]
+    fn test_agent_defaults_limit_warnings() {
+        let root = serde_json::json!({
+            "agents": { "defaults": {
+                "contextTokens": 100000,
+                "model": { "primary": "anthropic/claude-sonnet-4-5", "fallbacks": ["ollama/qwen2.5:7b", "x/unknown"] }
+            } }
+        });
+        let w = agent_defaults_limit_warnings(&root);
+        assert_eq!(w.len(), 1);
+        assert_eq!(w[0].model, "ollama/qwen2.5:7b");
+        assert_eq!(w[0].supported, 32_768);
+        assert!(agent_defaults_limit_warnings(&serde_json::json!({})).is_empty());
+    }
+}
@@ -0,0 +1,266 @@
+//! Typo-tolerant search over the model catalog, ranked so the model picker can show the top hits
+//! as the user types. Every word of the query has to match a model's name, id, alias (from
+//! agents.defaults.models in openclaw.json), provider or tags (size tag, parameter size,
+//! quantization, runtime, kind) exactly, by prefix, by substring, within a small edit distance
+//! or as a subsequence; better matches on more important fields rank higher. Scoring is a pure
+//! function for unit tests.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::catalog::{CatalogModel, ModelKind};
+
+const DEFAULT_LIMIT: usize = 50;
+const PINNED_BONUS: u32 = 5;
+
+/// Weights out of 10 per field.
+const NAME_WEIGHT: u32 = 10;
+const ALIAS_WEIGHT: u32 = 10;
+const ID_WEIGHT: u32 = 9;
+const PROVIDER_WEIGHT: u32 = 6;
+const TAG_WEIGHT: u32 = 5;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ModelSearchResult {
+    pub model: CatalogModel,
+    /// The model's alias in openclaw.json, if any.
+    pub alias: Option<String>,
+    /// Higher is better; only meaningful relative to other results of the same query.
+    pub score: u32,
+}
+
+/// Aliases by model id from agents.defaults.models.
+#[must_use]
+pub fn aliases_from_root(root: &Value) -> HashMap<String, String> {
+    root.pointer("/agents/defaults/models")
+        .and_then(|m| m.as_object())
+        .map(|models| {
+            models
+                .iter()
+                .filter_map(|(id, entry)| {
+                    let alias = entry.get("alias")?.as_str()?.trim();
+                    (!alias.is_empty()).then(|| (id.clone(), alias.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Optimal string alignment distance: edits, with swapping two neighbours counting as one.
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut prev2 = vec![0; b.len() + 1];
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut cur = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            cur[j] = (prev[j] + 1).min(cur[j - 1] + 1).min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                cur[j] = cur[j].min(prev2[j - 2] + 1);
+            }
+        }
+        prev2 = std::mem::replace(&mut prev, cur);
+    }
+    prev[b.len()]
+}
+
+fn is_subsequence(token: &str, field: &str) -> bool {
+    let mut chars = field.chars();
+    token.chars().all(|c| chars.any(|f| f == c))
+}
+
+/// How well one lowercase query word matches one lowercase field, 0 to 100.
+fn token_score(token: &str, field: &str) -> u32 {
+    if field.is_empty() {
+        return 0;
+    }
+    if field == token {
+        return 100;
+    }
+    if field.starts_with(token) {
+        return 80;
+    }
+    let words = field
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty());
+    if words.clone().any(|w| w.starts_with(token)) {
+        return 70;
+    }
+    if field.contains(token) {
+        return 60;
+    }
+    let token_chars: Vec<char> = token.chars().collect();
+    let max_edits = match token_chars.len() {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    };
+    if max_edits > 0 {
+        let best = words
+            .filter_map(|w| {
+                let w: Vec<char> = w.chars().collect();
+                // Compare against the word's start too, so "lama" finds "llama3".
+                let prefix = &w[..w.len().min(token_chars.len() + max_edits)];
+                (w.len().abs_diff(token_chars.len()) <= max_edits
+                    || prefix.len() >= token_chars.len())
+                .then(|| edit_distance(&token_chars, &w).min(edit_distance(&token_chars, prefix)))
+            })
+            .min();
+        if let Some(d) = best.filter(|&d| d <= max_edits) {
+            return 50 - 10 * d as u32;
+        }
+    }
+    if token_chars.len() >= 3 && is_subsequence(token, field) {
+        return 20;
+    }
+    0
+}
+
+/// The searchable fields of a model with their weights, lowercased.
+fn fields(model: &CatalogModel, alias: Option<&str>) -> Vec<(String, u32)> {
+    let mut out = vec![
+        (model.name.to_lowercase(), NAME_WEIGHT),
+        (model.id.to_lowercase(), ID_WEIGHT),
+        (model.runtime.clone(), PROVIDER_WEIGHT),
+    ];
+    out.extend(alias.map(|a| (a.to_lowercase(), ALIAS_WEIGHT)));
+    out.extend(
+        model
+            .provider
+            .as_ref()
+            .map(|p| (p.to_lowercase(), PROVIDER_WEIGHT)),
+    );
+    let tags = [
+        model.name.split_once(':').map(|(_, tag)| tag.to_string()),
+        model.parameter_size.clone(),
+        model.quantization.clone(),
+        Some(match model.kind {
+            ModelKind::Chat => "chat".to_string(),
+            ModelKind::Embedding => "embedding".to_string(),
+        }),
+    ];
+    out.extend(
+        tags.into_iter()
+            .flatten()
+            .map(|t| (t.to_lowercase(), TAG_WEIGHT)),
+    );
+    out
+}
+
+/// Score of `model` for the query words; None unless every word matches some field.
+fn score_model(model: &CatalogModel, alias: Option<&str>, tokens: &[String]) -> Option<u32> {
+    let fields = fields(model, alias);
+    let mut total = 0;
+    for token in tokens {
+        let best = fields
+            .iter()
+            .map(|(field, weight)| token_score(token, field) * weight / 10)
+            .max()
+            .unwrap_or(0);
+        if best == 0 {
+            return None;
+        }
+        total += best;
+    }
+    Some(total + if model.pinned { PINNED_BONUS } else { 0 })
+}
+
+/// The models matching `query`, best first and at most `limit` (default 50). An empty query
+/// returns the models in their given order.
+#[must_use]
+pub fn search_models(
+    models: &[CatalogModel],
+    aliases: &HashMap<String, String>,
+    query: &str,
+    limit: Option<usize>,
+) -> Vec<ModelSearchResult> {
+    let tokens: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    let mut results: Vec<ModelSearchResult> = models
+        .iter()
+        .filter_map(|m| {
+            let alias = aliases.get(&m.id).map(String::as_str);
+            let score = if tokens.is_empty() {
+                0
+            } else {
+                score_model(m, alias, &tokens)?
+            };
+            Some(ModelSearchResult {
+                model: m.clone(),
+                alias: alias.map(String::from),
+                score,
+            })
+        })
+        .collect();
+    // Stable, so equal scores keep the catalog order (pinned first, then by name).
+    results.sort_by_key(|r| std::cmp::Reverse(r.score));
+    results.truncate(limit.unwrap_or(DEFAULT_LIMIT));
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::{RUNTIME_LM_STUDIO, RUNTIME_OLLAMA, RUNTIME_REMOTE};
+
+    fn catalog() -> Vec<CatalogModel> {
+        let mut llama = CatalogModel::local(RUNTIME_OLLAMA, "llama3.2:3b");
+        llama.quantization = Some("Q4_K_M".to_string());
+        let qwen = CatalogModel::local(RUNTIME_LM_STUDIO, "qwen2.5-coder-7b-instruct");
+        let embed = CatalogModel::local(RUNTIME_OLLAMA, "nomic-embed-text:latest");
+        let mut sonnet = CatalogModel::local(RUNTIME_REMOTE, "claude-sonnet-4-5");
+        sonnet.id = "anthropic/claude-sonnet-4-5".to_string();
+        sonnet.provider = Some("anthropic".to_string());
+        vec![llama, qwen, embed, sonnet]
+    }
+
+    fn names(results: &[ModelSearchResult]) -> Vec<&str> {
+        results.iter().map(|r| r.model.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_search_models() {
+        let models = catalog();
+        let root = serde_json::json!({ "agents": { "defaults": { "models": {
+            "anthropic/claude-sonnet-4-5": { "alias": "sonnet" }
+        } } } });
+        let aliases = aliases_from_root(&root);
+
+        assert_eq!(
+            names(&search_models(&models, &aliases, "qwen", None)),
+            vec!["qwen2.5-coder-7b-instruct"]
+        );
+        // Typos and partial words.
+        let typo = search_models(&models, &aliases, "lama", None);
+        assert_eq!(names(&typo)[0], "llama3.2:3b");
+        assert_eq!(
+            names(&search_models(&models, &aliases, "codr", None))[0],
+            "qwen2.5-coder-7b-instruct"
+        );
+        // Alias, provider and tags.
+        let alias = search_models(&models, &aliases, "sonet", None);
+        assert_eq!(alias[0].alias.as_deref(), Some("sonnet"));
+        assert_eq!(
+            names(&search_models(&models, &aliases, "anthropic", None)),
+            vec!["claude-sonnet-4-5"]
+        );
+        assert_eq!(
+            names(&search_models(&models, &aliases, "ollama q4", None)),
+            vec!["llama3.2:3b"]
+        );
+        assert_eq!(
+            names(&search_models(&models, &aliases, "embedding", None)),
+            vec!["nomic-embed-text:latest"]
+        );
+        assert!(search_models(&models, &aliases, "mistral", None).is_empty());
+        assert_eq!(search_models(&models, &aliases, "  ", Some(2)).len(), 2);
+    }
+
+    #[test]
+    fn test_token_score() {
+        assert_eq!(token_score("qwen3", "qwen3"), 100);
+        assert!(token_score("qwen", "qwen3:8b") > token_score("8b", "qwen3:8b"));
+        assert!(token_score("mistrl", "mistral") > 0);
+        assert_eq!(token_score("gpt", "qwen3"), 0);
+    }
+}
@@ -0,0 +1,216 @@
+//! Ollama Modelfile parsing and validation. The parsed form maps onto the fields of /api/create
+//! (`from`, `system`, `template`, `parameters`, `messages`, `license`), which no longer takes raw Modelfile text.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const INT_PARAMS: &[&str] = &[
+    "num_ctx",
+    "num_predict",
+    "num_keep",
+    "num_batch",
+    "num_gpu",
+    "num_thread",
+    "repeat_last_n",
+    "seed",
+    "top_k",
+    "mirostat",
+];
+const FLOAT_PARAMS: &[&str] = &[
+    "temperature",
+    "top_p",
+    "min_p",
+    "typical_p",
+    "repeat_penalty",
+    "presence_penalty",
+    "frequency_penalty",
+    "mirostat_eta",
+    "mirostat_tau",
+];
+const BOOL_PARAMS: &[&str] = &["penalize_newline", "use_mmap", "use_mlock", "numa"];
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ModelfileMessage {
+    pub role: String,
+    pub content: String,
+}
+
+/// A validated Modelfile.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct Modelfile {
+    /// Base model name (e.g. "llama3.2:3b") or a path to a local GGUF file.
+    pub from: String,
+    pub system: Option<String>,
+    pub template: Option<String>,
+    pub license: Option<String>,
+    /// Typed parameter values; `stop` may repeat and becomes an array.
+    pub parameters: BTreeMap<String, serde_json::Value>,
+    pub messages: Vec<ModelfileMessage>,
+}
+
+/// Splits the source into (line number, instruction, argument), joining `"""` blocks that span lines.
+fn instructions(text: &str) -> Result<Vec<(usize, String, String)>, String> {
+    let mut out = Vec::new();
+    let mut lines = text.lines().enumerate();
+    while let Some((idx, line)) = lines.next() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (keyword, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let mut arg = rest.trim().to_string();
+        if let Some(after) = arg.strip_prefix("\"\"\"") {
+            let mut block = after.to_string();
+            while !block.ends_with("\"\"\"") || block.len() < 3 {
+                match lines.next() {
+                    Some((_, next)) => {
+                        block.push('\n');
+                        block.push_str(next);
+                    }
+                    None => return Err(format!("line {}: unterminated \"\"\" block", idx + 1)),
+                }
+            }
+            block.truncate(block.len() - 3);
+            arg = block;
+        } else if arg.len() >= 2 && arg.starts_with('"') && arg.ends_with('"') {
+            arg = arg[1..arg.len() - 1].to_string();
+        }
+        out.push((idx + 1, keyword.to_uppercase(), arg));
+    }
+    Ok(out)
+}
+
+fn parse_parameter(line: usize, arg: &str) -> Result<(String, serde_json::Value), String> {
+    let (name, value) = arg
+        .split_once(char::is_whitespace)
+        .map(|(n, v)| (n.to_lowercase(), v.trim()))
+        .ok_or_else(|| format!("line {}: PARAMETER needs a name and a value", line))?;
+    let value = value.trim_matches('"');
+    let parsed = if INT_PARAMS.contains(&name.as_str()) {
+        value.parse::<i64>().map(serde_json::Value::from).ok()
+    } else if FLOAT_PARAMS.contains(&name.as_str()) {
+        value.parse::<f64>().map(serde_json::Value::from).ok()
+    } else if BOOL_PARAMS.contains(&name.as_str()) {
+        value.parse::<bool>().map(serde_json::Value::from).ok()
+    } else if name == "stop" {
+        Some(serde_json::Value::from(value))
+    } else {
+        return Err(format!("line {}: unknown parameter \"{}\"", line, name));
+    };
+    parsed
+        .map(|v| (name.clone(), v))
+        .ok_or_else(|| format!("line {}: invalid value for {}: {}", line, name, value))
+}
+
+/// Parses and validates Modelfile text. Errors name the offending line.
+pub fn parse_modelfile(text: &str) -> Result<Modelfile, String> {
+    let mut mf = Modelfile::default();
+    for (line, keyword, arg) in instructions(text)? {
+        match keyword.as_str() {
+            "FROM" => {
+                if arg.is_empty() {
+                    return Err(format!("line {}: FROM needs a model or file", line));
+                }
+                mf.from = arg;
+            }
+            "PARAMETER" => {
+                let (name, value) = parse_parameter(line, &arg)?;
+                if name == "stop" {
+                    let stops = mf
+                        .parameters
+                        .entry(name)
+                        .or_insert_with(|| serde_json::json!([]));
+                    if let Some(a) = stops.as_array_mut() {
+                        a.push(value);
+                    }
+                } else {
+                    mf.parameters.insert(name, value);
+                }
+            }
+            "SYSTEM" => mf.system = Some(arg),
+            "TEMPLATE" => mf.template = Some(arg),
+            "LICENSE" => mf.license = Some(arg),
+            "MESSAGE" => {
+                let (role, content) = arg
+                    .split_once(char::is_whitespace)
+                    .ok_or_else(|| format!("line {}: MESSAGE needs a role and content", line))?;
+                if !matches!(role, "system" | "user" | "assistant") {
+                    return Err(format!("line {}: invalid MESSAGE role \"{}\"", line, role));
+                }
+                mf.messages.push(ModelfileMessage {
+                    role: role.to_string(),
+                    content: content.trim().trim_matches('"').to_string(),
+                });
+            }
+            "ADAPTER" => {
+                return Err(format!("line {}: ADAPTER is not supported here", line));
+            }
+            other => return Err(format!("line {}: unknown instruction {}", line, other)),
+        }
+    }
+    if mf.from.is_empty() {
+        return Err("Modelfile must contain a FROM instruction".to_string());
+    }
+    Ok(mf)
+}
+
+/// True when FROM names a local file rather than an existing model.
+#[must_use]
+pub fn from_is_local_file(from: &str) -> bool {
+    from.to_lowercase().ends_with(".gguf")
+        || from.starts_with('/')
+        || from.starts_with("./")
+        || from.starts_with("~/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_modelfile() {
+        let text = r#"
+# coding assistant
+FROM llama3.2:3b
+PARAMETER temperature 0.2
+PARAMETER num_ctx 8192
+PARAMETER stop "<|eot_id|>"
+PARAMETER stop "User:"
+SYSTEM """You are a terse
+coding assistant."""
+MESSAGE user Hi
+"#;
+        let mf = parse_modelfile(text).unwrap();
+        assert_eq!(mf.from, "llama3.2:3b");
+        assert_eq!(mf.parameters["temperature"], serde_json::json!(0.2));
+        assert_eq!(mf.parameters["num_ctx"], serde_json::json!(8192));
+        assert_eq!(
+            mf.parameters["stop"],
+            serde_json::json!(["<|eot_id|>", "User:"])
+        );
+        assert_eq!(
+            mf.system.as_deref(),
+            Some("You are a terse\ncoding assistant.")
+        );
+        assert_eq!(mf.messages[0].role, "user");
+    }
+
+    #[test]
+    fn test_parse_modelfile_errors() {
+        assert!(parse_modelfile("SYSTEM hi").unwrap_err().contains("FROM"));
+        assert!(parse_modelfile("FROM x\nPARAMETER num_ctx lots")
+            .unwrap_err()
+            .contains("line 2"));
+        assert!(parse_modelfile("FROM x\nPARAMETER bogus 1").is_err());
+        assert!(parse_modelfile("FROM x\nFOO bar").is_err());
+        assert!(parse_modelfile("FROM x\nSYSTEM \"\"\"open").is_err());
+        assert!(parse_modelfile("FROM x\nMESSAGE robot hi").is_err());
+    }
+
+    #[test]
+    fn test_from_is_local_file() {
+        assert!(from_is_local_file("./model.gguf"));
+        assert!(from_is_local_file("/models/x.gguf"));
+        assert!(!from_is_local_file("llama3.2:3b"));
+    }
+}
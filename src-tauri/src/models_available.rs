@@ -1,76 +1,316 @@
-//! Fetch list of models available on each runtime (Ollama, LM Studio).
+//! Fetch list of models available on each runtime (Ollama, LM Studio, vLLM).
 //! Parsing is separated for unit tests.
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::process::Command;
 
-use crate::detection;
+use crate::catalog::{self, RUNTIME_VLLM};
+use crate::operations::{self, LIST_TIMEOUT};
+use crate::pagination::{self, Page};
+use crate::{lm_studio, ollama, openclaw_config, settings};
 
-const OLLAMA_TAGS_URL: &str = "http://127.0.0.1:11434/api/tags";
+pub const VLLM_DEFAULT_BASE_URL: &str = "http://127.0.0.1:8000";
 
-#[derive(Deserialize)]
-struct OllamaTagsResponse {
-    models: Option<Vec<OllamaModel>>,
+/// Ollama's model list, at the configured endpoint.
+#[must_use]
+pub fn ollama_tags_url() -> String {
+    format!("{}/api/tags", ollama::ollama_base_url())
 }
 
-#[derive(Deserialize)]
-struct OllamaModel {
-    name: Option<String>,
+/// Ollama's loaded models, at the configured endpoint.
+#[must_use]
+pub fn ollama_ps_url() -> String {
+    format!("{}/api/ps", ollama::ollama_base_url())
 }
 
-/// Parses Ollama /api/tags JSON and returns model names.
-/// Input is the raw response body.
+/// LM Studio's REST model list, at the configured endpoint.
 #[must_use]
-pub fn parse_ollama_tags_json(body: &str) -> Vec<String> {
-    let resp: OllamaTagsResponse = match serde_json::from_str(body) {
-        Ok(r) => r,
+pub fn lm_studio_api_models_url() -> String {
+    format!("{}/api/v0/models", lm_studio::lm_studio_base_url())
+}
+
+/// One model from Ollama /api/tags.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct OllamaModelInfo {
+    pub name: String,
+    pub size_bytes: Option<u64>,
+    pub digest: Option<String>,
+    /// e.g. "8.0B".
+    pub parameter_size: Option<String>,
+    /// e.g. "Q4_K_M".
+    pub quantization: Option<String>,
+    pub family: Option<String>,
+}
+
+fn json_str(value: Option<&serde_json::Value>) -> Option<String> {
+    value
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+}
+
+/// Parses Ollama /api/tags JSON into model details. Tolerates what differs between Ollama
+/// versions: `model` instead of `name`, no `details` object, and sizes sent as floats or
+/// strings. Entries without a name are skipped; anything else missing is None.
+#[must_use]
+pub fn parse_ollama_tags_models(body: &str) -> Vec<OllamaModelInfo> {
+    let root: serde_json::Value = match serde_json::from_str(body) {
+        Ok(v) => v,
         Err(_) => return vec![],
     };
-    let models = match resp.models {
-        Some(m) => m,
-        None => return vec![],
+    let Some(models) = root.get("models").and_then(|m| m.as_array()) else {
+        return vec![];
     };
     models
+        .iter()
+        .filter_map(|m| {
+            let name = json_str(m.get("name")).or_else(|| json_str(m.get("model")))?;
+            let size_bytes = m.get("size").and_then(|v| {
+                v.as_u64()
+                    .or_else(|| v.as_f64().filter(|f| *f >= 0.0).map(|f| f as u64))
+                    .or_else(|| v.as_str()?.parse().ok())
+            });
+            let details = m.get("details");
+            let detail = |key: &str| json_str(details.and_then(|d| d.get(key)));
+            Some(OllamaModelInfo {
+                name,
+                size_bytes,
+                digest: json_str(m.get("digest")),
+                parameter_size: detail("parameter_size"),
+                quantization: detail("quantization_level"),
+                family: detail("family"),
+            })
+        })
+        .collect()
+}
+
+/// Parses Ollama /api/tags JSON and returns model names.
+/// Input is the raw response body.
+#[must_use]
+pub fn parse_ollama_tags_json(body: &str) -> Vec<String> {
+    parse_ollama_tags_models(body)
         .into_iter()
-        .filter_map(|m| m.name)
-        .filter(|s| !s.is_empty())
+        .map(|m| m.name)
         .collect()
 }
 
+/// GET a local runtime endpoint with a short timeout. Returns None if unreachable or non-2xx.
+#[must_use]
+pub fn http_get_body(url: &str) -> Option<String> {
+    ureq::get(url)
+        .timeout(std::time::Duration::from_secs(2))
+        .call()
+        .ok()?
+        .into_string()
+        .ok()
+}
+
 /// Fetches model list from Ollama API. Returns empty vec if not running or request fails.
 #[must_use]
 pub fn get_ollama_models() -> Vec<String> {
-    let resp = ureq::get(OLLAMA_TAGS_URL)
-        .timeout(std::time::Duration::from_secs(2))
-        .call();
-    match resp {
-        Ok(r) => {
-            let body = r.into_string().unwrap_or_default();
-            parse_ollama_tags_json(&body)
+    http_get_body(&ollama_tags_url())
+        .map(|body| parse_ollama_tags_json(&body))
+        .unwrap_or_default()
+}
+
+/// Like `get_ollama_models`, with size, digest, parameter size and quantization.
+#[must_use]
+pub fn get_ollama_model_details() -> Vec<OllamaModelInfo> {
+    http_get_body(&ollama_tags_url())
+        .map(|body| parse_ollama_tags_models(&body))
+        .unwrap_or_default()
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OllamaModelSort {
+    #[default]
+    Name,
+    Size,
+}
+
+/// Paging, sorting and name filtering for `get_ollama_models_page`. All fields optional.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OllamaModelsQuery {
+    pub offset: usize,
+    /// All remaining models when None.
+    pub limit: Option<usize>,
+    pub sort_by: OllamaModelSort,
+    pub descending: bool,
+    /// Case-insensitive substring of the name.
+    pub search: Option<String>,
+}
+
+/// Filters, sorts and pages `models`; `total` counts the matches.
+#[must_use]
+pub fn page_ollama_models(
+    mut models: Vec<OllamaModelInfo>,
+    query: &OllamaModelsQuery,
+) -> Page<OllamaModelInfo> {
+    if let Some(search) = query
+        .search
+        .as_deref()
+        .map(|s| s.trim().to_lowercase())
+        .filter(|s| !s.is_empty())
+    {
+        models.retain(|m| m.name.to_lowercase().contains(&search));
+    }
+    models.sort_by(|a, b| {
+        let ord = match query.sort_by {
+            OllamaModelSort::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            OllamaModelSort::Size => a.size_bytes.cmp(&b.size_bytes),
+        };
+        if query.descending {
+            ord.reverse()
+        } else {
+            ord
+        }
+    });
+    pagination::paginate(models, query.offset, query.limit)
+}
+
+/// One page of the installed Ollama models.
+#[must_use]
+pub fn get_ollama_models_page(query: &OllamaModelsQuery) -> Page<OllamaModelInfo> {
+    page_ollama_models(get_ollama_model_details(), query)
+}
+
+/// One row of the `lms ls` table.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct LmStudioLsModel {
+    /// The id `lms load` takes, without the "(2 variants)" suffix.
+    pub name: String,
+    /// Listed under the embedding models section.
+    pub embedding: bool,
+    /// e.g. "7B"; absent on LM Studio 0.2 and for most embedding models.
+    pub params: Option<String>,
+    pub arch: Option<String>,
+    pub size_bytes: Option<u64>,
+    pub loaded: bool,
+}
+
+/// Column titles `lms ls` has used; the name column is untitled or a section label
+/// ("LLMs (Large Language Models)", "LLM", "EMBEDDING").
+const LMS_LS_COLUMNS: [&str; 6] = ["PARAMS", "ARCHITECTURE", "ARCH", "SIZE", "DEVICE", "STATE"];
+
+/// "4.68 GB" → bytes (binary units, as LM Studio reports them).
+fn parse_lms_size(text: &str) -> Option<u64> {
+    let (num, unit) = text.trim().split_once(' ')?;
+    let num: f64 = num.parse().ok()?;
+    let scale: u64 = match unit.trim().to_uppercase().as_str() {
+        "B" => 1,
+        "KB" | "KIB" => 1 << 10,
+        "MB" | "MIB" => 1 << 20,
+        "GB" | "GIB" => 1 << 30,
+        "TB" | "TIB" => 1 << 40,
+        _ => return None,
+    };
+    Some((num * scale as f64) as u64)
+}
+
+/// Column titles in a header line with their char offsets, or None if it isn't a header.
+fn lms_ls_header(line: &str) -> Option<Vec<(&'static str, usize)>> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut columns = Vec::new();
+    let mut start = None;
+    for i in 0..=chars.len() {
+        let boundary = chars.get(i).is_none_or(|c| c.is_whitespace());
+        match (start, boundary) {
+            (None, false) => start = Some(i),
+            (Some(s), true) => {
+                let word: String = chars[s..i].iter().collect();
+                if let Some(title) = LMS_LS_COLUMNS.iter().find(|t| **t == word) {
+                    columns.push((*title, s));
+                }
+                start = None;
+            }
+            _ => {}
         }
-        Err(_) => vec![],
     }
+    columns.iter().any(|(t, _)| *t == "SIZE").then_some(columns)
+}
+
+/// The text of a cell: from `start` up to the first run of two spaces.
+fn lms_ls_cell(chars: &[char], start: usize) -> Option<String> {
+    let rest: String = chars.get(start..)?.iter().collect();
+    let cell = rest.trim_start();
+    let cell = cell.split("  ").next().unwrap_or_default().trim();
+    (!cell.is_empty()).then(|| cell.to_string())
 }
 
-/// Parses `lms ls` output: one model name per line (or tab-separated).
-/// Blank lines and whitespace-only lines are skipped.
+/// Parses `lms ls` output. Current versions print a table per section (LLMs, then embedding
+/// models) whose columns are read by the header's title offsets; a "✓ LOADED" marker may
+/// trail a row. Output without a table header is read as one model per line, named by its first
+/// tab-separated cell.
 #[must_use]
-pub fn parse_lm_studio_ls_output(stdout: &str) -> Vec<String> {
-    stdout
-        .lines()
-        .flat_map(|line| line.split('\t'))
-        .map(str::trim)
-        .filter(|s| !s.is_empty())
-        .map(String::from)
-        .collect()
+pub fn parse_lm_studio_ls_output(stdout: &str) -> Vec<LmStudioLsModel> {
+    let mut models = Vec::new();
+    let mut section: Option<(bool, Vec<(&str, usize)>)> = None;
+    let mut saw_header = false;
+    for line in stdout.lines() {
+        if line.trim().is_empty() {
+            section = None;
+            continue;
+        }
+        if let Some(columns) = lms_ls_header(line) {
+            saw_header = true;
+            let embedding = line.trim_start().to_uppercase().starts_with("EMBEDDING");
+            section = Some((embedding, columns));
+            continue;
+        }
+        let Some((embedding, columns)) = &section else {
+            continue;
+        };
+        let chars: Vec<char> = line.chars().collect();
+        let name = line.trim().split("  ").next().unwrap_or_default();
+        let name = match name.rfind(" (") {
+            Some(i) if name.ends_with("variant)") || name.ends_with("variants)") => &name[..i],
+            _ => name,
+        };
+        // A cell counts only if it starts under its title, so empty cells aren't filled from
+        // the next column.
+        let starts_at = |at: usize| {
+            let blank = |i: usize| chars.get(i).is_none_or(|c| c.is_whitespace());
+            !blank(at) && (at == 0 || blank(at - 1))
+        };
+        let cell = |title: &str| {
+            columns
+                .iter()
+                .filter(|(t, _)| *t == title)
+                .find_map(|(_, at)| starts_at(*at).then(|| lms_ls_cell(&chars, *at)).flatten())
+        };
+        models.push(LmStudioLsModel {
+            name: name.trim().to_string(),
+            embedding: *embedding,
+            params: cell("PARAMS"),
+            arch: cell("ARCHITECTURE").or_else(|| cell("ARCH")),
+            size_bytes: cell("SIZE").and_then(|s| parse_lms_size(&s)),
+            loaded: line.contains("LOADED"),
+        });
+    }
+    if !saw_header {
+        models = stdout
+            .lines()
+            .filter_map(|l| l.split('\t').next())
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with("You have "))
+            .map(|name| LmStudioLsModel {
+                name: name.to_string(),
+                ..Default::default()
+            })
+            .collect();
+    }
+    models
 }
 
-/// Returns model names from LM Studio CLI (`lms ls`).
-/// Requires LM Studio CLI in PATH or at ~/.lmstudio/bin/lms.
+/// Returns the models LM Studio CLI (`lms ls`) lists.
+/// Requires LM Studio CLI in PATH or at ~/.lmstudio/bin/lms; empty until it is bootstrapped
+/// (see `lm_studio::get_lms_cli_status`).
 #[must_use]
-pub fn get_lm_studio_models() -> Vec<String> {
-    let cmd = detection::lms_path().unwrap_or_else(|| "lms".to_string());
-    let out = Command::new(&cmd).args(["ls"]).output().ok();
+pub fn get_lm_studio_ls_models() -> Vec<LmStudioLsModel> {
+    let cmd = lm_studio::lms_command();
+    let out = operations::run_command(Command::new(&cmd).args(["ls"]), LIST_TIMEOUT, None).ok();
     let output = match out {
         Some(o) if o.status.success() => String::from_utf8_lossy(&o.stdout).to_string(),
         _ => return vec![],
@@ -78,6 +318,72 @@ pub fn get_lm_studio_models() -> Vec<String> {
     parse_lm_studio_ls_output(&output)
 }
 
+/// Returns model names from LM Studio CLI (`lms ls`).
+#[must_use]
+pub fn get_lm_studio_models() -> Vec<String> {
+    get_lm_studio_ls_models()
+        .into_iter()
+        .map(|m| m.name)
+        .collect()
+}
+
+/// Parses an OpenAI-style /v1/models response and returns the served model ids.
+#[must_use]
+pub fn parse_openai_models_json(body: &str) -> Vec<String> {
+    let root: serde_json::Value = match serde_json::from_str(body) {
+        Ok(v) => v,
+        Err(_) => return vec![],
+    };
+    root.get("data")
+        .and_then(|d| d.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|m| m.get("id").and_then(|v| v.as_str()))
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Builds the /v1/models URL from a base URL given with or without the /v1 suffix.
+#[must_use]
+pub fn vllm_models_url(base_url: &str) -> String {
+    let base = base_url.trim().trim_end_matches('/');
+    let base = base.strip_suffix("/v1").unwrap_or(base);
+    format!("{}/v1/models", base)
+}
+
+/// Base URL of the vLLM server: the baseUrl of the openclaw provider pointing at vLLM, else the
+/// `detection.vllm_url` setting, else the default port.
+#[must_use]
+pub fn configured_vllm_base_url() -> String {
+    openclaw_config::get_openclaw_providers_raw()
+        .ok()
+        .and_then(|providers| {
+            let name = catalog::provider_for_runtime(RUNTIME_VLLM, &providers)?;
+            providers
+                .get(&name)?
+                .get("baseUrl")?
+                .as_str()
+                .map(String::from)
+        })
+        .or_else(|| settings::runtime_url("vllm"))
+        .unwrap_or_else(|| VLLM_DEFAULT_BASE_URL.to_string())
+}
+
+/// Fetches served model ids from a vLLM server. Uses the configured base URL when `base_url` is None.
+/// Returns empty vec if not running or request fails.
+#[must_use]
+pub fn get_vllm_models(base_url: Option<&str>) -> Vec<String> {
+    let base = base_url
+        .map(String::from)
+        .unwrap_or_else(configured_vllm_base_url);
+    http_get_body(&vllm_models_url(&base))
+        .map(|body| parse_openai_models_json(&body))
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -99,17 +405,179 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_lm_studio_ls_output() {
-        let out = "model-a\nmodel-b\nmodel-c";
+    fn test_parse_ollama_tags_models() {
+        let json = r#"{"models":[
+            {"name":"qwen3:8b","model":"qwen3:8b","size":5225388164,"digest":"500a1f06",
+             "details":{"family":"qwen3","parameter_size":"8.2B","quantization_level":"Q4_K_M"}},
+            {"model":"llama2:latest","size":"3825819519"},
+            {"name":"old:7b","size":3.8e9,"details":null},
+            {"size":1}
+        ]}"#;
+        let models = parse_ollama_tags_models(json);
         assert_eq!(
-            parse_lm_studio_ls_output(out),
-            ["model-a", "model-b", "model-c"]
+            models[0],
+            OllamaModelInfo {
+                name: "qwen3:8b".to_string(),
+                size_bytes: Some(5_225_388_164),
+                digest: Some("500a1f06".to_string()),
+                parameter_size: Some("8.2B".to_string()),
+                quantization: Some("Q4_K_M".to_string()),
+                family: Some("qwen3".to_string()),
+            }
+        );
+        assert_eq!(models[1].name, "llama2:latest");
+        assert_eq!(models[1].size_bytes, Some(3_825_819_519));
+        assert_eq!(models[2].size_bytes, Some(3_800_000_000));
+        assert_eq!(models[2].parameter_size, None);
+        assert_eq!(models.len(), 3);
+    }
+
+    fn ls_names(out: &str) -> Vec<String> {
+        parse_lm_studio_ls_output(out)
+            .into_iter()
+            .map(|m| m.name)
+            .collect()
+    }
+
+    #[test]
+    fn test_page_ollama_models() {
+        let models: Vec<OllamaModelInfo> = [("qwen3:8b", 5), ("llama3.2:3b", 2), ("qwen3:32b", 20)]
+            .iter()
+            .map(|(name, size)| OllamaModelInfo {
+                name: name.to_string(),
+                size_bytes: Some(*size),
+                ..Default::default()
+            })
+            .collect();
+        let page = page_ollama_models(
+            models.clone(),
+            &OllamaModelsQuery {
+                limit: Some(2),
+                ..Default::default()
+            },
+        );
+        assert_eq!(page.total, 3);
+        assert_eq!(page.items[0].name, "llama3.2:3b");
+        assert_eq!(page.items.len(), 2);
+        let qwen = page_ollama_models(
+            models,
+            &OllamaModelsQuery {
+                offset: 1,
+                sort_by: OllamaModelSort::Size,
+                descending: true,
+                search: Some("QWEN".to_string()),
+                ..Default::default()
+            },
         );
+        assert_eq!(qwen.total, 2);
+        assert_eq!(qwen.items.len(), 1);
+        assert_eq!(qwen.items[0].name, "qwen3:8b");
+    }
+
+    #[test]
+    fn test_parse_lm_studio_ls_output() {
+        let out = "model-a\nmodel-b\nmodel-c";
+        assert_eq!(ls_names(out), ["model-a", "model-b", "model-c"]);
 
         let with_blanks = "a\n\nb\n  \nc";
-        assert_eq!(parse_lm_studio_ls_output(with_blanks), ["a", "b", "c"]);
+        assert_eq!(ls_names(with_blanks), ["a", "b", "c"]);
+
+        let tabs = "model-a\t4.6 GB\nmodel-b\t7B\tLlama\n";
+        assert_eq!(ls_names(tabs), ["model-a", "model-b"]);
 
         let empty = "";
         assert!(parse_lm_studio_ls_output(empty).is_empty());
     }
+
+    #[test]
+    fn test_parse_lm_studio_ls_table_0_2() {
+        let models = parse_lm_studio_ls_output(include_str!("../tests/fixtures/lms_ls_0.2.txt"));
+        assert_eq!(models.len(), 3);
+        assert_eq!(
+            models[0].name,
+            "lmstudio-community/Meta-Llama-3-8B-Instruct-GGUF/Meta-Llama-3-8B-Instruct-Q4_K_M.gguf"
+        );
+        assert_eq!(models[0].arch.as_deref(), Some("Llama"));
+        assert_eq!(models[0].params, None);
+        assert_eq!(
+            models[1].size_bytes,
+            Some((4.37 * (1u64 << 30) as f64) as u64)
+        );
+        assert!(models[2].embedding && !models[0].embedding);
+        assert_eq!(models[2].arch.as_deref(), Some("Nomic BERT"));
+    }
+
+    #[test]
+    fn test_parse_lm_studio_ls_table_0_3() {
+        let models = parse_lm_studio_ls_output(include_str!("../tests/fixtures/lms_ls_0.3.txt"));
+        assert_eq!(
+            models,
+            vec![
+                LmStudioLsModel {
+                    name: "qwen2.5-7b-instruct".to_string(),
+                    embedding: false,
+                    params: Some("7B".to_string()),
+                    arch: Some("Qwen2".to_string()),
+                    size_bytes: Some((4.68 * (1u64 << 30) as f64) as u64),
+                    loaded: false,
+                },
+                LmStudioLsModel {
+                    name: "meta-llama-3.1-8b-instruct".to_string(),
+                    embedding: false,
+                    params: Some("8B".to_string()),
+                    arch: Some("Llama".to_string()),
+                    size_bytes: Some((4.92 * (1u64 << 30) as f64) as u64),
+                    loaded: true,
+                },
+                LmStudioLsModel {
+                    name: "text-embedding-nomic-embed-text-v1.5".to_string(),
+                    embedding: true,
+                    params: None,
+                    arch: Some("Nomic BERT".to_string()),
+                    size_bytes: Some((84.11 * (1u64 << 20) as f64) as u64),
+                    loaded: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_lm_studio_ls_table_0_3_15() {
+        let models = parse_lm_studio_ls_output(include_str!("../tests/fixtures/lms_ls_0.3.15.txt"));
+        let names: Vec<&str> = models.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "qwen/qwen3-8b",
+                "google/gemma-3-12b",
+                "text-embedding-nomic-embed-text-v1.5"
+            ]
+        );
+        assert_eq!(models[1].params.as_deref(), Some("12B"));
+        assert_eq!(models[1].arch.as_deref(), Some("gemma3"));
+        assert!(models[1].loaded && !models[0].loaded);
+        assert!(models[2].embedding);
+        assert_eq!(models[2].params, None);
+        assert_eq!(models[2].arch.as_deref(), Some("Nomic BERT"));
+    }
+
+    #[test]
+    fn test_parse_openai_models_json() {
+        let json = r#"{"object":"list","data":[{"id":"Qwen/Qwen2.5-7B-Instruct","object":"model"},{"id":""}]}"#;
+        assert_eq!(parse_openai_models_json(json), ["Qwen/Qwen2.5-7B-Instruct"]);
+        assert!(parse_openai_models_json("{}").is_empty());
+        assert!(parse_openai_models_json("not json").is_empty());
+    }
+
+    #[test]
+    fn test_vllm_models_url() {
+        assert_eq!(
+            vllm_models_url("http://10.0.0.5:8001/v1/"),
+            "http://10.0.0.5:8001/v1/models"
+        );
+        assert_eq!(
+            vllm_models_url(VLLM_DEFAULT_BASE_URL),
+            "http://127.0.0.1:8000/v1/models"
+        );
+    }
 }
@@ -0,0 +1,234 @@
+//! Desktop notifications for events worth knowing about with the window closed: the gateway
+//! going down without being stopped, a provider rejecting its key, low disk space, model pulls
+//! finishing, agents drifting out of sync with openclaw.json, the gateway's TLS certificate
+//! nearing expiry, and spend limits being hit. Which kinds are sent is configurable in
+//! ~/.openclaw/host-config/notifications.json; repeats of one kind are throttled.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri_plugin_notification::NotificationExt;
+
+use crate::openclaw_config;
+use crate::operations;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    GatewayCrashed,
+    ProviderKeyInvalid,
+    DiskLow,
+    ModelPullFinished,
+    SyncDrift,
+    CertExpiring,
+    SpendLimit,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct NotificationRules {
+    /// Master switch.
+    pub enabled: bool,
+    pub gateway_crashed: bool,
+    pub provider_key_invalid: bool,
+    pub disk_low: bool,
+    pub model_pull_finished: bool,
+    pub sync_drift: bool,
+    pub cert_expiring: bool,
+    pub spend_limit: bool,
+    /// Minimum seconds between two notifications of the same kind.
+    pub min_interval_secs: u64,
+}
+
+impl Default for NotificationRules {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            gateway_crashed: true,
+            provider_key_invalid: true,
+            disk_low: true,
+            model_pull_finished: true,
+            sync_drift: true,
+            cert_expiring: true,
+            spend_limit: true,
+            min_interval_secs: 300,
+        }
+    }
+}
+
+impl NotificationRules {
+    fn allows(&self, kind: NotificationKind) -> bool {
+        self.enabled
+            && match kind {
+                NotificationKind::GatewayCrashed => self.gateway_crashed,
+                NotificationKind::ProviderKeyInvalid => self.provider_key_invalid,
+                NotificationKind::DiskLow => self.disk_low,
+                NotificationKind::ModelPullFinished => self.model_pull_finished,
+                NotificationKind::SyncDrift => self.sync_drift,
+                NotificationKind::CertExpiring => self.cert_expiring,
+                NotificationKind::SpendLimit => self.spend_limit,
+            }
+    }
+}
+
+fn rules_path() -> PathBuf {
+    openclaw_config::host_config_dir().join("notifications.json")
+}
+
+/// Stored rules, or the defaults (everything on) when none are saved.
+#[must_use]
+pub fn get_notification_rules() -> NotificationRules {
+    fs::read_to_string(rules_path())
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+pub fn set_notification_rules(rules: &NotificationRules) -> Result<(), String> {
+    let path = rules_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    fs::write(
+        &path,
+        serde_json::to_string_pretty(rules).map_err(|e| e.to_string())?,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Whether a notification of `kind` goes out now, given when that kind was last sent.
+#[must_use]
+pub fn should_notify(
+    rules: &NotificationRules,
+    kind: NotificationKind,
+    last_sent_secs: Option<u64>,
+    now_secs: u64,
+) -> bool {
+    rules.allows(kind)
+        && last_sent_secs.is_none_or(|t| now_secs.saturating_sub(t) >= rules.min_interval_secs)
+}
+
+/// A running gateway that is no longer running, without a stop from this app, has crashed.
+#[must_use]
+pub fn gateway_crashed(was_running: Option<bool>, running: bool, stop_requested: bool) -> bool {
+    was_running == Some(true) && !running && !stop_requested
+}
+
+/// Managed state: when each kind was last sent and the last gateway status seen.
+#[derive(Default)]
+pub struct Notifier {
+    last_sent: Mutex<HashMap<NotificationKind, u64>>,
+    gateway_running: Mutex<Option<bool>>,
+    gateway_stop_requested: AtomicBool,
+}
+
+impl Notifier {
+    /// Shows a desktop notification if the rules allow it. Returns whether one was shown.
+    pub fn notify(
+        &self,
+        app: &tauri::AppHandle,
+        kind: NotificationKind,
+        title: &str,
+        body: &str,
+    ) -> bool {
+        let now = operations::now_secs();
+        let mut last_sent = self.last_sent.lock().unwrap_or_else(|e| e.into_inner());
+        if !should_notify(
+            &get_notification_rules(),
+            kind,
+            last_sent.get(&kind).copied(),
+            now,
+        ) {
+            return false;
+        }
+        let shown = app
+            .notification()
+            .builder()
+            .title(title)
+            .body(body)
+            .show()
+            .is_ok();
+        if shown {
+            last_sent.insert(kind, now);
+        }
+        shown
+    }
+
+    /// Called before this app stops the gateway, so the next "not running" isn't a crash.
+    pub fn expect_gateway_stop(&self) {
+        self.gateway_stop_requested.store(true, Ordering::SeqCst);
+    }
+
+    /// Records a gateway status check and notifies when the gateway went down unexpectedly.
+    pub fn record_gateway_status(&self, app: &tauri::AppHandle, running: bool) {
+        let was_running = self
+            .gateway_running
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .replace(running);
+        let stop_requested = if running {
+            self.gateway_stop_requested.store(false, Ordering::SeqCst);
+            false
+        } else {
+            self.gateway_stop_requested.swap(false, Ordering::SeqCst)
+        };
+        if gateway_crashed(was_running, running, stop_requested) {
+            self.notify(
+                app,
+                NotificationKind::GatewayCrashed,
+                "OpenClaw gateway stopped",
+                "The gateway is no longer running. Open OpenClaw Config to restart it.",
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_notify() {
+        let rules = NotificationRules::default();
+        assert!(should_notify(&rules, NotificationKind::DiskLow, None, 1000));
+        assert!(!should_notify(
+            &rules,
+            NotificationKind::DiskLow,
+            Some(900),
+            1000
+        ));
+        assert!(should_notify(
+            &rules,
+            NotificationKind::DiskLow,
+            Some(700),
+            1000
+        ));
+        let quiet = NotificationRules {
+            model_pull_finished: false,
+            ..Default::default()
+        };
+        assert!(!should_notify(
+            &quiet,
+            NotificationKind::ModelPullFinished,
+            None,
+            1000
+        ));
+        let off = NotificationRules {
+            enabled: false,
+            ..Default::default()
+        };
+        assert!(!should_notify(&off, NotificationKind::DiskLow, None, 1000));
+    }
+
+    #[test]
+    fn test_gateway_crashed() {
+        assert!(gateway_crashed(Some(true), false, false));
+        assert!(!gateway_crashed(Some(true), false, true));
+        assert!(!gateway_crashed(None, false, false));
+        assert!(!gateway_crashed(Some(false), false, false));
+        assert!(!gateway_crashed(Some(true), true, false));
+    }
+}
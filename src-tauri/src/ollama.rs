@@ -0,0 +1,359 @@
+//! Ollama REST API control (blobs, create from GGUF or Modelfile, copy, pull) with streamed progress.
+//! Status-line parsing is separated for unit tests.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::modelfile::{self, Modelfile};
+use crate::operations::CancelToken;
+use crate::settings;
+
+pub const OLLAMA_BASE_URL: &str = "http://127.0.0.1:11434";
+
+/// The Ollama API root: the `detection.ollama_url` setting, else `OLLAMA_BASE_URL`.
+#[must_use]
+pub fn ollama_base_url() -> String {
+    settings::runtime_url("ollama").unwrap_or_else(|| OLLAMA_BASE_URL.to_string())
+}
+
+/// Progress update for long model operations (download, upload, create, pull).
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ModelTransferProgress {
+    /// Target model name (e.g. "qwen2.5-7b-instruct:q4_k_m").
+    pub model: String,
+    /// "download", "upload", "create", "pull", ...
+    pub stage: String,
+    /// Human-readable status from Ollama or the app.
+    pub status: String,
+    pub completed: Option<u64>,
+    pub total: Option<u64>,
+    pub percent: Option<f32>,
+    pub done: bool,
+}
+
+impl ModelTransferProgress {
+    #[must_use]
+    pub fn new(model: &str, stage: &str, status: impl Into<String>) -> Self {
+        Self {
+            model: model.to_string(),
+            stage: stage.to_string(),
+            status: status.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets completed/total and derives percent.
+    #[must_use]
+    pub fn with_bytes(mut self, completed: u64, total: Option<u64>) -> Self {
+        self.completed = Some(completed);
+        self.total = total;
+        self.percent = total
+            .filter(|t| *t > 0)
+            .map(|t| (completed as f64 / t as f64 * 100.0).min(100.0) as f32);
+        self
+    }
+}
+
+/// One NDJSON line from a streaming Ollama endpoint (/api/create, /api/pull).
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct OllamaStatusLine {
+    #[serde(default)]
+    pub status: String,
+    pub digest: Option<String>,
+    pub total: Option<u64>,
+    pub completed: Option<u64>,
+    pub error: Option<String>,
+}
+
+/// Parses a streamed status line; blank or invalid lines yield None.
+#[must_use]
+pub fn parse_status_line(line: &str) -> Option<OllamaStatusLine> {
+    let line = line.trim();
+    if line.is_empty() {
+        return None;
+    }
+    serde_json::from_str(line).ok()
+}
+
+fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .timeout_connect(Duration::from_secs(5))
+        .timeout_read(Duration::from_secs(300))
+        .build()
+}
+
+/// POSTs to a streaming endpoint and forwards each status line. Fails on an `error` line or HTTP error.
+/// Cancelling drops the connection at the next line, which makes Ollama abort the request.
+pub fn post_streaming(
+    path: &str,
+    body: serde_json::Value,
+    cancel: &CancelToken,
+    mut on_line: impl FnMut(OllamaStatusLine),
+) -> Result<(), String> {
+    let resp = agent()
+        .post(&format!("{}{}", ollama_base_url(), path))
+        .send_json(body)
+        .map_err(|e| format!("Ollama request failed: {}", e))?;
+    let reader = BufReader::new(resp.into_reader());
+    for line in reader.lines() {
+        cancel.check()?;
+        let line = line.map_err(|e| e.to_string())?;
+        if let Some(status) = parse_status_line(&line) {
+            if let Some(err) = status.error {
+                return Err(err);
+            }
+            on_line(status);
+        }
+    }
+    Ok(())
+}
+
+/// Uploads a local file as an Ollama blob (skipped when the server already has the digest).
+/// `digest` is "sha256:<hex>".
+pub fn push_blob(path: &Path, digest: &str) -> Result<(), String> {
+    let url = format!("{}/api/blobs/{}", ollama_base_url(), digest);
+    if agent().head(&url).call().is_ok() {
+        return Ok(());
+    }
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+    agent()
+        .post(&url)
+        .set("Content-Length", &len.to_string())
+        .send(file)
+        .map_err(|e| format!("Failed to upload blob: {}", e))?;
+    Ok(())
+}
+
+/// Creates a model from an uploaded GGUF blob via /api/create, streaming status.
+pub fn create_model_from_blob(
+    model: &str,
+    file_name: &str,
+    digest: &str,
+    cancel: &CancelToken,
+    mut on_progress: impl FnMut(ModelTransferProgress),
+) -> Result<(), String> {
+    let body = serde_json::json!({
+        "model": model,
+        "files": { file_name: digest },
+        "stream": true,
+    });
+    post_streaming("/api/create", body, cancel, |line| {
+        let mut p = ModelTransferProgress::new(model, "create", line.status.clone());
+        if let Some(c) = line.completed {
+            p = p.with_bytes(c, line.total);
+        }
+        p.done = line.status == "success";
+        on_progress(p);
+    })
+}
+
+/// Computes an Ollama blob digest ("sha256:<hex>") for a local file.
+pub fn file_digest(path: &Path) -> Result<String, String> {
+    let mut file = File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 256 * 1024];
+    loop {
+        let n = file.read(&mut buf).map_err(|e| e.to_string())?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    let hex: String = hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    Ok(format!("sha256:{}", hex))
+}
+
+/// Checks a model name Ollama will accept: "name" or "name:tag", optionally with a namespace.
+pub fn validate_model_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && name.len() <= 200
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':' | '/'))
+        && name.matches(':').count() <= 1
+        && !name.starts_with(['-', '.', ':', '/'])
+        && !name.ends_with([':', '/']);
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid model name: \"{}\"", name))
+    }
+}
+
+/// Request body for /api/create from a parsed Modelfile. `files` is set when FROM was a local GGUF.
+#[must_use]
+pub fn create_request_body(
+    model: &str,
+    mf: &Modelfile,
+    files: Option<(&str, &str)>,
+) -> serde_json::Value {
+    let mut body = serde_json::json!({ "model": model, "stream": true });
+    match files {
+        Some((file_name, digest)) => body["files"] = serde_json::json!({ file_name: digest }),
+        None => body["from"] = serde_json::json!(mf.from),
+    }
+    if let Some(s) = &mf.system {
+        body["system"] = serde_json::json!(s);
+    }
+    if let Some(t) = &mf.template {
+        body["template"] = serde_json::json!(t);
+    }
+    if let Some(l) = &mf.license {
+        body["license"] = serde_json::json!(l);
+    }
+    if !mf.parameters.is_empty() {
+        body["parameters"] = serde_json::json!(mf.parameters);
+    }
+    if !mf.messages.is_empty() {
+        body["messages"] = serde_json::json!(mf.messages);
+    }
+    body
+}
+
+/// Validates `modelfile_text` and creates `model` via /api/create, streaming status.
+/// A FROM pointing at a local GGUF file is uploaded as a blob first.
+pub fn create_model_from_modelfile(
+    model: &str,
+    modelfile_text: &str,
+    cancel: &CancelToken,
+    mut on_progress: impl FnMut(ModelTransferProgress),
+) -> Result<(), String> {
+    validate_model_name(model)?;
+    let mf = modelfile::parse_modelfile(modelfile_text)?;
+
+    let local = if modelfile::from_is_local_file(&mf.from) {
+        let path = match mf.from.strip_prefix("~/") {
+            Some(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+            None => PathBuf::from(&mf.from),
+        };
+        if !path.is_absolute() {
+            return Err("FROM must be an absolute path when it names a local file".to_string());
+        }
+        on_progress(ModelTransferProgress::new(
+            model,
+            "upload",
+            "uploading to Ollama",
+        ));
+        let digest = file_digest(&path)?;
+        push_blob(&path, &digest)?;
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .ok_or("Invalid file name")?;
+        Some((file_name, digest))
+    } else {
+        None
+    };
+
+    let body = create_request_body(
+        model,
+        &mf,
+        local.as_ref().map(|(f, d)| (f.as_str(), d.as_str())),
+    );
+    post_streaming("/api/create", body, cancel, |line| {
+        let mut p = ModelTransferProgress::new(model, "create", line.status.clone());
+        if let Some(c) = line.completed {
+            p = p.with_bytes(c, line.total);
+        }
+        p.done = line.status == "success";
+        on_progress(p);
+    })
+}
+
+/// Pulls `model` from the Ollama library via /api/pull, streaming per-layer progress.
+pub fn pull_model(
+    model: &str,
+    cancel: &CancelToken,
+    mut on_progress: impl FnMut(ModelTransferProgress),
+) -> Result<(), String> {
+    validate_model_name(model)?;
+    let body = serde_json::json!({ "model": model, "stream": true });
+    post_streaming("/api/pull", body, cancel, |line| {
+        let mut p = ModelTransferProgress::new(model, "pull", line.status.clone());
+        if let Some(c) = line.completed {
+            p = p.with_bytes(c, line.total);
+        }
+        p.done = line.status == "success";
+        on_progress(p);
+    })
+}
+
+/// Copies `source` to `destination` via /api/copy (e.g. snapshot "llama3.2:3b" as "llama3.2:3b-prev"
+/// before pulling an update). Both tags share blobs, so this costs no extra disk.
+pub fn copy_model(source: &str, destination: &str) -> Result<(), String> {
+    validate_model_name(destination)?;
+    if source == destination {
+        return Err("Source and destination are the same".to_string());
+    }
+    agent()
+        .post(&format!("{}/api/copy", ollama_base_url()))
+        .send_json(serde_json::json!({ "source": source, "destination": destination }))
+        .map_err(|e| match e {
+            ureq::Error::Status(404, _) => format!("Model not found: {}", source),
+            other => format!("Ollama request failed: {}", other),
+        })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_status_line() {
+        let l = parse_status_line(
+            r#"{"status":"pulling abc","digest":"sha256:abc","total":100,"completed":50}"#,
+        )
+        .unwrap();
+        assert_eq!(l.status, "pulling abc");
+        assert_eq!(l.total, Some(100));
+        assert_eq!(l.completed, Some(50));
+        let err = parse_status_line(r#"{"error":"model not found"}"#).unwrap();
+        assert_eq!(err.error.as_deref(), Some("model not found"));
+        assert!(parse_status_line("   ").is_none());
+        assert!(parse_status_line("not json").is_none());
+    }
+
+    #[test]
+    fn test_validate_model_name() {
+        assert!(validate_model_name("my-coder:latest").is_ok());
+        assert!(validate_model_name("user/assistant").is_ok());
+        assert!(validate_model_name("").is_err());
+        assert!(validate_model_name("has space").is_err());
+        assert!(validate_model_name("a:b:c").is_err());
+    }
+
+    #[test]
+    fn test_create_request_body() {
+        let mf = modelfile::parse_modelfile(
+            "FROM llama3.2:3b\nSYSTEM Be brief.\nPARAMETER temperature 0.1",
+        )
+        .unwrap();
+        let body = create_request_body("brief", &mf, None);
+        assert_eq!(body["from"], "llama3.2:3b");
+        assert_eq!(body["system"], "Be brief.");
+        assert_eq!(body["parameters"]["temperature"], 0.1);
+        assert!(body.get("files").is_none());
+
+        let local = create_request_body("local", &mf, Some(("m.gguf", "sha256:ab")));
+        assert_eq!(local["files"]["m.gguf"], "sha256:ab");
+        assert!(local.get("from").is_none());
+    }
+
+    #[test]
+    fn test_progress_with_bytes() {
+        let p = ModelTransferProgress::new("m", "download", "x").with_bytes(25, Some(100));
+        assert_eq!(p.percent, Some(25.0));
+        let unknown = ModelTransferProgress::new("m", "download", "x").with_bytes(25, None);
+        assert_eq!(unknown.percent, None);
+    }
+}
@@ -0,0 +1,280 @@
+//! Ollama upgrades: compares the installed version with the latest GitHub release and runs the
+//! platform's upgrader (Homebrew, the official install script on Linux installs it made, winget
+//! on Windows) with streamed output. Version comparison and upgrader choice are separated for unit tests.
+
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+use std::time::Duration;
+
+use crate::detection;
+use crate::llmfit_install::{self, InstallProgress};
+use crate::operations::{self, LIST_TIMEOUT, VERSION_TIMEOUT};
+
+const RELEASES_API_URL: &str = "https://api.github.com/repos/ollama/ollama/releases/latest";
+const INSTALL_SCRIPT: &str = "curl -fsSL https://ollama.com/install.sh | sh";
+/// What the install script leaves behind on Linux: the binary and its systemd unit.
+const SCRIPT_BINARY: &str = "/usr/local/bin/ollama";
+const SCRIPT_SERVICE_UNIT: &str = "/etc/systemd/system/ollama.service";
+
+pub const METHOD_BREW: &str = "brew";
+pub const METHOD_SCRIPT: &str = "script";
+pub const METHOD_WINGET: &str = "winget";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OllamaUpdate {
+    pub installed: bool,
+    /// e.g. "0.5.7"; None when not installed or not parseable.
+    pub current_version: Option<String>,
+    /// None when GitHub couldn't be reached.
+    pub latest_version: Option<String>,
+    pub update_available: bool,
+    /// How `upgrade_ollama` would upgrade ("brew", "script", "winget"); None when the app
+    /// can't, e.g. Ollama.app on macOS, which updates itself.
+    pub method: Option<String>,
+    pub release_url: Option<String>,
+    /// Why the latest version is unknown or no upgrade method exists.
+    pub message: Option<String>,
+}
+
+/// The dotted version in `ollama --version` output or a release tag, e.g. "0.5.7" from
+/// "ollama version is 0.5.7" or "v0.5.7-rc1".
+#[must_use]
+pub fn parse_version(text: &str) -> Option<String> {
+    text.split_whitespace().rev().find_map(|word| {
+        let word = word.trim_start_matches('v');
+        let end = word
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(word.len());
+        let version = word[..end].trim_end_matches('.');
+        (version.contains('.') && version.split('.').all(|p| p.parse::<u64>().is_ok()))
+            .then(|| version.to_string())
+    })
+}
+
+/// Whether `latest` is a higher version than `current`; missing parts count as 0.
+#[must_use]
+pub fn is_newer(latest: &str, current: &str) -> bool {
+    let parts = |v: &str| -> Vec<u64> { v.split('.').filter_map(|p| p.parse().ok()).collect() };
+    let (a, b) = (parts(latest), parts(current));
+    let n = a.len().max(b.len());
+    let pad = |v: &[u64]| {
+        (0..n)
+            .map(|i| v.get(i).copied().unwrap_or(0))
+            .collect::<Vec<_>>()
+    };
+    pad(&a) > pad(&b)
+}
+
+/// The upgrader for an OS (Rust `std::env::consts` names): Homebrew wherever it manages
+/// Ollama, else winget on Windows and the install script on Linux when the script installed
+/// it. Package manager, container and hand-built installs get None.
+#[must_use]
+pub fn select_upgrade_method(
+    os: &str,
+    brew_managed: bool,
+    has_winget: bool,
+    script_installed: bool,
+) -> Option<&'static str> {
+    match os {
+        _ if brew_managed => Some(METHOD_BREW),
+        "windows" if has_winget => Some(METHOD_WINGET),
+        "linux" if script_installed => Some(METHOD_SCRIPT),
+        _ => None,
+    }
+}
+
+/// Whether the install script put Ollama here: its binary and the systemd unit it creates.
+fn script_installed() -> bool {
+    [SCRIPT_BINARY, SCRIPT_SERVICE_UNIT]
+        .iter()
+        .all(|p| std::path::Path::new(p).exists())
+}
+
+/// Whether Homebrew installed Ollama (formula or cask).
+fn brew_managed() -> bool {
+    detection::command_exists("brew").is_some()
+        && operations::run_command(
+            Command::new("brew").args(["list", "--versions", "ollama"]),
+            LIST_TIMEOUT,
+            None,
+        )
+        .is_ok_and(|o| o.status.success() && !o.stdout.is_empty())
+}
+
+fn upgrade_method() -> Option<&'static str> {
+    select_upgrade_method(
+        std::env::consts::OS,
+        brew_managed(),
+        detection::command_exists("winget").is_some(),
+        cfg!(target_os = "linux") && script_installed(),
+    )
+}
+
+/// The latest release's version and page.
+fn latest_release() -> Result<(String, String), String> {
+    let release: serde_json::Value = ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .get(RELEASES_API_URL)
+        .set("User-Agent", "openclaw-host-config")
+        .call()
+        .map_err(|e| format!("GitHub request failed: {}", e))?
+        .into_json()
+        .map_err(|e| e.to_string())?;
+    let version = release
+        .get("tag_name")
+        .and_then(|t| t.as_str())
+        .and_then(parse_version)
+        .ok_or("Latest Ollama release has no version tag")?;
+    let url = release
+        .get("html_url")
+        .and_then(|u| u.as_str())
+        .unwrap_or("https://github.com/ollama/ollama/releases/latest")
+        .to_string();
+    Ok((version, url))
+}
+
+/// The installed version. Without a running server `ollama --version` starts with a warning
+/// line, which is all detection keeps, so the full output is read then.
+fn installed_version(status: &detection::LLMStatus) -> Option<String> {
+    status
+        .version
+        .as_deref()
+        .and_then(parse_version)
+        .or_else(|| {
+            let out = operations::run_command(
+                Command::new(status.path.as_deref()?).arg("--version"),
+                VERSION_TIMEOUT,
+                None,
+            )
+            .ok()?;
+            parse_version(&String::from_utf8_lossy(&out.stdout))
+        })
+}
+
+/// Compares the installed Ollama with the latest release.
+#[must_use]
+pub fn check_ollama_update() -> OllamaUpdate {
+    let status = detection::detect_ollama();
+    let current_version = installed_version(&status);
+    let method = if status.installed {
+        upgrade_method()
+    } else {
+        None
+    };
+    let (latest, message) = match latest_release() {
+        Ok(latest) => (Some(latest), None),
+        Err(e) => (None, Some(e)),
+    };
+    let update_available = match (&latest, &current_version) {
+        (Some((latest, _)), Some(current)) => is_newer(latest, current),
+        _ => false,
+    };
+    let message = message.or_else(|| {
+        (update_available && method.is_none())
+            .then(|| "Update Ollama from its menu bar app or ollama.com/download".to_string())
+    });
+    OllamaUpdate {
+        installed: status.installed,
+        current_version,
+        latest_version: latest.as_ref().map(|(v, _)| v.clone()),
+        update_available,
+        method: method.map(String::from),
+        release_url: latest.map(|(_, u)| u),
+        message,
+    }
+}
+
+/// Upgrades Ollama with this platform's upgrader, forwarding its output as progress, then
+/// checks the version again. On Linux the install script runs through pkexec when available,
+/// since it needs root. A running server keeps the old version until restarted.
+pub fn upgrade_ollama(
+    mut on_progress: impl FnMut(InstallProgress),
+) -> Result<OllamaUpdate, String> {
+    if !detection::detect_ollama().installed {
+        return Err("Ollama is not installed".to_string());
+    }
+    let method = upgrade_method().ok_or("No upgrader for Ollama on this platform")?;
+    match method {
+        METHOD_BREW => {
+            llmfit_install::run_installer(method, "brew", &["upgrade", "ollama"], &mut on_progress)?
+        }
+        METHOD_WINGET => llmfit_install::run_installer(
+            method,
+            "winget",
+            &[
+                "upgrade",
+                "--id",
+                "Ollama.Ollama",
+                "--silent",
+                "--accept-source-agreements",
+                "--accept-package-agreements",
+            ],
+            &mut on_progress,
+        )?,
+        _ if detection::command_exists("pkexec").is_some() => llmfit_install::run_installer(
+            method,
+            "pkexec",
+            &["sh", "-c", INSTALL_SCRIPT],
+            &mut on_progress,
+        )?,
+        _ => {
+            llmfit_install::run_installer(method, "sh", &["-c", INSTALL_SCRIPT], &mut on_progress)?
+        }
+    }
+    let update = check_ollama_update();
+    on_progress(InstallProgress {
+        method: method.to_string(),
+        message: format!(
+            "Ollama {} installed",
+            update
+                .current_version
+                .as_deref()
+                .unwrap_or("(unknown version)")
+        ),
+        done: true,
+    });
+    Ok(update)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version_and_is_newer() {
+        assert_eq!(
+            parse_version("ollama version is 0.5.7").as_deref(),
+            Some("0.5.7")
+        );
+        assert_eq!(parse_version("v0.6.0-rc1").as_deref(), Some("0.6.0"));
+        assert_eq!(
+            parse_version("Warning: client version is 0.5.4\n").as_deref(),
+            Some("0.5.4")
+        );
+        assert_eq!(parse_version("ollama"), None);
+        assert!(is_newer("0.5.10", "0.5.7"));
+        assert!(is_newer("0.6", "0.5.9"));
+        assert!(!is_newer("0.5.7", "0.5.7.0"));
+        assert!(!is_newer("0.5.6", "0.5.7"));
+    }
+
+    #[test]
+    fn test_select_upgrade_method() {
+        assert_eq!(
+            select_upgrade_method("macos", true, false, false),
+            Some(METHOD_BREW)
+        );
+        assert_eq!(select_upgrade_method("macos", false, false, false), None);
+        assert_eq!(
+            select_upgrade_method("linux", false, false, true),
+            Some(METHOD_SCRIPT)
+        );
+        assert_eq!(select_upgrade_method("linux", false, false, false), None);
+        assert_eq!(
+            select_upgrade_method("windows", false, true, false),
+            Some(METHOD_WINGET)
+        );
+        assert_eq!(select_upgrade_method("windows", false, false, false), None);
+    }
+}
@@ -5,6 +5,8 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
+use crate::{config_git, config_recovery, detection, policy};
+
 const OPENCLAW_CONFIG_FILENAME: &str = "openclaw.json";
 
 /// Path to openclaw.json (e.g. ~/.openclaw/openclaw.json).
@@ -16,6 +18,15 @@ pub fn openclaw_config_path() -> PathBuf {
         .join(OPENCLAW_CONFIG_FILENAME)
 }
 
+/// App-owned data directory (~/.openclaw/host-config): downloads, benchmark history, etc.
+#[must_use]
+pub fn host_config_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".openclaw")
+        .join("host-config")
+}
+
 /// View of the fields the UI needs: providers, primary model, models list, maxConcurrent, subagents.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct OpenClawConfigView {
@@ -64,6 +75,7 @@ pub fn get_openclaw_providers_raw() -> Result<serde_json::Value, String> {
 }
 
 /// Reads openclaw.json and returns a view with required fields. Missing file or invalid JSON returns defaults.
+/// Uncached; the app reads it through `AppState::config_view`, which builds the same view.
 #[must_use]
 pub fn get_openclaw_config() -> OpenClawConfigView {
     let path = openclaw_config_path();
@@ -74,7 +86,7 @@ pub fn get_openclaw_config() -> OpenClawConfigView {
     parse_config_view(&content).unwrap_or_else(|_| default_view())
 }
 
-fn default_view() -> OpenClawConfigView {
+pub(crate) fn default_view() -> OpenClawConfigView {
     OpenClawConfigView {
         provider_names: vec![],
         primary_model: None,
@@ -87,6 +99,11 @@ fn default_view() -> OpenClawConfigView {
 
 fn parse_config_view(content: &str) -> Result<OpenClawConfigView, ()> {
     let root: serde_json::Value = serde_json::from_str(content).map_err(|_| ())?;
+    config_view_from_root(&root)
+}
+
+/// Builds the view from an already-parsed openclaw.json root.
+pub(crate) fn config_view_from_root(root: &serde_json::Value) -> Result<OpenClawConfigView, ()> {
     let obj = root.as_object().ok_or(())?;
 
     let provider_names = obj
@@ -145,7 +162,7 @@ fn parse_config_view(content: &str) -> Result<OpenClawConfigView, ()> {
     })
 }
 
-fn parse_subagents_view(v: &serde_json::Value) -> SubagentsView {
+pub(crate) fn parse_subagents_view(v: &serde_json::Value) -> SubagentsView {
     let empty_map = serde_json::Map::new();
     let o = v.as_object().unwrap_or(&empty_map);
     SubagentsView {
@@ -158,66 +175,106 @@ fn parse_subagents_view(v: &serde_json::Value) -> SubagentsView {
     }
 }
 
-/// Updates a subset of openclaw.json. Merges into existing file or creates with minimal structure.
-pub fn update_openclaw_config(updates: OpenClawConfigUpdates) -> Result<(), String> {
+/// The minimal skeleton used while openclaw.json does not exist.
+#[must_use]
+pub fn empty_config_root() -> serde_json::Value {
+    serde_json::json!({ "agents": { "defaults": {} }, "models": {} })
+}
+
+/// Reads openclaw.json as a Value, or a minimal skeleton if the file does not exist yet.
+pub fn read_config_root() -> Result<serde_json::Value, String> {
     let path = openclaw_config_path();
-    let mut root: serde_json::Value = if path.exists() {
+    if path.exists() {
         let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
-        serde_json::from_str(&content).map_err(|e| e.to_string())?
+        serde_json::from_str(&content).map_err(|e| e.to_string())
     } else {
-        serde_json::json!({ "agents": { "defaults": {} }, "models": {} })
-    };
+        Ok(empty_config_root())
+    }
+}
 
-    ensure_agents_defaults(&mut root);
-    ensure_subagents(&mut root);
+/// Writes openclaw.json (pretty-printed), creating ~/.openclaw if needed. A file that doesn't
+/// parse is copied aside first so it is never lost. Fails when the team policy forbids the new
+/// content; with config versioning on, the change is committed.
+pub fn write_config_root(root: &serde_json::Value) -> Result<(), String> {
+    let before = config_git::is_enabled().then(|| {
+        fs::read_to_string(openclaw_config_path())
+            .ok()
+            .and_then(|c| serde_json::from_str(&c).ok())
+            .unwrap_or_default()
+    });
+    write_config_file(root)?;
+    if let Some(before) = before {
+        config_git::record_json_change(OPENCLAW_CONFIG_FILENAME, &before, root);
+    }
+    Ok(())
+}
+
+/// `write_config_root` without the versioning commit, for config_git's revert, which commits
+/// the restored files itself.
+pub(crate) fn write_config_file(root: &serde_json::Value) -> Result<(), String> {
+    policy::enforce_config(root)?;
+    let path = openclaw_config_path();
+    let dir = path.parent().ok_or("invalid path")?;
+    if !dir.exists() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    config_recovery::preserve_broken_config()?;
+    fs::write(
+        &path,
+        serde_json::to_string_pretty(root).map_err(|e| e.to_string())?,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Updates a subset of openclaw.json. Merges into existing file or creates with minimal structure.
+pub fn update_openclaw_config(updates: OpenClawConfigUpdates) -> Result<(), String> {
+    let mut root = read_config_root()?;
+    apply_config_updates(&mut root, updates);
+    write_config_root(&root)
+}
+
+/// Applies `updates` to an openclaw.json root, adding agents.defaults and its subagents if missing.
+pub fn apply_config_updates(root: &mut serde_json::Value, updates: OpenClawConfigUpdates) {
+    ensure_agents_defaults(root);
+    ensure_subagents(root);
 
     if let Some(v) = updates.primary_model {
-        set_nested(&mut root, &["agents", "defaults", "model", "primary"], serde_json::json!(v));
+        set_nested(root, &["agents", "defaults", "model", "primary"], serde_json::json!(v));
     }
     if let Some(v) = updates.fallbacks {
         set_nested(
-            &mut root,
+            root,
             &["agents", "defaults", "model", "fallbacks"],
             serde_json::Value::Array(v.into_iter().map(serde_json::Value::String).collect()),
         );
     }
     if let Some(v) = updates.max_concurrent {
-        set_nested(&mut root, &["agents", "defaults", "maxConcurrent"], serde_json::json!(v));
+        set_nested(root, &["agents", "defaults", "maxConcurrent"], serde_json::json!(v));
     }
     if let Some(v) = updates.subagents_max_concurrent {
         set_nested(
-            &mut root,
+            root,
             &["agents", "defaults", "subagents", "maxConcurrent"],
             serde_json::json!(v),
         );
     }
     if let Some(v) = updates.subagents_max_spawn_depth {
         set_nested(
-            &mut root,
+            root,
             &["agents", "defaults", "subagents", "maxSpawnDepth"],
             serde_json::json!(v),
         );
     }
     if let Some(v) = updates.subagents_max_children_per_agent {
         set_nested(
-            &mut root,
+            root,
             &["agents", "defaults", "subagents", "maxChildrenPerAgent"],
             serde_json::json!(v),
         );
     }
-
-    let dir = path.parent().ok_or("invalid path")?;
-    if !dir.exists() {
-        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
-    }
-    fs::write(
-        &path,
-        serde_json::to_string_pretty(&root).map_err(|e| e.to_string())?,
-    )
-    .map_err(|e| e.to_string())
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct OpenClawConfigUpdates {
     pub primary_model: Option<String>,
     pub fallbacks: Option<Vec<String>>,
@@ -227,6 +284,151 @@ pub struct OpenClawConfigUpdates {
     pub subagents_max_children_per_agent: Option<u32>,
 }
 
+/// Default baseUrl for a local runtime provider created on the fly.
+#[must_use]
+pub fn default_local_base_url(provider: &str) -> Option<&'static str> {
+    match provider {
+        "ollama" => Some("http://127.0.0.1:11434/v1"),
+        "lmstudio" | "lm-studio" => Some("http://127.0.0.1:1234/v1"),
+        "vllm" => Some("http://127.0.0.1:8000/v1"),
+        _ => None,
+    }
+}
+
+/// Whether a provider runs on this machine: a known local runtime name, or a baseUrl on
+/// localhost, a loopback address or 0.0.0.0. The one locality check used across the app.
+pub(crate) fn is_local_provider(name: &str, base_url: Option<&str>) -> bool {
+    default_local_base_url(name).is_some()
+        || base_url
+            .and_then(detection::url_host_port)
+            .is_some_and(|(host, _)| {
+                host.eq_ignore_ascii_case("localhost")
+                    || host
+                        .parse::<std::net::IpAddr>()
+                        .is_ok_and(|ip| ip.is_loopback() || ip.is_unspecified())
+            })
+}
+
+/// The baseUrl of provider `name` in a `models.providers` map.
+pub(crate) fn provider_base_url<'a>(
+    providers: &'a serde_json::Value,
+    name: &str,
+) -> Option<&'a str> {
+    providers.get(name)?.get("baseUrl")?.as_str()
+}
+
+/// `is_local_provider` for a "provider/model" id, with the baseUrl from `models.providers`.
+pub(crate) fn is_local_model(id: &str, providers: &serde_json::Value) -> bool {
+    id.split_once('/').is_some_and(|(provider, _)| {
+        is_local_provider(provider, provider_base_url(providers, provider))
+    })
+}
+
+/// `models.providers` of an openclaw.json root, creating `models` and `providers` when missing.
+pub fn providers_mut(
+    root: &mut serde_json::Value,
+) -> Result<&mut serde_json::Map<String, serde_json::Value>, String> {
+    root.as_object_mut()
+        .ok_or("openclaw.json root not an object")?
+        .entry("models")
+        .or_insert_with(|| serde_json::json!({}))
+        .as_object_mut()
+        .ok_or("models not an object")?
+        .entry("providers")
+        .or_insert_with(|| serde_json::json!({}))
+        .as_object_mut()
+        .ok_or_else(|| "models.providers not an object".to_string())
+}
+
+/// Adds `model` to `models.providers.<provider>.models` (if missing) and registers
+/// "<provider>/<model>" under `agents.defaults.models`. Creates the provider for known local runtimes.
+/// Returns true if anything changed.
+pub fn register_provider_model(
+    root: &mut serde_json::Value,
+    provider: &str,
+    model: &str,
+) -> Result<bool, String> {
+    ensure_agents_defaults(root);
+    let mut changed = false;
+
+    let providers = providers_mut(root)?;
+    if !providers.contains_key(provider) {
+        let base_url = default_local_base_url(provider)
+            .ok_or_else(|| format!("Provider {} is not configured", provider))?;
+        providers.insert(
+            provider.to_string(),
+            serde_json::json!({ "baseUrl": base_url, "api": "openai-completions", "models": [] }),
+        );
+        changed = true;
+    }
+    let models = providers
+        .get_mut(provider)
+        .and_then(|p| p.as_object_mut())
+        .ok_or("provider entry not an object")?
+        .entry("models")
+        .or_insert_with(|| serde_json::json!([]))
+        .as_array_mut()
+        .ok_or("provider models not an array")?;
+    let listed = models.iter().any(|m| {
+        m.get("id").and_then(|v| v.as_str()) == Some(model) || m.as_str() == Some(model)
+    });
+    if !listed {
+        models.push(serde_json::json!({ "id": model, "name": model }));
+        changed = true;
+    }
+
+    let full_id = format!("{}/{}", provider, model);
+    let defaults_models = root
+        .get_mut("agents")
+        .and_then(|a| a.get_mut("defaults"))
+        .and_then(|d| d.as_object_mut())
+        .ok_or("agents.defaults not an object")?
+        .entry("models")
+        .or_insert_with(|| serde_json::json!({}))
+        .as_object_mut()
+        .ok_or("agents.defaults.models not an object")?;
+    if !defaults_models.contains_key(&full_id) {
+        defaults_models.insert(full_id, serde_json::json!({}));
+        changed = true;
+    }
+    Ok(changed)
+}
+
+/// Sets `id` as agents.defaults.model.primary ("primary") or appends it to the fallbacks
+/// ("fallback"). A new primary is dropped from the fallbacks; the primary isn't added as one.
+pub fn set_model_role(root: &mut serde_json::Value, id: &str, role: &str) -> Result<(), String> {
+    ensure_agents_defaults(root);
+    let mut fallbacks: Vec<serde_json::Value> = root
+        .pointer("/agents/defaults/model/fallbacks")
+        .and_then(|f| f.as_array())
+        .cloned()
+        .unwrap_or_default();
+    match role {
+        "primary" => {
+            set_nested(
+                root,
+                &["agents", "defaults", "model", "primary"],
+                serde_json::json!(id),
+            );
+            fallbacks.retain(|f| f.as_str() != Some(id));
+        }
+        "fallback" => {
+            let primary = root.pointer("/agents/defaults/model/primary");
+            let listed = fallbacks.iter().any(|f| f.as_str() == Some(id));
+            if !listed && primary.and_then(|p| p.as_str()) != Some(id) {
+                fallbacks.push(serde_json::json!(id));
+            }
+        }
+        other => return Err(format!("Unknown model role: {}", other)),
+    }
+    set_nested(
+        root,
+        &["agents", "defaults", "model", "fallbacks"],
+        serde_json::Value::Array(fallbacks),
+    );
+    Ok(())
+}
+
 fn ensure_agents_defaults(root: &mut serde_json::Value) {
     let obj = root.as_object_mut().expect("root object");
     if !obj.contains_key("agents") {
@@ -263,7 +465,7 @@ fn ensure_subagents(root: &mut serde_json::Value) {
     }
 }
 
-fn set_nested(root: &mut serde_json::Value, path: &[&str], value: serde_json::Value) {
+pub(crate) fn set_nested(root: &mut serde_json::Value, path: &[&str], value: serde_json::Value) {
     let mut current = root;
     for (i, key) in path.iter().enumerate() {
         let is_last = i == path.len() - 1;
@@ -350,6 +552,54 @@ mod tests {
         assert!(parse_config_view("[]").is_err());
     }
 
+    #[test]
+    fn test_register_provider_model() {
+        let mut root = serde_json::json!({
+            "models": { "providers": { "anthropic": { "models": [] } } }
+        });
+        assert!(register_provider_model(&mut root, "ollama", "llama3.2:3b").unwrap());
+        assert!(!register_provider_model(&mut root, "ollama", "llama3.2:3b").unwrap());
+        let ollama = &root["models"]["providers"]["ollama"];
+        assert_eq!(ollama["baseUrl"], "http://127.0.0.1:11434/v1");
+        assert_eq!(ollama["models"][0]["id"], "llama3.2:3b");
+        assert!(root["agents"]["defaults"]["models"]
+            .get("ollama/llama3.2:3b")
+            .is_some());
+
+        assert!(register_provider_model(&mut root, "anthropic", "claude-haiku-4-5").unwrap());
+        assert!(register_provider_model(&mut root, "unknown-cloud", "x").is_err());
+    }
+
+    #[test]
+    fn test_set_model_role() {
+        let mut root = serde_json::json!({});
+        set_model_role(&mut root, "ollama/qwen2.5:7b", "primary").unwrap();
+        set_model_role(&mut root, "ollama/llama3.2:3b", "fallback").unwrap();
+        set_model_role(&mut root, "ollama/llama3.2:3b", "fallback").unwrap();
+        let view = parse_config_view(&root.to_string()).unwrap();
+        assert_eq!(view.primary_model.as_deref(), Some("ollama/qwen2.5:7b"));
+        assert_eq!(view.fallbacks, vec!["ollama/llama3.2:3b"]);
+        set_model_role(&mut root, "ollama/qwen2.5:7b", "fallback").unwrap();
+        set_model_role(&mut root, "ollama/llama3.2:3b", "primary").unwrap();
+        let view = parse_config_view(&root.to_string()).unwrap();
+        assert_eq!(view.primary_model.as_deref(), Some("ollama/llama3.2:3b"));
+        assert!(view.fallbacks.is_empty());
+        assert!(set_model_role(&mut root, "x/y", "backup").is_err());
+    }
+
+    #[test]
+    fn test_is_local_provider() {
+        assert!(is_local_provider("ollama", None));
+        assert!(is_local_provider("box", Some("http://localhost:8080/v1")));
+        assert!(is_local_provider("box", Some("http://[::1]:8000/v1")));
+        assert!(is_local_provider("box", Some("http://0.0.0.0:1234")));
+        assert!(!is_local_provider("box", Some("http://localhost.lan")));
+        assert!(!is_local_provider("groq", Some("https://api.groq.com/v1")));
+        let providers = serde_json::json!({ "gpu": { "baseUrl": "http://127.0.0.1:9000/v1" } });
+        assert!(is_local_model("gpu/qwen3:8b", &providers));
+        assert!(!is_local_model("anthropic/claude", &providers));
+    }
+
     #[test]
     fn test_get_openclaw_config_no_panic() {
         let view = get_openclaw_config();
@@ -0,0 +1,245 @@
+//! OpenRouter's public model catalog (pricing, context size) and adding picked models to an
+//! `openrouter` provider in openclaw.json, for setups that mix local runtimes with OpenRouter.
+//! Response parsing and config edits are separated for unit tests.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+
+use crate::openclaw_config;
+
+const OPENROUTER_API_URL: &str = "https://openrouter.ai/api/v1";
+pub const OPENROUTER_PROVIDER: &str = "openrouter";
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct OpenRouterModel {
+    /// OpenRouter id, e.g. "anthropic/claude-sonnet-4.5"; the openclaw id is "openrouter/<id>".
+    pub id: String,
+    pub name: String,
+    pub context_length: Option<u64>,
+    pub max_completion_tokens: Option<u64>,
+    /// USD per million input tokens.
+    pub input_price_per_mtok: Option<f64>,
+    /// USD per million output tokens.
+    pub output_price_per_mtok: Option<f64>,
+    pub free: bool,
+}
+
+/// OpenRouter prices are USD per token, as strings.
+fn price_per_mtok(pricing: Option<&Value>, key: &str) -> Option<f64> {
+    let per_token = match pricing?.get(key)? {
+        Value::String(s) => s.parse::<f64>().ok()?,
+        v => v.as_f64()?,
+    };
+    // Negative prices mark routers whose cost varies ("openrouter/auto").
+    (per_token >= 0.0).then_some(per_token * 1_000_000.0)
+}
+
+/// Parses the `/models` response.
+#[must_use]
+pub fn parse_openrouter_models(body: &str) -> Vec<OpenRouterModel> {
+    let Ok(root) = serde_json::from_str::<Value>(body) else {
+        return vec![];
+    };
+    root.get("data")
+        .and_then(|d| d.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|m| {
+                    let id = m.get("id")?.as_str()?.to_string();
+                    let pricing = m.get("pricing");
+                    let input = price_per_mtok(pricing, "prompt");
+                    let output = price_per_mtok(pricing, "completion");
+                    Some(OpenRouterModel {
+                        name: m
+                            .get("name")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or(&id)
+                            .to_string(),
+                        context_length: m.get("context_length").and_then(|v| v.as_u64()),
+                        max_completion_tokens: m
+                            .pointer("/top_provider/max_completion_tokens")
+                            .and_then(|v| v.as_u64()),
+                        free: input == Some(0.0) && output == Some(0.0),
+                        input_price_per_mtok: input,
+                        output_price_per_mtok: output,
+                        id,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Fetches the catalog; no key needed.
+pub fn get_openrouter_models() -> Result<Vec<OpenRouterModel>, String> {
+    let body = ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(15))
+        .build()
+        .get(&format!("{}/models", OPENROUTER_API_URL))
+        .call()
+        .map_err(|e| format!("OpenRouter request failed: {}", e))?
+        .into_string()
+        .map_err(|e| e.to_string())?;
+    let models = parse_openrouter_models(&body);
+    if models.is_empty() {
+        return Err("OpenRouter returned no models".to_string());
+    }
+    Ok(models)
+}
+
+/// Creates the `openrouter` provider if missing and sets its key when one is given.
+fn ensure_provider(root: &mut Value, api_key: Option<&str>) -> Result<(), String> {
+    let providers = openclaw_config::providers_mut(root)?;
+    let provider = providers
+        .entry(OPENROUTER_PROVIDER)
+        .or_insert_with(|| {
+            serde_json::json!({
+                "baseUrl": OPENROUTER_API_URL,
+                "api": "openai-completions",
+                "models": []
+            })
+        })
+        .as_object_mut()
+        .ok_or("openrouter provider entry not an object")?;
+    if let Some(key) = api_key.map(str::trim).filter(|k| !k.is_empty()) {
+        provider.insert("apiKey".to_string(), serde_json::json!(key));
+    }
+    Ok(())
+}
+
+/// Fills context size and pricing into the provider's entry for `model`, keeping values
+/// already set by hand.
+fn fill_model_entry(root: &mut Value, model: &OpenRouterModel) {
+    let Some(entry) = root
+        .pointer_mut("/models/providers/openrouter/models")
+        .and_then(|m| m.as_array_mut())
+        .and_then(|models| {
+            models
+                .iter_mut()
+                .find(|m| m.get("id").and_then(|v| v.as_str()) == Some(model.id.as_str()))
+        })
+        .and_then(|m| m.as_object_mut())
+    else {
+        return;
+    };
+    entry.insert("name".to_string(), serde_json::json!(model.name));
+    if let Some(n) = model.context_length {
+        entry
+            .entry("contextWindow")
+            .or_insert_with(|| serde_json::json!(n));
+    }
+    if let Some(n) = model.max_completion_tokens {
+        entry
+            .entry("maxTokens")
+            .or_insert_with(|| serde_json::json!(n));
+    }
+    if let (Some(input), Some(output)) = (model.input_price_per_mtok, model.output_price_per_mtok) {
+        entry
+            .entry("cost")
+            .or_insert_with(|| serde_json::json!({ "input": input, "output": output }));
+    }
+}
+
+/// Adds `models` to the `openrouter` provider (creating it, with `api_key` if given) and
+/// registers them under agents.defaults.models. Returns the openclaw ids that were added.
+pub fn add_models_to_root(
+    root: &mut Value,
+    models: &[OpenRouterModel],
+    api_key: Option<&str>,
+) -> Result<Vec<String>, String> {
+    ensure_provider(root, api_key)?;
+    let mut added = Vec::new();
+    for model in models {
+        if openclaw_config::register_provider_model(root, OPENROUTER_PROVIDER, &model.id)? {
+            added.push(format!("{}/{}", OPENROUTER_PROVIDER, model.id));
+        }
+        fill_model_entry(root, model);
+    }
+    Ok(added)
+}
+
+/// Adds catalog models by OpenRouter id (with or without the "openrouter/" prefix) and writes
+/// openclaw.json.
+pub fn add_openrouter_models(ids: &[String], api_key: Option<&str>) -> Result<Vec<String>, String> {
+    let catalog = get_openrouter_models()?;
+    let models = ids
+        .iter()
+        .map(|id| {
+            // Catalog ids can start with "openrouter/" themselves (openrouter/auto).
+            let bare = id
+                .strip_prefix(&format!("{}/", OPENROUTER_PROVIDER))
+                .unwrap_or(id);
+            catalog
+                .iter()
+                .find(|m| m.id == *id)
+                .or_else(|| catalog.iter().find(|m| m.id == bare))
+                .cloned()
+                .ok_or_else(|| format!("{} is not in the OpenRouter catalog", id))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let mut root = openclaw_config::read_config_root()?;
+    let added = add_models_to_root(&mut root, &models, api_key)?;
+    openclaw_config::write_config_root(&root)?;
+    Ok(added)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_openrouter_models() {
+        let body = r#"{"data":[
+            {"id":"anthropic/claude-sonnet-4.5","name":"Anthropic: Claude Sonnet 4.5","context_length":1000000,
+             "pricing":{"prompt":"0.000003","completion":"0.000015"},"top_provider":{"max_completion_tokens":64000}},
+            {"id":"meta-llama/llama-3.3-70b-instruct:free","context_length":131072,
+             "pricing":{"prompt":"0","completion":"0"}},
+            {"id":"openrouter/auto","pricing":{"prompt":"-1","completion":"-1"}}
+        ]}"#;
+        let models = parse_openrouter_models(body);
+        assert_eq!(models.len(), 3);
+        assert_eq!(models[0].context_length, Some(1_000_000));
+        assert_eq!(models[0].max_completion_tokens, Some(64000));
+        assert!((models[0].input_price_per_mtok.unwrap() - 3.0).abs() < 1e-9);
+        assert!((models[0].output_price_per_mtok.unwrap() - 15.0).abs() < 1e-9);
+        assert!(!models[0].free);
+        assert_eq!(models[1].name, "meta-llama/llama-3.3-70b-instruct:free");
+        assert!(models[1].free);
+        assert_eq!(models[2].input_price_per_mtok, None);
+        assert!(parse_openrouter_models("not json").is_empty());
+    }
+
+    #[test]
+    fn test_add_models_to_root() {
+        let mut root = serde_json::json!({ "agents": { "defaults": {} }, "models": {} });
+        let model = OpenRouterModel {
+            id: "deepseek/deepseek-chat".to_string(),
+            name: "DeepSeek V3".to_string(),
+            context_length: Some(163840),
+            max_completion_tokens: None,
+            input_price_per_mtok: Some(0.3),
+            output_price_per_mtok: Some(0.85),
+            free: false,
+        };
+        let added =
+            add_models_to_root(&mut root, std::slice::from_ref(&model), Some("sk-or-1")).unwrap();
+        assert_eq!(added, vec!["openrouter/deepseek/deepseek-chat"]);
+        let provider = &root["models"]["providers"]["openrouter"];
+        assert_eq!(provider["baseUrl"], OPENROUTER_API_URL);
+        assert_eq!(provider["apiKey"], "sk-or-1");
+        assert_eq!(provider["models"][0]["contextWindow"], 163840);
+        assert_eq!(provider["models"][0]["cost"]["output"], 0.85);
+        assert!(root["agents"]["defaults"]["models"]
+            .get("openrouter/deepseek/deepseek-chat")
+            .is_some());
+        assert!(add_models_to_root(&mut root, &[model], None)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            root["models"]["providers"]["openrouter"]["apiKey"],
+            "sk-or-1"
+        );
+    }
+}
@@ -0,0 +1,433 @@
+//! Timeouts, cancellation and progress for slow work. `run_command` replaces `Command::output`
+//! for CLIs that can hang (`ollama --version`, `lms ls`, `llmfit recommend`) and kills them on
+//! expiry. Long operations (pulls, benchmarks, bulk syncs, backups) are registered with an id,
+//! report a percentage and status, and take a `CancelToken` they check between chunks; the
+//! frontend can pass its own id to the command and cancel by it.
+
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// For `--version` and similar quick queries.
+pub const VERSION_TIMEOUT: Duration = Duration::from_secs(5);
+/// For listing commands such as `lms ls`, which may start LM Studio's daemon first.
+pub const LIST_TIMEOUT: Duration = Duration::from_secs(20);
+/// The error a cancelled operation returns.
+pub const CANCELLED: &str = "Cancelled";
+
+const POLL: Duration = Duration::from_millis(50);
+/// Finished operations kept for the progress tray.
+const KEEP_FINISHED: usize = 20;
+
+/// Shared flag for one operation; clones see the same state.
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Err(CANCELLED) once cancelled; for `?` between steps.
+    pub fn check(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            Err(CANCELLED.to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationKind {
+    ModelPull,
+    ModelCreate,
+    Benchmark,
+    AgentSync,
+    Backup,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationStatus {
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+/// A long task as the progress tray shows it.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct OperationInfo {
+    pub id: String,
+    pub kind: OperationKind,
+    /// e.g. "Pull qwen3:8b".
+    pub label: String,
+    pub status: OperationStatus,
+    /// 0-100 when the task can tell.
+    pub percent: Option<f32>,
+    /// Latest step, e.g. "pulling 8eeb52dfb3bb".
+    pub message: Option<String>,
+    /// Unix seconds.
+    pub started_at: u64,
+    pub finished_at: Option<u64>,
+    pub error: Option<String>,
+}
+
+struct Entry {
+    info: OperationInfo,
+    token: CancelToken,
+}
+
+type Listener = Arc<dyn Fn(&OperationInfo) + Send + Sync>;
+
+/// Running operations and the last few finished ones. Each change is passed to the listener,
+/// which the app uses to stream it to the window.
+#[derive(Default)]
+pub struct OperationRegistry {
+    entries: Mutex<Vec<Entry>>,
+    next_id: AtomicU64,
+    listener: Mutex<Option<Listener>>,
+}
+
+/// A registered operation. Dropped without `finish`, it ends as cancelled or failed.
+pub struct Operation<'a> {
+    registry: &'a OperationRegistry,
+    id: String,
+    token: CancelToken,
+}
+
+/// Seconds since the Unix epoch; 0 when the clock is set before it.
+#[must_use]
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Milliseconds since the Unix epoch; 0 when the clock is set before it.
+#[must_use]
+pub fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+impl Operation<'_> {
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    #[must_use]
+    pub fn token(&self) -> &CancelToken {
+        &self.token
+    }
+
+    /// Records progress. The listener only hears about whole-percent or message changes, so
+    /// chatty sources like pull streams don't flood the window.
+    pub fn progress(&self, percent: Option<f32>, message: &str) {
+        self.registry.update(&self.id, |info| {
+            let whole = |p: Option<f32>| p.map(|p| p.clamp(0.0, 100.0) as u32);
+            let changed =
+                whole(info.percent) != whole(percent) || info.message.as_deref() != Some(message);
+            info.percent = percent.map(|p| p.clamp(0.0, 100.0)).or(info.percent);
+            info.message = Some(message.to_string());
+            changed
+        });
+    }
+
+    /// Ends the operation with `result`; a cancelled token wins over the error it caused.
+    pub fn finish<T>(self, result: &Result<T, String>) {
+        let cancelled = self.token.is_cancelled();
+        self.registry.update(&self.id, |info| {
+            info.finished_at = Some(now_secs());
+            match result {
+                _ if cancelled => info.status = OperationStatus::Cancelled,
+                Ok(_) => {
+                    info.status = OperationStatus::Succeeded;
+                    info.percent = Some(100.0);
+                }
+                Err(e) => {
+                    info.status = OperationStatus::Failed;
+                    info.error = Some(e.clone());
+                }
+            }
+            true
+        });
+    }
+}
+
+impl Drop for Operation<'_> {
+    fn drop(&mut self) {
+        let cancelled = self.token.is_cancelled();
+        self.registry.update(&self.id, |info| {
+            if info.status != OperationStatus::Running {
+                return false;
+            }
+            info.finished_at = Some(now_secs());
+            info.status = if cancelled {
+                OperationStatus::Cancelled
+            } else {
+                OperationStatus::Failed
+            };
+            true
+        });
+    }
+}
+
+impl OperationRegistry {
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Entry>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Called with every operation that starts, progresses or ends.
+    pub fn set_listener(&self, listener: impl Fn(&OperationInfo) + Send + Sync + 'static) {
+        *self.listener.lock().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(listener));
+    }
+
+    fn notify(&self, info: &OperationInfo) {
+        let listener = self
+            .listener
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        if let Some(listener) = listener {
+            listener(info);
+        }
+    }
+
+    /// Applies `change` to an operation and tells the listener when it returns true.
+    fn update(&self, id: &str, change: impl FnOnce(&mut OperationInfo) -> bool) {
+        let info = {
+            let mut entries = self.lock();
+            let Some(entry) = entries.iter_mut().find(|e| e.info.id == id) else {
+                return;
+            };
+            if !change(&mut entry.info) {
+                return;
+            }
+            let info = entry.info.clone();
+            let mut finished = 0;
+            entries.retain(|e| {
+                if e.info.status == OperationStatus::Running {
+                    return true;
+                }
+                finished += 1;
+                finished <= KEEP_FINISHED
+            });
+            info
+        };
+        self.notify(&info);
+    }
+
+    /// Registers an operation under `op_id`, or a generated id when the caller has none. An id
+    /// can be reused once its previous operation has finished.
+    pub fn begin(
+        &self,
+        kind: OperationKind,
+        label: &str,
+        op_id: Option<&str>,
+    ) -> Result<Operation<'_>, String> {
+        let id = match op_id.map(str::trim).filter(|id| !id.is_empty()) {
+            Some(id) => id.to_string(),
+            None => format!("op-{}", self.next_id.fetch_add(1, Ordering::SeqCst) + 1),
+        };
+        let token = CancelToken::default();
+        let info = OperationInfo {
+            id: id.clone(),
+            kind,
+            label: label.to_string(),
+            status: OperationStatus::Running,
+            percent: None,
+            message: None,
+            started_at: now_secs(),
+            finished_at: None,
+            error: None,
+        };
+        {
+            let mut entries = self.lock();
+            if entries
+                .iter()
+                .any(|e| e.info.id == id && e.info.status == OperationStatus::Running)
+            {
+                return Err(format!("Operation {} is already running", id));
+            }
+            entries.retain(|e| e.info.id != id);
+            // Newest first.
+            entries.insert(
+                0,
+                Entry {
+                    info: info.clone(),
+                    token: token.clone(),
+                },
+            );
+        }
+        self.notify(&info);
+        Ok(Operation {
+            registry: self,
+            id,
+            token,
+        })
+    }
+
+    /// Cancels a running operation; false when no running operation has that id.
+    pub fn cancel(&self, op_id: &str) -> bool {
+        self.lock()
+            .iter()
+            .find(|e| e.info.id == op_id && e.info.status == OperationStatus::Running)
+            .map(|e| e.token.cancel())
+            .is_some()
+    }
+
+    /// Running operations and the most recent finished ones, newest first.
+    #[must_use]
+    pub fn list(&self) -> Vec<OperationInfo> {
+        self.lock().iter().map(|e| e.info.clone()).collect()
+    }
+}
+
+fn read_all(mut reader: impl Read) -> Vec<u8> {
+    let mut buf = Vec::new();
+    let _ = reader.read_to_end(&mut buf);
+    buf
+}
+
+/// "lms ls" for messages: the program's file name and its arguments.
+fn describe(cmd: &Command) -> String {
+    let program = std::path::Path::new(cmd.get_program())
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    std::iter::once(program)
+        .chain(cmd.get_args().map(|a| a.to_string_lossy().to_string()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Like `Command::output`, but kills the process when `timeout` passes or `cancel` is
+/// cancelled. stdin is closed so a prompt can't block it.
+pub fn run_command(
+    cmd: &mut Command,
+    timeout: Duration,
+    cancel: Option<&CancelToken>,
+) -> Result<Output, String> {
+    let label = describe(cmd);
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run {}: {}", label, e))?;
+    let stdout = child.stdout.take().map(|s| thread::spawn(|| read_all(s)));
+    let stderr = child.stderr.take().map(|s| thread::spawn(|| read_all(s)));
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(|e| e.to_string())? {
+            break status;
+        }
+        let cancelled = cancel.is_some_and(CancelToken::is_cancelled);
+        if cancelled || Instant::now() >= deadline {
+            // The readers are left to finish on their own; a grandchild may still hold the pipes.
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(if cancelled {
+                CANCELLED.to_string()
+            } else {
+                format!("{} timed out after {}s", label, timeout.as_secs_f32())
+            });
+        }
+        thread::sleep(POLL);
+    };
+    let join =
+        |h: Option<thread::JoinHandle<Vec<u8>>>| h.and_then(|h| h.join().ok()).unwrap_or_default();
+    Ok(Output {
+        status,
+        stdout: join(stdout),
+        stderr: join(stderr),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_command() {
+        let out = run_command(
+            Command::new("sh").args(["-c", "echo hi"]),
+            VERSION_TIMEOUT,
+            None,
+        )
+        .unwrap();
+        assert_eq!(String::from_utf8_lossy(&out.stdout).trim(), "hi");
+
+        let started = Instant::now();
+        let err = run_command(
+            Command::new("sleep").arg("5"),
+            Duration::from_millis(200),
+            None,
+        )
+        .unwrap_err();
+        assert!(err.contains("sleep 5 timed out"), "{}", err);
+        assert!(started.elapsed() < Duration::from_secs(3));
+
+        let token = CancelToken::default();
+        token.cancel();
+        let err = run_command(
+            Command::new("sleep").arg("5"),
+            VERSION_TIMEOUT,
+            Some(&token),
+        );
+        assert_eq!(err.unwrap_err(), CANCELLED);
+    }
+
+    #[test]
+    fn test_operation_registry() {
+        let registry = OperationRegistry::default();
+        let heard = Arc::new(Mutex::new(Vec::new()));
+        let sink = heard.clone();
+        registry.set_listener(move |info| sink.lock().unwrap().push(info.status));
+
+        let op = registry
+            .begin(OperationKind::ModelPull, "Pull qwen3:8b", Some("pull-1"))
+            .unwrap();
+        assert!(registry
+            .begin(OperationKind::ModelPull, "again", Some("pull-1"))
+            .is_err());
+        op.progress(Some(10.2), "pulling");
+        op.progress(Some(10.7), "pulling");
+        assert_eq!(registry.list()[0].percent, Some(10.7));
+        assert!(registry.cancel("pull-1"));
+        assert!(op.token().check().is_err());
+        assert!(!registry.cancel("other"));
+        op.finish(&Err::<(), _>(CANCELLED.to_string()));
+        assert_eq!(registry.list()[0].status, OperationStatus::Cancelled);
+        assert!(!registry.cancel("pull-1"));
+        // Start, one progress step (the second was within the same percent), end.
+        assert_eq!(heard.lock().unwrap().len(), 3);
+
+        let op = registry
+            .begin(OperationKind::Backup, "Back up", None)
+            .unwrap();
+        assert_eq!(op.id(), "op-1");
+        op.finish(&Ok::<(), String>(()));
+        let list = registry.list();
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].status, OperationStatus::Succeeded);
+        assert_eq!(list[0].percent, Some(100.0));
+    }
+}
@@ -0,0 +1,44 @@
+//! One page of a long list plus the full count, so the UI can virtualize model lists without
+//! receiving every entry over IPC.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Entries across all pages.
+    pub total: usize,
+    pub offset: usize,
+}
+
+/// The `limit` entries starting at `offset`; everything from `offset` on when `limit` is None.
+#[must_use]
+pub fn paginate<T>(items: Vec<T>, offset: usize, limit: Option<usize>) -> Page<T> {
+    let total = items.len();
+    let items = items
+        .into_iter()
+        .skip(offset)
+        .take(limit.unwrap_or(usize::MAX))
+        .collect();
+    Page {
+        items,
+        total,
+        offset,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paginate() {
+        let page = paginate((0..10).collect(), 4, Some(3));
+        assert_eq!(page.items, vec![4, 5, 6]);
+        assert_eq!(page.total, 10);
+        assert!(paginate((0..10).collect::<Vec<_>>(), 12, Some(3))
+            .items
+            .is_empty());
+        assert_eq!(paginate((0..10).collect(), 8, None).items, vec![8, 9]);
+    }
+}
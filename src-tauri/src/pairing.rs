@@ -0,0 +1,157 @@
+//! Pairing mobile and remote openclaw clients: the gateway's LAN URL and auth token from the
+//! gateway section of openclaw.json as a small JSON payload, plus that payload as a QR code (an
+//! SVG data URI) to scan. The payload carries the token in clear, so it is only returned to the
+//! UI, never written or logged. A `${VAR}` token is resolved from the environment or
+//! openclaw.json `env`. Building the payload and the QR code are pure functions for unit tests.
+
+use base64::Engine;
+use qrcode::render::svg;
+use qrcode::QrCode;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{gateway_reachability, openclaw_config};
+
+/// Bumped when the payload's fields change, so clients can tell formats apart.
+pub const PAIRING_PAYLOAD_VERSION: u32 = 1;
+const QR_MIN_SIZE: u32 = 256;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PairingInfo {
+    /// e.g. "ws://192.168.1.20:18789".
+    pub url: String,
+    /// The JSON the QR code holds: `{ type, version, url, token }`.
+    pub payload: String,
+    /// The payload as a QR code, `data:image/svg+xml;base64,...`.
+    pub qr_data_uri: String,
+    /// Why the URL may not work from another device yet.
+    pub warnings: Vec<String>,
+}
+
+/// The gateway token: literal, or `${VAR}` from the environment, else from openclaw.json `env`.
+fn resolve_token(root: &Value) -> Result<String, String> {
+    let token = root
+        .pointer("/gateway/auth/token")
+        .and_then(|t| t.as_str())
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .ok_or("gateway.auth.token is not set; pairing needs a token")?;
+    let Some(var) = token.strip_prefix("${").and_then(|t| t.strip_suffix('}')) else {
+        return Ok(token.to_string());
+    };
+    std::env::var(var)
+        .ok()
+        .or_else(|| {
+            root.pointer(&format!("/env/{}", var))
+                .and_then(|v| v.as_str())
+                .map(String::from)
+        })
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| format!("gateway.auth.token refers to {}, which is not set", var))
+}
+
+/// The pairing payload for openclaw.json `root` with the gateway at `lan_ip`, and warnings.
+pub fn pairing_payload(root: &Value, lan_ip: Option<&str>) -> Result<(Value, Vec<String>), String> {
+    let token = resolve_token(root)?;
+    let mut warnings = Vec::new();
+    let bind = root
+        .pointer("/gateway/bind")
+        .and_then(|b| b.as_str())
+        .unwrap_or("loopback");
+    if bind == "loopback" {
+        warnings.push(
+            "gateway.bind is \"loopback\": set it to \"lan\" and restart the gateway so other \
+             devices can connect"
+                .to_string(),
+        );
+    }
+    let host = lan_ip.unwrap_or_else(|| {
+        warnings.push("This machine has no LAN address; the URL only works locally".to_string());
+        "127.0.0.1"
+    });
+    let tls = root
+        .pointer("/gateway/tls/enabled")
+        .and_then(|t| t.as_bool())
+        .unwrap_or(false);
+    let url = format!(
+        "{}://{}:{}",
+        if tls { "wss" } else { "ws" },
+        host,
+        gateway_reachability::gateway_port(root)
+    );
+    let payload = serde_json::json!({
+        "type": "openclaw-gateway",
+        "version": PAIRING_PAYLOAD_VERSION,
+        "url": url,
+        "token": token,
+    });
+    Ok((payload, warnings))
+}
+
+/// `text` as a QR code in an SVG data URI.
+pub fn qr_data_uri(text: &str) -> Result<String, String> {
+    let code = QrCode::new(text.as_bytes()).map_err(|e| e.to_string())?;
+    let svg = code
+        .render::<svg::Color>()
+        .min_dimensions(QR_MIN_SIZE, QR_MIN_SIZE)
+        .build();
+    Ok(format!(
+        "data:image/svg+xml;base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(svg)
+    ))
+}
+
+/// Pairing info for the gateway configured in openclaw.json.
+pub fn generate_pairing_info() -> Result<PairingInfo, String> {
+    let root = openclaw_config::read_config_root()?;
+    let lan_ip = gateway_reachability::lan_ip();
+    let (payload, warnings) = pairing_payload(&root, lan_ip.as_deref())?;
+    let text = serde_json::to_string(&payload).map_err(|e| e.to_string())?;
+    Ok(PairingInfo {
+        url: payload["url"].as_str().unwrap_or_default().to_string(),
+        qr_data_uri: qr_data_uri(&text)?,
+        payload: text,
+        warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pairing_payload() {
+        let mut root = serde_json::json!({
+            "gateway": {
+                "port": 19000,
+                "bind": "lan",
+                "auth": { "token": "${PAIRING_TEST_TOKEN}" }
+            },
+            "env": { "PAIRING_TEST_TOKEN": "tok-123" }
+        });
+        let (payload, warnings) = pairing_payload(&root, Some("192.168.1.20")).unwrap();
+        assert_eq!(payload["url"], "ws://192.168.1.20:19000");
+        assert_eq!(payload["token"], "tok-123");
+        assert!(warnings.is_empty());
+
+        root["gateway"]["bind"] = "loopback".into();
+        let (payload, warnings) = pairing_payload(&root, None).unwrap();
+        assert_eq!(payload["url"], "ws://127.0.0.1:19000");
+        assert_eq!(warnings.len(), 2);
+
+        root["gateway"]["auth"] = serde_json::json!({});
+        assert!(pairing_payload(&root, None)
+            .unwrap_err()
+            .contains("not set"));
+    }
+
+    #[test]
+    fn test_qr_data_uri() {
+        let uri = qr_data_uri(r#"{"type":"openclaw-gateway"}"#).unwrap();
+        let b64 = uri.strip_prefix("data:image/svg+xml;base64,").unwrap();
+        let svg = base64::engine::general_purpose::STANDARD
+            .decode(b64)
+            .unwrap();
+        assert!(String::from_utf8(svg).unwrap().contains("<svg"));
+    }
+}
@@ -0,0 +1,334 @@
+//! Team policy for shared installs: an optional ~/.openclaw/host-config/policy.json that pins
+//! openclaw.json paths to fixed values (e.g. `"gateway.bind": "loopback"`), keeps providers to
+//! local runtimes and/or an allow-list. Every write of openclaw.json or an agent's models.json
+//! through this app is checked first and fails with a `POLICY_VIOLATION_ERROR`-prefixed message
+//! naming the offending path. Only violations a write introduces block it: ones the file already
+//! has are reported by `get_config_policy` but don't stop unrelated edits. A locked path may be
+//! left unset (openclaw's default applies) but not set to anything else. A policy that doesn't
+//! parse blocks writes rather than being ignored. The checks are pure functions for unit tests.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::{agents, local_mode, openclaw_config};
+
+/// Prefix of the error a write returns when it breaks the policy, so the UI can tell it apart.
+pub const POLICY_VIOLATION_ERROR: &str = "policy_violation";
+const OPENCLAW_JSON: &str = "openclaw.json";
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ConfigPolicy {
+    /// openclaw.json paths ("gateway.bind") and the only value each may be set to.
+    pub locked: BTreeMap<String, Value>,
+    /// Providers must be local runtimes or on localhost, and only their models may be selected.
+    pub local_providers_only: bool,
+    /// Provider names allowed in models.providers and model ids; empty allows any.
+    pub allowed_providers: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PolicyViolation {
+    /// "openclaw.json" or "agents/<name>/agent/models.json".
+    pub file: String,
+    /// Dot path inside the file, e.g. "models.providers.groq".
+    pub path: String,
+    pub message: String,
+}
+
+impl PolicyViolation {
+    /// The error string write commands return.
+    #[must_use]
+    pub fn to_error(&self) -> String {
+        format!(
+            "{}: {}: {} (in {})",
+            POLICY_VIOLATION_ERROR, self.path, self.message, self.file
+        )
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct PolicyStatus {
+    /// None when there is no policy.json.
+    pub policy: Option<ConfigPolicy>,
+    pub path: String,
+    /// Where the current openclaw.json already breaks the policy.
+    pub violations: Vec<PolicyViolation>,
+}
+
+fn policy_path() -> PathBuf {
+    openclaw_config::host_config_dir().join("policy.json")
+}
+
+/// The policy; None when there is none.
+pub fn load_policy() -> Result<Option<ConfigPolicy>, String> {
+    let path = policy_path();
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Ok(None);
+    };
+    serde_json::from_str(&content).map(Some).map_err(|e| {
+        format!(
+            "{}: policy.json doesn't parse: {}",
+            POLICY_VIOLATION_ERROR, e
+        )
+    })
+}
+
+fn lookup<'a>(root: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(root, |node, key| node.get(key))
+}
+
+fn is_allowed(policy: &ConfigPolicy, provider: &str) -> bool {
+    policy.allowed_providers.is_empty() || policy.allowed_providers.iter().any(|p| p == provider)
+}
+
+/// Why the policy rejects provider `name` (from the `providers` map), if it does.
+fn provider_problem(policy: &ConfigPolicy, name: &str, providers: &Value) -> Option<String> {
+    if !is_allowed(policy, name) {
+        return Some(format!("provider {} is not allowed", name));
+    }
+    let base_url = openclaw_config::provider_base_url(providers, name);
+    if policy.local_providers_only && !openclaw_config::is_local_provider(name, base_url) {
+        return Some(format!("provider {} is not a local endpoint", name));
+    }
+    None
+}
+
+fn check_providers(
+    policy: &ConfigPolicy,
+    file: &str,
+    prefix: &str,
+    providers: &Value,
+    out: &mut Vec<PolicyViolation>,
+) {
+    for name in providers.as_object().into_iter().flat_map(|p| p.keys()) {
+        if let Some(message) = provider_problem(policy, name, providers) {
+            out.push(PolicyViolation {
+                file: file.to_string(),
+                path: format!("{}.{}", prefix, name),
+                message,
+            });
+        }
+    }
+}
+
+/// Where `root` (openclaw.json) breaks `policy`.
+#[must_use]
+pub fn check_config(policy: &ConfigPolicy, root: &Value) -> Vec<PolicyViolation> {
+    let violation = |path: String, message: String| PolicyViolation {
+        file: OPENCLAW_JSON.to_string(),
+        path,
+        message,
+    };
+    let mut out: Vec<PolicyViolation> = policy
+        .locked
+        .iter()
+        .filter(|(path, value)| lookup(root, path).is_some_and(|actual| actual != *value))
+        .map(|(path, value)| violation(path.clone(), format!("locked to {}", value)))
+        .collect();
+    let providers = root
+        .pointer("/models/providers")
+        .cloned()
+        .unwrap_or_default();
+    check_providers(
+        policy,
+        OPENCLAW_JSON,
+        "models.providers",
+        &providers,
+        &mut out,
+    );
+
+    // Models selected by default or per agent.
+    let mut refs: Vec<(String, &str)> = Vec::new();
+    if let Some(model) = root.pointer("/agents/defaults/model") {
+        let ids = local_mode::model_value_ids(model);
+        refs.extend(
+            ids.into_iter()
+                .map(|id| ("agents.defaults.model".to_string(), id)),
+        );
+    }
+    if let Some(models) = root
+        .pointer("/agents/defaults/models")
+        .and_then(|m| m.as_object())
+    {
+        refs.extend(
+            models
+                .keys()
+                .map(|id| (format!("agents.defaults.models.{}", id), id.as_str())),
+        );
+    }
+    let list = root.pointer("/agents/list").and_then(|l| l.as_array());
+    for (i, agent) in list.into_iter().flatten().enumerate() {
+        if let Some(model) = agent.get("model") {
+            let ids = local_mode::model_value_ids(model);
+            refs.extend(
+                ids.into_iter()
+                    .map(|id| (format!("agents.list.{}.model", i), id)),
+            );
+        }
+    }
+    for (path, id) in refs {
+        let Some((provider, _)) = id.split_once('/') else {
+            continue;
+        };
+        let problem = if !is_allowed(policy, provider) {
+            Some(format!("provider {} is not allowed", provider))
+        } else if policy.local_providers_only && !openclaw_config::is_local_model(id, &providers) {
+            Some(format!("model {} is not on a local provider", id))
+        } else {
+            None
+        };
+        out.extend(problem.map(|message| violation(path, message)));
+    }
+    out
+}
+
+/// Where an agent's models.json `root` breaks `policy`.
+#[must_use]
+pub fn check_models_json(
+    policy: &ConfigPolicy,
+    agent_name: &str,
+    root: &Value,
+) -> Vec<PolicyViolation> {
+    let file = format!("agents/{}/agent/models.json", agent_name);
+    let providers = root.get("providers").cloned().unwrap_or_default();
+    let mut out = Vec::new();
+    check_providers(policy, &file, "providers", &providers, &mut out);
+    out
+}
+
+/// Violations in `after` that `before` doesn't already have.
+#[must_use]
+pub fn new_violations(
+    before: &[PolicyViolation],
+    after: Vec<PolicyViolation>,
+) -> Vec<PolicyViolation> {
+    after.into_iter().filter(|v| !before.contains(v)).collect()
+}
+
+fn first_error(violations: Vec<PolicyViolation>) -> Result<(), String> {
+    violations.first().map_or(Ok(()), |v| Err(v.to_error()))
+}
+
+/// Fails with the first violation that replacing openclaw.json `before` (None when there is
+/// none) with `root` would introduce.
+pub fn enforce_config_change(before: Option<&Value>, root: &Value) -> Result<(), String> {
+    let Some(policy) = load_policy()? else {
+        return Ok(());
+    };
+    let existing = before.map(|b| check_config(&policy, b)).unwrap_or_default();
+    first_error(new_violations(&existing, check_config(&policy, root)))
+}
+
+/// Fails with the first violation that writing `root` as openclaw.json would introduce.
+pub fn enforce_config(root: &Value) -> Result<(), String> {
+    enforce_config_change(openclaw_config::read_config_root().ok().as_ref(), root)
+}
+
+/// Fails with the first violation that writing `root` as the agent's models.json would
+/// introduce.
+pub fn enforce_models_json(agent_name: &str, root: &Value) -> Result<(), String> {
+    let Some(policy) = load_policy()? else {
+        return Ok(());
+    };
+    let existing = agents::read_models_json(agent_name)
+        .map(|b| check_models_json(&policy, agent_name, &b))
+        .unwrap_or_default();
+    first_error(new_violations(
+        &existing,
+        check_models_json(&policy, agent_name, root),
+    ))
+}
+
+/// The policy and where the current openclaw.json breaks it.
+pub fn get_config_policy() -> Result<PolicyStatus, String> {
+    let policy = load_policy()?;
+    let violations = match &policy {
+        Some(policy) => openclaw_config::read_config_root()
+            .map(|root| check_config(policy, &root))
+            .unwrap_or_default(),
+        None => vec![],
+    };
+    Ok(PolicyStatus {
+        policy,
+        path: policy_path().display().to_string(),
+        violations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> ConfigPolicy {
+        serde_json::from_value(serde_json::json!({
+            "locked": { "gateway.bind": "loopback" },
+            "local_providers_only": true
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_check_config() {
+        let mut root = serde_json::json!({
+            "models": { "providers": {
+                "ollama": { "baseUrl": "http://127.0.0.1:11434/v1" },
+                "box": { "baseUrl": "http://localhost:9000/v1" }
+            } },
+            "agents": { "defaults": { "model": { "primary": "ollama/qwen3:8b" } } }
+        });
+        assert!(check_config(&policy(), &root).is_empty());
+
+        root["gateway"] = serde_json::json!({ "bind": "lan" });
+        root["models"]["providers"]["groq"] =
+            serde_json::json!({ "baseUrl": "https://api.groq.com/openai/v1" });
+        root["agents"]["list"] =
+            serde_json::json!([{ "id": "dev", "model": "anthropic/claude-sonnet-4-5" }]);
+        let paths: Vec<String> = check_config(&policy(), &root)
+            .into_iter()
+            .map(|v| v.path)
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "gateway.bind",
+                "models.providers.groq",
+                "agents.list.0.model"
+            ]
+        );
+        let err = check_config(&policy(), &root)[0].to_error();
+        assert!(err.starts_with("policy_violation: gateway.bind: locked to \"loopback\""));
+
+        // An edit that leaves existing violations alone isn't blocked by them.
+        let before = check_config(&policy(), &root);
+        root["agents"]["defaults"]["maxConcurrent"] = 2.into();
+        assert!(new_violations(&before, check_config(&policy(), &root)).is_empty());
+        root["models"]["providers"]["openai"] = serde_json::json!({});
+        let added = new_violations(&before, check_config(&policy(), &root));
+        assert_eq!(added.len(), 1);
+        assert_eq!(added[0].path, "models.providers.openai");
+    }
+
+    #[test]
+    fn test_allowed_providers() {
+        let policy = ConfigPolicy {
+            allowed_providers: vec!["ollama".to_string(), "anthropic".to_string()],
+            ..ConfigPolicy::default()
+        };
+        let root = serde_json::json!({
+            "agents": { "defaults": { "models": {
+                "anthropic/claude-sonnet-4-5": {}, "openai/gpt-5": {}
+            } } }
+        });
+        let violations = check_config(&policy, &root);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].path, "agents.defaults.models.openai/gpt-5");
+        let models = serde_json::json!({ "providers": { "ollama": {}, "groq": {} } });
+        let violations = check_models_json(&policy, "main", &models);
+        assert_eq!(violations[0].path, "providers.groq");
+        assert_eq!(violations[0].file, "agents/main/agent/models.json");
+    }
+}
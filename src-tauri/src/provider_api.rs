@@ -0,0 +1,310 @@
+//! HTTP access to providers configured in openclaw.json, through their models endpoint: OpenAI
+//! style (`<baseUrl>/models` with a Bearer key) or Anthropic style (`/v1/models` with x-api-key).
+//! Used to test a provider's baseUrl and key before an agent trips over them, and to list the
+//! models a cloud provider currently offers.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::{Duration, Instant};
+
+use crate::openclaw_config;
+
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+/// Cloud providers whose model list can be fetched: name, default baseUrl, api, key env var.
+const CLOUD_PROVIDERS: [(&str, &str, &str, &str); 2] = [
+    (
+        "anthropic",
+        "https://api.anthropic.com",
+        "anthropic-messages",
+        "ANTHROPIC_API_KEY",
+    ),
+    (
+        "openai",
+        "https://api.openai.com/v1",
+        "openai-completions",
+        "OPENAI_API_KEY",
+    ),
+];
+/// OpenAI lists every model it serves; ids containing these aren't chat models.
+const NON_CHAT_MARKERS: [&str; 10] = [
+    "embedding",
+    "whisper",
+    "tts",
+    "dall-e",
+    "moderation",
+    "transcribe",
+    "realtime",
+    "audio",
+    "image",
+    "search",
+];
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiFlavor {
+    OpenAi,
+    Anthropic,
+}
+
+/// Anthropic when the provider's `api` says so or it points at api.anthropic.com; OpenAI style
+/// (which local runtimes speak too) otherwise.
+#[must_use]
+pub fn api_flavor(entry: &Value) -> ApiFlavor {
+    let api = entry.get("api").and_then(|v| v.as_str()).unwrap_or("");
+    let base_url = entry.get("baseUrl").and_then(|v| v.as_str()).unwrap_or("");
+    if api.starts_with("anthropic") || base_url.contains("anthropic.com") {
+        ApiFlavor::Anthropic
+    } else {
+        ApiFlavor::OpenAi
+    }
+}
+
+/// The models endpoint. OpenAI-style baseUrls already end in the version (…/v1); Anthropic's is
+/// the bare host.
+#[must_use]
+pub fn models_url(flavor: ApiFlavor, base_url: &str) -> String {
+    let base = base_url.trim_end_matches('/');
+    match flavor {
+        ApiFlavor::Anthropic if !base.ends_with("/v1") => format!("{}/v1/models", base),
+        _ => format!("{}/models", base),
+    }
+}
+
+/// The provider's apiKey, with a `${ENV_VAR}` reference looked up in the environment. None when
+/// unset or empty.
+#[must_use]
+pub fn resolve_api_key(entry: &Value) -> Option<String> {
+    let raw = entry.get("apiKey").and_then(|v| v.as_str())?.trim();
+    let key = match raw.strip_prefix("${").and_then(|r| r.strip_suffix('}')) {
+        Some(var) => std::env::var(var).ok()?,
+        None => raw.to_string(),
+    };
+    (!key.is_empty()).then_some(key)
+}
+
+/// A provider entry from openclaw.json models.providers.
+pub fn provider_entry(provider: &str) -> Result<Value, String> {
+    openclaw_config::read_config_root()?
+        .pointer("/models/providers")
+        .and_then(|p| p.get(provider))
+        .cloned()
+        .ok_or_else(|| format!("Provider {} is not in openclaw.json", provider))
+}
+
+fn agent(timeout: Duration) -> ureq::Agent {
+    ureq::AgentBuilder::new().timeout(timeout).build()
+}
+
+/// GETs the models endpoint with the provider's key. Any HTTP answer, error statuses included,
+/// comes back as (status, body); Err means the server couldn't be reached.
+pub(crate) fn get_models(entry: &Value, timeout: Duration) -> Result<(u16, String), String> {
+    let flavor = api_flavor(entry);
+    let base_url = entry.get("baseUrl").and_then(|v| v.as_str()).unwrap_or("");
+    let mut req = agent(timeout).get(&models_url(flavor, base_url));
+    if let Some(key) = resolve_api_key(entry) {
+        req = match flavor {
+            ApiFlavor::OpenAi => req.set("Authorization", &format!("Bearer {}", key)),
+            ApiFlavor::Anthropic => req
+                .set("x-api-key", &key)
+                .set("anthropic-version", ANTHROPIC_VERSION),
+        };
+    }
+    match req.call() {
+        Ok(resp) => Ok((resp.status(), resp.into_string().unwrap_or_default())),
+        Err(ureq::Error::Status(status, resp)) => {
+            Ok((status, resp.into_string().unwrap_or_default()))
+        }
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Model ids from an OpenAI- or Anthropic-style models response (both use `data[].id`).
+#[must_use]
+pub fn parse_model_ids(body: &str) -> Vec<String> {
+    serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|v| v.get("data").and_then(|d| d.as_array()).cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|m| m.get("id").and_then(|v| v.as_str()).map(String::from))
+        .collect()
+}
+
+/// Whether a listed model id can serve as an agent's chat model.
+#[must_use]
+pub fn is_chat_model(id: &str) -> bool {
+    let id = id.to_lowercase();
+    !NON_CHAT_MARKERS.iter().any(|m| id.contains(m))
+        && !["babbage", "davinci"].iter().any(|m| id.starts_with(m))
+}
+
+/// The provider's entry for a listing: the configured one, else the provider's defaults. A key
+/// missing from the entry is taken from openclaw.json `env`, then the process environment.
+pub(crate) fn cloud_entry(root: &Value, provider: &str) -> Result<Value, String> {
+    let (_, base_url, api, key_var) = CLOUD_PROVIDERS
+        .iter()
+        .find(|(name, ..)| *name == provider)
+        .ok_or_else(|| {
+            format!(
+                "Listing models is supported for anthropic and openai, not {}",
+                provider
+            )
+        })?;
+    let mut entry = root
+        .pointer("/models/providers")
+        .and_then(|p| p.get(provider))
+        .cloned()
+        .unwrap_or_else(|| serde_json::json!({ "baseUrl": base_url, "api": api }));
+    if resolve_api_key(&entry).is_none() {
+        let key = root
+            .pointer(&format!("/env/{}", key_var))
+            .and_then(|v| v.as_str())
+            .map(String::from)
+            .or_else(|| std::env::var(key_var).ok())
+            .filter(|k| !k.trim().is_empty())
+            .ok_or_else(|| format!("No API key stored for {}", provider))?;
+        if let Some(obj) = entry.as_object_mut() {
+            obj.insert("apiKey".to_string(), serde_json::json!(key.trim()));
+        }
+    }
+    Ok(entry)
+}
+
+/// Chat model ids ("provider/model") the provider's models endpoint lists for the stored key.
+pub fn get_cloud_provider_models(provider: &str) -> Result<Vec<String>, String> {
+    let entry = cloud_entry(&openclaw_config::read_config_root()?, provider)?;
+    let (status, body) = get_models(&entry, Duration::from_secs(15))?;
+    if !(200..300).contains(&status) {
+        return Err(format!(
+            "{} answered HTTP {}: {}",
+            provider,
+            status,
+            body.chars().take(300).collect::<String>()
+        ));
+    }
+    Ok(parse_model_ids(&body)
+        .into_iter()
+        .filter(|id| is_chat_model(id))
+        .map(|id| format!("{}/{}", provider, id))
+        .collect())
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProviderTestResult {
+    pub provider: String,
+    pub url: String,
+    pub flavor: ApiFlavor,
+    /// Reachable, and the key (if any) was accepted.
+    pub ok: bool,
+    /// HTTP status, when the server answered.
+    pub status: Option<u16>,
+    pub latency_ms: u64,
+    pub key_sent: bool,
+    /// Set when the server answered 401 or 403.
+    pub key_rejected: bool,
+    pub model_count: Option<usize>,
+    pub error: Option<String>,
+}
+
+/// Requests the provider's model list once and reports status, latency and any error.
+pub fn test_provider(provider: &str) -> Result<ProviderTestResult, String> {
+    let entry = provider_entry(provider)?;
+    let base_url = entry
+        .get("baseUrl")
+        .and_then(|v| v.as_str())
+        .filter(|u| !u.trim().is_empty())
+        .ok_or_else(|| format!("Provider {} has no baseUrl", provider))?;
+    let flavor = api_flavor(&entry);
+    let mut result = ProviderTestResult {
+        provider: provider.to_string(),
+        url: models_url(flavor, base_url),
+        flavor,
+        ok: false,
+        status: None,
+        latency_ms: 0,
+        key_sent: resolve_api_key(&entry).is_some(),
+        key_rejected: false,
+        model_count: None,
+        error: None,
+    };
+    let started = Instant::now();
+    let response = get_models(&entry, Duration::from_secs(10));
+    result.latency_ms = started.elapsed().as_millis() as u64;
+    match response {
+        Ok((status, body)) if (200..300).contains(&status) => {
+            result.status = Some(status);
+            result.ok = true;
+            result.model_count = Some(parse_model_ids(&body).len());
+        }
+        Ok((status, body)) => {
+            result.status = Some(status);
+            result.key_rejected = status == 401 || status == 403;
+            result.error = Some(format!(
+                "HTTP {}: {}",
+                status,
+                body.chars().take(300).collect::<String>()
+            ));
+        }
+        Err(e) => result.error = Some(e),
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_models_url() {
+        let anthropic = serde_json::json!({ "baseUrl": "https://api.anthropic.com", "api": "anthropic-messages" });
+        let flavor = api_flavor(&anthropic);
+        assert_eq!(flavor, ApiFlavor::Anthropic);
+        assert_eq!(
+            models_url(flavor, "https://api.anthropic.com/"),
+            "https://api.anthropic.com/v1/models"
+        );
+        let ollama = serde_json::json!({ "baseUrl": "http://127.0.0.1:11434/v1", "api": "openai-completions" });
+        assert_eq!(api_flavor(&ollama), ApiFlavor::OpenAi);
+        assert_eq!(
+            models_url(ApiFlavor::OpenAi, "http://127.0.0.1:11434/v1"),
+            "http://127.0.0.1:11434/v1/models"
+        );
+    }
+
+    #[test]
+    fn test_resolve_api_key_and_parse() {
+        assert_eq!(
+            resolve_api_key(&serde_json::json!({ "apiKey": " sk-1 " })).as_deref(),
+            Some("sk-1")
+        );
+        assert_eq!(resolve_api_key(&serde_json::json!({ "apiKey": "" })), None);
+        assert_eq!(
+            resolve_api_key(&serde_json::json!({ "apiKey": "${OPENCLAW_TEST_UNSET_KEY_VAR}" })),
+            None
+        );
+        let body = r#"{"data":[{"id":"gpt-5-mini","object":"model"},{"id":"gpt-5"}]}"#;
+        assert_eq!(parse_model_ids(body), vec!["gpt-5-mini", "gpt-5"]);
+        assert!(parse_model_ids("not json").is_empty());
+    }
+
+    #[test]
+    fn test_cloud_entry_and_chat_filter() {
+        let root = serde_json::json!({ "env": { "ANTHROPIC_API_KEY": "sk-ant-1" } });
+        let entry = cloud_entry(&root, "anthropic").unwrap();
+        assert_eq!(api_flavor(&entry), ApiFlavor::Anthropic);
+        assert_eq!(entry["apiKey"], "sk-ant-1");
+        let root = serde_json::json!({ "models": { "providers": { "openai": {
+            "baseUrl": "https://proxy.example/v1", "apiKey": "sk-2"
+        } } } });
+        assert_eq!(
+            cloud_entry(&root, "openai").unwrap()["baseUrl"],
+            "https://proxy.example/v1"
+        );
+        assert!(cloud_entry(&root, "ollama").is_err());
+        assert!(is_chat_model("gpt-5-mini"));
+        assert!(is_chat_model("claude-sonnet-4-5"));
+        assert!(!is_chat_model("text-embedding-3-small"));
+        assert!(!is_chat_model("gpt-4o-mini-tts"));
+        assert!(!is_chat_model("davinci-002"));
+    }
+}
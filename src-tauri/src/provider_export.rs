@@ -0,0 +1,234 @@
+//! Exports the providers and models in openclaw.json for other tools: a LiteLLM proxy
+//! `config.yaml` or a continue.dev `config.json`. Translation is pure for unit tests; the YAML is
+//! written by hand (strings as JSON-quoted scalars, which YAML accepts).
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+
+use crate::openclaw_config;
+use crate::provider_api::{self, ApiFlavor};
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    /// LiteLLM proxy config.yaml.
+    Litellm,
+    /// continue.dev config.json.
+    Continue,
+}
+
+/// One model of one provider, flattened from models.providers.
+struct ExportModel<'a> {
+    provider: &'a str,
+    id: &'a str,
+    entry: &'a Value,
+}
+
+impl ExportModel<'_> {
+    /// A numeric field of this model's entry in the provider's models array.
+    fn entry_model_u64(&self, key: &str) -> Option<u64> {
+        self.entry
+            .get("models")?
+            .as_array()?
+            .iter()
+            .find(|m| m.get("id").and_then(|v| v.as_str()) == Some(self.id))?
+            .get(key)?
+            .as_u64()
+    }
+}
+
+fn export_models(providers: &Value) -> Vec<ExportModel<'_>> {
+    let Some(providers) = providers.as_object() else {
+        return vec![];
+    };
+    providers
+        .iter()
+        .flat_map(|(provider, entry)| {
+            entry
+                .get("models")
+                .and_then(|m| m.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|m| m.get("id").and_then(|v| v.as_str()).or(m.as_str()))
+                .map(move |id| ExportModel {
+                    provider,
+                    id,
+                    entry,
+                })
+        })
+        .collect()
+}
+
+fn base_url(entry: &Value) -> Option<&str> {
+    entry
+        .get("baseUrl")
+        .and_then(|v| v.as_str())
+        .map(|u| u.trim_end_matches('/'))
+        .filter(|u| !u.is_empty())
+}
+
+/// The env var named by a `${VAR}` apiKey.
+fn key_env_var(entry: &Value) -> Option<&str> {
+    entry
+        .get("apiKey")
+        .and_then(|v| v.as_str())
+        .and_then(|k| k.trim().strip_prefix("${"))
+        .and_then(|k| k.strip_suffix('}'))
+}
+
+/// The provider's key: literal, `${VAR}` from the environment, else from openclaw.json `env`.
+fn resolved_key(entry: &Value, env: &Value) -> Option<String> {
+    provider_api::resolve_api_key(entry).or_else(|| {
+        env.get(key_env_var(entry)?)
+            .and_then(|v| v.as_str())
+            .map(String::from)
+    })
+}
+
+fn yaml_str(s: &str) -> String {
+    serde_json::to_string(s).unwrap_or_default()
+}
+
+/// LiteLLM `model_list`: model_name is the openclaw id. Anthropic providers map to
+/// `anthropic/<id>`, everything else to `openai/<id>` with the provider's baseUrl. `${VAR}`
+/// keys become `os.environ/VAR`; keyless (local) providers get a dummy key.
+#[must_use]
+pub fn to_litellm_yaml(providers: &Value) -> String {
+    let mut out = String::from("model_list:\n");
+    for m in export_models(providers) {
+        let flavor = provider_api::api_flavor(m.entry);
+        let prefix = match flavor {
+            ApiFlavor::Anthropic => "anthropic",
+            ApiFlavor::OpenAi => "openai",
+        };
+        out.push_str(&format!(
+            "  - model_name: {}\n    litellm_params:\n      model: {}\n",
+            yaml_str(&format!("{}/{}", m.provider, m.id)),
+            yaml_str(&format!("{}/{}", prefix, m.id))
+        ));
+        if let Some(url) = base_url(m.entry) {
+            out.push_str(&format!("      api_base: {}\n", yaml_str(url)));
+        }
+        let key = match key_env_var(m.entry) {
+            Some(var) => Some(format!("os.environ/{}", var)),
+            None => provider_api::resolve_api_key(m.entry),
+        };
+        let key = key.unwrap_or_else(|| "none".to_string());
+        out.push_str(&format!("      api_key: {}\n", yaml_str(&key)));
+        if let Some(n) = m.entry_model_u64("contextWindow") {
+            out.push_str(&format!("    model_info:\n      max_input_tokens: {}\n", n));
+        }
+    }
+    out
+}
+
+/// continue.dev `models`, primary first. Ollama and LM Studio use Continue's own providers
+/// (Ollama's apiBase without /v1); Anthropic providers use "anthropic", the rest "openai" with
+/// an apiBase. Continue's JSON config can't reference env vars, so keys are resolved.
+#[must_use]
+pub fn to_continue_config(providers: &Value, env: &Value, primary: Option<&str>) -> Value {
+    let mut models: Vec<Value> = export_models(providers)
+        .iter()
+        .map(|m| {
+            let flavor = provider_api::api_flavor(m.entry);
+            let url = base_url(m.entry);
+            let (provider, api_base) = match (m.provider, flavor) {
+                ("ollama", _) => (
+                    "ollama",
+                    url.map(|u| u.strip_suffix("/v1").unwrap_or(u).to_string()),
+                ),
+                ("lmstudio" | "lm-studio", _) => ("lmstudio", url.map(String::from)),
+                (_, ApiFlavor::Anthropic) => ("anthropic", None),
+                _ => ("openai", url.map(String::from)),
+            };
+            let mut model = serde_json::json!({
+                "title": format!("{}/{}", m.provider, m.id),
+                "provider": provider,
+                "model": m.id,
+            });
+            if let Some(base) = api_base {
+                model["apiBase"] = serde_json::json!(base);
+            }
+            if let Some(key) = resolved_key(m.entry, env) {
+                model["apiKey"] = serde_json::json!(key);
+            }
+            if let Some(n) = m.entry_model_u64("contextWindow") {
+                model["contextLength"] = serde_json::json!(n);
+            }
+            model
+        })
+        .collect();
+    if let Some(primary) = primary {
+        if let Some(i) = models.iter().position(|m| m["title"] == primary) {
+            let first = models.remove(i);
+            models.insert(0, first);
+        }
+    }
+    serde_json::json!({ "models": models })
+}
+
+/// Translates openclaw.json's providers to `format`, writes it to `path` when given, and returns
+/// the text.
+pub fn export_provider_config(format: ExportFormat, path: Option<&str>) -> Result<String, String> {
+    let root = openclaw_config::read_config_root()?;
+    let providers = root
+        .pointer("/models/providers")
+        .cloned()
+        .unwrap_or(serde_json::json!({}));
+    if export_models(&providers).is_empty() {
+        return Err("No provider models in openclaw.json to export".to_string());
+    }
+    let content = match format {
+        ExportFormat::Litellm => to_litellm_yaml(&providers),
+        ExportFormat::Continue => {
+            let env = root.get("env").cloned().unwrap_or(serde_json::json!({}));
+            let primary = root
+                .pointer("/agents/defaults/model/primary")
+                .and_then(|v| v.as_str());
+            serde_json::to_string_pretty(&to_continue_config(&providers, &env, primary))
+                .map_err(|e| e.to_string())?
+        }
+    };
+    if let Some(path) = path {
+        fs::write(path, &content).map_err(|e| e.to_string())?;
+    }
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn providers() -> Value {
+        serde_json::json!({
+            "anthropic": { "baseUrl": "https://api.anthropic.com", "api": "anthropic-messages",
+                "apiKey": "${OPENCLAW_TEST_EXPORT_KEY}", "models": [{ "id": "claude-sonnet-4-5" }] },
+            "ollama": { "baseUrl": "http://127.0.0.1:11434/v1", "api": "openai-completions",
+                "models": [{ "id": "qwen3:8b", "contextWindow": 32768 }] }
+        })
+    }
+
+    #[test]
+    fn test_to_litellm_yaml() {
+        let yaml = to_litellm_yaml(&providers());
+        assert!(yaml.starts_with("model_list:\n  - model_name: \"anthropic/claude-sonnet-4-5\"\n"));
+        assert!(yaml.contains("      model: \"anthropic/claude-sonnet-4-5\"\n"));
+        assert!(yaml.contains("      api_key: \"os.environ/OPENCLAW_TEST_EXPORT_KEY\"\n"));
+        assert!(yaml.contains("      model: \"openai/qwen3:8b\"\n      api_base: \"http://127.0.0.1:11434/v1\"\n      api_key: \"none\"\n"));
+        assert!(yaml.contains("    model_info:\n      max_input_tokens: 32768\n"));
+    }
+
+    #[test]
+    fn test_to_continue_config() {
+        let env = serde_json::json!({ "OPENCLAW_TEST_EXPORT_KEY": "sk-ant-1" });
+        let config = to_continue_config(&providers(), &env, Some("ollama/qwen3:8b"));
+        let models = config["models"].as_array().unwrap();
+        assert_eq!(models[0]["provider"], "ollama");
+        assert_eq!(models[0]["apiBase"], "http://127.0.0.1:11434");
+        assert_eq!(models[0]["contextLength"], 32768);
+        assert_eq!(models[1]["provider"], "anthropic");
+        assert_eq!(models[1]["apiKey"], "sk-ant-1");
+        assert!(models[1].get("apiBase").is_none());
+    }
+}
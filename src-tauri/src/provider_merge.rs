@@ -0,0 +1,328 @@
+//! Three-way merge of provider entries between openclaw.json and an agent's models.json, using the
+//! providers recorded at the last sync (~/.openclaw/host-config/sync/<agent>.json) as the base.
+//! Fields changed on one side merge cleanly; fields changed differently on both sides are
+//! conflicts for the user to resolve. apiKey is never merged or stored in snapshots: each side
+//! keeps its own, and a provider new to one side takes the other side's key.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::agents::{self, ProviderSyncStatus};
+use crate::{openclaw_config, policy};
+
+type Providers = Map<String, Value>;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeSide {
+    Openclaw,
+    Agent,
+}
+
+/// A field (or, when `field` is None, a whole provider that one side removed and the other
+/// changed) edited differently on both sides since the last sync. Missing values are null.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SyncConflict {
+    pub provider: String,
+    pub field: Option<String>,
+    pub base: Value,
+    pub openclaw: Value,
+    pub agent: Value,
+}
+
+/// The user's pick for one conflict.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ConflictResolution {
+    pub provider: String,
+    pub field: Option<String>,
+    pub take: MergeSide,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProviderMergeOutcome {
+    /// False when unresolved conflicts remain; nothing was written.
+    pub applied: bool,
+    pub conflicts: Vec<SyncConflict>,
+    pub status: ProviderSyncStatus,
+}
+
+fn snapshot_path(agent_name: &str) -> PathBuf {
+    openclaw_config::host_config_dir()
+        .join("sync")
+        .join(format!("{}.json", agent_name))
+}
+
+fn without_api_keys(providers: &Providers) -> Providers {
+    let mut out = providers.clone();
+    for p in out.values_mut().filter_map(|p| p.as_object_mut()) {
+        p.remove("apiKey");
+    }
+    out
+}
+
+/// Records `providers` (without API keys) as the base for the agent's next three-way merge.
+pub fn record_sync(agent_name: &str, providers: &Providers) -> Result<(), String> {
+    let path = snapshot_path(agent_name);
+    let dir = path.parent().ok_or("invalid path")?;
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    fs::write(
+        &path,
+        serde_json::to_string_pretty(&without_api_keys(providers)).map_err(|e| e.to_string())?,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Moves the agent's sync base along with a renamed agent. Missing snapshots are fine.
+pub fn rename_snapshot(old: &str, new: &str) {
+    let _ = fs::rename(snapshot_path(old), snapshot_path(new));
+}
+
+fn load_snapshot(agent_name: &str) -> Option<Providers> {
+    let content = fs::read_to_string(snapshot_path(agent_name)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Merges one value: unchanged sides defer to the changed one. Err on a real conflict.
+fn merge_value(
+    base: Option<&Value>,
+    openclaw: Option<&Value>,
+    agent: Option<&Value>,
+) -> Result<Option<Value>, ()> {
+    if openclaw == agent || agent == base {
+        Ok(openclaw.cloned())
+    } else if openclaw == base {
+        Ok(agent.cloned())
+    } else {
+        Err(())
+    }
+}
+
+fn resolution(
+    resolutions: &[ConflictResolution],
+    provider: &str,
+    field: Option<&str>,
+) -> Option<MergeSide> {
+    resolutions
+        .iter()
+        .find(|r| r.provider == provider && r.field.as_deref() == field)
+        .map(|r| r.take)
+}
+
+fn new_conflict(
+    provider: &str,
+    field: Option<&str>,
+    base: Option<&Value>,
+    openclaw: Option<&Value>,
+    agent: Option<&Value>,
+) -> SyncConflict {
+    SyncConflict {
+        provider: provider.to_string(),
+        field: field.map(String::from),
+        base: base.cloned().unwrap_or(Value::Null),
+        openclaw: openclaw.cloned().unwrap_or(Value::Null),
+        agent: agent.cloned().unwrap_or(Value::Null),
+    }
+}
+
+/// Three-way merges provider maps. Returns the merged providers (apiKey from openclaw.json, else
+/// the agent) and the conflicts no resolution covers; unresolved fields take openclaw's value.
+/// With no base (never synced), additions on either side merge and differing fields conflict.
+#[must_use]
+pub fn three_way_merge(
+    base: &Providers,
+    openclaw: &Providers,
+    agent: &Providers,
+    resolutions: &[ConflictResolution],
+) -> (Providers, Vec<SyncConflict>) {
+    let (base, oc, ag) = (
+        without_api_keys(base),
+        without_api_keys(openclaw),
+        without_api_keys(agent),
+    );
+    let names: BTreeSet<&String> = base.keys().chain(oc.keys()).chain(ag.keys()).collect();
+    let mut merged = Providers::new();
+    let mut conflicts = Vec::new();
+    for name in names {
+        let (b, o, a) = (base.get(name), oc.get(name), ag.get(name));
+        let value = match (o.and_then(|v| v.as_object()), a.and_then(|v| v.as_object())) {
+            (Some(o_obj), Some(a_obj)) => {
+                let b_obj = b.and_then(|v| v.as_object());
+                let keys: BTreeSet<&String> = o_obj.keys().chain(a_obj.keys()).collect();
+                let mut entry = Map::new();
+                for key in keys {
+                    let (bv, ov, av) = (
+                        b_obj.and_then(|m| m.get(key)),
+                        o_obj.get(key),
+                        a_obj.get(key),
+                    );
+                    let picked = merge_value(bv, ov, av).unwrap_or_else(|()| {
+                        match resolution(resolutions, name, Some(key)) {
+                            Some(MergeSide::Agent) => av.cloned(),
+                            Some(MergeSide::Openclaw) => ov.cloned(),
+                            None => {
+                                conflicts.push(new_conflict(name, Some(key), bv, ov, av));
+                                ov.cloned()
+                            }
+                        }
+                    });
+                    if let Some(v) = picked {
+                        entry.insert(key.clone(), v);
+                    }
+                }
+                Some(Value::Object(entry))
+            }
+            _ => merge_value(b, o, a).unwrap_or_else(|()| {
+                match resolution(resolutions, name, None) {
+                    Some(MergeSide::Agent) => a.cloned(),
+                    Some(MergeSide::Openclaw) => o.cloned(),
+                    None => {
+                        conflicts.push(new_conflict(name, None, b, o, a));
+                        o.cloned()
+                    }
+                }
+            }),
+        };
+        if let Some(mut v) = value {
+            let key = openclaw
+                .get(name)
+                .and_then(|p| p.get("apiKey"))
+                .or_else(|| agent.get(name).and_then(|p| p.get("apiKey")));
+            if let (Some(obj), Some(key)) = (v.as_object_mut(), key) {
+                obj.insert("apiKey".to_string(), key.clone());
+            }
+            merged.insert(name.clone(), v);
+        }
+    }
+    (merged, conflicts)
+}
+
+/// Three-way merges the agent's providers with openclaw.json. Writes both files and records the
+/// new base only when every conflict is resolved; otherwise returns the conflicts untouched.
+/// Both files are checked against the team policy before either is written.
+pub fn merge_agent_providers(
+    agent_name: &str,
+    resolutions: &[ConflictResolution],
+) -> Result<ProviderMergeOutcome, String> {
+    let openclaw_value = openclaw_config::get_openclaw_providers_raw()?;
+    let openclaw = openclaw_value.as_object().cloned().unwrap_or_default();
+    let agent_path = agents::agent_models_path(agent_name);
+    let mut agent_root: Value = match fs::read_to_string(&agent_path) {
+        Ok(content) => serde_json::from_str(&content).map_err(|e| e.to_string())?,
+        Err(_) => serde_json::json!({ "providers": {} }),
+    };
+    let agent = agent_root
+        .get("providers")
+        .and_then(|p| p.as_object())
+        .cloned()
+        .unwrap_or_default();
+    let base = load_snapshot(agent_name).unwrap_or_default();
+
+    let (merged, conflicts) = three_way_merge(&base, &openclaw, &agent, resolutions);
+    if !conflicts.is_empty() {
+        return Ok(ProviderMergeOutcome {
+            applied: false,
+            conflicts,
+            status: agents::get_provider_sync_status(agent_name),
+        });
+    }
+
+    let agent_providers = agent_root
+        .as_object_mut()
+        .ok_or("models.json root not an object")?
+        .entry("providers")
+        .or_insert_with(|| serde_json::json!({}))
+        .as_object_mut()
+        .ok_or("models.json providers not an object")?;
+    agents::merge_providers(agent_providers, &merged, true);
+
+    let mut root = openclaw_config::read_config_root()?;
+    let openclaw_providers = openclaw_config::providers_mut(&mut root)?;
+    agents::merge_providers(openclaw_providers, &merged, true);
+
+    policy::enforce_models_json(agent_name, &agent_root)?;
+    policy::enforce_config(&root)?;
+    if let Some(dir) = agent_path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    agents::write_models_json(agent_name, &agent_root)?;
+    openclaw_config::write_config_root(&root)?;
+
+    record_sync(agent_name, &merged)?;
+    Ok(ProviderMergeOutcome {
+        applied: true,
+        conflicts,
+        status: agents::get_provider_sync_status(agent_name),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn providers(v: Value) -> Providers {
+        v.as_object().cloned().unwrap()
+    }
+
+    #[test]
+    fn test_three_way_merge_clean() {
+        let base = providers(serde_json::json!({
+            "ollama": { "baseUrl": "http://127.0.0.1:11434/v1", "api": "openai-completions" },
+            "old": { "baseUrl": "https://old.example/v1" }
+        }));
+        let openclaw = providers(serde_json::json!({
+            "ollama": { "baseUrl": "http://127.0.0.1:11434/v1", "api": "openai-responses" },
+            "anthropic": { "apiKey": "oc-key" }
+        }));
+        let agent = providers(serde_json::json!({
+            "ollama": { "baseUrl": "http://10.0.0.5:11434/v1", "api": "openai-completions", "apiKey": "ag" },
+            "old": { "baseUrl": "https://old.example/v1" }
+        }));
+        let (merged, conflicts) = three_way_merge(&base, &openclaw, &agent, &[]);
+        assert!(conflicts.is_empty());
+        assert_eq!(merged["ollama"]["baseUrl"], "http://10.0.0.5:11434/v1");
+        assert_eq!(merged["ollama"]["api"], "openai-responses");
+        assert_eq!(merged["ollama"]["apiKey"], "ag");
+        assert_eq!(merged["anthropic"]["apiKey"], "oc-key");
+        assert!(!merged.contains_key("old"));
+    }
+
+    #[test]
+    fn test_three_way_merge_conflicts_and_resolutions() {
+        let base = providers(serde_json::json!({
+            "ollama": { "baseUrl": "http://127.0.0.1:11434/v1" },
+            "vllm": { "baseUrl": "http://127.0.0.1:8000/v1" }
+        }));
+        let openclaw = providers(serde_json::json!({
+            "ollama": { "baseUrl": "http://a:11434/v1" }
+        }));
+        let agent = providers(serde_json::json!({
+            "ollama": { "baseUrl": "http://b:11434/v1" },
+            "vllm": { "baseUrl": "http://127.0.0.1:8001/v1" }
+        }));
+        let (_, conflicts) = three_way_merge(&base, &openclaw, &agent, &[]);
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(conflicts[0].field.as_deref(), Some("baseUrl"));
+        assert_eq!(conflicts[1].provider, "vllm");
+        assert!(conflicts[1].field.is_none());
+
+        let resolutions = vec![
+            ConflictResolution {
+                provider: "ollama".to_string(),
+                field: Some("baseUrl".to_string()),
+                take: MergeSide::Agent,
+            },
+            ConflictResolution {
+                provider: "vllm".to_string(),
+                field: None,
+                take: MergeSide::Openclaw,
+            },
+        ];
+        let (merged, conflicts) = three_way_merge(&base, &openclaw, &agent, &resolutions);
+        assert!(conflicts.is_empty());
+        assert_eq!(merged["ollama"]["baseUrl"], "http://b:11434/v1");
+        assert!(!merged.contains_key("vllm"));
+    }
+}
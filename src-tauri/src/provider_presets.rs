@@ -0,0 +1,214 @@
+//! Presets for popular OpenAI-compatible cloud providers, so adding one to openclaw.json is a
+//! single call with the API key instead of hand-entering baseUrl, api and model ids.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::openclaw_config;
+
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct PresetModel {
+    pub id: &'static str,
+    pub context_window: u64,
+}
+
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct ProviderPreset {
+    /// Provider name in openclaw.json.
+    pub name: &'static str,
+    pub label: &'static str,
+    pub base_url: &'static str,
+    pub api: &'static str,
+    /// Environment variable referenced when no key is given.
+    pub key_env: &'static str,
+    pub models: &'static [PresetModel],
+}
+
+pub const PROVIDER_PRESETS: [ProviderPreset; 4] = [
+    ProviderPreset {
+        name: "groq",
+        label: "Groq",
+        base_url: "https://api.groq.com/openai/v1",
+        api: "openai-completions",
+        key_env: "GROQ_API_KEY",
+        models: &[
+            PresetModel {
+                id: "llama-3.3-70b-versatile",
+                context_window: 131072,
+            },
+            PresetModel {
+                id: "llama-3.1-8b-instant",
+                context_window: 131072,
+            },
+            PresetModel {
+                id: "openai/gpt-oss-120b",
+                context_window: 131072,
+            },
+        ],
+    },
+    ProviderPreset {
+        name: "mistral",
+        label: "Mistral",
+        base_url: "https://api.mistral.ai/v1",
+        api: "openai-completions",
+        key_env: "MISTRAL_API_KEY",
+        models: &[
+            PresetModel {
+                id: "mistral-large-latest",
+                context_window: 131072,
+            },
+            PresetModel {
+                id: "mistral-small-latest",
+                context_window: 131072,
+            },
+            PresetModel {
+                id: "codestral-latest",
+                context_window: 256000,
+            },
+        ],
+    },
+    ProviderPreset {
+        name: "together",
+        label: "Together AI",
+        base_url: "https://api.together.xyz/v1",
+        api: "openai-completions",
+        key_env: "TOGETHER_API_KEY",
+        models: &[
+            PresetModel {
+                id: "meta-llama/Llama-3.3-70B-Instruct-Turbo",
+                context_window: 131072,
+            },
+            PresetModel {
+                id: "Qwen/Qwen2.5-72B-Instruct-Turbo",
+                context_window: 32768,
+            },
+            PresetModel {
+                id: "deepseek-ai/DeepSeek-V3",
+                context_window: 131072,
+            },
+        ],
+    },
+    ProviderPreset {
+        name: "deepseek",
+        label: "DeepSeek",
+        base_url: "https://api.deepseek.com/v1",
+        api: "openai-completions",
+        key_env: "DEEPSEEK_API_KEY",
+        models: &[
+            PresetModel {
+                id: "deepseek-chat",
+                context_window: 128000,
+            },
+            PresetModel {
+                id: "deepseek-reasoner",
+                context_window: 128000,
+            },
+        ],
+    },
+];
+
+#[must_use]
+pub fn find_preset(name: &str) -> Option<&'static ProviderPreset> {
+    PROVIDER_PRESETS
+        .iter()
+        .find(|p| p.name.eq_ignore_ascii_case(name))
+}
+
+/// Writes the preset's provider entry (keeping models already listed under it) and registers
+/// its models under agents.defaults.models. Without a key an existing one is kept, else the entry
+/// references the preset's env var. Returns the openclaw ids of the preset's models.
+pub fn apply_preset(
+    root: &mut Value,
+    preset: &ProviderPreset,
+    key: Option<&str>,
+) -> Result<Vec<String>, String> {
+    let providers = openclaw_config::providers_mut(root)?;
+    let entry = providers
+        .entry(preset.name)
+        .or_insert_with(|| serde_json::json!({ "models": [] }))
+        .as_object_mut()
+        .ok_or_else(|| format!("Provider {} entry not an object", preset.name))?;
+    entry.insert("baseUrl".to_string(), serde_json::json!(preset.base_url));
+    entry.insert("api".to_string(), serde_json::json!(preset.api));
+    match key.map(str::trim).filter(|k| !k.is_empty()) {
+        Some(k) => {
+            entry.insert("apiKey".to_string(), serde_json::json!(k));
+        }
+        None => {
+            entry
+                .entry("apiKey")
+                .or_insert_with(|| serde_json::json!(format!("${{{}}}", preset.key_env)));
+        }
+    }
+
+    let mut ids = Vec::new();
+    for model in preset.models {
+        openclaw_config::register_provider_model(root, preset.name, model.id)?;
+        if let Some(entry) = root
+            .pointer_mut(&format!("/models/providers/{}/models", preset.name))
+            .and_then(|m| m.as_array_mut())
+            .and_then(|models| {
+                models
+                    .iter_mut()
+                    .find(|m| m.get("id").and_then(|v| v.as_str()) == Some(model.id))
+            })
+            .and_then(|m| m.as_object_mut())
+        {
+            entry
+                .entry("contextWindow")
+                .or_insert_with(|| serde_json::json!(model.context_window));
+        }
+        ids.push(format!("{}/{}", preset.name, model.id));
+    }
+    Ok(ids)
+}
+
+/// Adds (or refreshes) the preset's provider in openclaw.json.
+pub fn add_provider_from_preset(preset: &str, key: Option<&str>) -> Result<Vec<String>, String> {
+    let preset = find_preset(preset).ok_or_else(|| {
+        let names: Vec<&str> = PROVIDER_PRESETS.iter().map(|p| p.name).collect();
+        format!(
+            "Unknown provider preset {}; known: {}",
+            preset,
+            names.join(", ")
+        )
+    })?;
+    let mut root = openclaw_config::read_config_root()?;
+    let ids = apply_preset(&mut root, preset, key)?;
+    openclaw_config::write_config_root(&root)?;
+    Ok(ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_preset() {
+        let mut root = serde_json::json!({ "agents": { "defaults": {} }, "models": { "providers": {
+            "deepseek": { "baseUrl": "http://old", "models": [{ "id": "deepseek-chat", "contextWindow": 65536 }, { "id": "custom" }] }
+        } } });
+        let preset = find_preset("DeepSeek").unwrap();
+        let ids = apply_preset(&mut root, preset, None).unwrap();
+        assert_eq!(
+            ids,
+            vec!["deepseek/deepseek-chat", "deepseek/deepseek-reasoner"]
+        );
+        let entry = &root["models"]["providers"]["deepseek"];
+        assert_eq!(entry["baseUrl"], "https://api.deepseek.com/v1");
+        assert_eq!(entry["apiKey"], "${DEEPSEEK_API_KEY}");
+        let models = entry["models"].as_array().unwrap();
+        assert_eq!(models.len(), 3);
+        assert_eq!(models[0]["contextWindow"], 65536);
+        assert_eq!(models[2]["contextWindow"], 128000);
+        assert!(root["agents"]["defaults"]["models"]
+            .get("deepseek/deepseek-reasoner")
+            .is_some());
+
+        apply_preset(&mut root, find_preset("groq").unwrap(), Some(" gsk-1 ")).unwrap();
+        assert_eq!(root["models"]["providers"]["groq"]["apiKey"], "gsk-1");
+        apply_preset(&mut root, find_preset("groq").unwrap(), None).unwrap();
+        assert_eq!(root["models"]["providers"]["groq"]["apiKey"], "gsk-1");
+        assert!(find_preset("nope").is_none());
+    }
+}
@@ -0,0 +1,451 @@
+//! Other machines running openclaw (a headless server, a second desktop), managed over SSH. Hosts
+//! are registered in app settings; runtime detection, gateway status and openclaw.json reads and
+//! writes run through `ssh`/`scp` against the remote `~/.openclaw`, so keys and known_hosts come
+//! from the user's own SSH setup and a password prompt fails instead of hanging (BatchMode).
+//! Remote running checks use `curl` on the host. Argument building and output parsing are pure
+//! functions for unit tests.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+use std::time::Duration;
+
+use crate::detection::{self, LLMStatus, LocalLLMDetection};
+use crate::openclaw_config::{self, OpenClawConfigUpdates, OpenClawConfigView};
+use crate::{gateway, operations, policy, settings};
+
+const CONNECT_TIMEOUT_SECS: u32 = 10;
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
+/// Importing vllm on the host can take a while on a cold start.
+const DETECT_TIMEOUT: Duration = Duration::from_secs(60);
+/// Exit status of ssh itself failing (connection, auth, host key).
+const SSH_FAILED: i32 = 255;
+/// openclaw.json relative to the remote home directory.
+const REMOTE_CONFIG: &str = ".openclaw/openclaw.json";
+const UPLOAD_NAME: &str = ".openclaw-host-config-upload.json";
+
+/// Prints `<runtime>.path=`, `.version=` and `.running=1` lines for the runtimes it finds.
+const DETECT_SCRIPT: &str = r#"probe() {
+  p=$(command -v "$1" 2>/dev/null || { [ -x "$2" ] && echo "$2"; }) || return 0
+  echo "$1.path=$p"
+  echo "$1.version=$("$p" --version 2>/dev/null | head -n 1)"
+}
+listening() { curl -s -o /dev/null -m 2 "http://127.0.0.1:$2/" && echo "$1.running=1"; }
+probe ollama ""
+probe lms "$HOME/.lmstudio/bin/lms"
+v=$(python3 -c "import vllm; print(getattr(vllm, '__version__', 'unknown'))" 2>/dev/null) \
+  && echo "vllm.version=$v"
+listening ollama 11434
+listening lms 1234
+listening vllm 8000
+true"#;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct RemoteHost {
+    /// Unique label shown in the UI, e.g. "homelab".
+    pub name: String,
+    /// Hostname, IP address or an alias from ~/.ssh/config.
+    pub host: String,
+    pub user: Option<String>,
+    pub port: Option<u16>,
+    /// Private key to use instead of the agent and ~/.ssh defaults.
+    pub identity_file: Option<String>,
+    /// `openclaw` on the host when it isn't on the login shell's PATH.
+    pub openclaw_path: Option<String>,
+}
+
+impl RemoteHost {
+    /// `user@host`, or just the host.
+    #[must_use]
+    pub fn destination(&self) -> String {
+        match &self.user {
+            Some(user) => format!("{}@{}", user, self.host),
+            None => self.host.clone(),
+        }
+    }
+}
+
+fn check_field(label: &str, value: &str, allowed: &str) -> Result<(), String> {
+    let valid = !value.is_empty()
+        && !value.starts_with('-')
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || allowed.contains(c));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid remote host {}: {}", label, value))
+    }
+}
+
+/// Trims `host`'s fields, drops empty optional ones and rejects values ssh would misread.
+pub fn normalize_remote_host(mut host: RemoteHost) -> Result<RemoteHost, String> {
+    let trim = |v: Option<String>| v.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    host.name = host.name.trim().to_string();
+    host.host = host.host.trim().to_string();
+    host.user = trim(host.user);
+    host.identity_file = trim(host.identity_file);
+    host.openclaw_path = trim(host.openclaw_path);
+    check_field("name", &host.name, "-_.")?;
+    // Colons for IPv6 addresses.
+    check_field("address", &host.host, "-_.:")?;
+    if let Some(user) = &host.user {
+        check_field("user", user, "-_.")?;
+    }
+    if host.port == Some(0) {
+        return Err("Invalid remote host port: 0".to_string());
+    }
+    if host
+        .identity_file
+        .as_ref()
+        .is_some_and(|f| f.starts_with('-'))
+    {
+        return Err("Invalid remote host identity file".to_string());
+    }
+    Ok(host)
+}
+
+/// Adds `host` to `hosts`, replacing one with the same name.
+pub fn upsert_remote_host(hosts: &mut Vec<RemoteHost>, host: RemoteHost) -> Result<(), String> {
+    let host = normalize_remote_host(host)?;
+    match hosts.iter_mut().find(|h| h.name == host.name) {
+        Some(existing) => *existing = host,
+        None => hosts.push(host),
+    }
+    Ok(())
+}
+
+/// `value` in single quotes for a POSIX shell.
+#[must_use]
+pub fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Options shared by ssh and scp; scp spells the port flag `-P`.
+fn connection_args(host: &RemoteHost, port_flag: &str) -> Vec<String> {
+    let mut args = vec![
+        "-o".to_string(),
+        "BatchMode=yes".to_string(),
+        "-o".to_string(),
+        format!("ConnectTimeout={}", CONNECT_TIMEOUT_SECS),
+    ];
+    if let Some(port) = host.port {
+        args.extend([port_flag.to_string(), port.to_string()]);
+    }
+    if let Some(identity) = &host.identity_file {
+        args.extend(["-i".to_string(), identity.clone()]);
+    }
+    args
+}
+
+/// Arguments for `ssh` running `script` under `sh -l` on the host, whatever the login shell is.
+#[must_use]
+pub fn ssh_args(host: &RemoteHost, script: &str) -> Vec<String> {
+    let mut args = connection_args(host, "-p");
+    args.push(host.destination());
+    args.push(format!("sh -lc {}", shell_quote(script)));
+    args
+}
+
+/// Arguments for `scp` copying `local` to `remote_path` (relative to the remote home).
+#[must_use]
+pub fn scp_args(host: &RemoteHost, local: &str, remote_path: &str) -> Vec<String> {
+    let mut args = connection_args(host, "-P");
+    args.push("-q".to_string());
+    let address = if host.host.contains(':') {
+        format!("[{}]", host.host)
+    } else {
+        host.host.clone()
+    };
+    let destination = match &host.user {
+        Some(user) => format!("{}@{}", user, address),
+        None => address,
+    };
+    args.push(local.to_string());
+    args.push(format!("{}:{}", destination, remote_path));
+    args
+}
+
+/// Runtime status from the output of `DETECT_SCRIPT`.
+#[must_use]
+pub fn parse_detection(stdout: &str) -> LocalLLMDetection {
+    let values: HashMap<&str, &str> = stdout
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(k, v)| (k.trim(), v.trim()))
+        .collect();
+    let value = |runtime: &str, field: &str| {
+        values
+            .get(format!("{}.{}", runtime, field).as_str())
+            .filter(|v| !v.is_empty())
+            .map(|v| v.to_string())
+    };
+    let status = |runtime: &str| {
+        let path = value(runtime, "path");
+        let version = value(runtime, "version");
+        LLMStatus {
+            installed: path.is_some() || version.is_some(),
+            running: value(runtime, "running").is_some(),
+            version: version.and_then(|v| detection::parse_version_line(&v)),
+            path,
+        }
+    };
+    LocalLLMDetection {
+        ollama: status("ollama"),
+        lm_studio: status("lms"),
+        vllm: status("vllm"),
+    }
+}
+
+#[must_use]
+pub fn list_remote_hosts() -> Vec<RemoteHost> {
+    settings::get_app_settings().remote_hosts
+}
+
+fn find_remote_host(name: &str) -> Result<RemoteHost, String> {
+    list_remote_hosts()
+        .into_iter()
+        .find(|h| h.name == name)
+        .ok_or_else(|| format!("Unknown remote host: {}", name))
+}
+
+/// Registers `host` (or replaces the one with its name); returns all hosts.
+pub fn add_remote_host(host: RemoteHost) -> Result<Vec<RemoteHost>, String> {
+    let mut settings = settings::get_app_settings();
+    upsert_remote_host(&mut settings.remote_hosts, host)?;
+    settings::save_app_settings(&settings)?;
+    Ok(settings.remote_hosts)
+}
+
+pub fn remove_remote_host(name: &str) -> Result<Vec<RemoteHost>, String> {
+    let mut settings = settings::get_app_settings();
+    let before = settings.remote_hosts.len();
+    settings.remote_hosts.retain(|h| h.name != name);
+    if settings.remote_hosts.len() == before {
+        return Err(format!("Unknown remote host: {}", name));
+    }
+    settings::save_app_settings(&settings)?;
+    Ok(settings.remote_hosts)
+}
+
+fn stderr_of(out: &Output) -> String {
+    String::from_utf8_lossy(&out.stderr).trim().to_string()
+}
+
+/// Runs `script` on the host. Errors when ssh can't connect; the script's own exit status is
+/// left to the caller.
+fn run_remote(host: &RemoteHost, script: &str, timeout: Duration) -> Result<Output, String> {
+    let out = operations::run_command(
+        Command::new("ssh").args(ssh_args(host, script)),
+        timeout,
+        None,
+    )?;
+    if out.status.code() == Some(SSH_FAILED) {
+        return Err(format!("ssh to {} failed: {}", host.name, stderr_of(&out)));
+    }
+    Ok(out)
+}
+
+/// Ollama, LM Studio and vLLM on the host.
+pub fn detect_remote_llms(name: &str) -> Result<LocalLLMDetection, String> {
+    let host = find_remote_host(name)?;
+    let out = run_remote(&host, DETECT_SCRIPT, DETECT_TIMEOUT)?;
+    Ok(parse_detection(&String::from_utf8_lossy(&out.stdout)))
+}
+
+/// Whether `openclaw gateway discover` on the host finds a running gateway.
+pub fn remote_gateway_running(name: &str) -> Result<bool, String> {
+    let host = find_remote_host(name)?;
+    let openclaw = host.openclaw_path.as_deref().unwrap_or("openclaw");
+    let script = format!(
+        "{} gateway discover --json --timeout 500",
+        shell_quote(openclaw)
+    );
+    let out = run_remote(&host, &script, COMMAND_TIMEOUT)?;
+    if out.status.code() == Some(127) {
+        return Err(format!("openclaw not found on {}", host.name));
+    }
+    Ok(out.status.success()
+        && gateway::discovered_count(&String::from_utf8_lossy(&out.stdout)).is_some_and(|n| n > 0))
+}
+
+/// The host's openclaw.json; an empty config when it has none.
+pub fn read_remote_config_root(name: &str) -> Result<Value, String> {
+    let host = find_remote_host(name)?;
+    let script = format!(
+        "f=\"$HOME/{}\"; [ -f \"$f\" ] || exit 0; cat \"$f\"",
+        REMOTE_CONFIG
+    );
+    let out = run_remote(&host, &script, COMMAND_TIMEOUT)?;
+    if !out.status.success() {
+        return Err(format!(
+            "Failed to read openclaw.json on {}: {}",
+            host.name,
+            stderr_of(&out)
+        ));
+    }
+    let content = String::from_utf8_lossy(&out.stdout);
+    if content.trim().is_empty() {
+        return Ok(openclaw_config::empty_config_root());
+    }
+    let root: Value = serde_json::from_str(&content)
+        .map_err(|e| format!("openclaw.json on {} doesn't parse: {}", host.name, e))?;
+    if !root.is_object() {
+        return Err(format!(
+            "openclaw.json on {} is not a JSON object",
+            host.name
+        ));
+    }
+    Ok(root)
+}
+
+/// A new local file only the user can read, for staging a config that holds API keys. The name
+/// is random and the file must not exist yet, so nothing planted at the path is followed.
+fn write_private_temp(prefix: &str, content: &str) -> Result<PathBuf, String> {
+    let mut suffix = [0u8; 8];
+    getrandom::fill(&mut suffix).map_err(|e| e.to_string())?;
+    let suffix: String = suffix.iter().map(|b| format!("{:02x}", b)).collect();
+    let path = std::env::temp_dir().join(format!("{}-{}.json", prefix, suffix));
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&path).map_err(|e| e.to_string())?;
+    if let Err(e) = file.write_all(content.as_bytes()) {
+        let _ = fs::remove_file(&path);
+        return Err(e.to_string());
+    }
+    Ok(path)
+}
+
+/// Checks `root` against the team policy and uploads it as the host's openclaw.json with scp,
+/// keeping the previous file as `openclaw.json.bak`. The file is swapped in with `mv`, so a
+/// dropped connection leaves the old one in place, and is readable only by its owner, locally
+/// while staged and on the host.
+pub fn write_remote_config_root(name: &str, root: &Value) -> Result<(), String> {
+    if !root.is_object() {
+        return Err("openclaw.json root not an object".to_string());
+    }
+    let before = read_remote_config_root(name).ok();
+    policy::enforce_config_change(before.as_ref(), root)?;
+    let host = find_remote_host(name)?;
+    let content = serde_json::to_string_pretty(root).map_err(|e| e.to_string())?;
+    let local = write_private_temp("openclaw-host-config", &content)?;
+    let upload = operations::run_command(
+        Command::new("scp").args(scp_args(&host, &local.to_string_lossy(), UPLOAD_NAME)),
+        COMMAND_TIMEOUT,
+        None,
+    );
+    let _ = fs::remove_file(&local);
+    let upload = upload?;
+    if !upload.status.success() {
+        return Err(format!(
+            "scp to {} failed: {}",
+            host.name,
+            stderr_of(&upload)
+        ));
+    }
+    let script = format!(
+        "mkdir -p \"$HOME/.openclaw\" && cd \"$HOME\" && \
+         {{ [ ! -f {config} ] || cp -p {config} {config}.bak; }} && chmod 600 {upload} && \
+         mv {upload} {config} && chmod 600 {config}",
+        config = REMOTE_CONFIG,
+        upload = UPLOAD_NAME
+    );
+    let out = run_remote(&host, &script, COMMAND_TIMEOUT)?;
+    if !out.status.success() {
+        return Err(format!(
+            "Failed to replace openclaw.json on {}: {}",
+            host.name,
+            stderr_of(&out)
+        ));
+    }
+    Ok(())
+}
+
+/// The host's openclaw.json as the fields the UI edits.
+pub fn get_remote_openclaw_config(name: &str) -> Result<OpenClawConfigView, String> {
+    let root = read_remote_config_root(name)?;
+    openclaw_config::config_view_from_root(&root)
+        .map_err(|()| format!("openclaw.json on {} is not a JSON object", name))
+}
+
+/// Applies `updates` to the host's openclaw.json, as `update_openclaw_config` does locally.
+pub fn update_remote_openclaw_config(
+    name: &str,
+    updates: OpenClawConfigUpdates,
+) -> Result<OpenClawConfigView, String> {
+    let mut root = read_remote_config_root(name)?;
+    openclaw_config::apply_config_updates(&mut root, updates);
+    write_remote_config_root(name, &root)?;
+    openclaw_config::config_view_from_root(&root)
+        .map_err(|()| format!("openclaw.json on {} is not a JSON object", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server() -> RemoteHost {
+        RemoteHost {
+            name: "homelab".to_string(),
+            host: "10.0.0.5".to_string(),
+            user: Some("claw".to_string()),
+            port: Some(2222),
+            ..RemoteHost::default()
+        }
+    }
+
+    #[test]
+    fn test_remote_host_args() {
+        let mut hosts = vec![];
+        let mut host = server();
+        host.name = " homelab ".to_string();
+        host.identity_file = Some("  ".to_string());
+        upsert_remote_host(&mut hosts, host).unwrap();
+        upsert_remote_host(&mut hosts, server()).unwrap();
+        assert_eq!(hosts, vec![server()]);
+        let mut bad = server();
+        bad.host = "-oProxyCommand=x".to_string();
+        assert!(upsert_remote_host(&mut hosts, bad).is_err());
+
+        let args = ssh_args(&server(), "echo 'hi'");
+        assert_eq!(
+            &args[4..],
+            ["-p", "2222", "claw@10.0.0.5", r"sh -lc 'echo '\''hi'\'''"]
+        );
+        let mut v6 = server();
+        v6.host = "fe80::1".to_string();
+        let args = scp_args(&v6, "/tmp/x.json", UPLOAD_NAME);
+        assert_eq!(args[4], "-P");
+        assert_eq!(
+            args.last().unwrap(),
+            "claw@[fe80::1]:.openclaw-host-config-upload.json"
+        );
+    }
+
+    #[test]
+    fn test_parse_detection() {
+        let stdout = "ollama.path=/usr/local/bin/ollama\n\
+                      ollama.version=ollama version is 0.6.2\n\
+                      vllm.version=0.8.1\n\
+                      ollama.running=1\n";
+        let detection = parse_detection(stdout);
+        assert!(detection.ollama.installed && detection.ollama.running);
+        assert_eq!(
+            detection.ollama.version.as_deref(),
+            Some("ollama version is 0.6.2")
+        );
+        assert!(detection.vllm.installed && !detection.vllm.running);
+        assert!(detection.vllm.path.is_none());
+        assert!(!detection.lm_studio.installed);
+    }
+}
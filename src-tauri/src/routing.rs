@@ -0,0 +1,245 @@
+//! Dry run of openclaw's model routing: walks an agent's primary → fallbacks chain and checks
+//! each candidate the way a request would find it. A local model needs its runtime up and the
+//! model installed; a cloud model needs a key and a provider that accepts it. The first model
+//! that passes would serve the request, and the skipped ones carry the reason.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::{agents, openclaw_config, provider_api};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// What to route. Without an agent the default agent's chain is used; `model` goes in front of
+/// that chain, like a per-request model override.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RoutingRequest {
+    pub agent: Option<String>,
+    pub model: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct RoutingStep {
+    pub model: String,
+    pub available: bool,
+    /// Why it would serve, or why it was skipped.
+    pub reason: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct RoutingSimulation {
+    pub agent: String,
+    /// Candidates in order, duplicates removed.
+    pub chain: Vec<String>,
+    /// The model that would serve the request; None when every candidate was skipped.
+    pub served_by: Option<String>,
+    /// Checked candidates, ending at the one that serves.
+    pub steps: Vec<RoutingStep>,
+}
+
+/// The candidates in order: the requested model, then the primary and fallbacks, each once.
+#[must_use]
+pub fn routing_chain(
+    requested: Option<&str>,
+    primary: Option<&str>,
+    fallbacks: &[String],
+) -> Vec<String> {
+    let mut chain: Vec<String> = Vec::new();
+    for id in requested
+        .into_iter()
+        .chain(primary)
+        .chain(fallbacks.iter().map(String::as_str))
+    {
+        let id = id.trim();
+        if !id.is_empty() && !chain.iter().any(|c| c == id) {
+            chain.push(id.to_string());
+        }
+    }
+    chain
+}
+
+/// Checks candidates in order until one is available.
+pub fn walk_chain(
+    chain: &[String],
+    mut check: impl FnMut(&str) -> Result<String, String>,
+) -> (Option<String>, Vec<RoutingStep>) {
+    let mut steps = Vec::new();
+    for model in chain {
+        let (available, reason) = match check(model) {
+            Ok(reason) => (true, reason),
+            Err(reason) => (false, reason),
+        };
+        steps.push(RoutingStep {
+            model: model.clone(),
+            available,
+            reason,
+        });
+        if available {
+            return (Some(model.clone()), steps);
+        }
+    }
+    (None, steps)
+}
+
+/// Whether `model` is among the ids a local runtime lists; Ollama's implicit ":latest" tag
+/// matches either way.
+fn lists_model(ids: &[String], model: &str) -> bool {
+    let bare = |id: &str| id.strip_suffix(":latest").unwrap_or(id).to_string();
+    ids.iter().any(|id| bare(id) == bare(model))
+}
+
+/// Judges a provider's models-endpoint answer for one candidate.
+pub fn judge_response(
+    provider: &str,
+    model: &str,
+    local: bool,
+    response: &Result<(u16, String), String>,
+) -> Result<String, String> {
+    match response {
+        Err(e) if local => Err(format!("{} is not running: {}", provider, e)),
+        Err(e) => Err(format!("{} is unreachable: {}", provider, e)),
+        Ok((401 | 403, _)) => Err(format!("{} rejected the API key", provider)),
+        Ok((status, _)) if !(200..300).contains(status) => {
+            Err(format!("{} answered HTTP {}", provider, status))
+        }
+        Ok((_, body)) if local => {
+            if lists_model(&provider_api::parse_model_ids(body), model) {
+                Ok(format!("{} is running and has {}", provider, model))
+            } else {
+                Err(format!(
+                    "{} is running but {} is not installed",
+                    provider, model
+                ))
+            }
+        }
+        Ok(_) => Ok(format!("{} is reachable and accepted the key", provider)),
+    }
+}
+
+/// The provider's entry with a `${VAR}` key taken from openclaw.json `env` when the process
+/// environment lacks it. Anthropic and OpenAI work without an entry, as in openclaw.
+fn routing_entry(root: &Value, provider: &str) -> Result<Value, String> {
+    let Some(mut entry) = root
+        .pointer("/models/providers")
+        .and_then(|p| p.get(provider))
+        .cloned()
+    else {
+        if !["anthropic", "openai"].contains(&provider) {
+            return Err(format!("Provider {} is not configured", provider));
+        }
+        return provider_api::cloud_entry(root, provider);
+    };
+    if provider_api::resolve_api_key(&entry).is_none() {
+        let from_env = entry
+            .get("apiKey")
+            .and_then(|v| v.as_str())
+            .and_then(|k| k.trim().strip_prefix("${")?.strip_suffix('}'))
+            .and_then(|var| root.get("env")?.get(var)?.as_str())
+            .map(String::from);
+        if let (Some(key), Some(obj)) = (from_env, entry.as_object_mut()) {
+            obj.insert("apiKey".to_string(), serde_json::json!(key));
+        }
+    }
+    Ok(entry)
+}
+
+/// Walks the request's chain against the live providers and runtimes. Each provider is asked
+/// at most once.
+pub fn simulate_routing(request: &RoutingRequest) -> Result<RoutingSimulation, String> {
+    let root = openclaw_config::read_config_root()?;
+    let agent = request
+        .agent
+        .clone()
+        .unwrap_or_else(|| agents::default_agent_name(&root));
+    let config = agents::agent_model_config(&root, &agent);
+    let chain = routing_chain(
+        request.model.as_deref(),
+        config.effective_primary.as_deref(),
+        &config.effective_fallbacks,
+    );
+    if chain.is_empty() {
+        return Err(format!(
+            "No primary or fallback model configured for {}",
+            agent
+        ));
+    }
+    let mut responses: HashMap<String, Result<(u16, String), String>> = HashMap::new();
+    let (served_by, steps) = walk_chain(&chain, |id| {
+        let (provider, model) = id
+            .split_once('/')
+            .ok_or_else(|| format!("{} is not a provider/model id", id))?;
+        let entry = routing_entry(&root, provider)?;
+        let base_url = entry.get("baseUrl").and_then(|v| v.as_str());
+        let local = openclaw_config::is_local_provider(provider, base_url);
+        if !local && provider_api::resolve_api_key(&entry).is_none() {
+            return Err(format!("No API key set for {}", provider));
+        }
+        let response = responses
+            .entry(provider.to_string())
+            .or_insert_with(|| provider_api::get_models(&entry, TIMEOUT));
+        judge_response(provider, model, local, response)
+    });
+    Ok(RoutingSimulation {
+        agent,
+        chain,
+        served_by,
+        steps,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routing_chain_and_walk() {
+        let fallbacks = vec![
+            "ollama/qwen3:8b".to_string(),
+            "anthropic/claude-sonnet-4-5".to_string(),
+        ];
+        let chain = routing_chain(
+            Some("anthropic/claude-sonnet-4-5"),
+            Some("openai/gpt-4o"),
+            &fallbacks,
+        );
+        assert_eq!(
+            chain,
+            vec![
+                "anthropic/claude-sonnet-4-5",
+                "openai/gpt-4o",
+                "ollama/qwen3:8b"
+            ]
+        );
+        let (served, steps) = walk_chain(&chain, |id| {
+            if id.starts_with("ollama/") {
+                Ok("up".to_string())
+            } else {
+                Err("no key".to_string())
+            }
+        });
+        assert_eq!(served.as_deref(), Some("ollama/qwen3:8b"));
+        assert_eq!(steps.len(), 3);
+        assert!(!steps[0].available && steps[2].available);
+    }
+
+    #[test]
+    fn test_judge_response() {
+        let tags = Ok((200, r#"{"data":[{"id":"llama3.2:latest"}]}"#.to_string()));
+        assert!(judge_response("ollama", "llama3.2", true, &tags).is_ok());
+        assert!(judge_response("ollama", "qwen3:8b", true, &tags)
+            .unwrap_err()
+            .contains("not installed"));
+        let down = Err("Connection refused".to_string());
+        assert!(judge_response("ollama", "qwen3:8b", true, &down)
+            .unwrap_err()
+            .contains("not running"));
+        let rejected = Ok((401, String::new()));
+        assert!(judge_response("openai", "gpt-4o", false, &rejected)
+            .unwrap_err()
+            .contains("rejected"));
+        assert!(judge_response("openai", "gpt-4o", false, &Ok((200, "{}".to_string()))).is_ok());
+    }
+}
@@ -0,0 +1,168 @@
+//! What the model runtimes and the gateway are actually using: CPU, resident memory and (on
+//! NVIDIA) VRAM, summed over each runtime's processes (e.g. Ollama's server plus its runners).
+//! Process classification and nvidia-smi parsing are pure functions for unit tests.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+use crate::catalog::{RUNTIME_LM_STUDIO, RUNTIME_OLLAMA, RUNTIME_VLLM};
+use crate::system;
+
+pub const RUNTIME_GATEWAY: &str = "gateway";
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct RuntimeResourceUsage {
+    /// "ollama", "lmstudio", "vllm" or "gateway".
+    pub runtime: String,
+    pub pids: Vec<u32>,
+    /// Summed over processes; 100 is one full core.
+    pub cpu_percent: f32,
+    /// Resident set size.
+    pub memory_bytes: u64,
+    /// From nvidia-smi; None without an NVIDIA GPU or when no process holds GPU memory.
+    pub vram_bytes: Option<u64>,
+}
+
+/// Which runtime a process belongs to, from its name and command line.
+#[must_use]
+pub fn classify_process(name: &str, cmd: &str) -> Option<&'static str> {
+    let name = name.to_lowercase();
+    let cmd = cmd.to_lowercase();
+    if name.starts_with("ollama") {
+        Some(RUNTIME_OLLAMA)
+    } else if name.contains("lm studio")
+        || name.contains("lm-studio")
+        || name == "lms"
+        || cmd.contains("lm studio.app")
+    {
+        Some(RUNTIME_LM_STUDIO)
+    } else if cmd.contains("vllm") && !name.starts_with("openclaw") {
+        Some(RUNTIME_VLLM)
+    } else if (name.starts_with("openclaw") || cmd.contains("openclaw")) && cmd.contains("gateway")
+    {
+        Some(RUNTIME_GATEWAY)
+    } else {
+        None
+    }
+}
+
+/// Parses `nvidia-smi --query-compute-apps=pid,used_memory --format=csv,noheader,nounits`
+/// (MiB) into bytes per pid.
+#[must_use]
+pub fn parse_nvidia_compute_apps(stdout: &str) -> HashMap<u32, u64> {
+    const MIB: u64 = 1024 * 1024;
+    let mut usage = HashMap::new();
+    for line in stdout.lines() {
+        let Some((pid, used)) = line.split_once(',') else {
+            continue;
+        };
+        if let (Ok(pid), Ok(used)) = (pid.trim().parse::<u32>(), used.trim().parse::<u64>()) {
+            *usage.entry(pid).or_insert(0) += used * MIB;
+        }
+    }
+    usage
+}
+
+/// Samples all processes (twice, as CPU usage is a delta) and sums usage per runtime.
+/// Runtimes with no running process are left out.
+#[must_use]
+pub fn get_runtime_resource_usage() -> Vec<RuntimeResourceUsage> {
+    let mut sys = System::new();
+    let refresh = ProcessRefreshKind::new()
+        .with_cpu()
+        .with_memory()
+        .with_cmd(UpdateKind::OnlyIfNotSet);
+    sys.refresh_processes_specifics(ProcessesToUpdate::All, refresh);
+    std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+    sys.refresh_processes_specifics(ProcessesToUpdate::All, refresh);
+    let vram = system::command_stdout(
+        "nvidia-smi",
+        &[
+            "--query-compute-apps=pid,used_memory",
+            "--format=csv,noheader,nounits",
+        ],
+    )
+    .map(|s| parse_nvidia_compute_apps(&s))
+    .unwrap_or_default();
+
+    let mut by_runtime: Vec<RuntimeResourceUsage> = Vec::new();
+    for (pid, process) in sys.processes() {
+        // Threads show up as processes on Linux; count each process once.
+        if process.thread_kind().is_some() {
+            continue;
+        }
+        let cmd = process
+            .cmd()
+            .iter()
+            .map(|a| a.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let Some(runtime) = classify_process(&process.name().to_string_lossy(), &cmd) else {
+            continue;
+        };
+        let entry = match by_runtime.iter_mut().position(|u| u.runtime == runtime) {
+            Some(i) => &mut by_runtime[i],
+            None => {
+                by_runtime.push(RuntimeResourceUsage {
+                    runtime: runtime.to_string(),
+                    ..Default::default()
+                });
+                by_runtime.last_mut().expect("just pushed")
+            }
+        };
+        let pid = pid.as_u32();
+        entry.pids.push(pid);
+        entry.cpu_percent += process.cpu_usage();
+        entry.memory_bytes += process.memory();
+        if let Some(bytes) = vram.get(&pid) {
+            *entry.vram_bytes.get_or_insert(0) += bytes;
+        }
+    }
+    for entry in &mut by_runtime {
+        entry.pids.sort_unstable();
+    }
+    by_runtime.sort_by(|a, b| a.runtime.cmp(&b.runtime));
+    by_runtime
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_process() {
+        assert_eq!(classify_process("ollama", "ollama serve"), Some("ollama"));
+        assert_eq!(
+            classify_process("ollama_llama_server", "/usr/lib/ollama/runner"),
+            Some("ollama")
+        );
+        assert_eq!(
+            classify_process(
+                "LM Studio Helper",
+                "/Applications/LM Studio.app/Contents/..."
+            ),
+            Some("lmstudio")
+        );
+        assert_eq!(
+            classify_process("python3", "python3 -m vllm.entrypoints.openai.api_server"),
+            Some("vllm")
+        );
+        assert_eq!(
+            classify_process(
+                "node",
+                "node /usr/lib/node_modules/openclaw/dist/index.js gateway"
+            ),
+            Some("gateway")
+        );
+        assert_eq!(classify_process("openclaw-config", "openclaw-config"), None);
+        assert_eq!(classify_process("bash", "bash"), None);
+    }
+
+    #[test]
+    fn test_parse_nvidia_compute_apps() {
+        let usage = parse_nvidia_compute_apps("1234, 5120\n1234, 1024\n99, [N/A]\n");
+        assert_eq!(usage.get(&1234), Some(&(6144 * 1024 * 1024)));
+        assert!(!usage.contains_key(&99));
+    }
+}
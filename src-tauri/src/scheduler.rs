@@ -0,0 +1,322 @@
+//! Background jobs on fixed intervals: a nightly model-list refresh, a sync check of every
+//! agent's providers against openclaw.json, and weekly backups of openclaw.json and the agents'
+//! models.json to ~/.openclaw/backups/config/<unix secs>. Each job can be turned off in app
+//! settings; last runs are kept in ~/.openclaw/host-config/jobs.json so intervals survive
+//! restarts. Reacting to a run (notifications, events) is left to the caller of `spawn`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use crate::app_state::AppState;
+use crate::catalog::CatalogQuery;
+use crate::{agents, openclaw_config, operations, settings};
+
+/// How often the scheduler looks for due jobs.
+const TICK: Duration = Duration::from_secs(60);
+/// Config backups kept; older ones are deleted after each backup.
+const KEEP_BACKUPS: usize = 8;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    ModelRefresh,
+    SyncCheck,
+    ConfigBackup,
+}
+
+pub const ALL_JOBS: [JobKind; 3] = [
+    JobKind::ModelRefresh,
+    JobKind::SyncCheck,
+    JobKind::ConfigBackup,
+];
+
+impl JobKind {
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ModelRefresh => "Refresh model lists",
+            Self::SyncCheck => "Check agent provider sync",
+            Self::ConfigBackup => "Back up configs",
+        }
+    }
+
+    #[must_use]
+    pub fn interval_secs(self) -> u64 {
+        match self {
+            Self::ModelRefresh => 24 * 3600,
+            Self::SyncCheck => 15 * 60,
+            Self::ConfigBackup => 7 * 24 * 3600,
+        }
+    }
+}
+
+/// Which jobs run; all on by default.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct JobSettings {
+    pub model_refresh: bool,
+    pub sync_check: bool,
+    pub config_backup: bool,
+}
+
+impl Default for JobSettings {
+    fn default() -> Self {
+        Self {
+            model_refresh: true,
+            sync_check: true,
+            config_backup: true,
+        }
+    }
+}
+
+impl JobSettings {
+    #[must_use]
+    pub fn enabled(&self, kind: JobKind) -> bool {
+        match kind {
+            JobKind::ModelRefresh => self.model_refresh,
+            JobKind::SyncCheck => self.sync_check,
+            JobKind::ConfigBackup => self.config_backup,
+        }
+    }
+}
+
+/// Outcome of one run, as stored in jobs.json.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct JobRun {
+    pub kind: JobKind,
+    /// Unix seconds.
+    pub finished_at: u64,
+    pub ok: bool,
+    pub message: String,
+    /// Agents out of sync (sync check only).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub drifted_agents: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JobStatus {
+    pub kind: JobKind,
+    pub label: String,
+    pub enabled: bool,
+    pub interval_secs: u64,
+    pub last_run: Option<JobRun>,
+    /// Unix seconds; None when disabled.
+    pub next_run_at: Option<u64>,
+}
+
+fn jobs_path() -> PathBuf {
+    openclaw_config::host_config_dir().join("jobs.json")
+}
+
+fn read_runs() -> BTreeMap<JobKind, JobRun> {
+    fs::read_to_string(jobs_path())
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn record_run(run: &JobRun) -> Result<(), String> {
+    let mut runs = read_runs();
+    runs.insert(run.kind, run.clone());
+    let path = jobs_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    fs::write(
+        &path,
+        serde_json::to_string_pretty(&runs).map_err(|e| e.to_string())?,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// The last recorded run of `kind`.
+#[must_use]
+pub fn last_run(kind: JobKind) -> Option<JobRun> {
+    read_runs().remove(&kind)
+}
+
+/// When a job is next due: right away if it never ran, else one interval after the last run.
+#[must_use]
+pub fn next_run_at(kind: JobKind, last_finished: Option<u64>, now: u64) -> u64 {
+    last_finished.map_or(now, |t| t + kind.interval_secs())
+}
+
+/// Every job with its settings and last run.
+#[must_use]
+pub fn list_jobs() -> Vec<JobStatus> {
+    let jobs = settings::get_app_settings().jobs;
+    let mut runs = read_runs();
+    let now = operations::now_secs();
+    ALL_JOBS
+        .iter()
+        .map(|&kind| {
+            let last_run = runs.remove(&kind);
+            let enabled = jobs.enabled(kind);
+            JobStatus {
+                kind,
+                label: kind.label().to_string(),
+                enabled,
+                interval_secs: kind.interval_secs(),
+                next_run_at: enabled
+                    .then(|| next_run_at(kind, last_run.as_ref().map(|r| r.finished_at), now)),
+                last_run,
+            }
+        })
+        .collect()
+}
+
+/// Enabled jobs due at `now`.
+fn due_jobs(now: u64) -> Vec<JobKind> {
+    let jobs = settings::get_app_settings().jobs;
+    let runs = read_runs();
+    ALL_JOBS
+        .into_iter()
+        .filter(|&kind| jobs.enabled(kind))
+        .filter(|kind| next_run_at(*kind, runs.get(kind).map(|r| r.finished_at), now) <= now)
+        .collect()
+}
+
+/// Backup directory names (unix secs) beyond the newest `keep`, oldest first.
+#[must_use]
+pub fn backups_to_prune(mut names: Vec<String>, keep: usize) -> Vec<String> {
+    names.retain(|n| n.parse::<u64>().is_ok());
+    names.sort_by_key(|n| n.parse::<u64>().unwrap_or(0));
+    let excess = names.len().saturating_sub(keep);
+    names.truncate(excess);
+    names
+}
+
+fn copy_into(src: &Path, dest: &Path) -> Result<bool, String> {
+    if !src.is_file() {
+        return Ok(false);
+    }
+    if let Some(dir) = dest.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    fs::copy(src, dest).map_err(|e| format!("{}: {}", src.display(), e))?;
+    Ok(true)
+}
+
+/// ~/.openclaw/backups/config, holding one directory per backup.
+#[must_use]
+pub fn config_backups_dir() -> PathBuf {
+    agents::openclaw_root().join("backups").join("config")
+}
+
+/// Copies openclaw.json and every agent's models.json into a new backup directory, then deletes
+/// backups beyond the newest few. Returns the directory.
+pub fn backup_configs() -> Result<PathBuf, String> {
+    let backups = config_backups_dir();
+    let dir = backups.join(operations::now_secs().to_string());
+    let mut copied = 0;
+    if copy_into(
+        &openclaw_config::openclaw_config_path(),
+        &dir.join("openclaw.json"),
+    )? {
+        copied += 1;
+    }
+    for name in agents::list_agent_names() {
+        let dest = agents::models_path_in(&dir.join("agents"), &name);
+        if copy_into(&agents::agent_models_path(&name), &dest)? {
+            copied += 1;
+        }
+    }
+    if copied == 0 {
+        return Err("Nothing to back up".to_string());
+    }
+    let names = fs::read_dir(&backups)
+        .map(|d| {
+            d.filter_map(|e| e.ok())
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    for old in backups_to_prune(names, KEEP_BACKUPS) {
+        let _ = fs::remove_dir_all(backups.join(old));
+    }
+    Ok(dir)
+}
+
+fn execute(kind: JobKind, state: &AppState) -> Result<(String, Vec<String>), String> {
+    match kind {
+        JobKind::ModelRefresh => {
+            state.invalidate_models();
+            let count = state.catalog(&CatalogQuery::default()).len();
+            Ok((format!("{} models listed", count), vec![]))
+        }
+        JobKind::SyncCheck => {
+            let drifted: Vec<String> = agents::list_agent_names()
+                .into_iter()
+                .filter(|name| !agents::get_provider_sync_status(name).in_sync)
+                .collect();
+            let message = if drifted.is_empty() {
+                "All agents in sync".to_string()
+            } else {
+                format!("Out of sync: {}", drifted.join(", "))
+            };
+            Ok((message, drifted))
+        }
+        JobKind::ConfigBackup => {
+            backup_configs().map(|dir| (format!("Saved to {}", dir.display()), vec![]))
+        }
+    }
+}
+
+/// Runs `kind` now, whether enabled or not, and records the outcome.
+pub fn run_job(kind: JobKind, state: &AppState) -> Result<JobRun, String> {
+    let (ok, message, drifted_agents) = match execute(kind, state) {
+        Ok((message, drifted)) => (true, message, drifted),
+        Err(e) => (false, e, vec![]),
+    };
+    let run = JobRun {
+        kind,
+        finished_at: operations::now_secs(),
+        ok,
+        message,
+        drifted_agents,
+    };
+    record_run(&run)?;
+    Ok(run)
+}
+
+/// Checks for due jobs every minute and hands each to `run`, which should call `run_job`.
+pub fn spawn<F>(run: F)
+where
+    F: Fn(JobKind) + Send + 'static,
+{
+    thread::spawn(move || loop {
+        for kind in due_jobs(operations::now_secs()) {
+            run(kind);
+        }
+        thread::sleep(TICK);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_run_at() {
+        assert_eq!(next_run_at(JobKind::SyncCheck, None, 1000), 1000);
+        assert_eq!(next_run_at(JobKind::SyncCheck, Some(1000), 5000), 1900);
+        assert_eq!(
+            next_run_at(JobKind::ConfigBackup, Some(0), 10),
+            7 * 24 * 3600
+        );
+    }
+
+    #[test]
+    fn test_backups_to_prune() {
+        let names = ["300", "100", "notes.txt", "200", "1000"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(backups_to_prune(names, 2), vec!["100", "200"]);
+        assert!(backups_to_prune(vec!["1".to_string()], 8).is_empty());
+    }
+}
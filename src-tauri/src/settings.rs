@@ -0,0 +1,402 @@
+//! Preferences of this app itself (polling intervals, detection overrides, telemetry opt-in,
+//! binary paths, webhooks, metrics endpoint, background jobs, pinned models, remote hosts), kept
+//! in ~/.openclaw/host-config/settings.json so they never end up in the gateway's config.json or
+//! openclaw.json. Runtime endpoints and binary paths are read through `runtime_url`,
+//! `runtime_disabled` and `binary_path`. Updates are applied by a pure function for tests.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::metrics::MetricsSettings;
+use crate::openclaw_config;
+use crate::remote_hosts::RemoteHost;
+use crate::scheduler::JobSettings;
+use crate::webhooks::{self, Webhook};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct AppSettings {
+    /// How often the UI checks whether the gateway is running.
+    pub gateway_poll_secs: u32,
+    /// Default interval of the live stats stream.
+    pub live_stats_interval_ms: u64,
+    pub detection: DetectionOverrides,
+    /// Count which commands are used, locally only (see `usage_stats`).
+    pub telemetry_opt_in: bool,
+    pub binary_paths: BinaryPaths,
+    /// URLs that get state-change events.
+    pub webhooks: Vec<Webhook>,
+    /// Prometheus listener; off by default.
+    pub metrics: MetricsSettings,
+    /// Scheduled jobs that run.
+    pub jobs: JobSettings,
+    /// Model ids ("provider/model") listed first in the catalog and model pickers.
+    pub pinned_models: Vec<String>,
+    /// Machines managed over SSH.
+    pub remote_hosts: Vec<RemoteHost>,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            gateway_poll_secs: 5,
+            live_stats_interval_ms: 2000,
+            detection: DetectionOverrides::default(),
+            telemetry_opt_in: false,
+            binary_paths: BinaryPaths::default(),
+            webhooks: vec![],
+            metrics: MetricsSettings::default(),
+            jobs: JobSettings::default(),
+            pinned_models: vec![],
+            remote_hosts: vec![],
+        }
+    }
+}
+
+/// Runtime endpoints to use instead of the defaults, and runtimes to skip entirely.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct DetectionOverrides {
+    pub ollama_url: Option<String>,
+    pub lm_studio_url: Option<String>,
+    pub vllm_url: Option<String>,
+    /// "ollama", "lmstudio" or "vllm".
+    pub disabled_runtimes: Vec<String>,
+}
+
+impl DetectionOverrides {
+    /// The endpoint set for "ollama", "lmstudio" or "vllm", without a trailing slash.
+    #[must_use]
+    pub fn url(&self, runtime: &str) -> Option<String> {
+        match runtime {
+            "ollama" => self.ollama_url.as_deref(),
+            "lmstudio" => self.lm_studio_url.as_deref(),
+            "vllm" => self.vllm_url.as_deref(),
+            _ => None,
+        }
+        .map(|u| u.trim_end_matches('/').to_string())
+    }
+}
+
+/// Executables to run instead of looking them up on PATH.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct BinaryPaths {
+    pub openclaw: Option<String>,
+    pub ollama: Option<String>,
+    pub lms: Option<String>,
+    pub llmfit: Option<String>,
+}
+
+impl BinaryPaths {
+    /// The path set for "openclaw", "ollama", "lms" or "llmfit".
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&str> {
+        match name {
+            "openclaw" => self.openclaw.as_deref(),
+            "ollama" => self.ollama.as_deref(),
+            "lms" => self.lms.as_deref(),
+            "llmfit" => self.llmfit.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Fields to change; None leaves a field as is. An empty string clears a URL or path override.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettingsUpdates {
+    pub gateway_poll_secs: Option<u32>,
+    pub live_stats_interval_ms: Option<u64>,
+    pub ollama_url: Option<String>,
+    pub lm_studio_url: Option<String>,
+    pub vllm_url: Option<String>,
+    pub disabled_runtimes: Option<Vec<String>>,
+    pub telemetry_opt_in: Option<bool>,
+    pub openclaw_path: Option<String>,
+    pub ollama_path: Option<String>,
+    pub lms_path: Option<String>,
+    pub llmfit_path: Option<String>,
+    /// Replaces the whole list.
+    pub webhooks: Option<Vec<Webhook>>,
+    pub metrics_enabled: Option<bool>,
+    /// IP address to listen on.
+    pub metrics_bind: Option<String>,
+    pub metrics_port: Option<u16>,
+    pub model_refresh_job: Option<bool>,
+    pub sync_check_job: Option<bool>,
+    pub config_backup_job: Option<bool>,
+}
+
+fn settings_path() -> PathBuf {
+    openclaw_config::host_config_dir().join("settings.json")
+}
+
+/// Stored settings, or the defaults when none are saved.
+#[must_use]
+pub fn get_app_settings() -> AppSettings {
+    fs::read_to_string(settings_path())
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+/// The stored endpoint override for "ollama", "lmstudio" or "vllm".
+#[must_use]
+pub fn runtime_url(runtime: &str) -> Option<String> {
+    get_app_settings().detection.url(runtime)
+}
+
+/// Whether detection of "ollama", "lmstudio" or "vllm" is turned off.
+#[must_use]
+pub fn runtime_disabled(runtime: &str) -> bool {
+    get_app_settings()
+        .detection
+        .disabled_runtimes
+        .iter()
+        .any(|r| r == runtime)
+}
+
+/// The stored path for "ollama", "lms" or "llmfit" when that file exists; callers fall back to
+/// their usual lookup otherwise.
+#[must_use]
+pub fn binary_path(name: &str) -> Option<String> {
+    get_app_settings()
+        .binary_paths
+        .get(name)
+        .filter(|p| Path::new(p).is_file())
+        .map(String::from)
+}
+
+fn set_override(field: &mut Option<String>, value: Option<String>) {
+    if let Some(v) = value {
+        let v = v.trim();
+        *field = (!v.is_empty()).then(|| v.to_string());
+    }
+}
+
+/// Applies `updates` to `settings`, rejecting out-of-range intervals, unknown runtimes,
+/// non-http webhook URLs and bad metrics addresses.
+pub fn apply_settings_updates(
+    settings: &mut AppSettings,
+    updates: AppSettingsUpdates,
+) -> Result<(), String> {
+    if let Some(secs) = updates.gateway_poll_secs {
+        if !(1..=3600).contains(&secs) {
+            return Err("gateway_poll_secs must be between 1 and 3600".to_string());
+        }
+        settings.gateway_poll_secs = secs;
+    }
+    if let Some(ms) = updates.live_stats_interval_ms {
+        if !(500..=60_000).contains(&ms) {
+            return Err("live_stats_interval_ms must be between 500 and 60000".to_string());
+        }
+        settings.live_stats_interval_ms = ms;
+    }
+    if let Some(runtimes) = updates.disabled_runtimes {
+        if let Some(bad) = runtimes
+            .iter()
+            .find(|r| !["ollama", "lmstudio", "vllm"].contains(&r.as_str()))
+        {
+            return Err(format!("Unknown runtime: {}", bad));
+        }
+        settings.detection.disabled_runtimes = runtimes;
+    }
+    let detection = &mut settings.detection;
+    set_override(&mut detection.ollama_url, updates.ollama_url);
+    set_override(&mut detection.lm_studio_url, updates.lm_studio_url);
+    set_override(&mut detection.vllm_url, updates.vllm_url);
+    let paths = &mut settings.binary_paths;
+    set_override(&mut paths.openclaw, updates.openclaw_path);
+    set_override(&mut paths.ollama, updates.ollama_path);
+    set_override(&mut paths.lms, updates.lms_path);
+    set_override(&mut paths.llmfit, updates.llmfit_path);
+    if let Some(v) = updates.telemetry_opt_in {
+        settings.telemetry_opt_in = v;
+    }
+    if let Some(mut hooks) = updates.webhooks {
+        for hook in &mut hooks {
+            hook.url = hook.url.trim().to_string();
+            webhooks::validate_webhook_url(&hook.url)?;
+        }
+        settings.webhooks = hooks;
+    }
+    if let Some(bind) = updates.metrics_bind {
+        let bind = bind.trim();
+        bind.parse::<std::net::IpAddr>()
+            .map_err(|_| format!("metrics_bind must be an IP address: {}", bind))?;
+        settings.metrics.bind = bind.to_string();
+    }
+    if let Some(port) = updates.metrics_port {
+        if port < 1024 {
+            return Err("metrics_port must be between 1024 and 65535".to_string());
+        }
+        settings.metrics.port = port;
+    }
+    if let Some(v) = updates.metrics_enabled {
+        settings.metrics.enabled = v;
+    }
+    let jobs = &mut settings.jobs;
+    for (field, value) in [
+        (&mut jobs.model_refresh, updates.model_refresh_job),
+        (&mut jobs.sync_check, updates.sync_check_job),
+        (&mut jobs.config_backup, updates.config_backup_job),
+    ] {
+        if let Some(v) = value {
+            *field = v;
+        }
+    }
+    Ok(())
+}
+
+/// Adds `id` to or removes it from `pinned`; returns whether the list changed.
+pub fn set_model_pinned(pinned: &mut Vec<String>, id: &str, pin: bool) -> Result<bool, String> {
+    let id = id.trim();
+    if id.is_empty() {
+        return Err("Model id is empty".to_string());
+    }
+    let present = pinned.iter().any(|p| p == id);
+    match (pin, present) {
+        (true, false) => pinned.push(id.to_string()),
+        (false, true) => pinned.retain(|p| p != id),
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+/// Pins or unpins a model in the stored settings; returns the pinned ids.
+fn update_pinned(id: &str, pin: bool) -> Result<Vec<String>, String> {
+    let mut settings = get_app_settings();
+    if set_model_pinned(&mut settings.pinned_models, id, pin)? {
+        save_app_settings(&settings)?;
+    }
+    Ok(settings.pinned_models)
+}
+
+pub fn pin_model(id: &str) -> Result<Vec<String>, String> {
+    update_pinned(id, true)
+}
+
+pub fn unpin_model(id: &str) -> Result<Vec<String>, String> {
+    update_pinned(id, false)
+}
+
+/// Applies `updates` to the stored settings and saves them.
+pub fn update_app_settings(updates: AppSettingsUpdates) -> Result<AppSettings, String> {
+    let mut settings = get_app_settings();
+    apply_settings_updates(&mut settings, updates)?;
+    save_app_settings(&settings)?;
+    Ok(settings)
+}
+
+pub(crate) fn save_app_settings(settings: &AppSettings) -> Result<(), String> {
+    let path = settings_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    fs::write(
+        &path,
+        serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?,
+    )
+    .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_settings_updates() {
+        let mut settings = AppSettings::default();
+        apply_settings_updates(
+            &mut settings,
+            AppSettingsUpdates {
+                gateway_poll_secs: Some(10),
+                ollama_url: Some(" http://10.0.0.5:11434 ".to_string()),
+                disabled_runtimes: Some(vec!["vllm".to_string()]),
+                llmfit_path: Some("/opt/bin/llmfit".to_string()),
+                telemetry_opt_in: Some(true),
+                config_backup_job: Some(false),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(settings.gateway_poll_secs, 10);
+        assert_eq!(
+            settings.detection.ollama_url.as_deref(),
+            Some("http://10.0.0.5:11434")
+        );
+        assert_eq!(settings.detection.disabled_runtimes, vec!["vllm"]);
+        assert_eq!(
+            settings.binary_paths.llmfit.as_deref(),
+            Some("/opt/bin/llmfit")
+        );
+        assert!(settings.telemetry_opt_in);
+        assert!(!settings.jobs.config_backup);
+        assert!(settings.jobs.sync_check);
+        assert_eq!(settings.live_stats_interval_ms, 2000);
+
+        apply_settings_updates(
+            &mut settings,
+            AppSettingsUpdates {
+                ollama_url: Some(String::new()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(settings.detection.ollama_url.is_none());
+        assert_eq!(settings.binary_paths.get("llmfit"), Some("/opt/bin/llmfit"));
+        settings.detection.vllm_url = Some("http://gpu-box:8000/".to_string());
+        assert_eq!(
+            settings.detection.url("vllm").as_deref(),
+            Some("http://gpu-box:8000")
+        );
+
+        let bad_interval = AppSettingsUpdates {
+            gateway_poll_secs: Some(0),
+            ..Default::default()
+        };
+        assert!(apply_settings_updates(&mut settings, bad_interval).is_err());
+        let bad_runtime = AppSettingsUpdates {
+            disabled_runtimes: Some(vec!["koboldcpp".to_string()]),
+            ..Default::default()
+        };
+        assert!(apply_settings_updates(&mut settings, bad_runtime).is_err());
+        let bad_webhook = AppSettingsUpdates {
+            webhooks: Some(vec![Webhook {
+                url: "kuma.lan/push".to_string(),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+        assert!(apply_settings_updates(&mut settings, bad_webhook).is_err());
+        let bad_bind = AppSettingsUpdates {
+            metrics_enabled: Some(true),
+            metrics_bind: Some("localhost".to_string()),
+            ..Default::default()
+        };
+        assert!(apply_settings_updates(&mut settings, bad_bind).is_err());
+        assert!(!settings.metrics.enabled);
+    }
+
+    #[test]
+    fn test_set_model_pinned() {
+        let mut pinned = vec![];
+        assert_eq!(
+            set_model_pinned(&mut pinned, " ollama/qwen3:8b ", true),
+            Ok(true)
+        );
+        assert_eq!(
+            set_model_pinned(&mut pinned, "ollama/qwen3:8b", true),
+            Ok(false)
+        );
+        assert_eq!(pinned, vec!["ollama/qwen3:8b"]);
+        assert_eq!(
+            set_model_pinned(&mut pinned, "ollama/qwen3:8b", false),
+            Ok(true)
+        );
+        assert!(pinned.is_empty());
+        assert!(set_model_pinned(&mut pinned, "  ", true).is_err());
+    }
+}
@@ -0,0 +1,270 @@
+//! First-run setup, run one step at a time: detect runtimes, check the openclaw CLI, pick models
+//! (with llmfit suggestions), check their providers, then write openclaw.json. Progress is saved
+//! to ~/.openclaw/host-config/setup.json after each step so setup resumes where it stopped if the
+//! app closes midway.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::detection::{self, LocalLLMDetection};
+use crate::llmfit::{LlmfitCache, LlmfitRecommendation};
+use crate::{gateway, openclaw_config, system};
+
+/// Steps in the order they run.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum SetupStep {
+    DetectRuntimes,
+    CheckOpenclawCli,
+    PickModels,
+    ConfigureProviders,
+    GenerateConfig,
+}
+
+impl SetupStep {
+    pub const ALL: [SetupStep; 5] = [
+        SetupStep::DetectRuntimes,
+        SetupStep::CheckOpenclawCli,
+        SetupStep::PickModels,
+        SetupStep::ConfigureProviders,
+        SetupStep::GenerateConfig,
+    ];
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct OpenclawCliStatus {
+    pub found: bool,
+    pub version: Option<String>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SetupState {
+    pub completed: Vec<SetupStep>,
+    /// Next step to run; None once setup is done.
+    pub current: Option<SetupStep>,
+    pub detection: Option<LocalLLMDetection>,
+    pub openclaw_cli: Option<OpenclawCliStatus>,
+    /// llmfit recommendations offered at the pick-models step.
+    pub suggestions: Vec<LlmfitRecommendation>,
+    /// "provider/model" ids; the first becomes primary, the rest fallbacks.
+    pub selected_models: Vec<String>,
+    /// Providers the selected models use, checked at the configure-providers step.
+    pub providers: Vec<String>,
+}
+
+/// Input some steps take.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SetupInput {
+    /// Pick-models choice; without it the step only fills in suggestions.
+    pub models: Option<Vec<String>>,
+}
+
+fn setup_path() -> PathBuf {
+    openclaw_config::host_config_dir().join("setup.json")
+}
+
+/// The first step not yet completed.
+#[must_use]
+pub fn next_step(completed: &[SetupStep]) -> Option<SetupStep> {
+    SetupStep::ALL.into_iter().find(|s| !completed.contains(s))
+}
+
+/// Fails unless every step before `step` is completed.
+pub fn check_step_order(completed: &[SetupStep], step: SetupStep) -> Result<(), String> {
+    match SetupStep::ALL
+        .into_iter()
+        .find(|s| *s < step && !completed.contains(s))
+    {
+        Some(missing) => Err(format!(
+            "Setup step {:?} has to run before {:?}",
+            missing, step
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Splits "provider/model"; the model part may itself contain slashes.
+fn split_model_id(id: &str) -> Result<(&str, &str), String> {
+    id.split_once('/')
+        .filter(|(p, m)| !p.is_empty() && !m.is_empty())
+        .ok_or_else(|| format!("Expected provider/model, got {}", id))
+}
+
+/// Distinct providers of `models`, in first-seen order.
+pub fn providers_of(models: &[String]) -> Result<Vec<String>, String> {
+    let mut providers: Vec<String> = Vec::new();
+    for id in models {
+        let (provider, _) = split_model_id(id)?;
+        if !providers.iter().any(|p| p == provider) {
+            providers.push(provider.to_string());
+        }
+    }
+    Ok(providers)
+}
+
+/// Saved progress, or a fresh state at the first step.
+#[must_use]
+pub fn get_setup_state() -> SetupState {
+    let mut state: SetupState = fs::read_to_string(setup_path())
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default();
+    state.current = next_step(&state.completed);
+    state
+}
+
+fn save_setup_state(state: &SetupState) -> Result<(), String> {
+    let path = setup_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    fs::write(
+        &path,
+        serde_json::to_string_pretty(state).map_err(|e| e.to_string())?,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Forgets all progress so setup starts over.
+pub fn reset_setup() -> Result<SetupState, String> {
+    let path = setup_path();
+    if path.exists() {
+        fs::remove_file(&path).map_err(|e| e.to_string())?;
+    }
+    Ok(get_setup_state())
+}
+
+fn check_openclaw_cli() -> OpenclawCliStatus {
+    let version = system::command_stdout(&gateway::openclaw_bin(), &["--version"])
+        .and_then(|out| detection::parse_version_line(&out));
+    OpenclawCliStatus {
+        found: version.is_some(),
+        version,
+    }
+}
+
+/// Suggestions with a configurable id, installed models first.
+fn suggest_models(cache: &LlmfitCache) -> Vec<LlmfitRecommendation> {
+    let mut recs: Vec<LlmfitRecommendation> = cache
+        .recommendations(10, &Default::default())
+        .into_iter()
+        .filter(|r| r.provider_model_id.is_some())
+        .collect();
+    recs.sort_by_key(|r| !r.installed);
+    recs
+}
+
+/// Providers that can't be created on the fly (remote ones) must already be in openclaw.json.
+fn check_providers(providers: &[String]) -> Result<(), String> {
+    let root = openclaw_config::read_config_root()?;
+    let configured = root.pointer("/models/providers");
+    let missing: Vec<&str> = providers
+        .iter()
+        .map(String::as_str)
+        .filter(|p| {
+            configured.and_then(|c| c.get(*p)).is_none()
+                && openclaw_config::default_local_base_url(p).is_none()
+        })
+        .collect();
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Providers not configured: {}. Add them (with API keys) first.",
+            missing.join(", ")
+        ))
+    }
+}
+
+/// Registers the selected models under their providers, makes the first primary and the rest
+/// fallbacks, and writes openclaw.json. Models still have to be pulled into their runtime.
+fn generate_config(models: &[String]) -> Result<(), String> {
+    let mut root = openclaw_config::read_config_root()?;
+    for (i, id) in models.iter().enumerate() {
+        let (provider, model) = split_model_id(id)?;
+        openclaw_config::register_provider_model(&mut root, provider, model)?;
+        let role = if i == 0 { "primary" } else { "fallback" };
+        openclaw_config::set_model_role(&mut root, id, role)?;
+    }
+    openclaw_config::write_config_root(&root)
+}
+
+/// Runs `step` (all earlier steps must be done) and saves progress. Re-running a step clears the
+/// steps after it. The pick-models step stays current until `input.models` is given.
+pub fn run_setup_step(
+    step: SetupStep,
+    input: SetupInput,
+    cache: &LlmfitCache,
+) -> Result<SetupState, String> {
+    let mut state = get_setup_state();
+    check_step_order(&state.completed, step)?;
+    let done = match step {
+        SetupStep::DetectRuntimes => {
+            state.detection = Some(detection::detect_local_llms());
+            true
+        }
+        SetupStep::CheckOpenclawCli => {
+            state.openclaw_cli = Some(check_openclaw_cli());
+            true
+        }
+        SetupStep::PickModels => match input.models {
+            Some(models) => {
+                if models.is_empty() {
+                    return Err("Pick at least one model".to_string());
+                }
+                state.providers = providers_of(&models)?;
+                state.selected_models = models;
+                true
+            }
+            None => {
+                state.suggestions = suggest_models(cache);
+                false
+            }
+        },
+        SetupStep::ConfigureProviders => {
+            check_providers(&state.providers)?;
+            true
+        }
+        SetupStep::GenerateConfig => {
+            generate_config(&state.selected_models)?;
+            true
+        }
+    };
+    state.completed.retain(|s| *s < step);
+    if done {
+        state.completed.push(step);
+    }
+    state.current = next_step(&state.completed);
+    save_setup_state(&state)?;
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step_order() {
+        assert_eq!(next_step(&[]), Some(SetupStep::DetectRuntimes));
+        let done = [SetupStep::DetectRuntimes, SetupStep::CheckOpenclawCli];
+        assert_eq!(next_step(&done), Some(SetupStep::PickModels));
+        assert_eq!(next_step(&SetupStep::ALL), None);
+        assert!(check_step_order(&done, SetupStep::PickModels).is_ok());
+        assert!(check_step_order(&done, SetupStep::DetectRuntimes).is_ok());
+        assert!(check_step_order(&done, SetupStep::GenerateConfig).is_err());
+    }
+
+    #[test]
+    fn test_providers_of() {
+        let models = vec![
+            "ollama/qwen2.5:7b".to_string(),
+            "lmstudio/qwen/qwen3-8b".to_string(),
+            "ollama/llama3.2".to_string(),
+        ];
+        assert_eq!(providers_of(&models).unwrap(), vec!["ollama", "lmstudio"]);
+        assert!(providers_of(&["qwen2.5".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,636 @@
+//! Rate limits and spend budgets, for all providers or per provider. openclaw.json has no
+//! settings for either, so they are kept in ~/.openclaw/host-config/spend-limits.json and
+//! enforced by this app rather than the gateway. Spend is estimated from agent session usage
+//! (see `costs`) over a rolling day, week or month. When a budget is used up, its models (the
+//! provider's, or every non-local model for the global budget) are taken out of
+//! agents.defaults.model, as long as another model is left to run on, and put back once spend
+//! over the period drops below the budget or the budget is raised or removed. Per-agent model
+//! overrides are left alone. The gateway can't be throttled from outside, so rate limits are
+//! only checked against the requests in its log over the last minute and reported. Checks and
+//! model selection changes are pure functions for unit tests.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::agent_usage::UsagePeriod;
+use crate::costs::{self, CostLine};
+use crate::gateway_requests::{self, GatewayRequest, GatewayRequestFilter};
+use crate::openclaw_config;
+use crate::operations;
+
+// Upper bounds that catch unit mistakes (per hour for per minute, cents for dollars).
+const MAX_REQUESTS_PER_MINUTE: u64 = 100_000;
+const MAX_TOKENS_PER_MINUTE: u64 = 100_000_000;
+const MAX_BUDGET_USD: f64 = 1_000_000.0;
+const RATE_WINDOW_MS: u64 = 60_000;
+const RATE_REQUEST_LIMIT: usize = 2000;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetPeriod {
+    Day,
+    Week,
+    Month,
+}
+
+impl BudgetPeriod {
+    fn usage_period(self) -> UsagePeriod {
+        match self {
+            Self::Day => UsagePeriod::Day,
+            Self::Week => UsagePeriod::Week,
+            Self::Month => UsagePeriod::Month,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Budget {
+    /// USD the scope may spend per period; 0 allows no paid models at all.
+    pub amount_usd: f64,
+    pub period: BudgetPeriod,
+}
+
+/// Unset fields mean no limit.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SpendLimits {
+    pub requests_per_minute: Option<u64>,
+    pub tokens_per_minute: Option<u64>,
+    pub budget: Option<Budget>,
+}
+
+impl SpendLimits {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// A model taken out of agents.defaults.model because a budget was used up.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SuspendedModel {
+    pub model: String,
+    /// The provider whose budget it counts against; None for the global budget.
+    pub provider: Option<String>,
+    pub was_primary: bool,
+    /// Unix seconds.
+    pub suspended_at: u64,
+}
+
+/// What spend-limits.json holds.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SpendLimitsFile {
+    pub global: SpendLimits,
+    pub providers: BTreeMap<String, SpendLimits>,
+    pub suspended: Vec<SuspendedModel>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ProviderLimits {
+    pub provider: String,
+    pub limits: SpendLimits,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct SpendLimitsView {
+    pub global: SpendLimits,
+    /// Every provider in openclaw.json, by name; providers without limits have none set.
+    pub providers: Vec<ProviderLimits>,
+    pub suspended: Vec<SuspendedModel>,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitKind {
+    Budget,
+    RequestsPerMinute,
+    TokensPerMinute,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct LimitBreach {
+    /// None for the global limits.
+    pub provider: Option<String>,
+    pub kind: LimitKind,
+    pub limit: f64,
+    /// USD spent over the period, or requests/tokens in the last minute.
+    pub actual: f64,
+    pub period: Option<BudgetPeriod>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct SpendLimitReport {
+    pub breaches: Vec<LimitBreach>,
+    /// Models taken out of agents.defaults.model by this check.
+    pub suspended: Vec<String>,
+    /// Models put back by this check.
+    pub restored: Vec<String>,
+    /// Budgets that are used up but couldn't be enforced.
+    pub warnings: Vec<String>,
+}
+
+/// Rejects zero rates, negative or non-finite budgets and values too large to be intended.
+pub fn validate_limits(limits: &SpendLimits) -> Result<(), String> {
+    for (name, value, max) in [
+        (
+            "Requests per minute",
+            limits.requests_per_minute,
+            MAX_REQUESTS_PER_MINUTE,
+        ),
+        (
+            "Tokens per minute",
+            limits.tokens_per_minute,
+            MAX_TOKENS_PER_MINUTE,
+        ),
+    ] {
+        match value {
+            Some(0) => {
+                return Err(format!(
+                    "{} must be at least 1; leave it empty for no limit",
+                    name
+                ))
+            }
+            Some(n) if n > max => return Err(format!("{} must be at most {}", name, max)),
+            _ => {}
+        }
+    }
+    if let Some(budget) = &limits.budget {
+        if !budget.amount_usd.is_finite() || budget.amount_usd < 0.0 {
+            return Err("Budget must be a dollar amount of 0 or more".to_string());
+        }
+        if budget.amount_usd > MAX_BUDGET_USD {
+            return Err(format!("Budget must be at most ${}", MAX_BUDGET_USD));
+        }
+    }
+    Ok(())
+}
+
+fn provider_of(model: &str) -> &str {
+    model.split_once('/').map_or("", |(p, _)| p)
+}
+
+/// Scopes with limits: (None, global) first, then each provider.
+fn scopes(file: &SpendLimitsFile) -> impl Iterator<Item = (Option<&str>, &SpendLimits)> {
+    std::iter::once((None, &file.global)).chain(
+        file.providers
+            .iter()
+            .map(|(name, limits)| (Some(name.as_str()), limits)),
+    )
+}
+
+/// Limits in `file` that are exceeded. `spend` holds each budget period's cost per provider;
+/// `requests` the gateway's requests, of which those in the minute before `now_ms` count.
+#[must_use]
+pub fn find_breaches(
+    file: &SpendLimitsFile,
+    spend: &[(BudgetPeriod, Vec<CostLine>)],
+    requests: &[GatewayRequest],
+    now_ms: u64,
+) -> Vec<LimitBreach> {
+    let recent: Vec<&GatewayRequest> = requests
+        .iter()
+        .filter(|r| r.timestamp_ms + RATE_WINDOW_MS > now_ms)
+        .collect();
+    let mut out = Vec::new();
+    for (provider, limits) in scopes(file) {
+        let in_scope = |p: &str| provider.is_none_or(|name| name == p);
+        let breach = |kind, limit: f64, actual: f64, period| LimitBreach {
+            provider: provider.map(String::from),
+            kind,
+            limit,
+            actual,
+            period,
+        };
+        if let Some(budget) = limits.budget {
+            let spent: f64 = spend
+                .iter()
+                .filter(|(period, _)| *period == budget.period)
+                .flat_map(|(_, lines)| lines)
+                .filter(|line| in_scope(&line.key))
+                .map(|line| line.cost_usd)
+                .sum();
+            if spent >= budget.amount_usd {
+                out.push(breach(
+                    LimitKind::Budget,
+                    budget.amount_usd,
+                    spent,
+                    Some(budget.period),
+                ));
+            }
+        }
+        let scoped: Vec<&&GatewayRequest> = recent
+            .iter()
+            .filter(|r| in_scope(provider_of(&r.model)))
+            .collect();
+        if let Some(rpm) = limits.requests_per_minute {
+            if scoped.len() as u64 > rpm {
+                out.push(breach(
+                    LimitKind::RequestsPerMinute,
+                    rpm as f64,
+                    scoped.len() as f64,
+                    None,
+                ));
+            }
+        }
+        if let Some(tpm) = limits.tokens_per_minute {
+            let tokens: u64 = scoped
+                .iter()
+                .map(|r| r.input_tokens.unwrap_or(0) + r.output_tokens.unwrap_or(0))
+                .sum();
+            if tokens > tpm {
+                out.push(breach(
+                    LimitKind::TokensPerMinute,
+                    tpm as f64,
+                    tokens as f64,
+                    None,
+                ));
+            }
+        }
+    }
+    out
+}
+
+/// agents.defaults.model as (primary, fallbacks).
+fn default_selection(root: &Value) -> (Option<String>, Vec<String>) {
+    let model = root.pointer("/agents/defaults/model");
+    let primary = model
+        .and_then(|m| m.as_str().or_else(|| m.get("primary")?.as_str()))
+        .map(String::from);
+    let fallbacks = model
+        .and_then(|m| m.get("fallbacks"))
+        .and_then(|f| f.as_array())
+        .map(|a| {
+            a.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+    (primary, fallbacks)
+}
+
+fn set_default_selection(root: &mut Value, primary: Option<String>, fallbacks: Vec<String>) {
+    let mut model = match root.pointer("/agents/defaults/model") {
+        Some(Value::Object(obj)) => obj.clone(),
+        _ => serde_json::Map::new(),
+    };
+    match primary {
+        Some(p) => model.insert("primary".to_string(), Value::from(p)),
+        None => model.remove("primary"),
+    };
+    model.insert("fallbacks".to_string(), Value::from(fallbacks));
+    openclaw_config::set_nested(root, &["agents", "defaults", "model"], Value::Object(model));
+}
+
+/// Takes models that used-up budgets in `breaches` cover out of agents.defaults.model, recording
+/// them in `suspended`; a budget isn't enforced when that would leave no model. Returns the
+/// suspended model ids and warnings.
+pub fn suspend_models(
+    root: &mut Value,
+    breaches: &[LimitBreach],
+    suspended: &mut Vec<SuspendedModel>,
+    now_secs: u64,
+) -> (Vec<String>, Vec<String>) {
+    let providers = root
+        .pointer("/models/providers")
+        .cloned()
+        .unwrap_or_default();
+    let (mut primary, mut fallbacks) = default_selection(root);
+    let mut newly = Vec::new();
+    let mut warnings = Vec::new();
+    for breach in breaches.iter().filter(|b| b.kind == LimitKind::Budget) {
+        let covered = |id: &str| match &breach.provider {
+            Some(p) => provider_of(id) == p,
+            None => !openclaw_config::is_local_model(id, &providers),
+        };
+        let remaining: Vec<String> = primary
+            .iter()
+            .chain(&fallbacks)
+            .filter(|id| !covered(id))
+            .cloned()
+            .collect();
+        let hits = primary.iter().chain(&fallbacks).filter(|id| covered(id));
+        let hits: Vec<String> = hits.cloned().collect();
+        if hits.is_empty() {
+            continue;
+        }
+        let scope = breach.provider.as_deref().unwrap_or("all providers");
+        if remaining.is_empty() {
+            warnings.push(format!(
+                "The budget for {} is used up, but no other model is selected to switch to",
+                scope
+            ));
+            continue;
+        }
+        for id in hits {
+            suspended.retain(|s| s.model != id);
+            suspended.push(SuspendedModel {
+                was_primary: primary.as_deref() == Some(id.as_str()),
+                model: id.clone(),
+                provider: breach.provider.clone(),
+                suspended_at: now_secs,
+            });
+            newly.push(id);
+        }
+        primary = remaining.first().cloned();
+        fallbacks = remaining.into_iter().skip(1).collect();
+    }
+    if !newly.is_empty() {
+        set_default_selection(root, primary, fallbacks);
+    }
+    (newly, warnings)
+}
+
+/// Puts back suspended models whose budget is no longer used up (not in `breaches`): a former
+/// primary becomes primary again, the others return to the fallbacks. Returns their ids.
+pub fn restore_models(
+    root: &mut Value,
+    breaches: &[LimitBreach],
+    suspended: &mut Vec<SuspendedModel>,
+) -> Vec<String> {
+    let still_over = |s: &SuspendedModel| {
+        breaches
+            .iter()
+            .any(|b| b.kind == LimitKind::Budget && b.provider == s.provider)
+    };
+    let (back, keep): (Vec<SuspendedModel>, Vec<SuspendedModel>) =
+        suspended.drain(..).partition(|s| !still_over(s));
+    *suspended = keep;
+    if back.is_empty() {
+        return vec![];
+    }
+    let (mut primary, mut fallbacks) = default_selection(root);
+    for s in &back {
+        fallbacks.retain(|f| *f != s.model);
+        if primary.as_deref() == Some(s.model.as_str()) {
+            continue;
+        }
+        if s.was_primary {
+            if let Some(previous) = primary.replace(s.model.clone()) {
+                fallbacks.insert(0, previous);
+            }
+        } else {
+            fallbacks.push(s.model.clone());
+        }
+    }
+    set_default_selection(root, primary, fallbacks);
+    back.into_iter().map(|s| s.model).collect()
+}
+
+fn limits_path() -> PathBuf {
+    openclaw_config::host_config_dir().join("spend-limits.json")
+}
+
+fn read_file() -> SpendLimitsFile {
+    fs::read_to_string(limits_path())
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn write_file(file: &SpendLimitsFile) -> Result<(), String> {
+    let path = limits_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    fs::write(
+        &path,
+        serde_json::to_string_pretty(file).map_err(|e| e.to_string())?,
+    )
+    .map_err(|e| e.to_string())
+}
+
+fn view(file: &SpendLimitsFile, root: &Value) -> SpendLimitsView {
+    let names = root
+        .pointer("/models/providers")
+        .and_then(|p| p.as_object())
+        .into_iter()
+        .flat_map(|p| p.keys());
+    let mut providers: Vec<ProviderLimits> = names
+        .map(|name| ProviderLimits {
+            provider: name.clone(),
+            limits: file.providers.get(name).cloned().unwrap_or_default(),
+        })
+        .collect();
+    providers.sort_by(|a, b| a.provider.cmp(&b.provider));
+    SpendLimitsView {
+        global: file.global.clone(),
+        providers,
+        suspended: file.suspended.clone(),
+    }
+}
+
+/// The rate limits and budgets, with every provider in openclaw.json.
+pub fn get_spend_limits() -> Result<SpendLimitsView, String> {
+    let root = openclaw_config::read_config_root()?;
+    Ok(view(&read_file(), &root))
+}
+
+/// Replaces the limits for `provider` (global when None) and returns the updated view. Takes
+/// effect at the next `enforce_spend_limits`.
+pub fn update_spend_limits(
+    provider: Option<&str>,
+    limits: &SpendLimits,
+) -> Result<SpendLimitsView, String> {
+    validate_limits(limits)?;
+    let root = openclaw_config::read_config_root()?;
+    let mut file = read_file();
+    match provider {
+        None => file.global = limits.clone(),
+        Some(name) if limits.is_empty() => {
+            file.providers.remove(name);
+        }
+        Some(name) => {
+            if root
+                .pointer("/models/providers")
+                .and_then(|p| p.get(name))
+                .is_none()
+            {
+                return Err(format!("Unknown provider: {}", name));
+            }
+            file.providers.insert(name.to_string(), limits.clone());
+        }
+    }
+    write_file(&file)?;
+    Ok(view(&file, &root))
+}
+
+/// Checks the limits against current spend and gateway traffic, suspends or restores models
+/// for budgets and saves the changes.
+pub fn enforce_spend_limits() -> Result<SpendLimitReport, String> {
+    let mut file = read_file();
+    let all: Vec<&SpendLimits> = scopes(&file).map(|(_, l)| l).collect();
+    if all.iter().all(|l| l.is_empty()) && file.suspended.is_empty() {
+        return Ok(SpendLimitReport::default());
+    }
+    let mut periods: Vec<BudgetPeriod> = all
+        .iter()
+        .filter_map(|l| l.budget.map(|b| b.period))
+        .collect();
+    periods.sort();
+    periods.dedup();
+    let mut spend = Vec::new();
+    for period in periods {
+        spend.push((
+            period,
+            costs::estimate_costs(period.usage_period())?.by_provider,
+        ));
+    }
+    let now = operations::now_ms();
+    let requests = if all
+        .iter()
+        .any(|l| l.requests_per_minute.is_some() || l.tokens_per_minute.is_some())
+    {
+        let filter = GatewayRequestFilter {
+            since_ms: Some(now.saturating_sub(RATE_WINDOW_MS)),
+            limit: Some(RATE_REQUEST_LIMIT),
+            ..GatewayRequestFilter::default()
+        };
+        gateway_requests::tail_gateway_requests(&filter)
+            .map(|t| t.requests)
+            .unwrap_or_default()
+    } else {
+        vec![]
+    };
+    let breaches = find_breaches(&file, &spend, &requests, now);
+
+    let mut root = openclaw_config::read_config_root()?;
+    let restored = restore_models(&mut root, &breaches, &mut file.suspended);
+    let (suspended, warnings) =
+        suspend_models(&mut root, &breaches, &mut file.suspended, now / 1000);
+    if !restored.is_empty() || !suspended.is_empty() {
+        openclaw_config::write_config_root(&root)?;
+        write_file(&file)?;
+    }
+    Ok(SpendLimitReport {
+        breaches,
+        suspended,
+        restored,
+        warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(key: &str, cost_usd: f64) -> CostLine {
+        CostLine {
+            key: key.to_string(),
+            cost_usd,
+            ..CostLine::default()
+        }
+    }
+
+    fn budget(amount_usd: f64, period: BudgetPeriod) -> SpendLimits {
+        SpendLimits {
+            budget: Some(Budget { amount_usd, period }),
+            ..SpendLimits::default()
+        }
+    }
+
+    #[test]
+    fn test_validate_limits() {
+        assert!(validate_limits(&budget(0.0, BudgetPeriod::Day)).is_ok());
+        assert!(validate_limits(&budget(-1.0, BudgetPeriod::Day)).is_err());
+        assert!(validate_limits(&budget(f64::NAN, BudgetPeriod::Day)).is_err());
+        assert!(validate_limits(&budget(5e6, BudgetPeriod::Day)).is_err());
+        let rpm = SpendLimits {
+            requests_per_minute: Some(0),
+            ..SpendLimits::default()
+        };
+        assert!(validate_limits(&rpm).unwrap_err().contains("at least 1"));
+    }
+
+    #[test]
+    fn test_find_breaches() {
+        let mut file = SpendLimitsFile {
+            global: budget(100.0, BudgetPeriod::Month),
+            ..SpendLimitsFile::default()
+        };
+        let mut anthropic = budget(20.0, BudgetPeriod::Month);
+        anthropic.requests_per_minute = Some(1);
+        file.providers.insert("anthropic".to_string(), anthropic);
+        let spend = vec![(
+            BudgetPeriod::Month,
+            vec![line("anthropic", 25.0), line("openai", 10.0)],
+        )];
+        let request = |ts, model: &str| GatewayRequest {
+            timestamp_ms: ts,
+            agent: None,
+            model: model.to_string(),
+            latency_ms: None,
+            input_tokens: None,
+            output_tokens: None,
+            status: "ok".to_string(),
+            error: None,
+        };
+        let requests = vec![
+            request(100_000, "anthropic/claude-sonnet-4-5"),
+            request(110_000, "anthropic/claude-sonnet-4-5"),
+            request(10_000, "anthropic/claude-sonnet-4-5"),
+        ];
+        let breaches = find_breaches(&file, &spend, &requests, 120_000);
+        assert_eq!(breaches.len(), 2);
+        assert_eq!(breaches[0].kind, LimitKind::Budget);
+        assert_eq!(breaches[0].actual, 25.0);
+        assert_eq!(breaches[1].kind, LimitKind::RequestsPerMinute);
+        assert_eq!(breaches[1].actual, 2.0);
+
+        file.global = budget(30.0, BudgetPeriod::Month);
+        let breaches = find_breaches(&file, &spend, &[], 120_000);
+        assert_eq!(breaches[0].provider, None);
+        assert_eq!(breaches[0].actual, 35.0);
+    }
+
+    #[test]
+    fn test_suspend_and_restore() {
+        let mut root = serde_json::json!({
+            "agents": { "defaults": { "model": {
+                "primary": "anthropic/claude-sonnet-4-5",
+                "fallbacks": ["openai/gpt-4.1", "ollama/qwen3:8b"]
+            } } }
+        });
+        let over = |provider: Option<&str>| LimitBreach {
+            provider: provider.map(String::from),
+            kind: LimitKind::Budget,
+            limit: 20.0,
+            actual: 25.0,
+            period: Some(BudgetPeriod::Month),
+        };
+        let mut suspended = Vec::new();
+        let (ids, warnings) =
+            suspend_models(&mut root, &[over(Some("anthropic"))], &mut suspended, 1);
+        assert_eq!(ids, vec!["anthropic/claude-sonnet-4-5"]);
+        assert!(warnings.is_empty());
+        assert_eq!(
+            root["agents"]["defaults"]["model"]["primary"],
+            "openai/gpt-4.1"
+        );
+        assert!(suspended[0].was_primary);
+
+        // The global budget covers every non-local model; ollama is left to run on.
+        let (ids, _) = suspend_models(&mut root, &[over(None)], &mut suspended, 2);
+        assert_eq!(ids, vec!["openai/gpt-4.1"]);
+        assert_eq!(
+            root["agents"]["defaults"]["model"]["primary"],
+            "ollama/qwen3:8b"
+        );
+        let (ids, warnings) = suspend_models(&mut root, &[over(Some("ollama"))], &mut suspended, 3);
+        assert!(ids.is_empty());
+        assert_eq!(warnings.len(), 1);
+
+        let restored = restore_models(&mut root, &[over(None)], &mut suspended);
+        assert_eq!(restored, vec!["anthropic/claude-sonnet-4-5"]);
+        assert_eq!(
+            root["agents"]["defaults"]["model"],
+            serde_json::json!({
+                "primary": "anthropic/claude-sonnet-4-5",
+                "fallbacks": ["ollama/qwen3:8b"]
+            })
+        );
+        assert_eq!(suspended.len(), 1);
+    }
+}
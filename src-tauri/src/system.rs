@@ -1,9 +1,14 @@
-//! System information (RAM, etc.) for hardware-aware model selection.
+//! System information (RAM, GPU, disk) for hardware-aware model selection.
 
 use serde::{Deserialize, Serialize};
-use sysinfo::System;
+use std::path::Path;
+use std::process::Command;
+use sysinfo::{Disks, System};
 
-#[derive(Clone, Serialize, Deserialize)]
+use crate::agents;
+use crate::disk_usage;
+
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub struct SystemInfo {
     /// Total physical RAM in bytes.
     pub total_memory_bytes: u64,
@@ -13,6 +18,170 @@ pub struct SystemInfo {
     pub total_memory_human: String,
     /// Available memory as human-readable string.
     pub available_memory_human: String,
+    pub swap_total_bytes: u64,
+    pub swap_used_bytes: u64,
+    pub memory_pressure: MemoryPressure,
+    pub cpu: CpuInfo,
+    pub os: OsInfo,
+    /// Primary GPU; only filled by `get_system_info_with_gpu`.
+    pub gpu: Option<GpuInfo>,
+}
+
+/// How close the machine is to swapping: green is comfortable, yellow is tight or already
+/// swapping, red means new allocations will mostly come from swap.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryPressure {
+    #[default]
+    Green,
+    Yellow,
+    Red,
+}
+
+/// Classifies memory pressure from free RAM (as a share of total) and swap in use. Under 10%
+/// free is red, as is under 25% free with more than half the swap used; under 25% free or more
+/// than half the swap used alone is yellow.
+#[must_use]
+pub fn classify_memory_pressure(
+    total: u64,
+    available: u64,
+    swap_total: u64,
+    swap_used: u64,
+) -> MemoryPressure {
+    if total == 0 {
+        return MemoryPressure::Green;
+    }
+    let free = available as f64 / total as f64;
+    let swapping = swap_total > 0 && swap_used as f64 / swap_total as f64 > 0.5;
+    if free < 0.10 || (free < 0.25 && swapping) {
+        MemoryPressure::Red
+    } else if free < 0.25 || swapping {
+        MemoryPressure::Yellow
+    } else {
+        MemoryPressure::Green
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct OsInfo {
+    /// e.g. "Darwin", "Ubuntu", "Windows".
+    pub name: Option<String>,
+    /// e.g. "macOS 14.5 Sonoma", "Linux 24.04 Ubuntu".
+    pub version: Option<String>,
+    pub kernel: Option<String>,
+    /// Machine architecture: "x86_64", "aarch64" (or "arm64" on macOS), ...
+    pub arch: String,
+    /// Apple Silicon, including when this build runs under Rosetta.
+    pub apple_silicon: bool,
+    /// "wsl", "docker", "container" or "vm"; None on bare metal or when undetectable.
+    pub virtualization: Option<String>,
+}
+
+/// What `detect_virtualization` looks at, gathered from the filesystem and sysctl.
+#[derive(Clone, Debug, Default)]
+pub struct VirtualizationSignals {
+    /// /proc/sys/kernel/osrelease.
+    pub kernel_release: String,
+    /// /.dockerenv exists.
+    pub dockerenv: bool,
+    /// /run/.containerenv exists (Podman).
+    pub containerenv: bool,
+    /// /proc/1/cgroup.
+    pub init_cgroup: String,
+    /// /sys/class/dmi/id/sys_vendor and product_name.
+    pub dmi: String,
+    /// CPU "hypervisor" flag (Linux) or kern.hv_vmm_present (macOS).
+    pub hypervisor: bool,
+}
+
+/// Classifies the environment, most specific first: WSL, Docker, other containers, then VMs.
+#[must_use]
+pub fn detect_virtualization(signals: &VirtualizationSignals) -> Option<String> {
+    let kernel = signals.kernel_release.to_lowercase();
+    let cgroup = &signals.init_cgroup;
+    let dmi = signals.dmi.to_lowercase();
+    let name = if kernel.contains("microsoft") || kernel.contains("wsl") {
+        "wsl"
+    } else if signals.dockerenv || cgroup.contains("docker") {
+        "docker"
+    } else if signals.containerenv
+        || ["containerd", "kubepods", "lxc", "libpod"]
+            .iter()
+            .any(|c| cgroup.contains(c))
+    {
+        "container"
+    } else if signals.hypervisor
+        || [
+            "virtualbox",
+            "vmware",
+            "kvm",
+            "qemu",
+            "xen",
+            "parallels",
+            "virtual machine",
+        ]
+        .iter()
+        .any(|v| dmi.contains(v))
+    {
+        "vm"
+    } else {
+        return None;
+    };
+    Some(name.to_string())
+}
+
+fn virtualization_signals() -> VirtualizationSignals {
+    let read = |p: &str| std::fs::read_to_string(p).unwrap_or_default();
+    if cfg!(target_os = "macos") {
+        return VirtualizationSignals {
+            hypervisor: command_stdout("sysctl", &["-n", "kern.hv_vmm_present"])
+                .is_some_and(|v| v.trim() == "1"),
+            ..Default::default()
+        };
+    }
+    VirtualizationSignals {
+        kernel_release: read("/proc/sys/kernel/osrelease"),
+        dockerenv: Path::new("/.dockerenv").exists(),
+        containerenv: Path::new("/run/.containerenv").exists(),
+        init_cgroup: read("/proc/1/cgroup"),
+        dmi: format!(
+            "{} {}",
+            read("/sys/class/dmi/id/sys_vendor"),
+            read("/sys/class/dmi/id/product_name")
+        ),
+        hypervisor: cpu_flag_text()
+            .split_whitespace()
+            .any(|f| f == "hypervisor"),
+    }
+}
+
+fn os_info() -> OsInfo {
+    let arch = System::cpu_arch().unwrap_or_else(|| std::env::consts::ARCH.to_string());
+    let apple_silicon = cfg!(target_os = "macos")
+        && (arch == "arm64"
+            || arch == "aarch64"
+            || command_stdout("sysctl", &["-n", "sysctl.proc_translated"])
+                .is_some_and(|v| v.trim() == "1"));
+    OsInfo {
+        name: System::name(),
+        version: System::long_os_version(),
+        kernel: System::kernel_version(),
+        arch,
+        apple_silicon,
+        virtualization: detect_virtualization(&virtualization_signals()),
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct CpuInfo {
+    pub brand: String,
+    pub physical_cores: Option<u32>,
+    pub logical_cores: u32,
+    /// Base frequency where the OS reports it, else the current frequency of the first core.
+    pub frequency_mhz: Option<u64>,
+    /// Instruction-set extensions relevant to llama.cpp, lowercase: "avx", "avx2", "fma", "f16c",
+    /// "avx512", "avx512_vnni", "avx512_bf16", "amx", "neon", "dotprod", "i8mm", "sve".
+    pub features: Vec<String>,
 }
 
 /// Returns total and available system RAM.
@@ -24,12 +193,145 @@ pub fn get_system_info() -> SystemInfo {
 
     let total = sys.total_memory();
     let available = sys.available_memory();
+    let (swap_total, swap_used) = (sys.total_swap(), sys.used_swap());
+    let first = sys.cpus().first();
+    let cpu = CpuInfo {
+        brand: first
+            .map(|c| c.brand().trim().to_string())
+            .unwrap_or_default(),
+        physical_cores: sys.physical_core_count().map(|n| n as u32),
+        logical_cores: sys.cpus().len() as u32,
+        frequency_mhz: base_frequency_mhz()
+            .or_else(|| first.map(|c| c.frequency()).filter(|f| *f > 0)),
+        features: cpu_features(),
+    };
 
     SystemInfo {
         total_memory_bytes: total,
         available_memory_bytes: available,
         total_memory_human: bytes_to_human(total),
         available_memory_human: bytes_to_human(available),
+        swap_total_bytes: swap_total,
+        swap_used_bytes: swap_used,
+        memory_pressure: classify_memory_pressure(total, available, swap_total, swap_used),
+        cpu,
+        os: os_info(),
+        gpu: None,
+    }
+}
+
+/// `get_system_info` plus GPU detection (runs the vendor tools, so slower).
+#[must_use]
+pub fn get_system_info_with_gpu() -> SystemInfo {
+    SystemInfo {
+        gpu: detect_gpu(),
+        ..get_system_info()
+    }
+}
+
+/// Maps CPU flag names as reported by /proc/cpuinfo (x86 "flags", ARM "Features") or macOS
+/// sysctl (`machdep.cpu.*features`, `hw.optional.*: 1`) to `CpuInfo::features` names.
+#[must_use]
+pub fn cpu_features_from_flags(flags: &str) -> Vec<String> {
+    let mut features: Vec<String> = flags
+        .split(|c: char| c.is_whitespace() || c == ':')
+        .filter_map(|flag| {
+            // "hw.optional.arm.FEAT_DotProd" -> "dotprod".
+            let flag = flag
+                .strip_prefix("hw.optional.")
+                .map_or(flag, |f| f.rsplit('.').next().unwrap_or(f))
+                .trim_start_matches("FEAT_")
+                .to_lowercase();
+            let name = match flag.as_str() {
+                "avx" | "avx1.0" => "avx",
+                "avx2" => "avx2",
+                "fma" => "fma",
+                "f16c" => "f16c",
+                "avx512f" => "avx512",
+                "avx512_vnni" | "avx512vnni" => "avx512_vnni",
+                "avx512_bf16" | "avx512bf16" => "avx512_bf16",
+                "amx_tile" | "amx_int8" | "amx_bf16" => "amx",
+                "asimd" | "neon" | "advsimd" => "neon",
+                "asimddp" | "dotprod" => "dotprod",
+                "i8mm" => "i8mm",
+                "sve" => "sve",
+                _ => return None,
+            };
+            Some(name.to_string())
+        })
+        .collect();
+    features.sort();
+    features.dedup();
+    features
+}
+
+/// Raw flag text for `cpu_features_from_flags`. Empty when the OS offers nothing readable.
+fn cpu_flag_text() -> String {
+    if cfg!(target_os = "linux") {
+        let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
+        cpuinfo
+            .lines()
+            .find(|l| l.starts_with("flags") || l.starts_with("Features"))
+            .and_then(|l| l.split_once(':'))
+            .map(|(_, flags)| flags.to_string())
+            .unwrap_or_default()
+    } else if cfg!(target_os = "macos") {
+        let features = command_stdout(
+            "sysctl",
+            &["-n", "machdep.cpu.features", "machdep.cpu.leaf7_features"],
+        )
+        .unwrap_or_default();
+        // Only the "hw.optional.<feature>: 1" lines name present features.
+        let optional = command_stdout("sysctl", &["hw.optional"])
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|l| l.strip_suffix(": 1"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        format!("{} {}", features, optional)
+    } else {
+        String::new()
+    }
+}
+
+fn cpu_features() -> Vec<String> {
+    let mut text = cpu_flag_text();
+    #[cfg(target_arch = "x86_64")]
+    for (flag, present) in [
+        ("avx", std::is_x86_feature_detected!("avx")),
+        ("avx2", std::is_x86_feature_detected!("avx2")),
+        ("fma", std::is_x86_feature_detected!("fma")),
+        ("f16c", std::is_x86_feature_detected!("f16c")),
+        ("avx512f", std::is_x86_feature_detected!("avx512f")),
+    ] {
+        if present {
+            text.push(' ');
+            text.push_str(flag);
+        }
+    }
+    if cfg!(target_arch = "aarch64") {
+        text.push_str(" neon");
+    }
+    cpu_features_from_flags(&text)
+}
+
+/// Base clock: cpufreq's base_frequency (kHz) on Linux, hw.cpufrequency (Hz, Intel only) on macOS.
+fn base_frequency_mhz() -> Option<u64> {
+    if cfg!(target_os = "linux") {
+        std::fs::read_to_string("/sys/devices/system/cpu/cpu0/cpufreq/base_frequency")
+            .ok()?
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .map(|khz| khz / 1000)
+    } else if cfg!(target_os = "macos") {
+        command_stdout("sysctl", &["-n", "hw.cpufrequency"])?
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .map(|hz| hz / 1_000_000)
+    } else {
+        None
     }
 }
 
@@ -50,10 +352,280 @@ pub fn bytes_to_human(bytes: u64) -> String {
     }
 }
 
+/// Free and total space (bytes) of the volume holding `path`, picked by longest matching mount point.
+#[must_use]
+pub fn volume_space_for_path(path: &Path) -> Option<(u64, u64)> {
+    let disks = Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|d| path.starts_with(d.mount_point()))
+        .max_by_key(|d| d.mount_point().as_os_str().len())
+        .map(|d| (d.available_space(), d.total_space()))
+}
+
+/// Free space below which model downloads are flagged, on top of the download itself.
+pub const LOW_SPACE_THRESHOLD_BYTES: u64 = 10 * 1024 * 1024 * 1024;
+
+/// Size of a directory the app cares about and the free space on its volume.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DiskSpace {
+    /// "openclaw", "ollama" or "lmstudio".
+    pub label: String,
+    pub path: String,
+    pub used_bytes: u64,
+    pub used_human: String,
+    pub free_bytes: Option<u64>,
+    pub free_human: Option<String>,
+    pub volume_total_bytes: Option<u64>,
+    /// Free space is under `LOW_SPACE_THRESHOLD_BYTES`.
+    pub low_space: bool,
+}
+
+/// Payload for the `disk-space-low` event, sent before a download that may not fit.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct LowSpaceWarning {
+    pub path: String,
+    pub free_bytes: u64,
+    /// Estimated download size; 0 when unknown.
+    pub needed_bytes: u64,
+    pub message: String,
+}
+
+/// Warns when `free_bytes` can't hold `needed_bytes` plus the low-space threshold.
+#[must_use]
+pub fn low_space_warning(
+    path: &str,
+    free_bytes: u64,
+    needed_bytes: u64,
+) -> Option<LowSpaceWarning> {
+    if free_bytes >= needed_bytes.saturating_add(LOW_SPACE_THRESHOLD_BYTES) {
+        return None;
+    }
+    let message = if needed_bytes > free_bytes {
+        format!(
+            "Not enough space on the volume holding {}: {} needed, {} free",
+            path,
+            bytes_to_human(needed_bytes),
+            bytes_to_human(free_bytes)
+        )
+    } else {
+        format!(
+            "Low disk space on the volume holding {}: {} free",
+            path,
+            bytes_to_human(free_bytes)
+        )
+    };
+    Some(LowSpaceWarning {
+        path: path.to_string(),
+        free_bytes,
+        needed_bytes,
+        message,
+    })
+}
+
+/// `low_space_warning` for the volume holding `path`; None when the volume can't be found.
+#[must_use]
+pub fn check_low_space(path: &Path, needed_bytes: u64) -> Option<LowSpaceWarning> {
+    let (free, _) = volume_space_for_path(path)?;
+    low_space_warning(&path.to_string_lossy(), free, needed_bytes)
+}
+
+/// Usage and free space for ~/.openclaw and the Ollama and LM Studio model directories
+/// (those that exist).
+#[must_use]
+pub fn get_disk_space() -> Vec<DiskSpace> {
+    let mut dirs = vec![("openclaw", agents::openclaw_root())];
+    dirs.extend(disk_usage::ollama_models_dir().map(|d| ("ollama", d)));
+    dirs.extend(disk_usage::lm_studio_models_dir().map(|d| ("lmstudio", d)));
+    dirs.into_iter()
+        .filter(|(_, path)| path.exists())
+        .map(|(label, path)| {
+            let used = disk_usage::path_size(&path);
+            let volume = volume_space_for_path(&path);
+            DiskSpace {
+                label: label.to_string(),
+                path: path.to_string_lossy().to_string(),
+                used_bytes: used,
+                used_human: bytes_to_human(used),
+                free_bytes: volume.map(|(free, _)| free),
+                free_human: volume.map(|(free, _)| bytes_to_human(free)),
+                volume_total_bytes: volume.map(|(_, total)| total),
+                low_space: volume.is_some_and(|(free, _)| free < LOW_SPACE_THRESHOLD_BYTES),
+            }
+        })
+        .collect()
+}
+
+/// Primary GPU as seen by the local runtimes.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct GpuInfo {
+    pub name: String,
+    /// Dedicated VRAM, or the share of unified memory usable by the GPU on Apple Silicon.
+    pub vram_bytes: u64,
+    /// "cuda", "rocm" or "metal".
+    pub backend: String,
+    /// VRAM in use right now, when the vendor tool reports it.
+    pub vram_used_bytes: Option<u64>,
+    pub driver_version: Option<String>,
+}
+
+/// Parses `nvidia-smi --query-gpu=name,memory.total,memory.used,driver_version
+/// --format=csv,noheader,nounits` (MiB); picks the GPU with the most memory.
+#[must_use]
+pub fn parse_nvidia_smi(stdout: &str) -> Option<GpuInfo> {
+    const MIB: u64 = 1024 * 1024;
+    stdout
+        .lines()
+        .filter_map(|line| {
+            // Split from the right: GPU names may contain commas.
+            let mut fields = line.rsplitn(4, ',').map(str::trim);
+            let (driver, used, total, name) = (
+                fields.next()?,
+                fields.next()?,
+                fields.next()?,
+                fields.next()?,
+            );
+            let total: u64 = total.parse().ok()?;
+            Some(GpuInfo {
+                name: name.to_string(),
+                vram_bytes: total * MIB,
+                backend: "cuda".to_string(),
+                vram_used_bytes: used.parse::<u64>().ok().map(|mib| mib * MIB),
+                driver_version: Some(driver.to_string()).filter(|d| !d.is_empty()),
+            })
+        })
+        .max_by_key(|g| g.vram_bytes)
+}
+
+/// Parses `rocm-smi --showproductname --showmeminfo vram --showdriverversion --json`; picks the
+/// card with the most VRAM. The driver version is reported under "system", not per card.
+#[must_use]
+pub fn parse_rocm_smi(stdout: &str) -> Option<GpuInfo> {
+    let root: serde_json::Value = serde_json::from_str(stdout).ok()?;
+    let bytes = |card: &serde_json::Value, key: &str| {
+        card.get(key).and_then(|v| {
+            v.as_str()
+                .and_then(|s| s.parse().ok())
+                .or_else(|| v.as_u64())
+        })
+    };
+    let driver = root
+        .pointer("/system/Driver version")
+        .and_then(|v| v.as_str())
+        .map(String::from);
+    root.as_object()?
+        .values()
+        .filter_map(|card| {
+            let vram = bytes(card, "VRAM Total Memory (B)")?;
+            let name = card
+                .get("Card series")
+                .or_else(|| card.get("Card Series"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("AMD GPU");
+            Some(GpuInfo {
+                name: name.to_string(),
+                vram_bytes: vram,
+                backend: "rocm".to_string(),
+                vram_used_bytes: bytes(card, "VRAM Total Used Memory (B)"),
+                driver_version: driver.clone(),
+            })
+        })
+        .max_by_key(|g| g.vram_bytes)
+}
+
+pub(crate) fn command_stdout(cmd: &str, args: &[&str]) -> Option<String> {
+    let out = Command::new(cmd).args(args).output().ok()?;
+    out.status
+        .success()
+        .then(|| String::from_utf8_lossy(&out.stdout).to_string())
+}
+
+/// GPU model from `system_profiler SPDisplaysDataType -json` (e.g. "Apple M2 Pro").
+#[must_use]
+pub fn parse_system_profiler_gpu(stdout: &str) -> Option<String> {
+    let root: serde_json::Value = serde_json::from_str(stdout).ok()?;
+    root.get("SPDisplaysDataType")?
+        .as_array()?
+        .iter()
+        .find_map(|d| d.get("sppci_model").and_then(|v| v.as_str()))
+        .map(String::from)
+}
+
+/// Detects the primary GPU via nvidia-smi, rocm-smi, or Apple Silicon (75% of unified memory).
+/// Returns None on CPU-only machines or when the vendor tools are missing.
+#[must_use]
+pub fn detect_gpu() -> Option<GpuInfo> {
+    if let Some(gpu) = command_stdout(
+        "nvidia-smi",
+        &[
+            "--query-gpu=name,memory.total,memory.used,driver_version",
+            "--format=csv,noheader,nounits",
+        ],
+    )
+    .and_then(|s| parse_nvidia_smi(&s))
+    {
+        return Some(gpu);
+    }
+    if let Some(gpu) = command_stdout(
+        "rocm-smi",
+        &[
+            "--showproductname",
+            "--showmeminfo",
+            "vram",
+            "--showdriverversion",
+            "--json",
+        ],
+    )
+    .and_then(|s| parse_rocm_smi(&s))
+    {
+        return Some(gpu);
+    }
+    if cfg!(target_os = "macos") && cfg!(target_arch = "aarch64") {
+        let name = command_stdout("system_profiler", &["SPDisplaysDataType", "-json"])
+            .and_then(|s| parse_system_profiler_gpu(&s))
+            .or_else(|| command_stdout("sysctl", &["-n", "machdep.cpu.brand_string"]))
+            .unwrap_or_else(|| "Apple Silicon".to_string());
+        return Some(GpuInfo {
+            name: name.trim().to_string(),
+            vram_bytes: get_system_info().total_memory_bytes / 4 * 3,
+            backend: "metal".to_string(),
+            vram_used_bytes: None,
+            driver_version: None,
+        });
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_classify_memory_pressure() {
+        let gb = 1024 * 1024 * 1024;
+        assert_eq!(
+            classify_memory_pressure(16 * gb, 8 * gb, 2 * gb, 0),
+            MemoryPressure::Green
+        );
+        assert_eq!(
+            classify_memory_pressure(16 * gb, 3 * gb, 0, 0),
+            MemoryPressure::Yellow
+        );
+        assert_eq!(
+            classify_memory_pressure(16 * gb, 8 * gb, 2 * gb, 3 * gb / 2),
+            MemoryPressure::Yellow
+        );
+        assert_eq!(
+            classify_memory_pressure(16 * gb, 3 * gb, 2 * gb, 3 * gb / 2),
+            MemoryPressure::Red
+        );
+        assert_eq!(
+            classify_memory_pressure(16 * gb, gb, 0, 0),
+            MemoryPressure::Red
+        );
+    }
+
     #[test]
     fn test_bytes_to_human() {
         assert_eq!(bytes_to_human(0), "0 B");
@@ -75,4 +647,100 @@ mod tests {
         assert!(!info.total_memory_human.is_empty());
         assert!(!info.available_memory_human.is_empty());
     }
+
+    #[test]
+    fn test_cpu_features_from_flags() {
+        let x86 = "fpu sse2 avx avx2 fma f16c avx512f avx512_vnni amx_tile amx_int8";
+        assert_eq!(
+            cpu_features_from_flags(x86),
+            vec!["amx", "avx", "avx2", "avx512", "avx512_vnni", "f16c", "fma"]
+        );
+        let arm = "fp asimd evtstrm aes asimddp sve i8mm";
+        assert_eq!(
+            cpu_features_from_flags(arm),
+            vec!["dotprod", "i8mm", "neon", "sve"]
+        );
+        let mac = "FPU AVX1.0 FMA F16C AVX2 hw.optional.arm.FEAT_DotProd hw.optional.neon";
+        assert_eq!(
+            cpu_features_from_flags(mac),
+            vec!["avx", "avx2", "dotprod", "f16c", "fma", "neon"]
+        );
+    }
+
+    #[test]
+    fn test_parse_gpu_tools() {
+        let nv = parse_nvidia_smi(
+            "NVIDIA GeForce RTX 3060, 12288, 512, 550.54.14\nNVIDIA GeForce RTX 4090, 24564, 1024, 550.54.14\n",
+        )
+        .unwrap();
+        assert_eq!(nv.name, "NVIDIA GeForce RTX 4090");
+        assert_eq!(nv.vram_bytes, 24564 * 1024 * 1024);
+        assert_eq!(nv.vram_used_bytes, Some(1024 * 1024 * 1024));
+        assert_eq!(nv.driver_version.as_deref(), Some("550.54.14"));
+        assert!(parse_nvidia_smi("").is_none());
+
+        let rocm = r#"{"card0":{"Card series":"Radeon RX 7900 XTX","VRAM Total Memory (B)":"25753026560","VRAM Total Used Memory (B)":"1048576"},"system":{"Driver version":"6.8.5"}}"#;
+        let amd = parse_rocm_smi(rocm).unwrap();
+        assert_eq!(amd.vram_bytes, 25753026560);
+        assert_eq!(amd.vram_used_bytes, Some(1048576));
+        assert_eq!(amd.driver_version.as_deref(), Some("6.8.5"));
+        assert_eq!(amd.backend, "rocm");
+
+        let profiler =
+            r#"{"SPDisplaysDataType":[{"sppci_model":"Apple M2 Pro","sppci_cores":"19"}]}"#;
+        assert_eq!(
+            parse_system_profiler_gpu(profiler).as_deref(),
+            Some("Apple M2 Pro")
+        );
+    }
+
+    #[test]
+    fn test_detect_virtualization() {
+        let bare = VirtualizationSignals {
+            kernel_release: "6.8.0-45-generic".to_string(),
+            init_cgroup: "0::/init.scope".to_string(),
+            dmi: "Dell Inc. XPS 15".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(detect_virtualization(&bare), None);
+        let wsl = VirtualizationSignals {
+            kernel_release: "5.15.153.1-microsoft-standard-WSL2".to_string(),
+            hypervisor: true,
+            ..Default::default()
+        };
+        assert_eq!(detect_virtualization(&wsl).as_deref(), Some("wsl"));
+        let docker = VirtualizationSignals {
+            dockerenv: true,
+            ..bare.clone()
+        };
+        assert_eq!(detect_virtualization(&docker).as_deref(), Some("docker"));
+        let k8s = VirtualizationSignals {
+            init_cgroup: "0::/kubepods/besteffort/pod123".to_string(),
+            ..bare.clone()
+        };
+        assert_eq!(detect_virtualization(&k8s).as_deref(), Some("container"));
+        let vm = VirtualizationSignals {
+            dmi: "QEMU Standard PC (Q35 + ICH9, 2009)".to_string(),
+            ..bare
+        };
+        assert_eq!(detect_virtualization(&vm).as_deref(), Some("vm"));
+    }
+
+    #[test]
+    fn test_low_space_warning() {
+        const GB: u64 = 1024 * 1024 * 1024;
+        assert!(low_space_warning("/models", 100 * GB, 5 * GB).is_none());
+        let low = low_space_warning("/models", 12 * GB, 5 * GB).unwrap();
+        assert!(low.message.starts_with("Low disk space"));
+        let full = low_space_warning("/models", 3 * GB, 5 * GB).unwrap();
+        assert!(full.message.starts_with("Not enough space"));
+        assert_eq!(full.needed_bytes, 5 * GB);
+    }
+
+    #[test]
+    fn test_volume_space_for_path_no_panic() {
+        if let Some((free, total)) = volume_space_for_path(Path::new("/")) {
+            assert!(free <= total);
+        }
+    }
 }
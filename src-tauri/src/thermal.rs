@@ -0,0 +1,239 @@
+//! CPU/GPU temperatures and throttling, from whatever the platform exposes without root:
+//! Linux hwmon (/sys/class/hwmon), nvidia-smi, and `pmset -g therm` on macOS (which reports
+//! throttling but no temperatures). Parsers are pure functions for unit tests.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use crate::system;
+
+/// hwmon driver names that report the CPU package or cores.
+const CPU_SENSORS: &[&str] = &["coretemp", "k10temp", "zenpower", "cpu_thermal", "acpitz"];
+/// hwmon driver names that report a GPU.
+const GPU_SENSORS: &[&str] = &["amdgpu", "nouveau", "radeon"];
+/// nvidia-smi clocks_throttle_reasons bits that slow the GPU down, with their names.
+const NVIDIA_THROTTLE_REASONS: &[(u64, &str)] = &[
+    (0x4, "gpu_power_cap"),
+    (0x8, "gpu_hw_slowdown"),
+    (0x20, "gpu_sw_thermal"),
+    (0x40, "gpu_hw_thermal"),
+    (0x80, "gpu_power_brake"),
+];
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ThermalState {
+    /// Hottest CPU sensor, °C.
+    pub cpu_temp_c: Option<f64>,
+    /// Hottest GPU sensor, °C.
+    pub gpu_temp_c: Option<f64>,
+    /// None when the platform doesn't say.
+    pub cpu_throttled: Option<bool>,
+    pub gpu_throttled: Option<bool>,
+    /// Why, e.g. "cpu_at_max_temp", "cpu_speed_limit_70", "gpu_sw_thermal".
+    pub reasons: Vec<String>,
+}
+
+/// One hwmon temperature input: driver name, reading and its max/critical limits, all °C.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HwmonReading {
+    pub name: String,
+    pub temp_c: f64,
+    pub max_c: Option<f64>,
+    pub crit_c: Option<f64>,
+}
+
+/// Takes the hottest CPU and GPU readings. A sensor at or above its max (or critical) limit
+/// counts as throttling, since the kernel and firmware clamp clocks there.
+#[must_use]
+pub fn thermal_from_hwmon(readings: &[HwmonReading]) -> ThermalState {
+    let mut state = ThermalState::default();
+    for (sensors, temp, throttled, label) in [
+        (
+            CPU_SENSORS,
+            &mut state.cpu_temp_c,
+            &mut state.cpu_throttled,
+            "cpu",
+        ),
+        (
+            GPU_SENSORS,
+            &mut state.gpu_temp_c,
+            &mut state.gpu_throttled,
+            "gpu",
+        ),
+    ] {
+        let matching: Vec<&HwmonReading> = readings
+            .iter()
+            .filter(|r| sensors.contains(&r.name.as_str()))
+            .collect();
+        if matching.is_empty() {
+            continue;
+        }
+        *temp = matching.iter().map(|r| r.temp_c).reduce(f64::max);
+        let hot = matching
+            .iter()
+            .any(|r| r.max_c.or(r.crit_c).is_some_and(|limit| r.temp_c >= limit));
+        *throttled = Some(hot);
+        if hot {
+            state.reasons.push(format!("{}_at_max_temp", label));
+        }
+    }
+    state
+}
+
+/// Parses `nvidia-smi --query-gpu=temperature.gpu,clocks_throttle_reasons.active
+/// --format=csv,noheader,nounits`, e.g. "71, 0x0000000000000020". Takes the hottest GPU and
+/// the union of throttle reasons.
+#[must_use]
+pub fn parse_nvidia_thermal(stdout: &str) -> Option<(f64, Vec<String>)> {
+    let mut hottest: Option<f64> = None;
+    let mut reasons: Vec<String> = Vec::new();
+    for line in stdout.lines() {
+        let Some((temp, bits)) = line.split_once(',') else {
+            continue;
+        };
+        let Ok(temp) = temp.trim().parse::<f64>() else {
+            continue;
+        };
+        hottest = Some(hottest.map_or(temp, |h| h.max(temp)));
+        let bits = u64::from_str_radix(bits.trim().trim_start_matches("0x"), 16).unwrap_or(0);
+        for (bit, name) in NVIDIA_THROTTLE_REASONS {
+            if bits & bit != 0 && !reasons.iter().any(|r| r == name) {
+                reasons.push(name.to_string());
+            }
+        }
+    }
+    hottest.map(|t| (t, reasons))
+}
+
+/// Parses `pmset -g therm`: a CPU_Speed_Limit under 100 or a recorded thermal warning level
+/// means the CPU is being held back.
+#[must_use]
+pub fn parse_pmset_therm(stdout: &str) -> (bool, Vec<String>) {
+    let mut reasons = Vec::new();
+    for line in stdout.lines().map(str::trim) {
+        if let Some((key, value)) = line.split_once('=') {
+            let limit = value.trim().parse::<u32>().unwrap_or(100);
+            if key.trim() == "CPU_Speed_Limit" && limit < 100 {
+                reasons.push(format!("cpu_speed_limit_{}", limit));
+            }
+        } else if line.to_lowercase().contains("thermal warning level")
+            && !line.to_lowercase().starts_with("note: no")
+        {
+            reasons.push("cpu_thermal_warning".to_string());
+        }
+    }
+    (!reasons.is_empty(), reasons)
+}
+
+fn read_celsius(path: &std::path::Path) -> Option<f64> {
+    let milli: f64 = fs::read_to_string(path).ok()?.trim().parse().ok()?;
+    Some(milli / 1000.0)
+}
+
+fn hwmon_readings() -> Vec<HwmonReading> {
+    let mut readings = Vec::new();
+    for dir in fs::read_dir("/sys/class/hwmon")
+        .into_iter()
+        .flatten()
+        .flatten()
+    {
+        let dir = dir.path();
+        let Ok(name) = fs::read_to_string(dir.join("name")) else {
+            continue;
+        };
+        for entry in fs::read_dir(&dir).into_iter().flatten().flatten() {
+            let file = entry.file_name().to_string_lossy().to_string();
+            let Some(prefix) = file
+                .strip_suffix("_input")
+                .filter(|p| p.starts_with("temp"))
+            else {
+                continue;
+            };
+            if let Some(temp_c) = read_celsius(&entry.path()) {
+                readings.push(HwmonReading {
+                    name: name.trim().to_string(),
+                    temp_c,
+                    max_c: read_celsius(&dir.join(format!("{}_max", prefix))),
+                    crit_c: read_celsius(&dir.join(format!("{}_crit", prefix))),
+                });
+            }
+        }
+    }
+    readings
+}
+
+/// Reads temperatures and throttling now. Fields stay None where the platform has no source.
+#[must_use]
+pub fn get_thermal_state() -> ThermalState {
+    let mut state = if cfg!(target_os = "linux") {
+        thermal_from_hwmon(&hwmon_readings())
+    } else {
+        ThermalState::default()
+    };
+    if cfg!(target_os = "macos") {
+        if let Some(out) = system::command_stdout("pmset", &["-g", "therm"]) {
+            let (throttled, reasons) = parse_pmset_therm(&out);
+            state.cpu_throttled = Some(throttled);
+            state.reasons.extend(reasons);
+        }
+    }
+    if let Some((temp, reasons)) = system::command_stdout(
+        "nvidia-smi",
+        &[
+            "--query-gpu=temperature.gpu,clocks_throttle_reasons.active",
+            "--format=csv,noheader,nounits",
+        ],
+    )
+    .and_then(|s| parse_nvidia_thermal(&s))
+    {
+        state.gpu_temp_c = Some(temp);
+        state.gpu_throttled = Some(!reasons.is_empty());
+        state.reasons.extend(reasons);
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thermal_from_hwmon() {
+        let reading = |name: &str, temp_c: f64, max_c: Option<f64>| HwmonReading {
+            name: name.to_string(),
+            temp_c,
+            max_c,
+            crit_c: Some(105.0),
+        };
+        let state = thermal_from_hwmon(&[
+            reading("coretemp", 64.0, Some(100.0)),
+            reading("coretemp", 100.0, Some(100.0)),
+            reading("amdgpu", 58.0, None),
+            reading("nvme", 45.0, None),
+        ]);
+        assert_eq!(state.cpu_temp_c, Some(100.0));
+        assert_eq!(state.cpu_throttled, Some(true));
+        assert_eq!(state.gpu_temp_c, Some(58.0));
+        assert_eq!(state.gpu_throttled, Some(false));
+        assert_eq!(state.reasons, vec!["cpu_at_max_temp"]);
+        assert_eq!(thermal_from_hwmon(&[]), ThermalState::default());
+    }
+
+    #[test]
+    fn test_parse_nvidia_and_pmset() {
+        let (temp, reasons) =
+            parse_nvidia_thermal("71, 0x0000000000000020\n64, 0x0000000000000001\n").unwrap();
+        assert_eq!(temp, 71.0);
+        assert_eq!(reasons, vec!["gpu_sw_thermal"]);
+        assert!(parse_nvidia_thermal("[Not Supported], 0x0").is_none());
+
+        let cool = "Note: No thermal warning level has been recorded\n\
+                    Note: No performance warning level has been recorded\n";
+        assert_eq!(parse_pmset_therm(cool), (false, vec![]));
+        let hot = "CPU_Scheduler_Limit \t= 100\nCPU_Available_CPUs \t= 8\nCPU_Speed_Limit \t= 70\n";
+        assert_eq!(
+            parse_pmset_therm(hot),
+            (true, vec!["cpu_speed_limit_70".to_string()])
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! Tray icon showing gateway health (a green or red badge on the app icon), with quick actions:
+//! start, stop or restart the gateway, open the config folder, and switch the primary model.
+
+use std::path::Path;
+use std::process::Command;
+use tauri::image::Image;
+use tauri::menu::{
+    CheckMenuItem, IsMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem, Submenu,
+};
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, Manager, Wry};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::app_state::AppState;
+use crate::gateway_monitor::{self, GatewayMonitor};
+use crate::notifications::Notifier;
+use crate::{gateway, openclaw_config};
+
+const TRAY_ID: &str = "main";
+const MODEL_ITEM_PREFIX: &str = "model:";
+const BADGE_RUNNING: [u8; 4] = [34, 197, 94, 255];
+const BADGE_STOPPED: [u8; 4] = [239, 68, 68, 255];
+
+fn status_label(running: Option<bool>) -> &'static str {
+    match running {
+        Some(true) => "Gateway: running",
+        Some(false) => "Gateway: stopped",
+        None => "Gateway: unknown",
+    }
+}
+
+/// Paints a filled circle of `color` into the bottom-right corner of an RGBA image.
+fn paint_badge(rgba: &mut [u8], width: u32, height: u32, color: [u8; 4]) {
+    let r = f64::from(width.min(height)) / 4.0;
+    let (cx, cy) = (f64::from(width) - r, f64::from(height) - r);
+    for y in 0..height {
+        for x in 0..width {
+            let (dx, dy) = (f64::from(x) + 0.5 - cx, f64::from(y) + 0.5 - cy);
+            if dx * dx + dy * dy <= r * r {
+                let i = (y as usize * width as usize + x as usize) * 4;
+                if let Some(px) = rgba.get_mut(i..i + 4) {
+                    px.copy_from_slice(&color);
+                }
+            }
+        }
+    }
+}
+
+/// The app icon, badged with the gateway status when it is known.
+fn status_icon(app: &AppHandle, running: Option<bool>) -> Option<Image<'static>> {
+    let icon = app.default_window_icon()?;
+    let mut rgba = icon.rgba().to_vec();
+    if let Some(running) = running {
+        let color = if running {
+            BADGE_RUNNING
+        } else {
+            BADGE_STOPPED
+        };
+        paint_badge(&mut rgba, icon.width(), icon.height(), color);
+    }
+    Some(Image::new_owned(rgba, icon.width(), icon.height()))
+}
+
+fn build_menu(app: &AppHandle, running: Option<bool>) -> tauri::Result<Menu<Wry>> {
+    let view = app.state::<AppState>().config_view();
+    let item = |id: &str, text: &str, enabled: bool| {
+        MenuItem::with_id(app, id, text, enabled, None::<&str>)
+    };
+    let models = view
+        .models
+        .iter()
+        .map(|m| {
+            let checked = view.primary_model.as_deref() == Some(m.as_str());
+            let id = format!("{}{}", MODEL_ITEM_PREFIX, m);
+            CheckMenuItem::with_id(app, id, m, true, checked, None::<&str>)
+        })
+        .collect::<tauri::Result<Vec<_>>>()?;
+    let model_items: Vec<&dyn IsMenuItem<Wry>> =
+        models.iter().map(|m| m as &dyn IsMenuItem<Wry>).collect();
+    Menu::with_items(
+        app,
+        &[
+            &item("status", status_label(running), false)?,
+            &PredefinedMenuItem::separator(app)?,
+            &item("start", "Start gateway", running != Some(true))?,
+            &item("stop", "Stop gateway", running != Some(false))?,
+            &item("restart", "Restart gateway", running != Some(false))?,
+            &PredefinedMenuItem::separator(app)?,
+            &Submenu::with_items(app, "Primary model", !models.is_empty(), &model_items)?,
+            &item("open_config_folder", "Open config folder", true)?,
+            &item("show", "Show window", true)?,
+            &PredefinedMenuItem::separator(app)?,
+            &PredefinedMenuItem::quit(app, None)?,
+        ],
+    )
+}
+
+fn open_path(path: &Path) -> Result<(), String> {
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(windows) {
+        "explorer"
+    } else {
+        "xdg-open"
+    };
+    Command::new(opener)
+        .arg(path)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))
+}
+
+fn set_primary_model(model: &str) -> Result<(), String> {
+    openclaw_config::update_openclaw_config(openclaw_config::OpenClawConfigUpdates {
+        primary_model: Some(model.to_string()),
+        ..Default::default()
+    })
+}
+
+fn show_window(app: &AppHandle) -> Result<(), String> {
+    let window = app
+        .get_webview_window("main")
+        .ok_or("Main window not found")?;
+    window.show().map_err(|e| e.to_string())?;
+    window.set_focus().map_err(|e| e.to_string())
+}
+
+fn on_menu_event(app: &AppHandle, event: MenuEvent) {
+    let id = event.id().as_ref();
+    let result = match id {
+        "start" => gateway::start_gateway().map(|_| ()),
+        "stop" => {
+            app.state::<Notifier>().expect_gateway_stop();
+            gateway::stop_gateway().map(|_| ())
+        }
+        "restart" => {
+            app.state::<Notifier>().expect_gateway_stop();
+            gateway::restart_gateway().map(|_| ())
+        }
+        "open_config_folder" => match openclaw_config::openclaw_config_path().parent() {
+            Some(dir) => open_path(dir),
+            None => Ok(()),
+        },
+        "show" => show_window(app),
+        _ => match id.strip_prefix(MODEL_ITEM_PREFIX) {
+            Some(model) => set_primary_model(model),
+            None => Ok(()),
+        },
+    };
+    // With the window closed, a desktop notification is the only place to report a failure.
+    if let Err(e) = result {
+        let _ = app
+            .notification()
+            .builder()
+            .title("OpenClaw Config")
+            .body(e)
+            .show();
+    }
+    if matches!(id, "start" | "stop" | "restart") {
+        gateway_monitor::check_soon(app);
+    }
+    refresh(app);
+}
+
+pub fn create(app: &AppHandle) -> tauri::Result<()> {
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip(status_label(None))
+        .menu(&build_menu(app, None)?)
+        .on_menu_event(on_menu_event);
+    if let Some(icon) = status_icon(app, None) {
+        builder = builder.icon(icon);
+    }
+    builder.build(app)?;
+    Ok(())
+}
+
+/// Rebuilds the tray icon, tooltip and menu from the last gateway status and openclaw.json.
+pub fn refresh(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let running = app.state::<GatewayMonitor>().last_status();
+    let _ = tray.set_icon(status_icon(app, running));
+    let _ = tray.set_tooltip(Some(status_label(running)));
+    if let Ok(menu) = build_menu(app, running) {
+        let _ = tray.set_menu(Some(menu));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paint_badge() {
+        let (w, h) = (8u32, 8u32);
+        let mut rgba = vec![0u8; (w * h * 4) as usize];
+        paint_badge(&mut rgba, w, h, BADGE_RUNNING);
+        let px = |x: u32, y: u32| {
+            let i = ((y * w + x) * 4) as usize;
+            [rgba[i], rgba[i + 1], rgba[i + 2], rgba[i + 3]]
+        };
+        assert_eq!(px(6, 6), BADGE_RUNNING);
+        assert_eq!(px(0, 0), [0, 0, 0, 0]);
+        assert_eq!(px(6, 0), [0, 0, 0, 0]);
+    }
+}
@@ -0,0 +1,207 @@
+//! Anonymous usage counters, only with `telemetry_opt_in` in app settings: how many times each
+//! command was invoked, nothing else (no arguments, paths, model names or errors). Counts stay in
+//! ~/.openclaw/host-config/usage-stats.json; nothing is sent anywhere. `export_usage_stats`
+//! writes a reviewable JSON document the user can share by hand. Counts are kept in memory and
+//! written at most every minute. Merging and the export document are pure functions for unit
+//! tests.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::openclaw_config;
+use crate::operations;
+
+const FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct UsageStats {
+    /// Unix seconds of the first count.
+    pub since: u64,
+    pub updated_at: u64,
+    /// Invocations by command name.
+    pub counts: BTreeMap<String, u64>,
+}
+
+impl UsageStats {
+    /// Adds `pending` to the counts.
+    pub fn merge(&mut self, pending: &BTreeMap<String, u64>, now: u64) {
+        if pending.is_empty() {
+            return;
+        }
+        if self.since == 0 {
+            self.since = now;
+        }
+        for (command, n) in pending {
+            *self.counts.entry(command.clone()).or_insert(0) += n;
+        }
+        self.updated_at = now;
+    }
+}
+
+/// The document `export_usage_stats` writes: the counts plus the app version, most used first.
+#[must_use]
+pub fn export_document(stats: &UsageStats, app_version: &str, exported_at: u64) -> Value {
+    let mut counts: Vec<(&String, &u64)> = stats.counts.iter().collect();
+    counts.sort_by_key(|(_, n)| std::cmp::Reverse(**n));
+    serde_json::json!({
+        "source": "openclaw-host-config",
+        "app_version": app_version,
+        "exported_at": exported_at,
+        "since": stats.since,
+        "total": stats.counts.values().sum::<u64>(),
+        "commands": counts
+            .into_iter()
+            .map(|(command, n)| serde_json::json!({ "command": command, "count": n }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn stats_path() -> PathBuf {
+    openclaw_config::host_config_dir().join("usage-stats.json")
+}
+
+fn read_stats() -> UsageStats {
+    fs::read_to_string(stats_path())
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+struct Pending {
+    counts: BTreeMap<String, u64>,
+    last_flush: Instant,
+}
+
+/// Counts not yet written, shared by the invoke handler and the stats commands.
+pub struct UsageCounter {
+    enabled: AtomicBool,
+    pending: Mutex<Pending>,
+}
+
+impl UsageCounter {
+    #[must_use]
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled: AtomicBool::new(enabled),
+            pending: Mutex::new(Pending {
+                counts: BTreeMap::new(),
+                last_flush: Instant::now(),
+            }),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Pending> {
+        self.pending.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Follows the opt-in setting; turning it off drops counts not yet written.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::SeqCst);
+        if !enabled {
+            self.lock().counts.clear();
+        }
+    }
+
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+
+    /// Counts one invocation of `command` when opted in.
+    pub fn record(&self, command: &str) {
+        if !self.is_enabled() {
+            return;
+        }
+        let due = {
+            let mut pending = self.lock();
+            *pending.counts.entry(command.to_string()).or_insert(0) += 1;
+            pending.last_flush.elapsed() >= FLUSH_INTERVAL
+        };
+        if due {
+            // Not fatal: the counts stay pending for the next try.
+            let _ = self.flush();
+        }
+    }
+
+    /// Writes pending counts to usage-stats.json and returns the stored stats.
+    pub fn flush(&self) -> Result<UsageStats, String> {
+        let mut pending = self.lock();
+        let mut stats = read_stats();
+        if pending.counts.is_empty() {
+            return Ok(stats);
+        }
+        stats.merge(&pending.counts, operations::now_secs());
+        let path = stats_path();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        fs::write(
+            &path,
+            serde_json::to_string_pretty(&stats).map_err(|e| e.to_string())?,
+        )
+        .map_err(|e| e.to_string())?;
+        pending.counts.clear();
+        pending.last_flush = Instant::now();
+        Ok(stats)
+    }
+}
+
+/// The counts so far, pending ones included.
+pub fn get_usage_stats(counter: &UsageCounter) -> Result<UsageStats, String> {
+    counter.flush()
+}
+
+/// The export document as JSON, also written to `path` when given. This is the only way the
+/// counts leave the machine, and only by the user's hand.
+pub fn export_usage_stats(counter: &UsageCounter, path: Option<&str>) -> Result<String, String> {
+    let stats = counter.flush()?;
+    let document = export_document(&stats, env!("CARGO_PKG_VERSION"), operations::now_secs());
+    let content = serde_json::to_string_pretty(&document).map_err(|e| e.to_string())?;
+    if let Some(path) = path {
+        fs::write(path, &content).map_err(|e| e.to_string())?;
+    }
+    Ok(content)
+}
+
+/// Deletes all counts.
+pub fn reset_usage_stats(counter: &UsageCounter) -> Result<(), String> {
+    counter.lock().counts.clear();
+    match fs::remove_file(stats_path()) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_and_export() {
+        let mut stats = UsageStats::default();
+        let pending = BTreeMap::from([
+            ("get_model_catalog".to_string(), 3),
+            ("start_gateway".to_string(), 1),
+        ]);
+        stats.merge(&pending, 100);
+        stats.merge(&BTreeMap::from([("start_gateway".to_string(), 4)]), 200);
+        assert_eq!((stats.since, stats.updated_at), (100, 200));
+        assert_eq!(stats.counts["start_gateway"], 5);
+
+        let doc = export_document(&stats, "0.1.0", 300);
+        assert_eq!(doc["total"], 8);
+        assert_eq!(doc["commands"][0]["command"], "start_gateway");
+        assert_eq!(doc["commands"][1]["count"], 3);
+
+        let counter = UsageCounter::new(false);
+        counter.record("start_gateway");
+        assert!(counter.lock().counts.is_empty());
+    }
+}
@@ -0,0 +1,268 @@
+//! Filesystem watching with debouncing (via `notify`), used to keep model lists fresh
+//! when models are added or removed outside the app (e.g. `ollama pull` in a terminal).
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::agents;
+use crate::catalog::{RUNTIME_LM_STUDIO, RUNTIME_OLLAMA};
+use crate::disk_usage;
+
+/// Quiet period before a batch of filesystem events is reported.
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(1500);
+
+/// Keeps a watcher alive; dropping it stops watching and ends the debounce thread.
+pub struct DirWatcher {
+    _watcher: RecommendedWatcher,
+}
+
+/// Keeps the ~/.openclaw/agents watcher alive (a distinct type so Tauri can manage it alongside
+/// the model directory watcher).
+pub struct AgentsWatcher {
+    _watcher: DirWatcher,
+}
+
+/// Payload for the `models-available-changed` event.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ModelsChangedEvent {
+    /// Runtimes whose model directories changed ("ollama", "lmstudio").
+    pub runtimes: Vec<String>,
+    pub paths: Vec<String>,
+}
+
+/// Watches `paths` recursively and calls `on_change` with the changed paths once events go quiet
+/// for `debounce`. Paths that don't exist are skipped; errors only if none could be watched.
+pub fn watch_paths(
+    paths: &[PathBuf],
+    debounce: Duration,
+    on_change: impl Fn(Vec<PathBuf>) + Send + 'static,
+) -> Result<DirWatcher, String> {
+    let (tx, rx) = mpsc::channel::<notify::Result<notify::Event>>();
+    let mut watcher = notify::recommended_watcher(tx).map_err(|e| e.to_string())?;
+    let mut watched = 0;
+    for p in paths.iter().filter(|p| p.exists()) {
+        if watcher.watch(p, RecursiveMode::Recursive).is_ok() {
+            watched += 1;
+        }
+    }
+    if watched == 0 {
+        return Err("No watchable directories found".to_string());
+    }
+
+    thread::spawn(move || {
+        // Block for the first event of a batch, then drain until quiet.
+        while let Ok(first) = rx.recv() {
+            let mut changed = Vec::new();
+            collect_paths(first, &mut changed);
+            loop {
+                match rx.recv_timeout(debounce) {
+                    Ok(ev) => collect_paths(ev, &mut changed),
+                    Err(mpsc::RecvTimeoutError::Timeout) => break,
+                    Err(mpsc::RecvTimeoutError::Disconnected) => return,
+                }
+            }
+            changed.sort();
+            changed.dedup();
+            if !changed.is_empty() {
+                on_change(changed);
+            }
+        }
+    });
+    Ok(DirWatcher { _watcher: watcher })
+}
+
+fn collect_paths(ev: notify::Result<notify::Event>, out: &mut Vec<PathBuf>) {
+    if let Ok(ev) = ev {
+        if !matches!(ev.kind, EventKind::Access(_)) {
+            out.extend(ev.paths);
+        }
+    }
+}
+
+/// Directories that define each runtime's installed models. For Ollama only manifests matter:
+/// blobs churn during pulls, while a manifest appears/disappears exactly when a tag is added/removed.
+#[must_use]
+pub fn model_directories() -> Vec<(String, PathBuf)> {
+    let mut dirs = Vec::new();
+    if let Some(d) = disk_usage::ollama_models_dir() {
+        dirs.push((RUNTIME_OLLAMA.to_string(), d.join("manifests")));
+    }
+    if let Some(d) = disk_usage::lm_studio_models_dir() {
+        dirs.push((RUNTIME_LM_STUDIO.to_string(), d));
+    }
+    dirs
+}
+
+/// Maps changed paths back to the runtimes whose directories contain them.
+#[must_use]
+pub fn models_changed_event(dirs: &[(String, PathBuf)], paths: &[PathBuf]) -> ModelsChangedEvent {
+    let mut runtimes: Vec<String> = dirs
+        .iter()
+        .filter(|(_, dir)| paths.iter().any(|p| p.starts_with(dir)))
+        .map(|(rt, _)| rt.clone())
+        .collect();
+    runtimes.dedup();
+    ModelsChangedEvent {
+        runtimes,
+        paths: paths
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect(),
+    }
+}
+
+/// Watches the Ollama and LM Studio model directories and reports changes per runtime.
+pub fn watch_model_directories(
+    on_change: impl Fn(ModelsChangedEvent) + Send + 'static,
+) -> Result<DirWatcher, String> {
+    let dirs = model_directories();
+    let paths: Vec<PathBuf> = dirs.iter().map(|(_, p)| p.clone()).collect();
+    watch_paths(&paths, DEFAULT_DEBOUNCE, move |changed| {
+        on_change(models_changed_event(&dirs, &changed));
+    })
+}
+
+/// Agents that appeared, disappeared, or had their models.json touched between two listings of
+/// the agents directory. Names are emitted as `agent-added`, `agent-removed` and
+/// `agent-models-changed`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct AgentsChangedEvent {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub models_changed: Vec<String>,
+}
+
+/// Diffs agent listings and maps changed paths to agents whose models.json changed. Session and
+/// memory writes under the agents directory are ignored.
+#[must_use]
+pub fn agents_changed_event(
+    agents_dir: &Path,
+    before: &BTreeSet<String>,
+    after: &BTreeSet<String>,
+    paths: &[PathBuf],
+) -> AgentsChangedEvent {
+    AgentsChangedEvent {
+        added: after.difference(before).cloned().collect(),
+        removed: before.difference(after).cloned().collect(),
+        models_changed: before
+            .intersection(after)
+            .filter(|name| paths.contains(&agents::models_path_in(agents_dir, name)))
+            .cloned()
+            .collect(),
+    }
+}
+
+/// Watches ~/.openclaw/agents (recursively, to see models.json edits) and reports agent
+/// additions, removals and models.json changes. Batches with none of those are dropped.
+pub fn watch_agents(
+    on_change: impl Fn(AgentsChangedEvent) + Send + 'static,
+) -> Result<AgentsWatcher, String> {
+    let dir = agents::agents_dir();
+    let known = Mutex::new(
+        agents::list_agent_names()
+            .into_iter()
+            .collect::<BTreeSet<_>>(),
+    );
+    let watcher = watch_paths(
+        std::slice::from_ref(&dir.clone()),
+        DEFAULT_DEBOUNCE,
+        move |changed| {
+            let mut known = known.lock().unwrap_or_else(|e| e.into_inner());
+            let now: BTreeSet<String> = agents::list_agent_names().into_iter().collect();
+            let ev = agents_changed_event(&dir, &known, &now, &changed);
+            *known = now;
+            if ev != AgentsChangedEvent::default() {
+                on_change(ev);
+            }
+        },
+    )?;
+    Ok(AgentsWatcher { _watcher: watcher })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_models_changed_event() {
+        let dirs = vec![
+            (
+                RUNTIME_OLLAMA.to_string(),
+                PathBuf::from("/h/.ollama/models/manifests"),
+            ),
+            (
+                RUNTIME_LM_STUDIO.to_string(),
+                PathBuf::from("/h/.lmstudio/models"),
+            ),
+        ];
+        let ev = models_changed_event(
+            &dirs,
+            &[PathBuf::from("/h/.lmstudio/models/pub/model/file.gguf")],
+        );
+        assert_eq!(ev.runtimes, vec![RUNTIME_LM_STUDIO.to_string()]);
+        assert_eq!(ev.paths.len(), 1);
+    }
+
+    #[test]
+    fn test_agents_changed_event() {
+        let dir = PathBuf::from("/h/.openclaw/agents");
+        let before: BTreeSet<String> = ["main", "dev"].iter().map(|s| s.to_string()).collect();
+        let after: BTreeSet<String> = ["main", "research"].iter().map(|s| s.to_string()).collect();
+        let ev = agents_changed_event(
+            &dir,
+            &before,
+            &after,
+            &[
+                dir.join("main/agent/models.json"),
+                dir.join("main/sessions/abc.jsonl"),
+            ],
+        );
+        assert_eq!(ev.added, vec!["research"]);
+        assert_eq!(ev.removed, vec!["dev"]);
+        assert_eq!(ev.models_changed, vec!["main"]);
+        let quiet = agents_changed_event(&dir, &after, &after, &[dir.join("main/sessions/x")]);
+        assert_eq!(quiet, AgentsChangedEvent::default());
+    }
+
+    #[test]
+    fn test_watch_paths_reports_changes() {
+        let dir = std::env::temp_dir().join(format!("openclaw-watch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_cb = Arc::clone(&seen);
+        let _w = watch_paths(
+            std::slice::from_ref(&dir),
+            Duration::from_millis(100),
+            move |paths| {
+                seen_cb.lock().unwrap().extend(paths);
+            },
+        )
+        .unwrap();
+        fs::write(dir.join("model.gguf"), b"x").unwrap();
+        for _ in 0..50 {
+            if !seen.lock().unwrap().is_empty() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+        let _ = fs::remove_dir_all(&dir);
+        assert!(seen.lock().unwrap()[0].starts_with(&dir));
+    }
+
+    #[test]
+    fn test_watch_paths_missing_dirs_errors() {
+        assert!(watch_paths(
+            &[PathBuf::from("/definitely/not/here")],
+            DEFAULT_DEBOUNCE,
+            |_| {}
+        )
+        .is_err());
+    }
+}
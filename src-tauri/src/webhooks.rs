@@ -0,0 +1,210 @@
+//! Optional webhooks: JSON POSTs to URLs registered in app settings when the gateway goes up or
+//! down, an agent's providers drift from openclaw.json, or a model pull finishes, for homelab
+//! dashboards (Home Assistant, Uptime Kuma, ...). Deliveries run on a background thread and
+//! failures are dropped; `send_test_webhook` reports them instead. `SyncDrift` is sent once per
+//! agent until it is back in sync, as `{"agents": [...]}`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::{operations, settings};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    GatewayUp,
+    GatewayDown,
+    SyncDrift,
+    ModelPullFinished,
+    /// Sent only by `send_test_webhook`.
+    Test,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Webhook {
+    pub url: String,
+    pub enabled: bool,
+    /// Events to send; empty means all.
+    pub events: Vec<WebhookEvent>,
+}
+
+impl Default for Webhook {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            enabled: true,
+            events: vec![],
+        }
+    }
+}
+
+impl Webhook {
+    #[must_use]
+    pub fn wants(&self, event: WebhookEvent) -> bool {
+        self.enabled && (self.events.is_empty() || self.events.contains(&event))
+    }
+}
+
+/// Only http(s) URLs are accepted.
+pub fn validate_webhook_url(url: &str) -> Result<(), String> {
+    let rest = url
+        .strip_prefix("http://")
+        .or_else(|| url.strip_prefix("https://"))
+        .ok_or_else(|| format!("Webhook URL must start with http:// or https://: {}", url))?;
+    if rest.is_empty() || rest.starts_with('/') {
+        return Err(format!("Webhook URL has no host: {}", url));
+    }
+    Ok(())
+}
+
+/// The JSON body: event name, Unix time, and event-specific data.
+#[must_use]
+pub fn payload(event: WebhookEvent, data: Value, timestamp: u64) -> Value {
+    serde_json::json!({
+        "source": "openclaw-host-config",
+        "event": event,
+        "timestamp": timestamp,
+        "data": data,
+    })
+}
+
+fn post(url: &str, body: &Value) -> Result<u16, String> {
+    let agent = ureq::AgentBuilder::new().timeout(TIMEOUT).build();
+    match agent.post(url).send_json(body.clone()) {
+        Ok(resp) => Ok(resp.status()),
+        Err(ureq::Error::Status(status, _)) => Err(format!("HTTP {}", status)),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Posts `event` to every webhook that wants it, in the background.
+pub fn emit(event: WebhookEvent, data: Value) {
+    let urls: Vec<String> = settings::get_app_settings()
+        .webhooks
+        .into_iter()
+        .filter(|w| w.wants(event))
+        .map(|w| w.url)
+        .collect();
+    if urls.is_empty() {
+        return;
+    }
+    let body = payload(event, data, operations::now_secs());
+    std::thread::spawn(move || {
+        for url in urls {
+            let _ = post(&url, &body);
+        }
+    });
+}
+
+/// Agents whose drift was already sent; shared by the scheduled sync check and the agents watcher.
+#[derive(Default)]
+pub struct SyncDriftTracker {
+    reported: Mutex<BTreeSet<String>>,
+}
+
+impl SyncDriftTracker {
+    /// After a check of every agent: the drifted ones not reported yet. Agents no longer drifted
+    /// are forgotten, so they are reported again if they drift later.
+    pub fn all_checked(&self, drifted: &[String]) -> Vec<String> {
+        let mut reported = self.reported.lock().unwrap_or_else(|e| e.into_inner());
+        let fresh = drifted
+            .iter()
+            .filter(|a| !reported.contains(*a))
+            .cloned()
+            .collect();
+        *reported = drifted.iter().cloned().collect();
+        fresh
+    }
+
+    /// After a check of one agent: whether its drift is new.
+    pub fn agent_checked(&self, agent: &str, in_sync: bool) -> bool {
+        let mut reported = self.reported.lock().unwrap_or_else(|e| e.into_inner());
+        if in_sync {
+            reported.remove(agent);
+            false
+        } else {
+            reported.insert(agent.to_string())
+        }
+    }
+}
+
+/// Posts `SyncDrift` for `agents`; nothing when the list is empty.
+pub fn emit_sync_drift(agents: &[String]) {
+    if !agents.is_empty() {
+        emit(
+            WebhookEvent::SyncDrift,
+            serde_json::json!({ "agents": agents }),
+        );
+    }
+}
+
+/// Sends a test event to `url` and returns the HTTP status.
+pub fn send_test_webhook(url: &str) -> Result<u16, String> {
+    validate_webhook_url(url)?;
+    post(
+        url,
+        &payload(
+            WebhookEvent::Test,
+            serde_json::json!({}),
+            operations::now_secs(),
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_webhook_filtering_and_payload() {
+        let all = Webhook {
+            url: "http://ha.local:8123/api/webhook/openclaw".to_string(),
+            ..Default::default()
+        };
+        let gateway_only = Webhook {
+            events: vec![WebhookEvent::GatewayDown],
+            ..all.clone()
+        };
+        let off = Webhook {
+            enabled: false,
+            ..all.clone()
+        };
+        assert!(all.wants(WebhookEvent::SyncDrift));
+        assert!(gateway_only.wants(WebhookEvent::GatewayDown));
+        assert!(!gateway_only.wants(WebhookEvent::GatewayUp));
+        assert!(!off.wants(WebhookEvent::GatewayDown));
+
+        let body = payload(
+            WebhookEvent::ModelPullFinished,
+            serde_json::json!({ "model": "qwen3:8b" }),
+            1_700_000_000,
+        );
+        assert_eq!(body["event"], "model_pull_finished");
+        assert_eq!(body["data"]["model"], "qwen3:8b");
+        assert_eq!(body["timestamp"], 1_700_000_000);
+
+        assert!(validate_webhook_url("https://kuma.lan/api/push/abc").is_ok());
+        assert!(validate_webhook_url("ftp://x").is_err());
+        assert!(validate_webhook_url("http://").is_err());
+    }
+
+    #[test]
+    fn test_sync_drift_tracker() {
+        let tracker = SyncDriftTracker::default();
+        let main = vec!["main".to_string()];
+        assert_eq!(tracker.all_checked(&main), main);
+        assert!(tracker.all_checked(&main).is_empty());
+        assert!(!tracker.agent_checked("main", false));
+        assert!(tracker.agent_checked("coder", false));
+        assert!(!tracker.agent_checked("main", true));
+        assert!(tracker.agent_checked("main", false));
+        assert!(tracker.all_checked(&[]).is_empty());
+        assert_eq!(tracker.all_checked(&main), main);
+    }
+}
@@ -81,7 +81,13 @@ export default function Agents() {
     setUpdating(true)
     setError(null)
     try {
-      await invoke('update_agent_providers_from_openclaw', { agentName: selectedAgent })
+      try {
+        await invoke('update_agent_providers_from_openclaw', { agentName: selectedAgent })
+      } catch (e) {
+        const message = String(e)
+        if (!message.startsWith('protected_agent') || !window.confirm(message.replace(/^protected_agent: /, '') + '?')) throw e
+        await invoke('update_agent_providers_from_openclaw', { agentName: selectedAgent, force: true })
+      }
       await loadAgentDetail(selectedAgent)
     } catch (e) {
       setError(e instanceof Error ? e.message : String(e))
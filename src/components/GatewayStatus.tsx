@@ -1,4 +1,5 @@
 import { invoke } from '@tauri-apps/api/core'
+import { listen } from '@tauri-apps/api/event'
 import { useState, useEffect } from 'react'
 
 interface GatewayStatusProps { }
@@ -19,8 +20,27 @@ export default function GatewayStatus({ }: GatewayStatusProps) {
 
   useEffect(() => {
     checkStatus()
-    const interval = setInterval(checkStatus, 5000)
-    return () => clearInterval(interval)
+    let interval: ReturnType<typeof setInterval> | undefined
+    let cancelled = false
+    invoke<{ gateway_poll_secs: number }>('get_app_settings')
+      .then((s) => s.gateway_poll_secs)
+      .catch(() => 5)
+      .then((secs) => {
+        if (!cancelled) interval = setInterval(checkStatus, secs * 1000)
+      })
+    return () => {
+      cancelled = true
+      clearInterval(interval)
+    }
+  }, [])
+
+  useEffect(() => {
+    const unlisten = listen<boolean>('gateway-status', (event) => {
+      setStatus(event.payload ? 'running' : 'stopped')
+    })
+    return () => {
+      unlisten.then((f) => f())
+    }
   }, [])
 
   const handleStart = async () => {
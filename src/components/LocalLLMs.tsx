@@ -19,6 +19,23 @@ interface SystemInfo {
   available_memory_bytes: number
   total_memory_human: string
   available_memory_human: string
+  swap_total_bytes?: number
+  swap_used_bytes?: number
+  memory_pressure?: 'green' | 'yellow' | 'red'
+  cpu?: {
+    brand: string
+    physical_cores?: number | null
+    logical_cores: number
+    frequency_mhz?: number | null
+    features: string[]
+  }
+  gpu?: {
+    name: string
+    vram_bytes: number
+    backend: string
+    vram_used_bytes?: number
+    driver_version?: string
+  } | null
 }
 
 interface LlmfitRecommendation {
@@ -39,6 +56,20 @@ interface LlmfitSystemJson {
   backend?: string
 }
 
+interface ModelChoice {
+  name: string
+  pinned: boolean
+}
+
+interface DashboardState {
+  detection: LocalLLMDetection
+  system_info: SystemInfo
+  ollama_models: ModelChoice[]
+  lm_studio_models: ModelChoice[]
+  llmfit_system: LlmfitSystemJson | null
+  llmfit_recommendations: LlmfitRecommendation[]
+}
+
 function StatusBadge({ status }: { status: LLMStatus }) {
   if (!status.installed) {
     return <span className="badge badge-muted">Not installed</span>
@@ -59,7 +90,7 @@ function LLMRow({
   name: string
   status: LLMStatus
   defaultPort?: number
-  models?: string[]
+  models?: ModelChoice[]
 }) {
   return (
     <div className="status-item local-llm-row">
@@ -79,7 +110,7 @@ function LLMRow({
       {models != null && models.length > 0 && (
         <div style={{ marginTop: '0.5rem', fontSize: '0.85rem' }}>
           <strong>Models ({models.length}):</strong>{' '}
-          <span style={{ color: '#64748b' }}>{models.slice(0, 8).join(', ')}{models.length > 8 ? '…' : ''}</span>
+          <span style={{ color: '#64748b' }}>{models.slice(0, 8).map((m) => (m.pinned ? `★ ${m.name}` : m.name)).join(', ')}{models.length > 8 ? '…' : ''}</span>
         </div>
       )}
     </div>
@@ -89,31 +120,24 @@ function LLMRow({
 export default function LocalLLMs() {
   const [detection, setDetection] = useState<LocalLLMDetection | null>(null)
   const [systemInfo, setSystemInfo] = useState<SystemInfo | null>(null)
-  const [ollamaModels, setOllamaModels] = useState<string[]>([])
-  const [lmStudioModels, setLmStudioModels] = useState<string[]>([])
+  const [ollamaModels, setOllamaModels] = useState<ModelChoice[]>([])
+  const [lmStudioModels, setLmStudioModels] = useState<ModelChoice[]>([])
   const [llmfitSystem, setLlmfitSystem] = useState<LlmfitSystemJson | null>(null)
   const [llmfitRecs, setLlmfitRecs] = useState<LlmfitRecommendation[]>([])
   const [loading, setLoading] = useState(true)
   const [error, setError] = useState<string | null>(null)
 
-  const refresh = async () => {
+  const refresh = async (force = false) => {
     setLoading(true)
     setError(null)
     try {
-      const [det, sys, ollamaList, lmList, llmfitSys, llmfitRecList] = await Promise.all([
-        invoke<LocalLLMDetection>('detect_local_llms'),
-        invoke<SystemInfo>('get_system_info'),
-        invoke<string[]>('get_ollama_models').catch(() => []),
-        invoke<string[]>('get_lm_studio_models').catch(() => []),
-        invoke<LlmfitSystemJson | null>('get_llmfit_system').catch(() => null),
-        invoke<LlmfitRecommendation[]>('get_llmfit_recommendations', { limit: 10 }).catch(() => []),
-      ])
-      setDetection(det)
-      setSystemInfo(sys)
-      setOllamaModels(ollamaList)
-      setLmStudioModels(lmList)
-      setLlmfitSystem(llmfitSys)
-      setLlmfitRecs(llmfitRecList)
+      const state = await invoke<DashboardState>('get_dashboard_state', { recommendationLimit: 10, refresh: force })
+      setDetection(state.detection)
+      setSystemInfo(state.system_info)
+      setOllamaModels(state.ollama_models)
+      setLmStudioModels(state.lm_studio_models)
+      setLlmfitSystem(state.llmfit_system)
+      setLlmfitRecs(state.llmfit_recommendations)
     } catch (e) {
       setError(e instanceof Error ? e.message : String(e))
     } finally {
@@ -139,7 +163,7 @@ export default function LocalLLMs() {
       <div className="status-card">
         <h2>Local LLMs</h2>
         <p className="status-item error">{error}</p>
-        <button className="btn" onClick={refresh}>Retry</button>
+        <button className="btn" onClick={() => refresh(true)}>Retry</button>
       </div>
     )
   }
@@ -148,7 +172,7 @@ export default function LocalLLMs() {
     <div className="status-card">
       <div style={{ display: 'flex', alignItems: 'center', justifyContent: 'space-between', marginBottom: '1rem' }}>
         <h2>Local LLMs</h2>
-        <button className="btn" onClick={refresh} disabled={loading}>
+        <button className="btn" onClick={() => refresh(true)} disabled={loading}>
           {loading ? 'Refreshing…' : 'Refresh'}
         </button>
       </div>
@@ -160,7 +184,36 @@ export default function LocalLLMs() {
             Total: <strong>{systemInfo.total_memory_human}</strong>
             {' · '}
             Available: <strong>{systemInfo.available_memory_human}</strong>
+            {systemInfo.swap_total_bytes != null && systemInfo.swap_total_bytes > 0 &&
+              ` · Swap: ${((systemInfo.swap_used_bytes ?? 0) / 1024 ** 3).toFixed(1)} / ${(systemInfo.swap_total_bytes / 1024 ** 3).toFixed(1)} GB`}
+            {systemInfo.memory_pressure && (
+              <>
+                {' · '}
+                Pressure:{' '}
+                <strong style={{ color: { green: '#16a34a', yellow: '#ca8a04', red: '#dc2626' }[systemInfo.memory_pressure] }}>
+                  {systemInfo.memory_pressure}
+                </strong>
+              </>
+            )}
           </div>
+          {systemInfo.cpu && (
+            <div style={{ fontSize: '0.9rem', color: '#64748b' }}>
+              CPU: <strong>{systemInfo.cpu.brand}</strong>
+              {' · '}
+              {systemInfo.cpu.physical_cores ?? '?'} cores / {systemInfo.cpu.logical_cores} threads
+              {systemInfo.cpu.frequency_mhz != null && ` · ${(systemInfo.cpu.frequency_mhz / 1000).toFixed(2)} GHz`}
+              {systemInfo.cpu.features.length > 0 && ` · ${systemInfo.cpu.features.join(', ')}`}
+            </div>
+          )}
+          {systemInfo.gpu && (
+            <div style={{ fontSize: '0.9rem', color: '#64748b' }}>
+              GPU: <strong>{systemInfo.gpu.name}</strong>
+              {' · '}
+              {(systemInfo.gpu.vram_bytes / 1024 ** 3).toFixed(1)} GB {systemInfo.gpu.backend === 'metal' ? 'unified' : 'VRAM'}
+              {systemInfo.gpu.vram_used_bytes != null && ` (${(systemInfo.gpu.vram_used_bytes / 1024 ** 3).toFixed(1)} GB used)`}
+              {systemInfo.gpu.driver_version && ` · driver ${systemInfo.gpu.driver_version}`}
+            </div>
+          )}
         </div>
       )}
 
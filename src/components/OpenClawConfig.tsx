@@ -16,6 +16,8 @@ interface OpenClawConfigView {
   subagents: SubagentsView
 }
 
+const CLOUD_PROVIDERS = ['anthropic', 'openai']
+
 const DEFAULT_SUBAGENTS = { max_concurrent: 8, max_spawn_depth: 1, max_children_per_agent: 5 }
 
 export default function OpenClawConfig() {
@@ -28,6 +30,7 @@ export default function OpenClawConfig() {
   const [subMaxConcurrent, setSubMaxConcurrent] = useState<number | ''>('')
   const [subMaxSpawnDepth, setSubMaxSpawnDepth] = useState<number | ''>('')
   const [subMaxChildren, setSubMaxChildren] = useState<number | ''>('')
+  const [cloudModels, setCloudModels] = useState<string[]>([])
 
   const load = async () => {
     setLoading(true)
@@ -40,6 +43,7 @@ export default function OpenClawConfig() {
       setSubMaxConcurrent(c.subagents?.max_concurrent ?? '')
       setSubMaxSpawnDepth(c.subagents?.max_spawn_depth ?? '')
       setSubMaxChildren(c.subagents?.max_children_per_agent ?? '')
+      loadCloudModels()
     } catch (e) {
       setError(e instanceof Error ? e.message : String(e))
     } finally {
@@ -47,6 +51,18 @@ export default function OpenClawConfig() {
     }
   }
 
+  // Live ids from providers with a stored key; providers without one are skipped. Pinned first.
+  const loadCloudModels = async () => {
+    type Choice = { name: string; pinned: boolean }
+    const lists = await Promise.all(
+      CLOUD_PROVIDERS.map((p) =>
+        invoke<Choice[]>('get_cloud_provider_models', { provider: p }).catch(() => [] as Choice[])
+      )
+    )
+    const all = lists.flat()
+    setCloudModels([...all.filter((m) => m.pinned), ...all.filter((m) => !m.pinned)].map((m) => m.name))
+  }
+
   useEffect(() => {
     load()
   }, [])
@@ -92,7 +108,7 @@ export default function OpenClawConfig() {
     )
   }
 
-  const allModelOptions = [...new Set([...(config?.models ?? []), config?.primary_model].filter(Boolean))] as string[]
+  const allModelOptions = [...new Set([...(config?.models ?? []), ...cloudModels, config?.primary_model].filter(Boolean))] as string[]
   const hasEdits =
     primary !== (config?.primary_model ?? '') ||
     maxConcurrent !== (config?.max_concurrent ?? '') ||